@@ -14,6 +14,73 @@ mod m20260209_000007_create_tag_table;
 mod m20260209_000008_create_game_tag_table;
 mod m20260209_000009_seed_tags;
 mod m20260210_000001_update_game_version_table;
+mod m20261017_000001_add_game_search_index;
+mod m20261017_000002_add_game_trending_score;
+mod m20261017_000003_create_review_table;
+mod m20261017_000004_create_favorite_table;
+mod m20261017_000005_create_collection_tables;
+mod m20261017_000006_add_session_playing_started_at;
+mod m20261017_000007_add_asset_storage;
+mod m20261017_000008_add_game_asset_content_hash;
+mod m20261017_000009_add_game_version_scan_findings;
+mod m20261017_000010_create_game_template_table;
+mod m20261017_000011_create_game_collaborator_table;
+mod m20261017_000012_add_game_edit_lock;
+mod m20261017_000013_create_scheduled_publish_table;
+mod m20261017_000014_add_tag_retired_at;
+mod m20261017_000015_add_game_manifest;
+mod m20261017_000016_create_game_stats_tables;
+mod m20261017_000017_create_content_report_table;
+mod m20261017_000018_create_user_totp_table;
+mod m20261017_000019_create_webauthn_tables;
+mod m20261017_000020_add_refresh_token_device;
+mod m20261017_000021_create_api_token;
+mod m20261017_000022_create_oauth_server_tables;
+mod m20261017_000023_add_user_deletion_schedule;
+mod m20261017_000024_create_data_export;
+mod m20261017_000025_create_email_change;
+mod m20261017_000026_create_known_device_and_notification;
+mod m20261017_000027_create_login_event;
+mod m20261017_000028_create_impersonation_log;
+mod m20261017_000029_clear_plaintext_verification_tokens;
+mod m20261017_000030_create_follow;
+mod m20261017_000031_add_game_featured_at;
+mod m20261017_000032_create_notification_preference;
+mod m20261017_000033_create_friendship;
+mod m20261017_000034_create_session_invite;
+mod m20261017_000035_add_game_owner_index;
+mod m20261017_000036_add_user_profile_fields;
+mod m20261017_000037_create_achievement_tables;
+mod m20261017_000038_create_xp_event;
+mod m20261017_000039_create_verification_request;
+mod m20261017_000040_add_user_privacy_flags;
+mod m20261017_000041_create_user_preference;
+mod m20261017_000042_create_subscription;
+mod m20261017_000043_create_organization_tables;
+mod m20261017_000044_create_classroom_tables;
+mod m20261017_000045_add_content_report_moderation;
+mod m20261017_000046_create_game_takedown;
+mod m20261017_000047_create_audit_log;
+mod m20261017_000048_create_announcement;
+mod m20261017_000049_create_copyright_notice;
+mod m20261017_000050_add_user_shadow_banned;
+mod m20261017_000051_create_user_note;
+mod m20261017_000052_create_suspension_appeal;
+mod m20261017_000053_create_analytics_event;
+mod m20261017_000054_add_game_daily_stats_audience;
+mod m20261017_000055_create_platform_daily_stats;
+mod m20261017_000056_add_player_first_input_at;
+mod m20261017_000057_add_session_telemetry;
+mod m20261017_000058_add_country;
+mod m20261017_000059_create_experiment;
+mod m20261017_000060_create_health_check;
+mod m20261017_000061_create_status_incident;
+mod m20261017_000062_clear_plaintext_email_change_tokens;
+mod m20261017_000063_add_game_search_vector;
+mod m20261017_000064_add_oauth_code_redirect_uri_supplied;
+mod m20261017_000065_add_user_totp_failed_attempts;
+mod m20261017_000066_add_known_device_revoke_token_expires_at;
+mod m20261017_000067_create_stripe_event;
 
 pub struct Migrator;
 
@@ -35,6 +102,73 @@ impl MigratorTrait for Migrator {
             Box::new(m20260209_000008_create_game_tag_table::Migration),
             Box::new(m20260209_000009_seed_tags::Migration),
             Box::new(m20260210_000001_update_game_version_table::Migration),
+            Box::new(m20261017_000001_add_game_search_index::Migration),
+            Box::new(m20261017_000002_add_game_trending_score::Migration),
+            Box::new(m20261017_000003_create_review_table::Migration),
+            Box::new(m20261017_000004_create_favorite_table::Migration),
+            Box::new(m20261017_000005_create_collection_tables::Migration),
+            Box::new(m20261017_000006_add_session_playing_started_at::Migration),
+            Box::new(m20261017_000007_add_asset_storage::Migration),
+            Box::new(m20261017_000008_add_game_asset_content_hash::Migration),
+            Box::new(m20261017_000009_add_game_version_scan_findings::Migration),
+            Box::new(m20261017_000010_create_game_template_table::Migration),
+            Box::new(m20261017_000011_create_game_collaborator_table::Migration),
+            Box::new(m20261017_000012_add_game_edit_lock::Migration),
+            Box::new(m20261017_000013_create_scheduled_publish_table::Migration),
+            Box::new(m20261017_000014_add_tag_retired_at::Migration),
+            Box::new(m20261017_000015_add_game_manifest::Migration),
+            Box::new(m20261017_000016_create_game_stats_tables::Migration),
+            Box::new(m20261017_000017_create_content_report_table::Migration),
+            Box::new(m20261017_000018_create_user_totp_table::Migration),
+            Box::new(m20261017_000019_create_webauthn_tables::Migration),
+            Box::new(m20261017_000020_add_refresh_token_device::Migration),
+            Box::new(m20261017_000021_create_api_token::Migration),
+            Box::new(m20261017_000022_create_oauth_server_tables::Migration),
+            Box::new(m20261017_000023_add_user_deletion_schedule::Migration),
+            Box::new(m20261017_000024_create_data_export::Migration),
+            Box::new(m20261017_000025_create_email_change::Migration),
+            Box::new(m20261017_000026_create_known_device_and_notification::Migration),
+            Box::new(m20261017_000027_create_login_event::Migration),
+            Box::new(m20261017_000028_create_impersonation_log::Migration),
+            Box::new(m20261017_000029_clear_plaintext_verification_tokens::Migration),
+            Box::new(m20261017_000030_create_follow::Migration),
+            Box::new(m20261017_000031_add_game_featured_at::Migration),
+            Box::new(m20261017_000032_create_notification_preference::Migration),
+            Box::new(m20261017_000033_create_friendship::Migration),
+            Box::new(m20261017_000034_create_session_invite::Migration),
+            Box::new(m20261017_000035_add_game_owner_index::Migration),
+            Box::new(m20261017_000036_add_user_profile_fields::Migration),
+            Box::new(m20261017_000037_create_achievement_tables::Migration),
+            Box::new(m20261017_000038_create_xp_event::Migration),
+            Box::new(m20261017_000039_create_verification_request::Migration),
+            Box::new(m20261017_000040_add_user_privacy_flags::Migration),
+            Box::new(m20261017_000041_create_user_preference::Migration),
+            Box::new(m20261017_000042_create_subscription::Migration),
+            Box::new(m20261017_000043_create_organization_tables::Migration),
+            Box::new(m20261017_000044_create_classroom_tables::Migration),
+            Box::new(m20261017_000045_add_content_report_moderation::Migration),
+            Box::new(m20261017_000046_create_game_takedown::Migration),
+            Box::new(m20261017_000047_create_audit_log::Migration),
+            Box::new(m20261017_000048_create_announcement::Migration),
+            Box::new(m20261017_000049_create_copyright_notice::Migration),
+            Box::new(m20261017_000050_add_user_shadow_banned::Migration),
+            Box::new(m20261017_000051_create_user_note::Migration),
+            Box::new(m20261017_000052_create_suspension_appeal::Migration),
+            Box::new(m20261017_000053_create_analytics_event::Migration),
+            Box::new(m20261017_000054_add_game_daily_stats_audience::Migration),
+            Box::new(m20261017_000055_create_platform_daily_stats::Migration),
+            Box::new(m20261017_000056_add_player_first_input_at::Migration),
+            Box::new(m20261017_000057_add_session_telemetry::Migration),
+            Box::new(m20261017_000058_add_country::Migration),
+            Box::new(m20261017_000059_create_experiment::Migration),
+            Box::new(m20261017_000060_create_health_check::Migration),
+            Box::new(m20261017_000061_create_status_incident::Migration),
+            Box::new(m20261017_000062_clear_plaintext_email_change_tokens::Migration),
+            Box::new(m20261017_000063_add_game_search_vector::Migration),
+            Box::new(m20261017_000064_add_oauth_code_redirect_uri_supplied::Migration),
+            Box::new(m20261017_000065_add_user_totp_failed_attempts::Migration),
+            Box::new(m20261017_000066_add_known_device_revoke_token_expires_at::Migration),
+            Box::new(m20261017_000067_create_stripe_event::Migration),
         ]
     }
 }
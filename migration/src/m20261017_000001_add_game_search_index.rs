@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds a GIN full-text index over game titles and descriptions (`PostgreSQL` only).
+///
+/// The expression must match the one used by the search query for the planner to pick it up.
+/// `SQLite` has no `tsvector` support, so the search endpoint falls back to `LIKE` matching there
+/// and this migration is a no-op.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != sea_orm::DatabaseBackend::Postgres {
+            return Ok(());
+        }
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_game_search ON game USING GIN \
+                 (to_tsvector('english', coalesce(title, '') || ' ' || coalesce(description, '')))",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != sea_orm::DatabaseBackend::Postgres {
+            return Ok(());
+        }
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP INDEX IF EXISTS idx_game_search")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds the `trending_score` column to `game`, maintained by the trending background job.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Game::Table)
+                    .add_column(
+                        ColumnDef::new(Game::TrendingScore)
+                            .double()
+                            .not_null()
+                            .default(0.0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_game_trending_score")
+                    .table(Game::Table)
+                    .col(Game::TrendingScore)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_game_trending_score")
+                    .table(Game::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Game::Table)
+                    .drop_column(Game::TrendingScore)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Game {
+    Table,
+    TrendingScore,
+}
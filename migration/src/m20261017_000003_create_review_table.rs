@@ -0,0 +1,96 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates the `review` table, including the game creator's single public reply.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Review::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Review::Id).uuid().not_null().primary_key())
+                    .col(
+                        ColumnDef::new(Review::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Review::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(Review::DeletedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(Review::GameId).uuid().not_null())
+                    .col(ColumnDef::new(Review::UserId).uuid().not_null())
+                    .col(ColumnDef::new(Review::Rating).integer().not_null())
+                    .col(ColumnDef::new(Review::Body).text())
+                    .col(ColumnDef::new(Review::CreatorReply).text())
+                    .col(ColumnDef::new(Review::CreatorRepliedAt).timestamp_with_time_zone())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_review_game_id")
+                            .from(Review::Table, Review::GameId)
+                            .to(Game::Table, Game::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_review_user_id")
+                            .from(Review::Table, Review::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_review_game_id")
+                    .table(Review::Table)
+                    .col(Review::GameId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Review::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Review {
+    Table,
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    DeletedAt,
+    GameId,
+    UserId,
+    Rating,
+    Body,
+    CreatorReply,
+    CreatorRepliedAt,
+}
+
+#[derive(DeriveIden)]
+enum Game {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
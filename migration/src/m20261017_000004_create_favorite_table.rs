@@ -0,0 +1,79 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates the `favorite` join table between users and the games they bookmarked.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Favorite::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Favorite::UserId).uuid().not_null())
+                    .col(ColumnDef::new(Favorite::GameId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(Favorite::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(Index::create().col(Favorite::UserId).col(Favorite::GameId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_favorite_user_id")
+                            .from(Favorite::Table, Favorite::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_favorite_game_id")
+                            .from(Favorite::Table, Favorite::GameId)
+                            .to(Game::Table, Game::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Add index on game_id for reverse lookup
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_favorite_game_id")
+                    .table(Favorite::Table)
+                    .col(Favorite::GameId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Favorite::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Favorite {
+    Table,
+    UserId,
+    GameId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Game {
+    Table,
+    Id,
+}
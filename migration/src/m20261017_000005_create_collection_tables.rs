@@ -0,0 +1,145 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates user-curated game collections and their `collection_game` membership table.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Collection::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Collection::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Collection::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Collection::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(Collection::OwnerId).uuid().not_null())
+                    .col(ColumnDef::new(Collection::Title).string().not_null())
+                    .col(ColumnDef::new(Collection::Description).text())
+                    .col(
+                        ColumnDef::new(Collection::Visibility)
+                            .string()
+                            .not_null()
+                            .default("private"),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_collection_owner_id")
+                            .from(Collection::Table, Collection::OwnerId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_collection_owner_id")
+                    .table(Collection::Table)
+                    .col(Collection::OwnerId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(CollectionGame::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CollectionGame::CollectionId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(CollectionGame::GameId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(CollectionGame::AddedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(CollectionGame::CollectionId)
+                            .col(CollectionGame::GameId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_collection_game_collection_id")
+                            .from(CollectionGame::Table, CollectionGame::CollectionId)
+                            .to(Collection::Table, Collection::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_collection_game_game_id")
+                            .from(CollectionGame::Table, CollectionGame::GameId)
+                            .to(Game::Table, Game::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CollectionGame::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Collection::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Collection {
+    Table,
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    OwnerId,
+    Title,
+    Description,
+    Visibility,
+}
+
+#[derive(DeriveIden)]
+enum CollectionGame {
+    Table,
+    CollectionId,
+    GameId,
+    AddedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Game {
+    Table,
+    Id,
+}
@@ -0,0 +1,42 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds `playing_started_at` to `session`, marking when the currently loaded game started.
+///
+/// Used to accrue `game.total_play_time` when the game is swapped out or the session ends.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Session::Table)
+                    .add_column(
+                        ColumnDef::new(Session::PlayingStartedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Session::Table)
+                    .drop_column(Session::PlayingStartedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Session {
+    Table,
+    PlayingStartedAt,
+}
@@ -0,0 +1,105 @@
+use sea_orm_migration::prelude::*;
+
+/// Moves asset bytes behind a pluggable storage backend.
+///
+/// Adds `storage_backend` / `storage_key` to `game_asset` (existing rows stay `inline`, i.e.
+/// read from `file_data`, until backfilled) and creates `storage_object`, the blob table used
+/// by the database backend.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only supports one column per ALTER TABLE
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameAsset::Table)
+                    .add_column(
+                        ColumnDef::new(GameAsset::StorageBackend)
+                            .string()
+                            .not_null()
+                            .default("inline"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameAsset::Table)
+                    .add_column(ColumnDef::new(GameAsset::StorageKey).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(StorageObject::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(StorageObject::Key)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(StorageObject::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(StorageObject::ContentType)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(StorageObject::Data).binary().not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(StorageObject::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameAsset::Table)
+                    .drop_column(GameAsset::StorageKey)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameAsset::Table)
+                    .drop_column(GameAsset::StorageBackend)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GameAsset {
+    Table,
+    StorageBackend,
+    StorageKey,
+}
+
+#[derive(DeriveIden)]
+enum StorageObject {
+    Table,
+    Key,
+    CreatedAt,
+    ContentType,
+    Data,
+}
@@ -0,0 +1,56 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds `content_hash` (hex SHA-256 of the bytes) to `game_asset` so identical uploads can
+/// share a stored blob.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameAsset::Table)
+                    .add_column(ColumnDef::new(GameAsset::ContentHash).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_game_asset_content_hash")
+                    .table(GameAsset::Table)
+                    .col(GameAsset::ContentHash)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_game_asset_content_hash")
+                    .table(GameAsset::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameAsset::Table)
+                    .drop_column(GameAsset::ContentHash)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GameAsset {
+    Table,
+    ContentHash,
+}
@@ -0,0 +1,37 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds `scan_findings` to `game_version`: the non-blocking safety scanner warnings
+/// recorded when the version was published.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameVersion::Table)
+                    .add_column(ColumnDef::new(GameVersion::ScanFindings).json().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameVersion::Table)
+                    .drop_column(GameVersion::ScanFindings)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GameVersion {
+    Table,
+    ScanFindings,
+}
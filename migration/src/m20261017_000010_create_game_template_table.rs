@@ -0,0 +1,262 @@
+use sea_orm_migration::prelude::*;
+use uuid::Uuid;
+
+/// Creates the `game_template` table and seeds the starter templates offered when creating a
+/// game.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// A single seeded template.
+struct Template {
+    id: &'static str,
+    slug: &'static str,
+    name: &'static str,
+    description: &'static str,
+    min_players: i32,
+    max_players: i32,
+    game_screen_code: &'static str,
+    controller_screen_code: &'static str,
+}
+
+const TEMPLATES: &[Template] = &[
+    Template {
+        id: "04000000-0000-4000-8000-000000000001",
+        slug: "pong",
+        name: "Pong",
+        description: "Two paddles and a ball. Each player moves a paddle from their phone.",
+        min_players: 2,
+        max_players: 2,
+        game_screen_code: r"let ball;
+let paddles = [200, 200];
+
+function setup() {
+  createCanvas(800, 400);
+  ball = { x: width / 2, y: height / 2, vx: 5, vy: 3 };
+}
+
+function draw() {
+  background(0);
+  fill(255);
+
+  ball.x += ball.vx;
+  ball.y += ball.vy;
+  if (ball.y < 0 || ball.y > height) ball.vy *= -1;
+  if (ball.x < 20 || ball.x > width - 20) ball.vx *= -1;
+
+  rect(10, paddles[0] - 40, 10, 80);
+  rect(width - 20, paddles[1] - 40, 10, 80);
+  circle(ball.x, ball.y, 16);
+}
+",
+        controller_screen_code: r"function setup() {
+  createCanvas(windowWidth, windowHeight);
+}
+
+function draw() {
+  background(30);
+  fill(255);
+  textAlign(CENTER, CENTER);
+  text('Drag up and down to move your paddle', width / 2, height / 2);
+}
+",
+    },
+    Template {
+        id: "04000000-0000-4000-8000-000000000002",
+        slug: "quiz",
+        name: "Quiz",
+        description: "Multiple-choice questions on the big screen, answer buttons on every phone.",
+        min_players: 1,
+        max_players: 8,
+        game_screen_code: r"const questions = [
+  { prompt: 'What is 2 + 2?', answers: ['3', '4', '5', '22'], correct: 1 },
+];
+let current = 0;
+
+function setup() {
+  createCanvas(800, 600);
+  textAlign(CENTER, CENTER);
+}
+
+function draw() {
+  background(20, 30, 60);
+  fill(255);
+  const q = questions[current];
+  textSize(32);
+  text(q.prompt, width / 2, 120);
+  textSize(24);
+  q.answers.forEach((answer, i) => text(answer, width / 2, 240 + i * 60));
+}
+",
+        controller_screen_code: r"const labels = ['A', 'B', 'C', 'D'];
+
+function setup() {
+  createCanvas(windowWidth, windowHeight);
+  textAlign(CENTER, CENTER);
+}
+
+function draw() {
+  background(30);
+  const h = height / labels.length;
+  labels.forEach((label, i) => {
+    fill(60 + i * 40, 80, 160);
+    rect(0, i * h, width, h - 4);
+    fill(255);
+    text(label, width / 2, i * h + h / 2);
+  });
+}
+",
+    },
+    Template {
+        id: "04000000-0000-4000-8000-000000000003",
+        slug: "drawing",
+        name: "Drawing Game",
+        description: "Players sketch on their phones and the drawings appear on the shared canvas.",
+        min_players: 2,
+        max_players: 8,
+        game_screen_code: r"let strokes = [];
+
+function setup() {
+  createCanvas(800, 600);
+}
+
+function draw() {
+  background(250);
+  stroke(0);
+  strokeWeight(4);
+  for (const s of strokes) {
+    line(s.x1, s.y1, s.x2, s.y2);
+  }
+}
+",
+        controller_screen_code: r"function setup() {
+  createCanvas(windowWidth, windowHeight);
+  background(255);
+}
+
+function draw() {
+  stroke(0);
+  strokeWeight(4);
+  if (mouseIsPressed) {
+    line(pmouseX, pmouseY, mouseX, mouseY);
+  }
+}
+",
+    },
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GameTemplate::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GameTemplate::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(GameTemplate::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(GameTemplate::Slug)
+                            .string_len(50)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(GameTemplate::Name)
+                            .string_len(100)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(GameTemplate::Description).text().not_null())
+                    .col(
+                        ColumnDef::new(GameTemplate::Technology)
+                            .string_len(20)
+                            .not_null()
+                            .default("p5js"),
+                    )
+                    .col(
+                        ColumnDef::new(GameTemplate::MinPlayers)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GameTemplate::MaxPlayers)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GameTemplate::GameScreenCode)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GameTemplate::ControllerScreenCode)
+                            .text()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        for template in TEMPLATES {
+            let id = Uuid::parse_str(template.id).map_err(|e| DbErr::Custom(e.to_string()))?;
+            let insert = Query::insert()
+                .into_table(GameTemplate::Table)
+                .columns([
+                    GameTemplate::Id,
+                    GameTemplate::Slug,
+                    GameTemplate::Name,
+                    GameTemplate::Description,
+                    GameTemplate::MinPlayers,
+                    GameTemplate::MaxPlayers,
+                    GameTemplate::GameScreenCode,
+                    GameTemplate::ControllerScreenCode,
+                ])
+                .values([
+                    id.into(),
+                    template.slug.into(),
+                    template.name.into(),
+                    template.description.into(),
+                    template.min_players.into(),
+                    template.max_players.into(),
+                    template.game_screen_code.into(),
+                    template.controller_screen_code.into(),
+                ])
+                .map_err(|e| DbErr::Custom(e.to_string()))?
+                .on_conflict(OnConflict::column(GameTemplate::Id).do_nothing().to_owned())
+                .to_owned();
+            manager.exec_stmt(insert).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GameTemplate::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GameTemplate {
+    Table,
+    Id,
+    CreatedAt,
+    Slug,
+    Name,
+    Description,
+    Technology,
+    MinPlayers,
+    MaxPlayers,
+    GameScreenCode,
+    ControllerScreenCode,
+}
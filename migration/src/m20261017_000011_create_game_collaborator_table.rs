@@ -0,0 +1,95 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates the `game_collaborator` table: users other than the owner who may view or edit a game.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GameCollaborator::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(GameCollaborator::GameId).uuid().not_null())
+                    .col(ColumnDef::new(GameCollaborator::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(GameCollaborator::Role)
+                            .string_len(20)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GameCollaborator::InvitedBy)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GameCollaborator::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(GameCollaborator::GameId)
+                            .col(GameCollaborator::UserId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_game_collaborator_game_id")
+                            .from(GameCollaborator::Table, GameCollaborator::GameId)
+                            .to(Game::Table, Game::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_game_collaborator_user_id")
+                            .from(GameCollaborator::Table, GameCollaborator::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Add index on user_id for "games shared with me" lookups
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_game_collaborator_user_id")
+                    .table(GameCollaborator::Table)
+                    .col(GameCollaborator::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GameCollaborator::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GameCollaborator {
+    Table,
+    GameId,
+    UserId,
+    Role,
+    InvitedBy,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Game {
+    Table,
+    Id,
+}
@@ -0,0 +1,62 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds `locked_by` / `lock_expires_at` to `game`: the collaborator currently editing it.
+///
+/// A lock past its expiry is treated as released.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only supports one column per ALTER TABLE
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Game::Table)
+                    .add_column(ColumnDef::new(Game::LockedBy).uuid().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Game::Table)
+                    .add_column(
+                        ColumnDef::new(Game::LockExpiresAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Game::Table)
+                    .drop_column(Game::LockExpiresAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Game::Table)
+                    .drop_column(Game::LockedBy)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Game {
+    Table,
+    LockedBy,
+    LockExpiresAt,
+}
@@ -0,0 +1,96 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates the `scheduled_publish` table: at most one pending publish per game, carried out by
+/// the background scheduler at `publish_at`.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ScheduledPublish::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ScheduledPublish::GameId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ScheduledPublish::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(ScheduledPublish::PublishAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ScheduledPublish::RequestedBy)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ScheduledPublish::Changelog).text().null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_scheduled_publish_game_id")
+                            .from(ScheduledPublish::Table, ScheduledPublish::GameId)
+                            .to(Game::Table, Game::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_scheduled_publish_requested_by")
+                            .from(ScheduledPublish::Table, ScheduledPublish::RequestedBy)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // The scheduler polls for due rows
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_scheduled_publish_publish_at")
+                    .table(ScheduledPublish::Table)
+                    .col(ScheduledPublish::PublishAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ScheduledPublish::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ScheduledPublish {
+    Table,
+    GameId,
+    CreatedAt,
+    PublishAt,
+    RequestedBy,
+    Changelog,
+}
+
+#[derive(DeriveIden)]
+enum Game {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
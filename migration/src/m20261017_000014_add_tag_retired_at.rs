@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds `retired_at` to `tag`. Retired tags stay on the games that already use them but are
+/// hidden from the catalog and can no longer be assigned.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tag::Table)
+                    .add_column(
+                        ColumnDef::new(Tag::RetiredAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tag::Table)
+                    .drop_column(Tag::RetiredAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Tag {
+    Table,
+    RetiredAt,
+}
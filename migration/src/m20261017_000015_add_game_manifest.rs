@@ -0,0 +1,62 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds a `manifest` JSON column to `game` (the editable draft) and `game_version` (the copy
+/// frozen at publish time). The manifest declares the controls, orientation and player counts a
+/// generic controller UI needs.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Game::Table)
+                    .add_column(ColumnDef::new(Game::Manifest).json().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameVersion::Table)
+                    .add_column(ColumnDef::new(GameVersion::Manifest).json().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameVersion::Table)
+                    .drop_column(GameVersion::Manifest)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Game::Table)
+                    .drop_column(Game::Manifest)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Game {
+    Table,
+    Manifest,
+}
+
+#[derive(DeriveIden)]
+enum GameVersion {
+    Table,
+    Manifest,
+}
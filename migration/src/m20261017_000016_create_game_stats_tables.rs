@@ -0,0 +1,180 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `game_play` (one row per play of a game in a session) and `game_daily_stats` (plays
+/// rolled up per game and day), which back the creator analytics endpoint.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    #[allow(clippy::too_many_lines)]
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GamePlay::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(GamePlay::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(GamePlay::GameId).uuid().not_null())
+                    .col(ColumnDef::new(GamePlay::SessionId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(GamePlay::StartedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GamePlay::EndedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(GamePlay::PlaySeconds)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(GamePlay::PlayerCount)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_game_play_game_id")
+                            .from(GamePlay::Table, GamePlay::GameId)
+                            .to(Game::Table, Game::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_game_play_session_id")
+                            .from(GamePlay::Table, GamePlay::SessionId)
+                            .to(Session::Table, Session::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_game_play_game_id_started_at")
+                    .table(GamePlay::Table)
+                    .col(GamePlay::GameId)
+                    .col(GamePlay::StartedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Segment bookkeeping looks up the open play of a session
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_game_play_session_id")
+                    .table(GamePlay::Table)
+                    .col(GamePlay::SessionId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(GameDailyStats::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(GameDailyStats::GameId).uuid().not_null())
+                    .col(ColumnDef::new(GameDailyStats::Day).date().not_null())
+                    .col(
+                        ColumnDef::new(GameDailyStats::Plays)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(GameDailyStats::Sessions)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(GameDailyStats::PlaySeconds)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(GameDailyStats::Players)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(GameDailyStats::PeakPlayers)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(GameDailyStats::GameId)
+                            .col(GameDailyStats::Day),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_game_daily_stats_game_id")
+                            .from(GameDailyStats::Table, GameDailyStats::GameId)
+                            .to(Game::Table, Game::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GameDailyStats::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(GamePlay::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GamePlay {
+    Table,
+    Id,
+    GameId,
+    SessionId,
+    StartedAt,
+    EndedAt,
+    PlaySeconds,
+    PlayerCount,
+}
+
+#[derive(DeriveIden)]
+enum GameDailyStats {
+    Table,
+    GameId,
+    Day,
+    Plays,
+    Sessions,
+    PlaySeconds,
+    Players,
+    PeakPlayers,
+}
+
+#[derive(DeriveIden)]
+enum Game {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Session {
+    Table,
+    Id,
+}
@@ -0,0 +1,104 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates the `content_report` table: abuse reports against games, reviews or users, waiting
+/// in the moderation queue.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ContentReport::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ContentReport::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReport::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(ContentReport::ReporterId).uuid().null())
+                    .col(ColumnDef::new(ContentReport::ReporterIp).string().null())
+                    .col(
+                        ColumnDef::new(ContentReport::TargetType)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ContentReport::TargetId).uuid().not_null())
+                    .col(ColumnDef::new(ContentReport::Reason).string().not_null())
+                    .col(ColumnDef::new(ContentReport::Details).text().null())
+                    .col(
+                        ColumnDef::new(ContentReport::Status)
+                            .string()
+                            .not_null()
+                            .default("open"),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_content_report_reporter_id")
+                            .from(ContentReport::Table, ContentReport::ReporterId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // The moderation queue lists reports by status, oldest first
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_content_report_status_created_at")
+                    .table(ContentReport::Table)
+                    .col(ContentReport::Status)
+                    .col(ContentReport::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_content_report_target")
+                    .table(ContentReport::Table)
+                    .col(ContentReport::TargetType)
+                    .col(ContentReport::TargetId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ContentReport::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ContentReport {
+    Table,
+    Id,
+    CreatedAt,
+    ReporterId,
+    ReporterIp,
+    TargetType,
+    TargetId,
+    Reason,
+    Details,
+    Status,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,68 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates the `user_totp` table: a user's TOTP secret for two-factor sign-in, enabled once
+/// the first code is confirmed.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserTotp::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserTotp::UserId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(UserTotp::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(UserTotp::Secret).string().not_null())
+                    .col(
+                        ColumnDef::new(UserTotp::ConfirmedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(ColumnDef::new(UserTotp::LastUsedStep).big_integer().null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_totp_user_id")
+                            .from(UserTotp::Table, UserTotp::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserTotp::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserTotp {
+    Table,
+    UserId,
+    CreatedAt,
+    Secret,
+    ConfirmedAt,
+    LastUsedStep,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
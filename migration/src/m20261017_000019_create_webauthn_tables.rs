@@ -0,0 +1,157 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `webauthn_credential` (passkeys registered to a user) and `webauthn_challenge`
+/// (single-use challenges for registration and sign-in ceremonies in progress).
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    #[allow(clippy::too_many_lines)]
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(WebauthnCredential::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WebauthnCredential::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(WebauthnCredential::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(WebauthnCredential::CredentialId)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WebauthnCredential::PublicKey)
+                            .binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebauthnCredential::SignCount)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(WebauthnCredential::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(WebauthnCredential::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(WebauthnCredential::LastUsedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_webauthn_credential_user_id")
+                            .from(WebauthnCredential::Table, WebauthnCredential::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_webauthn_credential_user_id")
+                    .table(WebauthnCredential::Table)
+                    .col(WebauthnCredential::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(WebauthnChallenge::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WebauthnChallenge::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(WebauthnChallenge::UserId).uuid().null())
+                    .col(
+                        ColumnDef::new(WebauthnChallenge::Ceremony)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebauthnChallenge::Challenge)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebauthnChallenge::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(WebauthnChallenge::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_webauthn_challenge_user_id")
+                            .from(WebauthnChallenge::Table, WebauthnChallenge::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(WebauthnChallenge::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(WebauthnCredential::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum WebauthnCredential {
+    Table,
+    Id,
+    UserId,
+    CredentialId,
+    PublicKey,
+    SignCount,
+    Name,
+    CreatedAt,
+    LastUsedAt,
+}
+
+#[derive(DeriveIden)]
+enum WebauthnChallenge {
+    Table,
+    Id,
+    UserId,
+    Ceremony,
+    Challenge,
+    CreatedAt,
+    ExpiresAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
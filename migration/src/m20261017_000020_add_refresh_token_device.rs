@@ -0,0 +1,72 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds `user_agent` / `ip_address` / `signed_in_at` to `refresh_token`, so active tokens can
+/// be listed as signed-in devices.
+///
+/// `signed_in_at` is carried over when a token is rotated, so it marks when the device
+/// originally signed in; `created_at` is then the last refresh.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only supports one column per ALTER TABLE
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RefreshToken::Table)
+                    .add_column(ColumnDef::new(RefreshToken::UserAgent).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RefreshToken::Table)
+                    .add_column(ColumnDef::new(RefreshToken::IpAddress).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RefreshToken::Table)
+                    .add_column(
+                        ColumnDef::new(RefreshToken::SignedInAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [
+            RefreshToken::SignedInAt,
+            RefreshToken::IpAddress,
+            RefreshToken::UserAgent,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(RefreshToken::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum RefreshToken {
+    Table,
+    UserAgent,
+    IpAddress,
+    SignedInAt,
+}
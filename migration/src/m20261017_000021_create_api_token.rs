@@ -0,0 +1,92 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `api_token`: long-lived, scoped personal access tokens for scripts and CI.
+///
+/// Only a SHA-256 hash of each token is stored; `token_prefix` keeps its first characters so
+/// users can tell their tokens apart.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ApiToken::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ApiToken::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(ApiToken::UserId).uuid().not_null())
+                    .col(ColumnDef::new(ApiToken::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(ApiToken::TokenHash)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(ApiToken::TokenPrefix).string().not_null())
+                    .col(ColumnDef::new(ApiToken::Scopes).string().not_null())
+                    .col(
+                        ColumnDef::new(ApiToken::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ApiToken::LastUsedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ApiToken::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_api_token_user_id")
+                            .from(ApiToken::Table, ApiToken::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_api_token_user_id")
+                    .table(ApiToken::Table)
+                    .col(ApiToken::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ApiToken::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ApiToken {
+    Table,
+    Id,
+    UserId,
+    Name,
+    TokenHash,
+    TokenPrefix,
+    Scopes,
+    ExpiresAt,
+    LastUsedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,205 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `oauth_client` (third-party apps registered to act on behalf of users) and
+/// `oauth_authorization_code` (single-use codes from the authorization-code flow), and adds
+/// `api_token.oauth_client_id` to mark tokens issued to a client.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    #[allow(clippy::too_many_lines)]
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(OauthClient::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(OauthClient::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(OauthClient::OwnerId).uuid().not_null())
+                    .col(ColumnDef::new(OauthClient::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(OauthClient::ClientSecretHash)
+                            .string()
+                            .null(),
+                    )
+                    .col(ColumnDef::new(OauthClient::RedirectUris).text().not_null())
+                    .col(
+                        ColumnDef::new(OauthClient::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_oauth_client_owner_id")
+                            .from(OauthClient::Table, OauthClient::OwnerId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_oauth_client_owner_id")
+                    .table(OauthClient::Table)
+                    .col(OauthClient::OwnerId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(OauthAuthorizationCode::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(OauthAuthorizationCode::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(OauthAuthorizationCode::CodeHash)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(OauthAuthorizationCode::ClientId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(OauthAuthorizationCode::UserId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(OauthAuthorizationCode::RedirectUri)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(OauthAuthorizationCode::Scopes)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(OauthAuthorizationCode::CodeChallenge)
+                            .string()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(OauthAuthorizationCode::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(OauthAuthorizationCode::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_oauth_authorization_code_client_id")
+                            .from(
+                                OauthAuthorizationCode::Table,
+                                OauthAuthorizationCode::ClientId,
+                            )
+                            .to(OauthClient::Table, OauthClient::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_oauth_authorization_code_user_id")
+                            .from(
+                                OauthAuthorizationCode::Table,
+                                OauthAuthorizationCode::UserId,
+                            )
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // SQLite cannot add a foreign key to an existing table; deleting a client removes its
+        // tokens in the handler instead
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ApiToken::Table)
+                    .add_column(ColumnDef::new(ApiToken::OauthClientId).uuid().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ApiToken::Table)
+                    .drop_column(ApiToken::OauthClientId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(OauthAuthorizationCode::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(OauthClient::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum OauthClient {
+    Table,
+    Id,
+    OwnerId,
+    Name,
+    ClientSecretHash,
+    RedirectUris,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum OauthAuthorizationCode {
+    Table,
+    Id,
+    CodeHash,
+    ClientId,
+    UserId,
+    RedirectUri,
+    Scopes,
+    CodeChallenge,
+    ExpiresAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ApiToken {
+    Table,
+    OauthClientId,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
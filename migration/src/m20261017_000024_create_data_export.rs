@@ -0,0 +1,95 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `data_export`: archives of a user's personal data, assembled in the background and
+/// kept in blob storage until they expire.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DataExport::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DataExport::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(DataExport::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(DataExport::Status)
+                            .string()
+                            .not_null()
+                            .default("pending"),
+                    )
+                    .col(ColumnDef::new(DataExport::StorageBackend).string().null())
+                    .col(ColumnDef::new(DataExport::StorageKey).string().null())
+                    .col(ColumnDef::new(DataExport::SizeBytes).big_integer().null())
+                    .col(
+                        ColumnDef::new(DataExport::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(DataExport::CompletedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(DataExport::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_data_export_user_id")
+                            .from(DataExport::Table, DataExport::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_data_export_user_id")
+                    .table(DataExport::Table)
+                    .col(DataExport::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DataExport::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DataExport {
+    Table,
+    Id,
+    UserId,
+    Status,
+    StorageBackend,
+    StorageKey,
+    SizeBytes,
+    CreatedAt,
+    CompletedAt,
+    ExpiresAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,86 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `email_change`: email address changes waiting for the current address to confirm
+/// them before they are applied.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(EmailChange::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(EmailChange::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(EmailChange::UserId)
+                            .uuid()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(EmailChange::NewEmail).string().not_null())
+                    .col(
+                        ColumnDef::new(EmailChange::ConfirmationToken)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(EmailChange::VerificationToken)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(EmailChange::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(EmailChange::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_email_change_user_id")
+                            .from(EmailChange::Table, EmailChange::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(EmailChange::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum EmailChange {
+    Table,
+    Id,
+    UserId,
+    NewEmail,
+    ConfirmationToken,
+    VerificationToken,
+    ExpiresAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
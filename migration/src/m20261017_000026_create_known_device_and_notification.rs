@@ -0,0 +1,159 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `known_device`, the client fingerprints each user has signed in from, and
+/// `notification`, messages shown to a user in the app.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    #[allow(clippy::too_many_lines)]
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(KnownDevice::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(KnownDevice::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(KnownDevice::UserId).uuid().not_null())
+                    .col(ColumnDef::new(KnownDevice::Fingerprint).string().not_null())
+                    .col(ColumnDef::new(KnownDevice::UserAgent).string().null())
+                    .col(ColumnDef::new(KnownDevice::IpPrefix).string().null())
+                    .col(ColumnDef::new(KnownDevice::RefreshTokenId).uuid().null())
+                    .col(
+                        ColumnDef::new(KnownDevice::RevokeToken)
+                            .string()
+                            .null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(KnownDevice::FirstSeenAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(KnownDevice::LastSeenAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_known_device_user_id")
+                            .from(KnownDevice::Table, KnownDevice::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_known_device_user_fingerprint")
+                    .table(KnownDevice::Table)
+                    .col(KnownDevice::UserId)
+                    .col(KnownDevice::Fingerprint)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Notification::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Notification::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Notification::UserId).uuid().not_null())
+                    .col(ColumnDef::new(Notification::Kind).string().not_null())
+                    .col(ColumnDef::new(Notification::Title).string().not_null())
+                    .col(ColumnDef::new(Notification::Body).text().not_null())
+                    .col(ColumnDef::new(Notification::Link).string().null())
+                    .col(
+                        ColumnDef::new(Notification::ReadAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(Notification::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notification_user_id")
+                            .from(Notification::Table, Notification::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notification_user_id")
+                    .table(Notification::Table)
+                    .col(Notification::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Notification::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(KnownDevice::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum KnownDevice {
+    Table,
+    Id,
+    UserId,
+    Fingerprint,
+    UserAgent,
+    IpPrefix,
+    RefreshTokenId,
+    RevokeToken,
+    FirstSeenAt,
+    LastSeenAt,
+}
+
+#[derive(DeriveIden)]
+enum Notification {
+    Table,
+    Id,
+    UserId,
+    Kind,
+    Title,
+    Body,
+    Link,
+    ReadAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
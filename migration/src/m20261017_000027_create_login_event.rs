@@ -0,0 +1,90 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `login_event`: an audit log of sign-in attempts, successful or not.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(LoginEvent::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LoginEvent::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(LoginEvent::UserId).uuid().null())
+                    .col(ColumnDef::new(LoginEvent::Identifier).string().null())
+                    .col(ColumnDef::new(LoginEvent::Provider).string().not_null())
+                    .col(ColumnDef::new(LoginEvent::Outcome).string().not_null())
+                    .col(ColumnDef::new(LoginEvent::IpAddress).string().null())
+                    .col(ColumnDef::new(LoginEvent::UserAgent).string().null())
+                    .col(
+                        ColumnDef::new(LoginEvent::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_login_event_user_id")
+                            .from(LoginEvent::Table, LoginEvent::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_login_event_user_id_created_at")
+                    .table(LoginEvent::Table)
+                    .col(LoginEvent::UserId)
+                    .col(LoginEvent::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_login_event_created_at")
+                    .table(LoginEvent::Table)
+                    .col(LoginEvent::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LoginEvent::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum LoginEvent {
+    Table,
+    Id,
+    UserId,
+    Identifier,
+    Provider,
+    Outcome,
+    IpAddress,
+    UserAgent,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,104 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `impersonation_log`: every request an admin makes while impersonating a user.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ImpersonationLog::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ImpersonationLog::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ImpersonationLog::AdminId).uuid().not_null())
+                    .col(ColumnDef::new(ImpersonationLog::UserId).uuid().not_null())
+                    .col(ColumnDef::new(ImpersonationLog::Method).string().not_null())
+                    .col(ColumnDef::new(ImpersonationLog::Path).string().not_null())
+                    .col(
+                        ColumnDef::new(ImpersonationLog::Outcome)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ImpersonationLog::Reason).text().null())
+                    .col(ColumnDef::new(ImpersonationLog::IpAddress).string().null())
+                    .col(
+                        ColumnDef::new(ImpersonationLog::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_impersonation_log_admin_id")
+                            .from(ImpersonationLog::Table, ImpersonationLog::AdminId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_impersonation_log_user_id")
+                            .from(ImpersonationLog::Table, ImpersonationLog::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_impersonation_log_user_id_created_at")
+                    .table(ImpersonationLog::Table)
+                    .col(ImpersonationLog::UserId)
+                    .col(ImpersonationLog::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_impersonation_log_admin_id_created_at")
+                    .table(ImpersonationLog::Table)
+                    .col(ImpersonationLog::AdminId)
+                    .col(ImpersonationLog::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ImpersonationLog::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ImpersonationLog {
+    Table,
+    Id,
+    AdminId,
+    UserId,
+    Method,
+    Path,
+    Outcome,
+    Reason,
+    IpAddress,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
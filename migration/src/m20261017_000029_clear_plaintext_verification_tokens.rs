@@ -0,0 +1,28 @@
+use sea_orm_migration::prelude::*;
+
+/// Clears outstanding verification and reset tokens, which were stored in plaintext.
+///
+/// `auth_provider.verification_token` now holds a SHA-256 digest of the token, so the old values
+/// could no longer be redeemed anyway. Affected users request a new link.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE auth_provider SET verification_token = NULL, token_expires_at = NULL \
+                 WHERE verification_token IS NOT NULL",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        // Cleared tokens cannot be restored
+        Ok(())
+    }
+}
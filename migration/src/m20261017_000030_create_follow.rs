@@ -0,0 +1,77 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates the `follow` join table between users and the creators they follow.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Follow::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Follow::FollowerId).uuid().not_null())
+                    .col(ColumnDef::new(Follow::FolloweeId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(Follow::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(Follow::FollowerId)
+                            .col(Follow::FolloweeId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_follow_follower_id")
+                            .from(Follow::Table, Follow::FollowerId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_follow_followee_id")
+                            .from(Follow::Table, Follow::FolloweeId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Follower listings and counts look rows up by the followed user
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_follow_followee_id")
+                    .table(Follow::Table)
+                    .col(Follow::FolloweeId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Follow::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Follow {
+    Table,
+    FollowerId,
+    FolloweeId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,94 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `notification_preference`, the kinds of non-transactional email each user wants.
+///
+/// Users without a row get the defaults, so there is nothing to backfill.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(NotificationPreference::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(NotificationPreference::UserId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreference::ProductUpdates)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreference::ReviewAlerts)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreference::SecurityAlerts)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreference::SessionSummaries)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreference::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notification_preference_user_id")
+                            .from(
+                                NotificationPreference::Table,
+                                NotificationPreference::UserId,
+                            )
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(NotificationPreference::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum NotificationPreference {
+    Table,
+    UserId,
+    ProductUpdates,
+    ReviewAlerts,
+    SecurityAlerts,
+    SessionSummaries,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
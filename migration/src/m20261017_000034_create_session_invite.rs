@@ -0,0 +1,145 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `session_invite`, the users a host invited into a session, and adds the
+/// `session_invites` email preference.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SessionInvite::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SessionInvite::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SessionInvite::SessionId).uuid().not_null())
+                    .col(ColumnDef::new(SessionInvite::InviterId).uuid().not_null())
+                    .col(ColumnDef::new(SessionInvite::InviteeId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(SessionInvite::Status)
+                            .string()
+                            .not_null()
+                            .default("pending"),
+                    )
+                    .col(
+                        ColumnDef::new(SessionInvite::Emailed)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(SessionInvite::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(SessionInvite::RespondedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_session_invite_session_id")
+                            .from(SessionInvite::Table, SessionInvite::SessionId)
+                            .to(Session::Table, Session::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_session_invite_inviter_id")
+                            .from(SessionInvite::Table, SessionInvite::InviterId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_session_invite_invitee_id")
+                            .from(SessionInvite::Table, SessionInvite::InviteeId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_session_invite_session_invitee")
+                    .table(SessionInvite::Table)
+                    .col(SessionInvite::SessionId)
+                    .col(SessionInvite::InviteeId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(NotificationPreference::Table)
+                    .add_column(
+                        ColumnDef::new(NotificationPreference::SessionInvites)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(NotificationPreference::Table)
+                    .drop_column(NotificationPreference::SessionInvites)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(SessionInvite::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SessionInvite {
+    Table,
+    Id,
+    SessionId,
+    InviterId,
+    InviteeId,
+    Status,
+    Emailed,
+    CreatedAt,
+    RespondedAt,
+}
+
+#[derive(DeriveIden)]
+enum NotificationPreference {
+    Table,
+    SessionInvites,
+}
+
+#[derive(DeriveIden)]
+enum Session {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
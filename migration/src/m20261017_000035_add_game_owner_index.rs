@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+/// Indexes `game` by owner and status, so a creator's published games (and the counters
+/// cached on them) can be aggregated for their profile without scanning the table.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_game_owner_id_status")
+                    .table(Game::Table)
+                    .col(Game::OwnerId)
+                    .col(Game::Status)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_game_owner_id_status")
+                    .table(Game::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Game {
+    Table,
+    OwnerId,
+    Status,
+}
@@ -0,0 +1,57 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds the optional public profile fields `location`, `website_url`, `pronouns` and
+/// `social_links` (a JSON map of platform to URL) to `user`.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only supports one column per ALTER TABLE
+        for column in [
+            ColumnDef::new(User::Location).string().null().to_owned(),
+            ColumnDef::new(User::WebsiteUrl).string().null().to_owned(),
+            ColumnDef::new(User::Pronouns).string().null().to_owned(),
+            ColumnDef::new(User::SocialLinks).json().null().to_owned(),
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(User::Table)
+                        .add_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [
+            User::SocialLinks,
+            User::Pronouns,
+            User::WebsiteUrl,
+            User::Location,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(User::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Location,
+    WebsiteUrl,
+    Pronouns,
+    SocialLinks,
+}
@@ -0,0 +1,148 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `achievement`, the catalog of badges users can earn, seeded with a starter set,
+/// and `user_achievement`, the badges each user has earned.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// A seeded achievement: (id, name, description, sort order).
+const ACHIEVEMENTS: &[(&str, &str, &str, i32)] = &[
+    (
+        "first_publish",
+        "Published!",
+        "Published a game for the first time.",
+        1,
+    ),
+    (
+        "hundred_plays",
+        "Crowd Pleaser",
+        "Had a game played 100 times.",
+        2,
+    ),
+    (
+        "ten_sessions_hosted",
+        "Party Host",
+        "Hosted 10 game sessions.",
+        3,
+    ),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Achievement::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Achievement::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Achievement::Name).string().not_null())
+                    .col(ColumnDef::new(Achievement::Description).string().not_null())
+                    .col(
+                        ColumnDef::new(Achievement::SortOrder)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserAchievement::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(UserAchievement::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(UserAchievement::AchievementId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserAchievement::AwardedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(UserAchievement::UserId)
+                            .col(UserAchievement::AchievementId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_achievement_user_id")
+                            .from(UserAchievement::Table, UserAchievement::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_achievement_achievement_id")
+                            .from(UserAchievement::Table, UserAchievement::AchievementId)
+                            .to(Achievement::Table, Achievement::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let mut seed = Query::insert();
+        seed.into_table(Achievement::Table)
+            .columns([
+                Achievement::Id,
+                Achievement::Name,
+                Achievement::Description,
+                Achievement::SortOrder,
+            ])
+            .on_conflict(OnConflict::column(Achievement::Id).do_nothing().to_owned());
+        for (id, name, description, sort_order) in ACHIEVEMENTS {
+            seed.values([
+                (*id).into(),
+                (*name).into(),
+                (*description).into(),
+                (*sort_order).into(),
+            ])
+            .map_err(|e| DbErr::Custom(e.to_string()))?;
+        }
+        manager.exec_stmt(seed).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserAchievement::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Achievement::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Achievement {
+    Table,
+    Id,
+    Name,
+    Description,
+    SortOrder,
+}
+
+#[derive(DeriveIden)]
+enum UserAchievement {
+    Table,
+    UserId,
+    AchievementId,
+    AwardedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,74 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `xp_event`, the ledger of platform XP users earn. Each event is tied to the thing
+/// that earned it (a play, a game, a review), so the same thing never pays out twice.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(XpEvent::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(XpEvent::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(XpEvent::UserId).uuid().not_null())
+                    .col(ColumnDef::new(XpEvent::Source).string().not_null())
+                    .col(ColumnDef::new(XpEvent::ReferenceId).uuid().not_null())
+                    .col(ColumnDef::new(XpEvent::Amount).integer().not_null())
+                    .col(
+                        ColumnDef::new(XpEvent::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_xp_event_user_id")
+                            .from(XpEvent::Table, XpEvent::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_xp_event_user_source_reference")
+                    .table(XpEvent::Table)
+                    .col(XpEvent::UserId)
+                    .col(XpEvent::Source)
+                    .col(XpEvent::ReferenceId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(XpEvent::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum XpEvent {
+    Table,
+    Id,
+    UserId,
+    Source,
+    ReferenceId,
+    Amount,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,146 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds the `verified` creator badge to `user` and creates `verification_request`, where
+/// creators apply for it and admins approve or deny their applications.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .add_column(
+                        ColumnDef::new(User::Verified)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(VerificationRequest::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(VerificationRequest::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(VerificationRequest::UserId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(VerificationRequest::Justification)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(VerificationRequest::Status)
+                            .string()
+                            .not_null()
+                            .default("pending"),
+                    )
+                    .col(
+                        ColumnDef::new(VerificationRequest::ReviewerId)
+                            .uuid()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(VerificationRequest::ReviewNote)
+                            .text()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(VerificationRequest::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(VerificationRequest::ReviewedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_verification_request_user_id")
+                            .from(VerificationRequest::Table, VerificationRequest::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_verification_request_reviewer_id")
+                            .from(VerificationRequest::Table, VerificationRequest::ReviewerId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_verification_request_status_created_at")
+                    .table(VerificationRequest::Table)
+                    .col(VerificationRequest::Status)
+                    .col(VerificationRequest::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_verification_request_user_id")
+                    .table(VerificationRequest::Table)
+                    .col(VerificationRequest::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(VerificationRequest::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .drop_column(User::Verified)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum VerificationRequest {
+    Table,
+    Id,
+    UserId,
+    Justification,
+    Status,
+    ReviewerId,
+    ReviewNote,
+    CreatedAt,
+    ReviewedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+    Verified,
+}
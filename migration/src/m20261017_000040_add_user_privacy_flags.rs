@@ -0,0 +1,45 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds the privacy flags `hide_from_search`, `hide_games` and `hide_activity` to `user`.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only supports one column per ALTER TABLE
+        for column in [User::HideFromSearch, User::HideGames, User::HideActivity] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(User::Table)
+                        .add_column(ColumnDef::new(column).boolean().not_null().default(false))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [User::HideActivity, User::HideGames, User::HideFromSearch] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(User::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    HideFromSearch,
+    HideGames,
+    HideActivity,
+}
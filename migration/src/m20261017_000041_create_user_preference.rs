@@ -0,0 +1,60 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `user_preference`, one JSON document of client settings (theme, editor, controller)
+/// per user, synced across their devices.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserPreference::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserPreference::UserId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserPreference::Data).json().not_null())
+                    .col(
+                        ColumnDef::new(UserPreference::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_preference_user_id")
+                            .from(UserPreference::Table, UserPreference::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserPreference::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserPreference {
+    Table,
+    UserId,
+    Data,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
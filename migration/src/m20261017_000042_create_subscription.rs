@@ -0,0 +1,79 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `subscription`, linking a user to their Stripe customer and subscription. The plan
+/// it grants is mirrored onto `user.subscription_plan` / `user.subscription_expires_at`.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Subscription::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Subscription::UserId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Subscription::StripeCustomerId)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Subscription::StripeSubscriptionId)
+                            .string()
+                            .null(),
+                    )
+                    .col(ColumnDef::new(Subscription::Status).string().not_null())
+                    .col(
+                        ColumnDef::new(Subscription::CurrentPeriodEnd)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(Subscription::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_subscription_user_id")
+                            .from(Subscription::Table, Subscription::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Subscription::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Subscription {
+    Table,
+    UserId,
+    StripeCustomerId,
+    StripeSubscriptionId,
+    Status,
+    CurrentPeriodEnd,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,212 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `organization` and `organization_member`, and adds `owner_type` /
+/// `organization_id` to `game` so a game can belong to an organization rather than its
+/// creator alone.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    #[allow(clippy::too_many_lines)]
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Organization::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Organization::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Organization::Name)
+                            .string_len(100)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Organization::Slug)
+                            .string_len(100)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(Organization::Description).text().null())
+                    .col(
+                        ColumnDef::new(Organization::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Organization::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(OrganizationMember::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(OrganizationMember::OrganizationId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(OrganizationMember::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(OrganizationMember::Role)
+                            .string_len(20)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(OrganizationMember::InvitedBy).uuid().null())
+                    .col(
+                        ColumnDef::new(OrganizationMember::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(OrganizationMember::AcceptedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(OrganizationMember::OrganizationId)
+                            .col(OrganizationMember::UserId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_organization_member_organization_id")
+                            .from(
+                                OrganizationMember::Table,
+                                OrganizationMember::OrganizationId,
+                            )
+                            .to(Organization::Table, Organization::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_organization_member_user_id")
+                            .from(OrganizationMember::Table, OrganizationMember::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // A user's organizations and pending invites are looked up by member
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_organization_member_user_id")
+                    .table(OrganizationMember::Table)
+                    .col(OrganizationMember::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // SQLite only supports one column per ALTER TABLE, and none with a foreign key
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Game::Table)
+                    .add_column(
+                        ColumnDef::new(Game::OwnerType)
+                            .string_len(20)
+                            .not_null()
+                            .default("user"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Game::Table)
+                    .add_column(ColumnDef::new(Game::OrganizationId).uuid().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_game_organization_id")
+                    .table(Game::Table)
+                    .col(Game::OrganizationId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_game_organization_id")
+                    .table(Game::Table)
+                    .to_owned(),
+            )
+            .await?;
+        for column in [Game::OrganizationId, Game::OwnerType] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Game::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        manager
+            .drop_table(Table::drop().table(OrganizationMember::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Organization::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Organization {
+    Table,
+    Id,
+    Name,
+    Slug,
+    Description,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum OrganizationMember {
+    Table,
+    OrganizationId,
+    UserId,
+    Role,
+    InvitedBy,
+    CreatedAt,
+    AcceptedAt,
+}
+
+#[derive(DeriveIden)]
+enum Game {
+    Table,
+    OwnerType,
+    OrganizationId,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
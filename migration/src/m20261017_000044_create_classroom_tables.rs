@@ -0,0 +1,141 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `classroom` (a teacher's class, joined with its class code) and
+/// `classroom_student` (the managed student accounts in each class).
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Classroom::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Classroom::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Classroom::TeacherId).uuid().not_null())
+                    .col(ColumnDef::new(Classroom::Name).string_len(100).not_null())
+                    .col(
+                        ColumnDef::new(Classroom::Code)
+                            .string_len(10)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Classroom::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_classroom_teacher_id")
+                            .from(Classroom::Table, Classroom::TeacherId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_classroom_teacher_id")
+                    .table(Classroom::Table)
+                    .col(Classroom::TeacherId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ClassroomStudent::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ClassroomStudent::UserId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassroomStudent::ClassroomId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassroomStudent::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_classroom_student_user_id")
+                            .from(ClassroomStudent::Table, ClassroomStudent::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_classroom_student_classroom_id")
+                            .from(ClassroomStudent::Table, ClassroomStudent::ClassroomId)
+                            .to(Classroom::Table, Classroom::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Class rosters list students by class
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_classroom_student_classroom_id")
+                    .table(ClassroomStudent::Table)
+                    .col(ClassroomStudent::ClassroomId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ClassroomStudent::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Classroom::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Classroom {
+    Table,
+    Id,
+    TeacherId,
+    Name,
+    Code,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ClassroomStudent {
+    Table,
+    UserId,
+    ClassroomId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
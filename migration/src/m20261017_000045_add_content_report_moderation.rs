@@ -0,0 +1,100 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds the moderation queue columns to `content_report`: who claimed the report, and who
+/// resolved it, how and when.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only supports one column per ALTER TABLE, and none with a foreign key
+        let columns = [
+            ColumnDef::new(ContentReport::AssignedTo)
+                .uuid()
+                .null()
+                .to_owned(),
+            ColumnDef::new(ContentReport::AssignedAt)
+                .timestamp_with_time_zone()
+                .null()
+                .to_owned(),
+            ColumnDef::new(ContentReport::ResolvedBy)
+                .uuid()
+                .null()
+                .to_owned(),
+            ColumnDef::new(ContentReport::Resolution)
+                .string_len(20)
+                .null()
+                .to_owned(),
+            ColumnDef::new(ContentReport::ResolutionNote)
+                .text()
+                .null()
+                .to_owned(),
+            ColumnDef::new(ContentReport::ResolvedAt)
+                .timestamp_with_time_zone()
+                .null()
+                .to_owned(),
+        ];
+        for mut column in columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(ContentReport::Table)
+                        .add_column(&mut column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // Moderators list the reports they have claimed
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_content_report_assigned_to")
+                    .table(ContentReport::Table)
+                    .col(ContentReport::AssignedTo)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_content_report_assigned_to")
+                    .table(ContentReport::Table)
+                    .to_owned(),
+            )
+            .await?;
+        for column in [
+            ContentReport::ResolvedAt,
+            ContentReport::ResolutionNote,
+            ContentReport::Resolution,
+            ContentReport::ResolvedBy,
+            ContentReport::AssignedAt,
+            ContentReport::AssignedTo,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(ContentReport::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ContentReport {
+    Table,
+    AssignedTo,
+    AssignedAt,
+    ResolvedBy,
+    Resolution,
+    ResolutionNote,
+    ResolvedAt,
+}
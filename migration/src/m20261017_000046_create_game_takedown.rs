@@ -0,0 +1,134 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `game_takedown`: a moderator's removal of a game, and the creator's appeal of it.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GameTakedown::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GameTakedown::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(GameTakedown::GameId).uuid().not_null())
+                    .col(ColumnDef::new(GameTakedown::ModeratorId).uuid().null())
+                    .col(ColumnDef::new(GameTakedown::Reason).text().not_null())
+                    .col(
+                        ColumnDef::new(GameTakedown::PreviousStatus)
+                            .string_len(20)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GameTakedown::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(GameTakedown::AppealMessage).text().null())
+                    .col(
+                        ColumnDef::new(GameTakedown::AppealedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(GameTakedown::AppealStatus)
+                            .string_len(20)
+                            .null(),
+                    )
+                    .col(ColumnDef::new(GameTakedown::AppealReviewerId).uuid().null())
+                    .col(ColumnDef::new(GameTakedown::AppealReviewNote).text().null())
+                    .col(
+                        ColumnDef::new(GameTakedown::AppealReviewedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_game_takedown_game_id")
+                            .from(GameTakedown::Table, GameTakedown::GameId)
+                            .to(Game::Table, Game::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_game_takedown_moderator_id")
+                            .from(GameTakedown::Table, GameTakedown::ModeratorId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_game_takedown_appeal_reviewer_id")
+                            .from(GameTakedown::Table, GameTakedown::AppealReviewerId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_game_takedown_game_id")
+                    .table(GameTakedown::Table)
+                    .col(GameTakedown::GameId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Admins work through pending appeals
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_game_takedown_appeal_status")
+                    .table(GameTakedown::Table)
+                    .col(GameTakedown::AppealStatus)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GameTakedown::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GameTakedown {
+    Table,
+    Id,
+    GameId,
+    ModeratorId,
+    Reason,
+    PreviousStatus,
+    CreatedAt,
+    AppealMessage,
+    AppealedAt,
+    AppealStatus,
+    AppealReviewerId,
+    AppealReviewNote,
+    AppealReviewedAt,
+}
+
+#[derive(DeriveIden)]
+enum Game {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,97 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `audit_log`: who changed what through an admin or moderator endpoint, and from
+/// where. Entries keep their actor ID without a foreign key so they outlive purged accounts.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditLog::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(AuditLog::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(AuditLog::ActorId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(AuditLog::ActorRole)
+                            .string_len(20)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AuditLog::Action).string_len(50).not_null())
+                    .col(
+                        ColumnDef::new(AuditLog::TargetType)
+                            .string_len(30)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AuditLog::TargetId).uuid().not_null())
+                    .col(ColumnDef::new(AuditLog::Before).json().null())
+                    .col(ColumnDef::new(AuditLog::After).json().null())
+                    .col(ColumnDef::new(AuditLog::IpAddress).string().null())
+                    .col(
+                        ColumnDef::new(AuditLog::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_log_created_at")
+                    .table(AuditLog::Table)
+                    .col(AuditLog::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_log_actor_id_created_at")
+                    .table(AuditLog::Table)
+                    .col(AuditLog::ActorId)
+                    .col(AuditLog::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // The history of one record, e.g. everything done to a game
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_log_target")
+                    .table(AuditLog::Table)
+                    .col(AuditLog::TargetType)
+                    .col(AuditLog::TargetId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AuditLog::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AuditLog {
+    Table,
+    Id,
+    ActorId,
+    ActorRole,
+    Action,
+    TargetType,
+    TargetId,
+    Before,
+    After,
+    IpAddress,
+    CreatedAt,
+}
@@ -0,0 +1,120 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `announcement`: platform-wide banners (maintenance notices, events) admins
+/// schedule for the frontends to show.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Announcement::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Announcement::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Announcement::Title)
+                            .string_len(100)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Announcement::Body).text().not_null())
+                    .col(
+                        ColumnDef::new(Announcement::Severity)
+                            .string_len(20)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Announcement::LinkUrl).string().null())
+                    .col(
+                        ColumnDef::new(Announcement::Dismissible)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(Announcement::DismissalKey)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Announcement::StartsAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Announcement::EndsAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(ColumnDef::new(Announcement::CreatedBy).uuid().null())
+                    .col(
+                        ColumnDef::new(Announcement::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Announcement::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_announcement_created_by")
+                            .from(Announcement::Table, Announcement::CreatedBy)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // The public endpoint looks up banners whose window contains now
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_announcement_starts_at_ends_at")
+                    .table(Announcement::Table)
+                    .col(Announcement::StartsAt)
+                    .col(Announcement::EndsAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Announcement::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Announcement {
+    Table,
+    Id,
+    Title,
+    Body,
+    Severity,
+    LinkUrl,
+    Dismissible,
+    DismissalKey,
+    StartsAt,
+    EndsAt,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
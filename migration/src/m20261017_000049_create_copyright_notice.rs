@@ -0,0 +1,236 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `copyright_notice`: a rights holder's takedown notice against a game, its review
+/// by an admin, and the creator's counter-notice.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    #[allow(clippy::too_many_lines)]
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CopyrightNotice::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CopyrightNotice::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(CopyrightNotice::Status)
+                            .string_len(20)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(CopyrightNotice::GameId).uuid().null())
+                    .col(
+                        ColumnDef::new(CopyrightNotice::ClaimantName)
+                            .string_len(200)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(CopyrightNotice::ClaimantEmail)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(CopyrightNotice::ClaimantAddress)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(CopyrightNotice::ClaimantPhone)
+                            .string_len(50)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(CopyrightNotice::ClaimantIp)
+                            .string_len(45)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(CopyrightNotice::CopyrightedWork)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(CopyrightNotice::Signature)
+                            .string_len(200)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(CopyrightNotice::ReviewerId).uuid().null())
+                    .col(ColumnDef::new(CopyrightNotice::ReviewNote).text().null())
+                    .col(
+                        ColumnDef::new(CopyrightNotice::ReviewStartedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(CopyrightNotice::DecidedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(ColumnDef::new(CopyrightNotice::TakedownId).uuid().null())
+                    .col(
+                        ColumnDef::new(CopyrightNotice::CounterName)
+                            .string_len(200)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(CopyrightNotice::CounterAddress)
+                            .text()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(CopyrightNotice::CounterStatement)
+                            .text()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(CopyrightNotice::CounterSignature)
+                            .string_len(200)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(CopyrightNotice::CounterNoticedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(CopyrightNotice::RestoreAfter)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(CopyrightNotice::RestoredAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(CopyrightNotice::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(CopyrightNotice::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    // Notices are legal records: they outlive the game and the admin
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_copyright_notice_game_id")
+                            .from(CopyrightNotice::Table, CopyrightNotice::GameId)
+                            .to(Game::Table, Game::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_copyright_notice_reviewer_id")
+                            .from(CopyrightNotice::Table, CopyrightNotice::ReviewerId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_copyright_notice_takedown_id")
+                            .from(CopyrightNotice::Table, CopyrightNotice::TakedownId)
+                            .to(GameTakedown::Table, GameTakedown::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Admins work through the queue by status, oldest first
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_copyright_notice_status_created_at")
+                    .table(CopyrightNotice::Table)
+                    .col(CopyrightNotice::Status)
+                    .col(CopyrightNotice::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_copyright_notice_game_id")
+                    .table(CopyrightNotice::Table)
+                    .col(CopyrightNotice::GameId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // The restore job looks for counter-notices whose window has passed
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_copyright_notice_restore_after")
+                    .table(CopyrightNotice::Table)
+                    .col(CopyrightNotice::RestoreAfter)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CopyrightNotice::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CopyrightNotice {
+    Table,
+    Id,
+    Status,
+    GameId,
+    ClaimantName,
+    ClaimantEmail,
+    ClaimantAddress,
+    ClaimantPhone,
+    ClaimantIp,
+    CopyrightedWork,
+    Signature,
+    ReviewerId,
+    ReviewNote,
+    ReviewStartedAt,
+    DecidedAt,
+    TakedownId,
+    CounterName,
+    CounterAddress,
+    CounterStatement,
+    CounterSignature,
+    CounterNoticedAt,
+    RestoreAfter,
+    RestoredAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Game {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum GameTakedown {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,78 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `user_note`: moderators' private notes on user accounts (prior warnings, context
+/// for the next moderator).
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserNote::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(UserNote::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(UserNote::UserId).uuid().not_null())
+                    .col(ColumnDef::new(UserNote::AuthorId).uuid().null())
+                    .col(ColumnDef::new(UserNote::Body).text().not_null())
+                    .col(
+                        ColumnDef::new(UserNote::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_note_user")
+                            .from(UserNote::Table, UserNote::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_note_author")
+                            .from(UserNote::Table, UserNote::AuthorId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // The admin user detail lists a user's notes newest first
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_note_user_id_created_at")
+                    .table(UserNote::Table)
+                    .col(UserNote::UserId)
+                    .col(UserNote::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserNote::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserNote {
+    Table,
+    Id,
+    UserId,
+    AuthorId,
+    Body,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,117 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `suspension_appeal`: suspended users' requests to have their account reinstated,
+/// and the admin decision on each.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SuspensionAppeal::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SuspensionAppeal::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SuspensionAppeal::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(SuspensionAppeal::SuspensionReason)
+                            .text()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SuspensionAppeal::Statement)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SuspensionAppeal::Status)
+                            .string_len(20)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(SuspensionAppeal::ReviewerId).uuid().null())
+                    .col(ColumnDef::new(SuspensionAppeal::ReviewNote).text().null())
+                    .col(
+                        ColumnDef::new(SuspensionAppeal::ReviewedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SuspensionAppeal::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_suspension_appeal_user")
+                            .from(SuspensionAppeal::Table, SuspensionAppeal::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_suspension_appeal_reviewer")
+                            .from(SuspensionAppeal::Table, SuspensionAppeal::ReviewerId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Admins work through pending appeals oldest first
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_suspension_appeal_status_created_at")
+                    .table(SuspensionAppeal::Table)
+                    .col(SuspensionAppeal::Status)
+                    .col(SuspensionAppeal::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_suspension_appeal_user_id")
+                    .table(SuspensionAppeal::Table)
+                    .col(SuspensionAppeal::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SuspensionAppeal::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SuspensionAppeal {
+    Table,
+    Id,
+    UserId,
+    SuspensionReason,
+    Statement,
+    Status,
+    ReviewerId,
+    ReviewNote,
+    ReviewedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,108 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `analytics_event`: raw product analytics events reported by clients (games opened,
+/// controller latency, editor actions).
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AnalyticsEvent::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AnalyticsEvent::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AnalyticsEvent::Name)
+                            .string_len(50)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AnalyticsEvent::UserId).uuid().null())
+                    .col(
+                        ColumnDef::new(AnalyticsEvent::AnonymousId)
+                            .string_len(64)
+                            .null(),
+                    )
+                    .col(ColumnDef::new(AnalyticsEvent::GameId).uuid().null())
+                    .col(ColumnDef::new(AnalyticsEvent::SessionId).uuid().null())
+                    .col(ColumnDef::new(AnalyticsEvent::Properties).json().not_null())
+                    .col(
+                        ColumnDef::new(AnalyticsEvent::OccurredAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AnalyticsEvent::ReceivedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_analytics_event_user")
+                            .from(AnalyticsEvent::Table, AnalyticsEvent::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Reports count one kind of event over a time range
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_analytics_event_name_occurred_at")
+                    .table(AnalyticsEvent::Table)
+                    .col(AnalyticsEvent::Name)
+                    .col(AnalyticsEvent::OccurredAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // ...or the events of one game
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_analytics_event_game_id_occurred_at")
+                    .table(AnalyticsEvent::Table)
+                    .col(AnalyticsEvent::GameId)
+                    .col(AnalyticsEvent::OccurredAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AnalyticsEvent::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AnalyticsEvent {
+    Table,
+    Id,
+    Name,
+    UserId,
+    AnonymousId,
+    GameId,
+    SessionId,
+    Properties,
+    OccurredAt,
+    ReceivedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,65 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds `unique_players` and `opens` to `game_daily_stats`: distinct players in the sessions
+/// that played the game, and how often clients reported opening it.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One column per statement: SQLite cannot add several at once
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameDailyStats::Table)
+                    .add_column(
+                        ColumnDef::new(GameDailyStats::UniquePlayers)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameDailyStats::Table)
+                    .add_column(
+                        ColumnDef::new(GameDailyStats::Opens)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameDailyStats::Table)
+                    .drop_column(GameDailyStats::Opens)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameDailyStats::Table)
+                    .drop_column(GameDailyStats::UniquePlayers)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GameDailyStats {
+    Table,
+    UniquePlayers,
+    Opens,
+}
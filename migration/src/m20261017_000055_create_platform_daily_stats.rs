@@ -0,0 +1,87 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `platform_daily_stats`: sessions, plays, players and analytics events across the
+/// whole platform, rolled up per UTC day for the admin dashboard.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PlatformDailyStats::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PlatformDailyStats::Day)
+                            .date()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(PlatformDailyStats::Sessions)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(PlatformDailyStats::SessionSeconds)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(PlatformDailyStats::Plays)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(PlatformDailyStats::PlaySeconds)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(PlatformDailyStats::UniquePlayers)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(PlatformDailyStats::GamesPlayed)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(PlatformDailyStats::Events)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PlatformDailyStats::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PlatformDailyStats {
+    Table,
+    Day,
+    Sessions,
+    SessionSeconds,
+    Plays,
+    PlaySeconds,
+    UniquePlayers,
+    GamesPlayed,
+    Events,
+}
@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds `first_input_at` to `player`: when the player first sent controller input, telling
+/// players who actually played apart from those who only joined.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Player::Table)
+                    .add_column(
+                        ColumnDef::new(Player::FirstInputAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Player::Table)
+                    .drop_column(Player::FirstInputAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Player {
+    Table,
+    FirstInputAt,
+}
@@ -0,0 +1,99 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds the telemetry summary recorded on `session` when it ends: how long it lasted, the
+/// most players connected at once, messages relayed and player disconnects.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One column per statement: SQLite cannot add several at once
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Session::Table)
+                    .add_column(
+                        ColumnDef::new(Session::DurationSeconds)
+                            .big_integer()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Session::Table)
+                    .add_column(ColumnDef::new(Session::PeakPlayers).integer().null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Session::Table)
+                    .add_column(
+                        ColumnDef::new(Session::MessagesRelayed)
+                            .big_integer()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Session::Table)
+                    .add_column(ColumnDef::new(Session::Disconnects).integer().null())
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Session::Table)
+                    .drop_column(Session::Disconnects)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Session::Table)
+                    .drop_column(Session::MessagesRelayed)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Session::Table)
+                    .drop_column(Session::PeakPlayers)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Session::Table)
+                    .drop_column(Session::DurationSeconds)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Session {
+    Table,
+    DurationSeconds,
+    PeakPlayers,
+    MessagesRelayed,
+    Disconnects,
+}
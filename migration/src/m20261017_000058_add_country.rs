@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds `country` to `player` and `login_event`: the ISO 3166-1 alpha-2 code of the country
+/// the client's IP address resolved to, for country breakdowns in analytics.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Player::Table)
+                    .add_column(ColumnDef::new(Player::Country).string_len(2).null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LoginEvent::Table)
+                    .add_column(ColumnDef::new(LoginEvent::Country).string_len(2).null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LoginEvent::Table)
+                    .drop_column(LoginEvent::Country)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Player::Table)
+                    .drop_column(Player::Country)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Player {
+    Table,
+    Country,
+}
+
+#[derive(DeriveIden)]
+enum LoginEvent {
+    Table,
+    Country,
+}
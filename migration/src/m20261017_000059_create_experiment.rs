@@ -0,0 +1,95 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `experiment`: A/B experiments and the variants their traffic is split between.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Experiment::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Experiment::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Experiment::Key)
+                            .string_len(64)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(Experiment::Description).text().null())
+                    .col(ColumnDef::new(Experiment::Variants).json().not_null())
+                    .col(
+                        ColumnDef::new(Experiment::Status)
+                            .string_len(20)
+                            .not_null()
+                            .default("draft"),
+                    )
+                    .col(ColumnDef::new(Experiment::CreatedBy).uuid().null())
+                    .col(
+                        ColumnDef::new(Experiment::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Experiment::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_experiment_created_by")
+                            .from(Experiment::Table, Experiment::CreatedBy)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Assignment looks up every running experiment
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_experiment_status")
+                    .table(Experiment::Table)
+                    .col(Experiment::Status)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Experiment::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Experiment {
+    Table,
+    Id,
+    Key,
+    Description,
+    Variants,
+    Status,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
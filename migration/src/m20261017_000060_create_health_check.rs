@@ -0,0 +1,69 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `health_check`: the periodic results of each component's health check, from
+/// which the public status endpoint computes uptime.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(HealthCheck::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HealthCheck::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(HealthCheck::Component)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HealthCheck::Status)
+                            .string_len(20)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(HealthCheck::LatencyMs).big_integer().null())
+                    .col(
+                        ColumnDef::new(HealthCheck::CheckedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Uptime is counted over recent checks, and old checks are pruned by age
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_health_check_checked_at")
+                    .table(HealthCheck::Table)
+                    .col(HealthCheck::CheckedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HealthCheck::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum HealthCheck {
+    Table,
+    Id,
+    Component,
+    Status,
+    LatencyMs,
+    CheckedAt,
+}
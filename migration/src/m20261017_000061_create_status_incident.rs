@@ -0,0 +1,85 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `status_incident`: incidents admins post on the public status page.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(StatusIncident::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(StatusIncident::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(StatusIncident::Title)
+                            .string_len(100)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(StatusIncident::Message).text().not_null())
+                    .col(
+                        ColumnDef::new(StatusIncident::Impact)
+                            .string_len(20)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(StatusIncident::StartedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(StatusIncident::ResolvedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(ColumnDef::new(StatusIncident::CreatedBy).uuid().null())
+                    .col(
+                        ColumnDef::new(StatusIncident::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_status_incident_created_by")
+                            .from(StatusIncident::Table, StatusIncident::CreatedBy)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(StatusIncident::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum StatusIncident {
+    Table,
+    Id,
+    Title,
+    Message,
+    Impact,
+    StartedAt,
+    ResolvedAt,
+    CreatedBy,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,25 @@
+use sea_orm_migration::prelude::*;
+
+/// Deletes pending email changes, whose tokens were stored in plaintext.
+///
+/// `email_change.confirmation_token` and `verification_token` now hold SHA-256 digests, so the
+/// old values could no longer be redeemed anyway. Affected users request the change again.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DELETE FROM email_change")
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        // Deleted changes cannot be restored
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use sea_orm_migration::prelude::*;
+
+/// Indexes game tags for full-text search (`PostgreSQL` only).
+///
+/// Adds `game.tag_names`, the game's tag names kept current by triggers on `game_tag` and
+/// `tag`, and a generated `game.search_vector` weighting title, description, and tags. Its GIN
+/// index replaces `idx_game_search`, which did not cover tags. `SQLite` has no `tsvector`
+/// support, so this migration is a no-op there.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const UP: &str = "
+ALTER TABLE game ADD COLUMN tag_names TEXT NOT NULL DEFAULT '';
+
+CREATE OR REPLACE FUNCTION refresh_game_tag_names(target UUID) RETURNS VOID AS $$
+    UPDATE game SET tag_names = coalesce(
+        (SELECT string_agg(t.name, ' ' ORDER BY t.name)
+         FROM game_tag gt JOIN tag t ON t.id = gt.tag_id
+         WHERE gt.game_id = target),
+        '')
+    WHERE id = target;
+$$ LANGUAGE sql;
+
+CREATE OR REPLACE FUNCTION game_tag_changed() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP <> 'DELETE' THEN
+        PERFORM refresh_game_tag_names(NEW.game_id);
+    END IF;
+    IF TG_OP <> 'INSERT' THEN
+        PERFORM refresh_game_tag_names(OLD.game_id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER game_tag_names AFTER INSERT OR UPDATE OR DELETE ON game_tag
+    FOR EACH ROW EXECUTE FUNCTION game_tag_changed();
+
+CREATE OR REPLACE FUNCTION tag_renamed() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM refresh_game_tag_names(gt.game_id) FROM game_tag gt WHERE gt.tag_id = NEW.id;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER tag_names_renamed AFTER UPDATE OF name ON tag
+    FOR EACH ROW EXECUTE FUNCTION tag_renamed();
+
+SELECT refresh_game_tag_names(g.id) FROM game g WHERE EXISTS (
+    SELECT 1 FROM game_tag gt WHERE gt.game_id = g.id
+);
+
+ALTER TABLE game ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (
+    setweight(to_tsvector('english', coalesce(title, '')), 'A')
+    || setweight(to_tsvector('english', coalesce(description, '')), 'B')
+    || setweight(to_tsvector('english', tag_names), 'C')
+) STORED;
+
+CREATE INDEX idx_game_search_vector ON game USING GIN (search_vector);
+
+DROP INDEX IF EXISTS idx_game_search;
+";
+
+const DOWN: &str = "
+CREATE INDEX IF NOT EXISTS idx_game_search ON game USING GIN
+    (to_tsvector('english', coalesce(title, '') || ' ' || coalesce(description, '')));
+
+ALTER TABLE game DROP COLUMN search_vector;
+DROP TRIGGER tag_names_renamed ON tag;
+DROP FUNCTION tag_renamed();
+DROP TRIGGER game_tag_names ON game_tag;
+DROP FUNCTION game_tag_changed();
+DROP FUNCTION refresh_game_tag_names(UUID);
+ALTER TABLE game DROP COLUMN tag_names;
+";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != sea_orm::DatabaseBackend::Postgres {
+            return Ok(());
+        }
+
+        manager.get_connection().execute_unprepared(UP).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != sea_orm::DatabaseBackend::Postgres {
+            return Ok(());
+        }
+
+        manager.get_connection().execute_unprepared(DOWN).await?;
+
+        Ok(())
+    }
+}
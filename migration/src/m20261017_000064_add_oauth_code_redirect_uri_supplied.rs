@@ -0,0 +1,43 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds `redirect_uri_supplied` to `oauth_authorization_code`: whether the authorization
+/// request named a `redirect_uri`, which the token request must then repeat. Codes issued
+/// before keep requiring it.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OauthAuthorizationCode::Table)
+                    .add_column(
+                        ColumnDef::new(OauthAuthorizationCode::RedirectUriSupplied)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OauthAuthorizationCode::Table)
+                    .drop_column(OauthAuthorizationCode::RedirectUriSupplied)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum OauthAuthorizationCode {
+    Table,
+    RedirectUriSupplied,
+}
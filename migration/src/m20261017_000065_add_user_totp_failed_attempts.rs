@@ -0,0 +1,63 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds `failed_attempts` and `last_failed_at` to `user_totp`, counting the sign-in codes
+/// tried since the last accepted one so a challenge cannot be used to guess codes forever.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserTotp::Table)
+                    .add_column(
+                        ColumnDef::new(UserTotp::FailedAttempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserTotp::Table)
+                    .add_column(
+                        ColumnDef::new(UserTotp::LastFailedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserTotp::Table)
+                    .drop_column(UserTotp::LastFailedAt)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserTotp::Table)
+                    .drop_column(UserTotp::FailedAttempts)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserTotp {
+    Table,
+    FailedAttempts,
+    LastFailedAt,
+}
@@ -0,0 +1,54 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds `revoke_token_expires_at` to `known_device` and clears the outstanding "this wasn't
+/// me" tokens, which were stored in plaintext.
+///
+/// `known_device.revoke_token` now holds a SHA-256 digest, so the old values could no longer be
+/// redeemed anyway. The devices stay known; only their pending links stop working.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(KnownDevice::Table)
+                    .add_column(
+                        ColumnDef::new(KnownDevice::RevokeTokenExpiresAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(KnownDevice::Table)
+                    .value(KnownDevice::RevokeToken, Option::<String>::None)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Cleared tokens cannot be restored
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(KnownDevice::Table)
+                    .drop_column(KnownDevice::RevokeTokenExpiresAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum KnownDevice {
+    Table,
+    RevokeToken,
+    RevokeTokenExpiresAt,
+}
@@ -0,0 +1,74 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `stripe_event`, the Stripe webhook events already applied, so redeliveries are
+/// skipped, and adds `subscription.last_event_at`, the `created` time of the last event applied
+/// to it, so events arriving out of order do not overwrite newer state.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(StripeEvent::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(StripeEvent::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(StripeEvent::Kind).string().not_null())
+                    .col(
+                        ColumnDef::new(StripeEvent::ProcessedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Subscription::Table)
+                    .add_column(
+                        ColumnDef::new(Subscription::LastEventAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Subscription::Table)
+                    .drop_column(Subscription::LastEventAt)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(StripeEvent::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum StripeEvent {
+    Table,
+    Id,
+    Kind,
+    ProcessedAt,
+}
+
+#[derive(DeriveIden)]
+enum Subscription {
+    Table,
+    LastEventAt,
+}
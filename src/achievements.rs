@@ -0,0 +1,121 @@
+//! Achievements: badges users earn for what they do on the platform.
+//!
+//! The catalog lives in the `achievement` table, seeded by migration. Flows that can earn one
+//! call the matching hook here once their own work has succeeded. Awarding is idempotent, and
+//! a newly earned achievement also sends its owner an in-app notification.
+
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveValue::Set, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter,
+};
+use uuid::Uuid;
+
+use crate::entities::{achievement, game, session, user_achievement};
+use crate::notifications::{self, NewNotification, NotificationHub};
+
+/// Awarded to a creator the first time one of their games is published.
+pub const FIRST_PUBLISH: &str = "first_publish";
+
+/// Awarded to a creator once one of their games has been played 100 times.
+pub const HUNDRED_PLAYS: &str = "hundred_plays";
+
+/// Awarded to a user once they have hosted 10 sessions.
+pub const TEN_SESSIONS_HOSTED: &str = "ten_sessions_hosted";
+
+/// Plays a single game needs for [`HUNDRED_PLAYS`].
+const PLAYS_FOR_HUNDRED_PLAYS: i64 = 100;
+
+/// Sessions a host needs for [`TEN_SESSIONS_HOSTED`].
+const SESSIONS_FOR_TEN_SESSIONS_HOSTED: u64 = 10;
+
+/// Award `achievement_id` to `user_id`, returning whether they did not have it yet.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails, including when `achievement_id` is not in
+/// the catalog.
+pub async fn award<C: ConnectionTrait>(
+    db: &C,
+    hub: &NotificationHub,
+    user_id: Uuid,
+    achievement_id: &str,
+) -> anyhow::Result<bool> {
+    let inserted = user_achievement::Entity::insert(user_achievement::ActiveModel {
+        user_id: Set(user_id),
+        achievement_id: Set(achievement_id.to_string()),
+        awarded_at: Set(chrono::Utc::now().fixed_offset()),
+    })
+    .on_conflict(
+        OnConflict::columns([
+            user_achievement::Column::UserId,
+            user_achievement::Column::AchievementId,
+        ])
+        .do_nothing()
+        .to_owned(),
+    )
+    .exec_without_returning(db)
+    .await?;
+    if inserted == 0 {
+        return Ok(false);
+    }
+
+    if let Some(earned) = achievement::Entity::find_by_id(achievement_id)
+        .one(db)
+        .await?
+    {
+        let new = NewNotification {
+            kind: notifications::ACHIEVEMENT_UNLOCKED,
+            title: format!("Achievement unlocked: {}", earned.name),
+            body: earned.description,
+            link: None,
+        };
+        notifications::send(db, hub, user_id, new).await?;
+    }
+    Ok(true)
+}
+
+/// Hook for a published game: its owner earns [`FIRST_PUBLISH`].
+pub async fn game_published<C: ConnectionTrait>(db: &C, hub: &NotificationHub, owner_id: Uuid) {
+    award_best_effort(db, hub, owner_id, FIRST_PUBLISH).await;
+}
+
+/// Hook for a counted play of `game_id`: its owner earns [`HUNDRED_PLAYS`] once the game has
+/// enough plays.
+pub async fn game_played<C: ConnectionTrait>(db: &C, hub: &NotificationHub, game_id: Uuid) {
+    match game::Entity::find_by_id(game_id).one(db).await {
+        Ok(Some(played)) if played.play_count >= PLAYS_FOR_HUNDRED_PLAYS => {
+            award_best_effort(db, hub, played.owner_id, HUNDRED_PLAYS).await;
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!(error = %e, %game_id, "Failed to check play achievements"),
+    }
+}
+
+/// Hook for a newly created session: its host earns [`TEN_SESSIONS_HOSTED`] once they have
+/// hosted enough.
+pub async fn session_hosted<C: ConnectionTrait>(db: &C, hub: &NotificationHub, host_id: Uuid) {
+    let hosted = session::Entity::find()
+        .filter(session::Column::HostId.eq(host_id))
+        .count(db)
+        .await;
+    match hosted {
+        Ok(hosted) if hosted >= SESSIONS_FOR_TEN_SESSIONS_HOSTED => {
+            award_best_effort(db, hub, host_id, TEN_SESSIONS_HOSTED).await;
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!(error = %e, %host_id, "Failed to check hosting achievements"),
+    }
+}
+
+/// Achievements are a side effect of the flow that earns them: failing to award one is logged
+/// rather than failing the request.
+async fn award_best_effort<C: ConnectionTrait>(
+    db: &C,
+    hub: &NotificationHub,
+    user_id: Uuid,
+    achievement_id: &str,
+) {
+    if let Err(e) = award(db, hub, user_id, achievement_id).await {
+        tracing::warn!(error = %e, %user_id, achievement_id, "Failed to award achievement");
+    }
+}
@@ -0,0 +1,245 @@
+//! Alerts on error-rate spikes.
+//!
+//! When `ALERT_WEBHOOK_URL` is set, an in-process monitor samples the share of requests
+//! answered with a 5xx status and the share of `WebSocket` sends that failed because the
+//! client was gone, each over a sliding window. Crossing a threshold `POST`s an alert to the
+//! webhook, and another one follows once the rate is back under it. The payload carries the
+//! message as both `text` and `content`, so Slack and Discord incoming webhooks accept it.
+
+use std::collections::VecDeque;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use axum::extract::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+use serde_json::{Value, json};
+
+use crate::config::Config;
+use crate::sessions::SessionManager;
+
+/// How often the counters are sampled.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Samples in the sliding window: five minutes' worth.
+const WINDOW_SAMPLES: usize = 30;
+
+/// Fewest events in the window for a rate to count; below it a handful of failures would
+/// look like a spike.
+const MIN_VOLUME: u64 = 20;
+
+/// How long posting an alert may take before it is given up on.
+const SEND_TIMEOUT: Duration = Duration::from_secs(5);
+
+static MONITOR: OnceLock<Monitor> = OnceLock::new();
+
+/// Counters fed by [`track`] between samples.
+#[derive(Default)]
+struct Monitor {
+    requests: AtomicU64,
+    server_errors: AtomicU64,
+}
+
+/// Start monitoring if `config.alert_webhook_url` is set.
+pub fn init(config: &Config, session_manager: SessionManager) {
+    if config.alert_webhook_url.is_empty() || MONITOR.set(Monitor::default()).is_err() {
+        return;
+    }
+    let webhook_url = config.alert_webhook_url.clone();
+    let mut http = Alarm::new("HTTP 5xx rate", config.alert_error_rate);
+    let mut ws = Alarm::new("WebSocket send failure rate", config.alert_ws_failure_rate);
+
+    tokio::spawn(async move {
+        let client = reqwest::Client::new();
+        let mut interval = tokio::time::interval(SAMPLE_INTERVAL);
+        let mut last_relay = session_manager.metrics();
+        loop {
+            interval.tick().await;
+            let Some(monitor) = MONITOR.get() else {
+                return;
+            };
+
+            let requests = monitor.requests.swap(0, Ordering::Relaxed);
+            let server_errors = monitor.server_errors.swap(0, Ordering::Relaxed);
+            let relay = session_manager.metrics();
+            let dropped = relay
+                .dropped_sends_total
+                .saturating_sub(last_relay.dropped_sends_total);
+            let sends = dropped
+                + relay
+                    .messages_relayed_total
+                    .saturating_sub(last_relay.messages_relayed_total);
+            last_relay = relay;
+
+            for alert in [
+                http.observe(server_errors, requests),
+                ws.observe(dropped, sends),
+            ]
+            .into_iter()
+            .flatten()
+            {
+                send(&client, &webhook_url, &alert).await;
+            }
+        }
+    });
+    tracing::info!("Alerting on error-rate spikes");
+}
+
+/// Middleware: count responses, and those with a 5xx status, for the monitor.
+pub async fn track(req: Request, next: Next) -> Response {
+    let response = next.run(req).await;
+    if let Some(monitor) = MONITOR.get() {
+        monitor.requests.fetch_add(1, Ordering::Relaxed);
+        if response.status().is_server_error() {
+            monitor.server_errors.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    response
+}
+
+async fn send(client: &reqwest::Client, webhook_url: &str, alert: &Value) {
+    let result = client
+        .post(webhook_url)
+        .timeout(SEND_TIMEOUT)
+        .json(alert)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status);
+    if let Err(e) = result {
+        tracing::warn!(error = %e, "Failed to post alert to webhook");
+    }
+}
+
+/// Failures and totals of the last [`WINDOW_SAMPLES`] samples.
+#[derive(Debug, Default)]
+struct SlidingWindow {
+    samples: VecDeque<(u64, u64)>,
+}
+
+impl SlidingWindow {
+    fn push(&mut self, failures: u64, total: u64) {
+        if self.samples.len() == WINDOW_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((failures, total));
+    }
+
+    /// Share of failures in the window, or `None` below [`MIN_VOLUME`].
+    #[allow(clippy::cast_precision_loss)]
+    fn rate(&self) -> Option<f64> {
+        let (failures, total) = self
+            .samples
+            .iter()
+            .fold((0, 0), |(f, t), (failures, total)| {
+                (f + failures, t + total)
+            });
+        (total >= MIN_VOLUME).then(|| failures as f64 / total as f64)
+    }
+}
+
+/// A rate that alerts when it reaches `threshold` and again when it drops back under it.
+#[derive(Debug)]
+struct Alarm {
+    name: &'static str,
+    threshold: f64,
+    window: SlidingWindow,
+    firing: bool,
+}
+
+impl Alarm {
+    fn new(name: &'static str, threshold: f64) -> Self {
+        Self {
+            name,
+            threshold,
+            window: SlidingWindow::default(),
+            firing: false,
+        }
+    }
+
+    /// Add a sample, returning the alert to post if the alarm fired or resolved.
+    fn observe(&mut self, failures: u64, total: u64) -> Option<Value> {
+        self.window.push(failures, total);
+        let rate = self.window.rate().unwrap_or_default();
+        let firing = rate >= self.threshold && self.threshold > 0.0;
+        if firing == self.firing {
+            return None;
+        }
+        self.firing = firing;
+
+        let minutes =
+            (SAMPLE_INTERVAL * u32::try_from(WINDOW_SAMPLES).unwrap_or(u32::MAX)).as_secs() / 60;
+        let text = if firing {
+            format!(
+                ":rotating_light: AirCade API: {} is {:.1}% over the last {minutes} min (threshold {:.1}%)",
+                self.name,
+                rate * 100.0,
+                self.threshold * 100.0,
+            )
+        } else {
+            format!(
+                ":white_check_mark: AirCade API: {} is back to {:.1}% (threshold {:.1}%)",
+                self.name,
+                rate * 100.0,
+                self.threshold * 100.0,
+            )
+        };
+        Some(json!({
+            "text": text,
+            "content": text,
+            "alert": {
+                "name": self.name,
+                "status": if firing { "firing" } else { "resolved" },
+                "rate": rate,
+                "threshold": self.threshold,
+            },
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rates_need_enough_volume() {
+        let mut window = SlidingWindow::default();
+        window.push(5, 10);
+        assert_eq!(window.rate(), None);
+        window.push(0, 10);
+        assert_eq!(window.rate(), Some(0.25));
+    }
+
+    #[test]
+    fn old_samples_leave_the_window() {
+        let mut window = SlidingWindow::default();
+        window.push(100, 100);
+        for _ in 0..WINDOW_SAMPLES {
+            window.push(0, 100);
+        }
+        assert_eq!(window.rate(), Some(0.0));
+    }
+
+    #[test]
+    fn alarms_fire_once_and_resolve_once() {
+        let mut alarm = Alarm::new("HTTP 5xx rate", 0.1);
+        assert!(alarm.observe(1, 100).is_none());
+
+        let fired = alarm.observe(30, 100);
+        assert_eq!(
+            fired.as_ref().map(|alert| &alert["alert"]["status"]),
+            Some(&json!("firing"))
+        );
+        assert_eq!(
+            fired.as_ref().map(|alert| &alert["text"]),
+            fired.as_ref().map(|alert| &alert["content"])
+        );
+        assert!(alarm.observe(30, 100).is_none());
+
+        let resolved = (0..WINDOW_SAMPLES).find_map(|_| alarm.observe(0, 100));
+        assert_eq!(
+            resolved.map(|alert| alert["alert"]["status"].clone()),
+            Some(json!("resolved"))
+        );
+    }
+}
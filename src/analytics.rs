@@ -0,0 +1,150 @@
+//! Product analytics events reported by clients.
+//!
+//! `POST /api/v1/analytics/events` validates each batch and hands it to the
+//! [`AnalyticsWriter`], which buffers events in memory and inserts them in bulk from a
+//! background task, so reporting never waits on the database. Analytics are best-effort:
+//! events are dropped, with a warning, when the buffer is full or an insert fails.
+//!
+//! Raw events are rolled up into the daily game and platform stats and pruned after
+//! [`RETENTION_DAYS`].
+
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use sea_orm::{DatabaseConnection, EntityTrait, IntoActiveModel};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::entities::analytics_event;
+
+/// `analytics_event.name` of a player or creator opening a game.
+pub const GAME_OPENED: &str = "game_opened";
+
+/// `analytics_event.name` of a controller's measured round-trip latency in a session.
+pub const CONTROLLER_LATENCY: &str = "controller_latency";
+
+/// `analytics_event.name` of an action taken in the game editor.
+pub const EDITOR_ACTION: &str = "editor_action";
+
+/// `analytics_event.name` of a subject being shown its variant of an A/B experiment. Logged
+/// by the server when it hands out assignments, so clients cannot report it.
+pub const EXPERIMENT_EXPOSURE: &str = "experiment_exposure";
+
+/// Every `analytics_event.name` clients may report.
+pub const EVENT_NAMES: [&str; 3] = [GAME_OPENED, CONTROLLER_LATENCY, EDITOR_ACTION];
+
+/// Longest `anonymousId` a client may identify itself by, in characters.
+pub const MAX_ANONYMOUS_ID_LENGTH: usize = 64;
+
+/// Days raw events are kept before the platform stats job prunes them.
+pub const RETENTION_DAYS: i64 = 90;
+
+/// Events buffered before new ones are dropped.
+const BUFFER_CAPACITY: usize = 10_000;
+
+/// Events written per insert.
+const BATCH_SIZE: usize = 500;
+
+/// Longest an event waits in the buffer before it is written.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
+enum Command {
+    Record(analytics_event::Model),
+    Flush(oneshot::Sender<()>),
+}
+
+/// Buffered writer of `analytics_event` rows, shared by every request handler.
+///
+/// The background task is started by the first call, so creating a writer needs neither a
+/// database connection nor a runtime.
+#[derive(Debug, Clone, Default)]
+pub struct AnalyticsWriter {
+    sender: Arc<OnceLock<mpsc::Sender<Command>>>,
+}
+
+impl AnalyticsWriter {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue events to be written. Returns how many were accepted; the rest were dropped
+    /// because the buffer is full.
+    pub fn record(&self, db: &DatabaseConnection, events: Vec<analytics_event::Model>) -> usize {
+        let sender = self.sender(db);
+        let total = events.len();
+        let mut accepted = 0;
+        for event in events {
+            if sender.try_send(Command::Record(event)).is_err() {
+                break;
+            }
+            accepted += 1;
+        }
+        if accepted < total {
+            tracing::warn!(
+                dropped = total - accepted,
+                "Analytics buffer is full; dropping events"
+            );
+        }
+        accepted
+    }
+
+    /// Wait until every event recorded so far has been written (or failed to be).
+    pub async fn flush(&self, db: &DatabaseConnection) {
+        let (done, written) = oneshot::channel();
+        if self.sender(db).send(Command::Flush(done)).await.is_ok() {
+            let _ = written.await;
+        }
+    }
+
+    fn sender(&self, db: &DatabaseConnection) -> &mpsc::Sender<Command> {
+        self.sender.get_or_init(|| {
+            let (sender, receiver) = mpsc::channel(BUFFER_CAPACITY);
+            tokio::spawn(run(db.clone(), receiver));
+            sender
+        })
+    }
+}
+
+/// Collect recorded events and write them whenever a batch fills up, the flush interval
+/// passes, or a flush is requested.
+async fn run(db: DatabaseConnection, mut receiver: mpsc::Receiver<Command>) {
+    let mut buffer = Vec::with_capacity(BATCH_SIZE);
+    let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+    loop {
+        tokio::select! {
+            command = receiver.recv() => match command {
+                Some(Command::Record(event)) => {
+                    buffer.push(event);
+                    if buffer.len() >= BATCH_SIZE {
+                        write(&db, &mut buffer).await;
+                    }
+                }
+                Some(Command::Flush(done)) => {
+                    write(&db, &mut buffer).await;
+                    let _ = done.send(());
+                }
+                None => {
+                    write(&db, &mut buffer).await;
+                    return;
+                }
+            },
+            _ = interval.tick() => write(&db, &mut buffer).await,
+        }
+    }
+}
+
+/// Insert the buffered events and empty the buffer.
+async fn write(db: &DatabaseConnection, buffer: &mut Vec<analytics_event::Model>) {
+    if buffer.is_empty() {
+        return;
+    }
+    let count = buffer.len();
+    let rows = buffer.drain(..).map(IntoActiveModel::into_active_model);
+    match analytics_event::Entity::insert_many(rows)
+        .exec_without_returning(db)
+        .await
+    {
+        Ok(_) => tracing::debug!(count, "Analytics events written"),
+        Err(e) => tracing::warn!(error = %e, count, "Failed to write analytics events"),
+    }
+}
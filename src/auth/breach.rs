@@ -0,0 +1,97 @@
+use std::collections::HashSet;
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use sha1::{Digest, Sha1};
+
+/// How long a fetched hash range is reused.
+const CACHE_TTL: Duration = Duration::from_hours(1);
+
+/// Ranges kept before the cache is cleared (each holds a few hundred hash suffixes).
+const MAX_CACHED_RANGES: usize = 256;
+
+/// Timeout for the range request; a slow breach API must not stall signups.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(3);
+
+struct CachedRange {
+    suffixes: HashSet<String>,
+    fetched_at: Instant,
+}
+
+/// Hash suffixes by API base URL and 5-character prefix.
+static RANGE_CACHE: LazyLock<DashMap<(String, String), CachedRange>> = LazyLock::new(DashMap::new);
+
+/// Whether `password` appears in the Have I Been Pwned breach corpus.
+///
+/// Uses the k-anonymity range API: only the first 5 hex characters of the password's SHA-1
+/// leave the server. Responses are cached per prefix for [`CACHE_TTL`].
+///
+/// # Errors
+///
+/// Returns an error if the range request fails.
+pub async fn is_breached(api_url: &str, password: &str) -> anyhow::Result<bool> {
+    let hash = hex::encode_upper(Sha1::digest(password.as_bytes()));
+    let (prefix, suffix) = hash.split_at(5);
+    let key = (api_url.to_string(), prefix.to_string());
+
+    if let Some(cached) = RANGE_CACHE.get(&key)
+        && cached.fetched_at.elapsed() < CACHE_TTL
+    {
+        return Ok(cached.suffixes.contains(suffix));
+    }
+
+    let body = reqwest::Client::new()
+        .get(format!("{}/range/{prefix}", api_url.trim_end_matches('/')))
+        // Pads the response with fake entries so its size does not hint at the prefix
+        .header("Add-Padding", "true")
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| anyhow::anyhow!("Breached password range request failed: {e}"))?
+        .text()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to read breached password range: {e}"))?;
+
+    let suffixes = parse_range(&body);
+    let breached = suffixes.contains(suffix);
+
+    if RANGE_CACHE.len() >= MAX_CACHED_RANGES {
+        RANGE_CACHE.clear();
+    }
+    RANGE_CACHE.insert(
+        key,
+        CachedRange {
+            suffixes,
+            fetched_at: Instant::now(),
+        },
+    );
+
+    Ok(breached)
+}
+
+/// Parse a range response (`SUFFIX:COUNT` per line), skipping padding entries (count 0).
+fn parse_range(body: &str) -> HashSet<String> {
+    body.lines()
+        .filter_map(|line| line.trim().split_once(':'))
+        .filter(|(_, count)| count.trim().parse::<u64>().is_ok_and(|count| count > 0))
+        .map(|(suffix, _)| suffix.to_ascii_uppercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_range_skips_padding() {
+        let body = "0018A45C4D1DEF81644B54AB7F969B88D65:3\r\n\
+                    00D4F6E8FA6EECAD2A3AA415EEC418D38EC:0\r\n\
+                    011053FD0102E94D6AE2F8B83D76FAF94F6:1\r\n";
+        let suffixes = parse_range(body);
+        assert_eq!(suffixes.len(), 2);
+        assert!(suffixes.contains("0018A45C4D1DEF81644B54AB7F969B88D65"));
+        assert!(!suffixes.contains("00D4F6E8FA6EECAD2A3AA415EEC418D38EC"));
+    }
+}
@@ -0,0 +1,114 @@
+use axum::http::{HeaderMap, HeaderValue, header};
+use data_encoding::BASE64URL_NOPAD;
+use rand::RngCore;
+
+use crate::config::Config;
+
+/// Cookie carrying the access token; sent with every API request.
+pub const ACCESS_COOKIE: &str = "aircade_access";
+
+/// Cookie carrying the refresh token; only sent to the auth routes that rotate or revoke it.
+pub const REFRESH_COOKIE: &str = "aircade_refresh";
+
+/// Cookie carrying the CSRF token. Unlike the token cookies it is readable by scripts, which
+/// must echo it in [`CSRF_HEADER`].
+pub const CSRF_COOKIE: &str = "aircade_csrf";
+
+/// Header a request authenticated by cookie must repeat the CSRF cookie in when it mutates.
+pub const CSRF_HEADER: &str = "x-csrf-token";
+
+const ACCESS_PATH: &str = "/api/v1";
+const REFRESH_PATH: &str = "/api/v1/auth";
+
+/// Random bytes in a CSRF token.
+const CSRF_TOKEN_BYTES: usize = 32;
+
+/// The value of cookie `name` in the request's `Cookie` headers.
+#[must_use]
+pub fn get<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+/// A fresh CSRF token for [`set_tokens`].
+#[must_use]
+pub fn generate_csrf_token() -> String {
+    let mut csrf = [0u8; CSRF_TOKEN_BYTES];
+    rand::thread_rng().fill_bytes(&mut csrf);
+    BASE64URL_NOPAD.encode(&csrf)
+}
+
+/// Add `Set-Cookie` headers delivering a token pair and `csrf_token`.
+///
+/// The CSRF cookie is host-only on the API's origin, so a frontend on another origin cannot read
+/// it; the response body should carry `csrf_token` as well.
+pub fn set_tokens(
+    headers: &mut HeaderMap,
+    config: &Config,
+    access_token: &str,
+    refresh_token: &str,
+    csrf_token: &str,
+) {
+    for cookie in [
+        format!(
+            "{ACCESS_COOKIE}={access_token}; Path={ACCESS_PATH}; Max-Age={}; HttpOnly; Secure; \
+             SameSite=Lax",
+            config.jwt_access_expiration_secs
+        ),
+        format!(
+            "{REFRESH_COOKIE}={refresh_token}; Path={REFRESH_PATH}; Max-Age={}; HttpOnly; \
+             Secure; SameSite=Lax",
+            config.jwt_refresh_expiration_secs
+        ),
+        format!(
+            "{CSRF_COOKIE}={csrf_token}; Path=/; Max-Age={}; Secure; SameSite=Lax",
+            config.jwt_refresh_expiration_secs
+        ),
+    ] {
+        append(headers, &cookie);
+    }
+}
+
+/// Add `Set-Cookie` headers expiring every auth cookie.
+pub fn clear_tokens(headers: &mut HeaderMap) {
+    for (name, path) in [
+        (ACCESS_COOKIE, ACCESS_PATH),
+        (REFRESH_COOKIE, REFRESH_PATH),
+        (CSRF_COOKIE, "/"),
+    ] {
+        append(
+            headers,
+            &format!("{name}=; Path={path}; Max-Age=0; HttpOnly; Secure; SameSite=Lax"),
+        );
+    }
+}
+
+fn append(headers: &mut HeaderMap, cookie: &str) {
+    if let Ok(value) = HeaderValue::from_str(cookie) {
+        headers.append(header::SET_COOKIE, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_cookies_across_headers() {
+        let mut headers = HeaderMap::new();
+        headers.append(header::COOKIE, HeaderValue::from_static("theme=dark; a=1"));
+        headers.append(
+            header::COOKIE,
+            HeaderValue::from_static("aircade_access=abc.def; b=2"),
+        );
+        assert_eq!(get(&headers, ACCESS_COOKIE), Some("abc.def"));
+        assert_eq!(get(&headers, "a"), Some("1"));
+        assert_eq!(get(&headers, REFRESH_COOKIE), None);
+    }
+}
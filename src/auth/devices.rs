@@ -0,0 +1,223 @@
+use std::net::IpAddr;
+
+use chrono::Utc;
+use sea_orm::ActiveValue::Set;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::auth;
+use crate::config::Config;
+use crate::email;
+use crate::entities::{known_device, refresh_token};
+use crate::notifications::{self, NewNotification, NotificationHub};
+
+/// `notification.kind` of the alert sent when an account signs in from an unfamiliar device.
+pub const NEW_DEVICE_NOTIFICATION: &str = "new_device_signin";
+
+/// How long the "this wasn't me" link of a sign-in alert keeps working.
+const REVOKE_TOKEN_TTL_DAYS: i64 = 7;
+
+/// Network a client address belongs to, coarse enough that a device keeps its fingerprint
+/// when its address changes within the same network: `/24` for IPv4, `/48` for IPv6.
+#[must_use]
+pub fn ip_prefix(ip: &str) -> Option<String> {
+    match ip.parse::<IpAddr>().ok()? {
+        IpAddr::V4(v4) => {
+            let [a, b, c, _] = v4.octets();
+            Some(format!("{a}.{b}.{c}.0/24"))
+        }
+        IpAddr::V6(v6) => {
+            let [a, b, c, ..] = v6.segments();
+            Some(format!("{a:x}:{b:x}:{c:x}::/48"))
+        }
+    }
+}
+
+/// Fingerprint of a client: the SHA-256 of its `User-Agent` and IP prefix.
+#[must_use]
+pub fn fingerprint(user_agent: Option<&str>, ip_prefix: Option<&str>) -> String {
+    let material = format!("{}\n{}", user_agent.unwrap_or(""), ip_prefix.unwrap_or(""));
+    hex::encode(Sha256::digest(material.as_bytes()))
+}
+
+/// Remember the device behind a new sign-in, alerting the user if it is unfamiliar.
+///
+/// The first device an account signs in from is recorded silently. After that, a sign-in
+/// whose fingerprint the account has not seen before creates an in-app notification and,
+/// unless the user turned security alerts off, an email, both carrying a "this wasn't me"
+/// link that revokes `refresh_token_id`.
+///
+/// # Errors
+///
+/// Returns an error if a database query fails.
+pub async fn record_signin(
+    db: &DatabaseConnection,
+    config: &Config,
+    hub: &NotificationHub,
+    user_id: Uuid,
+    user_agent: Option<&str>,
+    ip: Option<&str>,
+    refresh_token_id: Uuid,
+) -> anyhow::Result<()> {
+    let now = Utc::now().fixed_offset();
+    let prefix = ip.and_then(ip_prefix);
+    let fingerprint = fingerprint(user_agent, prefix.as_deref());
+
+    let existing = known_device::Entity::find()
+        .filter(known_device::Column::UserId.eq(user_id))
+        .filter(known_device::Column::Fingerprint.eq(&fingerprint))
+        .one(db)
+        .await?;
+    if let Some(device) = existing {
+        let mut active: known_device::ActiveModel = device.into();
+        active.last_seen_at = Set(now);
+        active.update(db).await?;
+        return Ok(());
+    }
+
+    let first_device = known_device::Entity::find()
+        .filter(known_device::Column::UserId.eq(user_id))
+        .count(db)
+        .await?
+        == 0;
+    let revoke_token = (!first_device).then(auth::generate_verification_token);
+
+    known_device::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id),
+        fingerprint: Set(fingerprint),
+        user_agent: Set(user_agent.map(str::to_string)),
+        ip_prefix: Set(prefix.clone()),
+        refresh_token_id: Set(Some(refresh_token_id)),
+        revoke_token: Set(revoke_token.as_deref().map(auth::hash_verification_token)),
+        revoke_token_expires_at: Set(revoke_token
+            .is_some()
+            .then(|| now + chrono::Duration::days(REVOKE_TOKEN_TTL_DAYS))),
+        first_seen_at: Set(now),
+        last_seen_at: Set(now),
+    }
+    .insert(db)
+    .await?;
+
+    let Some(revoke_token) = revoke_token else {
+        return Ok(());
+    };
+
+    let device = user_agent.unwrap_or("an unknown device");
+    let network = prefix.as_deref().unwrap_or("an unknown network");
+    let link = format!(
+        "{}/account/not-me?token={revoke_token}",
+        config.frontend_url.trim_end_matches('/')
+    );
+    notifications::send(
+        db,
+        hub,
+        user_id,
+        NewNotification {
+            kind: NEW_DEVICE_NOTIFICATION,
+            title: "New sign-in to your account".to_string(),
+            body: format!(
+                "Your account was signed in to from {device} on {network}. \
+                 If this wasn't you, use the link to sign that device out and change your \
+                 password."
+            ),
+            link: Some(link.clone()),
+        },
+    )
+    .await?;
+
+    email::send_to_user(
+        db,
+        user_id,
+        email::Category::SecurityAlerts,
+        "New sign-in to your account",
+        &format!(
+            "Your account was signed in to from {device} on {network}. If this wasn't you, \
+             sign that device out and change your password: {link}"
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Act on a "this wasn't me" link: sign out the session the alerted sign-in started and
+/// forget the device, so signing in from it alerts again.
+///
+/// Returns `false` if the token does not match an alert or its link has expired.
+///
+/// # Errors
+///
+/// Returns an error if a database query fails.
+pub async fn revoke_unrecognized(
+    db: &DatabaseConnection,
+    revoke_token: &str,
+) -> anyhow::Result<bool> {
+    let Some(device) = known_device::Entity::find()
+        .filter(known_device::Column::RevokeToken.eq(auth::hash_verification_token(revoke_token)))
+        .filter(known_device::Column::RevokeTokenExpiresAt.gt(Utc::now().fixed_offset()))
+        .one(db)
+        .await?
+    else {
+        return Ok(false);
+    };
+
+    // Refresh tokens rotate, but every token of a session shares its `signed_in_at`
+    if let Some(token_id) = device.refresh_token_id
+        && let Some(token) = refresh_token::Entity::find_by_id(token_id).one(db).await?
+    {
+        let signed_in_at = token.signed_in_at.unwrap_or(token.created_at);
+        refresh_token::Entity::update_many()
+            .col_expr(
+                refresh_token::Column::RevokedAt,
+                Expr::value(Utc::now().fixed_offset()),
+            )
+            .filter(refresh_token::Column::UserId.eq(device.user_id))
+            .filter(refresh_token::Column::SignedInAt.eq(signed_in_at))
+            .filter(refresh_token::Column::RevokedAt.is_null())
+            .exec(db)
+            .await?;
+    }
+
+    known_device::Entity::delete_by_id(device.id)
+        .exec(db)
+        .await?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ipv4_prefix_is_slash_24() {
+        assert_eq!(ip_prefix("203.0.113.77").as_deref(), Some("203.0.113.0/24"));
+    }
+
+    #[test]
+    fn ipv6_prefix_is_slash_48() {
+        assert_eq!(
+            ip_prefix("2001:db8:abcd:12::1").as_deref(),
+            Some("2001:db8:abcd::/48")
+        );
+    }
+
+    #[test]
+    fn unparseable_ip_has_no_prefix() {
+        assert_eq!(ip_prefix("not-an-ip"), None);
+    }
+
+    #[test]
+    fn fingerprint_ignores_host_part_of_address() {
+        let a = fingerprint(Some("Firefox"), ip_prefix("198.51.100.1").as_deref());
+        let b = fingerprint(Some("Firefox"), ip_prefix("198.51.100.200").as_deref());
+        let c = fingerprint(Some("Chrome"), ip_prefix("198.51.100.1").as_deref());
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+}
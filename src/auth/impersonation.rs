@@ -0,0 +1,148 @@
+use axum::http::{HeaderMap, Method};
+use chrono::Utc;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, DbErr};
+use uuid::Uuid;
+
+use crate::auth::extract_client_ip;
+use crate::entities::impersonation_log;
+
+/// Route prefixes (below `/api/v1`) an impersonating admin may read.
+///
+/// Impersonation is for seeing and reproducing what a user sees, so only reads are allowed,
+/// and only of routes that show what the user sees in the app.
+const READABLE: &[&str] = &[
+    "/announcements",
+    "/auth/appeal",
+    "/auth/password-policy",
+    "/avatars",
+    "/classrooms",
+    "/collections",
+    "/experiments",
+    "/games",
+    "/oembed",
+    "/organizations",
+    "/search",
+    "/sessions",
+    "/status",
+    "/tags",
+    "/templates",
+    "/users",
+];
+
+/// Reads under [`READABLE`] that are still refused: exporting creates an archive of personal
+/// data, and the others list the user's credentials.
+const UNREADABLE: &[&str] = &["/users/me/export", "/users/me/tokens", "/users/me/sessions"];
+
+/// Whether an impersonating admin is refused `method` on `path` (the full `/api/v1/...` path).
+///
+/// Everything is refused except reads of [`READABLE`] routes, so routes added later stay off
+/// limits until they are allowed here.
+#[must_use]
+pub fn is_refused(method: &Method, path: &str) -> bool {
+    if !matches!(*method, Method::GET | Method::HEAD) {
+        return true;
+    }
+    let path = path
+        .strip_prefix("/api/v1")
+        .unwrap_or(path)
+        .trim_end_matches('/');
+    let under = |prefix: &&str| {
+        path == *prefix
+            || path
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.starts_with('/'))
+    };
+
+    !READABLE.iter().any(under) || UNREADABLE.iter().any(under)
+}
+
+/// A request made by `admin_id` while acting as `user_id`.
+pub struct Entry<'a> {
+    pub admin_id: Uuid,
+    pub user_id: Uuid,
+    pub method: &'a Method,
+    pub path: &'a str,
+    pub headers: &'a HeaderMap,
+}
+
+impl Entry<'_> {
+    /// Write the request to `impersonation_log` with its `outcome` (see
+    /// [`impersonation_log::Model::outcome`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the insert fails; the request must not go ahead unlogged.
+    pub async fn record(
+        &self,
+        db: &DatabaseConnection,
+        outcome: &str,
+        reason: Option<&str>,
+    ) -> Result<(), DbErr> {
+        impersonation_log::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            admin_id: Set(self.admin_id),
+            user_id: Set(self.user_id),
+            method: Set(self.method.to_string()),
+            path: Set(self.path.to_string()),
+            outcome: Set(outcome.to_string()),
+            reason: Set(reason.map(str::to_string)),
+            ip_address: Set(extract_client_ip(self.headers)),
+            created_at: Set(Utc::now().fixed_offset()),
+        }
+        .insert(db)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deletes_are_always_refused() {
+        assert!(is_refused(&Method::DELETE, "/api/v1/games/abc"));
+        assert!(is_refused(&Method::DELETE, "/api/v1/users/me/avatar"));
+    }
+
+    #[test]
+    fn writes_are_refused() {
+        assert!(is_refused(&Method::POST, "/api/v1/auth/password/change"));
+        assert!(is_refused(&Method::POST, "/api/v1/users/me/delete"));
+        assert!(is_refused(&Method::POST, "/api/v1/users/me/merge"));
+        assert!(is_refused(&Method::PATCH, "/api/v1/users/me/email"));
+        assert!(is_refused(&Method::POST, "/api/v1/users/me/tokens"));
+        assert!(is_refused(&Method::POST, "/api/v1/users/me/games/bulk"));
+        assert!(is_refused(&Method::PATCH, "/api/v1/games/abc"));
+        assert!(is_refused(&Method::POST, "/api/v1/games"));
+        assert!(is_refused(&Method::POST, "/api/v1/billing/checkout"));
+        assert!(is_refused(
+            &Method::POST,
+            "/api/v1/classrooms/abc/students/def/password"
+        ));
+    }
+
+    #[test]
+    fn credential_and_unlisted_reads_are_refused() {
+        assert!(is_refused(&Method::GET, "/api/v1/users/me/tokens"));
+        assert!(is_refused(&Method::GET, "/api/v1/users/me/export"));
+        assert!(is_refused(&Method::GET, "/api/v1/users/me/sessions"));
+        assert!(is_refused(
+            &Method::GET,
+            "/api/v1/auth/webauthn/credentials"
+        ));
+        assert!(is_refused(&Method::GET, "/api/v1/oauth/clients"));
+        assert!(is_refused(&Method::GET, "/api/v1/admin/stats"));
+        assert!(is_refused(&Method::GET, "/api/v1/usersnot"));
+    }
+
+    #[test]
+    fn ordinary_reads_are_allowed() {
+        assert!(!is_refused(&Method::GET, "/api/v1/users/me"));
+        assert!(!is_refused(&Method::GET, "/api/v1/users/me/games"));
+        assert!(!is_refused(&Method::HEAD, "/api/v1/games/abc"));
+        assert!(!is_refused(&Method::GET, "/api/v1/classrooms/abc"));
+        assert!(!is_refused(&Method::GET, "/api/v1/users/me/notifications"));
+    }
+}
@@ -0,0 +1,59 @@
+use axum::http::HeaderMap;
+use chrono::Utc;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, DatabaseConnection};
+use uuid::Uuid;
+
+use crate::auth::{extract_client_ip, extract_user_agent};
+use crate::entities::login_event;
+use crate::geo;
+
+/// Days sign-in events are kept before the purge job removes them.
+pub const RETENTION_DAYS: i64 = 90;
+
+/// A sign-in attempt, described once and recorded with whatever outcome it reaches.
+pub struct Attempt<'a> {
+    /// `"email"`, `"totp"`, `"webauthn"`, `"classroom"`, or an `OAuth` / OIDC provider name.
+    pub provider: &'a str,
+    /// What the client signed in with, when it names the account (e.g. an email address).
+    pub identifier: Option<&'a str>,
+    pub headers: &'a HeaderMap,
+}
+
+impl<'a> Attempt<'a> {
+    #[must_use]
+    pub const fn new(provider: &'a str, headers: &'a HeaderMap) -> Self {
+        Self {
+            provider,
+            identifier: None,
+            headers,
+        }
+    }
+
+    #[must_use]
+    pub const fn identified_by(mut self, identifier: &'a str) -> Self {
+        self.identifier = Some(identifier);
+        self
+    }
+
+    /// Record the attempt's `outcome` (see [`login_event::Model::outcome`]) for `user_id`.
+    ///
+    /// Best-effort: a failure to record is logged but never fails the sign-in itself.
+    pub async fn record(&self, db: &DatabaseConnection, user_id: Option<Uuid>, outcome: &str) {
+        let ip_address = extract_client_ip(self.headers);
+        let event = login_event::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            user_id: Set(user_id),
+            identifier: Set(self.identifier.map(str::to_string)),
+            provider: Set(self.provider.to_string()),
+            outcome: Set(outcome.to_string()),
+            country: Set(ip_address.as_deref().and_then(geo::country_of)),
+            ip_address: Set(ip_address),
+            user_agent: Set(extract_user_agent(self.headers)),
+            created_at: Set(Utc::now().fixed_offset()),
+        };
+        if let Err(e) = event.insert(db).await {
+            tracing::warn!(error = %e, outcome, "Failed to record sign-in event");
+        }
+    }
+}
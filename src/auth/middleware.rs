@@ -1,14 +1,22 @@
-use axum::extract::FromRequestParts;
+use axum::extract::{FromRequestParts, OriginalUri};
 use axum::http::request::Parts;
-use sea_orm::EntityTrait;
+use chrono::Utc;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 
-use crate::auth::jwt;
-use crate::entities::user;
+use crate::auth::{cookies, impersonation, jwt, personal_token};
+use crate::classroom;
+use crate::entities::{api_token, user};
 use crate::error::AppError;
+use crate::error_reporting;
 use crate::state::AppState;
 
 /// Authenticated user extracted from the `Authorization: Bearer <token>` header.
 ///
+/// The token is either a session access token or a personal access token (`acd_pat_...`);
+/// personal access tokens only reach endpoints covered by their scopes. When
+/// [`Config::auth_cookies`](crate::config::Config::auth_cookies) is on, a request without the
+/// header may carry the access token in its cookie instead.
+///
 /// Use as an extractor in handler parameters to require authentication:
 /// ```ignore
 /// async fn handler(AuthUser(user): AuthUser) -> impl IntoResponse { ... }
@@ -23,23 +31,38 @@ impl FromRequestParts<AppState> for AuthUser {
         parts: &mut Parts,
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
-        let header = parts
+        let missing = || AppError::Unauthorized("Missing authorization header.".to_string());
+        let token = match parts
             .headers
             .get("authorization")
             .and_then(|v| v.to_str().ok())
-            .ok_or_else(|| AppError::Unauthorized("Missing authorization header.".to_string()))?;
-
-        let token = header.strip_prefix("Bearer ").ok_or_else(|| {
-            AppError::Unauthorized("Invalid authorization header format.".to_string())
-        })?;
-
-        let claims = jwt::validate_access_token(token, &state.config.jwt_secret)
-            .map_err(|_| AppError::Unauthorized("Invalid or expired token.".to_string()))?;
-
-        let user_id: uuid::Uuid = claims
-            .sub
-            .parse()
-            .map_err(|_| AppError::Unauthorized("Invalid token subject.".to_string()))?;
+        {
+            Some(header) => header.strip_prefix("Bearer ").ok_or_else(|| {
+                AppError::Unauthorized("Invalid authorization header format.".to_string())
+            })?,
+            // Browsers in cookie mode send the access token as a cookie instead
+            None if state.config.auth_cookies => {
+                cookies::get(&parts.headers, cookies::ACCESS_COOKIE).ok_or_else(missing)?
+            }
+            None => return Err(missing()),
+        };
+
+        let user_id = if token.starts_with(personal_token::TOKEN_PREFIX) {
+            personal_token_user(parts, state, token).await?
+        } else {
+            let claims = jwt::validate_access_token(token, &state.jwt_keys)
+                .map_err(|_| AppError::Unauthorized("Invalid or expired token.".to_string()))?;
+
+            let user_id = claims
+                .sub
+                .parse()
+                .map_err(|_| AppError::Unauthorized("Invalid token subject.".to_string()))?;
+
+            if let Some(actor) = &claims.act {
+                check_impersonation(parts, state, actor, user_id).await?;
+            }
+            user_id
+        };
 
         let user_model = user::Entity::find_by_id(user_id)
             .one(&state.db)
@@ -47,13 +70,13 @@ impl FromRequestParts<AppState> for AuthUser {
             .map_err(|e| AppError::Internal(e.into()))?
             .ok_or_else(|| AppError::Unauthorized("User not found.".to_string()))?;
 
-        // Reject soft-deleted accounts
-        if user_model.deleted_at.is_some() {
+        // Reject soft-deleted and anonymized accounts
+        if user_model.deleted_at.is_some() || user_model.account_status == "deleted" {
             return Err(AppError::Unauthorized("User not found.".to_string()));
         }
 
-        // Reject suspended accounts
-        if user_model.account_status == "suspended" {
+        // Reject suspended accounts, except when appealing the suspension
+        if user_model.account_status == "suspended" && request_path(parts) != APPEAL_PATH {
             let reason = user_model
                 .suspension_reason
                 .as_deref()
@@ -68,10 +91,113 @@ impl FromRequestParts<AppState> for AuthUser {
             return Err(AppError::Forbidden("Account is deactivated.".to_string()));
         }
 
+        // Managed student accounts only reach the endpoints their class uses
+        if user_model.role == classroom::STUDENT_ROLE
+            && !classroom::is_allowed(&parts.method, request_path(parts))
+        {
+            return Err(AppError::Forbidden(
+                "Student accounts cannot use this endpoint.".to_string(),
+            ));
+        }
+
+        error_reporting::set_user(user_model.id);
         Ok(Self(user_model))
     }
 }
 
+/// Log a request made with an impersonation token and refuse it unless it is an allowed read
+/// (see [`impersonation::is_refused`]) and the impersonating admin still has the admin role.
+async fn check_impersonation(
+    parts: &Parts,
+    state: &AppState,
+    actor: &jwt::Actor,
+    user_id: uuid::Uuid,
+) -> Result<(), AppError> {
+    let admin_id: uuid::Uuid = actor
+        .sub
+        .parse()
+        .map_err(|_| AppError::Unauthorized("Invalid token actor.".to_string()))?;
+    let admin_active = user::Entity::find_by_id(admin_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?
+        .is_some_and(|admin| admin.role == "admin" && admin.deleted_at.is_none());
+    if !admin_active {
+        return Err(AppError::Unauthorized(
+            "Invalid or expired token.".to_string(),
+        ));
+    }
+
+    let path = request_path(parts);
+    let refused = impersonation::is_refused(&parts.method, path);
+    impersonation::Entry {
+        admin_id,
+        user_id,
+        method: &parts.method,
+        path,
+        headers: &parts.headers,
+    }
+    .record(&state.db, if refused { "refused" } else { "allowed" }, None)
+    .await
+    .map_err(|e| AppError::Internal(e.into()))?;
+
+    if refused {
+        return Err(AppError::Forbidden(
+            "This action is not available while impersonating a user.".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The one endpoint suspended accounts may still use, to appeal their suspension.
+const APPEAL_PATH: &str = "/api/v1/auth/appeal";
+
+/// The full request path; nested routers only see the part below their prefix.
+fn request_path(parts: &Parts) -> &str {
+    parts
+        .extensions
+        .get::<OriginalUri>()
+        .map_or_else(|| parts.uri.path(), |uri| uri.path())
+}
+
+/// Resolve a personal access token to its owner, checking it covers this request.
+async fn personal_token_user(
+    parts: &Parts,
+    state: &AppState,
+    token: &str,
+) -> Result<uuid::Uuid, AppError> {
+    let now = Utc::now().fixed_offset();
+    let record = api_token::Entity::find()
+        .filter(api_token::Column::TokenHash.eq(personal_token::hash(token)))
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?
+        .filter(|t| t.expires_at.is_none_or(|expires_at| expires_at > now))
+        .ok_or_else(|| AppError::Unauthorized("Invalid or expired token.".to_string()))?;
+
+    let path = request_path(parts);
+    let scope = personal_token::required_scope(&parts.method, path).ok_or_else(|| {
+        AppError::Forbidden("Personal access tokens cannot be used for this endpoint.".to_string())
+    })?;
+    if !personal_token::has_scope(&record.scopes, scope) {
+        return Err(AppError::Forbidden(format!(
+            "Token is missing the {scope} scope."
+        )));
+    }
+
+    api_token::Entity::update_many()
+        .col_expr(
+            api_token::Column::LastUsedAt,
+            sea_orm::sea_query::Expr::value(now),
+        )
+        .filter(api_token::Column::Id.eq(record.id))
+        .exec(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    Ok(record.user_id)
+}
+
 /// Requires the authenticated user to have at least `"moderator"` or `"admin"` role.
 #[derive(Debug, Clone)]
 pub struct ModeratorUser(pub user::Model);
@@ -1,14 +1,28 @@
+pub mod breach;
+pub mod cookies;
+pub mod devices;
+pub mod impersonation;
 pub mod jwt;
+pub mod login_events;
 pub mod middleware;
 pub mod oauth;
+pub mod oauth_server;
+pub mod oidc;
 pub mod password;
+pub mod personal_token;
+pub mod totp;
+pub mod webauthn;
 
 use axum::http::HeaderMap;
+use data_encoding::BASE64URL_NOPAD;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
 
 /// Extract the client IP address from request headers.
 ///
 /// Checks `X-Forwarded-For` first (for reverse proxies like Railway),
-/// then falls back to `X-Real-IP`.
+/// then falls back to `X-Real-IP`. Both are only trustworthy once
+/// [`crate::middleware::proxy::resolve`] has rewritten them.
 #[must_use]
 pub fn extract_client_ip(headers: &HeaderMap) -> Option<String> {
     headers
@@ -23,3 +37,35 @@ pub fn extract_client_ip(headers: &HeaderMap) -> Option<String> {
                 .map(std::string::ToString::to_string)
         })
 }
+
+/// Longest `User-Agent` kept by [`extract_user_agent`]; anything longer is truncated.
+const MAX_USER_AGENT_LEN: usize = 512;
+
+/// Extract the client's `User-Agent`, truncated to a length that is safe to store.
+#[must_use]
+pub fn extract_user_agent(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(axum::http::header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(|ua| ua.chars().take(MAX_USER_AGENT_LEN).collect())
+}
+
+/// Random bytes in a verification/reset token.
+const VERIFICATION_TOKEN_BYTES: usize = 32;
+
+/// Generate a random verification/reset token.
+#[must_use]
+pub fn generate_verification_token() -> String {
+    let mut bytes = [0u8; VERIFICATION_TOKEN_BYTES];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    BASE64URL_NOPAD.encode(&bytes)
+}
+
+/// Hex-encoded SHA-256 of a verification/reset token.
+///
+/// Stored in place of the token in `auth_provider.verification_token`, the `email_change`
+/// tokens and `known_device.revoke_token`, so a database leak does not hand out working links.
+#[must_use]
+pub fn hash_verification_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
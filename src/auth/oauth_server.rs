@@ -0,0 +1,87 @@
+use data_encoding::BASE64URL_NOPAD;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+/// Random bytes in client secrets and authorization codes.
+const SECRET_BYTES: usize = 32;
+
+/// Prefix of client secrets, so they are easy to spot in leaked logs.
+const CLIENT_SECRET_PREFIX: &str = "acd_cs_";
+
+/// Generate a client secret.
+#[must_use]
+pub fn generate_client_secret() -> String {
+    format!("{CLIENT_SECRET_PREFIX}{}", random_string())
+}
+
+/// Generate an authorization code.
+#[must_use]
+pub fn generate_code() -> String {
+    random_string()
+}
+
+fn random_string() -> String {
+    let mut bytes = [0u8; SECRET_BYTES];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    BASE64URL_NOPAD.encode(&bytes)
+}
+
+/// Check a PKCE `code_verifier` against the `S256` `code_challenge` sent to `/authorize`.
+#[must_use]
+pub fn verify_pkce(verifier: &str, challenge: &str) -> bool {
+    // RFC 7636: 43-128 characters
+    (43..=128).contains(&verifier.len())
+        && BASE64URL_NOPAD.encode(&Sha256::digest(verifier.as_bytes())) == challenge
+}
+
+/// Check a redirect URI a client wants to register.
+///
+/// It must be an absolute `https` URL without a fragment; plain `http` is only allowed for
+/// loopback addresses, for tools that listen locally.
+///
+/// # Errors
+///
+/// Returns a description of what is wrong with the URI.
+pub fn validate_redirect_uri(uri: &str) -> Result<(), String> {
+    let url = reqwest::Url::parse(uri).map_err(|_| format!("'{uri}' is not a valid URL."))?;
+    if url.fragment().is_some() {
+        return Err(format!("'{uri}' must not contain a fragment."));
+    }
+    let loopback = matches!(url.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+    match url.scheme() {
+        "https" => Ok(()),
+        "http" if loopback => Ok(()),
+        _ => Err(format!(
+            "'{uri}' must use https (http is only allowed for localhost)."
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pkce_s256_matches_rfc_7636_example() {
+        // Appendix B of RFC 7636
+        let verifier = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+        let challenge = "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM";
+        assert!(verify_pkce(verifier, challenge));
+        assert!(!verify_pkce(
+            "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXx",
+            challenge
+        ));
+        assert!(!verify_pkce("short", challenge));
+    }
+
+    #[test]
+    fn redirect_uris_must_be_https_or_loopback() {
+        assert!(validate_redirect_uri("https://tool.example.com/callback").is_ok());
+        assert!(validate_redirect_uri("http://127.0.0.1:8765/callback").is_ok());
+        assert!(validate_redirect_uri("http://localhost/cb").is_ok());
+        assert!(validate_redirect_uri("http://tool.example.com/callback").is_err());
+        assert!(validate_redirect_uri("https://tool.example.com/cb#frag").is_err());
+        assert!(validate_redirect_uri("javascript:alert(1)").is_err());
+        assert!(validate_redirect_uri("/relative").is_err());
+    }
+}
@@ -0,0 +1,275 @@
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use jsonwebtoken::jwk::JwkSet;
+use serde::Deserialize;
+
+use crate::auth::oauth;
+use crate::config::{Config, OidcProviderConfig};
+
+/// How long a provider's discovery document and signing keys are reused.
+const CACHE_TTL: Duration = Duration::from_hours(1);
+
+struct Cached<T> {
+    value: T,
+    fetched_at: Instant,
+}
+
+impl<T: Clone> Cached<T> {
+    fn fresh(&self) -> Option<T> {
+        (self.fetched_at.elapsed() < CACHE_TTL).then(|| self.value.clone())
+    }
+}
+
+/// Discovery documents by issuer.
+static DOCUMENTS: LazyLock<DashMap<String, Cached<DiscoveryDocument>>> =
+    LazyLock::new(DashMap::new);
+
+/// Signing keys by JWKS URL.
+static SIGNING_KEYS: LazyLock<DashMap<String, Cached<JwkSet>>> = LazyLock::new(DashMap::new);
+
+/// The parts of a provider's discovery document needed to sign in.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DiscoveryDocument {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub userinfo_endpoint: Option<String>,
+    pub jwks_uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    id_token: String,
+}
+
+/// Standard claims read from the ID token (and the userinfo endpoint, when the token lacks an
+/// email).
+#[derive(Debug, Deserialize)]
+pub struct OidcClaims {
+    pub sub: String,
+    pub email: Option<String>,
+    pub email_verified: Option<bool>,
+    pub name: Option<String>,
+    pub preferred_username: Option<String>,
+    pub picture: Option<String>,
+    /// The `nonce` of the authorization request; only in the ID token.
+    #[serde(default)]
+    pub nonce: Option<String>,
+}
+
+/// Find a configured provider by name.
+#[must_use]
+pub fn find_provider<'a>(config: &'a Config, name: &str) -> Option<&'a OidcProviderConfig> {
+    config.oidc_providers.iter().find(|p| p.name == name)
+}
+
+/// Fetch the provider's discovery document from `{issuer}/.well-known/openid-configuration`,
+/// reusing it for [`CACHE_TTL`].
+///
+/// # Errors
+///
+/// Returns an error if the request fails, the document is malformed, or it names a different
+/// issuer.
+pub async fn discover(provider: &OidcProviderConfig) -> anyhow::Result<DiscoveryDocument> {
+    if let Some(document) = DOCUMENTS
+        .get(&provider.issuer)
+        .and_then(|cached| cached.fresh())
+    {
+        return Ok(document);
+    }
+
+    let document: DiscoveryDocument = reqwest::Client::new()
+        .get(format!(
+            "{}/.well-known/openid-configuration",
+            provider.issuer
+        ))
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| anyhow::anyhow!("Failed to fetch {} discovery document: {e}", provider.name))?
+        .json()
+        .await
+        .map_err(|e| {
+            anyhow::anyhow!("Failed to parse {} discovery document: {e}", provider.name)
+        })?;
+
+    if document.issuer.trim_end_matches('/') != provider.issuer {
+        return Err(anyhow::anyhow!(
+            "{} discovery document is for issuer {}",
+            provider.name,
+            document.issuer
+        ));
+    }
+
+    DOCUMENTS.insert(
+        provider.issuer.clone(),
+        Cached {
+            value: document.clone(),
+            fetched_at: Instant::now(),
+        },
+    );
+    Ok(document)
+}
+
+/// Fetch the provider's signing keys, reusing them for [`CACHE_TTL`] unless `refresh` is set.
+async fn signing_keys(
+    client: &reqwest::Client,
+    provider: &OidcProviderConfig,
+    jwks_uri: &str,
+    refresh: bool,
+) -> anyhow::Result<JwkSet> {
+    if !refresh && let Some(keys) = SIGNING_KEYS.get(jwks_uri).and_then(|cached| cached.fresh()) {
+        return Ok(keys);
+    }
+
+    let keys: JwkSet = client
+        .get(jwks_uri)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| anyhow::anyhow!("Failed to fetch {} signing keys: {e}", provider.name))?
+        .json()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to parse {} signing keys: {e}", provider.name))?;
+
+    SIGNING_KEYS.insert(
+        jwks_uri.to_string(),
+        Cached {
+            value: keys.clone(),
+            fetched_at: Instant::now(),
+        },
+    );
+    Ok(keys)
+}
+
+/// Build the authorization URL to send the user to. The provider puts `nonce` in the ID token.
+///
+/// # Errors
+///
+/// Returns an error if the discovered authorization endpoint is not a valid URL.
+pub fn authorize_url(
+    provider: &OidcProviderConfig,
+    document: &DiscoveryDocument,
+    state: &str,
+    nonce: &str,
+) -> anyhow::Result<String> {
+    let mut url = reqwest::Url::parse(&document.authorization_endpoint)
+        .map_err(|e| anyhow::anyhow!("Invalid authorization endpoint: {e}"))?;
+    url.query_pairs_mut()
+        .append_pair("response_type", "code")
+        .append_pair("client_id", &provider.client_id)
+        .append_pair("redirect_uri", &provider.redirect_uri)
+        .append_pair("scope", &provider.scopes.join(" "))
+        .append_pair("state", state)
+        .append_pair("nonce", nonce);
+    Ok(url.into())
+}
+
+/// Exchange an authorization code and return the validated claims of the signed-in user.
+///
+/// When the code comes from a sign-in started with [`authorize_url`], `nonce` is the value
+/// sent there and the ID token must carry it.
+///
+/// # Errors
+///
+/// Returns an error if a request to the provider fails, the ID token does not validate or
+/// has another nonce, or the userinfo response is for a different subject.
+pub async fn verify_code(
+    provider: &OidcProviderConfig,
+    code: &str,
+    nonce: Option<&str>,
+) -> anyhow::Result<OidcClaims> {
+    let document = discover(provider).await?;
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&document.token_endpoint)
+        .basic_auth(
+            urlencoding::encode(&provider.client_id),
+            Some(urlencoding::encode(&provider.client_secret)),
+        )
+        .form(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", provider.redirect_uri.as_str()),
+        ])
+        .send()
+        .await
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to exchange {} authorization code: {e}",
+                provider.name
+            )
+        })?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        return Err(anyhow::anyhow!(
+            "{} token request failed ({status}): {body}",
+            provider.name
+        ));
+    }
+    let token: TokenResponse = resp
+        .json()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to parse {} token response: {e}", provider.name))?;
+
+    let mut keys = signing_keys(&client, provider, &document.jwks_uri, false).await?;
+    // An unknown key id means the provider rotated its keys since they were cached. The token
+    // came straight from the provider, so this cannot be used to make us refetch at will.
+    let kid = jsonwebtoken::decode_header(&token.id_token)
+        .ok()
+        .and_then(|header| header.kid);
+    if kid.is_some_and(|kid| keys.find(&kid).is_none()) {
+        keys = signing_keys(&client, provider, &document.jwks_uri, true).await?;
+    }
+
+    let mut claims: OidcClaims = oauth::validate_id_token(
+        &token.id_token,
+        &keys,
+        &document.issuer,
+        &provider.client_id,
+    )?;
+    if let Some(nonce) = nonce
+        && claims.nonce.as_deref() != Some(nonce)
+    {
+        return Err(anyhow::anyhow!(
+            "{} ID token is for a different sign-in",
+            provider.name
+        ));
+    }
+
+    // Some providers only put profile claims in the userinfo response
+    if claims.email.is_none()
+        && let Some(userinfo_endpoint) = &document.userinfo_endpoint
+    {
+        let userinfo: OidcClaims = client
+            .get(userinfo_endpoint)
+            .bearer_auth(&token.access_token)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| anyhow::anyhow!("Failed to fetch {} userinfo: {e}", provider.name))?
+            .json()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to parse {} userinfo: {e}", provider.name))?;
+
+        if userinfo.sub != claims.sub {
+            return Err(anyhow::anyhow!(
+                "{} userinfo is for a different user",
+                provider.name
+            ));
+        }
+        claims.email = userinfo.email;
+        claims.email_verified = userinfo.email_verified;
+        claims.name = claims.name.or(userinfo.name);
+        claims.preferred_username = claims.preferred_username.or(userinfo.preferred_username);
+        claims.picture = claims.picture.or(userinfo.picture);
+    }
+
+    Ok(claims)
+}
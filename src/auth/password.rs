@@ -1,6 +1,7 @@
 use argon2::password_hash::SaltString;
 use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
 use rand::rngs::OsRng;
+use serde::Serialize;
 
 /// Hash a password using `Argon2id`.
 ///
@@ -31,19 +32,96 @@ pub fn verify_password(password: &str, hash: &str) -> anyhow::Result<bool> {
         .is_ok())
 }
 
-/// Validate password complexity rules.
-///
-/// Requirements: at least 8 characters, at most 128 characters.
+/// Rules new passwords must follow, configured with the `PASSWORD_*` settings and served at
+/// `GET /api/v1/auth/password-policy` so frontends can validate the same way.
+// One flag per character class keeps the served JSON flat for frontends
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordPolicy {
+    /// Minimum length in characters.
+    pub min_length: usize,
+    /// Maximum length in characters.
+    pub max_length: usize,
+    pub require_uppercase: bool,
+    pub require_lowercase: bool,
+    pub require_digit: bool,
+    /// Require a character that is not a letter or digit.
+    pub require_symbol: bool,
+    /// Passwords refused outright, compared case-insensitively. Not served to clients.
+    #[serde(skip)]
+    pub banned: Vec<String>,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            max_length: 128,
+            require_uppercase: false,
+            require_lowercase: false,
+            require_digit: false,
+            require_symbol: false,
+            banned: Vec::new(),
+        }
+    }
+}
+
+/// Validate a new password against `policy`.
 ///
 /// # Errors
 ///
-/// Returns a descriptive error message if validation fails.
-pub fn validate_password(password: &str) -> Result<(), String> {
-    if password.len() < 8 {
-        return Err("Password must be at least 8 characters.".to_string());
+/// Returns a descriptive error message for the first rule the password breaks.
+pub fn validate_password(password: &str, policy: &PasswordPolicy) -> Result<(), String> {
+    let length = password.chars().count();
+    if length < policy.min_length {
+        return Err(format!(
+            "Password must be at least {} characters.",
+            policy.min_length
+        ));
+    }
+    if length > policy.max_length {
+        return Err(format!(
+            "Password must be at most {} characters.",
+            policy.max_length
+        ));
+    }
+
+    let classes = [
+        (
+            policy.require_uppercase,
+            password.chars().any(char::is_uppercase),
+            "an uppercase letter",
+        ),
+        (
+            policy.require_lowercase,
+            password.chars().any(char::is_lowercase),
+            "a lowercase letter",
+        ),
+        (
+            policy.require_digit,
+            password.chars().any(|c| c.is_ascii_digit()),
+            "a digit",
+        ),
+        (
+            policy.require_symbol,
+            password.chars().any(|c| !c.is_alphanumeric()),
+            "a symbol",
+        ),
+    ];
+    if let Some((_, _, class)) = classes
+        .iter()
+        .find(|(required, present, _)| *required && !present)
+    {
+        return Err(format!("Password must contain {class}."));
     }
-    if password.len() > 128 {
-        return Err("Password must be at most 128 characters.".to_string());
+
+    if policy
+        .banned
+        .iter()
+        .any(|banned| banned.eq_ignore_ascii_case(password))
+    {
+        return Err("This password is too common. Please choose another.".to_string());
     }
     Ok(())
 }
@@ -65,12 +143,108 @@ pub fn validate_email(email: &str) -> Result<(), String> {
     Ok(())
 }
 
-/// Validate username format: 3-50 alphanumeric characters and underscores.
+/// Names no account may take: routes, roles and the service's own name.
+const RESERVED_USERNAMES: &[&str] = &[
+    "admin",
+    "administrator",
+    "aircade",
+    "api",
+    "auth",
+    "games",
+    "help",
+    "login",
+    "me",
+    "mod",
+    "moderator",
+    "null",
+    "oauth",
+    "root",
+    "settings",
+    "signin",
+    "signout",
+    "signup",
+    "staff",
+    "support",
+    "system",
+    "tags",
+    "undefined",
+    "users",
+    "www",
+];
+
+/// Words no username may contain. Kept to terms long and distinctive enough that substring
+/// matching does not catch innocent names.
+const BLOCKED_USERNAME_WORDS: &[&str] = &[
+    "bitch", "cunt", "faggot", "fuck", "nigger", "pussy", "rapist", "shit", "whore",
+];
+
+/// Usernames refused at signup and rename, configured with `USERNAME_RESERVED` and
+/// `USERNAME_BLOCKED_WORDS` on top of the built-in lists.
+///
+/// Names are compared after [`normalize_username`], so `4dm1n` and `Ad_Min` count as `admin`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsernameBlocklist {
+    /// Names refused when the whole username matches.
+    pub reserved: Vec<String>,
+    /// Words refused anywhere in the username.
+    pub words: Vec<String>,
+}
+
+impl Default for UsernameBlocklist {
+    fn default() -> Self {
+        Self {
+            reserved: RESERVED_USERNAMES.iter().map(ToString::to_string).collect(),
+            words: BLOCKED_USERNAME_WORDS
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+}
+
+impl UsernameBlocklist {
+    /// Whether `username` is reserved or contains a blocked word.
+    #[must_use]
+    pub fn blocks(&self, username: &str) -> bool {
+        let name = normalize_username(username);
+        self.reserved
+            .iter()
+            .any(|reserved| normalize_username(reserved) == name)
+            || self
+                .words
+                .iter()
+                .map(|word| normalize_username(word))
+                .any(|word| !word.is_empty() && name.contains(&word))
+    }
+}
+
+/// Lower-case `username`, undo common leetspeak substitutions and drop separators, so
+/// look-alike spellings of a blocked name compare equal to it.
+#[must_use]
+pub fn normalize_username(username: &str) -> String {
+    username
+        .chars()
+        .filter(|c| !matches!(c, '_' | '-' | '.'))
+        .map(|c| match c.to_ascii_lowercase() {
+            '0' => 'o',
+            '1' | '!' | '|' => 'i',
+            '3' => 'e',
+            '4' | '@' => 'a',
+            '5' | '$' => 's',
+            '7' | '+' => 't',
+            '8' => 'b',
+            '9' => 'g',
+            c => c,
+        })
+        .collect()
+}
+
+/// Validate username format: 3-50 alphanumeric characters and underscores, not on `blocklist`.
 ///
 /// # Errors
 ///
 /// Returns a descriptive error message if validation fails.
-pub fn validate_username(username: &str) -> Result<(), String> {
+pub fn validate_username(username: &str, blocklist: &UsernameBlocklist) -> Result<(), String> {
     if username.len() < 3 {
         return Err("Username must be at least 3 characters.".to_string());
     }
@@ -80,5 +254,81 @@ pub fn validate_username(username: &str) -> Result<(), String> {
     if !username.chars().all(|c| c.is_alphanumeric() || c == '_') {
         return Err("Username may only contain letters, numbers, and underscores.".to_string());
     }
+    if blocklist.blocks(username) {
+        return Err("This username is not available. Please choose another.".to_string());
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_policy_only_checks_length() {
+        let policy = PasswordPolicy::default();
+        assert!(validate_password("password", &policy).is_ok());
+        assert!(validate_password("short", &policy).is_err());
+        assert!(validate_password(&"x".repeat(129), &policy).is_err());
+    }
+
+    #[test]
+    fn length_counts_characters_not_bytes() {
+        let policy = PasswordPolicy {
+            min_length: 4,
+            ..PasswordPolicy::default()
+        };
+        assert!(validate_password("ääää", &policy).is_ok());
+        assert!(validate_password("äää", &policy).is_err());
+    }
+
+    #[test]
+    fn required_classes_are_enforced() {
+        let policy = PasswordPolicy {
+            require_uppercase: true,
+            require_digit: true,
+            require_symbol: true,
+            ..PasswordPolicy::default()
+        };
+        assert_eq!(
+            validate_password("lowercase1!", &policy),
+            Err("Password must contain an uppercase letter.".to_string())
+        );
+        assert_eq!(
+            validate_password("Uppercase!", &policy),
+            Err("Password must contain a digit.".to_string())
+        );
+        assert!(validate_password("Upper1case", &policy).is_err());
+        assert!(validate_password("Upper1case!", &policy).is_ok());
+    }
+
+    #[test]
+    fn banned_passwords_are_refused_in_any_case() {
+        let policy = PasswordPolicy {
+            banned: vec!["letmein123".to_string()],
+            ..PasswordPolicy::default()
+        };
+        assert!(validate_password("LetMeIn123", &policy).is_err());
+        assert!(validate_password("letmein1234", &policy).is_ok());
+    }
+
+    #[test]
+    fn reserved_usernames_match_look_alikes() {
+        let blocklist = UsernameBlocklist::default();
+        assert!(validate_username("admin", &blocklist).is_err());
+        assert!(validate_username("Ad_M1n", &blocklist).is_err());
+        assert!(validate_username("4dm1n", &blocklist).is_err());
+        assert!(validate_username("admin_fan", &blocklist).is_ok());
+        assert!(validate_username("gamer", &blocklist).is_ok());
+    }
+
+    #[test]
+    fn blocked_words_match_anywhere() {
+        let blocklist = UsernameBlocklist {
+            reserved: Vec::new(),
+            words: vec!["darn".to_string()],
+        };
+        assert!(validate_username("so_D4RN_cool", &blocklist).is_err());
+        assert!(validate_username("dart_player", &blocklist).is_ok());
+    }
+}
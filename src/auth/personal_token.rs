@@ -0,0 +1,173 @@
+use axum::http::Method;
+use data_encoding::BASE64URL_NOPAD;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+/// Prefix of every personal access token, so they are easy to tell apart from session JWTs
+/// (and to spot in leaked logs).
+pub const TOKEN_PREFIX: &str = "acd_pat_";
+
+/// Characters of a token kept in `api_token.token_prefix` for display.
+const DISPLAY_PREFIX_LEN: usize = 12;
+
+/// Random bytes in a token.
+const TOKEN_BYTES: usize = 32;
+
+/// Scopes a token can be granted.
+pub const SCOPES: [&str; 5] = [
+    "games:read",
+    "games:write",
+    "collections:read",
+    "collections:write",
+    "profile:read",
+];
+
+/// A newly generated token: the value to show the user once, plus what gets stored.
+pub struct GeneratedToken {
+    pub token: String,
+    pub hash: String,
+    pub display_prefix: String,
+}
+
+/// Generate a new random token.
+#[must_use]
+pub fn generate() -> GeneratedToken {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let token = format!("{TOKEN_PREFIX}{}", BASE64URL_NOPAD.encode(&bytes));
+    GeneratedToken {
+        hash: hash(&token),
+        display_prefix: token.chars().take(DISPLAY_PREFIX_LEN).collect(),
+        token,
+    }
+}
+
+/// Hex-encoded SHA-256 of a token, as stored in `api_token.token_hash`.
+#[must_use]
+pub fn hash(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Whether a space-separated scope list contains `scope`.
+#[must_use]
+pub fn has_scope(scopes: &str, scope: &str) -> bool {
+    scopes.split_whitespace().any(|s| s == scope)
+}
+
+/// Check scopes against [`SCOPES`] and return them deduplicated and space-separated, as
+/// stored in `api_token.scopes`.
+///
+/// # Errors
+///
+/// Returns the first unknown scope, or an empty string if there are no scopes.
+pub fn normalize_scopes<'a>(scopes: impl IntoIterator<Item = &'a str>) -> Result<String, String> {
+    let mut unique = std::collections::BTreeSet::new();
+    for scope in scopes {
+        if !SCOPES.contains(&scope) {
+            return Err(scope.to_string());
+        }
+        unique.insert(scope);
+    }
+    if unique.is_empty() {
+        return Err(String::new());
+    }
+    Ok(unique.into_iter().collect::<Vec<_>>().join(" "))
+}
+
+/// The scope a request to `path` (the full `/api/v1/...` path) needs.
+///
+/// Returns `None` for endpoints tokens can never use, such as account settings and token
+/// management.
+#[must_use]
+pub fn required_scope(method: &Method, path: &str) -> Option<&'static str> {
+    let read = matches!(*method, Method::GET | Method::HEAD);
+    let path = path.strip_prefix("/api/v1")?.trim_end_matches('/');
+    let under = |prefix: &str| {
+        path == prefix
+            || path
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.starts_with('/'))
+    };
+
+    if under("/games") || under("/users/me/games") || path == "/users/me/favorites" {
+        Some(if read { "games:read" } else { "games:write" })
+    } else if under("/collections") || under("/users/me/collections") {
+        Some(if read {
+            "collections:read"
+        } else {
+            "collections:write"
+        })
+    } else if path == "/users/me" && read {
+        Some("profile:read")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_tokens_are_prefixed_and_hashed() {
+        let generated = generate();
+        assert!(generated.token.starts_with(TOKEN_PREFIX));
+        assert!(generated.token.starts_with(&generated.display_prefix));
+        assert_eq!(generated.hash, hash(&generated.token));
+        assert_ne!(generated.token, generate().token);
+    }
+
+    #[test]
+    fn scopes_follow_the_resource_and_method() {
+        assert_eq!(
+            required_scope(&Method::GET, "/api/v1/games/abc"),
+            Some("games:read")
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/v1/games/abc/publish"),
+            Some("games:write")
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/v1/users/me/games"),
+            Some("games:read")
+        );
+        assert_eq!(
+            required_scope(&Method::PATCH, "/api/v1/users/me/collections/abc"),
+            Some("collections:write")
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/v1/users/me"),
+            Some("profile:read")
+        );
+        assert_eq!(required_scope(&Method::GET, "/api/v1/gamesx"), None);
+        assert_eq!(required_scope(&Method::PATCH, "/api/v1/users/me"), None);
+        assert_eq!(
+            required_scope(&Method::GET, "/api/v1/users/me/tokens"),
+            None
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/v1/auth/change-password"),
+            None
+        );
+    }
+
+    #[test]
+    fn normalize_scopes_sorts_and_deduplicates() {
+        assert_eq!(
+            normalize_scopes(["games:write", "games:read", "games:write"]),
+            Ok("games:read games:write".to_string())
+        );
+        assert_eq!(
+            normalize_scopes(["games:read", "admin"]),
+            Err("admin".to_string())
+        );
+        assert_eq!(normalize_scopes([]), Err(String::new()));
+    }
+
+    #[test]
+    fn has_scope_matches_whole_scopes() {
+        assert!(has_scope("games:read games:write", "games:write"));
+        assert!(!has_scope("games:read", "games:write"));
+        assert!(!has_scope("games:readx", "games:read"));
+    }
+}
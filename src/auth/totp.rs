@@ -0,0 +1,121 @@
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha1::Sha1;
+
+/// Issuer shown by authenticator apps.
+const ISSUER: &str = "AirCade";
+
+/// Seconds each code is valid for.
+const STEP_SECONDS: i64 = 30;
+
+/// Digits in a code.
+const DIGITS: u32 = 6;
+
+/// Size of a generated secret (160 bits, as RFC 4226 recommends).
+const SECRET_BYTES: usize = 20;
+
+/// Codes from this many steps before or after the current one are still accepted, to allow for
+/// clock drift.
+const ALLOWED_DRIFT_STEPS: i64 = 1;
+
+/// Generate a new random secret, Base32-encoded.
+#[must_use]
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; SECRET_BYTES];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    BASE32_NOPAD.encode(&bytes)
+}
+
+/// Build the `otpauth://` URI authenticator apps enroll from (usually shown as a QR code).
+#[must_use]
+pub fn provisioning_uri(secret: &str, account: &str) -> String {
+    format!(
+        "otpauth://totp/{issuer}:{account}?secret={secret}&issuer={issuer}&algorithm=SHA1&digits={DIGITS}&period={STEP_SECONDS}",
+        issuer = urlencoding::encode(ISSUER),
+        account = urlencoding::encode(account),
+    )
+}
+
+/// The code `secret` produces at Unix time `now`, or `None` if the secret is malformed.
+#[must_use]
+pub fn code(secret: &str, now: i64) -> Option<String> {
+    let key = BASE32_NOPAD.decode(secret.as_bytes()).ok()?;
+    code_at(&key, now.div_euclid(STEP_SECONDS))
+}
+
+/// Check `code` against `secret` at Unix time `now`.
+///
+/// Returns the time step the code belongs to, so callers can reject a code that was already
+/// used. Returns `None` if the code does not match or the secret is malformed.
+#[must_use]
+pub fn verify(secret: &str, code: &str, now: i64) -> Option<i64> {
+    let code = code.trim();
+    if code.len() != DIGITS as usize || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let key = BASE32_NOPAD.decode(secret.as_bytes()).ok()?;
+    let current = now.div_euclid(STEP_SECONDS);
+
+    (current - ALLOWED_DRIFT_STEPS..=current + ALLOWED_DRIFT_STEPS)
+        .find(|&step| code_at(&key, step).is_some_and(|expected| expected == code))
+}
+
+/// The code for time step `step` (HOTP with the step as counter).
+fn code_at(key: &[u8], step: i64) -> Option<String> {
+    let counter = u64::try_from(step).ok()?;
+    let mut mac = Hmac::<Sha1>::new_from_slice(key).ok()?;
+    mac.update(&counter.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    let offset = usize::from(digest.last()? & 0x0f);
+    let bytes: [u8; 4] = digest.get(offset..offset + 4)?.try_into().ok()?;
+    let value = u32::from_be_bytes(bytes) & 0x7fff_ffff;
+
+    Some(format!(
+        "{:0width$}",
+        value % 10u32.pow(DIGITS),
+        width = DIGITS as usize
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The RFC 6238 SHA-1 test secret, `12345678901234567890`.
+    const RFC_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    #[test]
+    fn matches_rfc_6238_vectors() {
+        // Last six digits of the RFC's eight-digit codes
+        assert_eq!(verify(RFC_SECRET, "287082", 59), Some(1));
+        assert_eq!(
+            verify(RFC_SECRET, "081804", 1_111_111_109),
+            Some(37_037_036)
+        );
+        assert_eq!(
+            verify(RFC_SECRET, "005924", 1_234_567_890),
+            Some(41_152_263)
+        );
+        assert_eq!(code(RFC_SECRET, 59).as_deref(), Some("287082"));
+    }
+
+    #[test]
+    fn rejects_wrong_or_stale_codes() {
+        assert_eq!(verify(RFC_SECRET, "287083", 59), None);
+        assert_eq!(verify(RFC_SECRET, "287082", 59 + 3 * STEP_SECONDS), None);
+        assert_eq!(verify(RFC_SECRET, "28708", 59), None);
+        assert_eq!(verify("not base32!", "287082", 59), None);
+    }
+
+    #[test]
+    fn generated_secrets_round_trip() {
+        let secret = generate_secret();
+        assert_eq!(
+            BASE32_NOPAD.decode(secret.as_bytes()).map(|k| k.len()),
+            Ok(20)
+        );
+        assert!(provisioning_uri(&secret, "me@example.com").contains(&secret));
+    }
+}
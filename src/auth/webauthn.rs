@@ -0,0 +1,473 @@
+//! Server side of the `WebAuthn` (passkey) registration and authentication ceremonies.
+//!
+//! Only what browsers and platform authenticators need for passkeys is supported: ES256
+//! (P-256) keys and the `none` attestation format. Attestation statements, when an
+//! authenticator sends one anyway, are ignored.
+
+use data_encoding::BASE64URL_NOPAD;
+use rand::RngCore;
+use ring::signature::{ECDSA_P256_SHA256_ASN1, UnparsedPublicKey};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+/// Size of a generated challenge.
+const CHALLENGE_BYTES: usize = 32;
+
+/// COSE algorithm identifier for ECDSA with SHA-256 on P-256.
+pub const COSE_ALG_ES256: i64 = -7;
+
+/// Authenticator data flags (`WebAuthn` §6.1).
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+
+/// Fixed-size prefix of authenticator data: RP ID hash, flags and signature counter.
+const AUTH_DATA_HEADER_LEN: usize = 37;
+
+/// Longest credential ID allowed by the spec.
+const MAX_CREDENTIAL_ID_LEN: usize = 1023;
+
+/// A rejected ceremony; the message is safe to show to the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CeremonyError(pub &'static str);
+
+impl std::fmt::Display for CeremonyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl std::error::Error for CeremonyError {}
+
+/// The relying party credentials are scoped to: the frontend's host and origin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelyingParty {
+    pub id: String,
+    pub origin: String,
+}
+
+impl RelyingParty {
+    /// Derive the relying party from `FRONTEND_URL`, or `None` if it is not a valid URL.
+    #[must_use]
+    pub fn from_frontend_url(frontend_url: &str) -> Option<Self> {
+        let url = reqwest::Url::parse(frontend_url).ok()?;
+        Some(Self {
+            id: url.host_str()?.to_string(),
+            origin: url.origin().ascii_serialization(),
+        })
+    }
+}
+
+/// A credential created by a successful registration ceremony.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewCredential {
+    /// Base64url-encoded credential ID.
+    pub credential_id: String,
+    /// Uncompressed P-256 public key point.
+    pub public_key: Vec<u8>,
+    pub sign_count: u32,
+}
+
+#[derive(Deserialize)]
+struct ClientData {
+    #[serde(rename = "type")]
+    kind: String,
+    challenge: String,
+    origin: String,
+}
+
+/// Generate a new random challenge, base64url-encoded.
+#[must_use]
+pub fn generate_challenge() -> String {
+    let mut bytes = [0u8; CHALLENGE_BYTES];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    BASE64URL_NOPAD.encode(&bytes)
+}
+
+/// Decode base64url, with or without padding.
+///
+/// # Errors
+///
+/// Returns an error if `value` is not valid base64url.
+pub fn decode_base64url(value: &str) -> Result<Vec<u8>, CeremonyError> {
+    BASE64URL_NOPAD
+        .decode(value.trim_end_matches('=').as_bytes())
+        .map_err(|_| CeremonyError("Invalid base64url encoding"))
+}
+
+/// Verify the response to a `navigator.credentials.create()` call.
+///
+/// # Errors
+///
+/// Returns an error if the client data does not match the challenge and origin, the
+/// authenticator data is malformed or for another relying party, the user was not verified,
+/// or the key is not an ES256 key.
+pub fn verify_registration(
+    rp: &RelyingParty,
+    challenge: &str,
+    client_data_json: &[u8],
+    attestation_object: &[u8],
+) -> Result<NewCredential, CeremonyError> {
+    check_client_data(rp, "webauthn.create", challenge, client_data_json)?;
+
+    let (attestation, _) = cbor::decode(attestation_object)?;
+    let auth_data = attestation
+        .map_get_text("authData")
+        .and_then(cbor::Value::as_bytes)
+        .ok_or(CeremonyError(
+            "Attestation object has no authenticator data",
+        ))?;
+
+    let (flags, sign_count) = check_auth_data(rp, auth_data)?;
+    if flags & FLAG_ATTESTED_CREDENTIAL_DATA == 0 {
+        return Err(CeremonyError("Authenticator data has no credential"));
+    }
+
+    // AAGUID (16 bytes), credential ID length (2 bytes), credential ID, COSE public key
+    let rest = auth_data
+        .get(AUTH_DATA_HEADER_LEN + 16..)
+        .ok_or(CeremonyError("Authenticator data is truncated"))?;
+    let (len, rest) = rest
+        .split_first_chunk::<2>()
+        .ok_or(CeremonyError("Authenticator data is truncated"))?;
+    let len = usize::from(u16::from_be_bytes(*len));
+    if len == 0 || len > MAX_CREDENTIAL_ID_LEN || rest.len() < len {
+        return Err(CeremonyError("Invalid credential ID"));
+    }
+    let (credential_id, rest) = rest.split_at(len);
+    let (cose_key, _) = cbor::decode(rest)?;
+
+    Ok(NewCredential {
+        credential_id: BASE64URL_NOPAD.encode(credential_id),
+        public_key: es256_public_key(&cose_key)?,
+        sign_count,
+    })
+}
+
+/// Verify the response to a `navigator.credentials.get()` call against the stored public key.
+///
+/// Returns the authenticator's new signature counter.
+///
+/// # Errors
+///
+/// Returns an error if the client data does not match the challenge and origin, the
+/// authenticator data is malformed or for another relying party, the user was not verified,
+/// or the signature is invalid.
+pub fn verify_authentication(
+    rp: &RelyingParty,
+    challenge: &str,
+    public_key: &[u8],
+    client_data_json: &[u8],
+    authenticator_data: &[u8],
+    signature: &[u8],
+) -> Result<u32, CeremonyError> {
+    check_client_data(rp, "webauthn.get", challenge, client_data_json)?;
+    let (_, sign_count) = check_auth_data(rp, authenticator_data)?;
+
+    // The authenticator signs its data followed by the hash of the client data
+    let mut signed = authenticator_data.to_vec();
+    signed.extend_from_slice(&Sha256::digest(client_data_json));
+    UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, public_key)
+        .verify(&signed, signature)
+        .map_err(|_| CeremonyError("Invalid signature"))?;
+
+    Ok(sign_count)
+}
+
+/// Whether a reported signature counter is consistent with the stored one.
+///
+/// Authenticators that do not implement counters always report zero; otherwise the counter
+/// must increase, or the credential may have been cloned.
+#[must_use]
+pub const fn sign_count_is_valid(stored: u32, reported: u32) -> bool {
+    (stored == 0 && reported == 0) || reported > stored
+}
+
+fn check_client_data(
+    rp: &RelyingParty,
+    kind: &str,
+    challenge: &str,
+    client_data_json: &[u8],
+) -> Result<(), CeremonyError> {
+    let client_data: ClientData = serde_json::from_slice(client_data_json)
+        .map_err(|_| CeremonyError("Invalid client data"))?;
+
+    if client_data.kind != kind {
+        return Err(CeremonyError("Unexpected ceremony type"));
+    }
+    if client_data.challenge.trim_end_matches('=') != challenge {
+        return Err(CeremonyError("Challenge does not match"));
+    }
+    if client_data.origin != rp.origin {
+        return Err(CeremonyError("Origin does not match"));
+    }
+    Ok(())
+}
+
+/// Check the RP ID hash and user flags, returning the flags and signature counter.
+fn check_auth_data(rp: &RelyingParty, auth_data: &[u8]) -> Result<(u8, u32), CeremonyError> {
+    let header = auth_data
+        .get(..AUTH_DATA_HEADER_LEN)
+        .ok_or(CeremonyError("Authenticator data is truncated"))?;
+    let (rp_id_hash, rest) = header.split_at(32);
+    let (&flags, counter) = rest
+        .split_first()
+        .ok_or(CeremonyError("Authenticator data is truncated"))?;
+
+    if rp_id_hash != Sha256::digest(rp.id.as_bytes()).as_slice() {
+        return Err(CeremonyError("Credential is for another relying party"));
+    }
+    if flags & FLAG_USER_PRESENT == 0 || flags & FLAG_USER_VERIFIED == 0 {
+        return Err(CeremonyError("User was not verified"));
+    }
+
+    let counter: [u8; 4] = counter
+        .try_into()
+        .map_err(|_| CeremonyError("Authenticator data is truncated"))?;
+    Ok((flags, u32::from_be_bytes(counter)))
+}
+
+/// Convert an EC2 COSE key (RFC 9053) to an uncompressed P-256 point.
+fn es256_public_key(key: &cbor::Value) -> Result<Vec<u8>, CeremonyError> {
+    const KTY_EC2: i64 = 2;
+    const CRV_P256: i64 = 1;
+
+    let int = |label| key.map_get_int(label).and_then(cbor::Value::as_int);
+    let coordinate = |label| {
+        key.map_get_int(label)
+            .and_then(cbor::Value::as_bytes)
+            .filter(|c| c.len() == 32)
+    };
+
+    if int(1) != Some(KTY_EC2) || int(3) != Some(COSE_ALG_ES256) || int(-1) != Some(CRV_P256) {
+        return Err(CeremonyError("Only ES256 (P-256) passkeys are supported"));
+    }
+    let (Some(x), Some(y)) = (coordinate(-2), coordinate(-3)) else {
+        return Err(CeremonyError("Invalid public key"));
+    };
+
+    let mut point = Vec::with_capacity(65);
+    point.push(0x04);
+    point.extend_from_slice(x);
+    point.extend_from_slice(y);
+    Ok(point)
+}
+
+/// Just enough of a CBOR (RFC 8949) decoder for attestation objects and COSE keys.
+mod cbor {
+    use super::CeremonyError;
+
+    /// Deepest nesting accepted, to bound recursion on hostile input.
+    const MAX_DEPTH: usize = 8;
+
+    const MALFORMED: CeremonyError = CeremonyError("Malformed CBOR");
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Value {
+        Int(i64),
+        Bytes(Vec<u8>),
+        Text(String),
+        Array(Vec<Self>),
+        Map(Vec<(Self, Self)>),
+        /// Booleans, null, undefined and floats, which passkeys never need.
+        Simple,
+    }
+
+    impl Value {
+        pub const fn as_int(&self) -> Option<i64> {
+            match self {
+                Self::Int(i) => Some(*i),
+                _ => None,
+            }
+        }
+
+        pub fn as_bytes(&self) -> Option<&[u8]> {
+            match self {
+                Self::Bytes(b) => Some(b),
+                _ => None,
+            }
+        }
+
+        pub fn map_get_int(&self, key: i64) -> Option<&Self> {
+            self.map_get(|k| *k == Self::Int(key))
+        }
+
+        pub fn map_get_text(&self, key: &str) -> Option<&Self> {
+            self.map_get(|k| matches!(k, Self::Text(t) if t == key))
+        }
+
+        fn map_get(&self, matches: impl Fn(&Self) -> bool) -> Option<&Self> {
+            match self {
+                Self::Map(entries) => entries.iter().find(|(k, _)| matches(k)).map(|(_, v)| v),
+                _ => None,
+            }
+        }
+    }
+
+    /// Decode one item from the start of `input`, returning it and the remaining bytes.
+    pub fn decode(input: &[u8]) -> Result<(Value, &[u8]), CeremonyError> {
+        decode_item(input, 0)
+    }
+
+    fn decode_item(input: &[u8], depth: usize) -> Result<(Value, &[u8]), CeremonyError> {
+        if depth > MAX_DEPTH {
+            return Err(MALFORMED);
+        }
+        let (&initial, rest) = input.split_first().ok_or(MALFORMED)?;
+        let major = initial >> 5;
+        let (argument, mut rest) = read_argument(initial & 0x1f, rest)?;
+
+        let value = match major {
+            0 => Value::Int(i64::try_from(argument).map_err(|_| MALFORMED)?),
+            1 => Value::Int(-1 - i64::try_from(argument).map_err(|_| MALFORMED)?),
+            2 | 3 => {
+                let len = usize::try_from(argument).map_err(|_| MALFORMED)?;
+                if rest.len() < len {
+                    return Err(MALFORMED);
+                }
+                let (bytes, tail) = rest.split_at(len);
+                rest = tail;
+                if major == 2 {
+                    Value::Bytes(bytes.to_vec())
+                } else {
+                    Value::Text(String::from_utf8(bytes.to_vec()).map_err(|_| MALFORMED)?)
+                }
+            }
+            4 => {
+                // Every item takes at least one byte, which bounds the allocation
+                let len = usize::try_from(argument).map_err(|_| MALFORMED)?;
+                if rest.len() < len {
+                    return Err(MALFORMED);
+                }
+                let mut items = Vec::with_capacity(len);
+                for _ in 0..len {
+                    let (item, tail) = decode_item(rest, depth + 1)?;
+                    items.push(item);
+                    rest = tail;
+                }
+                Value::Array(items)
+            }
+            5 => {
+                let len = usize::try_from(argument).map_err(|_| MALFORMED)?;
+                if rest.len() < len.saturating_mul(2) {
+                    return Err(MALFORMED);
+                }
+                let mut entries = Vec::with_capacity(len);
+                for _ in 0..len {
+                    let (key, tail) = decode_item(rest, depth + 1)?;
+                    let (value, tail) = decode_item(tail, depth + 1)?;
+                    entries.push((key, value));
+                    rest = tail;
+                }
+                Value::Map(entries)
+            }
+            // Tags wrap a single item
+            6 => decode_item(rest, depth + 1).map(|(item, tail)| {
+                rest = tail;
+                item
+            })?,
+            _ => Value::Simple,
+        };
+        Ok((value, rest))
+    }
+
+    /// Read the argument encoded by the low five bits of the initial byte.
+    ///
+    /// Indefinite lengths are rejected: `WebAuthn` requires the canonical encoding.
+    fn read_argument(info: u8, input: &[u8]) -> Result<(u64, &[u8]), CeremonyError> {
+        let len = match info {
+            0..=23 => return Ok((u64::from(info), input)),
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            _ => return Err(MALFORMED),
+        };
+        if input.len() < len {
+            return Err(MALFORMED);
+        }
+        let (bytes, rest) = input.split_at(len);
+        let value = bytes.iter().fold(0u64, |acc, &b| (acc << 8) | u64::from(b));
+        Ok((value, rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rp() -> RelyingParty {
+        RelyingParty {
+            id: "example.com".to_string(),
+            origin: "https://example.com".to_string(),
+        }
+    }
+
+    fn auth_data(flags: u8, counter: u32) -> Vec<u8> {
+        let mut data = Sha256::digest(b"example.com").to_vec();
+        data.push(flags);
+        data.extend_from_slice(&counter.to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn relying_party_comes_from_frontend_url() {
+        assert_eq!(
+            RelyingParty::from_frontend_url("http://localhost:3001/"),
+            Some(RelyingParty {
+                id: "localhost".to_string(),
+                origin: "http://localhost:3001".to_string(),
+            })
+        );
+        assert_eq!(RelyingParty::from_frontend_url("not a url"), None);
+    }
+
+    #[test]
+    fn decodes_cose_keys() {
+        // {1: 2, 3: -7, -1: 1, -2: h'01'*32, -3: h'02'*32}
+        let mut key = vec![0xa5, 0x01, 0x02, 0x03, 0x26, 0x20, 0x01, 0x21, 0x58, 0x20];
+        key.extend_from_slice(&[1; 32]);
+        key.extend_from_slice(&[0x22, 0x58, 0x20]);
+        key.extend_from_slice(&[2; 32]);
+
+        let (value, rest) = cbor::decode(&key).unwrap_or((cbor::Value::Simple, &[]));
+        assert!(rest.is_empty());
+        let point = es256_public_key(&value);
+        assert_eq!(point.as_ref().map(Vec::len), Ok(65));
+        assert_eq!(
+            point.ok().and_then(|p| p.get(1..33).map(<[u8]>::to_vec)),
+            Some(vec![1; 32])
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_cbor() {
+        assert!(cbor::decode(&[]).is_err());
+        assert!(cbor::decode(&[0x58, 0x20, 0x01]).is_err());
+        assert!(cbor::decode(&[0x9f]).is_err());
+        assert!(cbor::decode(&[0x81; 64]).is_err());
+    }
+
+    #[test]
+    fn checks_authenticator_data() {
+        let flags = FLAG_USER_PRESENT | FLAG_USER_VERIFIED;
+        assert_eq!(check_auth_data(&rp(), &auth_data(flags, 7)), Ok((flags, 7)));
+        assert!(check_auth_data(&rp(), &auth_data(FLAG_USER_PRESENT, 7)).is_err());
+        assert!(check_auth_data(&rp(), &auth_data(flags, 7)[..36]).is_err());
+
+        let other = RelyingParty {
+            id: "evil.example".to_string(),
+            origin: "https://evil.example".to_string(),
+        };
+        assert!(check_auth_data(&other, &auth_data(flags, 7)).is_err());
+    }
+
+    #[test]
+    fn sign_counters_must_increase() {
+        assert!(sign_count_is_valid(0, 0));
+        assert!(sign_count_is_valid(0, 1));
+        assert!(sign_count_is_valid(4, 5));
+        assert!(!sign_count_is_valid(5, 5));
+        assert!(!sign_count_is_valid(5, 0));
+    }
+}
@@ -0,0 +1,139 @@
+//! Generated fallback avatars.
+//!
+//! Users without an uploaded avatar and session guests get an SVG generated from a seed (their
+//! username or display name) instead, so clients always have an image to show. The same seed
+//! always produces the same image.
+
+use std::fmt::Write;
+
+use sha2::{Digest, Sha256};
+
+/// Route that serves generated avatars, relative to the API root.
+const GENERATE_PATH: &str = "/api/v1/avatars/generate";
+
+/// Longest seed accepted, in characters.
+pub const MAX_SEED_LENGTH: usize = 100;
+
+/// How a generated avatar is drawn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Style {
+    /// Up to two initials on a colored background.
+    #[default]
+    Initials,
+    /// A symmetric 5×5 pattern.
+    Identicon,
+}
+
+/// The avatar to show for someone: their uploaded one, or a generated one seeded by `seed`.
+#[must_use]
+pub fn url_or_fallback(avatar_url: Option<String>, seed: &str) -> String {
+    avatar_url.unwrap_or_else(|| fallback_url(seed))
+}
+
+/// The URL of the generated avatar for `seed`.
+#[must_use]
+pub fn fallback_url(seed: &str) -> String {
+    let seed: String = seed.chars().take(MAX_SEED_LENGTH).collect();
+    format!("{GENERATE_PATH}?seed={}", urlencoding::encode(&seed))
+}
+
+/// Render the avatar for `seed` as an SVG document.
+#[must_use]
+pub fn svg(seed: &str, style: Style) -> String {
+    let digest = Sha256::digest(seed.as_bytes());
+    let hue = u16::from_be_bytes([digest[0], digest[1]]) % 360;
+    let color = format!("hsl({hue}, 55%, 45%)");
+
+    match style {
+        Style::Initials => format!(
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="128" height="128" "#,
+                r#"viewBox="0 0 128 128"><rect width="128" height="128" fill="{color}"/>"#,
+                r##"<text x="50%" y="50%" dy=".35em" text-anchor="middle" fill="#ffffff" "##,
+                r#"font-family="sans-serif" font-size="56" font-weight="600">{initials}</text>"#,
+                "</svg>"
+            ),
+            color = color,
+            initials = initials(seed),
+        ),
+        Style::Identicon => {
+            let mut cells = String::new();
+            for row in 0..5 {
+                for col in 0..3 {
+                    let bit = row * 3 + col;
+                    if digest[2 + bit / 8] & (1 << (bit % 8)) == 0 {
+                        continue;
+                    }
+                    // Mirror the left columns onto the right
+                    let xs: &[usize] = if col == 2 { &[2] } else { &[col, 4 - col] };
+                    for x in xs {
+                        let _ = write!(
+                            cells,
+                            r#"<rect x="{}" y="{}" width="20" height="20"/>"#,
+                            14 + x * 20,
+                            14 + row * 20
+                        );
+                    }
+                }
+            }
+            format!(
+                concat!(
+                    r#"<svg xmlns="http://www.w3.org/2000/svg" width="128" height="128" "#,
+                    r##"viewBox="0 0 128 128"><rect width="128" height="128" fill="#f0f0f0"/>"##,
+                    r#"<g fill="{color}">{cells}</g></svg>"#
+                ),
+                color = color,
+                cells = cells,
+            )
+        }
+    }
+}
+
+/// Up to two uppercase initials from the words of `seed`, or `?` if it has no letters or
+/// digits. Only alphanumeric characters are used, so the result is safe to embed in SVG.
+fn initials(seed: &str) -> String {
+    let initials: String = seed
+        .split(|c: char| !c.is_alphanumeric())
+        .filter_map(|word| word.chars().next())
+        .take(2)
+        .flat_map(char::to_uppercase)
+        .collect();
+    if initials.is_empty() {
+        "?".to_string()
+    } else {
+        initials
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initials_come_from_the_first_two_words() {
+        assert_eq!(initials("Ada Lovelace"), "AL");
+        assert_eq!(initials("grace_hopper_92"), "GH");
+        assert_eq!(initials("alan"), "A");
+        assert_eq!(initials("<script>"), "S");
+        assert_eq!(initials("!!!"), "?");
+    }
+
+    #[test]
+    fn avatars_are_deterministic() {
+        assert_eq!(svg("ada", Style::Initials), svg("ada", Style::Initials));
+        assert_eq!(svg("ada", Style::Identicon), svg("ada", Style::Identicon));
+        assert_ne!(svg("ada", Style::Identicon), svg("alan", Style::Identicon));
+    }
+
+    #[test]
+    fn fallback_url_encodes_the_seed() {
+        assert_eq!(
+            fallback_url("Ada L&co"),
+            "/api/v1/avatars/generate?seed=Ada%20L%26co"
+        );
+        assert_eq!(
+            url_or_fallback(Some("avatars/a.webp".to_string()), "ada"),
+            "avatars/a.webp"
+        );
+    }
+}
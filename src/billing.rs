@@ -0,0 +1,370 @@
+//! Subscription billing through Stripe.
+//!
+//! Users upgrade to the pro plan through a Stripe Checkout session. Stripe then reports the
+//! subscription's lifecycle to the webhook, and each event is mirrored onto
+//! `user.subscription_plan` / `user.subscription_expires_at`. Plans whose paid period ran out
+//! without a renewal are downgraded by [`crate::jobs::subscription_expiry`].
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, DatabaseConnection, DbErr,
+    EntityTrait, QueryFilter, TransactionTrait,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use sha2::Sha256;
+
+use crate::config::Config;
+use crate::entities::{stripe_event, subscription, user};
+
+/// The plan sold through Stripe.
+pub const PRO_PLAN: &str = "pro";
+
+/// The plan users fall back to when their subscription ends.
+pub const FREE_PLAN: &str = "free";
+
+/// How far a webhook's signature timestamp may be from now, in seconds, before it is treated
+/// as a replay.
+const SIGNATURE_TOLERANCE_SECS: i64 = 300;
+
+/// Stripe subscription statuses that keep the plan until the paid period ends.
+const LIVE_STATUSES: [&str; 3] = ["active", "trialing", "past_due"];
+
+/// Event types that report a subscription's status and period.
+const SUBSCRIPTION_EVENTS: [&str; 3] = [
+    "customer.subscription.created",
+    "customer.subscription.updated",
+    "customer.subscription.deleted",
+];
+
+/// A webhook event, with the fields billing reads.
+#[derive(Debug, Deserialize)]
+pub struct Event {
+    /// Stripe's event ID; a redelivered event keeps it.
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// When Stripe created the event, in unix seconds.
+    pub created: i64,
+    pub data: EventData,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventData {
+    pub object: Value,
+}
+
+/// Check a webhook `payload` against its `Stripe-Signature` header, signed with `secret`, at
+/// unix time `now`.
+///
+/// # Errors
+///
+/// Returns a message if the header is malformed, too old, or no signature in it matches.
+pub fn verify_signature(
+    payload: &[u8],
+    header: &str,
+    secret: &str,
+    now: i64,
+) -> Result<(), String> {
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", t)) => timestamp = t.parse::<i64>().ok(),
+            Some(("v1", sig)) => signatures.extend(hex::decode(sig).ok()),
+            _ => {}
+        }
+    }
+    let timestamp = timestamp.ok_or("Signature header has no timestamp.")?;
+    if (now - timestamp).abs() > SIGNATURE_TOLERANCE_SECS {
+        return Err("Signature timestamp is outside the tolerance.".to_string());
+    }
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).map_err(|e| e.to_string())?;
+    mac.update(format!("{timestamp}.").as_bytes());
+    mac.update(payload);
+    // Stripe sends one signature per active secret while one is being rolled
+    if signatures
+        .iter()
+        .any(|sig| mac.clone().verify_slice(sig).is_ok())
+    {
+        Ok(())
+    } else {
+        Err("No signature matches the payload.".to_string())
+    }
+}
+
+/// Start a Stripe Checkout session for `user` to subscribe to the pro plan and return its URL.
+///
+/// # Errors
+///
+/// Returns an error if a Stripe request or database operation fails.
+pub async fn create_checkout_session(
+    db: &DatabaseConnection,
+    config: &Config,
+    user: &user::Model,
+) -> anyhow::Result<String> {
+    let customer_id = customer_id(db, config, user).await?;
+    let success_url = format!("{}/settings/billing?checkout=success", config.frontend_url);
+    let cancel_url = format!(
+        "{}/settings/billing?checkout=cancelled",
+        config.frontend_url
+    );
+    let user_id = user.id.to_string();
+    let session = post_form(
+        config,
+        "/v1/checkout/sessions",
+        &[
+            ("mode", "subscription"),
+            ("customer", customer_id.as_str()),
+            ("client_reference_id", user_id.as_str()),
+            ("line_items[0][price]", config.stripe_pro_price_id.as_str()),
+            ("line_items[0][quantity]", "1"),
+            ("success_url", success_url.as_str()),
+            ("cancel_url", cancel_url.as_str()),
+        ],
+    )
+    .await?;
+
+    string_field(&session, "url").ok_or_else(|| anyhow::anyhow!("Stripe checkout has no URL"))
+}
+
+/// Apply a webhook event. Events for customers this API did not create, and event types
+/// billing does not use, are ignored.
+///
+/// Stripe may deliver an event more than once and in any order. An event whose ID was already
+/// applied is skipped, as is one created before the last event applied to the subscription.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn apply_event(db: &DatabaseConnection, event: &Event) -> Result<(), DbErr> {
+    let object = &event.data.object;
+    let subscription_event = SUBSCRIPTION_EVENTS.contains(&event.kind.as_str());
+    if !subscription_event && event.kind != "checkout.session.completed" {
+        return Ok(());
+    }
+    let Some(customer_id) = string_field(object, "customer") else {
+        return Ok(());
+    };
+    let Some(created) = DateTime::from_timestamp(event.created, 0) else {
+        return Ok(());
+    };
+    let now = Utc::now().fixed_offset();
+
+    let txn = db.begin().await?;
+    let recorded = stripe_event::Entity::insert(stripe_event::ActiveModel {
+        id: Set(event.id.clone()),
+        kind: Set(event.kind.clone()),
+        processed_at: Set(now),
+    })
+    .on_conflict(
+        OnConflict::column(stripe_event::Column::Id)
+            .do_nothing()
+            .to_owned(),
+    )
+    .exec_without_returning(&txn)
+    .await?;
+    if recorded == 0 {
+        tracing::debug!(event_id = %event.id, "Skipping redelivered Stripe event");
+        return Ok(());
+    }
+
+    let Some(existing) = subscription::Entity::find()
+        .filter(subscription::Column::StripeCustomerId.eq(&customer_id))
+        .one(&txn)
+        .await?
+    else {
+        tracing::warn!(%customer_id, kind = %event.kind, "Stripe event for unknown customer");
+        return txn.commit().await;
+    };
+
+    // Conditional, so of two events racing for the row the older one loses either way
+    let update = subscription::Entity::update_many()
+        .col_expr(
+            subscription::Column::LastEventAt,
+            Expr::value(created.fixed_offset()),
+        )
+        .col_expr(subscription::Column::UpdatedAt, Expr::value(now))
+        .filter(subscription::Column::UserId.eq(existing.user_id))
+        .filter(
+            Condition::any()
+                .add(subscription::Column::LastEventAt.is_null())
+                .add(subscription::Column::LastEventAt.lte(created.fixed_offset())),
+        );
+    let (update, plan) = if subscription_event {
+        let status = if event.kind == "customer.subscription.deleted" {
+            "canceled".to_string()
+        } else {
+            string_field(object, "status").unwrap_or_default()
+        };
+        let period_end = current_period_end(object).map(|t| t.fixed_offset());
+        let update = update
+            .col_expr(
+                subscription::Column::StripeSubscriptionId,
+                Expr::value(string_field(object, "id")),
+            )
+            .col_expr(subscription::Column::Status, Expr::value(status.clone()))
+            .col_expr(
+                subscription::Column::CurrentPeriodEnd,
+                Expr::value(period_end),
+            );
+        (update, Some((status, period_end)))
+    } else {
+        let update = update.col_expr(
+            subscription::Column::StripeSubscriptionId,
+            Expr::value(string_field(object, "subscription")),
+        );
+        (update, None)
+    };
+    if update.exec(&txn).await?.rows_affected == 0 {
+        tracing::info!(
+            event_id = %event.id,
+            kind = %event.kind,
+            "Ignoring Stripe event older than the last one applied"
+        );
+        return txn.commit().await;
+    }
+
+    if let Some((status, period_end)) = plan {
+        let mut account = user::ActiveModel {
+            id: Set(existing.user_id),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        if LIVE_STATUSES.contains(&status.as_str()) {
+            account.subscription_plan = Set(PRO_PLAN.to_string());
+            account.subscription_expires_at = Set(period_end);
+        } else if status != "incomplete" {
+            account.subscription_plan = Set(FREE_PLAN.to_string());
+            account.subscription_expires_at = Set(None);
+        }
+        account.update(&txn).await?;
+    }
+    txn.commit().await
+}
+
+/// `user`'s Stripe customer, created on their first checkout.
+async fn customer_id(
+    db: &DatabaseConnection,
+    config: &Config,
+    user: &user::Model,
+) -> anyhow::Result<String> {
+    if let Some(existing) = subscription::Entity::find_by_id(user.id).one(db).await? {
+        return Ok(existing.stripe_customer_id);
+    }
+
+    let user_id = user.id.to_string();
+    let customer = post_form(
+        config,
+        "/v1/customers",
+        &[
+            ("email", user.email.as_str()),
+            ("metadata[user_id]", user_id.as_str()),
+        ],
+    )
+    .await?;
+    let customer_id = string_field(&customer, "id")
+        .ok_or_else(|| anyhow::anyhow!("Stripe customer has no ID"))?;
+    // A concurrent checkout may have stored its customer first; both then use that one
+    subscription::Entity::insert(subscription::ActiveModel {
+        user_id: Set(user.id),
+        stripe_customer_id: Set(customer_id),
+        stripe_subscription_id: Set(None),
+        status: Set("incomplete".to_string()),
+        current_period_end: Set(None),
+        last_event_at: Set(None),
+        updated_at: Set(Utc::now().fixed_offset()),
+    })
+    .on_conflict(
+        OnConflict::column(subscription::Column::UserId)
+            .do_nothing()
+            .to_owned(),
+    )
+    .exec_without_returning(db)
+    .await?;
+
+    subscription::Entity::find_by_id(user.id)
+        .one(db)
+        .await?
+        .map(|s| s.stripe_customer_id)
+        .ok_or_else(|| anyhow::anyhow!("Subscription of user {} disappeared", user.id))
+}
+
+/// POST a form to the Stripe API and return the JSON response.
+async fn post_form(config: &Config, path: &str, form: &[(&str, &str)]) -> anyhow::Result<Value> {
+    let resp = reqwest::Client::new()
+        .post(format!("{}{path}", config.stripe_api_url))
+        .bearer_auth(&config.stripe_secret_key)
+        .form(form)
+        .send()
+        .await
+        .map_err(|e| anyhow::anyhow!("Stripe request to {path} failed: {e}"))?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        return Err(anyhow::anyhow!(
+            "Stripe request to {path} failed ({status}): {body}"
+        ));
+    }
+    resp.json()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to parse Stripe response from {path}: {e}"))
+}
+
+fn string_field(object: &Value, key: &str) -> Option<String> {
+    object.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+/// End of a subscription's current period. Newer API versions report it per item instead.
+fn current_period_end(subscription: &Value) -> Option<DateTime<Utc>> {
+    subscription
+        .get("current_period_end")
+        .or_else(|| subscription.pointer("/items/data/0/current_period_end"))
+        .and_then(Value::as_i64)
+        .and_then(|t| DateTime::from_timestamp(t, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "whsec_test";
+
+    /// The `Stripe-Signature` header Stripe would send for `payload` at unix time `timestamp`.
+    fn signature_header(payload: &[u8], secret: &str, timestamp: i64) -> String {
+        let signature = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).map_or_else(
+            |_| String::new(),
+            |mut mac| {
+                mac.update(format!("{timestamp}.").as_bytes());
+                mac.update(payload);
+                hex::encode(mac.finalize().into_bytes())
+            },
+        );
+        format!("t={timestamp},v1={signature}")
+    }
+
+    #[test]
+    fn signatures_verify_within_the_tolerance() {
+        let payload = br#"{"type":"ping"}"#;
+        let header = signature_header(payload, SECRET, 1_000);
+        assert!(verify_signature(payload, &header, SECRET, 1_100).is_ok());
+        assert!(verify_signature(payload, &header, SECRET, 1_000 + 301).is_err());
+        assert!(verify_signature(payload, &header, "whsec_other", 1_100).is_err());
+        assert!(verify_signature(b"{}", &header, SECRET, 1_100).is_err());
+        assert!(verify_signature(payload, "v1=abc", SECRET, 1_100).is_err());
+    }
+
+    #[test]
+    fn period_end_is_read_from_either_place() {
+        let legacy = serde_json::json!({ "current_period_end": 1_700_000_000 });
+        let per_item = serde_json::json!({
+            "items": { "data": [{ "current_period_end": 1_700_000_000 }] }
+        });
+        assert_eq!(current_period_end(&legacy), current_period_end(&per_item));
+        assert!(current_period_end(&legacy).is_some());
+    }
+}
@@ -0,0 +1,149 @@
+//! Classroom mode: teacher-managed student accounts.
+//!
+//! A teacher creates a class and bulk-creates student accounts in it. Students have no email;
+//! they sign in with the class code, their generated username and a password the teacher
+//! hands out (and can reset). Student accounts only reach the endpoints in [`is_allowed`] and
+//! only see the games of their class: those owned by the teacher or a classmate.
+
+use axum::http::Method;
+use rand::Rng;
+use sea_orm::{DatabaseConnection, DbErr, EntityTrait};
+use uuid::Uuid;
+
+use crate::entities::{classroom, classroom_student, game};
+
+/// `user.role` of managed student accounts.
+pub const STUDENT_ROLE: &str = "student";
+
+/// `auth_provider.provider` of a student's password, set by their teacher.
+pub const STUDENT_PROVIDER: &str = "classroom";
+
+/// Characters used for class codes and student passwords — excludes ambiguous chars
+/// (0/O, 1/I/L).
+const CODE_CHARS: &[u8] = b"ABCDEFGHJKMNPQRSTUVWXYZ23456789";
+
+const CLASS_CODE_LENGTH: usize = 6;
+
+const PASSWORD_LENGTH: usize = 10;
+
+/// Endpoints under `/games/{id}` that list other games, which may be outside the class.
+const GAME_LISTINGS: [&str; 3] = ["/related", "/forks", "/lineage"];
+
+/// Generate a random class code (not yet checked for uniqueness).
+#[must_use]
+pub fn generate_class_code() -> String {
+    random_chars(CLASS_CODE_LENGTH)
+}
+
+/// Generate a password for a student account. Lowercase, so it is easy to type on a
+/// classroom device.
+#[must_use]
+pub fn generate_password() -> String {
+    random_chars(PASSWORD_LENGTH).to_lowercase()
+}
+
+fn random_chars(len: usize) -> String {
+    let mut rng = rand::thread_rng();
+    (0..len)
+        .map(|_| char::from(CODE_CHARS[rng.gen_range(0..CODE_CHARS.len())]))
+        .collect()
+}
+
+/// Whether a student account may call `method` on `path`.
+///
+/// Students can manage their own profile and preferences, see their class, make and play
+/// games, and sign out. Everything else (browsing the public catalog, social features,
+/// credentials and account management) is left to the teacher.
+#[must_use]
+pub fn is_allowed(method: &Method, path: &str) -> bool {
+    let path = path
+        .strip_prefix("/api/v1")
+        .unwrap_or(path)
+        .trim_end_matches('/');
+    let under = |prefix: &str| {
+        path == prefix
+            || path
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.starts_with('/'))
+    };
+
+    match path {
+        "/users/me" => *method == Method::GET || *method == Method::PATCH,
+        "/users/me/preferences" | "/classrooms/mine" | "/auth/signout" => true,
+        "/games" => *method == Method::POST,
+        _ => {
+            (under("/games") && !GAME_LISTINGS.iter().any(|suffix| path.ends_with(suffix)))
+                || under("/sessions")
+        }
+    }
+}
+
+/// The class `user_id` is a student in, if they are a student.
+///
+/// # Errors
+///
+/// Returns [`DbErr`] if the database query fails.
+pub async fn student_classroom(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+) -> Result<Option<classroom::Model>, DbErr> {
+    let Some(student) = classroom_student::Entity::find_by_id(user_id)
+        .one(db)
+        .await?
+    else {
+        return Ok(None);
+    };
+    classroom::Entity::find_by_id(student.classroom_id)
+        .one(db)
+        .await
+}
+
+/// Whether `game` belongs to `classroom`: it is owned by the teacher or one of the students.
+///
+/// # Errors
+///
+/// Returns [`DbErr`] if the database query fails.
+pub async fn is_class_game(
+    db: &DatabaseConnection,
+    classroom: &classroom::Model,
+    game: &game::Model,
+) -> Result<bool, DbErr> {
+    if game.owner_id == classroom.teacher_id {
+        return Ok(true);
+    }
+    Ok(classroom_student::Entity::find_by_id(game.owner_id)
+        .one(db)
+        .await?
+        .is_some_and(|s| s.classroom_id == classroom.id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn students_reach_only_class_endpoints() {
+        assert!(is_allowed(&Method::GET, "/api/v1/users/me"));
+        assert!(is_allowed(&Method::GET, "/api/v1/classrooms/mine"));
+        assert!(is_allowed(&Method::POST, "/api/v1/games"));
+        assert!(is_allowed(&Method::PATCH, "/api/v1/games/abc"));
+        assert!(is_allowed(&Method::POST, "/api/v1/sessions/ABCDE/join"));
+
+        assert!(!is_allowed(&Method::GET, "/api/v1/games"));
+        assert!(!is_allowed(&Method::GET, "/api/v1/games/abc/related"));
+        assert!(!is_allowed(&Method::GET, "/api/v1/games/abc/forks"));
+        assert!(!is_allowed(&Method::GET, "/api/v1/search/games"));
+        assert!(!is_allowed(&Method::POST, "/api/v1/users/me/delete"));
+        assert!(!is_allowed(&Method::POST, "/api/v1/auth/password/change"));
+        assert!(!is_allowed(&Method::GET, "/api/v1/users/someone"));
+        assert!(!is_allowed(&Method::GET, "/api/v1/gamesx"));
+    }
+
+    #[test]
+    fn codes_use_the_unambiguous_alphabet() {
+        let code = generate_class_code();
+        assert_eq!(code.len(), CLASS_CODE_LENGTH);
+        assert!(code.bytes().all(|c| CODE_CHARS.contains(&c)));
+        assert_eq!(generate_password().len(), PASSWORD_LENGTH);
+    }
+}
@@ -1,5 +1,11 @@
 use std::net::{IpAddr, SocketAddr};
 
+use crate::auth::password::{PasswordPolicy, UsernameBlocklist};
+use crate::middleware::ip_filter::{self, Cidr};
+use crate::middleware::rate_limit::{self, RateLimitPolicy, RateLimits};
+use crate::text_moderation::{self, TextModeration};
+use crate::validation::{self, ScanRule};
+
 /// Application configuration loaded from environment variables.
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -8,7 +14,30 @@ pub struct Config {
     pub server_port: u16,
     pub environment: Environment,
     pub log_level: String,
+    pub log_format: LogFormat,
+    /// Sentry DSN that internal errors and panics are reported to; empty disables reporting.
+    pub sentry_dsn: String,
+    /// Statements taking at least this many milliseconds are logged with the request that
+    /// ran them; 0 disables slow statement logging.
+    pub slow_query_threshold_ms: u64,
+    /// Slack or Discord compatible webhook that error-rate alerts are posted to; empty
+    /// disables alerting.
+    pub alert_webhook_url: String,
+    /// Share of requests answered with a 5xx status that triggers an alert.
+    pub alert_error_rate: f64,
+    /// Share of `WebSocket` sends failing on closed connections that triggers an alert.
+    pub alert_ws_failure_rate: f64,
+    /// IP-to-country database client countries are resolved with (see [`crate::geo`]);
+    /// empty disables the lookup.
+    pub geoip_database: String,
     pub jwt_secret: String,
+    /// Retired secrets that still validate tokens during a rotation.
+    pub jwt_previous_secrets: Vec<String>,
+    /// PEM-encoded PKCS#8 P-256 key; when set, tokens are signed with ES256 instead of
+    /// `jwt_secret` and the public key is published at `/.well-known/jwks.json`.
+    pub jwt_private_key: String,
+    /// Concatenated PEM public keys of retired private keys that still validate tokens.
+    pub jwt_previous_public_keys: String,
     pub jwt_access_expiration_secs: u64,
     pub jwt_refresh_expiration_secs: u64,
     pub google_client_id: String,
@@ -17,10 +46,89 @@ pub struct Config {
     pub github_client_id: String,
     pub github_client_secret: String,
     pub github_redirect_uri: String,
+    pub apple_client_id: String,
+    pub apple_team_id: String,
+    pub apple_key_id: String,
+    /// PEM-encoded ES256 private key (the `.p8` file) that signs Apple client secrets.
+    pub apple_private_key: String,
+    pub apple_redirect_uri: String,
+    /// Operator-configured `OpenID` Connect providers (Keycloak, Authentik, ...).
+    pub oidc_providers: Vec<OidcProviderConfig>,
     pub frontend_url: String,
     pub upload_dir: String,
+    pub storage_backend: StorageBackend,
+    pub s3_endpoint: String,
+    pub s3_bucket: String,
+    pub s3_region: String,
+    pub s3_access_key_id: String,
+    pub s3_secret_access_key: String,
+    pub code_scan_rules: Vec<ScanRule>,
+    /// Days soft-deleted rows are kept before the purge job removes them.
+    pub deleted_retention_days: i64,
+    /// Reject passwords found in the Have I Been Pwned breach corpus.
+    pub hibp_enabled: bool,
+    /// Base URL of the Pwned Passwords range API.
+    pub hibp_api_url: String,
+    /// Networks allowed to reach any route; empty allows everyone.
+    pub allowed_ips: Vec<Cidr>,
+    /// Networks allowed to reach `/api/v1/admin/...`; empty allows everyone.
+    pub admin_allowed_ips: Vec<Cidr>,
+    /// Networks refused on every route.
+    pub denied_ips: Vec<Cidr>,
+    /// Reverse proxies whose `X-Forwarded-For` hops are believed (see
+    /// [`crate::middleware::proxy::TrustedProxies`]); empty ignores the header.
+    pub trusted_proxies: Vec<Cidr>,
+    /// Deliver token pairs as `HttpOnly` cookies instead of in response bodies, with
+    /// double-submit CSRF protection for requests they authenticate.
+    pub auth_cookies: bool,
+    /// Rules new passwords must follow.
+    pub password_policy: PasswordPolicy,
+    /// Usernames refused at signup and rename.
+    pub username_blocklist: UsernameBlocklist,
+    /// How game text, reviews and display names are screened.
+    pub text_moderation: TextModeration,
+    /// Quotas of the rate-limited route groups.
+    pub rate_limits: RateLimits,
+    /// Stripe API secret key; billing is disabled while it is empty.
+    pub stripe_secret_key: String,
+    /// Signing secret of the Stripe webhook endpoint (`whsec_...`).
+    pub stripe_webhook_secret: String,
+    /// Stripe price the pro plan is sold at.
+    pub stripe_pro_price_id: String,
+    /// Base URL of the Stripe API.
+    pub stripe_api_url: String,
+}
+
+/// Where uploaded game asset bytes are stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageBackend {
+    /// `storage_object` table in the main database (default; fine for development).
+    Database,
+    /// Files under `UPLOAD_DIR/assets`.
+    Filesystem,
+    /// An S3-compatible bucket (AWS S3, Cloudflare R2, `MinIO`, ...).
+    S3,
 }
 
+/// An `OpenID` Connect provider users can sign in with at `/auth/oidc/{name}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OidcProviderConfig {
+    /// Identifier used in URLs and stored as the auth provider name (e.g. `keycloak`).
+    pub name: String,
+    /// Label for sign-in buttons.
+    pub display_name: String,
+    /// Issuer URL; the discovery document is fetched from
+    /// `{issuer}/.well-known/openid-configuration`.
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_uri: String,
+    pub scopes: Vec<String>,
+}
+
+/// Provider names taken by the built-in sign-in methods.
+const RESERVED_PROVIDER_NAMES: [&str; 4] = ["email", "google", "github", "apple"];
+
 /// Deployment environment.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Environment {
@@ -29,18 +137,33 @@ pub enum Environment {
     Production,
 }
 
+/// How log lines are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable lines, for terminals.
+    Text,
+    /// One JSON object per line (see [`crate::logging::JsonFormat`]).
+    Json,
+}
+
 impl Config {
     /// Load configuration from environment variables.
     ///
     /// Required: `DATABASE_URL`
-    /// Optional with defaults: `SERVER_HOST`, `SERVER_PORT`, `ENVIRONMENT`, `LOG_LEVEL`
+    /// Optional with defaults: `SERVER_HOST`, `SERVER_PORT`, `ENVIRONMENT`, `LOG_LEVEL`,
+    /// `LOG_FORMAT`, `SENTRY_DSN`, `SLOW_QUERY_THRESHOLD_MS`,
+    /// `ALERT_WEBHOOK_URL` (plus `ALERT_ERROR_RATE`, `ALERT_WS_FAILURE_RATE`), `GEOIP_DATABASE`,
+    /// `STORAGE_BACKEND` (plus `S3_*` when it is `s3`), `CODE_SCAN_RULES`,
+    /// `DELETED_RETENTION_DAYS`, `OIDC_PROVIDERS` (plus `OIDC_{NAME}_*` for each provider),
+    /// `RATE_LIMIT_{GROUP}_{IP,ACCOUNT}` for the `CREDENTIALS`, `JOIN` and `ANALYTICS` groups
     ///
     /// On Railway, `PORT` overrides `SERVER_PORT` and host defaults to `0.0.0.0`.
     ///
     /// # Errors
     ///
-    /// Returns an error if `DATABASE_URL` is not set, or if `SERVER_HOST` / `SERVER_PORT`
-    /// contain invalid values.
+    /// Returns an error if `DATABASE_URL` is not set, if `SERVER_HOST` / `SERVER_PORT`
+    /// contain invalid values, or if the storage backend or an OIDC provider is misconfigured.
+    #[allow(clippy::too_many_lines)]
     pub fn from_env() -> anyhow::Result<Self> {
         dotenvy::dotenv().ok();
 
@@ -76,9 +199,43 @@ impl Config {
             .map_err(|_| anyhow::anyhow!("SERVER_HOST must be a valid IP address"))?;
 
         let log_level = std::env::var("LOG_LEVEL").unwrap_or_else(|_| "info".to_string());
+        let log_format = match std::env::var("LOG_FORMAT")
+            .unwrap_or_else(|_| "text".to_string())
+            .as_str()
+        {
+            "text" => LogFormat::Text,
+            "json" => LogFormat::Json,
+            other => anyhow::bail!("LOG_FORMAT must be one of text, json (got '{other}')"),
+        };
+        let sentry_dsn = std::env::var("SENTRY_DSN").unwrap_or_default();
+        let slow_query_threshold_ms = std::env::var("SLOW_QUERY_THRESHOLD_MS")
+            .unwrap_or_else(|_| "500".to_string())
+            .parse::<u64>()
+            .map_err(|_| {
+                anyhow::anyhow!("SLOW_QUERY_THRESHOLD_MS must be a non-negative integer")
+            })?;
+        let alert_webhook_url = std::env::var("ALERT_WEBHOOK_URL").unwrap_or_default();
+        let alert_error_rate = parse_rate("ALERT_ERROR_RATE", 0.05)?;
+        let alert_ws_failure_rate = parse_rate("ALERT_WS_FAILURE_RATE", 0.2)?;
+        let geoip_database = std::env::var("GEOIP_DATABASE")
+            .unwrap_or_else(|_| "data/dbip-country-lite.csv.gz".to_string());
 
         let jwt_secret =
             std::env::var("JWT_SECRET").map_err(|_| anyhow::anyhow!("JWT_SECRET must be set"))?;
+        let jwt_previous_secrets = std::env::var("JWT_PREVIOUS_SECRETS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+            .collect();
+        // Single-line env vars carry the PEM's newlines as `\n`
+        let jwt_private_key = std::env::var("JWT_PRIVATE_KEY")
+            .unwrap_or_else(|_| String::new())
+            .replace("\\n", "\n");
+        let jwt_previous_public_keys = std::env::var("JWT_PREVIOUS_PUBLIC_KEYS")
+            .unwrap_or_else(|_| String::new())
+            .replace("\\n", "\n");
 
         let jwt_access_expiration_secs = std::env::var("JWT_ACCESS_EXPIRATION")
             .unwrap_or_else(|_| "900".to_string())
@@ -100,17 +257,117 @@ impl Config {
             std::env::var("GITHUB_CLIENT_SECRET").unwrap_or_else(|_| String::new());
         let github_redirect_uri =
             std::env::var("GITHUB_REDIRECT_URI").unwrap_or_else(|_| String::new());
+        let apple_client_id = std::env::var("APPLE_CLIENT_ID").unwrap_or_else(|_| String::new());
+        let apple_team_id = std::env::var("APPLE_TEAM_ID").unwrap_or_else(|_| String::new());
+        let apple_key_id = std::env::var("APPLE_KEY_ID").unwrap_or_else(|_| String::new());
+        // Single-line env vars carry the PEM's newlines as `\n`
+        let apple_private_key = std::env::var("APPLE_PRIVATE_KEY")
+            .unwrap_or_else(|_| String::new())
+            .replace("\\n", "\n");
+        let apple_redirect_uri =
+            std::env::var("APPLE_REDIRECT_URI").unwrap_or_else(|_| String::new());
+        let oidc_providers = parse_oidc_providers(|key| std::env::var(key).ok())?;
         let frontend_url =
             std::env::var("FRONTEND_URL").unwrap_or_else(|_| "http://localhost:3001".to_string());
         let upload_dir = std::env::var("UPLOAD_DIR").unwrap_or_else(|_| "uploads".to_string());
 
-        Ok(Self {
+        let storage_backend = match std::env::var("STORAGE_BACKEND")
+            .unwrap_or_else(|_| "database".to_string())
+            .as_str()
+        {
+            "database" => StorageBackend::Database,
+            "filesystem" => StorageBackend::Filesystem,
+            "s3" => StorageBackend::S3,
+            other => anyhow::bail!(
+                "STORAGE_BACKEND must be one of database, filesystem, s3 (got '{other}')"
+            ),
+        };
+        let s3_endpoint =
+            std::env::var("S3_ENDPOINT").unwrap_or_else(|_| "https://s3.amazonaws.com".to_string());
+        let s3_bucket = std::env::var("S3_BUCKET").unwrap_or_else(|_| String::new());
+        let s3_region = std::env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string());
+        let s3_access_key_id = std::env::var("S3_ACCESS_KEY_ID").unwrap_or_else(|_| String::new());
+        let s3_secret_access_key =
+            std::env::var("S3_SECRET_ACCESS_KEY").unwrap_or_else(|_| String::new());
+
+        if storage_backend == StorageBackend::S3 && s3_bucket.is_empty() {
+            anyhow::bail!("S3_BUCKET must be set when STORAGE_BACKEND=s3");
+        }
+
+        let code_scan_rules = validation::apply_rule_overrides(
+            validation::default_scan_rules(),
+            &std::env::var("CODE_SCAN_RULES").unwrap_or_default(),
+        )
+        .map_err(|e| anyhow::anyhow!("CODE_SCAN_RULES is invalid: {e}"))?;
+
+        let deleted_retention_days = std::env::var("DELETED_RETENTION_DAYS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse::<i64>()
+            .ok()
+            .filter(|days| *days > 0)
+            .ok_or_else(|| anyhow::anyhow!("DELETED_RETENTION_DAYS must be a positive integer"))?;
+
+        let hibp_enabled = std::env::var("HIBP_ENABLED")
+            .is_ok_and(|v| matches!(v.to_lowercase().as_str(), "true" | "1" | "yes"));
+        let hibp_api_url = std::env::var("HIBP_API_URL")
+            .unwrap_or_else(|_| "https://api.pwnedpasswords.com".to_string());
+
+        let ip_list = |key: &str| {
+            ip_filter::parse_list(&std::env::var(key).unwrap_or_default())
+                .map_err(|e| anyhow::anyhow!("{key} is invalid: {e}"))
+        };
+        let allowed_ips = ip_list("ALLOWED_IPS")?;
+        let admin_allowed_ips = ip_list("ADMIN_ALLOWED_IPS")?;
+        let denied_ips = ip_list("DENIED_IPS")?;
+        let trusted_proxies = ip_list("TRUSTED_PROXIES")?;
+
+        let auth_cookies = std::env::var("AUTH_COOKIES")
+            .is_ok_and(|v| matches!(v.to_lowercase().as_str(), "true" | "1" | "yes"));
+
+        let password_policy = password_policy_from_env()?;
+
+        let mut username_blocklist = UsernameBlocklist::default();
+        let names = |key: &str| {
+            std::env::var(key)
+                .unwrap_or_default()
+                .split(',')
+                .map(|n| n.trim().to_lowercase())
+                .filter(|n| !n.is_empty())
+                .collect::<Vec<_>>()
+        };
+        username_blocklist
+            .reserved
+            .extend(names("USERNAME_RESERVED"));
+        username_blocklist
+            .words
+            .extend(names("USERNAME_BLOCKED_WORDS"));
+
+        let text_moderation = text_moderation_from_env()?;
+        let rate_limits = rate_limits_from_env()?;
+
+        let stripe_secret_key = std::env::var("STRIPE_SECRET_KEY").unwrap_or_default();
+        let stripe_webhook_secret = std::env::var("STRIPE_WEBHOOK_SECRET").unwrap_or_default();
+        let stripe_pro_price_id = std::env::var("STRIPE_PRO_PRICE_ID").unwrap_or_default();
+        let stripe_api_url = std::env::var("STRIPE_API_URL")
+            .unwrap_or_else(|_| "https://api.stripe.com".to_string());
+
+        let config = Self {
             database_url,
             server_host,
             server_port,
             environment,
             log_level,
+            log_format,
+            sentry_dsn,
+            slow_query_threshold_ms,
+            alert_webhook_url,
+            alert_error_rate,
+            alert_ws_failure_rate,
+            geoip_database,
             jwt_secret,
+            jwt_previous_secrets,
+            jwt_private_key,
+            jwt_previous_public_keys,
             jwt_access_expiration_secs,
             jwt_refresh_expiration_secs,
             google_client_id,
@@ -119,9 +376,40 @@ impl Config {
             github_client_id,
             github_client_secret,
             github_redirect_uri,
+            apple_client_id,
+            apple_team_id,
+            apple_key_id,
+            apple_private_key,
+            apple_redirect_uri,
+            oidc_providers,
             frontend_url,
             upload_dir,
-        })
+            storage_backend,
+            s3_endpoint,
+            s3_bucket,
+            s3_region,
+            s3_access_key_id,
+            s3_secret_access_key,
+            code_scan_rules,
+            deleted_retention_days,
+            hibp_enabled,
+            hibp_api_url,
+            allowed_ips,
+            admin_allowed_ips,
+            denied_ips,
+            trusted_proxies,
+            auth_cookies,
+            password_policy,
+            username_blocklist,
+            text_moderation,
+            rate_limits,
+            stripe_secret_key,
+            stripe_webhook_secret,
+            stripe_pro_price_id,
+            stripe_api_url,
+        };
+
+        Ok(config)
     }
 
     /// Build the socket address for the server to bind to.
@@ -131,6 +419,169 @@ impl Config {
     }
 }
 
+/// Read the providers listed in `OIDC_PROVIDERS` (comma-separated names), each configured by
+/// `OIDC_{NAME}_ISSUER`, `_CLIENT_ID`, `_CLIENT_SECRET`, `_REDIRECT_URI` and optionally
+/// `_SCOPES` (space-separated, default `openid email profile`) and `_DISPLAY_NAME`.
+///
+/// `NAME` is the provider name upper-cased, with `-` replaced by `_`.
+fn parse_oidc_providers(
+    var: impl Fn(&str) -> Option<String>,
+) -> anyhow::Result<Vec<OidcProviderConfig>> {
+    let names = var("OIDC_PROVIDERS").unwrap_or_default();
+    let mut providers: Vec<OidcProviderConfig> = Vec::new();
+
+    for name in names.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        let name = name.to_lowercase();
+        let valid_name = name.len() <= 32
+            && name.starts_with(|c: char| c.is_ascii_alphanumeric())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if !valid_name {
+            anyhow::bail!("OIDC_PROVIDERS: '{name}' must be at most 32 letters, digits or dashes");
+        }
+        if RESERVED_PROVIDER_NAMES.contains(&name.as_str()) {
+            anyhow::bail!("OIDC_PROVIDERS: '{name}' is reserved for a built-in provider");
+        }
+        if providers.iter().any(|p| p.name == name) {
+            anyhow::bail!("OIDC_PROVIDERS: '{name}' is listed twice");
+        }
+
+        let prefix = format!("OIDC_{}_", name.to_uppercase().replace('-', "_"));
+        let setting = |key: &str| var(&format!("{prefix}{key}")).filter(|v| !v.trim().is_empty());
+        let required =
+            |key: &str| setting(key).ok_or_else(|| anyhow::anyhow!("{prefix}{key} must be set"));
+
+        let mut scopes: Vec<String> = setting("SCOPES")
+            .unwrap_or_else(|| "openid email profile".to_string())
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+        if !scopes.iter().any(|s| s == "openid") {
+            scopes.insert(0, "openid".to_string());
+        }
+
+        providers.push(OidcProviderConfig {
+            display_name: setting("DISPLAY_NAME").unwrap_or_else(|| name.clone()),
+            issuer: required("ISSUER")?.trim_end_matches('/').to_string(),
+            client_id: required("CLIENT_ID")?,
+            client_secret: required("CLIENT_SECRET")?,
+            redirect_uri: required("REDIRECT_URI")?,
+            scopes,
+            name,
+        });
+    }
+
+    Ok(providers)
+}
+
+/// Read the `PASSWORD_*` settings, falling back to [`PasswordPolicy::default`] for each one
+/// that is unset. `PASSWORD_BANNED` is a comma-separated list.
+fn password_policy_from_env() -> anyhow::Result<PasswordPolicy> {
+    let defaults = PasswordPolicy::default();
+    let length = |key: &str, default: usize| {
+        std::env::var(key).map_or(Ok(default), |v| {
+            v.parse::<usize>()
+                .ok()
+                .filter(|len| *len > 0)
+                .ok_or_else(|| anyhow::anyhow!("{key} must be a positive integer"))
+        })
+    };
+    let flag = |key: &str| {
+        std::env::var(key).is_ok_and(|v| matches!(v.to_lowercase().as_str(), "true" | "1" | "yes"))
+    };
+
+    let min_length = length("PASSWORD_MIN_LENGTH", defaults.min_length)?;
+    let max_length = length("PASSWORD_MAX_LENGTH", defaults.max_length)?;
+    if max_length < min_length {
+        anyhow::bail!("PASSWORD_MAX_LENGTH must not be less than PASSWORD_MIN_LENGTH");
+    }
+
+    Ok(PasswordPolicy {
+        min_length,
+        max_length,
+        require_uppercase: flag("PASSWORD_REQUIRE_UPPERCASE"),
+        require_lowercase: flag("PASSWORD_REQUIRE_LOWERCASE"),
+        require_digit: flag("PASSWORD_REQUIRE_DIGIT"),
+        require_symbol: flag("PASSWORD_REQUIRE_SYMBOL"),
+        banned: std::env::var("PASSWORD_BANNED")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(String::from)
+            .collect(),
+    })
+}
+
+/// Read the `TEXT_MODERATION_*` settings, falling back to [`TextModeration::default`] for
+/// each one that is unset. `TEXT_MODERATION_WORDS` is a comma-separated list of `term` or
+/// `term:severity` entries.
+fn text_moderation_from_env() -> anyhow::Result<TextModeration> {
+    let defaults = TextModeration::default();
+    let threshold = |key: &str, default: u8| {
+        std::env::var(key).map_or(Ok(default), |v| {
+            v.parse::<u8>()
+                .ok()
+                .filter(|t| (1..=100).contains(t))
+                .ok_or_else(|| anyhow::anyhow!("{key} must be a number from 1 to 100"))
+        })
+    };
+
+    let words =
+        text_moderation::parse_words(&std::env::var("TEXT_MODERATION_WORDS").unwrap_or_default())
+            .map_err(|e| anyhow::anyhow!("TEXT_MODERATION_WORDS is invalid: {e}"))?;
+    let block_threshold = threshold("TEXT_MODERATION_BLOCK_THRESHOLD", defaults.block_threshold)?;
+    let flag_threshold = threshold("TEXT_MODERATION_FLAG_THRESHOLD", defaults.flag_threshold)?;
+    if flag_threshold > block_threshold {
+        anyhow::bail!(
+            "TEXT_MODERATION_FLAG_THRESHOLD must not be greater than \
+             TEXT_MODERATION_BLOCK_THRESHOLD"
+        );
+    }
+
+    Ok(TextModeration {
+        words,
+        block_threshold,
+        flag_threshold,
+        api_url: std::env::var("TEXT_MODERATION_API_URL").unwrap_or_default(),
+        api_key: std::env::var("TEXT_MODERATION_API_KEY").unwrap_or_default(),
+    })
+}
+
+/// Read the `RATE_LIMIT_{GROUP}_IP` and `RATE_LIMIT_{GROUP}_ACCOUNT` quotas (see
+/// [`rate_limit::parse_quota`]), falling back to [`RateLimits::default`] for each one that is
+/// unset.
+fn rate_limits_from_env() -> anyhow::Result<RateLimits> {
+    let defaults = RateLimits::default();
+    let policy = |group: &str, default: RateLimitPolicy| -> anyhow::Result<RateLimitPolicy> {
+        let quota = |scope: &str, default| {
+            let key = format!("RATE_LIMIT_{group}_{scope}");
+            std::env::var(&key).map_or(Ok(default), |v| {
+                rate_limit::parse_quota(&v).map_err(|e| anyhow::anyhow!("{key} is invalid: {e}"))
+            })
+        };
+        Ok(RateLimitPolicy {
+            per_ip: quota("IP", default.per_ip)?,
+            per_account: quota("ACCOUNT", default.per_account)?,
+        })
+    };
+
+    Ok(RateLimits {
+        credentials: policy("CREDENTIALS", defaults.credentials)?,
+        join: policy("JOIN", defaults.join)?,
+        analytics: policy("ANALYTICS", defaults.analytics)?,
+    })
+}
+
+/// Read a fraction from 0 to 1 from `key`, or `default` if it is unset.
+fn parse_rate(key: &str, default: f64) -> anyhow::Result<f64> {
+    std::env::var(key).map_or(Ok(default), |v| {
+        v.parse::<f64>()
+            .ok()
+            .filter(|rate| (0.0..=1.0).contains(rate))
+            .ok_or_else(|| anyhow::anyhow!("{key} must be a number from 0 to 1"))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -143,7 +594,17 @@ mod tests {
             server_port: 3000,
             environment: Environment::Development,
             log_level: "info".to_string(),
+            log_format: LogFormat::Text,
+            sentry_dsn: String::new(),
+            slow_query_threshold_ms: 500,
+            alert_webhook_url: String::new(),
+            alert_error_rate: 0.05,
+            alert_ws_failure_rate: 0.2,
+            geoip_database: String::new(),
             jwt_secret: "test-secret".to_string(),
+            jwt_previous_secrets: Vec::new(),
+            jwt_private_key: String::new(),
+            jwt_previous_public_keys: String::new(),
             jwt_access_expiration_secs: 900,
             jwt_refresh_expiration_secs: 604_800,
             google_client_id: String::new(),
@@ -152,10 +613,82 @@ mod tests {
             github_client_id: String::new(),
             github_client_secret: String::new(),
             github_redirect_uri: String::new(),
+            apple_client_id: String::new(),
+            apple_team_id: String::new(),
+            apple_key_id: String::new(),
+            apple_private_key: String::new(),
+            apple_redirect_uri: String::new(),
+            oidc_providers: Vec::new(),
             frontend_url: "http://localhost:3001".to_string(),
             upload_dir: "test_uploads".to_string(),
+            storage_backend: StorageBackend::Database,
+            s3_endpoint: String::new(),
+            s3_bucket: String::new(),
+            s3_region: String::new(),
+            s3_access_key_id: String::new(),
+            s3_secret_access_key: String::new(),
+            code_scan_rules: validation::default_scan_rules(),
+            deleted_retention_days: 30,
+            hibp_enabled: false,
+            hibp_api_url: String::new(),
+            allowed_ips: Vec::new(),
+            admin_allowed_ips: Vec::new(),
+            denied_ips: Vec::new(),
+            trusted_proxies: Vec::new(),
+            auth_cookies: false,
+            password_policy: crate::auth::password::PasswordPolicy::default(),
+            username_blocklist: crate::auth::password::UsernameBlocklist::default(),
+            text_moderation: crate::text_moderation::TextModeration::default(),
+            rate_limits: RateLimits::default(),
+            stripe_secret_key: String::new(),
+            stripe_webhook_secret: String::new(),
+            stripe_pro_price_id: String::new(),
+            stripe_api_url: String::new(),
         };
         let addr = config.socket_addr();
         assert_eq!(addr.port(), 3000);
     }
+
+    #[test]
+    fn test_parse_oidc_providers() {
+        let env = |vars: &[(&str, &str)]| {
+            let vars: std::collections::HashMap<String, String> = vars
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect();
+            move |key: &str| vars.get(key).cloned()
+        };
+
+        let providers = parse_oidc_providers(env(&[
+            ("OIDC_PROVIDERS", "Keycloak, my-sso"),
+            (
+                "OIDC_KEYCLOAK_ISSUER",
+                "https://sso.example.com/realms/main/",
+            ),
+            ("OIDC_KEYCLOAK_CLIENT_ID", "aircade"),
+            ("OIDC_KEYCLOAK_CLIENT_SECRET", "secret"),
+            ("OIDC_KEYCLOAK_REDIRECT_URI", "https://api.example.com/cb"),
+            ("OIDC_KEYCLOAK_DISPLAY_NAME", "Company SSO"),
+            ("OIDC_MY_SSO_ISSUER", "https://auth.example.com"),
+            ("OIDC_MY_SSO_CLIENT_ID", "aircade"),
+            ("OIDC_MY_SSO_CLIENT_SECRET", "secret"),
+            ("OIDC_MY_SSO_REDIRECT_URI", "https://api.example.com/cb2"),
+            ("OIDC_MY_SSO_SCOPES", "email groups"),
+        ]))
+        .unwrap_or_default();
+
+        assert_eq!(providers.len(), 2);
+        assert_eq!(providers[0].name, "keycloak");
+        assert_eq!(providers[0].display_name, "Company SSO");
+        assert_eq!(providers[0].issuer, "https://sso.example.com/realms/main");
+        assert_eq!(providers[0].scopes, ["openid", "email", "profile"]);
+        assert_eq!(providers[1].name, "my-sso");
+        assert_eq!(providers[1].display_name, "my-sso");
+        assert_eq!(providers[1].scopes, ["openid", "email", "groups"]);
+
+        assert!(parse_oidc_providers(env(&[])).is_ok_and(|p| p.is_empty()));
+        assert!(parse_oidc_providers(env(&[("OIDC_PROVIDERS", "google")])).is_err());
+        assert!(parse_oidc_providers(env(&[("OIDC_PROVIDERS", "a b")])).is_err());
+        assert!(parse_oidc_providers(env(&[("OIDC_PROVIDERS", "keycloak")])).is_err());
+    }
 }
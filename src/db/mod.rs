@@ -1,21 +1,74 @@
-use sea_orm::{ConnectOptions, Database, DatabaseConnection};
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+use log::LevelFilter;
+use sea_orm::{ConnectOptions, ConnectionTrait, Database, DatabaseConnection, DbBackend, sqlx};
+use serde::Serialize;
 
 /// Establish a connection to the database with connection pooling.
 ///
+/// Statements taking at least `slow_query_threshold` are logged as warnings on the
+/// `sqlx::query` target, inside the span of the request that ran them; a zero threshold
+/// turns statement logging off.
+///
 /// # Errors
 ///
 /// Returns an error if the connection cannot be established.
-pub async fn connect(database_url: &str) -> anyhow::Result<DatabaseConnection> {
+pub async fn connect(
+    database_url: &str,
+    slow_query_threshold: Duration,
+) -> anyhow::Result<DatabaseConnection> {
     let mut opts = ConnectOptions::new(database_url);
     opts.max_connections(20)
         .min_connections(2)
         .connect_timeout(Duration::from_secs(5))
         .acquire_timeout(Duration::from_secs(5))
-        .idle_timeout(Duration::from_secs(300))
-        .max_lifetime(Duration::from_secs(1800))
-        .sqlx_logging(false);
+        .idle_timeout(Duration::from_mins(5))
+        .max_lifetime(Duration::from_mins(30))
+        .sqlx_logging(!slow_query_threshold.is_zero())
+        .sqlx_logging_level(LevelFilter::Off)
+        .sqlx_slow_statements_logging_settings(LevelFilter::Warn, slow_query_threshold);
 
     let db = Database::connect(opts).await?;
     Ok(db)
 }
+
+/// Utilization of the connection pool.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolMetrics {
+    /// Open connections, in use or idle.
+    pub size: u32,
+    pub in_use: u32,
+    pub idle: u32,
+    pub max_connections: u32,
+    /// How long checking out a connection took when the metrics were taken; high values
+    /// mean requests are queueing for connections.
+    pub acquire_wait_ms: u64,
+}
+
+/// Sample the utilization of `db`'s connection pool, or `None` for backends without one.
+pub async fn pool_metrics(db: &DatabaseConnection) -> Option<PoolMetrics> {
+    match db.get_database_backend() {
+        DbBackend::Postgres => Some(sample(db.get_postgres_connection_pool()).await),
+        DbBackend::Sqlite => Some(sample(db.get_sqlite_connection_pool()).await),
+        DbBackend::MySql => None,
+    }
+}
+
+async fn sample<DB: sqlx::Database>(pool: &sqlx::Pool<DB>) -> PoolMetrics {
+    let size = pool.size();
+    let idle = u32::try_from(pool.num_idle()).unwrap_or(u32::MAX);
+
+    let started = Instant::now();
+    // Times out after the pool's acquire timeout, which is then the wait reported
+    let _connection = pool.acquire().await;
+    let acquire_wait = started.elapsed();
+
+    PoolMetrics {
+        size,
+        in_use: size.saturating_sub(idle),
+        idle,
+        max_connections: pool.options().get_max_connections(),
+        acquire_wait_ms: u64::try_from(acquire_wait.as_millis()).unwrap_or(u64::MAX),
+    }
+}
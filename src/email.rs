@@ -0,0 +1,126 @@
+//! Outgoing email.
+//!
+//! Delivery is not implemented yet, so messages are logged instead of sent. Transactional
+//! mail (verification, password reset, email change) always goes out. Everything else is
+//! sent with [`send_to_user`], which drops the message if the recipient has opted out of
+//! its [`Category`].
+
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::entities::{notification_preference, user};
+
+/// The kinds of non-transactional email, each of which a user can turn off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    ProductUpdates,
+    ReviewAlerts,
+    SecurityAlerts,
+    SessionSummaries,
+    SessionInvites,
+}
+
+/// Which categories of non-transactional email a user wants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Preferences {
+    pub product_updates: bool,
+    pub review_alerts: bool,
+    pub security_alerts: bool,
+    pub session_summaries: bool,
+    pub session_invites: bool,
+}
+
+impl Default for Preferences {
+    /// Everything but product updates, which are opt-in.
+    fn default() -> Self {
+        Self {
+            product_updates: false,
+            review_alerts: true,
+            security_alerts: true,
+            session_summaries: true,
+            session_invites: true,
+        }
+    }
+}
+
+impl From<notification_preference::Model> for Preferences {
+    fn from(model: notification_preference::Model) -> Self {
+        Self {
+            product_updates: model.product_updates,
+            review_alerts: model.review_alerts,
+            security_alerts: model.security_alerts,
+            session_summaries: model.session_summaries,
+            session_invites: model.session_invites,
+        }
+    }
+}
+
+impl Preferences {
+    /// Whether mail of `category` may be sent.
+    #[must_use]
+    pub const fn allows(&self, category: Category) -> bool {
+        match category {
+            Category::ProductUpdates => self.product_updates,
+            Category::ReviewAlerts => self.review_alerts,
+            Category::SecurityAlerts => self.security_alerts,
+            Category::SessionSummaries => self.session_summaries,
+            Category::SessionInvites => self.session_invites,
+        }
+    }
+}
+
+/// Load `user_id`'s preferences, falling back to the defaults if they never set any.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub async fn preferences<C: ConnectionTrait>(db: &C, user_id: Uuid) -> Result<Preferences, DbErr> {
+    Ok(notification_preference::Entity::find_by_id(user_id)
+        .one(db)
+        .await?
+        .map(Preferences::from)
+        .unwrap_or_default())
+}
+
+/// Email `user_id` unless they have opted out of `category`.
+///
+/// Returns whether the message was sent. Nothing is sent to deleted, suspended or guest
+/// accounts either.
+///
+/// # Errors
+///
+/// Returns an error if a database query fails.
+pub async fn send_to_user<C: ConnectionTrait>(
+    db: &C,
+    user_id: Uuid,
+    category: Category,
+    subject: &str,
+    body: &str,
+) -> anyhow::Result<bool> {
+    let Some(recipient) = user::Entity::find_by_id(user_id)
+        .filter(user::Column::DeletedAt.is_null())
+        .filter(user::Column::AccountStatus.eq("active"))
+        .filter(user::Column::Role.ne("guest"))
+        .one(db)
+        .await?
+    else {
+        return Ok(false);
+    };
+
+    if !preferences(db, user_id).await?.allows(category) {
+        tracing::debug!(%user_id, ?category, "Email suppressed by notification preferences");
+        return Ok(false);
+    }
+
+    tracing::info!(
+        email = %recipient.email,
+        ?category,
+        subject,
+        body,
+        "Email queued (email sending not yet implemented)"
+    );
+    Ok(true)
+}
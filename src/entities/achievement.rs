@@ -0,0 +1,29 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A badge users can earn. The catalog is seeded by migration.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "achievement")]
+pub struct Model {
+    /// Stable key, e.g. `"first_publish"`.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Position in listings, lowest first.
+    pub sort_order: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::user_achievement::Entity")]
+    UserAchievement,
+}
+
+impl Related<super::user_achievement::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::UserAchievement.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
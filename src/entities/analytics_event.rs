@@ -0,0 +1,42 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A product analytics event reported by a client (see [`crate::analytics`]).
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "analytics_event")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// One of [`crate::analytics::EVENT_NAMES`], or
+    /// [`crate::analytics::EXPERIMENT_EXPOSURE`] for events the server logs itself.
+    pub name: String,
+    /// The signed-in user who reported it; `None` for anonymous clients.
+    pub user_id: Option<Uuid>,
+    /// The client's own identifier when not signed in.
+    pub anonymous_id: Option<String>,
+    pub game_id: Option<Uuid>,
+    pub session_id: Option<Uuid>,
+    /// JSON object of the event's properties, validated per event name.
+    pub properties: Json,
+    /// When the client says the event happened.
+    pub occurred_at: DateTimeWithTimeZone,
+    pub received_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
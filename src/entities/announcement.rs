@@ -0,0 +1,39 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A platform-wide banner, shown between `starts_at` and `ends_at`.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "announcement")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub title: String,
+    #[sea_orm(column_type = "Text")]
+    pub body: String,
+    /// `"info"`, `"warning"` or `"critical"`.
+    pub severity: String,
+    pub link_url: Option<String>,
+    /// Whether users may hide the banner.
+    pub dismissible: bool,
+    /// What frontends remember a dismissal by. Changing it shows the banner again to users
+    /// who dismissed it.
+    pub dismissal_key: String,
+    pub starts_at: DateTimeWithTimeZone,
+    /// `None` for banners shown until an admin removes them.
+    pub ends_at: Option<DateTimeWithTimeZone>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::CreatedBy",
+        to = "super::user::Column::Id"
+    )]
+    Creator,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
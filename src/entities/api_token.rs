@@ -0,0 +1,42 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "api_token")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// User-chosen label, e.g. "GitHub Actions deploy".
+    pub name: String,
+    /// Hex-encoded SHA-256 of the token; the token itself is only shown once.
+    #[sea_orm(unique)]
+    pub token_hash: String,
+    /// First characters of the token, shown in listings.
+    pub token_prefix: String,
+    /// Space-separated scopes, e.g. `"games:read games:write"`.
+    pub scopes: String,
+    pub expires_at: Option<DateTimeWithTimeZone>,
+    pub last_used_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+    /// The OAuth client the token was issued to; `None` for tokens the user created.
+    pub oauth_client_id: Option<Uuid>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
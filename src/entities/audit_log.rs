@@ -0,0 +1,31 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A change made through an admin or moderator endpoint.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "audit_log")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// The user who acted. Not a foreign key, so entries outlive purged accounts.
+    pub actor_id: Uuid,
+    /// The actor's role at the time, e.g. `"moderator"`.
+    pub actor_role: String,
+    /// What was done, as `<target>.<verb>`, e.g. `"tag.merge"` or `"report.takedown"`.
+    pub action: String,
+    /// `"tag"`, `"game"`, `"user"`, `"report"`, `"verification_request"`, `"takedown"`,
+    /// `"announcement"`, `"copyright_notice"`, `"session"` or `"suspension_appeal"`.
+    pub target_type: String,
+    pub target_id: Uuid,
+    /// The fields the action changed, as they were before it.
+    pub before: Option<Json>,
+    /// The same fields afterwards.
+    pub after: Option<Json>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,42 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "classroom")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// The teacher who created the class and manages its student accounts.
+    pub teacher_id: Uuid,
+    pub name: String,
+    /// Class code students sign in with, alongside their username and password.
+    #[sea_orm(unique)]
+    pub code: String,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::TeacherId",
+        to = "super::user::Column::Id"
+    )]
+    Teacher,
+    #[sea_orm(has_many = "super::classroom_student::Entity")]
+    Students,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Teacher.def()
+    }
+}
+
+impl Related<super::classroom_student::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Students.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
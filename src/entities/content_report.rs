@@ -0,0 +1,48 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "content_report")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub created_at: DateTimeWithTimeZone,
+    /// `None` for logged-out reporters (or once the reporter's account is purged).
+    pub reporter_id: Option<Uuid>,
+    /// Client IP of logged-out reporters, used for rate limiting.
+    pub reporter_ip: Option<String>,
+    /// `game`, `review` or `user`.
+    pub target_type: String,
+    pub target_id: Uuid,
+    pub reason: String,
+    pub details: Option<String>,
+    /// `open` until a moderator handles it, then `resolved`.
+    pub status: String,
+    /// Moderator who claimed the report, so others leave it to them.
+    pub assigned_to: Option<Uuid>,
+    pub assigned_at: Option<DateTimeWithTimeZone>,
+    /// Moderator who resolved the report.
+    pub resolved_by: Option<Uuid>,
+    /// `dismissed`, `warned` or `taken_down` once resolved.
+    pub resolution: Option<String>,
+    pub resolution_note: Option<String>,
+    pub resolved_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::ReporterId",
+        to = "super::user::Column::Id"
+    )]
+    Reporter,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Reporter.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
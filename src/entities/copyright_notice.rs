@@ -0,0 +1,80 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A rights holder's takedown notice against a game, its review by an admin, and the
+/// creator's counter-notice.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "copyright_notice")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// `"received"`, `"under_review"`, `"actioned"`, `"rejected"`, `"counter_noticed"`,
+    /// `"restored"` or `"court_action"`.
+    pub status: String,
+    /// The game the notice is against; `None` once that game is purged.
+    pub game_id: Option<Uuid>,
+    pub claimant_name: String,
+    pub claimant_email: String,
+    #[sea_orm(column_type = "Text")]
+    pub claimant_address: String,
+    pub claimant_phone: Option<String>,
+    /// Where the notice was sent from, for rate limiting.
+    pub claimant_ip: Option<String>,
+    /// The work the claimant says the game infringes.
+    #[sea_orm(column_type = "Text")]
+    pub copyrighted_work: String,
+    /// The claimant's electronic signature.
+    pub signature: String,
+    /// The admin reviewing the notice.
+    pub reviewer_id: Option<Uuid>,
+    /// The reviewer's reason for their decision.
+    #[sea_orm(column_type = "Text", nullable)]
+    pub review_note: Option<String>,
+    pub review_started_at: Option<DateTimeWithTimeZone>,
+    pub decided_at: Option<DateTimeWithTimeZone>,
+    /// The removal of the game, once the notice is actioned.
+    pub takedown_id: Option<Uuid>,
+    pub counter_name: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub counter_address: Option<String>,
+    /// The creator's statement that the game was removed by mistake or misidentification.
+    #[sea_orm(column_type = "Text", nullable)]
+    pub counter_statement: Option<String>,
+    pub counter_signature: Option<String>,
+    pub counter_noticed_at: Option<DateTimeWithTimeZone>,
+    /// When the game is put back unless the claimant reports court action first.
+    pub restore_after: Option<DateTimeWithTimeZone>,
+    pub restored_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::game::Entity",
+        from = "Column::GameId",
+        to = "super::game::Column::Id"
+    )]
+    Game,
+    #[sea_orm(
+        belongs_to = "super::game_takedown::Entity",
+        from = "Column::TakedownId",
+        to = "super::game_takedown::Column::Id"
+    )]
+    Takedown,
+}
+
+impl Related<super::game::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Game.def()
+    }
+}
+
+impl Related<super::game_takedown::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Takedown.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,40 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "email_change")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// At most one pending change per user; a new request replaces the old one.
+    #[sea_orm(unique)]
+    pub user_id: Uuid,
+    pub new_email: String,
+    /// SHA-256 digest of the token sent to the current address; presenting the token applies
+    /// the change.
+    #[sea_orm(unique)]
+    pub confirmation_token: String,
+    /// SHA-256 digest of the token sent to the new address; becomes the email provider's
+    /// verification token once the change is applied.
+    pub verification_token: String,
+    pub expires_at: DateTimeWithTimeZone,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
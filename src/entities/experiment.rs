@@ -0,0 +1,35 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// An A/B experiment splitting traffic between variants (see [`crate::experiments`]).
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "experiment")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// What clients and exposure events name the experiment by. Part of every subject's
+    /// bucket, so it cannot be changed.
+    #[sea_orm(unique)]
+    pub key: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub description: Option<String>,
+    /// JSON array of [`crate::experiments::Variant`]s.
+    pub variants: Json,
+    /// One of [`crate::experiments::STATUSES`]; only running experiments assign variants.
+    pub status: String,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::CreatedBy",
+        to = "super::user::Column::Id"
+    )]
+    Creator,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
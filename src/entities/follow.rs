@@ -0,0 +1,32 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "follow")]
+pub struct Model {
+    /// The user doing the following.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub follower_id: Uuid,
+    /// The creator being followed.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub followee_id: Uuid,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::FollowerId",
+        to = "super::user::Column::Id"
+    )]
+    Follower,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::FolloweeId",
+        to = "super::user::Column::Id"
+    )]
+    Followee,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,36 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "friendship")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// The user who sent the friend request.
+    pub requester_id: Uuid,
+    /// The user the request was sent to.
+    pub addressee_id: Uuid,
+    /// `"pending"` until the addressee accepts, then `"accepted"`. Declined requests and
+    /// removed friendships are deleted.
+    pub status: String,
+    pub created_at: DateTimeWithTimeZone,
+    pub accepted_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::RequesterId",
+        to = "super::user::Column::Id"
+    )]
+    Requester,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::AddresseeId",
+        to = "super::user::Column::Id"
+    )]
+    Addressee,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
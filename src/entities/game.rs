@@ -28,6 +28,19 @@ pub struct Model {
     pub avg_rating: f32,
     pub review_count: i64,
     pub forked_from_id: Option<Uuid>,
+    pub trending_score: f64,
+    /// Collaborator holding the editing lock, until `lock_expires_at`.
+    pub locked_by: Option<Uuid>,
+    pub lock_expires_at: Option<DateTimeWithTimeZone>,
+    /// Draft controller manifest, validated and copied onto the version at publish time.
+    pub manifest: Option<Json>,
+    /// When an admin featured the game, if they have.
+    pub featured_at: Option<DateTimeWithTimeZone>,
+    /// `"user"` for games owned by their creator alone, or `"organization"` for games in an
+    /// organization's catalog, whose members then share access to it.
+    pub owner_type: String,
+    /// The owning organization when `owner_type` is `"organization"`.
+    pub organization_id: Option<Uuid>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -46,6 +59,14 @@ pub enum Relation {
     GameAssets,
     #[sea_orm(has_many = "super::game_tag::Entity")]
     GameTags,
+    #[sea_orm(has_many = "super::review::Entity")]
+    Reviews,
+    #[sea_orm(has_many = "super::favorite::Entity")]
+    Favorites,
+    #[sea_orm(has_many = "super::collection_game::Entity")]
+    CollectionGames,
+    #[sea_orm(has_many = "super::game_takedown::Entity")]
+    Takedowns,
 }
 
 impl Related<super::user::Entity> for Entity {
@@ -78,6 +99,30 @@ impl Related<super::game_tag::Entity> for Entity {
     }
 }
 
+impl Related<super::review::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Reviews.def()
+    }
+}
+
+impl Related<super::favorite::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Favorites.def()
+    }
+}
+
+impl Related<super::collection_game::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CollectionGames.def()
+    }
+}
+
+impl Related<super::game_takedown::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Takedowns.def()
+    }
+}
+
 impl Related<super::tag::Entity> for Entity {
     fn to() -> RelationDef {
         super::game_tag::Relation::Tag.def()
@@ -15,6 +15,11 @@ pub struct Model {
     #[serde(skip)]
     pub file_data: Vec<u8>,
     pub storage_url: String,
+    /// Backend holding the bytes; `inline` means they are still in `file_data`.
+    pub storage_backend: String,
+    pub storage_key: Option<String>,
+    /// Hex SHA-256 of the bytes; identical uploads by the same creator share a storage key.
+    pub content_hash: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,42 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A game's plays rolled up per UTC day, keyed by the day each play started.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "game_daily_stats")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub game_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub day: Date,
+    pub plays: i64,
+    /// Distinct sessions that played the game.
+    pub sessions: i64,
+    pub play_seconds: i64,
+    /// Sum of player counts over all plays.
+    pub players: i64,
+    pub peak_players: i32,
+    /// Distinct players who joined the sessions that played the game: signed-in users once
+    /// each, guests per session.
+    pub unique_players: i64,
+    /// `game_opened` analytics events reported for the game.
+    pub opens: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::game::Entity",
+        from = "Column::GameId",
+        to = "super::game::Column::Id"
+    )]
+    Game,
+}
+
+impl Related<super::game::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Game.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,49 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A moderator's removal of a game, and the creator's appeal of it.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "game_takedown")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub game_id: Uuid,
+    /// The moderator who removed the game.
+    pub moderator_id: Option<Uuid>,
+    /// Why the game was removed, shown to its creator.
+    #[sea_orm(column_type = "Text")]
+    pub reason: String,
+    /// `game.status` before the takedown, restored if an appeal is approved.
+    pub previous_status: String,
+    pub created_at: DateTimeWithTimeZone,
+    /// The creator's case for restoring the game.
+    #[sea_orm(column_type = "Text", nullable)]
+    pub appeal_message: Option<String>,
+    pub appealed_at: Option<DateTimeWithTimeZone>,
+    /// `"pending"`, `"approved"` or `"denied"` once the creator appeals.
+    pub appeal_status: Option<String>,
+    /// The admin who decided the appeal.
+    pub appeal_reviewer_id: Option<Uuid>,
+    /// The reviewer's note to the creator.
+    #[sea_orm(column_type = "Text", nullable)]
+    pub appeal_review_note: Option<String>,
+    pub appeal_reviewed_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::game::Entity",
+        from = "Column::GameId",
+        to = "super::game::Column::Id"
+    )]
+    Game,
+}
+
+impl Related<super::game::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Game.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
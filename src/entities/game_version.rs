@@ -14,6 +14,10 @@ pub struct Model {
     pub change_log: Option<String>,
     pub changelog: Option<String>,
     pub published_by_id: Option<Uuid>,
+    /// Safety scanner warnings recorded at publish time (a JSON array of diagnostics).
+    pub scan_findings: Option<Json>,
+    /// Controls, orientation and player counts declared by the game (see `validation::manifest`).
+    pub manifest: Option<Json>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,21 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The result of one component's periodic health check (see [`crate::jobs::health_checks`]).
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "health_check")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// `database`, `migrations`, `storage` or `email`.
+    pub component: String,
+    /// `up`, `down` or `disabled` (see [`crate::health`]).
+    pub status: String,
+    pub latency_ms: Option<i64>,
+    pub checked_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,46 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "impersonation_log")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// The admin acting as the user.
+    pub admin_id: Uuid,
+    /// The user being impersonated.
+    pub user_id: Uuid,
+    pub method: String,
+    pub path: String,
+    /// `"started"` when the admin begins impersonating, then `"allowed"` or `"refused"` for
+    /// each request made with the impersonation token.
+    pub outcome: String,
+    /// Why the admin started impersonating, e.g. a support ticket; only set on `"started"`.
+    pub reason: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::AdminId",
+        to = "super::user::Column::Id"
+    )]
+    Admin,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
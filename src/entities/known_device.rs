@@ -0,0 +1,43 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "known_device")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// SHA-256 of the `User-Agent` and IP prefix, unique per user.
+    pub fingerprint: String,
+    pub user_agent: Option<String>,
+    /// Network the device signed in from: `/24` for IPv4, `/48` for IPv6.
+    pub ip_prefix: Option<String>,
+    /// Refresh token issued on the device's first sign-in, revoked by "this wasn't me".
+    pub refresh_token_id: Option<Uuid>,
+    /// SHA-256 digest of the token in the "this wasn't me" link; set only when the user was
+    /// alerted.
+    #[sea_orm(unique)]
+    pub revoke_token: Option<String>,
+    /// When the "this wasn't me" link stops working.
+    pub revoke_token_expires_at: Option<DateTimeWithTimeZone>,
+    pub first_seen_at: DateTimeWithTimeZone,
+    pub last_seen_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,41 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "login_event")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// `None` when the attempt named an account that does not exist.
+    pub user_id: Option<Uuid>,
+    /// What the client signed in with, e.g. the email address, when it names the account.
+    pub identifier: Option<String>,
+    /// `"email"`, `"totp"`, `"webauthn"`, or an `OAuth` / OIDC provider name.
+    pub provider: String,
+    /// `"success"`, `"two_factor_required"`, `"invalid_credentials"`, `"invalid_two_factor"`,
+    /// `"two_factor_locked"`, `"unknown_account"`, `"suspended"` or `"deactivated"`.
+    pub outcome: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    /// ISO 3166-1 alpha-2 code of the country `ip_address` resolved to, if known.
+    pub country: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
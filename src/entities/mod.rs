@@ -1,10 +1,59 @@
+pub mod achievement;
+pub mod analytics_event;
+pub mod announcement;
+pub mod api_token;
+pub mod audit_log;
 pub mod auth_provider;
+pub mod classroom;
+pub mod classroom_student;
+pub mod collection;
+pub mod collection_game;
+pub mod content_report;
+pub mod copyright_notice;
+pub mod data_export;
+pub mod email_change;
+pub mod experiment;
+pub mod favorite;
+pub mod follow;
+pub mod friendship;
 pub mod game;
 pub mod game_asset;
+pub mod game_collaborator;
+pub mod game_daily_stats;
+pub mod game_play;
 pub mod game_tag;
+pub mod game_takedown;
+pub mod game_template;
 pub mod game_version;
+pub mod health_check;
+pub mod impersonation_log;
+pub mod known_device;
+pub mod login_event;
+pub mod notification;
+pub mod notification_preference;
+pub mod oauth_authorization_code;
+pub mod oauth_client;
+pub mod organization;
+pub mod organization_member;
+pub mod platform_daily_stats;
 pub mod player;
 pub mod refresh_token;
+pub mod review;
+pub mod scheduled_publish;
 pub mod session;
+pub mod session_invite;
+pub mod status_incident;
+pub mod storage_object;
+pub mod stripe_event;
+pub mod subscription;
+pub mod suspension_appeal;
 pub mod tag;
 pub mod user;
+pub mod user_achievement;
+pub mod user_note;
+pub mod user_preference;
+pub mod user_totp;
+pub mod verification_request;
+pub mod webauthn_challenge;
+pub mod webauthn_credential;
+pub mod xp_event;
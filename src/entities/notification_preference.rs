@@ -0,0 +1,41 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The kinds of non-transactional email a user wants. Users without a row get the defaults
+/// in [`crate::email::Preferences::default`].
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "notification_preference")]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: Uuid,
+    /// News about the platform itself. Off unless the user opts in.
+    pub product_updates: bool,
+    /// A review was posted on one of the user's games.
+    pub review_alerts: bool,
+    /// Sign-ins from unfamiliar devices and similar account activity.
+    pub security_alerts: bool,
+    /// Summaries of sessions the user hosted.
+    pub session_summaries: bool,
+    /// Invitations into other users' sessions, when the host asks for them to be emailed.
+    pub session_invites: bool,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
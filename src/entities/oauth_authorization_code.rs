@@ -0,0 +1,58 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "oauth_authorization_code")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// Hex-encoded SHA-256 of the code.
+    #[sea_orm(unique)]
+    pub code_hash: String,
+    pub client_id: Uuid,
+    /// The user who approved the request.
+    pub user_id: Uuid,
+    /// Must be sent again, unchanged, when the code is exchanged, if the authorization
+    /// request named it.
+    #[sea_orm(column_type = "Text")]
+    pub redirect_uri: String,
+    /// Whether the authorization request named `redirect_uri` rather than falling back to the
+    /// client's only registered one (RFC 6749 section 4.1.3).
+    pub redirect_uri_supplied: bool,
+    /// Space-separated scopes the user approved.
+    pub scopes: String,
+    /// PKCE `S256` challenge, if the client sent one.
+    pub code_challenge: Option<String>,
+    pub expires_at: DateTimeWithTimeZone,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::oauth_client::Entity",
+        from = "Column::ClientId",
+        to = "super::oauth_client::Column::Id"
+    )]
+    Client,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::oauth_client::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Client.def()
+    }
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,39 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "oauth_client")]
+pub struct Model {
+    /// Also the public `client_id`.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// The developer who registered the client.
+    pub owner_id: Uuid,
+    /// Shown to users on the consent screen.
+    pub name: String,
+    /// Hex-encoded SHA-256 of the client secret; `None` for public clients, which must use
+    /// PKCE instead.
+    pub client_secret_hash: Option<String>,
+    /// Space-separated redirect URIs; `redirect_uri` must match one exactly.
+    #[sea_orm(column_type = "Text")]
+    pub redirect_uris: String,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::OwnerId",
+        to = "super::user::Column::Id"
+    )]
+    Owner,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Owner.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
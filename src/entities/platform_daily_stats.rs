@@ -0,0 +1,28 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Platform-wide activity rolled up per UTC day. Sessions count towards the day they were
+/// created on, plays towards the day they started on.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "platform_daily_stats")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub day: Date,
+    pub sessions: i64,
+    /// Time from creation to end of those sessions, or until the rollup for open ones.
+    pub session_seconds: i64,
+    pub plays: i64,
+    pub play_seconds: i64,
+    /// Distinct players who joined those sessions: signed-in users once each, guests per
+    /// session.
+    pub unique_players: i64,
+    /// Distinct games played.
+    pub games_played: i64,
+    /// Analytics events that occurred that day.
+    pub events: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
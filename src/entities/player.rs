@@ -13,6 +13,10 @@ pub struct Model {
     pub avatar_url: Option<String>,
     pub connection_status: String,
     pub left_at: Option<DateTimeWithTimeZone>,
+    /// When the player first sent controller input; `None` if they never did.
+    pub first_input_at: Option<DateTimeWithTimeZone>,
+    /// ISO 3166-1 alpha-2 code of the country the player joined from, if known.
+    pub country: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
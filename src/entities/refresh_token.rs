@@ -12,6 +12,11 @@ pub struct Model {
     pub expires_at: DateTimeWithTimeZone,
     pub revoked_at: Option<DateTimeWithTimeZone>,
     pub created_at: DateTimeWithTimeZone,
+    /// `User-Agent` of the client the token was issued to.
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    /// When the device first signed in; kept across token rotations.
+    pub signed_in_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -16,6 +16,15 @@ pub struct Model {
     pub session_code: String,
     pub status: String,
     pub max_players: i32,
+    pub playing_started_at: Option<DateTimeWithTimeZone>,
+    /// Seconds from creation to end; `None` until the session ends, like the fields below.
+    pub duration_seconds: Option<i64>,
+    /// Most players connected at the same time.
+    pub peak_players: Option<i32>,
+    /// Messages relayed to the session's clients.
+    pub messages_relayed: Option<i64>,
+    /// Times a player's `WebSocket` closed.
+    pub disconnects: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,49 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "session_invite")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub session_id: Uuid,
+    /// The host who sent the invite.
+    pub inviter_id: Uuid,
+    pub invitee_id: Uuid,
+    /// `"pending"`, then `"accepted"` once the invitee joins signed in, or `"declined"`.
+    pub status: String,
+    /// Whether the invite was also emailed, which the invitee's preferences can prevent.
+    pub emailed: bool,
+    pub created_at: DateTimeWithTimeZone,
+    pub responded_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::session::Entity",
+        from = "Column::SessionId",
+        to = "super::session::Column::Id"
+    )]
+    Session,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::InviterId",
+        to = "super::user::Column::Id"
+    )]
+    Inviter,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::InviteeId",
+        to = "super::user::Column::Id"
+    )]
+    Invitee,
+}
+
+impl Related<super::session::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Session.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,32 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// An incident admins post on the public status page, shown until it is resolved.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "status_incident")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub title: String,
+    #[sea_orm(column_type = "Text")]
+    pub message: String,
+    /// `"maintenance"`, `"minor"` or `"major"`.
+    pub impact: String,
+    pub started_at: DateTimeWithTimeZone,
+    /// `None` while the incident is ongoing.
+    pub resolved_at: Option<DateTimeWithTimeZone>,
+    pub created_by: Option<Uuid>,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::CreatedBy",
+        to = "super::user::Column::Id"
+    )]
+    Creator,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
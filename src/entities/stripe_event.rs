@@ -0,0 +1,20 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A Stripe webhook event that has been applied. Its ID is unique, so a redelivered event is
+/// recognised and skipped.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "stripe_event")]
+pub struct Model {
+    /// Stripe's event ID, e.g. `"evt_..."`.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// The event type, e.g. `"customer.subscription.updated"`.
+    pub kind: String,
+    pub processed_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
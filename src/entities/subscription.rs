@@ -0,0 +1,40 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A user's Stripe billing relationship, created when they first start a checkout.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "subscription")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: Uuid,
+    #[sea_orm(unique)]
+    pub stripe_customer_id: String,
+    /// Set once a checkout completes.
+    pub stripe_subscription_id: Option<String>,
+    /// Stripe's subscription status (`"active"`, `"past_due"`, `"canceled"`, ...), or
+    /// `"incomplete"` before the first checkout completes.
+    pub status: String,
+    /// End of the period paid for; the plan lapses after it unless renewed.
+    pub current_period_end: Option<DateTimeWithTimeZone>,
+    /// `created` time of the last webhook event applied; older events are ignored.
+    pub last_event_at: Option<DateTimeWithTimeZone>,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,49 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A suspended user's request to have their account reinstated.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "suspension_appeal")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// The suspension reason when the appeal was filed.
+    #[sea_orm(column_type = "Text", nullable)]
+    pub suspension_reason: Option<String>,
+    /// The user's case for lifting the suspension.
+    #[sea_orm(column_type = "Text")]
+    pub statement: String,
+    /// `"pending"`, `"approved"` (the suspension was lifted) or `"denied"`.
+    pub status: String,
+    pub reviewer_id: Option<Uuid>,
+    /// Shown to the user, e.g. why the appeal was denied.
+    #[sea_orm(column_type = "Text", nullable)]
+    pub review_note: Option<String>,
+    pub reviewed_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::ReviewerId",
+        to = "super::user::Column::Id"
+    )]
+    Reviewer,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,6 +1,7 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "user")]
 pub struct Model {
@@ -13,7 +14,23 @@ pub struct Model {
     pub display_name: Option<String>,
     pub avatar_url: Option<String>,
     pub bio: Option<String>,
+    pub location: Option<String>,
+    pub website_url: Option<String>,
+    pub pronouns: Option<String>,
+    /// Links to the user's profiles elsewhere, as a JSON object of platform name to URL.
+    pub social_links: Option<Json>,
     pub email_verified: bool,
+    /// Whether the user carries the verified creator badge.
+    pub verified: bool,
+    /// Keep the user's profile out of search engines and their games out of search.
+    pub hide_from_search: bool,
+    /// Hide the user's published-game list and game stats from other users.
+    pub hide_games: bool,
+    /// Keep the user's activity out of their followers' feeds.
+    pub hide_activity: bool,
+    /// Set by moderators: the user's games, reviews and notifications to others are hidden from
+    /// everyone else, while the user keeps seeing them as usual.
+    pub shadow_banned: bool,
     pub role: String,
     pub subscription_plan: String,
     pub subscription_expires_at: Option<DateTimeWithTimeZone>,
@@ -24,6 +41,10 @@ pub struct Model {
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
     pub deleted_at: Option<DateTimeWithTimeZone>,
+    /// When a requested account deletion will be carried out; cancellable until then.
+    pub deletion_scheduled_at: Option<DateTimeWithTimeZone>,
+    /// `"delete"` or `"keep"`: what happens to the user's games when the account is deleted.
+    pub deletion_game_policy: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
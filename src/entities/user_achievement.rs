@@ -0,0 +1,36 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "user_achievement")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub achievement_id: String,
+    pub awarded_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+    #[sea_orm(
+        belongs_to = "super::achievement::Entity",
+        from = "Column::AchievementId",
+        to = "super::achievement::Column::Id"
+    )]
+    Achievement,
+}
+
+impl Related<super::achievement::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Achievement.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,38 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "user_totp")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: Uuid,
+    pub created_at: DateTimeWithTimeZone,
+    /// Base32-encoded shared secret.
+    pub secret: String,
+    /// `None` while enrollment is pending; two-factor sign-in is enforced once set.
+    pub confirmed_at: Option<DateTimeWithTimeZone>,
+    /// Time step of the last accepted code, so a code cannot be used twice.
+    pub last_used_step: Option<i64>,
+    /// Sign-in codes tried since the last accepted one, counted before each is checked.
+    pub failed_attempts: i32,
+    /// When the last of `failed_attempts` was tried; older attempts no longer count.
+    pub last_failed_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,41 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A creator's application for the verified badge.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "verification_request")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// Why the applicant should be verified.
+    #[sea_orm(column_type = "Text")]
+    pub justification: String,
+    /// `"pending"`, `"approved"` or `"denied"`.
+    pub status: String,
+    /// The admin who decided the request.
+    pub reviewer_id: Option<Uuid>,
+    /// The reviewer's note to the applicant.
+    #[sea_orm(column_type = "Text", nullable)]
+    pub review_note: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+    pub reviewed_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    Applicant,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::ReviewerId",
+        to = "super::user::Column::Id"
+    )]
+    Reviewer,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,39 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "webauthn_credential")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// Authenticator-assigned credential ID, base64url-encoded without padding.
+    #[sea_orm(unique)]
+    pub credential_id: String,
+    /// Uncompressed P-256 public key point (`0x04 || x || y`).
+    pub public_key: Vec<u8>,
+    /// Last signature counter reported by the authenticator, used to spot cloned keys.
+    pub sign_count: i64,
+    /// User-chosen label, e.g. "`MacBook` Touch ID".
+    pub name: String,
+    pub created_at: DateTimeWithTimeZone,
+    pub last_used_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,11 +1,73 @@
+use axum::Json;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
-use axum::Json;
 use serde_json::json;
 
+use crate::error_reporting::InternalError;
+
+/// Machine-readable error codes, sent as `error.code` for clients to branch on.
+///
+/// Every code the API returns is listed here; the `message` next to it is for people and may
+/// change. Each [`AppError`] variant has a fixed code, except
+/// [`AppError::Unprocessable`], which takes one of the domain codes below.
+pub mod code {
+    // General codes, one per `AppError` variant
+
+    /// 400: the request is malformed or a field is invalid.
+    pub const BAD_REQUEST: &str = "BAD_REQUEST";
+    /// 401: missing, expired or invalid credentials.
+    pub const UNAUTHORIZED: &str = "UNAUTHORIZED";
+    /// 403: signed in, but not allowed to do this.
+    pub const FORBIDDEN: &str = "FORBIDDEN";
+    /// 404: the resource does not exist, or is hidden from the caller.
+    pub const NOT_FOUND: &str = "NOT_FOUND";
+    /// 409: conflicts with existing data, e.g. a username that is taken.
+    pub const CONFLICT: &str = "CONFLICT";
+    /// 413: the request body or upload is too large.
+    pub const PAYLOAD_TOO_LARGE: &str = "PAYLOAD_TOO_LARGE";
+    /// 422: the request is well-formed but fails validation.
+    pub const VALIDATION_ERROR: &str = "VALIDATION_ERROR";
+    /// 429: rate limited; see the `Retry-After` header.
+    pub const RATE_LIMITED: &str = "RATE_LIMITED";
+    /// 500: an unexpected error, reported to Sentry; the message is generic.
+    pub const INTERNAL_ERROR: &str = "INTERNAL_ERROR";
+
+    // Domain codes, all 422
+
+    /// Text contains language the moderation filter blocks.
+    pub const CONTENT_BLOCKED: &str = "CONTENT_BLOCKED";
+    /// The new password appears in a known data breach.
+    pub const PASSWORD_BREACHED: &str = "PASSWORD_BREACHED";
+    /// The game is already archived.
+    pub const ALREADY_ARCHIVED: &str = "ALREADY_ARCHIVED";
+    /// The game is not archived, so it cannot be restored.
+    pub const NOT_ARCHIVED: &str = "NOT_ARCHIVED";
+    /// The game has never been published, so it cannot be remixed.
+    pub const NO_PUBLISHED_VERSION: &str = "NO_PUBLISHED_VERSION";
+    /// A moderator took the game down; it stays unpublished unless an appeal is approved.
+    pub const REMOVED_BY_MODERATION: &str = "REMOVED_BY_MODERATION";
+    /// Guest accounts must be upgraded first.
+    pub const GUEST_ACCOUNT: &str = "GUEST_ACCOUNT";
+    /// The account's email must be verified first.
+    pub const EMAIL_NOT_VERIFIED: &str = "EMAIL_NOT_VERIFIED";
+    /// The game is missing a title or code.
+    pub const INVALID_GAME: &str = "INVALID_GAME";
+    /// The game's manifest is invalid.
+    pub const INVALID_MANIFEST: &str = "INVALID_MANIFEST";
+    /// The game's code has validation errors.
+    pub const INVALID_GAME_CODE: &str = "INVALID_GAME_CODE";
+    /// The game's code uses an API the safety scanner forbids.
+    pub const UNSAFE_GAME_CODE: &str = "UNSAFE_GAME_CODE";
+    /// The upload would exceed the account's storage quota.
+    pub const QUOTA_EXCEEDED: &str = "QUOTA_EXCEEDED";
+    /// Games removed for a copyright notice are restored through a counter-notice.
+    pub const COUNTER_NOTICE_REQUIRED: &str = "COUNTER_NOTICE_REQUIRED";
+}
+
 /// Unified application error type that maps to JSON HTTP responses.
 ///
-/// Matches the API specification error format: `{ "error": { "code": "...", "message": "..." } }`.
+/// Matches the API specification error format: `{ "error": { "code": "...", "message": "..." } }`,
+/// with `code` from the [`code`] catalog.
 pub enum AppError {
     /// 400 Bad Request
     BadRequest(String),
@@ -21,42 +83,45 @@ pub enum AppError {
     PayloadTooLarge(String),
     /// 422 Unprocessable Entity (generic, code defaults to `VALIDATION_ERROR`)
     UnprocessableEntity(String),
-    /// 422 Unprocessable Entity with explicit error code
-    Unprocessable(String, String),
+    /// 422 Unprocessable Entity with one of the domain codes in [`code`]
+    Unprocessable(&'static str, String),
+    /// 429 Too Many Requests
+    TooManyRequests(String),
     /// 500 Internal Server Error (wraps any error, logs details, returns generic message)
     Internal(anyhow::Error),
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        let mut internal = None;
         let (status, code, message) = match self {
-            Self::BadRequest(msg) => (StatusCode::BAD_REQUEST, "BAD_REQUEST".to_string(), msg),
-            Self::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, "UNAUTHORIZED".to_string(), msg),
-            Self::Forbidden(msg) => (StatusCode::FORBIDDEN, "FORBIDDEN".to_string(), msg),
-            Self::NotFound(msg) => (StatusCode::NOT_FOUND, "NOT_FOUND".to_string(), msg),
-            Self::Conflict(msg) => (StatusCode::CONFLICT, "CONFLICT".to_string(), msg),
-            Self::PayloadTooLarge(msg) => (
-                StatusCode::PAYLOAD_TOO_LARGE,
-                "PAYLOAD_TOO_LARGE".to_string(),
-                msg,
-            ),
+            Self::BadRequest(msg) => (StatusCode::BAD_REQUEST, code::BAD_REQUEST, msg),
+            Self::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, code::UNAUTHORIZED, msg),
+            Self::Forbidden(msg) => (StatusCode::FORBIDDEN, code::FORBIDDEN, msg),
+            Self::NotFound(msg) => (StatusCode::NOT_FOUND, code::NOT_FOUND, msg),
+            Self::Conflict(msg) => (StatusCode::CONFLICT, code::CONFLICT, msg),
+            Self::PayloadTooLarge(msg) => {
+                (StatusCode::PAYLOAD_TOO_LARGE, code::PAYLOAD_TOO_LARGE, msg)
+            }
             Self::UnprocessableEntity(msg) => (
                 StatusCode::UNPROCESSABLE_ENTITY,
-                "VALIDATION_ERROR".to_string(),
+                code::VALIDATION_ERROR,
                 msg,
             ),
             Self::Unprocessable(code, msg) => (StatusCode::UNPROCESSABLE_ENTITY, code, msg),
+            Self::TooManyRequests(msg) => (StatusCode::TOO_MANY_REQUESTS, code::RATE_LIMITED, msg),
             Self::Internal(err) => {
                 tracing::error!("Internal server error: {err:#}");
+                internal = Some(InternalError(format!("{err:#}")));
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
-                    "INTERNAL_ERROR".to_string(),
+                    code::INTERNAL_ERROR,
                     "An internal error occurred".to_string(),
                 )
             }
         };
 
-        (
+        let mut response = (
             status,
             Json(json!({
                 "error": {
@@ -65,7 +130,11 @@ impl IntoResponse for AppError {
                 }
             })),
         )
-            .into_response()
+            .into_response();
+        if let Some(internal) = internal {
+            response.extensions_mut().insert(internal);
+        }
+        response
     }
 }
 
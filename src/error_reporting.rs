@@ -0,0 +1,298 @@
+//! Error reporting to Sentry.
+//!
+//! When `SENTRY_DSN` is set, internal errors returned by handlers and panics anywhere in the
+//! process (including detached `WebSocket` tasks) are sent to Sentry's store endpoint. Events
+//! raised while serving a request carry its method, route, request ID and, once the auth
+//! extractor has run, the user ID. Without a DSN nothing is sent.
+
+use std::panic::PanicHookInfo;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use axum::extract::{MatchedPath, Request};
+use axum::middleware::Next;
+use axum::response::Response;
+use chrono::Utc;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::config::{Config, Environment};
+use crate::middleware::request_id::REQUEST_ID_HEADER;
+
+/// How long a report may take before it is given up on.
+const SEND_TIMEOUT: Duration = Duration::from_secs(5);
+
+static REPORTER: OnceLock<Reporter> = OnceLock::new();
+
+tokio::task_local! {
+    static REQUEST: Arc<RequestContext>;
+}
+
+/// Where and how to send events, parsed from a DSN such as
+/// `https://<key>@o1.ingest.sentry.io/<project>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dsn {
+    store_url: String,
+    public_key: String,
+}
+
+impl Dsn {
+    /// Parse a Sentry DSN, or `None` if it is malformed.
+    #[must_use]
+    pub fn parse(dsn: &str) -> Option<Self> {
+        let url = reqwest::Url::parse(dsn).ok()?;
+        let public_key = url.username();
+        let host = url.host_str()?;
+        let (prefix, project) = url.path().trim_end_matches('/').rsplit_once('/')?;
+        if public_key.is_empty() || project.is_empty() {
+            return None;
+        }
+        let port = url.port().map(|p| format!(":{p}")).unwrap_or_default();
+        Some(Self {
+            store_url: format!(
+                "{}://{host}{port}{prefix}/api/{project}/store/",
+                url.scheme()
+            ),
+            public_key: public_key.to_string(),
+        })
+    }
+
+    fn request(&self, client: &reqwest::Client, event: &Value) -> reqwest::RequestBuilder {
+        client
+            .post(&self.store_url)
+            .header(
+                "X-Sentry-Auth",
+                format!(
+                    "Sentry sentry_version=7, sentry_client=aircade-api/{}, sentry_key={}",
+                    env!("CARGO_PKG_VERSION"),
+                    self.public_key
+                ),
+            )
+            .timeout(SEND_TIMEOUT)
+            .json(event)
+    }
+}
+
+/// The request an event was raised in.
+struct RequestContext {
+    method: String,
+    route: String,
+    request_id: Option<String>,
+    /// Set by the auth extractor once it knows who is calling.
+    user_id: OnceLock<Uuid>,
+}
+
+struct Reporter {
+    dsn: Dsn,
+    environment: &'static str,
+    client: reqwest::Client,
+}
+
+/// Start reporting to Sentry if `config.sentry_dsn` is set, and report panics from then on.
+///
+/// Logs a warning and reports nothing if the DSN is malformed.
+pub fn init(config: &Config) {
+    if config.sentry_dsn.is_empty() {
+        return;
+    }
+    let Some(dsn) = Dsn::parse(&config.sentry_dsn) else {
+        tracing::warn!("SENTRY_DSN is malformed; errors will not be reported");
+        return;
+    };
+    let environment = match config.environment {
+        Environment::Development => "development",
+        Environment::Staging => "staging",
+        Environment::Production => "production",
+    };
+    let reporter = Reporter {
+        dsn,
+        environment,
+        client: reqwest::Client::new(),
+    };
+    if REPORTER.set(reporter).is_err() {
+        return;
+    }
+
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        report_panic(info);
+        previous(info);
+    }));
+    tracing::info!("Reporting errors to Sentry");
+}
+
+/// Remember the authenticated user of the current request for any event it raises.
+pub fn set_user(user_id: Uuid) {
+    let _ = REQUEST.try_with(|request| request.user_id.set(user_id));
+}
+
+/// Middleware: run the request with its context available to reports, and report it if a
+/// handler answered with an internal error (see [`InternalError`]).
+pub async fn capture(req: Request, next: Next) -> Response {
+    if REPORTER.get().is_none() {
+        return next.run(req).await;
+    }
+
+    let context = Arc::new(RequestContext {
+        method: req.method().to_string(),
+        route: req.extensions().get::<MatchedPath>().map_or_else(
+            || req.uri().path().to_string(),
+            |path| path.as_str().to_string(),
+        ),
+        request_id: req
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+        user_id: OnceLock::new(),
+    });
+    let response = REQUEST.scope(Arc::clone(&context), next.run(req)).await;
+
+    if let Some(InternalError(message)) = response.extensions().get::<InternalError>()
+        && let Some(reporter) = REPORTER.get()
+    {
+        let event = event(
+            "error",
+            "InternalError",
+            message,
+            Some(&context),
+            reporter.environment,
+        );
+        let request = reporter.dsn.request(&reporter.client, &event);
+        tokio::spawn(async move {
+            if let Err(e) = request.send().await {
+                tracing::warn!(error = %e, "Failed to report error to Sentry");
+            }
+        });
+    }
+    response
+}
+
+/// Attached to the response of an [`crate::error::AppError::Internal`] so [`capture`] can
+/// report the underlying error, which the client never sees.
+#[derive(Clone)]
+pub struct InternalError(pub String);
+
+/// Report a panic before the process unwinds or aborts.
+///
+/// The report is sent from a separate thread with its own runtime and waited for, since the
+/// panicking thread may be a runtime worker and, in release builds, the process aborts right
+/// after the hook returns.
+fn report_panic(info: &PanicHookInfo<'_>) {
+    let Some(reporter) = REPORTER.get() else {
+        return;
+    };
+    let message = info
+        .payload()
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string());
+    let message = info.location().map_or_else(
+        || message.clone(),
+        |location| format!("{message} at {location}"),
+    );
+    let context = REQUEST.try_with(Arc::clone).ok();
+    tracing::error!(panic = %message, "Panic");
+
+    let event = event(
+        "fatal",
+        "Panic",
+        &message,
+        context.as_deref(),
+        reporter.environment,
+    );
+    let dsn = reporter.dsn.clone();
+    let sender = std::thread::spawn(move || {
+        let Ok(runtime) = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        else {
+            return;
+        };
+        // A fresh client: the shared one's connections belong to the main runtime
+        let client = reqwest::Client::new();
+        let _ = runtime.block_on(dsn.request(&client, &event).send());
+    });
+    let _ = sender.join();
+}
+
+/// A Sentry event of `level` for an error of `kind`.
+fn event(
+    level: &str,
+    kind: &str,
+    message: &str,
+    request: Option<&RequestContext>,
+    environment: &str,
+) -> Value {
+    let mut event = json!({
+        "event_id": Uuid::new_v4().simple().to_string(),
+        "timestamp": Utc::now().to_rfc3339(),
+        "platform": "native",
+        "level": level,
+        "logger": "aircade_api",
+        "release": concat!("aircade-api@", env!("CARGO_PKG_VERSION")),
+        "environment": environment,
+        "exception": { "values": [{ "type": kind, "value": message }] },
+    });
+    if let Some(request) = request {
+        event["transaction"] = json!(format!("{} {}", request.method, request.route));
+        event["request"] = json!({ "method": request.method, "url": request.route });
+        event["tags"] = json!({
+            "route": request.route,
+            "request_id": request.request_id,
+        });
+        if let Some(user_id) = request.user_id.get() {
+            event["user"] = json!({ "id": user_id });
+        }
+    }
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dsn_resolves_the_store_endpoint() {
+        assert_eq!(
+            Dsn::parse("https://abc123@o42.ingest.sentry.io/7"),
+            Some(Dsn {
+                store_url: "https://o42.ingest.sentry.io/api/7/store/".to_string(),
+                public_key: "abc123".to_string(),
+            })
+        );
+        assert_eq!(
+            Dsn::parse("http://key@localhost:9000/sentry/3/"),
+            Some(Dsn {
+                store_url: "http://localhost:9000/sentry/api/3/store/".to_string(),
+                public_key: "key".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn malformed_dsns_are_rejected() {
+        assert!(Dsn::parse("not a url").is_none());
+        assert!(Dsn::parse("https://o42.ingest.sentry.io/7").is_none());
+        assert!(Dsn::parse("https://key@o42.ingest.sentry.io/").is_none());
+    }
+
+    #[test]
+    fn events_carry_the_request_context() {
+        let user_id = Uuid::new_v4();
+        let request = RequestContext {
+            method: "POST".to_string(),
+            route: "/api/v1/games/{id}/publish".to_string(),
+            request_id: Some("req-1".to_string()),
+            user_id: OnceLock::from(user_id),
+        };
+
+        let event = event("error", "InternalError", "boom", Some(&request), "staging");
+        assert_eq!(event["environment"], "staging");
+        assert_eq!(event["exception"]["values"][0]["value"], "boom");
+        assert_eq!(event["tags"]["route"], "/api/v1/games/{id}/publish");
+        assert_eq!(event["tags"]["request_id"], "req-1");
+        assert_eq!(event["user"]["id"], user_id.to_string());
+    }
+}
@@ -0,0 +1,168 @@
+//! A/B experiments: splitting users and anonymous clients between variants.
+//!
+//! An experiment divides traffic between named variants in proportion to their weights.
+//! Assignment is deterministic: a subject (a user ID, or an anonymous client's own
+//! identifier) is bucketed by hashing it together with the experiment key, so it gets the
+//! same variant on every request and every server without assignments being stored, and
+//! buckets in different experiments are independent. Changing an experiment's variants or
+//! weights may move subjects between variants.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Status of experiments that assign variants.
+pub const RUNNING: &str = "running";
+
+/// Allowed values for `experiment.status`.
+pub const STATUSES: [&str; 3] = ["draft", RUNNING, "stopped"];
+
+/// Most variants in one experiment.
+pub const MAX_VARIANTS: usize = 10;
+
+/// Longest variant name, in characters.
+pub const MAX_VARIANT_NAME_LENGTH: usize = 50;
+
+/// Highest weight of a single variant.
+pub const MAX_WEIGHT: u32 = 10_000;
+
+/// A variant of an experiment and its share of traffic, as stored in `experiment.variants`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Variant {
+    pub name: String,
+    /// Relative share of subjects assigned to this variant; `0` turns it off.
+    pub weight: u32,
+}
+
+/// Check an experiment's variants.
+///
+/// # Errors
+///
+/// Returns a message for the client if there are fewer than two or more than
+/// [`MAX_VARIANTS`] variants, a name is empty, too long or repeated, a weight is above
+/// [`MAX_WEIGHT`], or every weight is zero.
+pub fn validate_variants(variants: &[Variant]) -> Result<(), String> {
+    if !(2..=MAX_VARIANTS).contains(&variants.len()) {
+        return Err(format!(
+            "An experiment needs between 2 and {MAX_VARIANTS} variants."
+        ));
+    }
+    for (index, variant) in variants.iter().enumerate() {
+        let length = variant.name.chars().count();
+        if length == 0 || length > MAX_VARIANT_NAME_LENGTH || variant.name.trim() != variant.name {
+            return Err(format!(
+                "variants[{index}]: name must be 1 to {MAX_VARIANT_NAME_LENGTH} characters \
+                 without surrounding spaces."
+            ));
+        }
+        if variants[..index].iter().any(|v| v.name == variant.name) {
+            return Err(format!(
+                "variants[{index}]: name '{}' is used more than once.",
+                variant.name
+            ));
+        }
+        if variant.weight > MAX_WEIGHT {
+            return Err(format!(
+                "variants[{index}]: weight must be at most {MAX_WEIGHT}."
+            ));
+        }
+    }
+    if variants.iter().all(|v| v.weight == 0) {
+        return Err("At least one variant needs a weight above zero.".to_string());
+    }
+    Ok(())
+}
+
+/// The variant of experiment `key` that `subject` is assigned to, or `None` if every
+/// weight is zero.
+#[must_use]
+pub fn assign<'a>(key: &str, subject: &str, variants: &'a [Variant]) -> Option<&'a Variant> {
+    let total: u64 = variants.iter().map(|v| u64::from(v.weight)).sum();
+    if total == 0 {
+        return None;
+    }
+
+    let digest = Sha256::digest(format!("{key}:{subject}").as_bytes());
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&digest[..8]);
+    let mut bucket = u64::from_be_bytes(prefix) % total;
+
+    variants.iter().find(|v| {
+        let weight = u64::from(v.weight);
+        if bucket < weight {
+            true
+        } else {
+            bucket -= weight;
+            false
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variants(weights: &[(&str, u32)]) -> Vec<Variant> {
+        weights
+            .iter()
+            .map(|(name, weight)| Variant {
+                name: (*name).to_string(),
+                weight: *weight,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn assignment_is_stable_for_a_subject() {
+        let variants = variants(&[("control", 1), ("treatment", 1)]);
+        let first = assign("new-lobby", "user-1", &variants);
+        for _ in 0..10 {
+            assert_eq!(assign("new-lobby", "user-1", &variants), first);
+        }
+    }
+
+    #[test]
+    fn traffic_is_split_by_weight() {
+        let variants = variants(&[("control", 3), ("treatment", 1)]);
+        let treated = (0..4000)
+            .filter(|i| {
+                assign("new-lobby", &format!("user-{i}"), &variants)
+                    .is_some_and(|v| v.name == "treatment")
+            })
+            .count();
+        assert!((800..1200).contains(&treated), "{treated} of 4000 treated");
+    }
+
+    #[test]
+    fn zero_weight_variants_are_never_assigned() {
+        let variants = variants(&[("control", 1), ("off", 0), ("treatment", 1)]);
+        assert!((0..500).all(|i| {
+            assign("new-lobby", &format!("user-{i}"), &variants).is_some_and(|v| v.name != "off")
+        }));
+        assert_eq!(
+            assign("new-lobby", "user-1", &self::variants(&[("a", 0)])),
+            None
+        );
+    }
+
+    #[test]
+    fn experiments_bucket_independently() {
+        let variants = variants(&[("a", 1), ("b", 1)]);
+        let differing = (0..200)
+            .filter(|i| {
+                let subject = format!("user-{i}");
+                assign("first", &subject, &variants) != assign("second", &subject, &variants)
+            })
+            .count();
+        assert!(differing > 50, "only {differing} of 200 differ");
+    }
+
+    #[test]
+    fn invalid_variants_are_rejected() {
+        assert!(validate_variants(&variants(&[("control", 1), ("treatment", 1)])).is_ok());
+        assert!(validate_variants(&variants(&[("control", 1)])).is_err());
+        assert!(validate_variants(&variants(&[("a", 1), ("a", 1)])).is_err());
+        assert!(validate_variants(&variants(&[("a", 1), (" b", 1)])).is_err());
+        assert!(validate_variants(&variants(&[("a", 0), ("b", 0)])).is_err());
+        assert!(validate_variants(&variants(&[("a", 1), ("b", MAX_WEIGHT + 1)])).is_err());
+    }
+}
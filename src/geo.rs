@@ -0,0 +1,183 @@
+//! Country lookup for client IP addresses.
+//!
+//! Addresses are resolved against an IP-to-country database in the CSV layout of DB-IP's
+//! free "IP to Country Lite" download: one `first_ip,last_ip,country_code` range per line,
+//! for IPv4 and IPv6 alike, optionally gzipped. It is loaded from `GEOIP_DATABASE` at startup
+//! (by default the copy bundled under `data/`). Only the two-letter country code is ever
+//! stored; without a database every lookup is `None`.
+
+use std::io::Read;
+use std::net::IpAddr;
+use std::sync::OnceLock;
+
+use flate2::read::GzDecoder;
+
+use crate::config::Config;
+
+static DATABASE: OnceLock<GeoDatabase> = OnceLock::new();
+
+/// Code some databases give ranges of unknown location.
+const UNKNOWN_COUNTRY: &str = "ZZ";
+
+/// IP ranges and the country each belongs to, sorted by first address.
+#[derive(Debug, Default)]
+pub struct GeoDatabase {
+    v4: Vec<(u32, u32, [u8; 2])>,
+    v6: Vec<(u128, u128, [u8; 2])>,
+}
+
+impl GeoDatabase {
+    /// Parse a database in `first_ip,last_ip,country_code` CSV layout.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message naming the first malformed line.
+    pub fn parse(csv: &str) -> Result<Self, String> {
+        let mut database = Self::default();
+        for (number, line) in csv.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line
+                .split(',')
+                .map(|f| f.trim().trim_matches('"'))
+                .collect();
+            let [first, last, country] = fields[..] else {
+                return Err(format!(
+                    "line {}: expected first_ip,last_ip,country_code",
+                    number + 1
+                ));
+            };
+            let country = match country.as_bytes() {
+                [a, b] if a.is_ascii_alphabetic() && b.is_ascii_alphabetic() => {
+                    [a.to_ascii_uppercase(), b.to_ascii_uppercase()]
+                }
+                _ => {
+                    return Err(format!(
+                        "line {}: invalid country code '{country}'",
+                        number + 1
+                    ));
+                }
+            };
+            match (first.parse::<IpAddr>(), last.parse::<IpAddr>()) {
+                (Ok(IpAddr::V4(first)), Ok(IpAddr::V4(last))) if first <= last => {
+                    database.v4.push((first.into(), last.into(), country));
+                }
+                (Ok(IpAddr::V6(first)), Ok(IpAddr::V6(last))) if first <= last => {
+                    database.v6.push((first.into(), last.into(), country));
+                }
+                _ => return Err(format!("line {}: invalid address range", number + 1)),
+            }
+        }
+        database.v4.sort_unstable_by_key(|(first, _, _)| *first);
+        database.v6.sort_unstable_by_key(|(first, _, _)| *first);
+        Ok(database)
+    }
+
+    /// The country `ip` is in, as an uppercase ISO 3166-1 alpha-2 code.
+    #[must_use]
+    pub fn country(&self, ip: IpAddr) -> Option<String> {
+        let country = match ip.to_canonical() {
+            IpAddr::V4(ip) => find(&self.v4, u32::from(ip)),
+            IpAddr::V6(ip) => find(&self.v6, u128::from(ip)),
+        }?;
+        let country = String::from_utf8_lossy(&country).into_owned();
+        (country != UNKNOWN_COUNTRY).then_some(country)
+    }
+}
+
+/// The country of the range containing `ip`, in ranges sorted by first address.
+fn find<T: Ord + Copy>(ranges: &[(T, T, [u8; 2])], ip: T) -> Option<[u8; 2]> {
+    let index = ranges.partition_point(|(first, _, _)| *first <= ip);
+    let (_, last, country) = ranges.get(index.checked_sub(1)?)?;
+    (ip <= *last).then_some(*country)
+}
+
+/// Load the database at `config.geoip_database`, if there is one.
+///
+/// Logs a warning and resolves nothing if it cannot be read or parsed.
+pub fn init(config: &Config) {
+    let path = &config.geoip_database;
+    if path.is_empty() {
+        return;
+    }
+    let csv = std::fs::read(path).and_then(|bytes| {
+        let gzipped = std::path::Path::new(path)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("gz"));
+        if gzipped {
+            let mut csv = String::new();
+            GzDecoder::new(bytes.as_slice()).read_to_string(&mut csv)?;
+            Ok(csv)
+        } else {
+            String::from_utf8(bytes).map_err(std::io::Error::other)
+        }
+    });
+    let database = match csv
+        .map_err(|e| e.to_string())
+        .and_then(|csv| GeoDatabase::parse(&csv))
+    {
+        Ok(database) => database,
+        Err(e) => {
+            tracing::warn!(
+                path,
+                "GeoIP database not loaded, countries will not be recorded: {e}"
+            );
+            return;
+        }
+    };
+    let ranges = database.v4.len() + database.v6.len();
+    if install(database) {
+        tracing::info!(path, ranges, "GeoIP database loaded");
+    }
+}
+
+/// Use `database` for lookups from now on. Returns `false` if one is already installed.
+pub fn install(database: GeoDatabase) -> bool {
+    DATABASE.set(database).is_ok()
+}
+
+/// The country of a client address such as [`crate::auth::extract_client_ip`] returns, or
+/// `None` if it is unknown or no database is loaded.
+#[must_use]
+pub fn country_of(ip: &str) -> Option<String> {
+    DATABASE.get()?.country(ip.parse().ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "\
+1.0.0.0,1.0.0.255,AU
+1.0.1.0,1.0.3.255,cn
+\"2.0.0.0\",\"2.0.0.255\",\"ZZ\"
+2001:200::,2001:200:ffff:ffff:ffff:ffff:ffff:ffff,JP
+";
+
+    #[test]
+    fn addresses_resolve_to_their_range() -> Result<(), String> {
+        let database = GeoDatabase::parse(CSV)?;
+        let country = |ip: &str| ip.parse().ok().and_then(|ip| database.country(ip));
+        assert_eq!(country("1.0.0.0").as_deref(), Some("AU"));
+        assert_eq!(country("1.0.2.7").as_deref(), Some("CN"));
+        assert_eq!(country("::ffff:1.0.0.9").as_deref(), Some("AU"));
+        assert_eq!(country("2001:200::1").as_deref(), Some("JP"));
+        assert_eq!(country("1.0.4.0"), None);
+        assert_eq!(country("0.255.255.255"), None);
+        assert_eq!(country("2.0.0.1"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_lines_are_rejected() {
+        assert_eq!(
+            GeoDatabase::parse("1.0.0.0,1.0.0.255").err().as_deref(),
+            Some("line 1: expected first_ip,last_ip,country_code")
+        );
+        assert!(GeoDatabase::parse("1.0.0.9,1.0.0.0,AU").is_err());
+        assert!(GeoDatabase::parse("1.0.0.0,::1,AU").is_err());
+        assert!(GeoDatabase::parse("1.0.0.0,1.0.0.255,AUS").is_err());
+    }
+}
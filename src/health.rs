@@ -0,0 +1,128 @@
+//! Health checks of the components the API depends on.
+//!
+//! [`check_components`] probes each component concurrently. It backs the detailed health
+//! endpoint, and the health check job records its results every minute in `health_check`,
+//! from which the public status endpoint computes uptime.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, Statement};
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::storage;
+
+/// Longest a single component check may take before it is reported as down.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Key read from the storage backend to check it is reachable; it is never written.
+const STORAGE_PROBE_KEY: &str = "health/probe";
+
+/// Status of a component that answered its check.
+pub const UP: &str = "up";
+
+/// Status of a component whose check failed or timed out.
+pub const DOWN: &str = "down";
+
+/// Status of a component that is not in use.
+pub const DISABLED: &str = "disabled";
+
+/// Result of checking one component.
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentHealth {
+    /// [`UP`], [`DOWN`] or [`DISABLED`].
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u128>,
+    /// What is wrong, or why the component is disabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl ComponentHealth {
+    const fn disabled(detail: String) -> Self {
+        Self {
+            status: DISABLED,
+            latency_ms: None,
+            detail: Some(detail),
+        }
+    }
+}
+
+/// Check the database with a `SELECT 1` query, that no migrations are pending, and that the
+/// storage backend answers, all concurrently. Email is reported as disabled.
+///
+/// Returns the health of `database`, `migrations`, `storage` and `email`.
+pub async fn check_components(
+    db: &DatabaseConnection,
+    config: &Config,
+) -> BTreeMap<&'static str, ComponentHealth> {
+    let (database, migrations, storage) = tokio::join!(
+        check("database", async {
+            db.execute(Statement::from_string(
+                DbBackend::Postgres,
+                "SELECT 1".to_string(),
+            ))
+            .await?;
+            Ok(())
+        }),
+        check("migrations", async {
+            let pending = Migrator::get_pending_migrations(db).await?;
+            if !pending.is_empty() {
+                anyhow::bail!("{} migrations pending", pending.len());
+            }
+            Ok(())
+        }),
+        check("storage", async {
+            storage::configured(config, db)
+                .get(STORAGE_PROBE_KEY)
+                .await?;
+            Ok(())
+        }),
+    );
+    // Delivery is not implemented yet (see `crate::email`), so there is no provider to reach
+    let email = ComponentHealth::disabled("email is logged, not sent".to_string());
+
+    BTreeMap::from([
+        ("database", database),
+        ("migrations", migrations),
+        ("storage", storage),
+        ("email", email),
+    ])
+}
+
+/// Run `check`, timing it and reporting an error or timeout as the component being down.
+async fn check<F>(name: &'static str, check: F) -> ComponentHealth
+where
+    F: Future<Output = anyhow::Result<()>>,
+{
+    let start = Instant::now();
+    let result = tokio::time::timeout(CHECK_TIMEOUT, check)
+        .await
+        .unwrap_or_else(|_| {
+            Err(anyhow::anyhow!(
+                "timed out after {}s",
+                CHECK_TIMEOUT.as_secs()
+            ))
+        });
+    let latency_ms = Some(start.elapsed().as_millis());
+
+    match result {
+        Ok(()) => ComponentHealth {
+            status: UP,
+            latency_ms,
+            detail: None,
+        },
+        Err(e) => {
+            tracing::warn!(component = name, "Health check failed: {e}");
+            ComponentHealth {
+                status: DOWN,
+                latency_ms,
+                detail: Some(e.to_string()),
+            }
+        }
+    }
+}
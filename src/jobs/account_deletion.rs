@@ -0,0 +1,272 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sea_orm::ActiveValue::Set;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DatabaseTransaction, EntityTrait,
+    QueryFilter, QuerySelect, TransactionTrait,
+};
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::entities::{
+    api_token, auth_provider, collection, content_report, email_change, favorite, follow,
+    friendship, game, game_collaborator, known_device, login_event, notification,
+    oauth_authorization_code, oauth_client, player, refresh_token, review, scheduled_publish, user,
+    user_preference, user_totp, webauthn_challenge, webauthn_credential,
+};
+use crate::jobs::data_export;
+
+/// Days between requesting account deletion and it being carried out.
+pub const GRACE_PERIOD_DAYS: i64 = 14;
+
+/// Accepted values of `deletion_game_policy`.
+pub const GAME_POLICIES: [&str; 2] = ["delete", "keep"];
+
+/// How often due deletions are looked for.
+const POLL_INTERVAL: Duration = Duration::from_hours(1);
+
+/// Spawn the background task that carries out scheduled account deletions once they are due.
+pub fn spawn(db: DatabaseConnection, config: Config) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            match run_due(&db, &config, Utc::now()).await {
+                Ok(0) => {}
+                Ok(deleted) => tracing::info!(deleted, "Scheduled account deletions carried out"),
+                Err(e) => tracing::warn!(error = %e, "Failed to run scheduled account deletions"),
+            }
+        }
+    });
+}
+
+/// Delete every account whose `deletion_scheduled_at` is at or before `now`.
+///
+/// The user row itself is kept as an anonymized tombstone so that anything left credited to
+/// it stays consistent; everything personal hanging off it is removed. With the `"delete"`
+/// game policy the user's games are soft-deleted and the tombstone is marked deleted, so the
+/// purge job removes both once the retention window passes. With `"keep"` the games stay
+/// published under the anonymized account. Returns the number of accounts deleted.
+///
+/// # Errors
+///
+/// Returns an error if a query fails. Accounts not yet deleted stay scheduled and are retried
+/// on the next run.
+pub async fn run_due(
+    db: &DatabaseConnection,
+    config: &Config,
+    now: DateTime<Utc>,
+) -> anyhow::Result<u64> {
+    let due = user::Entity::find()
+        .filter(user::Column::DeletionScheduledAt.lte(now.fixed_offset()))
+        .all(db)
+        .await?;
+
+    let mut deleted = 0;
+    for user_model in due {
+        let avatar_url = user_model.avatar_url.clone();
+        data_export::delete_for_user(db, config, user_model.id).await?;
+
+        let txn = db.begin().await?;
+        delete_account(&txn, user_model, now).await?;
+        txn.commit().await?;
+        deleted += 1;
+
+        // Best-effort: the row no longer points at the file either way
+        if let Some(url) = avatar_url {
+            let file_path = std::path::Path::new(&config.upload_dir).join(url);
+            let _ = tokio::fs::remove_file(&file_path).await;
+        }
+    }
+
+    Ok(deleted)
+}
+
+/// Anonymize `user_model` and remove its personal data, inside `txn`.
+#[allow(clippy::too_many_lines)]
+pub(crate) async fn delete_account(
+    txn: &DatabaseTransaction,
+    user_model: user::Model,
+    now: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    let user_id = user_model.id;
+    let now = now.fixed_offset();
+    let delete_games = user_model.deletion_game_policy.as_deref() != Some("keep");
+
+    delete_credentials(txn, user_id).await?;
+
+    notification::Entity::delete_many()
+        .filter(notification::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+    login_event::Entity::delete_many()
+        .filter(login_event::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+    user_preference::Entity::delete_many()
+        .filter(user_preference::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+
+    follow::Entity::delete_many()
+        .filter(
+            Condition::any()
+                .add(follow::Column::FollowerId.eq(user_id))
+                .add(follow::Column::FolloweeId.eq(user_id)),
+        )
+        .exec(txn)
+        .await?;
+    friendship::Entity::delete_many()
+        .filter(
+            Condition::any()
+                .add(friendship::Column::RequesterId.eq(user_id))
+                .add(friendship::Column::AddresseeId.eq(user_id)),
+        )
+        .exec(txn)
+        .await?;
+
+    // Activity on other people's games
+    favorite::Entity::delete_many()
+        .filter(favorite::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+    collection::Entity::delete_many()
+        .filter(collection::Column::OwnerId.eq(user_id))
+        .exec(txn)
+        .await?;
+    review::Entity::delete_many()
+        .filter(review::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+    game_collaborator::Entity::delete_many()
+        .filter(game_collaborator::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+    scheduled_publish::Entity::delete_many()
+        .filter(scheduled_publish::Column::RequestedBy.eq(user_id))
+        .exec(txn)
+        .await?;
+    content_report::Entity::update_many()
+        .col_expr(
+            content_report::Column::ReporterId,
+            Expr::value(Option::<Uuid>::None),
+        )
+        .filter(content_report::Column::ReporterId.eq(user_id))
+        .exec(txn)
+        .await?;
+    player::Entity::update_many()
+        .col_expr(player::Column::UserId, Expr::value(Option::<Uuid>::None))
+        .filter(player::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+    game::Entity::update_many()
+        .col_expr(game::Column::LockedBy, Expr::value(Option::<Uuid>::None))
+        .filter(game::Column::LockedBy.eq(user_id))
+        .exec(txn)
+        .await?;
+
+    if delete_games {
+        game::Entity::update_many()
+            .col_expr(game::Column::DeletedAt, Expr::value(now))
+            .filter(game::Column::OwnerId.eq(user_id))
+            .filter(game::Column::DeletedAt.is_null())
+            .exec(txn)
+            .await?;
+    }
+
+    let mut active: user::ActiveModel = user_model.into();
+    active.email = Set(format!("deleted-{}@deleted.invalid", user_id.simple()));
+    active.username = Set(format!("deleted-{}", user_id.simple()));
+    active.display_name = Set(None);
+    active.avatar_url = Set(None);
+    active.bio = Set(None);
+    active.location = Set(None);
+    active.website_url = Set(None);
+    active.pronouns = Set(None);
+    active.social_links = Set(None);
+    active.email_verified = Set(false);
+    active.verified = Set(false);
+    active.suspension_reason = Set(None);
+    active.last_login_at = Set(None);
+    active.last_login_ip = Set(None);
+    active.account_status = Set("deleted".to_string());
+    active.deletion_scheduled_at = Set(None);
+    active.deletion_game_policy = Set(None);
+    active.updated_at = Set(now);
+    if delete_games {
+        active.deleted_at = Set(Some(now));
+    }
+    active.update(txn).await?;
+
+    Ok(())
+}
+
+/// Remove every way of signing in as `user_id` or acting on its behalf.
+pub(crate) async fn delete_credentials(
+    txn: &DatabaseTransaction,
+    user_id: Uuid,
+) -> anyhow::Result<()> {
+    auth_provider::Entity::delete_many()
+        .filter(auth_provider::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+    refresh_token::Entity::delete_many()
+        .filter(refresh_token::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+    email_change::Entity::delete_many()
+        .filter(email_change::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+    known_device::Entity::delete_many()
+        .filter(known_device::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+    user_totp::Entity::delete_many()
+        .filter(user_totp::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+    webauthn_credential::Entity::delete_many()
+        .filter(webauthn_credential::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+    webauthn_challenge::Entity::delete_many()
+        .filter(webauthn_challenge::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+
+    // API tokens, both the user's own and those other users granted to the user's OAuth apps
+    let client_ids: Vec<Uuid> = oauth_client::Entity::find()
+        .select_only()
+        .column(oauth_client::Column::Id)
+        .filter(oauth_client::Column::OwnerId.eq(user_id))
+        .into_tuple()
+        .all(txn)
+        .await?;
+    api_token::Entity::delete_many()
+        .filter(api_token::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+    oauth_authorization_code::Entity::delete_many()
+        .filter(oauth_authorization_code::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+    if !client_ids.is_empty() {
+        api_token::Entity::delete_many()
+            .filter(api_token::Column::OauthClientId.is_in(client_ids.clone()))
+            .exec(txn)
+            .await?;
+        oauth_authorization_code::Entity::delete_many()
+            .filter(oauth_authorization_code::Column::ClientId.is_in(client_ids.clone()))
+            .exec(txn)
+            .await?;
+        oauth_client::Entity::delete_many()
+            .filter(oauth_client::Column::Id.is_in(client_ids))
+            .exec(txn)
+            .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    TransactionTrait, sea_query::Expr,
+};
+
+use crate::entities::{copyright_notice, game, game_takedown};
+use crate::notifications::{self, NewNotification, NotificationHub};
+use crate::routes::games::REMOVED_BY_MODERATION;
+use crate::routes::legal::{COUNTER_NOTICED, RESTORED};
+
+/// How often counter-notice windows are checked.
+const POLL_INTERVAL: Duration = Duration::from_mins(5);
+
+/// Spawn the background task that restores games once their counter-notice window passes.
+pub fn spawn(db: DatabaseConnection, hub: NotificationHub) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            match run_due(&db, &hub, Utc::now()).await {
+                Ok(0) => {}
+                Ok(restored) => tracing::info!(restored, "Restored games after counter-notices"),
+                Err(e) => tracing::warn!(error = %e, "Failed to restore counter-noticed games"),
+            }
+        }
+    });
+}
+
+/// Restore the game of every counter-noticed copyright notice whose `restore_after` is at or
+/// before `now`, to the status it had before the takedown. Returns the number of notices
+/// closed.
+///
+/// # Errors
+///
+/// Returns an error if a query or a restore transaction fails. Notices not yet closed are
+/// retried on the next run.
+pub async fn run_due(
+    db: &DatabaseConnection,
+    hub: &NotificationHub,
+    now: DateTime<Utc>,
+) -> anyhow::Result<u64> {
+    let due = copyright_notice::Entity::find()
+        .filter(copyright_notice::Column::Status.eq(COUNTER_NOTICED))
+        .filter(copyright_notice::Column::RestoreAfter.lte(now.fixed_offset()))
+        .all(db)
+        .await?;
+
+    let mut restored = 0;
+    for notice in due {
+        let txn = db.begin().await?;
+
+        // Claim the notice first so an admin recording a court action meanwhile wins
+        let claimed = copyright_notice::Entity::update_many()
+            .col_expr(copyright_notice::Column::Status, Expr::value(RESTORED))
+            .col_expr(
+                copyright_notice::Column::RestoredAt,
+                Expr::value(now.fixed_offset()),
+            )
+            .col_expr(
+                copyright_notice::Column::UpdatedAt,
+                Expr::value(now.fixed_offset()),
+            )
+            .filter(copyright_notice::Column::Id.eq(notice.id))
+            .filter(copyright_notice::Column::Status.eq(COUNTER_NOTICED))
+            .exec(&txn)
+            .await?;
+        if claimed.rows_affected == 0 {
+            continue;
+        }
+        restored += 1;
+
+        let takedown = match notice.takedown_id {
+            Some(id) => game_takedown::Entity::find_by_id(id).one(&txn).await?,
+            None => None,
+        };
+        let game = match notice.game_id {
+            Some(id) => {
+                game::Entity::find_by_id(id)
+                    .filter(game::Column::DeletedAt.is_null())
+                    .one(&txn)
+                    .await?
+            }
+            None => None,
+        };
+        let (Some(takedown), Some(game)) = (takedown, game) else {
+            txn.commit().await?;
+            continue;
+        };
+        if game.status != REMOVED_BY_MODERATION {
+            txn.commit().await?;
+            continue;
+        }
+
+        let mut active: game::ActiveModel = game.into();
+        active.status = Set(takedown.previous_status);
+        active.updated_at = Set(now.fixed_offset());
+        let game = active.update(&txn).await?;
+        txn.commit().await?;
+
+        let new = NewNotification {
+            kind: notifications::COPYRIGHT_RESTORED,
+            title: format!("{} was restored", game.title),
+            body: format!(
+                "{} is back: no court action was reported within the counter-notice window.",
+                game.title
+            ),
+            link: None,
+        };
+        if let Err(e) = notifications::send(db, hub, game.owner_id, new).await {
+            tracing::warn!(error = %e, game_id = %game.id, "Failed to send notification");
+        }
+    }
+
+    Ok(restored)
+}
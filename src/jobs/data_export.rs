@@ -0,0 +1,430 @@
+use std::io::{Cursor, Write};
+
+use chrono::Utc;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+};
+use serde::Serialize;
+use uuid::Uuid;
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use crate::config::Config;
+use crate::entities::{
+    auth_provider, data_export, game, game_asset, game_version, refresh_token, review, user,
+};
+use crate::storage;
+
+/// Days a finished archive stays downloadable.
+pub const EXPORT_TTL_DAYS: i64 = 7;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Archive contents
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// `profile.json`
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfileExport {
+    id: Uuid,
+    email: String,
+    username: String,
+    display_name: Option<String>,
+    avatar_url: Option<String>,
+    bio: Option<String>,
+    location: Option<String>,
+    website_url: Option<String>,
+    pronouns: Option<String>,
+    social_links: Option<serde_json::Value>,
+    email_verified: bool,
+    role: String,
+    subscription_plan: String,
+    subscription_expires_at: Option<String>,
+    account_status: String,
+    last_login_at: Option<String>,
+    last_login_ip: Option<String>,
+    created_at: String,
+    updated_at: String,
+    auth_providers: Vec<AuthProviderExport>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthProviderExport {
+    provider: String,
+    provider_email: Option<String>,
+    linked_at: String,
+}
+
+/// An entry of `games.json`. Code lives next to it under `games/{slug}/`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GameExport {
+    id: Uuid,
+    title: String,
+    slug: String,
+    description: Option<String>,
+    technology: String,
+    status: String,
+    visibility: String,
+    min_players: i32,
+    max_players: i32,
+    play_count: i64,
+    created_at: String,
+    updated_at: String,
+    deleted_at: Option<String>,
+    versions: Vec<VersionExport>,
+    assets: Vec<AssetExport>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VersionExport {
+    version_number: i32,
+    changelog: Option<String>,
+    published_at: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AssetExport {
+    file_name: String,
+    file_type: String,
+    file_size: i32,
+    storage_url: String,
+    created_at: String,
+    deleted_at: Option<String>,
+}
+
+/// An entry of `sessions.json`: one refresh token issued to the user.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionExport {
+    user_agent: Option<String>,
+    ip_address: Option<String>,
+    signed_in_at: Option<String>,
+    issued_at: String,
+    expires_at: String,
+    revoked_at: Option<String>,
+}
+
+/// An entry of `reviews.json`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewExport {
+    game_id: Uuid,
+    rating: i32,
+    body: Option<String>,
+    creator_reply: Option<String>,
+    created_at: String,
+    updated_at: String,
+    deleted_at: Option<String>,
+}
+
+/// Everything gathered for one archive, before it is zipped.
+struct ExportData {
+    profile: ProfileExport,
+    games: Vec<GameExport>,
+    /// `(path, code)` of every draft and published screen.
+    code: Vec<(String, String)>,
+    sessions: Vec<SessionExport>,
+    reviews: Vec<ReviewExport>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Job
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Assemble the archive for the pending export `export_id` in the background.
+pub fn spawn(db: DatabaseConnection, config: Config, export_id: Uuid) {
+    tokio::spawn(async move {
+        if let Err(e) = run(&db, &config, export_id).await {
+            tracing::warn!(error = %e, %export_id, "Failed to assemble data export");
+            let failed = data_export::ActiveModel {
+                id: Set(export_id),
+                status: Set("failed".to_string()),
+                completed_at: Set(Some(Utc::now().fixed_offset())),
+                ..Default::default()
+            };
+            if let Err(e) = failed.update(&db).await {
+                tracing::warn!(error = %e, %export_id, "Failed to mark data export as failed");
+            }
+        }
+    });
+}
+
+/// Gather the user's data for `export_id`, store it as a zip archive and mark the export ready.
+///
+/// # Errors
+///
+/// Returns an error if the export is missing, a query fails, or the archive cannot be written
+/// or stored.
+pub async fn run(db: &DatabaseConnection, config: &Config, export_id: Uuid) -> anyhow::Result<()> {
+    let export = data_export::Entity::find_by_id(export_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Data export {export_id} not found"))?;
+
+    let data = gather(db, export.user_id).await?;
+    let archive = tokio::task::spawn_blocking(move || write_archive(&data)).await??;
+
+    let storage = storage::configured(config, db);
+    let key = format!("exports/{}/{export_id}.zip", export.user_id);
+    let size = i64::try_from(archive.len()).unwrap_or(i64::MAX);
+    storage.put(&key, archive, "application/zip").await?;
+
+    let now = Utc::now().fixed_offset();
+    let mut active: data_export::ActiveModel = export.into();
+    active.status = Set("ready".to_string());
+    active.storage_backend = Set(Some(storage.name().to_string()));
+    active.storage_key = Set(Some(key));
+    active.size_bytes = Set(Some(size));
+    active.completed_at = Set(Some(now));
+    active.expires_at = Set(Some(now + chrono::Duration::days(EXPORT_TTL_DAYS)));
+    active.update(db).await?;
+
+    Ok(())
+}
+
+/// Delete all of a user's exports along with their stored archives.
+///
+/// Archive deletion is best-effort: failures are logged and the rows are removed regardless.
+///
+/// # Errors
+///
+/// Returns an error if a query fails.
+pub async fn delete_for_user(
+    db: &DatabaseConnection,
+    config: &Config,
+    user_id: Uuid,
+) -> anyhow::Result<()> {
+    let exports = data_export::Entity::find()
+        .filter(data_export::Column::UserId.eq(user_id))
+        .all(db)
+        .await?;
+
+    for export in &exports {
+        let (Some(backend), Some(key)) = (&export.storage_backend, &export.storage_key) else {
+            continue;
+        };
+        let Some(storage) = storage::by_name(backend, config, db) else {
+            continue;
+        };
+        if let Err(e) = storage.delete(key).await {
+            tracing::warn!(error = %e, backend, key, "Failed to delete data export archive");
+        }
+    }
+
+    if !exports.is_empty() {
+        data_export::Entity::delete_many()
+            .filter(data_export::Column::Id.is_in(exports.iter().map(|e| e.id)))
+            .exec(db)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Load everything stored about `user_id`.
+async fn gather(db: &DatabaseConnection, user_id: Uuid) -> anyhow::Result<ExportData> {
+    let user_model = user::Entity::find_by_id(user_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("User {user_id} not found"))?;
+
+    let auth_providers = auth_provider::Entity::find()
+        .filter(auth_provider::Column::UserId.eq(user_id))
+        .order_by_asc(auth_provider::Column::CreatedAt)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|p| AuthProviderExport {
+            provider: p.provider,
+            provider_email: p.provider_email,
+            linked_at: p.created_at.to_rfc3339(),
+        })
+        .collect();
+
+    let profile = ProfileExport {
+        id: user_model.id,
+        email: user_model.email,
+        username: user_model.username,
+        display_name: user_model.display_name,
+        avatar_url: user_model.avatar_url,
+        bio: user_model.bio,
+        location: user_model.location,
+        website_url: user_model.website_url,
+        pronouns: user_model.pronouns,
+        social_links: user_model.social_links,
+        email_verified: user_model.email_verified,
+        role: user_model.role,
+        subscription_plan: user_model.subscription_plan,
+        subscription_expires_at: user_model.subscription_expires_at.map(|t| t.to_rfc3339()),
+        account_status: user_model.account_status,
+        last_login_at: user_model.last_login_at.map(|t| t.to_rfc3339()),
+        last_login_ip: user_model.last_login_ip,
+        created_at: user_model.created_at.to_rfc3339(),
+        updated_at: user_model.updated_at.to_rfc3339(),
+        auth_providers,
+    };
+
+    let (games, code) = gather_games(db, user_id).await?;
+
+    let sessions = refresh_token::Entity::find()
+        .filter(refresh_token::Column::UserId.eq(user_id))
+        .order_by_asc(refresh_token::Column::CreatedAt)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|t| SessionExport {
+            user_agent: t.user_agent,
+            ip_address: t.ip_address,
+            signed_in_at: t.signed_in_at.map(|t| t.to_rfc3339()),
+            issued_at: t.created_at.to_rfc3339(),
+            expires_at: t.expires_at.to_rfc3339(),
+            revoked_at: t.revoked_at.map(|t| t.to_rfc3339()),
+        })
+        .collect();
+
+    let reviews = review::Entity::find()
+        .filter(review::Column::UserId.eq(user_id))
+        .order_by_asc(review::Column::CreatedAt)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|r| ReviewExport {
+            game_id: r.game_id,
+            rating: r.rating,
+            body: r.body,
+            creator_reply: r.creator_reply,
+            created_at: r.created_at.to_rfc3339(),
+            updated_at: r.updated_at.to_rfc3339(),
+            deleted_at: r.deleted_at.map(|t| t.to_rfc3339()),
+        })
+        .collect();
+
+    Ok(ExportData {
+        profile,
+        games,
+        code,
+        sessions,
+        reviews,
+    })
+}
+
+/// Load the user's games with their versions and asset metadata, plus every screen's code as
+/// `(path, code)` pairs. Games in the trash are included; they are still the user's data.
+async fn gather_games(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+) -> anyhow::Result<(Vec<GameExport>, Vec<(String, String)>)> {
+    let owned = game::Entity::find()
+        .filter(game::Column::OwnerId.eq(user_id))
+        .order_by_asc(game::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let mut games = Vec::with_capacity(owned.len());
+    let mut code = Vec::new();
+    for g in owned {
+        let versions = game_version::Entity::find()
+            .filter(game_version::Column::GameId.eq(g.id))
+            .order_by_asc(game_version::Column::VersionNumber)
+            .all(db)
+            .await?;
+        let assets = game_asset::Entity::find()
+            .filter(game_asset::Column::GameId.eq(g.id))
+            .order_by_asc(game_asset::Column::CreatedAt)
+            .all(db)
+            .await?;
+
+        let dir = format!("games/{}", g.slug);
+        push_code(
+            &mut code,
+            &format!("{dir}/draft"),
+            g.game_screen_code,
+            g.controller_screen_code,
+        );
+
+        let versions = versions
+            .into_iter()
+            .map(|v| {
+                push_code(
+                    &mut code,
+                    &format!("{dir}/versions/{}", v.version_number),
+                    v.game_screen_code,
+                    v.controller_screen_code,
+                );
+                VersionExport {
+                    version_number: v.version_number,
+                    changelog: v.changelog.or(v.change_log),
+                    published_at: v.created_at.to_rfc3339(),
+                }
+            })
+            .collect();
+
+        games.push(GameExport {
+            id: g.id,
+            title: g.title,
+            slug: g.slug,
+            description: g.description,
+            technology: g.technology,
+            status: g.status,
+            visibility: g.visibility,
+            min_players: g.min_players,
+            max_players: g.max_players,
+            play_count: g.play_count,
+            created_at: g.created_at.to_rfc3339(),
+            updated_at: g.updated_at.to_rfc3339(),
+            deleted_at: g.deleted_at.map(|t| t.to_rfc3339()),
+            versions,
+            assets: assets
+                .into_iter()
+                .map(|a| AssetExport {
+                    file_name: a.file_name,
+                    file_type: a.file_type,
+                    file_size: a.file_size,
+                    storage_url: a.storage_url,
+                    created_at: a.created_at.to_rfc3339(),
+                    deleted_at: a.deleted_at.map(|t| t.to_rfc3339()),
+                })
+                .collect(),
+        });
+    }
+
+    Ok((games, code))
+}
+
+fn push_code(
+    code: &mut Vec<(String, String)>,
+    dir: &str,
+    game_screen: Option<String>,
+    controller_screen: Option<String>,
+) {
+    if let Some(source) = game_screen {
+        code.push((format!("{dir}/game-screen.js"), source));
+    }
+    if let Some(source) = controller_screen {
+        code.push((format!("{dir}/controller-screen.js"), source));
+    }
+}
+
+/// Build the zip archive. CPU-bound; call it from `spawn_blocking`.
+fn write_archive(data: &ExportData) -> anyhow::Result<Vec<u8>> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let mut write = |path: &str, bytes: &[u8]| -> anyhow::Result<()> {
+        zip.start_file(path, options)?;
+        zip.write_all(bytes)?;
+        Ok(())
+    };
+
+    write("profile.json", &serde_json::to_vec_pretty(&data.profile)?)?;
+    write("games.json", &serde_json::to_vec_pretty(&data.games)?)?;
+    write("sessions.json", &serde_json::to_vec_pretty(&data.sessions)?)?;
+    write("reviews.json", &serde_json::to_vec_pretty(&data.reviews)?)?;
+    for (path, source) in &data.code {
+        write(path, source.as_bytes())?;
+    }
+
+    Ok(zip.finish()?.into_inner())
+}
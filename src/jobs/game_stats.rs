@@ -0,0 +1,255 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use sea_orm::{
+    ActiveValue, ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
+    QuerySelect, prelude::DateTimeWithTimeZone, sea_query::OnConflict,
+};
+use uuid::Uuid;
+
+use crate::analytics::GAME_OPENED;
+use crate::entities::{analytics_event, game, game_daily_stats, game_play, player};
+
+/// How often recent plays are rolled up into `game_daily_stats`.
+const REFRESH_INTERVAL: Duration = Duration::from_mins(15);
+
+/// Plays started, ended or accruing time within this window are re-aggregated on every run.
+const LOOKBACK_HOURS: i64 = 48;
+
+/// Spawn the background task that keeps `game_daily_stats` up to date.
+pub fn spawn(db: DatabaseConnection) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+        loop {
+            interval.tick().await;
+            match rollup(&db, Utc::now()).await {
+                Ok(days) => tracing::debug!(days, "Game stats rolled up"),
+                Err(e) => tracing::warn!(error = %e, "Failed to roll up game stats"),
+            }
+        }
+    });
+}
+
+/// Recompute the daily stats of every (game, day) touched by a recent play or a recently
+/// reported `game_opened` event.
+///
+/// A play counts towards the UTC day it started on. Plays still in progress are included
+/// with the time accrued so far, so their days are recomputed until they end. Unique players
+/// are those who joined the sessions that played the game that day.
+///
+/// Returns the number of (game, day) rows written.
+///
+/// # Errors
+///
+/// Returns [`DbErr`] if any query fails.
+pub async fn rollup(db: &DatabaseConnection, now: DateTime<Utc>) -> Result<u64, DbErr> {
+    let since = (now - chrono::Duration::hours(LOOKBACK_HOURS)).fixed_offset();
+
+    let recent = game_play::Entity::find()
+        .filter(
+            Condition::any()
+                .add(game_play::Column::StartedAt.gte(since))
+                .add(game_play::Column::EndedAt.gte(since))
+                .add(game_play::Column::EndedAt.is_null()),
+        )
+        .all(db)
+        .await?;
+
+    // Earliest touched day per game; everything from there on is recomputed
+    let mut first_day: HashMap<Uuid, NaiveDate> = HashMap::new();
+    let mut touch = |game_id: Uuid, day: NaiveDate| {
+        first_day
+            .entry(game_id)
+            .and_modify(|d| *d = (*d).min(day))
+            .or_insert(day);
+    };
+    for play in &recent {
+        touch(play.game_id, play_day(play));
+    }
+    for (game_id, day) in recently_opened(db, since).await? {
+        touch(game_id, day);
+    }
+
+    let mut written = 0;
+    for (game_id, day) in first_day {
+        let from = day
+            .and_time(chrono::NaiveTime::MIN)
+            .and_utc()
+            .fixed_offset();
+        let plays = game_play::Entity::find()
+            .filter(game_play::Column::GameId.eq(game_id))
+            .filter(game_play::Column::StartedAt.gte(from))
+            .all(db)
+            .await?;
+        let players = session_players(db, plays.iter().map(|p| p.session_id)).await?;
+        let opens: Vec<DateTimeWithTimeZone> = analytics_event::Entity::find()
+            .select_only()
+            .column(analytics_event::Column::OccurredAt)
+            .filter(analytics_event::Column::Name.eq(GAME_OPENED))
+            .filter(analytics_event::Column::GameId.eq(game_id))
+            .filter(analytics_event::Column::OccurredAt.gte(from))
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        let rows: Vec<game_daily_stats::ActiveModel> = aggregate(&plays, &players, &opens)
+            .into_iter()
+            .map(|(day, stats)| game_daily_stats::ActiveModel {
+                game_id: ActiveValue::Set(game_id),
+                day: ActiveValue::Set(day),
+                plays: ActiveValue::Set(stats.plays),
+                sessions: ActiveValue::Set(stats.sessions),
+                play_seconds: ActiveValue::Set(stats.play_seconds),
+                players: ActiveValue::Set(stats.players),
+                peak_players: ActiveValue::Set(stats.peak_players),
+                unique_players: ActiveValue::Set(stats.unique_players),
+                opens: ActiveValue::Set(stats.opens),
+            })
+            .collect();
+        if rows.is_empty() {
+            continue;
+        }
+        written += rows.len() as u64;
+
+        game_daily_stats::Entity::insert_many(rows)
+            .on_conflict(
+                OnConflict::columns([
+                    game_daily_stats::Column::GameId,
+                    game_daily_stats::Column::Day,
+                ])
+                .update_columns([
+                    game_daily_stats::Column::Plays,
+                    game_daily_stats::Column::Sessions,
+                    game_daily_stats::Column::PlaySeconds,
+                    game_daily_stats::Column::Players,
+                    game_daily_stats::Column::PeakPlayers,
+                    game_daily_stats::Column::UniquePlayers,
+                    game_daily_stats::Column::Opens,
+                ])
+                .to_owned(),
+            )
+            .exec_without_returning(db)
+            .await?;
+    }
+
+    Ok(written)
+}
+
+/// Games opened according to `game_opened` events received since `since`, with the day of
+/// each open.
+async fn recently_opened(
+    db: &DatabaseConnection,
+    since: DateTimeWithTimeZone,
+) -> Result<Vec<(Uuid, NaiveDate)>, DbErr> {
+    let opened: Vec<(Uuid, DateTimeWithTimeZone)> = analytics_event::Entity::find()
+        .select_only()
+        .column(analytics_event::Column::GameId)
+        .column(analytics_event::Column::OccurredAt)
+        .filter(analytics_event::Column::Name.eq(GAME_OPENED))
+        .filter(analytics_event::Column::GameId.is_not_null())
+        .filter(analytics_event::Column::ReceivedAt.gte(since))
+        .into_tuple()
+        .all(db)
+        .await?;
+    // Clients may report any game ID; only games that exist get stats
+    let existing: HashSet<Uuid> = game::Entity::find()
+        .select_only()
+        .column(game::Column::Id)
+        .filter(game::Column::Id.is_in(opened.iter().map(|(id, _)| *id)))
+        .into_tuple()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+    Ok(opened
+        .into_iter()
+        .filter(|(game_id, _)| existing.contains(game_id))
+        .map(|(game_id, occurred_at)| (game_id, utc_day(occurred_at)))
+        .collect())
+}
+
+/// Who joined each of `sessions`, identified by user ID, or by player ID for guests.
+///
+/// # Errors
+///
+/// Returns [`DbErr`] if the query fails.
+pub(crate) async fn session_players(
+    db: &DatabaseConnection,
+    sessions: impl IntoIterator<Item = Uuid>,
+) -> Result<HashMap<Uuid, Vec<Uuid>>, DbErr> {
+    let sessions: HashSet<Uuid> = sessions.into_iter().collect();
+    if sessions.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let rows: Vec<(Uuid, Uuid, Option<Uuid>)> = player::Entity::find()
+        .select_only()
+        .column(player::Column::SessionId)
+        .column(player::Column::Id)
+        .column(player::Column::UserId)
+        .filter(player::Column::SessionId.is_in(sessions))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let mut players: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for (session_id, player_id, user_id) in rows {
+        players
+            .entry(session_id)
+            .or_default()
+            .push(user_id.unwrap_or(player_id));
+    }
+    Ok(players)
+}
+
+/// The UTC day of `at`.
+pub(crate) fn utc_day(at: DateTimeWithTimeZone) -> NaiveDate {
+    at.with_timezone(&Utc).date_naive()
+}
+
+#[derive(Default)]
+struct DayStats {
+    plays: i64,
+    sessions: i64,
+    play_seconds: i64,
+    players: i64,
+    peak_players: i32,
+    unique_players: i64,
+    opens: i64,
+}
+
+fn play_day(play: &game_play::Model) -> NaiveDate {
+    utc_day(play.started_at)
+}
+
+fn aggregate(
+    plays: &[game_play::Model],
+    players: &HashMap<Uuid, Vec<Uuid>>,
+    opens: &[DateTimeWithTimeZone],
+) -> BTreeMap<NaiveDate, DayStats> {
+    let mut days: BTreeMap<NaiveDate, DayStats> = BTreeMap::new();
+    let mut sessions: HashSet<(NaiveDate, Uuid)> = HashSet::new();
+    let mut unique_players: HashSet<(NaiveDate, Uuid)> = HashSet::new();
+
+    for play in plays {
+        let day = play_day(play);
+        let stats = days.entry(day).or_default();
+        stats.plays += 1;
+        if sessions.insert((day, play.session_id)) {
+            stats.sessions += 1;
+        }
+        stats.play_seconds += play.play_seconds;
+        stats.players += i64::from(play.player_count);
+        stats.peak_players = stats.peak_players.max(play.player_count);
+        for player in players.get(&play.session_id).into_iter().flatten() {
+            if unique_players.insert((day, *player)) {
+                stats.unique_players += 1;
+            }
+        }
+    }
+    for opened_at in opens {
+        days.entry(utc_day(*opened_at)).or_default().opens += 1;
+    }
+
+    days
+}
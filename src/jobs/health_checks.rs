@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, IntoActiveModel, QueryFilter};
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::entities::health_check;
+use crate::health;
+
+/// How often every component is checked.
+const CHECK_INTERVAL: Duration = Duration::from_mins(1);
+
+/// Days of checks kept, the longest window the status endpoint reports uptime over.
+pub const HISTORY_DAYS: i64 = 30;
+
+/// Spawn the background task that records a health check of every component each minute
+/// and prunes checks older than [`HISTORY_DAYS`].
+pub fn spawn(db: DatabaseConnection, config: Config) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            let now = Utc::now();
+            if let Err(e) = record(&db, &config, now).await {
+                tracing::warn!(error = %e, "Failed to record health checks");
+            }
+            match prune(&db, now).await {
+                Ok(0) => {}
+                Ok(checks) => tracing::debug!(checks, "Pruned old health checks"),
+                Err(e) => tracing::warn!(error = %e, "Failed to prune health checks"),
+            }
+        }
+    });
+}
+
+/// Check every component and record the results as checked at `now`.
+///
+/// # Errors
+///
+/// Returns [`DbErr`] if the insert fails.
+pub async fn record(
+    db: &DatabaseConnection,
+    config: &Config,
+    now: DateTime<Utc>,
+) -> Result<(), DbErr> {
+    let checks =
+        health::check_components(db, config)
+            .await
+            .into_iter()
+            .map(|(component, result)| {
+                health_check::Model {
+                    id: Uuid::new_v4(),
+                    component: component.to_string(),
+                    status: result.status.to_string(),
+                    latency_ms: result
+                        .latency_ms
+                        .map(|ms| i64::try_from(ms).unwrap_or(i64::MAX)),
+                    checked_at: now.fixed_offset(),
+                }
+                .into_active_model()
+            });
+    health_check::Entity::insert_many(checks).exec(db).await?;
+    Ok(())
+}
+
+/// Delete checks older than [`HISTORY_DAYS`]. Returns how many were deleted.
+///
+/// # Errors
+///
+/// Returns [`DbErr`] if the delete fails.
+pub async fn prune(db: &DatabaseConnection, now: DateTime<Utc>) -> Result<u64, DbErr> {
+    let cutoff = now - chrono::Duration::days(HISTORY_DAYS);
+    let result = health_check::Entity::delete_many()
+        .filter(health_check::Column::CheckedAt.lt(cutoff.fixed_offset()))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
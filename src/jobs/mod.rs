@@ -0,0 +1,11 @@
+pub mod account_deletion;
+pub mod copyright_restore;
+pub mod data_export;
+pub mod game_stats;
+pub mod health_checks;
+pub mod platform_stats;
+pub mod purge;
+pub mod scheduled_publish;
+pub mod storage_backfill;
+pub mod subscription_expiry;
+pub mod trending;
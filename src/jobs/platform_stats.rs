@@ -0,0 +1,207 @@
+use std::collections::{BTreeMap, HashSet};
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use sea_orm::{
+    ActiveValue, ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, prelude::DateTimeWithTimeZone, sea_query::OnConflict,
+};
+use uuid::Uuid;
+
+use super::game_stats::{session_players, utc_day};
+use crate::analytics::RETENTION_DAYS;
+use crate::entities::{analytics_event, game_play, platform_daily_stats, session};
+
+/// How often recent activity is rolled up into `platform_daily_stats`.
+const REFRESH_INTERVAL: Duration = Duration::from_mins(15);
+
+/// Days with sessions or plays created, ended or still running within this window are
+/// recomputed on every run.
+const LOOKBACK_HOURS: i64 = 48;
+
+/// Spawn the background task that keeps `platform_daily_stats` up to date and prunes
+/// analytics events past [`RETENTION_DAYS`].
+pub fn spawn(db: DatabaseConnection) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+        loop {
+            interval.tick().await;
+            let now = Utc::now();
+            match rollup(&db, now).await {
+                Ok(days) => tracing::debug!(days, "Platform stats rolled up"),
+                Err(e) => tracing::warn!(error = %e, "Failed to roll up platform stats"),
+            }
+            match prune_events(&db, now).await {
+                Ok(0) => {}
+                Ok(events) => tracing::info!(events, "Pruned expired analytics events"),
+                Err(e) => tracing::warn!(error = %e, "Failed to prune analytics events"),
+            }
+        }
+    });
+}
+
+/// Recompute the platform stats of every day from the earliest one touched by recent
+/// activity up to today.
+///
+/// Sessions count towards the UTC day they were created on and plays towards the day they
+/// started on; ones still running are included with the time accrued so far.
+///
+/// Returns the number of days written.
+///
+/// # Errors
+///
+/// Returns [`DbErr`] if any query fails.
+pub async fn rollup(db: &DatabaseConnection, now: DateTime<Utc>) -> Result<u64, DbErr> {
+    let since = (now - chrono::Duration::hours(LOOKBACK_HOURS)).fixed_offset();
+
+    let first_day = first_touched_day(db, since).await?;
+    let from = day_start(first_day);
+
+    let sessions = session::Entity::find()
+        .filter(session::Column::CreatedAt.gte(from))
+        .all(db)
+        .await?;
+    let plays = game_play::Entity::find()
+        .filter(game_play::Column::StartedAt.gte(from))
+        .all(db)
+        .await?;
+    let players = session_players(db, sessions.iter().map(|s| s.id)).await?;
+
+    let today = now.date_naive();
+    let mut days: BTreeMap<NaiveDate, DayStats> = first_day
+        .iter_days()
+        .take_while(|day| *day <= today)
+        .map(|day| (day, DayStats::default()))
+        .collect();
+
+    for session in &sessions {
+        let Some(stats) = days.get_mut(&utc_day(session.created_at)) else {
+            continue;
+        };
+        let ended_at = session.ended_at.map_or(now, |at| at.with_timezone(&Utc));
+        stats.sessions += 1;
+        stats.session_seconds += (ended_at - session.created_at.with_timezone(&Utc))
+            .num_seconds()
+            .max(0);
+        stats
+            .players
+            .extend(players.get(&session.id).into_iter().flatten().copied());
+    }
+    for play in &plays {
+        let Some(stats) = days.get_mut(&utc_day(play.started_at)) else {
+            continue;
+        };
+        stats.plays += 1;
+        stats.play_seconds += play.play_seconds;
+        stats.games.insert(play.game_id);
+    }
+
+    let mut rows = Vec::with_capacity(days.len());
+    for (day, stats) in days {
+        let events = analytics_event::Entity::find()
+            .filter(analytics_event::Column::OccurredAt.gte(day_start(day)))
+            .filter(analytics_event::Column::OccurredAt.lt(day_start(day + chrono::Days::new(1))))
+            .count(db)
+            .await?;
+        rows.push(platform_daily_stats::ActiveModel {
+            day: ActiveValue::Set(day),
+            sessions: ActiveValue::Set(stats.sessions),
+            session_seconds: ActiveValue::Set(stats.session_seconds),
+            plays: ActiveValue::Set(stats.plays),
+            play_seconds: ActiveValue::Set(stats.play_seconds),
+            unique_players: ActiveValue::Set(
+                i64::try_from(stats.players.len()).unwrap_or(i64::MAX),
+            ),
+            games_played: ActiveValue::Set(i64::try_from(stats.games.len()).unwrap_or(i64::MAX)),
+            events: ActiveValue::Set(i64::try_from(events).unwrap_or(i64::MAX)),
+        });
+    }
+    let written = rows.len() as u64;
+
+    platform_daily_stats::Entity::insert_many(rows)
+        .on_conflict(
+            OnConflict::column(platform_daily_stats::Column::Day)
+                .update_columns([
+                    platform_daily_stats::Column::Sessions,
+                    platform_daily_stats::Column::SessionSeconds,
+                    platform_daily_stats::Column::Plays,
+                    platform_daily_stats::Column::PlaySeconds,
+                    platform_daily_stats::Column::UniquePlayers,
+                    platform_daily_stats::Column::GamesPlayed,
+                    platform_daily_stats::Column::Events,
+                ])
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+
+    Ok(written)
+}
+
+/// Delete analytics events that occurred more than [`RETENTION_DAYS`] before `now`. Their
+/// days were rolled up long before.
+///
+/// Returns the number of events deleted.
+///
+/// # Errors
+///
+/// Returns [`DbErr`] if the delete fails.
+pub async fn prune_events(db: &DatabaseConnection, now: DateTime<Utc>) -> Result<u64, DbErr> {
+    let cutoff = (now - chrono::Duration::days(RETENTION_DAYS)).fixed_offset();
+    let result = analytics_event::Entity::delete_many()
+        .filter(analytics_event::Column::OccurredAt.lt(cutoff))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+/// The earliest day since `since`, or of a session or play that ended since then or is still
+/// running.
+async fn first_touched_day(
+    db: &DatabaseConnection,
+    since: DateTimeWithTimeZone,
+) -> Result<NaiveDate, DbErr> {
+    let oldest_session: Option<DateTimeWithTimeZone> = session::Entity::find()
+        .select_only()
+        .column(session::Column::CreatedAt)
+        .filter(
+            Condition::any()
+                .add(session::Column::EndedAt.gte(since))
+                .add(session::Column::EndedAt.is_null()),
+        )
+        .order_by_asc(session::Column::CreatedAt)
+        .into_tuple()
+        .one(db)
+        .await?;
+    let oldest_play: Option<DateTimeWithTimeZone> = game_play::Entity::find()
+        .select_only()
+        .column(game_play::Column::StartedAt)
+        .filter(
+            Condition::any()
+                .add(game_play::Column::EndedAt.gte(since))
+                .add(game_play::Column::EndedAt.is_null()),
+        )
+        .order_by_asc(game_play::Column::StartedAt)
+        .into_tuple()
+        .one(db)
+        .await?;
+    Ok([oldest_session, oldest_play]
+        .into_iter()
+        .flatten()
+        .map(utc_day)
+        .fold(utc_day(since), NaiveDate::min))
+}
+
+#[derive(Default)]
+struct DayStats {
+    sessions: i64,
+    session_seconds: i64,
+    plays: i64,
+    play_seconds: i64,
+    players: HashSet<Uuid>,
+    games: HashSet<Uuid>,
+}
+
+fn day_start(day: NaiveDate) -> DateTimeWithTimeZone {
+    day.and_time(NaiveTime::MIN).and_utc().fixed_offset()
+}
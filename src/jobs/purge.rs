@@ -0,0 +1,193 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    QuerySelect,
+};
+use uuid::Uuid;
+
+use crate::auth::login_events;
+use crate::config::Config;
+use crate::entities::{game, game_asset, login_event, refresh_token, user};
+use crate::storage::{self, INLINE_BACKEND};
+
+/// How often expired rows are looked for.
+const POLL_INTERVAL: Duration = Duration::from_hours(1);
+
+/// Rows permanently removed by one purge run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PurgeStats {
+    pub games: u64,
+    pub assets: u64,
+    pub users: u64,
+    pub refresh_tokens: u64,
+    /// Sign-in events older than [`login_events::RETENTION_DAYS`].
+    pub login_events: u64,
+    /// Stored blobs deleted because no remaining asset referenced them.
+    pub blobs: u64,
+}
+
+/// Spawn the background task that hard-deletes rows soft-deleted longer than
+/// `DELETED_RETENTION_DAYS` ago.
+pub fn spawn(db: DatabaseConnection, config: Config) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            match run(&db, &config, Utc::now()).await {
+                Ok(stats) if stats == PurgeStats::default() => {}
+                Ok(stats) => tracing::info!(
+                    games = stats.games,
+                    assets = stats.assets,
+                    users = stats.users,
+                    refresh_tokens = stats.refresh_tokens,
+                    login_events = stats.login_events,
+                    blobs = stats.blobs,
+                    "Purged expired soft-deleted rows"
+                ),
+                Err(e) => tracing::warn!(error = %e, "Failed to purge soft-deleted rows"),
+            }
+        }
+    });
+}
+
+/// Permanently remove games, assets and users soft-deleted before the retention window ending
+/// at `now`, plus refresh tokens revoked or expired before it and sign-in events past their
+/// own retention.
+///
+/// Asset rows (including those of purged games and of purged users' games) are deleted
+/// explicitly first so their blobs can be cleaned up; a blob is only removed once no remaining
+/// asset shares its storage key. Blob deletion failures are only logged, since the rows pointing
+/// at them are already gone.
+///
+/// # Errors
+///
+/// Returns an error if a query fails.
+pub async fn run(
+    db: &DatabaseConnection,
+    config: &Config,
+    now: DateTime<Utc>,
+) -> anyhow::Result<PurgeStats> {
+    let cutoff = (now - chrono::Duration::days(config.deleted_retention_days)).fixed_offset();
+    let mut stats = PurgeStats::default();
+
+    let expired_users: Vec<Uuid> = user::Entity::find()
+        .select_only()
+        .column(user::Column::Id)
+        .filter(user::Column::DeletedAt.lt(cutoff))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let mut game_filter = Condition::any().add(game::Column::DeletedAt.lt(cutoff));
+    if !expired_users.is_empty() {
+        game_filter = game_filter.add(game::Column::OwnerId.is_in(expired_users.clone()));
+    }
+    let expired_games: Vec<Uuid> = game::Entity::find()
+        .select_only()
+        .column(game::Column::Id)
+        .filter(game_filter)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let mut asset_filter = Condition::any().add(game_asset::Column::DeletedAt.lt(cutoff));
+    if !expired_games.is_empty() {
+        asset_filter = asset_filter.add(game_asset::Column::GameId.is_in(expired_games.clone()));
+    }
+    let assets = game_asset::Entity::find()
+        .select_only()
+        .column(game_asset::Column::Id)
+        .column(game_asset::Column::StorageBackend)
+        .column(game_asset::Column::StorageKey)
+        .filter(asset_filter)
+        .into_tuple::<(Uuid, String, Option<String>)>()
+        .all(db)
+        .await?;
+
+    if !assets.is_empty() {
+        stats.assets = game_asset::Entity::delete_many()
+            .filter(game_asset::Column::Id.is_in(assets.iter().map(|(id, _, _)| *id)))
+            .exec(db)
+            .await?
+            .rows_affected;
+    }
+
+    // Games cascade to versions, tags, reviews, favorites, collaborators, ...
+    if !expired_games.is_empty() {
+        stats.games = game::Entity::delete_many()
+            .filter(game::Column::Id.is_in(expired_games))
+            .exec(db)
+            .await?
+            .rows_affected;
+    }
+    if !expired_users.is_empty() {
+        stats.users = user::Entity::delete_many()
+            .filter(user::Column::Id.is_in(expired_users))
+            .exec(db)
+            .await?
+            .rows_affected;
+    }
+
+    stats.refresh_tokens = refresh_token::Entity::delete_many()
+        .filter(
+            Condition::any()
+                .add(refresh_token::Column::RevokedAt.lt(cutoff))
+                .add(refresh_token::Column::ExpiresAt.lt(cutoff)),
+        )
+        .exec(db)
+        .await?
+        .rows_affected;
+
+    let login_event_cutoff =
+        (now - chrono::Duration::days(login_events::RETENTION_DAYS)).fixed_offset();
+    stats.login_events = login_event::Entity::delete_many()
+        .filter(login_event::Column::CreatedAt.lt(login_event_cutoff))
+        .exec(db)
+        .await?
+        .rows_affected;
+
+    let blobs: HashSet<(String, String)> = assets
+        .into_iter()
+        .filter(|(_, backend, _)| backend != INLINE_BACKEND)
+        .filter_map(|(_, backend, key)| Some((backend, key?)))
+        .collect();
+    for (backend, key) in blobs {
+        if delete_orphaned_blob(db, config, &backend, &key).await? {
+            stats.blobs += 1;
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Delete the blob at `key` unless another asset still uses it. Returns whether it was deleted.
+async fn delete_orphaned_blob(
+    db: &DatabaseConnection,
+    config: &Config,
+    backend: &str,
+    key: &str,
+) -> anyhow::Result<bool> {
+    let still_used = game_asset::Entity::find()
+        .filter(game_asset::Column::StorageBackend.eq(backend))
+        .filter(game_asset::Column::StorageKey.eq(key))
+        .count(db)
+        .await?
+        > 0;
+    if still_used {
+        return Ok(false);
+    }
+
+    let Some(storage) = storage::by_name(backend, config, db) else {
+        return Ok(false);
+    };
+    match storage.delete(key).await {
+        Ok(()) => Ok(true),
+        Err(e) => {
+            tracing::warn!(error = %e, backend, key, "Failed to delete purged asset blob");
+            Ok(false)
+        }
+    }
+}
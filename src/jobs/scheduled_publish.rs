@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, TransactionTrait};
+
+use crate::achievements;
+use crate::config::Config;
+use crate::entities::{game, scheduled_publish};
+use crate::error::AppError;
+use crate::notifications::NotificationHub;
+use crate::routes::games::{ensure_publishable, ensure_valid_code, publish_new_version};
+use crate::xp;
+
+/// How often due publishes are looked for.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Spawn the background task that carries out scheduled publishes once they are due.
+pub fn spawn(db: DatabaseConnection, config: Config, hub: NotificationHub) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            match run_due(&db, &config, &hub, Utc::now()).await {
+                Ok(0) => {}
+                Ok(published) => tracing::info!(published, "Scheduled publishes carried out"),
+                Err(e) => tracing::warn!(error = %e, "Failed to run scheduled publishes"),
+            }
+        }
+    });
+}
+
+/// Publish every game whose scheduled `publish_at` is at or before `now`.
+///
+/// Each game is published from its code at that moment and re-checked like a manual publish;
+/// a game that no longer passes (or was deleted) has its schedule dropped with a warning.
+/// Returns the number of games published.
+///
+/// # Errors
+///
+/// Returns an error if a query or a publish transaction fails. Publishes not yet carried out
+/// stay scheduled and are retried on the next run.
+pub async fn run_due(
+    db: &DatabaseConnection,
+    config: &Config,
+    hub: &NotificationHub,
+    now: DateTime<Utc>,
+) -> anyhow::Result<u64> {
+    let due = scheduled_publish::Entity::find()
+        .filter(scheduled_publish::Column::PublishAt.lte(now.fixed_offset()))
+        .all(db)
+        .await?;
+
+    let mut published = 0;
+    for pending in due {
+        let txn = db.begin().await?;
+
+        // Claim the row first so concurrent schedulers cannot publish twice
+        let claimed = scheduled_publish::Entity::delete_by_id(pending.game_id)
+            .filter(scheduled_publish::Column::PublishAt.eq(pending.publish_at))
+            .exec(&txn)
+            .await?;
+        if claimed.rows_affected == 0 {
+            continue;
+        }
+
+        let Some(game) = game::Entity::find_by_id(pending.game_id)
+            .filter(game::Column::DeletedAt.is_null())
+            .one(&txn)
+            .await?
+        else {
+            txn.commit().await?;
+            continue;
+        };
+
+        let scan_findings =
+            match ensure_publishable(&game).and_then(|()| ensure_valid_code(&game, config)) {
+                Ok(findings) => findings,
+                Err(e) => {
+                    let reason = match e {
+                        AppError::Unprocessable(_, message) => message,
+                        _ => "game is not publishable".to_string(),
+                    };
+                    tracing::warn!(game_id = %game.id, %reason, "Dropped scheduled publish");
+                    txn.commit().await?;
+                    continue;
+                }
+            };
+
+        let (game_id, owner_id) = (game.id, game.owner_id);
+        publish_new_version(
+            &txn,
+            game,
+            pending.requested_by,
+            pending.changelog,
+            scan_findings,
+        )
+        .await?;
+        txn.commit().await?;
+        achievements::game_published(db, hub, owner_id).await;
+        xp::game_published(db, owner_id, game_id).await;
+        published += 1;
+    }
+
+    Ok(published)
+}
@@ -0,0 +1,66 @@
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect,
+};
+
+use crate::config::Config;
+use crate::entities::game_asset;
+use crate::storage::{self, INLINE_BACKEND};
+
+/// Number of assets moved per query.
+const BATCH_SIZE: u64 = 50;
+
+/// Spawn a one-off background task that moves `inline` assets into the configured storage.
+pub fn spawn(db: DatabaseConnection, config: Config) {
+    tokio::spawn(async move {
+        match run(&db, &config).await {
+            Ok(0) => {}
+            Ok(moved) => tracing::info!(moved, "Inline assets moved to storage"),
+            Err(e) => tracing::warn!(error = %e, "Asset storage backfill failed"),
+        }
+    });
+}
+
+/// Move every asset whose bytes still live in `game_asset.file_data` into the configured
+/// storage backend, clearing `file_data` and recording its content hash.
+///
+/// Each asset is written to storage before its row is updated, so an interrupted run can
+/// simply be restarted. Returns the number of assets moved.
+///
+/// # Errors
+///
+/// Returns an error if a query, a storage write, or a row update fails.
+pub async fn run(db: &DatabaseConnection, config: &Config) -> anyhow::Result<u64> {
+    let storage = storage::configured(config, db);
+    let mut moved = 0;
+
+    loop {
+        let batch = game_asset::Entity::find()
+            .filter(game_asset::Column::StorageBackend.eq(INLINE_BACKEND))
+            .order_by_asc(game_asset::Column::CreatedAt)
+            .limit(BATCH_SIZE)
+            .all(db)
+            .await?;
+
+        if batch.is_empty() {
+            return Ok(moved);
+        }
+
+        for asset in batch {
+            let key = storage::asset_key(asset.game_id, asset.id);
+            let file_data = asset.file_data.clone();
+            storage
+                .put(&key, file_data.clone(), &asset.file_type)
+                .await?;
+
+            let mut active: game_asset::ActiveModel = asset.into();
+            active.file_data = ActiveValue::Set(Vec::new());
+            active.storage_backend = ActiveValue::Set(storage.name().to_string());
+            active.storage_key = ActiveValue::Set(Some(key));
+            active.content_hash = ActiveValue::Set(Some(storage::content_hash(&file_data)));
+            active.update(db).await?;
+
+            moved += 1;
+        }
+    }
+}
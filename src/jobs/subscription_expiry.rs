@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, sea_query::Expr};
+
+use crate::billing::FREE_PLAN;
+use crate::entities::user;
+
+/// How often lapsed plans are looked for.
+const POLL_INTERVAL: Duration = Duration::from_hours(1);
+
+/// How long after its paid period a plan is kept, so a renewal reported late by Stripe does
+/// not downgrade the user in between.
+const GRACE_PERIOD_HOURS: i64 = 24;
+
+/// Spawn the background task that moves users whose paid plan lapsed back to the free plan.
+pub fn spawn(db: DatabaseConnection) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            match run_due(&db, Utc::now()).await {
+                Ok(0) => {}
+                Ok(downgraded) => tracing::info!(downgraded, "Downgraded lapsed subscriptions"),
+                Err(e) => tracing::warn!(error = %e, "Failed to downgrade lapsed subscriptions"),
+            }
+        }
+    });
+}
+
+/// Move every user whose plan expired more than the grace period before `now` to the free
+/// plan, returning how many were downgraded. Plans without an expiry never lapse.
+///
+/// # Errors
+///
+/// Returns an error if the database update fails.
+pub async fn run_due(db: &DatabaseConnection, now: DateTime<Utc>) -> Result<u64, DbErr> {
+    let cutoff = (now - chrono::Duration::hours(GRACE_PERIOD_HOURS)).fixed_offset();
+    let result = user::Entity::update_many()
+        .col_expr(user::Column::SubscriptionPlan, Expr::value(FREE_PLAN))
+        .col_expr(
+            user::Column::SubscriptionExpiresAt,
+            Expr::value(Option::<DateTime<chrono::FixedOffset>>::None),
+        )
+        .col_expr(user::Column::UpdatedAt, Expr::value(now.fixed_offset()))
+        .filter(user::Column::SubscriptionPlan.ne(FREE_PLAN))
+        .filter(user::Column::SubscriptionExpiresAt.lt(cutoff))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, TransactionTrait,
+    sea_query::Expr,
+};
+use uuid::Uuid;
+
+use crate::entities::{game, player, session};
+use crate::routes::games::not_shadow_banned;
+
+/// How often the trending scores are recomputed.
+const REFRESH_INTERVAL: Duration = Duration::from_mins(15);
+
+/// Only sessions active within this window contribute to the score.
+const WINDOW_DAYS: i64 = 7;
+
+/// A session's contribution halves every `HALF_LIFE_HOURS`.
+const HALF_LIFE_HOURS: f64 = 48.0;
+
+/// Base weight of a single play (a session that loaded the game).
+const PLAY_WEIGHT: f64 = 1.0;
+
+/// Additional weight for every player that joined the session.
+const PLAYER_WEIGHT: f64 = 0.5;
+
+/// Spawn the background task that periodically refreshes `game.trending_score`.
+pub fn spawn(db: DatabaseConnection) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+        loop {
+            interval.tick().await;
+            match recompute(&db, Utc::now()).await {
+                Ok(updated) => tracing::debug!(updated, "Trending scores refreshed"),
+                Err(e) => tracing::warn!(error = %e, "Failed to refresh trending scores"),
+            }
+        }
+    });
+}
+
+/// Recompute the time-decayed trending score of every game.
+///
+/// Each session that loaded a game within the last [`WINDOW_DAYS`], and was not hosted by a
+/// shadow-banned user, contributes `PLAY_WEIGHT + PLAYER_WEIGHT * players`, decayed
+/// exponentially by the age of its last activity. Games with no recent activity are reset to zero.
+///
+/// Returns the number of games that received a non-zero score.
+///
+/// # Errors
+///
+/// Returns [`DbErr`] if any query or the update transaction fails.
+pub async fn recompute(db: &DatabaseConnection, now: DateTime<Utc>) -> Result<u64, DbErr> {
+    let since = now - chrono::Duration::days(WINDOW_DAYS);
+
+    let sessions = session::Entity::find()
+        .filter(session::Column::GameId.is_not_null())
+        .filter(session::Column::UpdatedAt.gte(since.fixed_offset()))
+        .filter(not_shadow_banned(session::Column::HostId, None))
+        .all(db)
+        .await?;
+
+    let session_ids: Vec<Uuid> = sessions.iter().map(|s| s.id).collect();
+    let mut players_per_session: HashMap<Uuid, u32> = HashMap::new();
+    if !session_ids.is_empty() {
+        for p in player::Entity::find()
+            .filter(player::Column::SessionId.is_in(session_ids))
+            .all(db)
+            .await?
+        {
+            *players_per_session.entry(p.session_id).or_default() += 1;
+        }
+    }
+
+    let mut scores: HashMap<Uuid, f64> = HashMap::new();
+    for s in &sessions {
+        let Some(game_id) = s.game_id else {
+            continue;
+        };
+        let players = players_per_session.get(&s.id).copied().unwrap_or_default();
+        *scores.entry(game_id).or_default() += session_score(
+            players,
+            now.signed_duration_since(s.updated_at.with_timezone(&Utc)),
+        );
+    }
+
+    let txn = db.begin().await?;
+
+    game::Entity::update_many()
+        .col_expr(game::Column::TrendingScore, Expr::value(0.0_f64))
+        .filter(game::Column::TrendingScore.ne(0.0_f64))
+        .exec(&txn)
+        .await?;
+
+    for (game_id, score) in &scores {
+        game::Entity::update_many()
+            .col_expr(game::Column::TrendingScore, Expr::value(*score))
+            .filter(game::Column::Id.eq(*game_id))
+            .exec(&txn)
+            .await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(scores.len() as u64)
+}
+
+/// Decayed contribution of a single session with `players` participants and the given age.
+fn session_score(players: u32, age: chrono::Duration) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let age_hours = age.num_seconds().max(0) as f64 / 3600.0;
+    PLAYER_WEIGHT.mul_add(f64::from(players), PLAY_WEIGHT)
+        * 0.5_f64.powf(age_hours / HALF_LIFE_HOURS)
+}
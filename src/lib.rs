@@ -1,8 +1,31 @@
+pub mod achievements;
+pub mod alerting;
+pub mod analytics;
+pub mod audit;
 pub mod auth;
+pub mod avatars;
+pub mod billing;
+pub mod classroom;
 pub mod config;
 pub mod db;
+pub mod email;
 pub mod entities;
 pub mod error;
+pub mod error_reporting;
+pub mod experiments;
+pub mod geo;
+pub mod health;
+pub mod jobs;
+pub mod logging;
+pub mod markdown;
+pub mod media;
+pub mod middleware;
+pub mod notifications;
+pub mod preferences;
 pub mod routes;
 pub mod sessions;
 pub mod state;
+pub mod storage;
+pub mod text_moderation;
+pub mod validation;
+pub mod xp;
@@ -0,0 +1,339 @@
+//! Log output formats and the runtime log filter.
+//!
+//! Logs are human-readable by default. With `LOG_FORMAT=json` every line is a JSON object
+//! instead, so log pipelines (Railway, ELK) can index the fields without parsing text.
+//!
+//! The log filter can be overridden while the server runs (`PUT /api/v1/admin/log-level`),
+//! e.g. to turn on debug logging while diagnosing an issue. Overrides expire on their own,
+//! restoring the filter the process started with.
+
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber, span};
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{EnvFilter, Registry, reload};
+use uuid::Uuid;
+
+/// Formats each event as one line of JSON for log ingestion: `timestamp`, `level`, `target`,
+/// `message`, the event's `fields`, and the `spans` it happened in, outermost first.
+///
+/// The `request_id` of the enclosing request span is repeated at the top level, so a
+/// request's lines can be found without looking into `spans`. Pair it with [`JsonFields`],
+/// which stores span fields as JSON for this formatter to read back.
+pub struct JsonFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let metadata = event.metadata();
+        let mut fields = JsonVisitor::default();
+        event.record(&mut fields);
+        let mut fields = fields.0;
+
+        let mut line = Map::new();
+        line.insert(
+            "timestamp".to_string(),
+            Utc::now()
+                .to_rfc3339_opts(SecondsFormat::Millis, true)
+                .into(),
+        );
+        line.insert("level".to_string(), metadata.level().as_str().into());
+        line.insert("target".to_string(), metadata.target().into());
+        if let Some(message) = fields.remove("message") {
+            line.insert("message".to_string(), message);
+        }
+
+        let mut spans = Vec::new();
+        if let Some(scope) = ctx.event_scope() {
+            for span in scope.from_root() {
+                let mut entry = Map::new();
+                entry.insert("name".to_string(), span.name().into());
+                if let Some(formatted) = span.extensions().get::<FormattedFields<N>>()
+                    && let Ok(span_fields) = serde_json::from_str::<Map<String, Value>>(formatted)
+                {
+                    if let Some(request_id) = span_fields.get("request_id") {
+                        line.insert("request_id".to_string(), request_id.clone());
+                    }
+                    entry.extend(span_fields);
+                }
+                spans.push(Value::Object(entry));
+            }
+        }
+
+        if !fields.is_empty() {
+            line.insert("fields".to_string(), Value::Object(fields));
+        }
+        if !spans.is_empty() {
+            line.insert("spans".to_string(), Value::Array(spans));
+        }
+        writeln!(writer, "{}", Value::Object(line))
+    }
+}
+
+/// Records span fields as a JSON object, for [`JsonFormat`] to include in each line.
+pub struct JsonFields;
+
+impl<'writer> FormatFields<'writer> for JsonFields {
+    fn format_fields<R: RecordFields>(
+        &self,
+        mut writer: Writer<'writer>,
+        fields: R,
+    ) -> fmt::Result {
+        let mut visitor = JsonVisitor::default();
+        fields.record(&mut visitor);
+        write!(writer, "{}", Value::Object(visitor.0))
+    }
+
+    fn add_fields(
+        &self,
+        current: &'writer mut FormattedFields<Self>,
+        fields: &span::Record<'_>,
+    ) -> fmt::Result {
+        // Fields recorded after the span was created, like the response status
+        let mut visitor = JsonVisitor(serde_json::from_str(current).unwrap_or_default());
+        fields.record(&mut visitor);
+        current.fields = Value::Object(visitor.0).to_string();
+        Ok(())
+    }
+}
+
+/// Collects fields as JSON values, keeping numbers and booleans typed.
+#[derive(Default)]
+struct JsonVisitor(Map<String, Value>);
+
+impl Visit for JsonVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{value:?}").into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        self.0
+            .insert(field.name().to_string(), value.to_string().into());
+    }
+}
+
+/// The process's log filter, swappable at runtime.
+struct FilterControl {
+    handle: reload::Handle<EnvFilter, Registry>,
+    /// The directives the process started with, restored when an override ends.
+    default_directives: String,
+    current: Mutex<Option<FilterOverride>>,
+}
+
+static FILTER: OnceLock<FilterControl> = OnceLock::new();
+
+/// A temporary replacement of the log filter, set by an admin.
+#[derive(Debug, Clone)]
+pub struct FilterOverride {
+    pub id: Uuid,
+    /// `EnvFilter` directives, e.g. `aircade_api=debug,tower_http=debug`.
+    pub directives: String,
+    /// When the default filter is restored.
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Build the log filter layer from `directives` and keep a handle to it, so
+/// [`override_filter`] can replace it later. Called once, when tracing is initialized.
+pub fn reloadable_filter(directives: &str) -> reload::Layer<EnvFilter, Registry> {
+    let (layer, handle) = reload::Layer::new(EnvFilter::new(directives));
+    let _ = FILTER.set(FilterControl {
+        handle,
+        default_directives: directives.to_string(),
+        current: Mutex::new(None),
+    });
+    layer
+}
+
+/// Check that `directives` parse as an `EnvFilter`.
+///
+/// # Errors
+///
+/// Returns the parse error, for the client.
+pub fn validate_directives(directives: &str) -> Result<(), String> {
+    EnvFilter::try_new(directives)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// The default filter's directives, and the override in effect, if any. `None` if the
+/// filter is not reloadable (see [`reloadable_filter`]).
+pub fn filter_state() -> Option<(String, Option<FilterOverride>)> {
+    let control = FILTER.get()?;
+    let current = control
+        .current
+        .lock()
+        .map(|current| current.clone())
+        .unwrap_or_default();
+    Some((control.default_directives.clone(), current))
+}
+
+/// Replace the log filter with `directives` for `ttl`, after which the default filter is
+/// restored. Replaces any override already in effect.
+///
+/// # Errors
+///
+/// Returns an error if the directives do not parse or the filter is not reloadable.
+pub fn override_filter(directives: &str, ttl: Duration) -> anyhow::Result<FilterOverride> {
+    let control = FILTER
+        .get()
+        .context("the log filter is not reloadable in this process")?;
+    control.handle.reload(EnvFilter::try_new(directives)?)?;
+
+    let filter_override = FilterOverride {
+        id: Uuid::new_v4(),
+        directives: directives.to_string(),
+        expires_at: Utc::now() + chrono::Duration::from_std(ttl)?,
+    };
+    if let Ok(mut current) = control.current.lock() {
+        *current = Some(filter_override.clone());
+    }
+    tracing::warn!(
+        directives,
+        ttl_secs = ttl.as_secs(),
+        "Log filter overridden"
+    );
+
+    let id = filter_override.id;
+    tokio::spawn(async move {
+        tokio::time::sleep(ttl).await;
+        if let Err(e) = restore(Some(id)) {
+            tracing::warn!(error = %e, "Failed to restore the default log filter");
+        }
+    });
+    Ok(filter_override)
+}
+
+/// Restore the default log filter now. Returns the override that was in effect, if any.
+///
+/// # Errors
+///
+/// Returns an error if the filter is not reloadable.
+pub fn reset_filter() -> anyhow::Result<Option<FilterOverride>> {
+    restore(None)
+}
+
+/// Restore the default filter, or with `only`, only while that override is still in effect
+/// so an expiring override does not end a newer one.
+fn restore(only: Option<Uuid>) -> anyhow::Result<Option<FilterOverride>> {
+    let control = FILTER
+        .get()
+        .context("the log filter is not reloadable in this process")?;
+    let Ok(mut current) = control.current.lock() else {
+        anyhow::bail!("the log filter override state is poisoned");
+    };
+    if only.is_some() && current.as_ref().map(|o| o.id) != only {
+        return Ok(None);
+    }
+    let Some(ended) = current.take() else {
+        return Ok(None);
+    };
+    control
+        .handle
+        .reload(EnvFilter::new(&control.default_directives))?;
+    tracing::warn!(
+        directives = control.default_directives.as_str(),
+        "Default log filter restored"
+    );
+    Ok(Some(ended))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    /// Captures everything the subscriber writes.
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0
+                .lock()
+                .map_err(|_| io::Error::other("poisoned"))?
+                .extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn events_are_single_json_lines_with_span_fields() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .event_format(JsonFormat)
+                .fmt_fields(JsonFields)
+                .with_writer(move || writer.clone()),
+        );
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!(
+                "http_request",
+                request_id = "req-1",
+                status_code = tracing::field::Empty,
+            );
+            let _entered = span.enter();
+            span.record("status_code", 201);
+            tracing::info!(latency_ms = 12, cached = false, "response");
+        });
+
+        let output = buffer.0.lock().map(|b| b.clone()).unwrap_or_default();
+        let output = String::from_utf8(output).unwrap_or_default();
+        assert_eq!(output.lines().count(), 1);
+        let line: Value = serde_json::from_str(output.trim()).unwrap_or_default();
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["message"], "response");
+        assert_eq!(line["request_id"], "req-1");
+        assert_eq!(line["fields"]["latency_ms"], 12);
+        assert_eq!(line["fields"]["cached"], false);
+        assert_eq!(line["spans"][0]["name"], "http_request");
+        assert_eq!(line["spans"][0]["status_code"], 201);
+    }
+}
@@ -1,16 +1,23 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use axum::Router;
-use axum::http::{Method, Request, header};
+use axum::http::Request;
 use axum::response::Response;
 use migration::{Migrator, MigratorTrait};
-use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
 use tracing::Span;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 
-use aircade_api::config::{Config, Environment};
+use aircade_api::analytics::AnalyticsWriter;
+use aircade_api::auth::jwt::KeySet;
+use aircade_api::config::{Config, LogFormat, StorageBackend};
+use aircade_api::logging::{self, JsonFields, JsonFormat};
+use aircade_api::middleware::ip_filter::IpFilter;
+use aircade_api::middleware::proxy::TrustedProxies;
+use aircade_api::middleware::request_id::{self, REQUEST_ID_HEADER};
+use aircade_api::notifications::NotificationHub;
 use aircade_api::sessions::SessionManager;
 use aircade_api::state::AppState;
 
@@ -18,9 +25,13 @@ use aircade_api::state::AppState;
 async fn main() -> anyhow::Result<()> {
     // Load configuration
     let config = Config::from_env()?;
+    // Fail at startup rather than on the first sign-in
+    let jwt_keys = Arc::new(KeySet::from_config(&config)?);
 
     // Initialize structured logging
-    init_tracing(&config.log_level);
+    init_tracing(&config.log_level, config.log_format);
+    aircade_api::error_reporting::init(&config);
+    aircade_api::geo::init(&config);
 
     tracing::info!(
         version = env!("CARGO_PKG_VERSION"),
@@ -30,7 +41,11 @@ async fn main() -> anyhow::Result<()> {
 
     // Connect to database
     tracing::info!("Connecting to database...");
-    let db = aircade_api::db::connect(&config.database_url).await?;
+    let db = aircade_api::db::connect(
+        &config.database_url,
+        Duration::from_millis(config.slow_query_threshold_ms),
+    )
+    .await?;
     tracing::info!("Database connected");
 
     // Run migrations
@@ -38,11 +53,37 @@ async fn main() -> anyhow::Result<()> {
     Migrator::up(&db, None).await?;
     tracing::info!("Migrations applied");
 
+    let notification_hub = NotificationHub::new();
+
+    // Start background jobs
+    aircade_api::jobs::trending::spawn(db.clone());
+    aircade_api::jobs::game_stats::spawn(db.clone());
+    aircade_api::jobs::platform_stats::spawn(db.clone());
+    aircade_api::jobs::health_checks::spawn(db.clone(), config.clone());
+    aircade_api::jobs::scheduled_publish::spawn(
+        db.clone(),
+        config.clone(),
+        notification_hub.clone(),
+    );
+    aircade_api::jobs::copyright_restore::spawn(db.clone(), notification_hub.clone());
+    aircade_api::jobs::purge::spawn(db.clone(), config.clone());
+    aircade_api::jobs::account_deletion::spawn(db.clone(), config.clone());
+    aircade_api::jobs::subscription_expiry::spawn(db.clone());
+    if config.storage_backend != StorageBackend::Database {
+        aircade_api::jobs::storage_backfill::spawn(db.clone(), config.clone());
+    }
+
+    let session_manager = SessionManager::new();
+    aircade_api::alerting::init(&config, session_manager.clone());
+
     // Build application state
     let state = AppState {
         db,
         config: config.clone(),
-        session_manager: SessionManager::new(),
+        jwt_keys,
+        session_manager,
+        notification_hub,
+        analytics: AnalyticsWriter::new(),
     };
 
     // Build the application with middleware
@@ -53,35 +94,18 @@ async fn main() -> anyhow::Result<()> {
     let listener = tokio::net::TcpListener::bind(addr).await?;
     tracing::info!(%addr, "Server listening");
 
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
 
 /// Build the full application router with all middleware layers.
 fn build_app(state: AppState, config: &Config) -> Router {
-    let cors = if config.environment == Environment::Production {
-        let origin = config
-            .frontend_url
-            .parse::<axum::http::HeaderValue>()
-            .unwrap_or_else(|_| axum::http::HeaderValue::from_static("http://localhost:3001"));
-
-        CorsLayer::new()
-            .allow_origin(origin)
-            .allow_methods([
-                Method::GET,
-                Method::POST,
-                Method::PUT,
-                Method::PATCH,
-                Method::DELETE,
-                Method::OPTIONS,
-            ])
-            .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE, header::ACCEPT])
-            .allow_credentials(true)
-            .max_age(Duration::from_secs(3600))
-    } else {
-        CorsLayer::permissive()
-    };
+    let cors = aircade_api::middleware::cors::layer(config);
 
     let trace = TraceLayer::new_for_http()
         .make_span_with(|request: &Request<_>| {
@@ -89,6 +113,8 @@ fn build_app(state: AppState, config: &Config) -> Router {
                 "http_request",
                 method = %request.method(),
                 uri = %request.uri(),
+                route = tracing::field::Empty,
+                request_id = %request_id(request),
                 status_code = tracing::field::Empty,
             )
         })
@@ -97,19 +123,53 @@ fn build_app(state: AppState, config: &Config) -> Router {
             tracing::info!(latency_ms = latency.as_millis(), "response");
         });
 
-    aircade_api::routes::router()
+    let ip_filter = axum::middleware::from_fn_with_state(
+        IpFilter::from_config(config),
+        aircade_api::middleware::ip_filter::enforce,
+    );
+    let client_ip = axum::middleware::from_fn_with_state(
+        TrustedProxies::from_config(config),
+        aircade_api::middleware::proxy::resolve,
+    );
+
+    aircade_api::routes::router(config)
+        .layer(ip_filter)
+        .layer(client_ip)
         .with_state(state)
         .layer(cors)
         .layer(trace)
+        .layer(axum::middleware::from_fn(request_id::assign))
 }
 
-/// Initialize the `tracing` subscriber with an environment-based filter.
-fn init_tracing(log_level: &str) {
-    let env_filter = tracing_subscriber::EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| format!("aircade_api={log_level},tower_http=info,sea_orm=warn").into());
+/// The ID [`request_id::assign`] gave the request, so every log line of a request can be
+/// correlated.
+fn request_id<B>(request: &Request<B>) -> &str {
+    request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+}
 
-    tracing_subscriber::registry()
-        .with(env_filter)
-        .with(tracing_subscriber::fmt::layer())
-        .init();
+/// Initialize the `tracing` subscriber with an environment-based filter admins can override
+/// at runtime, writing text or JSON lines depending on `log_format`.
+fn init_tracing(log_level: &str, log_format: LogFormat) {
+    let directives = std::env::var(tracing_subscriber::EnvFilter::DEFAULT_ENV)
+        .ok()
+        .filter(|directives| logging::validate_directives(directives).is_ok())
+        .unwrap_or_else(|| {
+            format!("aircade_api={log_level},tower_http=info,sea_orm=warn,sqlx::query=warn")
+        });
+
+    let registry = tracing_subscriber::registry().with(logging::reloadable_filter(&directives));
+    match log_format {
+        LogFormat::Text => registry.with(tracing_subscriber::fmt::layer()).init(),
+        LogFormat::Json => registry
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .event_format(JsonFormat)
+                    .fmt_fields(JsonFields),
+            )
+            .init(),
+    }
 }
@@ -0,0 +1,35 @@
+use pulldown_cmark::{Options, Parser, html};
+
+/// Render creator-written Markdown to HTML that is safe to embed in a page.
+///
+/// Raw HTML in the source is passed through the renderer and then stripped down to
+/// `ammonia`'s default allowlist (no scripts, event handlers or `javascript:` links).
+#[must_use]
+pub fn render(markdown: &str) -> String {
+    let options =
+        Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS;
+    let mut rendered = String::new();
+    html::push_html(&mut rendered, Parser::new_ext(markdown, options));
+
+    ammonia::clean(&rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_markdown() {
+        assert_eq!(
+            render("**New:** faster paddles"),
+            "<p><strong>New:</strong> faster paddles</p>\n"
+        );
+    }
+
+    #[test]
+    fn strips_unsafe_html() {
+        let html = render("Hi <script>alert(1)</script> [x](javascript:alert(1))");
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("javascript:"));
+    }
+}
@@ -0,0 +1,83 @@
+use std::io::Cursor;
+
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat, ImageReader, ImageResult, Limits};
+
+/// Largest source dimension (in pixels) accepted for thumbnail generation.
+const MAX_SOURCE_DIMENSION: u32 = 8192;
+
+/// A standard thumbnail size, cropped to fill.
+#[derive(Debug, Clone, Copy)]
+pub struct ThumbnailSize {
+    pub name: &'static str,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Thumbnail sizes generated for every game (16:9).
+pub const THUMBNAIL_SIZES: [ThumbnailSize; 3] = [
+    ThumbnailSize {
+        name: "small",
+        width: 320,
+        height: 180,
+    },
+    ThumbnailSize {
+        name: "medium",
+        width: 640,
+        height: 360,
+    },
+    ThumbnailSize {
+        name: "large",
+        width: 1280,
+        height: 720,
+    },
+];
+
+/// The size whose URL is stored in `game.thumbnail`.
+pub const DEFAULT_THUMBNAIL_SIZE: &str = "medium";
+
+/// A generated WebP thumbnail.
+#[derive(Debug)]
+pub struct Thumbnail {
+    pub size: ThumbnailSize,
+    pub data: Vec<u8>,
+}
+
+/// MIME types accepted as thumbnail sources.
+pub const THUMBNAIL_SOURCE_TYPES: &[&str] = &["image/png", "image/jpeg", "image/gif", "image/webp"];
+
+/// Decode a raster image and render it into every [`THUMBNAIL_SIZES`] entry as WebP.
+///
+/// This is CPU-bound; call it from `spawn_blocking`.
+///
+/// # Errors
+///
+/// Returns an [`image::ImageError`] if the data is not a supported image, exceeds
+/// [`MAX_SOURCE_DIMENSION`], or encoding fails.
+pub fn generate_thumbnails(data: &[u8]) -> ImageResult<Vec<Thumbnail>> {
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_SOURCE_DIMENSION);
+    limits.max_image_height = Some(MAX_SOURCE_DIMENSION);
+
+    let mut reader = ImageReader::new(Cursor::new(data)).with_guessed_format()?;
+    reader.limits(limits);
+    let source = reader.decode()?;
+
+    THUMBNAIL_SIZES
+        .iter()
+        .map(|size| {
+            let resized = source.resize_to_fill(size.width, size.height, FilterType::Lanczos3);
+            Ok(Thumbnail {
+                size: *size,
+                data: encode_webp(&resized)?,
+            })
+        })
+        .collect()
+}
+
+/// Encode as lossless WebP (the only WebP mode supported by the pure-Rust encoder).
+fn encode_webp(img: &DynamicImage) -> ImageResult<Vec<u8>> {
+    let mut out = Cursor::new(Vec::new());
+    DynamicImage::ImageRgba8(img.to_rgba8()).write_to(&mut out, ImageFormat::WebP)?;
+    Ok(out.into_inner())
+}
@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+use axum::http::{HeaderName, HeaderValue, Method, header};
+use tower_http::cors::CorsLayer;
+
+use crate::auth::cookies::CSRF_HEADER;
+use crate::config::{Config, Environment};
+
+/// The CORS policy of the API.
+///
+/// In production only `FRONTEND_URL` may call it, with credentials so cookie auth works, and
+/// with the headers the API reads, including [`CSRF_HEADER`] for cookie-authenticated
+/// mutations. Elsewhere any origin may call it without credentials.
+pub fn layer(config: &Config) -> CorsLayer {
+    if config.environment != Environment::Production {
+        return CorsLayer::permissive();
+    }
+
+    let origin = config
+        .frontend_url
+        .parse::<HeaderValue>()
+        .unwrap_or_else(|_| HeaderValue::from_static("http://localhost:3001"));
+
+    CorsLayer::new()
+        .allow_origin(origin)
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+            Method::OPTIONS,
+        ])
+        .allow_headers([
+            header::AUTHORIZATION,
+            header::CONTENT_TYPE,
+            header::ACCEPT,
+            HeaderName::from_static(CSRF_HEADER),
+        ])
+        .allow_credentials(true)
+        .max_age(Duration::from_hours(1))
+}
@@ -0,0 +1,205 @@
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use super::client_ip;
+use crate::config::Config;
+use crate::error::AppError;
+
+/// Path prefix of the routes `ADMIN_ALLOWED_IPS` restricts.
+const ADMIN_PREFIX: &str = "/api/v1/admin";
+
+/// An IP network in CIDR notation, e.g. `10.0.0.0/8` or `2001:db8::/32`. A bare address is a
+/// network of one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cidr {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl Cidr {
+    /// Whether `ip` is inside this network. IPv4-mapped IPv6 addresses match IPv4 networks.
+    #[must_use]
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.network, ip.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - u32::from(self.prefix_len))
+                    .unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - u32::from(self.prefix_len))
+                    .unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for Cidr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let entry = s.trim();
+        let (address, prefix_len) = entry
+            .split_once('/')
+            .map_or((entry, None), |(a, p)| (a, Some(p)));
+        let network: IpAddr = address
+            .parse()
+            .map_err(|_| format!("'{s}' is not an IP address or CIDR network"))?;
+        let max_len = if network.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix_len {
+            None => max_len,
+            Some(len) => len
+                .parse::<u8>()
+                .ok()
+                .filter(|len| *len <= max_len)
+                .ok_or_else(|| format!("'{s}' has an invalid prefix length"))?,
+        };
+        Ok(Self {
+            network,
+            prefix_len,
+        })
+    }
+}
+
+impl fmt::Display for Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix_len)
+    }
+}
+
+/// Parse a comma-separated list of CIDR networks; blank entries are ignored.
+///
+/// # Errors
+///
+/// Returns a description of the first entry that is not an address or CIDR network.
+pub fn parse_list(list: &str) -> Result<Vec<Cidr>, String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+/// Which client networks may reach the API, built from the `*_IPS` settings in [`Config`].
+///
+/// The deny list wins over both allow lists. An empty allow list allows everyone; a non-empty
+/// one also rejects clients whose address cannot be determined.
+///
+/// Apply it with `axum::middleware::from_fn_with_state(IpFilter::from_config(&config),
+/// enforce)`, inside [`super::proxy::resolve`], which decides the client address.
+#[derive(Debug, Clone, Default)]
+pub struct IpFilter {
+    allowed: Vec<Cidr>,
+    admin_allowed: Vec<Cidr>,
+    denied: Vec<Cidr>,
+}
+
+impl IpFilter {
+    #[must_use]
+    pub fn from_config(config: &Config) -> Arc<Self> {
+        Arc::new(Self {
+            allowed: config.allowed_ips.clone(),
+            admin_allowed: config.admin_allowed_ips.clone(),
+            denied: config.denied_ips.clone(),
+        })
+    }
+
+    /// Whether a client at `ip` may request `path`.
+    #[must_use]
+    pub fn permits(&self, ip: Option<IpAddr>, path: &str) -> bool {
+        let admin = path == ADMIN_PREFIX
+            || path
+                .strip_prefix(ADMIN_PREFIX)
+                .is_some_and(|rest| rest.starts_with('/'));
+        let in_any = |list: &[Cidr]| ip.is_some_and(|ip| list.iter().any(|c| c.contains(ip)));
+
+        if in_any(&self.denied) {
+            return false;
+        }
+        if !self.allowed.is_empty() && !in_any(&self.allowed) {
+            return false;
+        }
+        !admin || self.admin_allowed.is_empty() || in_any(&self.admin_allowed)
+    }
+}
+
+/// Middleware rejecting requests the filter does not permit with `403 Forbidden`.
+pub async fn enforce(State(filter): State<Arc<IpFilter>>, req: Request, next: Next) -> Response {
+    let ip = client_ip(&req).and_then(|ip| ip.parse().ok());
+    if !filter.permits(ip, req.uri().path()) {
+        tracing::warn!(ip = ?ip, path = %req.uri().path(), "Request rejected by IP filter");
+        return AppError::Forbidden("Access from your network is not allowed".to_string())
+            .into_response();
+    }
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cidrs(list: &str) -> Vec<Cidr> {
+        parse_list(list).unwrap_or_default()
+    }
+
+    fn ip(s: &str) -> Option<IpAddr> {
+        s.parse().ok()
+    }
+
+    #[test]
+    fn parses_networks_and_bare_addresses() {
+        let parsed = cidrs("10.0.0.0/8, 192.0.2.7 ,2001:db8::/32,");
+        let shown: Vec<String> = parsed.iter().map(ToString::to_string).collect();
+        assert_eq!(shown, ["10.0.0.0/8", "192.0.2.7/32", "2001:db8::/32"]);
+        assert!(parse_list("10.0.0.0/33").is_err());
+        assert!(parse_list("intranet").is_err());
+    }
+
+    #[test]
+    fn networks_contain_their_addresses() {
+        let v4 = cidrs("10.1.0.0/16");
+        assert!(ip("10.1.200.3").is_some_and(|ip| v4[0].contains(ip)));
+        assert!(!ip("10.2.0.1").is_some_and(|ip| v4[0].contains(ip)));
+        assert!(ip("::ffff:10.1.0.9").is_some_and(|ip| v4[0].contains(ip)));
+
+        let any = cidrs("0.0.0.0/0");
+        assert!(ip("203.0.113.1").is_some_and(|ip| any[0].contains(ip)));
+        assert!(!ip("2001:db8::1").is_some_and(|ip| any[0].contains(ip)));
+    }
+
+    #[test]
+    fn admin_allowlist_only_restricts_admin_routes() {
+        let filter = IpFilter {
+            admin_allowed: cidrs("10.0.0.0/8"),
+            ..IpFilter::default()
+        };
+        assert!(filter.permits(ip("10.0.0.5"), "/api/v1/admin/tags"));
+        assert!(!filter.permits(ip("203.0.113.1"), "/api/v1/admin/tags"));
+        assert!(!filter.permits(None, "/api/v1/admin/tags"));
+        assert!(filter.permits(ip("203.0.113.1"), "/api/v1/games"));
+        assert!(filter.permits(ip("203.0.113.1"), "/api/v1/administrators"));
+    }
+
+    #[test]
+    fn deny_list_wins_over_allow_lists() {
+        let filter = IpFilter {
+            allowed: cidrs("198.51.100.0/24"),
+            denied: cidrs("198.51.100.66"),
+            ..IpFilter::default()
+        };
+        assert!(filter.permits(ip("198.51.100.1"), "/api/v1/games"));
+        assert!(!filter.permits(ip("198.51.100.66"), "/api/v1/games"));
+        assert!(!filter.permits(ip("192.0.2.1"), "/api/v1/games"));
+        assert!(IpFilter::default().permits(None, "/api/v1/admin/tags"));
+    }
+}
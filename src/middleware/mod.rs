@@ -0,0 +1,25 @@
+//! Tower layers shared by the route groups.
+
+pub mod cors;
+pub mod csrf;
+pub mod ip_filter;
+pub mod proxy;
+pub mod rate_limit;
+pub mod request_id;
+pub mod route;
+
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, Request};
+
+use crate::auth::extract_client_ip;
+
+/// The client's IP address: `X-Forwarded-For` as [`proxy::resolve`] rewrote it, otherwise the
+/// peer address.
+fn client_ip(req: &Request) -> Option<String> {
+    extract_client_ip(req.headers()).or_else(|| {
+        req.extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip().to_string())
+    })
+}
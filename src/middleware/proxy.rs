@@ -0,0 +1,152 @@
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use axum::extract::{ConnectInfo, FromRequestParts, Request, State};
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::Response;
+
+use super::ip_filter::Cidr;
+use crate::config::Config;
+
+const FORWARDED_FOR: &str = "x-forwarded-for";
+const REAL_IP: &str = "x-real-ip";
+
+/// The reverse proxies in front of the API, from `TRUSTED_PROXIES` in [`Config`].
+///
+/// `X-Forwarded-For` lists the hops a request passed through, and the client can prefill it
+/// with anything; each proxy appends the address it received the request from. The client is
+/// therefore the rightmost hop, counting the peer of the connection, that is not a trusted
+/// proxy. With no trusted proxies the header is ignored and the client is the peer.
+///
+/// Apply it outermost with `axum::middleware::from_fn_with_state(TrustedProxies::from_config(
+/// &config), resolve)`, so the IP filter, rate limiters and handlers all see the resolved
+/// address.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    networks: Vec<Cidr>,
+}
+
+impl TrustedProxies {
+    #[must_use]
+    pub fn from_config(config: &Config) -> Arc<Self> {
+        Arc::new(Self {
+            networks: config.trusted_proxies.clone(),
+        })
+    }
+
+    /// The client address of a request from `peer` carrying the `forwarded_for` hops, oldest
+    /// first. `None` without a peer, or if a hop that has to be read is not an address.
+    #[must_use]
+    pub fn client_ip(&self, peer: Option<IpAddr>, forwarded_for: &[&str]) -> Option<IpAddr> {
+        let trusted = |ip: IpAddr| self.networks.iter().any(|c| c.contains(ip));
+        let mut client = peer?;
+        for hop in forwarded_for.iter().rev() {
+            if !trusted(client) {
+                break;
+            }
+            client = parse_hop(hop)?;
+        }
+        Some(client)
+    }
+}
+
+/// An `X-Forwarded-For` entry: an address, optionally with a port.
+fn parse_hop(hop: &str) -> Option<IpAddr> {
+    let hop = hop.trim();
+    hop.parse::<IpAddr>()
+        .ok()
+        .or_else(|| hop.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+}
+
+/// Middleware replacing `X-Forwarded-For` with the client address [`TrustedProxies`] resolves.
+///
+/// The header is removed when there is none, and `X-Real-IP` is always dropped, so everything
+/// after it, such as [`crate::auth::extract_client_ip`], reads a header the client cannot forge.
+pub async fn resolve(
+    State(proxies): State<Arc<TrustedProxies>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let (mut parts, body) = req.into_parts();
+    let peer = ConnectInfo::<SocketAddr>::from_request_parts(&mut parts, &())
+        .await
+        .ok()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let hops: Vec<&str> = parts
+        .headers
+        .get_all(FORWARDED_FOR)
+        .iter()
+        .flat_map(|value| value.to_str().unwrap_or_default().split(','))
+        .collect();
+    let client = proxies
+        .client_ip(peer, &hops)
+        .and_then(|ip| HeaderValue::from_str(&ip.to_string()).ok());
+
+    parts.headers.remove(REAL_IP);
+    match client {
+        Some(client) => {
+            parts.headers.insert(FORWARDED_FOR, client);
+        }
+        None => {
+            parts.headers.remove(FORWARDED_FOR);
+        }
+    }
+    next.run(Request::from_parts(parts, body)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::ip_filter::parse_list;
+
+    fn proxies(list: &str) -> TrustedProxies {
+        TrustedProxies {
+            networks: parse_list(list).unwrap_or_default(),
+        }
+    }
+
+    fn ip(s: &str) -> Option<IpAddr> {
+        s.parse().ok()
+    }
+
+    #[test]
+    fn without_trusted_proxies_the_peer_is_the_client() {
+        let none = TrustedProxies::default();
+        assert_eq!(
+            none.client_ip(ip("203.0.113.5"), &["10.0.0.1"]),
+            ip("203.0.113.5")
+        );
+        assert_eq!(none.client_ip(None, &["10.0.0.1"]), None);
+    }
+
+    #[test]
+    fn the_rightmost_untrusted_hop_is_the_client() {
+        let proxies = proxies("192.0.2.0/24");
+        let proxy = ip("192.0.2.1");
+
+        assert_eq!(
+            proxies.client_ip(proxy, &["203.0.113.5"]),
+            ip("203.0.113.5")
+        );
+        // A prefilled hop is to the left of the one the proxy appended
+        assert_eq!(
+            proxies.client_ip(proxy, &["10.0.0.1", " 203.0.113.5"]),
+            ip("203.0.113.5")
+        );
+        // Chained proxies are skipped
+        assert_eq!(
+            proxies.client_ip(proxy, &["203.0.113.5", "192.0.2.7:443"]),
+            ip("203.0.113.5")
+        );
+        // Untrusted peers cannot forward anything
+        assert_eq!(
+            proxies.client_ip(ip("198.51.100.9"), &["10.0.0.1"]),
+            ip("198.51.100.9")
+        );
+        // Only proxies: the earliest one is the client
+        assert_eq!(proxies.client_ip(proxy, &["192.0.2.7"]), ip("192.0.2.7"));
+        assert_eq!(proxies.client_ip(proxy, &[]), proxy);
+        assert_eq!(proxies.client_ip(proxy, &["10.0.0.1", "unknown"]), None);
+    }
+}
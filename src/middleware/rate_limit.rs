@@ -0,0 +1,264 @@
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::body::{Body, to_bytes};
+use axum::extract::{Request, State};
+use axum::http::{HeaderValue, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use dashmap::DashMap;
+
+use super::client_ip;
+use crate::error::AppError;
+
+/// Largest request body inspected for an account identifier.
+const MAX_INSPECTED_BODY: usize = 64 * 1024;
+
+/// Buckets kept before idle, fully refilled ones are dropped.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// A token bucket: up to `burst` requests at once, refilled by one every `refill`.
+#[derive(Debug, Clone, Copy)]
+pub struct Quota {
+    pub burst: u32,
+    pub refill: Duration,
+}
+
+/// Limits applied to one route group.
+///
+/// The per-account bucket is keyed by the `email` field of the JSON body, so it only applies
+/// to routes that identify an account that way.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitPolicy {
+    pub per_ip: Option<Quota>,
+    pub per_account: Option<Quota>,
+}
+
+/// Quotas of each rate-limited route group, configurable with `RATE_LIMIT_*` (see
+/// [`crate::config::Config::from_env`]).
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimits {
+    /// Signin, signup (including guest accounts), password reset and two-factor and passkey
+    /// sign-in, to blunt credential stuffing and spam.
+    pub credentials: RateLimitPolicy,
+    /// Joining a session, to blunt session code guessing and lobby spam. The per-account
+    /// bucket is keyed on the signed-in user rather than an `email` field.
+    pub join: RateLimitPolicy,
+    /// Analytics event batches. Only the per-account bucket applies, keyed on the reporting
+    /// user, anonymous client or IP address.
+    pub analytics: RateLimitPolicy,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            credentials: RateLimitPolicy {
+                per_ip: Some(Quota {
+                    burst: 20,
+                    refill: Duration::from_secs(6),
+                }),
+                per_account: Some(Quota {
+                    burst: 10,
+                    refill: Duration::from_secs(30),
+                }),
+            },
+            join: RateLimitPolicy {
+                per_ip: Some(Quota {
+                    burst: 30,
+                    refill: Duration::from_secs(2),
+                }),
+                per_account: Some(Quota {
+                    burst: 10,
+                    refill: Duration::from_secs(6),
+                }),
+            },
+            analytics: RateLimitPolicy {
+                per_ip: None,
+                per_account: Some(Quota {
+                    burst: 30,
+                    refill: Duration::from_secs(2),
+                }),
+            },
+        }
+    }
+}
+
+/// Parse a quota written as `burst/refill_secs` (e.g. `20/6`: up to 20 requests at once,
+/// refilled by one every six seconds), or `off` for no limit.
+///
+/// # Errors
+///
+/// Returns an error if the value is neither `off` nor two positive integers.
+pub fn parse_quota(value: &str) -> Result<Option<Quota>, String> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("off") {
+        return Ok(None);
+    }
+    let positive = |part: &str| part.trim().parse::<u32>().ok().filter(|n| *n > 0);
+    value
+        .split_once('/')
+        .and_then(|(burst, refill)| Some((positive(burst)?, positive(refill)?)))
+        .map(|(burst, refill)| {
+            Some(Quota {
+                burst,
+                refill: Duration::from_secs(u64::from(refill)),
+            })
+        })
+        .ok_or_else(|| format!("`{value}` is not `off` or `burst/refill_secs`"))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// In-memory token buckets for one route group, shared by every route the layer wraps.
+///
+/// Apply it with `axum::middleware::from_fn_with_state(RateLimiter::new(policy), enforce)`, or
+/// call [`RateLimiter::try_acquire`] from a handler that identifies the caller itself.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    policy: RateLimitPolicy,
+    buckets: Arc<DashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    #[must_use]
+    pub fn new(policy: RateLimitPolicy) -> Self {
+        Self {
+            policy,
+            buckets: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Take a token from the per-account bucket of `account`, or return how long until one is
+    /// available. A policy without a per-account quota never limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns the wait until a token is available when the bucket is empty.
+    pub fn try_acquire(&self, account: &str) -> Result<(), Duration> {
+        let Some(quota) = self.policy.per_account else {
+            return Ok(());
+        };
+        let now = Instant::now();
+        self.prune(now);
+        self.acquire(format!("account:{account}"), quota, now)
+    }
+
+    /// Take a token from the bucket at `key`, or return how long until one is available.
+    fn acquire(&self, key: String, quota: Quota, now: Instant) -> Result<(), Duration> {
+        let burst = f64::from(quota.burst.max(1));
+        let refill = quota.refill.as_secs_f64().max(f64::EPSILON);
+
+        let mut bucket = self.buckets.entry(key).or_insert(Bucket {
+            tokens: burst,
+            updated_at: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        bucket.tokens = elapsed
+            .as_secs_f64()
+            .mul_add(1.0 / refill, bucket.tokens)
+            .min(burst);
+        bucket.updated_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) * refill))
+        }
+    }
+
+    /// Drop buckets that have refilled completely, once there are many of them.
+    fn prune(&self, now: Instant) {
+        if self.buckets.len() < PRUNE_THRESHOLD {
+            return;
+        }
+        let longest_refill = [self.policy.per_ip, self.policy.per_account]
+            .into_iter()
+            .flatten()
+            .map(|q| q.refill * q.burst)
+            .max()
+            .unwrap_or_default();
+        self.buckets
+            .retain(|_, b| now.saturating_duration_since(b.updated_at) < longest_refill);
+    }
+}
+
+/// Middleware rejecting requests over the limiter's quotas with `429 Too Many Requests` and a
+/// `Retry-After` header.
+///
+/// The per-IP bucket is keyed on the address [`super::proxy::resolve`] settled on, so a client
+/// cannot reset it by changing `X-Forwarded-For`. Requests whose client IP cannot be determined
+/// are not limited per IP.
+pub async fn enforce(State(limiter): State<RateLimiter>, req: Request, next: Next) -> Response {
+    let now = Instant::now();
+    limiter.prune(now);
+
+    if let Some(quota) = limiter.policy.per_ip
+        && let Some(ip) = client_ip(&req)
+            .and_then(|ip| ip.parse::<IpAddr>().ok())
+            .map(|ip| ip.to_canonical())
+        && let Err(wait) = limiter.acquire(format!("ip:{ip}"), quota, now)
+    {
+        return too_many_requests(wait);
+    }
+
+    let Some(quota) = limiter.policy.per_account else {
+        return next.run(req).await;
+    };
+
+    let (parts, body) = req.into_parts();
+    let Ok(bytes) = to_bytes(body, MAX_INSPECTED_BODY).await else {
+        return AppError::PayloadTooLarge("Request body is too large".to_string()).into_response();
+    };
+    if let Some(email) = account_email(&bytes)
+        && let Err(wait) = limiter.acquire(format!("account:{email}"), quota, now)
+    {
+        return too_many_requests(wait);
+    }
+
+    next.run(Request::from_parts(parts, Body::from(bytes)))
+        .await
+}
+
+/// The normalized `email` field of a JSON body, if any.
+fn account_email(body: &[u8]) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_slice(body).ok()?;
+    let email = value.get("email")?.as_str()?.trim().to_lowercase();
+    (!email.is_empty()).then_some(email)
+}
+
+/// `429 Too Many Requests` with a `Retry-After` of `wait`, rounded up to whole seconds.
+#[must_use]
+pub fn too_many_requests(wait: Duration) -> Response {
+    let seconds = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+    let mut response =
+        AppError::TooManyRequests("Too many requests, please try again later".to_string())
+            .into_response();
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(seconds.max(1)));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotas_parse_from_burst_and_refill() {
+        let quota = parse_quota(" 20/6 ").ok().flatten();
+        assert_eq!(quota.map(|q| q.burst), Some(20));
+        assert_eq!(quota.map(|q| q.refill), Some(Duration::from_secs(6)));
+        assert!(parse_quota("OFF").is_ok_and(|q| q.is_none()));
+
+        assert!(parse_quota("20").is_err());
+        assert!(parse_quota("0/6").is_err());
+        assert!(parse_quota("20/-1").is_err());
+        assert!(parse_quota("").is_err());
+    }
+}
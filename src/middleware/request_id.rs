@@ -0,0 +1,32 @@
+use axum::extract::Request;
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::Response;
+use uuid::Uuid;
+
+/// Header carrying the request ID, in requests and responses.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest caller-supplied request ID that is kept.
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+/// Give every request an ID, keeping one a proxy already assigned, and echo it in the
+/// response so clients can quote it in bug reports.
+///
+/// Logs and error reports read the ID from the request's [`REQUEST_ID_HEADER`].
+pub async fn assign(mut req: Request, next: Next) -> Response {
+    let id = req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .filter(|value| !value.is_empty() && value.len() <= MAX_REQUEST_ID_LENGTH)
+        .cloned()
+        .unwrap_or_else(|| {
+            HeaderValue::from_str(&Uuid::new_v4().to_string())
+                .unwrap_or_else(|_| HeaderValue::from_static("unknown"))
+        });
+    req.headers_mut().insert(REQUEST_ID_HEADER, id.clone());
+
+    let mut response = next.run(req).await;
+    response.headers_mut().insert(REQUEST_ID_HEADER, id);
+    response
+}
@@ -0,0 +1,15 @@
+use axum::extract::{MatchedPath, Request};
+use axum::middleware::Next;
+use axum::response::Response;
+use tracing::Span;
+
+/// Record the matched route template (e.g. `/api/v1/games/{id}`) on the request's span.
+///
+/// Events logged while serving the request, such as slow statements, then name the route
+/// rather than only the concrete URI.
+pub async fn record(req: Request, next: Next) -> Response {
+    if let Some(path) = req.extensions().get::<MatchedPath>() {
+        Span::current().record("route", path.as_str());
+    }
+    next.run(req).await
+}
@@ -0,0 +1,524 @@
+//! In-app notifications: storing them and pushing them to signed-in users.
+//!
+//! Flows that notify someone call one of the helpers here (or [`send`] directly). The
+//! notification is stored, then pushed over every `WebSocket` the recipient has open on
+//! `GET /api/v1/users/me/notifications/ws`, so clients can update without polling.
+//!
+//! Helpers for notices caused by another user (reviews, follows, friend requests, invites)
+//! drop them silently when that user is shadow-banned.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::Utc;
+use dashmap::DashMap;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, ConnectionTrait};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::email;
+use crate::entities::{
+    content_report, game, game_takedown, notification, organization, suspension_appeal, user,
+    verification_request,
+};
+use crate::sessions::WsTx;
+use crate::state::AppState;
+
+/// `notification.kind` of the notice sent to a creator when their game is reviewed.
+pub const REVIEW_POSTED: &str = "review_posted";
+
+/// `notification.kind` of the notice sent to a creator when an admin features their game.
+pub const GAME_FEATURED: &str = "game_featured";
+
+/// `notification.kind` of the notice sent to a user when someone follows them.
+pub const FOLLOWER_GAINED: &str = "follower_gained";
+
+/// `notification.kind` of the notice sent to a user when someone sends them a friend request.
+pub const FRIEND_REQUEST_RECEIVED: &str = "friend_request_received";
+
+/// `notification.kind` of the notice sent to a user when their friend request is accepted.
+pub const FRIEND_REQUEST_ACCEPTED: &str = "friend_request_accepted";
+
+/// `notification.kind` of the invite a host sends into one of their sessions.
+pub const SESSION_INVITE: &str = "session_invite";
+
+/// `notification.kind` of the notice sent to a user when they earn an achievement.
+pub const ACHIEVEMENT_UNLOCKED: &str = "achievement_unlocked";
+
+/// `notification.kind` of the notice sent to a user when an admin decides their verification
+/// request.
+pub const VERIFICATION_REVIEWED: &str = "verification_reviewed";
+
+/// `notification.kind` of the invite an organization owner or admin sends to a new member.
+pub const ORGANIZATION_INVITE: &str = "organization_invite";
+
+/// `notification.kind` of the warning sent when a moderator upholds a report against a user's
+/// content without removing it.
+pub const MODERATION_WARNING: &str = "moderation_warning";
+
+/// `notification.kind` of the notice sent when a moderator takes down a user's content.
+pub const CONTENT_REMOVED: &str = "content_removed";
+
+/// `notification.kind` of the notice sent to a creator when an admin decides their appeal of a
+/// game takedown.
+pub const TAKEDOWN_APPEAL_REVIEWED: &str = "takedown_appeal_reviewed";
+
+/// `notification.kind` of the notice sent to a creator when their game is removed for a
+/// copyright notice.
+pub const COPYRIGHT_REMOVAL: &str = "copyright_removal";
+
+/// `notification.kind` of the notice sent to a creator when their game is restored after a
+/// counter-notice.
+pub const COPYRIGHT_RESTORED: &str = "copyright_restored";
+
+/// `notification.kind` of the notice sent to a suspended user when an admin decides their
+/// appeal.
+pub const SUSPENSION_APPEAL_REVIEWED: &str = "suspension_appeal_reviewed";
+
+/// A notification as shown to its recipient, both in listings and in pushed messages.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationView {
+    pub id: Uuid,
+    pub kind: String,
+    pub title: String,
+    pub body: String,
+    pub link: Option<String>,
+    pub read_at: Option<String>,
+    pub created_at: String,
+}
+
+impl From<notification::Model> for NotificationView {
+    fn from(notification: notification::Model) -> Self {
+        Self {
+            id: notification.id,
+            kind: notification.kind,
+            title: notification.title,
+            body: notification.body,
+            link: notification.link,
+            read_at: notification.read_at.map(|t| t.to_rfc3339()),
+            created_at: notification.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// What a new notification says.
+#[derive(Debug, Clone)]
+pub struct NewNotification {
+    pub kind: &'static str,
+    pub title: String,
+    pub body: String,
+    /// Where acting on the notification takes the user, if anywhere.
+    pub link: Option<String>,
+}
+
+/// Open notification sockets, by user. A user may have several (one per tab or device).
+#[derive(Debug, Clone, Default)]
+pub struct NotificationHub {
+    /// `user_id` → connection id → sender channel
+    connections: Arc<DashMap<Uuid, DashMap<u64, WsTx>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl NotificationHub {
+    /// Create a hub with no connections.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a socket for `user_id`, returning the id to unregister it with.
+    #[must_use]
+    pub fn register(&self, user_id: Uuid, tx: WsTx) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.connections.entry(user_id).or_default().insert(id, tx);
+        id
+    }
+
+    /// Unregister a socket registered with [`Self::register`].
+    pub fn unregister(&self, user_id: Uuid, connection_id: u64) {
+        if let Some(sockets) = self.connections.get(&user_id) {
+            sockets.remove(&connection_id);
+            if sockets.is_empty() {
+                drop(sockets);
+                self.connections.remove(&user_id);
+            }
+        }
+    }
+
+    /// Send a message to every socket `user_id` has open.
+    pub fn push(&self, user_id: Uuid, message: &str) {
+        if let Some(sockets) = self.connections.get(&user_id) {
+            for entry in sockets.iter() {
+                // A closed channel is cleaned up when its socket task unregisters it
+                let _ = entry.value().send(message.to_string());
+            }
+        }
+    }
+}
+
+/// Store a notification for `user_id` and push it to their open sockets.
+///
+/// # Errors
+///
+/// Returns an error if the notification cannot be stored.
+pub async fn send<C: ConnectionTrait>(
+    db: &C,
+    hub: &NotificationHub,
+    user_id: Uuid,
+    new: NewNotification,
+) -> anyhow::Result<notification::Model> {
+    let stored = notification::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id),
+        kind: Set(new.kind.to_string()),
+        title: Set(new.title),
+        body: Set(new.body),
+        link: Set(new.link),
+        read_at: Set(None),
+        created_at: Set(Utc::now().fixed_offset()),
+    }
+    .insert(db)
+    .await?;
+
+    let message = serde_json::json!({
+        "type": "notification",
+        "payload": NotificationView::from(stored.clone()),
+    });
+    hub.push(user_id, &message.to_string());
+
+    Ok(stored)
+}
+
+/// Tell a creator their game was reviewed, in the app and, if they want review alerts, by
+/// email.
+pub async fn review_posted(
+    state: &AppState,
+    game: &game::Model,
+    reviewer: &user::Model,
+    rating: i32,
+) {
+    if reviewer.shadow_banned {
+        return;
+    }
+    let link = game_link(state, game);
+    let new = NewNotification {
+        kind: REVIEW_POSTED,
+        title: format!("New review on {}", game.title),
+        body: format!(
+            "{} rated {} {rating}/5.",
+            display_name(reviewer),
+            game.title
+        ),
+        link: Some(link.clone()),
+    };
+    let subject = new.title.clone();
+    let body = format!("{} {link}", new.body);
+    send_best_effort(state, game.owner_id, new).await;
+
+    if let Err(e) = email::send_to_user(
+        &state.db,
+        game.owner_id,
+        email::Category::ReviewAlerts,
+        &subject,
+        &body,
+    )
+    .await
+    {
+        tracing::warn!(error = %e, user_id = %game.owner_id, "Failed to send review alert email");
+    }
+}
+
+/// Tell a creator an admin featured their game.
+pub async fn game_featured(state: &AppState, game: &game::Model) {
+    let new = NewNotification {
+        kind: GAME_FEATURED,
+        title: format!("{} was featured", game.title),
+        body: format!("{} is now featured on AirCade.", game.title),
+        link: Some(game_link(state, game)),
+    };
+    send_best_effort(state, game.owner_id, new).await;
+}
+
+/// Tell a user someone started following them.
+pub async fn follower_gained(state: &AppState, followee_id: Uuid, follower: &user::Model) {
+    if follower.shadow_banned {
+        return;
+    }
+    let new = NewNotification {
+        kind: FOLLOWER_GAINED,
+        title: "You have a new follower".to_string(),
+        body: format!("{} started following you.", display_name(follower)),
+        link: Some(format!(
+            "{}/users/{}",
+            state.config.frontend_url.trim_end_matches('/'),
+            follower.username
+        )),
+    };
+    send_best_effort(state, followee_id, new).await;
+}
+
+/// Tell a user someone sent them a friend request.
+pub async fn friend_request_received(
+    state: &AppState,
+    addressee_id: Uuid,
+    requester: &user::Model,
+) {
+    if requester.shadow_banned {
+        return;
+    }
+    let new = NewNotification {
+        kind: FRIEND_REQUEST_RECEIVED,
+        title: "New friend request".to_string(),
+        body: format!("{} sent you a friend request.", display_name(requester)),
+        link: Some(friends_link(state)),
+    };
+    send_best_effort(state, addressee_id, new).await;
+}
+
+/// Tell a user their friend request was accepted.
+pub async fn friend_request_accepted(
+    state: &AppState,
+    requester_id: Uuid,
+    addressee: &user::Model,
+) {
+    if addressee.shadow_banned {
+        return;
+    }
+    let new = NewNotification {
+        kind: FRIEND_REQUEST_ACCEPTED,
+        title: "Friend request accepted".to_string(),
+        body: format!("{} accepted your friend request.", display_name(addressee)),
+        link: Some(friends_link(state)),
+    };
+    send_best_effort(state, requester_id, new).await;
+}
+
+/// Invite a user into `host`'s session, returning the notification's title, body and link so
+/// the invite can be emailed too.
+pub async fn session_invite(
+    state: &AppState,
+    invitee_id: Uuid,
+    host: &user::Model,
+    session_code: &str,
+) -> NewNotification {
+    let new = NewNotification {
+        kind: SESSION_INVITE,
+        title: format!("{} invited you to play", display_name(host)),
+        body: format!("Join their session with the code {session_code}."),
+        link: Some(session_link(state, session_code)),
+    };
+    if !host.shadow_banned {
+        send_best_effort(state, invitee_id, new.clone()).await;
+    }
+    new
+}
+
+/// Tell a user an admin approved or denied their verification request.
+pub async fn verification_reviewed(state: &AppState, request: &verification_request::Model) {
+    let approved = request.status == "approved";
+    let mut body = if approved {
+        "Your profile now shows the verified creator badge.".to_string()
+    } else {
+        "Your request for the verified creator badge was not approved.".to_string()
+    };
+    if let Some(note) = &request.review_note {
+        body = format!("{body} {note}");
+    }
+    let new = NewNotification {
+        kind: VERIFICATION_REVIEWED,
+        title: if approved {
+            "You're verified".to_string()
+        } else {
+            "Verification request denied".to_string()
+        },
+        body,
+        link: None,
+    };
+    send_best_effort(state, request.user_id, new).await;
+}
+
+/// Invite a user to join an organization.
+pub async fn organization_invite(
+    state: &AppState,
+    invitee_id: Uuid,
+    inviter: &user::Model,
+    organization: &organization::Model,
+) {
+    let new = NewNotification {
+        kind: ORGANIZATION_INVITE,
+        title: format!(
+            "{} invited you to {}",
+            display_name(inviter),
+            organization.name
+        ),
+        body: "Accept the invite to work on the organization's games.".to_string(),
+        link: Some(format!(
+            "{}/organizations/{}",
+            state.config.frontend_url.trim_end_matches('/'),
+            organization.slug
+        )),
+    };
+    send_best_effort(state, invitee_id, new).await;
+}
+
+/// Tell a user a moderator warned them about, or took down, `content` (e.g. "your game
+/// Space Race") after `report`.
+pub async fn moderation_outcome(
+    state: &AppState,
+    user_id: Uuid,
+    report: &content_report::Model,
+    content: &str,
+) {
+    let removed = report.resolution.as_deref() == Some("taken_down");
+    let mut body = if removed {
+        format!(
+            "A moderator removed {content} after a report for {}.",
+            report.reason
+        )
+    } else {
+        format!(
+            "A moderator reviewed a report for {} against {content} and issued a warning.",
+            report.reason
+        )
+    };
+    if let Some(note) = &report.resolution_note {
+        body = format!("{body} {note}");
+    }
+    let new = NewNotification {
+        kind: if removed {
+            CONTENT_REMOVED
+        } else {
+            MODERATION_WARNING
+        },
+        title: if removed {
+            "Content removed".to_string()
+        } else {
+            "Moderation warning".to_string()
+        },
+        body,
+        link: None,
+    };
+    send_best_effort(state, user_id, new).await;
+}
+
+/// Tell a creator a moderator removed their game, and why.
+pub async fn game_removed(state: &AppState, game: &game::Model, takedown: &game_takedown::Model) {
+    let new = NewNotification {
+        kind: CONTENT_REMOVED,
+        title: format!("{} was removed", game.title),
+        body: format!(
+            "A moderator removed {}: {} You can appeal the decision from the game's page.",
+            game.title, takedown.reason
+        ),
+        link: Some(game_link(state, game)),
+    };
+    send_best_effort(state, game.owner_id, new).await;
+}
+
+/// Tell a creator their game was removed for a copyright notice, and how to answer it.
+pub async fn copyright_removal(state: &AppState, game: &game::Model) {
+    let new = NewNotification {
+        kind: COPYRIGHT_REMOVAL,
+        title: format!("{} was removed", game.title),
+        body: format!(
+            "{} was removed in response to a copyright notice. If you believe this is a \
+             mistake, you can file a counter-notice from the game's page.",
+            game.title
+        ),
+        link: Some(game_link(state, game)),
+    };
+    send_best_effort(state, game.owner_id, new).await;
+}
+
+/// Tell a creator an admin approved or denied their appeal of a game takedown.
+pub async fn appeal_reviewed(
+    state: &AppState,
+    game: &game::Model,
+    takedown: &game_takedown::Model,
+) {
+    let approved = takedown.appeal_status.as_deref() == Some("approved");
+    let mut body = if approved {
+        format!("{} has been restored.", game.title)
+    } else {
+        format!("{} stays removed.", game.title)
+    };
+    if let Some(note) = &takedown.appeal_review_note {
+        body = format!("{body} {note}");
+    }
+    let new = NewNotification {
+        kind: TAKEDOWN_APPEAL_REVIEWED,
+        title: if approved {
+            "Appeal approved".to_string()
+        } else {
+            "Appeal denied".to_string()
+        },
+        body,
+        link: Some(game_link(state, game)),
+    };
+    send_best_effort(state, game.owner_id, new).await;
+}
+
+/// Tell a suspended user an admin approved or denied their appeal.
+pub async fn suspension_appeal_reviewed(
+    state: &AppState,
+    user: &user::Model,
+    appeal: &suspension_appeal::Model,
+) {
+    let approved = appeal.status == "approved";
+    let mut body = if approved {
+        "Your account has been reinstated.".to_string()
+    } else {
+        "Your account stays suspended.".to_string()
+    };
+    if let Some(note) = &appeal.review_note {
+        body = format!("{body} {note}");
+    }
+    let new = NewNotification {
+        kind: SUSPENSION_APPEAL_REVIEWED,
+        title: if approved {
+            "Appeal approved".to_string()
+        } else {
+            "Appeal denied".to_string()
+        },
+        body,
+        link: None,
+    };
+    send_best_effort(state, user.id, new).await;
+}
+
+/// Notifications about other people's activity are a side effect of that activity: failing
+/// to store one is logged rather than failing the request.
+async fn send_best_effort(state: &AppState, user_id: Uuid, new: NewNotification) {
+    let kind = new.kind;
+    if let Err(e) = send(&state.db, &state.notification_hub, user_id, new).await {
+        tracing::warn!(error = %e, %user_id, kind, "Failed to send notification");
+    }
+}
+
+fn display_name(user: &user::Model) -> &str {
+    user.display_name.as_deref().unwrap_or(&user.username)
+}
+
+fn game_link(state: &AppState, game: &game::Model) -> String {
+    format!(
+        "{}/games/{}",
+        state.config.frontend_url.trim_end_matches('/'),
+        game.slug
+    )
+}
+
+fn friends_link(state: &AppState) -> String {
+    format!(
+        "{}/friends",
+        state.config.frontend_url.trim_end_matches('/')
+    )
+}
+
+fn session_link(state: &AppState, session_code: &str) -> String {
+    format!(
+        "{}/join/{session_code}",
+        state.config.frontend_url.trim_end_matches('/')
+    )
+}
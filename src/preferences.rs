@@ -0,0 +1,96 @@
+//! Synced client preferences.
+//!
+//! Frontends keep lightweight settings (theme, editor settings, controller haptics) in one JSON
+//! document per user so they follow the user across devices. The server does not interpret
+//! them; it only checks the document against an allowlist of keys and a size limit.
+
+use serde_json::Value;
+
+/// Top-level keys a preferences document may have, with the JSON type each must be.
+pub const KEYS: &[(&str, Kind)] = &[
+    ("theme", Kind::String),
+    ("editor", Kind::Object),
+    ("controllerHaptics", Kind::Bool),
+];
+
+/// Largest preferences document accepted, in bytes of serialized JSON.
+pub const MAX_BYTES: usize = 8 * 1024;
+
+/// The JSON type a preference holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    String,
+    Bool,
+    Object,
+}
+
+impl Kind {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::String => "a string",
+            Self::Bool => "a boolean",
+            Self::Object => "an object",
+        }
+    }
+
+    const fn matches(self, value: &Value) -> bool {
+        matches!(
+            (self, value),
+            (Self::String, Value::String(_))
+                | (Self::Bool, Value::Bool(_))
+                | (Self::Object, Value::Object(_))
+        )
+    }
+}
+
+/// Check `document` is an object with only allowlisted keys of the right types, and small
+/// enough to store.
+///
+/// # Errors
+///
+/// Returns a message describing the first problem found.
+pub fn validate(document: &Value) -> Result<(), String> {
+    let Value::Object(entries) = document else {
+        return Err("Preferences must be a JSON object.".to_string());
+    };
+    for (key, value) in entries {
+        let Some((_, kind)) = KEYS.iter().find(|(k, _)| k == key) else {
+            return Err(format!("Unknown preference \"{key}\"."));
+        };
+        if !kind.matches(value) {
+            return Err(format!("Preference \"{key}\" must be {}.", kind.name()));
+        }
+    }
+    if document.to_string().len() > MAX_BYTES {
+        return Err(format!("Preferences must be at most {MAX_BYTES} bytes."));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn allowlisted_keys_of_the_right_type_are_accepted() {
+        assert!(validate(&json!({})).is_ok());
+        assert!(
+            validate(&json!({
+                "theme": "dark",
+                "editor": { "fontSize": 14, "vim": true },
+                "controllerHaptics": false,
+            }))
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn unknown_keys_wrong_types_and_large_documents_are_rejected() {
+        assert!(validate(&json!([])).is_err());
+        assert!(validate(&json!({ "sidebar": "left" })).is_err());
+        assert!(validate(&json!({ "theme": 1 })).is_err());
+        assert!(validate(&json!({ "editor": { "notes": "x".repeat(MAX_BYTES) } })).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::HashSet;
+
+use axum::{Json, extract::State, response::IntoResponse};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::Set,
+    ColumnTrait, Condition, DatabaseTransaction, EntityTrait, QueryFilter, TransactionTrait,
+    sea_query::{Expr, OnConflict},
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::{jwt, middleware::AuthUser},
+    entities::{auth_provider, follow, game, session, user},
+    error::AppError,
+    jobs::account_deletion,
+    state::AppState,
+};
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeAccountRequest {
+    /// An access token of the account to merge in, proving the caller controls it too.
+    token: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MergeAccountResponse {
+    merged_user_id: Uuid,
+    auth_providers: u64,
+    games: u64,
+    sessions: u64,
+    follows: u64,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `POST /users/me/merge` — Merge another account the caller controls into the authenticated
+/// one.
+///
+/// Meant for users who signed up twice, e.g. once with Google and once with GitHub. The other
+/// account's sign-in methods, games, sessions and follows move to the caller's account, and the
+/// other account is soft-deleted with every remaining credential revoked.
+///
+/// # Errors
+///
+/// Returns [`AppError::Unauthorized`] if `token` is not a valid access token of an active
+/// account, [`AppError::BadRequest`] if it belongs to the caller, [`AppError::Conflict`] if
+/// both accounts use the same sign-in provider, or [`AppError`] if a database operation fails.
+pub async fn merge_account(
+    State(state): State<AppState>,
+    AuthUser(survivor): AuthUser,
+    Json(body): Json<MergeAccountRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let invalid = || AppError::Unauthorized("Invalid or expired token.".to_string());
+    let claims = jwt::validate_access_token(&body.token, &state.jwt_keys).map_err(|_| invalid())?;
+    // Impersonation tokens do not prove control of an account
+    if claims.act.is_some() {
+        return Err(invalid());
+    }
+    let merged_id: Uuid = claims.sub.parse().map_err(|_| invalid())?;
+    if merged_id == survivor.id {
+        return Err(AppError::BadRequest(
+            "You cannot merge an account into itself.".to_string(),
+        ));
+    }
+
+    let txn = state.db.begin().await?;
+    let merged = user::Entity::find_by_id(merged_id)
+        .filter(user::Column::DeletedAt.is_null())
+        .filter(user::Column::AccountStatus.eq("active"))
+        .one(&txn)
+        .await?
+        .ok_or_else(invalid)?;
+
+    let auth_providers = move_auth_providers(&txn, merged.id, survivor.id).await?;
+    let games = game::Entity::update_many()
+        .col_expr(game::Column::OwnerId, Expr::value(survivor.id))
+        .filter(game::Column::OwnerId.eq(merged.id))
+        .exec(&txn)
+        .await?
+        .rows_affected;
+    let sessions = session::Entity::update_many()
+        .col_expr(session::Column::HostId, Expr::value(survivor.id))
+        .filter(session::Column::HostId.eq(merged.id))
+        .exec(&txn)
+        .await?
+        .rows_affected;
+    let follows = move_follows(&txn, merged.id, survivor.id).await?;
+
+    account_deletion::delete_credentials(&txn, merged.id).await?;
+    let now = Utc::now().fixed_offset();
+    let mut active: user::ActiveModel = merged.into();
+    active.deleted_at = Set(Some(now));
+    active.updated_at = Set(now);
+    active.update(&txn).await?;
+
+    txn.commit().await?;
+
+    Ok(Json(MergeAccountResponse {
+        merged_user_id: merged_id,
+        auth_providers,
+        games,
+        sessions,
+        follows,
+    }))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Move `from`'s sign-in methods to `to`. A user has at most one provider of each type, so a
+/// provider both accounts use is a conflict.
+async fn move_auth_providers(
+    txn: &DatabaseTransaction,
+    from: Uuid,
+    to: Uuid,
+) -> Result<u64, AppError> {
+    let existing: HashSet<String> = auth_provider::Entity::find()
+        .filter(auth_provider::Column::UserId.eq(to))
+        .all(txn)
+        .await?
+        .into_iter()
+        .map(|p| p.provider)
+        .collect();
+    let moving = auth_provider::Entity::find()
+        .filter(auth_provider::Column::UserId.eq(from))
+        .all(txn)
+        .await?;
+    if let Some(shared) = moving.iter().find(|p| existing.contains(&p.provider)) {
+        return Err(AppError::Conflict(format!(
+            "Both accounts sign in with {}; remove it from one of them first.",
+            shared.provider
+        )));
+    }
+
+    Ok(auth_provider::Entity::update_many()
+        .col_expr(auth_provider::Column::UserId, Expr::value(to))
+        .filter(auth_provider::Column::UserId.eq(from))
+        .exec(txn)
+        .await?
+        .rows_affected)
+}
+
+/// Re-point `from`'s follows, in both directions, at `to`. Follows `to` already has and
+/// follows between the two accounts are dropped.
+async fn move_follows(txn: &DatabaseTransaction, from: Uuid, to: Uuid) -> Result<u64, AppError> {
+    let involving_from = Condition::any()
+        .add(follow::Column::FollowerId.eq(from))
+        .add(follow::Column::FolloweeId.eq(from));
+    let follows = follow::Entity::find()
+        .filter(involving_from.clone())
+        .all(txn)
+        .await?;
+
+    let swap = |id: Uuid| if id == from { to } else { id };
+    let mut moved = 0;
+    for f in follows {
+        if swap(f.follower_id) == swap(f.followee_id) {
+            continue;
+        }
+        moved += follow::Entity::insert(follow::ActiveModel {
+            follower_id: Set(swap(f.follower_id)),
+            followee_id: Set(swap(f.followee_id)),
+            created_at: Set(f.created_at),
+        })
+        .on_conflict(
+            OnConflict::columns([follow::Column::FollowerId, follow::Column::FolloweeId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(txn)
+        .await?;
+    }
+
+    follow::Entity::delete_many()
+        .filter(involving_from)
+        .exec(txn)
+        .await?;
+    Ok(moved)
+}
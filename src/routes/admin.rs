@@ -0,0 +1,814 @@
+use std::collections::HashMap;
+
+use axum::{
+    Json, Router,
+    extract::{OriginalUri, Path, Query, State},
+    http::{HeaderMap, Method, StatusCode},
+    response::IntoResponse,
+    routing::{get, patch, post, put},
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, Condition, ConnectionTrait, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait, sea_query::OnConflict,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    audit,
+    auth::{impersonation, jwt, middleware::AdminUser},
+    entities::{audit_log, game, game_tag, login_event, tag, user},
+    error::AppError,
+    notifications,
+    routes::games::{PaginatedResponse, find_active_game},
+    routes::{
+        announcements, experiments, legal, log_level, metrics, moderation, seed, sessions, stats,
+        status, suspension_appeals, takedowns, verification,
+    },
+    state::AppState,
+};
+
+/// Allowed values for `tag.category`.
+const TAG_CATEGORIES: [&str; 3] = ["genre", "mood", "playerStyle"];
+
+/// Maximum length (in characters) of a tag name.
+const MAX_TAG_NAME_LENGTH: usize = 50;
+
+/// Admin router: `/admin/...`
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/tags", get(list_tags).post(create_tag))
+        .route("/tags/{id}", patch(update_tag).delete(retire_tag))
+        .route("/tags/{id}/merge", post(merge_tag))
+        .route("/login-events", get(list_login_events))
+        .route("/audit-log", get(list_audit_log))
+        .route(
+            "/announcements",
+            get(announcements::list_announcements).post(announcements::create_announcement),
+        )
+        .route(
+            "/announcements/{id}",
+            put(announcements::update_announcement).delete(announcements::delete_announcement),
+        )
+        .route(
+            "/experiments",
+            get(experiments::list_experiments).post(experiments::create_experiment),
+        )
+        .route("/experiments/{id}", put(experiments::update_experiment))
+        .route(
+            "/status/incident",
+            put(status::set_incident).delete(status::resolve_incident),
+        )
+        .route(
+            "/log-level",
+            get(log_level::get_log_level)
+                .put(log_level::set_log_level)
+                .delete(log_level::reset_log_level),
+        )
+        .route("/users/{id}", get(moderation::get_user))
+        .route("/users/{id}/notes", post(moderation::create_user_note))
+        .route("/users/{id}/impersonate", post(impersonate_user))
+        .route(
+            "/users/{id}/shadow-ban",
+            post(moderation::shadow_ban_user).delete(moderation::shadow_unban_user),
+        )
+        .route(
+            "/games/{id}/feature",
+            post(feature_game).delete(unfeature_game),
+        )
+        .route("/games/{id}/takedown", post(takedowns::take_down_game))
+        .route("/seed", post(seed::seed))
+        .route("/stats", get(stats::get_platform_stats))
+        .route("/metrics", get(metrics::get_metrics))
+        .route("/sessions", get(sessions::list_sessions))
+        .route("/sessions/{id}/end", post(sessions::force_end_session))
+        .route("/appeals", get(takedowns::list_appeals))
+        .route("/copyright-notices", get(legal::list_notices))
+        .route("/copyright-notices/{id}/review", post(legal::review_notice))
+        .route("/copyright-notices/{id}/action", post(legal::action_notice))
+        .route("/copyright-notices/{id}/reject", post(legal::reject_notice))
+        .route(
+            "/copyright-notices/{id}/court-action",
+            post(legal::record_court_action),
+        )
+        .route("/appeals/{id}/approve", post(takedowns::approve_appeal))
+        .route("/appeals/{id}/deny", post(takedowns::deny_appeal))
+        .route(
+            "/suspension-appeals",
+            get(suspension_appeals::list_suspension_appeals),
+        )
+        .route(
+            "/suspension-appeals/{id}/approve",
+            post(suspension_appeals::approve_suspension_appeal),
+        )
+        .route(
+            "/suspension-appeals/{id}/deny",
+            post(suspension_appeals::deny_suspension_appeal),
+        )
+        .route("/reports", get(moderation::list_reports))
+        .route("/reports/{id}/claim", post(moderation::claim_report))
+        .route("/reports/{id}/assign", post(moderation::assign_report))
+        .route("/reports/{id}/dismiss", post(moderation::dismiss_report))
+        .route("/reports/{id}/warn", post(moderation::warn_report))
+        .route("/reports/{id}/takedown", post(moderation::takedown_report))
+        .route(
+            "/verification-requests",
+            get(verification::list_verification_requests),
+        )
+        .route(
+            "/verification-requests/{id}/approve",
+            post(verification::approve_verification_request),
+        )
+        .route(
+            "/verification-requests/{id}/deny",
+            post(verification::deny_verification_request),
+        )
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateTagRequest {
+    name: String,
+    slug: Option<String>,
+    category: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateTagRequest {
+    name: Option<String>,
+    slug: Option<String>,
+    category: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MergeTagRequest {
+    /// The tag that absorbs the merged tag's games.
+    into_tag_id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AdminTagResponse {
+    id: Uuid,
+    name: String,
+    slug: String,
+    category: String,
+    retired_at: Option<String>,
+    game_count: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct AdminTagsResponse {
+    data: Vec<AdminTagResponse>,
+}
+
+/// Filters for `GET /admin/login-events`; all optional and combined with AND.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LoginEventsQuery {
+    user_id: Option<Uuid>,
+    /// Exact match on what the attempt signed in with, e.g. an email address.
+    identifier: Option<String>,
+    ip_address: Option<String>,
+    outcome: Option<String>,
+    #[serde(default)]
+    offset: u64,
+    #[serde(default = "default_login_events_limit")]
+    limit: u64,
+}
+
+const fn default_login_events_limit() -> u64 {
+    50
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AdminLoginEventResponse {
+    id: Uuid,
+    user_id: Option<Uuid>,
+    identifier: Option<String>,
+    provider: String,
+    outcome: String,
+    ip_address: Option<String>,
+    user_agent: Option<String>,
+    created_at: String,
+}
+
+/// Filters for `GET /admin/audit-log`; all optional and combined with AND.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AuditLogQuery {
+    actor_id: Option<Uuid>,
+    action: Option<String>,
+    target_type: Option<String>,
+    target_id: Option<Uuid>,
+    #[serde(default)]
+    offset: u64,
+    #[serde(default = "default_audit_log_limit")]
+    limit: u64,
+}
+
+const fn default_audit_log_limit() -> u64 {
+    50
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AdminAuditLogEntryResponse {
+    id: Uuid,
+    actor_id: Uuid,
+    actor_role: String,
+    action: String,
+    target_type: String,
+    target_id: Uuid,
+    before: Option<serde_json::Value>,
+    after: Option<serde_json::Value>,
+    ip_address: Option<String>,
+    created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImpersonateRequest {
+    /// Why the admin needs to act as the user, e.g. a support ticket reference.
+    reason: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ImpersonationResponse {
+    token: String,
+    expires_at: String,
+    user_id: Uuid,
+    username: String,
+}
+
+// ============================================================================
+// Tag Catalog
+// ============================================================================
+
+/// `GET /admin/tags` — List every tag, including retired ones, with usage counts.
+async fn list_tags(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+) -> Result<impl IntoResponse, AppError> {
+    let tags = tag::Entity::find()
+        .order_by_asc(tag::Column::Category)
+        .order_by_asc(tag::Column::Name)
+        .all(&state.db)
+        .await?;
+    let counts = count_games(&state.db, None).await?;
+
+    Ok(Json(AdminTagsResponse {
+        data: tags
+            .into_iter()
+            .map(|t| {
+                let count = counts.get(&t.id).copied().unwrap_or_default();
+                to_admin_tag_response(t, count)
+            })
+            .collect(),
+    }))
+}
+
+/// `POST /admin/tags` — Add a tag to the catalog.
+async fn create_tag(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    headers: HeaderMap,
+    Json(req): Json<CreateTagRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let name = validate_name(&req.name)?;
+    let slug = validate_slug(req.slug.as_deref().unwrap_or(&name))?;
+    let category = validate_category(&req.category)?;
+
+    ensure_unique(&state.db, None, &name, &slug).await?;
+
+    let created = tag::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        name: ActiveValue::Set(name),
+        slug: ActiveValue::Set(slug),
+        category: ActiveValue::Set(category),
+        retired_at: ActiveValue::Set(None),
+    }
+    .insert(&state.db)
+    .await?;
+
+    audit::Event::new("tag.create", "tag", created.id)
+        .after(tag_snapshot(&created))
+        .record(&state.db, &admin, &headers)
+        .await;
+
+    Ok((StatusCode::CREATED, Json(to_admin_tag_response(created, 0))))
+}
+
+/// `PATCH /admin/tags/:id` — Rename or recategorize a tag.
+async fn update_tag(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<UpdateTagRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let existing = find_tag(&state.db, id).await?;
+
+    let name = req
+        .name
+        .as_deref()
+        .map(validate_name)
+        .transpose()?
+        .unwrap_or_else(|| existing.name.clone());
+    let slug = req
+        .slug
+        .as_deref()
+        .map(validate_slug)
+        .transpose()?
+        .unwrap_or_else(|| existing.slug.clone());
+
+    ensure_unique(&state.db, Some(id), &name, &slug).await?;
+
+    let before = tag_snapshot(&existing);
+    let mut active: tag::ActiveModel = existing.into();
+    active.name = ActiveValue::Set(name);
+    active.slug = ActiveValue::Set(slug);
+    if let Some(category) = req.category {
+        active.category = ActiveValue::Set(validate_category(&category)?);
+    }
+    let updated = active.update(&state.db).await?;
+
+    audit::Event::new("tag.update", "tag", id)
+        .before(before)
+        .after(tag_snapshot(&updated))
+        .record(&state.db, &admin, &headers)
+        .await;
+
+    let count = count_games(&state.db, Some(id))
+        .await?
+        .get(&id)
+        .copied()
+        .unwrap_or_default();
+
+    Ok(Json(to_admin_tag_response(updated, count)))
+}
+
+/// `POST /admin/tags/:id/merge` — Fold a tag into another: its games are re-tagged with the
+/// target and the merged tag is deleted.
+async fn merge_tag(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<MergeTagRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if req.into_tag_id == id {
+        return Err(AppError::BadRequest(
+            "A tag cannot be merged into itself".to_string(),
+        ));
+    }
+
+    let source = find_tag(&state.db, id).await?;
+    let target = find_tag(&state.db, req.into_tag_id).await?;
+    if target.retired_at.is_some() {
+        return Err(AppError::BadRequest(
+            "Cannot merge into a retired tag".to_string(),
+        ));
+    }
+
+    let txn = state.db.begin().await?;
+
+    let game_ids: Vec<Uuid> = game_tag::Entity::find()
+        .select_only()
+        .column(game_tag::Column::GameId)
+        .filter(game_tag::Column::TagId.eq(source.id))
+        .into_tuple()
+        .all(&txn)
+        .await?;
+
+    // Games already carrying the target keep a single row
+    for game_id in game_ids {
+        game_tag::Entity::insert(game_tag::ActiveModel {
+            game_id: ActiveValue::Set(game_id),
+            tag_id: ActiveValue::Set(target.id),
+        })
+        .on_conflict(
+            OnConflict::columns([game_tag::Column::GameId, game_tag::Column::TagId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(&txn)
+        .await?;
+    }
+
+    game_tag::Entity::delete_many()
+        .filter(game_tag::Column::TagId.eq(source.id))
+        .exec(&txn)
+        .await?;
+    tag::Entity::delete_by_id(source.id).exec(&txn).await?;
+
+    txn.commit().await?;
+
+    audit::Event::new("tag.merge", "tag", source.id)
+        .before(tag_snapshot(&source))
+        .after(serde_json::json!({ "mergedInto": target.id }))
+        .record(&state.db, &admin, &headers)
+        .await;
+
+    let count = count_games(&state.db, Some(target.id))
+        .await?
+        .get(&target.id)
+        .copied()
+        .unwrap_or_default();
+
+    Ok(Json(to_admin_tag_response(target, count)))
+}
+
+/// `DELETE /admin/tags/:id` — Retire a tag. Games keep it, but it can no longer be assigned.
+async fn retire_tag(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let existing = find_tag(&state.db, id).await?;
+
+    if existing.retired_at.is_none() {
+        let before = tag_snapshot(&existing);
+        let mut active: tag::ActiveModel = existing.into();
+        active.retired_at = ActiveValue::Set(Some(chrono::Utc::now().into()));
+        let retired = active.update(&state.db).await?;
+
+        audit::Event::new("tag.retire", "tag", id)
+            .before(before)
+            .after(tag_snapshot(&retired))
+            .record(&state.db, &admin, &headers)
+            .await;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Featured Games
+// ============================================================================
+
+/// `POST /admin/games/{id}/feature` — Feature a published public game and let its creator
+/// know. Featuring an already featured game is a no-op.
+async fn feature_game(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let existing = find_active_game(&state.db, id).await?;
+    if existing.status != "published" || existing.visibility != "public" {
+        return Err(AppError::Conflict(
+            "Only published public games can be featured".to_string(),
+        ));
+    }
+
+    if existing.featured_at.is_none() {
+        let mut active: game::ActiveModel = existing.into();
+        active.featured_at = ActiveValue::Set(Some(chrono::Utc::now().into()));
+        let featured = active.update(&state.db).await?;
+
+        audit::Event::new("game.feature", "game", id)
+            .before(serde_json::json!({ "featuredAt": null }))
+            .after(
+                serde_json::json!({ "featuredAt": featured.featured_at.map(|t| t.to_rfc3339()) }),
+            )
+            .record(&state.db, &admin, &headers)
+            .await;
+        notifications::game_featured(&state, &featured).await;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `DELETE /admin/games/{id}/feature` — Stop featuring a game.
+async fn unfeature_game(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let existing = find_active_game(&state.db, id).await?;
+
+    if let Some(featured_at) = existing.featured_at {
+        let mut active: game::ActiveModel = existing.into();
+        active.featured_at = ActiveValue::Set(None);
+        active.update(&state.db).await?;
+
+        audit::Event::new("game.unfeature", "game", id)
+            .before(serde_json::json!({ "featuredAt": featured_at.to_rfc3339() }))
+            .after(serde_json::json!({ "featuredAt": null }))
+            .record(&state.db, &admin, &headers)
+            .await;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Sign-in Events
+// ============================================================================
+
+/// `GET /admin/login-events` — Search the sign-in audit log, newest first, e.g. for every
+/// attempt from one IP address when investigating credential stuffing.
+async fn list_login_events(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    Query(query): Query<LoginEventsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let mut find = login_event::Entity::find();
+    if let Some(user_id) = query.user_id {
+        find = find.filter(login_event::Column::UserId.eq(user_id));
+    }
+    if let Some(identifier) = query.identifier {
+        find = find.filter(login_event::Column::Identifier.eq(identifier.trim().to_lowercase()));
+    }
+    if let Some(ip_address) = query.ip_address {
+        find = find.filter(login_event::Column::IpAddress.eq(ip_address));
+    }
+    if let Some(outcome) = query.outcome {
+        find = find.filter(login_event::Column::Outcome.eq(outcome));
+    }
+
+    let total = find.clone().count(&state.db).await?;
+    let events = find
+        .order_by_desc(login_event::Column::CreatedAt)
+        .offset(query.offset)
+        .limit(query.limit)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(PaginatedResponse {
+        data: events
+            .into_iter()
+            .map(|e| AdminLoginEventResponse {
+                id: e.id,
+                user_id: e.user_id,
+                identifier: e.identifier,
+                provider: e.provider,
+                outcome: e.outcome,
+                ip_address: e.ip_address,
+                user_agent: e.user_agent,
+                created_at: e.created_at.to_rfc3339(),
+            })
+            .collect(),
+        total,
+        offset: query.offset,
+        limit: query.limit,
+    }))
+}
+
+// ============================================================================
+// Audit Log
+// ============================================================================
+
+/// `GET /admin/audit-log` — Search the log of admin and moderator actions, newest first, e.g.
+/// for everything done to one game.
+async fn list_audit_log(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let mut find = audit_log::Entity::find();
+    if let Some(actor_id) = query.actor_id {
+        find = find.filter(audit_log::Column::ActorId.eq(actor_id));
+    }
+    if let Some(action) = query.action {
+        find = find.filter(audit_log::Column::Action.eq(action));
+    }
+    if let Some(target_type) = query.target_type {
+        find = find.filter(audit_log::Column::TargetType.eq(target_type));
+    }
+    if let Some(target_id) = query.target_id {
+        find = find.filter(audit_log::Column::TargetId.eq(target_id));
+    }
+
+    let total = find.clone().count(&state.db).await?;
+    let entries = find
+        .order_by_desc(audit_log::Column::CreatedAt)
+        .offset(query.offset)
+        .limit(query.limit)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(PaginatedResponse {
+        data: entries
+            .into_iter()
+            .map(|e| AdminAuditLogEntryResponse {
+                id: e.id,
+                actor_id: e.actor_id,
+                actor_role: e.actor_role,
+                action: e.action,
+                target_type: e.target_type,
+                target_id: e.target_id,
+                before: e.before,
+                after: e.after,
+                ip_address: e.ip_address,
+                created_at: e.created_at.to_rfc3339(),
+            })
+            .collect(),
+        total,
+        offset: query.offset,
+        limit: query.limit,
+    }))
+}
+
+// ============================================================================
+// Impersonation
+// ============================================================================
+
+/// `POST /admin/users/{id}/impersonate` — Issue a short-lived access token that acts as the
+/// user, for reproducing what they see. The token cannot be refreshed, can only read what the
+/// user sees, and every request made with it is written to `impersonation_log`.
+async fn impersonate_user(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    method: Method,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    Json(req): Json<ImpersonateRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let reason = req.reason.trim();
+    if reason.is_empty() {
+        return Err(AppError::BadRequest(
+            "A reason for impersonating is required".to_string(),
+        ));
+    }
+
+    let target = user::Entity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .filter(|u| u.deleted_at.is_none() && u.account_status != "deleted")
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    if target.role == "admin" {
+        return Err(AppError::Forbidden(
+            "Admins cannot be impersonated".to_string(),
+        ));
+    }
+    if target.account_status != "active" {
+        return Err(AppError::Conflict(
+            "Only active accounts can be impersonated".to_string(),
+        ));
+    }
+
+    impersonation::Entry {
+        admin_id: admin.id,
+        user_id: target.id,
+        method: &method,
+        path: uri.path(),
+        headers: &headers,
+    }
+    .record(&state.db, "started", Some(reason))
+    .await?;
+
+    audit::Event::new("user.impersonate", "user", target.id)
+        .after(serde_json::json!({ "reason": reason }))
+        .record(&state.db, &admin, &headers)
+        .await;
+
+    let (token, expires_at) =
+        jwt::generate_impersonation_token(target.id, &target.role, admin.id, &state.jwt_keys)?;
+
+    tracing::info!(
+        admin_id = %admin.id,
+        user_id = %target.id,
+        reason,
+        "Admin started impersonating a user"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ImpersonationResponse {
+            token,
+            expires_at: expires_at.to_rfc3339(),
+            user_id: target.id,
+            username: target.username,
+        }),
+    ))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+async fn find_tag<C: ConnectionTrait>(db: &C, id: Uuid) -> Result<tag::Model, AppError> {
+    tag::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Tag not found".to_string()))
+}
+
+/// Reject a name or slug already used by another tag.
+async fn ensure_unique<C: ConnectionTrait>(
+    db: &C,
+    exclude_id: Option<Uuid>,
+    name: &str,
+    slug: &str,
+) -> Result<(), AppError> {
+    let mut find = tag::Entity::find().filter(
+        Condition::any()
+            .add(tag::Column::Name.eq(name))
+            .add(tag::Column::Slug.eq(slug)),
+    );
+    if let Some(id) = exclude_id {
+        find = find.filter(tag::Column::Id.ne(id));
+    }
+
+    if find.one(db).await?.is_some() {
+        return Err(AppError::Conflict(
+            "A tag with this name or slug already exists".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Count games per tag, for one tag or the whole catalog.
+async fn count_games<C: ConnectionTrait>(
+    db: &C,
+    tag_id: Option<Uuid>,
+) -> Result<HashMap<Uuid, u64>, AppError> {
+    let mut find = game_tag::Entity::find()
+        .select_only()
+        .column(game_tag::Column::TagId)
+        .column_as(game_tag::Column::GameId.count(), "count")
+        .group_by(game_tag::Column::TagId);
+    if let Some(id) = tag_id {
+        find = find.filter(game_tag::Column::TagId.eq(id));
+    }
+
+    let rows: Vec<(Uuid, i64)> = find.into_tuple().all(db).await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(id, count)| (id, u64::try_from(count).unwrap_or_default()))
+        .collect())
+}
+
+fn validate_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::BadRequest("Tag name is required".to_string()));
+    }
+    if name.chars().count() > MAX_TAG_NAME_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Tag name must be at most {MAX_TAG_NAME_LENGTH} characters"
+        )));
+    }
+    Ok(name.to_string())
+}
+
+/// Normalize a slug (or a name to derive one from) to lowercase words joined by `-`.
+fn validate_slug(slug: &str) -> Result<String, AppError> {
+    let slug = slug
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    if slug.is_empty() {
+        return Err(AppError::BadRequest(
+            "Tag slug must contain letters or digits".to_string(),
+        ));
+    }
+    Ok(slug)
+}
+
+fn validate_category(category: &str) -> Result<String, AppError> {
+    if TAG_CATEGORIES.contains(&category) {
+        Ok(category.to_string())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Invalid category '{category}'. Expected one of: genre, mood, playerStyle"
+        )))
+    }
+}
+
+/// The audited fields of a tag.
+fn tag_snapshot(t: &tag::Model) -> serde_json::Value {
+    serde_json::json!({
+        "name": t.name,
+        "slug": t.slug,
+        "category": t.category,
+        "retiredAt": t.retired_at.map(|at| at.to_rfc3339()),
+    })
+}
+
+fn to_admin_tag_response(t: tag::Model, game_count: u64) -> AdminTagResponse {
+    AdminTagResponse {
+        id: t.id,
+        name: t.name,
+        slug: t.slug,
+        category: t.category,
+        retired_at: t.retired_at.map(|at| at.to_string()),
+        game_count,
+    }
+}
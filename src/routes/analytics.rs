@@ -0,0 +1,240 @@
+use axum::{
+    Extension, Json, Router,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use crate::{
+    analytics::{
+        CONTROLLER_LATENCY, EDITOR_ACTION, EVENT_NAMES, GAME_OPENED, MAX_ANONYMOUS_ID_LENGTH,
+    },
+    auth::extract_client_ip,
+    entities::analytics_event,
+    error::AppError,
+    middleware::rate_limit::{self, RateLimitPolicy, RateLimiter},
+    routes::games::OptionalAuth,
+    state::AppState,
+};
+
+/// Most events accepted in one batch.
+const MAX_BATCH_SIZE: usize = 100;
+
+/// Longest string property value, in characters.
+const MAX_PROPERTY_LENGTH: usize = 50;
+
+/// Highest `latencyMs` a `controller_latency` event may report.
+const MAX_LATENCY_MS: f64 = 60_000.0;
+
+/// How far in the future an event's `occurredAt` may be, to allow for clock skew.
+const MAX_CLOCK_SKEW: chrono::Duration = chrono::Duration::minutes(5);
+
+/// How old an event may be when it is reported, so queued events from offline clients are
+/// still accepted.
+const MAX_EVENT_AGE: chrono::Duration = chrono::Duration::days(7);
+
+/// Analytics router: `/analytics`, with `limit` applied to each reporting user, anonymous
+/// client or IP address.
+pub fn router(limit: RateLimitPolicy) -> Router<AppState> {
+    Router::new()
+        .route("/events", post(ingest_events))
+        .layer(Extension(RateLimiter::new(limit)))
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IngestRequest {
+    /// The client's own identifier, used when not signed in.
+    anonymous_id: Option<String>,
+    events: Vec<EventInput>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EventInput {
+    name: String,
+    game_id: Option<Uuid>,
+    session_id: Option<Uuid>,
+    #[serde(default)]
+    properties: Map<String, Value>,
+    /// Defaults to when the batch is received.
+    occurred_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IngestResponse {
+    accepted: usize,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `POST /analytics/events` — Report a batch of product analytics events.
+///
+/// Works without signing in. The whole batch is rejected if any event fails validation;
+/// accepted events are written in the background, so they may take a few seconds to appear.
+/// Batches are rate limited per user, else per `anonymousId`, else per IP address.
+async fn ingest_events(
+    State(state): State<AppState>,
+    Extension(limiter): Extension<RateLimiter>,
+    OptionalAuth(opt_user): OptionalAuth,
+    headers: HeaderMap,
+    Json(req): Json<IngestRequest>,
+) -> Result<Response, AppError> {
+    let anonymous_id = req
+        .anonymous_id
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty());
+    if anonymous_id
+        .as_ref()
+        .is_some_and(|id| id.chars().count() > MAX_ANONYMOUS_ID_LENGTH)
+    {
+        return Err(AppError::BadRequest(format!(
+            "anonymousId must be at most {MAX_ANONYMOUS_ID_LENGTH} characters"
+        )));
+    }
+
+    let user_id = opt_user.map(|u| u.id);
+    let key = user_id
+        .map(|id| format!("user:{id}"))
+        .or_else(|| anonymous_id.as_ref().map(|id| format!("anonymous:{id}")))
+        .or_else(|| extract_client_ip(&headers).map(|ip| format!("ip:{ip}")));
+    if let Some(key) = key
+        && let Err(wait) = limiter.try_acquire(&key)
+    {
+        return Ok(rate_limit::too_many_requests(wait));
+    }
+
+    if req.events.is_empty() {
+        return Err(AppError::BadRequest(
+            "At least one event is required".to_string(),
+        ));
+    }
+    if req.events.len() > MAX_BATCH_SIZE {
+        return Err(AppError::BadRequest(format!(
+            "At most {MAX_BATCH_SIZE} events may be sent at once"
+        )));
+    }
+
+    let now = Utc::now();
+    let events = req
+        .events
+        .into_iter()
+        .enumerate()
+        .map(|(index, event)| {
+            validate_event(&event, now)
+                .map_err(|message| AppError::BadRequest(format!("events[{index}]: {message}")))?;
+            Ok(analytics_event::Model {
+                id: Uuid::new_v4(),
+                name: event.name,
+                user_id,
+                anonymous_id: if user_id.is_some() {
+                    None
+                } else {
+                    anonymous_id.clone()
+                },
+                game_id: event.game_id,
+                session_id: event.session_id,
+                properties: Value::Object(event.properties),
+                occurred_at: event.occurred_at.unwrap_or(now).fixed_offset(),
+                received_at: now.fixed_offset(),
+            })
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+
+    let accepted = state.analytics.record(&state.db, events);
+    Ok((StatusCode::ACCEPTED, Json(IngestResponse { accepted })).into_response())
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Check an event against the schema of its name.
+fn validate_event(event: &EventInput, now: DateTime<Utc>) -> Result<(), String> {
+    if let Some(occurred_at) = event.occurred_at {
+        if occurred_at > now + MAX_CLOCK_SKEW {
+            return Err("occurredAt is in the future".to_string());
+        }
+        if occurred_at < now - MAX_EVENT_AGE {
+            return Err("occurredAt is more than 7 days ago".to_string());
+        }
+    }
+
+    match event.name.as_str() {
+        GAME_OPENED => {
+            require(event.game_id.is_some(), "gameId is required")?;
+            allow_properties(&event.properties, &["source"])?;
+            optional_string(&event.properties, "source")
+        }
+        CONTROLLER_LATENCY => {
+            require(event.session_id.is_some(), "sessionId is required")?;
+            allow_properties(&event.properties, &["latencyMs"])?;
+            let latency = event
+                .properties
+                .get("latencyMs")
+                .and_then(Value::as_f64)
+                .ok_or("properties.latencyMs must be a number")?;
+            require(
+                (0.0..=MAX_LATENCY_MS).contains(&latency),
+                "properties.latencyMs must be between 0 and 60000",
+            )
+        }
+        EDITOR_ACTION => {
+            require(event.game_id.is_some(), "gameId is required")?;
+            allow_properties(&event.properties, &["action"])?;
+            require(
+                event.properties.contains_key("action"),
+                "properties.action is required",
+            )?;
+            optional_string(&event.properties, "action")
+        }
+        name => Err(format!(
+            "Invalid name '{name}'. Expected one of: {}",
+            EVENT_NAMES.join(", ")
+        )),
+    }
+}
+
+fn require(condition: bool, message: &str) -> Result<(), String> {
+    if condition {
+        Ok(())
+    } else {
+        Err(message.to_string())
+    }
+}
+
+/// Reject properties outside `allowed`.
+fn allow_properties(properties: &Map<String, Value>, allowed: &[&str]) -> Result<(), String> {
+    properties
+        .keys()
+        .find(|key| !allowed.contains(&key.as_str()))
+        .map_or(Ok(()), |key| Err(format!("Unknown property '{key}'")))
+}
+
+/// Check that `key`, if present, is a non-empty string of at most [`MAX_PROPERTY_LENGTH`]
+/// characters.
+fn optional_string(properties: &Map<String, Value>, key: &str) -> Result<(), String> {
+    let Some(value) = properties.get(key) else {
+        return Ok(());
+    };
+    let length = value
+        .as_str()
+        .map(|s| s.trim().chars().count())
+        .ok_or_else(|| format!("properties.{key} must be a string"))?;
+    require(
+        (1..=MAX_PROPERTY_LENGTH).contains(&length),
+        &format!("properties.{key} must be 1 to {MAX_PROPERTY_LENGTH} characters"),
+    )
+}
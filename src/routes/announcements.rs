@@ -0,0 +1,404 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, DatabaseConnection, EntityTrait,
+    QueryFilter, QueryOrder,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    audit, auth::middleware::AdminUser, entities::announcement, error::AppError, state::AppState,
+};
+
+/// Allowed values for `announcement.severity`, least to most urgent.
+const SEVERITIES: [&str; 3] = ["info", "warning", "critical"];
+
+/// Longest title, in characters.
+const MAX_TITLE_LENGTH: usize = 100;
+
+/// Longest body, in characters.
+const MAX_BODY_LENGTH: usize = 1000;
+
+/// Longest dismissal key, in characters.
+const MAX_DISMISSAL_KEY_LENGTH: usize = 64;
+
+/// Public announcements router: `/announcements`
+pub fn router() -> Router<AppState> {
+    Router::new().route("/active", get(list_active_announcements))
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+/// The full announcement, for both creating and replacing one.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnouncementRequest {
+    title: String,
+    body: String,
+    /// `info`, `warning` or `critical`.
+    severity: String,
+    /// An http(s) URL or a frontend path such as `/events/summer-jam`.
+    link_url: Option<String>,
+    #[serde(default = "default_dismissible")]
+    dismissible: bool,
+    /// Defaults to a fresh key, so replacing an announcement without one shows it again to
+    /// everyone who dismissed the old version.
+    dismissal_key: Option<String>,
+    /// Defaults to now.
+    starts_at: Option<DateTime<Utc>>,
+    ends_at: Option<DateTime<Utc>>,
+}
+
+const fn default_dismissible() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AnnouncementResponse {
+    id: Uuid,
+    title: String,
+    body: String,
+    severity: String,
+    link_url: Option<String>,
+    dismissible: bool,
+    dismissal_key: String,
+    starts_at: String,
+    ends_at: Option<String>,
+}
+
+impl From<announcement::Model> for AnnouncementResponse {
+    fn from(a: announcement::Model) -> Self {
+        Self {
+            id: a.id,
+            title: a.title,
+            body: a.body,
+            severity: a.severity,
+            link_url: a.link_url,
+            dismissible: a.dismissible,
+            dismissal_key: a.dismissal_key,
+            starts_at: a.starts_at.to_rfc3339(),
+            ends_at: a.ends_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+/// An announcement as admins see it, with who created it and when.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AdminAnnouncementResponse {
+    #[serde(flatten)]
+    announcement: AnnouncementResponse,
+    created_by: Option<Uuid>,
+    created_at: String,
+    updated_at: String,
+}
+
+impl From<announcement::Model> for AdminAnnouncementResponse {
+    fn from(a: announcement::Model) -> Self {
+        Self {
+            created_by: a.created_by,
+            created_at: a.created_at.to_rfc3339(),
+            updated_at: a.updated_at.to_rfc3339(),
+            announcement: a.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct AnnouncementsResponse<T> {
+    data: Vec<T>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `GET /announcements/active` — Banners to show right now, most urgent first.
+async fn list_active_announcements(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let now = Utc::now().fixed_offset();
+    let mut active = announcement::Entity::find()
+        .filter(announcement::Column::StartsAt.lte(now))
+        .filter(
+            Condition::any()
+                .add(announcement::Column::EndsAt.is_null())
+                .add(announcement::Column::EndsAt.gt(now)),
+        )
+        .order_by_desc(announcement::Column::StartsAt)
+        .all(&state.db)
+        .await?;
+    active.sort_by_key(|a| std::cmp::Reverse(severity_rank(&a.severity)));
+
+    Ok(Json(AnnouncementsResponse {
+        data: active.into_iter().map(AnnouncementResponse::from).collect(),
+    }))
+}
+
+/// `GET /admin/announcements` — Every announcement, past, current and scheduled, newest
+/// start first.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the database query fails.
+pub async fn list_announcements(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+) -> Result<impl IntoResponse, AppError> {
+    let announcements = announcement::Entity::find()
+        .order_by_desc(announcement::Column::StartsAt)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(AnnouncementsResponse {
+        data: announcements
+            .into_iter()
+            .map(AdminAnnouncementResponse::from)
+            .collect(),
+    }))
+}
+
+/// `POST /admin/announcements` — Schedule a banner.
+///
+/// # Errors
+///
+/// Returns [`AppError::BadRequest`] if the announcement is invalid, or [`AppError`] if a
+/// database operation fails.
+pub async fn create_announcement(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    headers: HeaderMap,
+    Json(req): Json<AnnouncementRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let valid = validate(req)?;
+    let id = Uuid::new_v4();
+    let now = Utc::now().fixed_offset();
+
+    let created = announcement::ActiveModel {
+        id: Set(id),
+        title: Set(valid.title),
+        body: Set(valid.body),
+        severity: Set(valid.severity),
+        link_url: Set(valid.link_url),
+        dismissible: Set(valid.dismissible),
+        dismissal_key: Set(valid.dismissal_key),
+        starts_at: Set(valid.starts_at.unwrap_or(now)),
+        ends_at: Set(valid.ends_at),
+        created_by: Set(Some(admin.id)),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(&state.db)
+    .await?;
+
+    audit::Event::new("announcement.create", "announcement", id)
+        .after(snapshot(&created))
+        .record(&state.db, &admin, &headers)
+        .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(AdminAnnouncementResponse::from(created)),
+    ))
+}
+
+/// `PUT /admin/announcements/{id}` — Replace an announcement.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if there is no announcement with that ID,
+/// [`AppError::BadRequest`] if the announcement is invalid, or [`AppError`] if a database
+/// operation fails.
+pub async fn update_announcement(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<AnnouncementRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let existing = find_announcement(&state.db, id).await?;
+    let valid = validate(req)?;
+    let before = snapshot(&existing);
+
+    let starts_at = valid
+        .starts_at
+        .map_or(existing.starts_at, |t| t.fixed_offset());
+    let mut active: announcement::ActiveModel = existing.into();
+    active.title = Set(valid.title);
+    active.body = Set(valid.body);
+    active.severity = Set(valid.severity);
+    active.link_url = Set(valid.link_url);
+    active.dismissible = Set(valid.dismissible);
+    active.dismissal_key = Set(valid.dismissal_key);
+    active.starts_at = Set(starts_at);
+    active.ends_at = Set(valid.ends_at);
+    active.updated_at = Set(Utc::now().fixed_offset());
+    let updated = active.update(&state.db).await?;
+
+    audit::Event::new("announcement.update", "announcement", id)
+        .before(before)
+        .after(snapshot(&updated))
+        .record(&state.db, &admin, &headers)
+        .await;
+
+    Ok(Json(AdminAnnouncementResponse::from(updated)))
+}
+
+/// `DELETE /admin/announcements/{id}` — Remove an announcement.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if there is no announcement with that ID, or [`AppError`]
+/// if a database operation fails.
+pub async fn delete_announcement(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let existing = find_announcement(&state.db, id).await?;
+    announcement::Entity::delete_by_id(id)
+        .exec(&state.db)
+        .await?;
+
+    audit::Event::new("announcement.delete", "announcement", id)
+        .before(snapshot(&existing))
+        .record(&state.db, &admin, &headers)
+        .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// An [`AnnouncementRequest`] that passed validation.
+struct ValidAnnouncement {
+    title: String,
+    body: String,
+    severity: String,
+    link_url: Option<String>,
+    dismissible: bool,
+    dismissal_key: String,
+    starts_at: Option<sea_orm::prelude::DateTimeWithTimeZone>,
+    ends_at: Option<sea_orm::prelude::DateTimeWithTimeZone>,
+}
+
+fn validate(req: AnnouncementRequest) -> Result<ValidAnnouncement, AppError> {
+    let title = req.title.trim().to_string();
+    if !(1..=MAX_TITLE_LENGTH).contains(&title.chars().count()) {
+        return Err(AppError::BadRequest(format!(
+            "Title must be between 1 and {MAX_TITLE_LENGTH} characters."
+        )));
+    }
+    let body = req.body.trim().to_string();
+    if !(1..=MAX_BODY_LENGTH).contains(&body.chars().count()) {
+        return Err(AppError::BadRequest(format!(
+            "Body must be between 1 and {MAX_BODY_LENGTH} characters."
+        )));
+    }
+    if !SEVERITIES.contains(&req.severity.as_str()) {
+        return Err(AppError::BadRequest(format!(
+            "Invalid severity '{}'. Expected one of: {}",
+            req.severity,
+            SEVERITIES.join(", ")
+        )));
+    }
+
+    let link_url = req
+        .link_url
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty());
+    if let Some(url) = &link_url
+        && !is_valid_link(url)
+    {
+        return Err(AppError::BadRequest(
+            "Link must be an http(s) URL or a path starting with '/'.".to_string(),
+        ));
+    }
+
+    let dismissal_key = match req.dismissal_key {
+        Some(key) => {
+            let key = key.trim().to_string();
+            let valid = (1..=MAX_DISMISSAL_KEY_LENGTH).contains(&key.len())
+                && key
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+            if !valid {
+                return Err(AppError::BadRequest(format!(
+                    "Dismissal key must be 1 to {MAX_DISMISSAL_KEY_LENGTH} letters, digits, \
+                     '-' or '_'."
+                )));
+            }
+            key
+        }
+        None => Uuid::new_v4().simple().to_string(),
+    };
+
+    if let (Some(starts_at), Some(ends_at)) = (req.starts_at, req.ends_at)
+        && ends_at <= starts_at
+    {
+        return Err(AppError::BadRequest(
+            "endsAt must be after startsAt.".to_string(),
+        ));
+    }
+
+    Ok(ValidAnnouncement {
+        title,
+        body,
+        severity: req.severity,
+        link_url,
+        dismissible: req.dismissible,
+        dismissal_key,
+        starts_at: req.starts_at.map(|t| t.fixed_offset()),
+        ends_at: req.ends_at.map(|t| t.fixed_offset()),
+    })
+}
+
+/// An absolute `http`/`https` URL, or a path on the frontend.
+fn is_valid_link(url: &str) -> bool {
+    url.len() <= 2048
+        && ((url.starts_with('/') && !url.starts_with("//"))
+            || reqwest::Url::parse(url)
+                .is_ok_and(|u| matches!(u.scheme(), "http" | "https") && u.host_str().is_some()))
+}
+
+/// Position of `severity` in [`SEVERITIES`], so more urgent banners sort first.
+fn severity_rank(severity: &str) -> usize {
+    SEVERITIES
+        .iter()
+        .position(|s| *s == severity)
+        .unwrap_or_default()
+}
+
+async fn find_announcement(
+    db: &DatabaseConnection,
+    id: Uuid,
+) -> Result<announcement::Model, AppError> {
+    announcement::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Announcement not found.".to_string()))
+}
+
+/// The audited fields of an announcement.
+fn snapshot(a: &announcement::Model) -> serde_json::Value {
+    serde_json::json!({
+        "title": a.title,
+        "severity": a.severity,
+        "dismissalKey": a.dismissal_key,
+        "startsAt": a.starts_at.to_rfc3339(),
+        "endsAt": a.ends_at.map(|t| t.to_rfc3339()),
+    })
+}
@@ -0,0 +1,260 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::{middleware::AuthUser, personal_token},
+    entities::api_token,
+    error::AppError,
+    state::AppState,
+};
+
+/// Maximum length (in characters) of a token name.
+const MAX_NAME_LENGTH: usize = 100;
+
+/// Longest lifetime a token can be created with; omit `expiresInDays` for no expiry.
+const MAX_EXPIRES_IN_DAYS: i64 = 365;
+
+/// Tokens a single user can hold.
+const MAX_TOKENS_PER_USER: u64 = 50;
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTokenRequest {
+    name: String,
+    scopes: Vec<String>,
+    expires_in_days: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTokenRequest {
+    name: Option<String>,
+    scopes: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenResponse {
+    id: Uuid,
+    name: String,
+    token_prefix: String,
+    scopes: Vec<String>,
+    expires_at: Option<String>,
+    last_used_at: Option<String>,
+    created_at: String,
+    /// Set for tokens issued to an OAuth client the user authorized.
+    oauth_client_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CreatedTokenResponse {
+    #[serde(flatten)]
+    token: TokenResponse,
+    /// The token itself; only returned here.
+    secret: String,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `GET /users/me/tokens` — List the authenticated user's personal access tokens.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the database query fails.
+pub async fn list_my_tokens(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    let tokens = api_token::Entity::find()
+        .filter(api_token::Column::UserId.eq(user.id))
+        .order_by_desc(api_token::Column::CreatedAt)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(
+        tokens
+            .into_iter()
+            .map(to_token_response)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// `POST /users/me/tokens` — Create a personal access token.
+///
+/// The token is returned once, in `secret`; only its hash is stored.
+///
+/// # Errors
+///
+/// Returns [`AppError::BadRequest`] for an invalid name, scope or lifetime,
+/// [`AppError::UnprocessableEntity`] if the user already has the maximum number of tokens, or
+/// [`AppError`] if the database operation fails.
+pub async fn create_my_token(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(body): Json<CreateTokenRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let name = validate_name(&body.name)?;
+    let scopes = validate_scopes(&body.scopes)?;
+    let now = Utc::now().fixed_offset();
+    let expires_at = match body.expires_in_days {
+        None => None,
+        Some(days @ 1..=MAX_EXPIRES_IN_DAYS) => Some(now + chrono::Duration::days(days)),
+        Some(_) => {
+            return Err(AppError::BadRequest(format!(
+                "expiresInDays must be between 1 and {MAX_EXPIRES_IN_DAYS}."
+            )));
+        }
+    };
+
+    let existing = api_token::Entity::find()
+        .filter(api_token::Column::UserId.eq(user.id))
+        .filter(api_token::Column::OauthClientId.is_null())
+        .count(&state.db)
+        .await?;
+    if existing >= MAX_TOKENS_PER_USER {
+        return Err(AppError::UnprocessableEntity(format!(
+            "You can have at most {MAX_TOKENS_PER_USER} tokens; delete one first."
+        )));
+    }
+
+    let generated = personal_token::generate();
+    let record = api_token::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        user_id: ActiveValue::Set(user.id),
+        name: ActiveValue::Set(name),
+        token_hash: ActiveValue::Set(generated.hash),
+        token_prefix: ActiveValue::Set(generated.display_prefix),
+        scopes: ActiveValue::Set(scopes),
+        expires_at: ActiveValue::Set(expires_at),
+        last_used_at: ActiveValue::Set(None),
+        created_at: ActiveValue::Set(now),
+        oauth_client_id: ActiveValue::Set(None),
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedTokenResponse {
+            token: to_token_response(record),
+            secret: generated.token,
+        }),
+    ))
+}
+
+/// `PATCH /users/me/tokens/{id}` — Rename a token or change its scopes.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if the token does not exist or belongs to someone else,
+/// [`AppError::BadRequest`] for an invalid name or scope, or [`AppError`] if the database
+/// operation fails.
+pub async fn update_my_token(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<UpdateTokenRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let record = find_my_token(&state, user.id, id).await?;
+
+    let mut active: api_token::ActiveModel = record.into();
+    if let Some(name) = body.name {
+        active.name = ActiveValue::Set(validate_name(&name)?);
+    }
+    if let Some(scopes) = body.scopes {
+        active.scopes = ActiveValue::Set(validate_scopes(&scopes)?);
+    }
+    let record = active.update(&state.db).await?;
+
+    Ok(Json(to_token_response(record)))
+}
+
+/// `DELETE /users/me/tokens/{id}` — Revoke a token.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if the token does not exist or belongs to someone else, or
+/// [`AppError`] if the database operation fails.
+pub async fn delete_my_token(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let record = find_my_token(&state, user.id, id).await?;
+    api_token::Entity::delete_by_id(record.id)
+        .exec(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+async fn find_my_token(
+    state: &AppState,
+    user_id: Uuid,
+    id: Uuid,
+) -> Result<api_token::Model, AppError> {
+    api_token::Entity::find_by_id(id)
+        .filter(api_token::Column::UserId.eq(user_id))
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Token not found.".to_string()))
+}
+
+fn validate_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Name must be between 1 and {MAX_NAME_LENGTH} characters."
+        )));
+    }
+    Ok(name.to_string())
+}
+
+/// Check `scopes` against the known scopes and return them space-separated, deduplicated.
+fn validate_scopes(scopes: &[String]) -> Result<String, AppError> {
+    personal_token::normalize_scopes(scopes.iter().map(String::as_str)).map_err(|unknown| {
+        if unknown.is_empty() {
+            AppError::BadRequest("At least one scope is required.".to_string())
+        } else {
+            AppError::BadRequest(format!(
+                "Unknown scope '{unknown}'. Valid scopes: {}.",
+                personal_token::SCOPES.join(", ")
+            ))
+        }
+    })
+}
+
+fn to_token_response(t: api_token::Model) -> TokenResponse {
+    TokenResponse {
+        id: t.id,
+        name: t.name,
+        token_prefix: t.token_prefix,
+        scopes: t.scopes.split_whitespace().map(String::from).collect(),
+        expires_at: t.expires_at.map(|d| d.to_rfc3339()),
+        last_used_at: t.last_used_at.map(|d| d.to_rfc3339()),
+        created_at: t.created_at.to_rfc3339(),
+        oauth_client_id: t.oauth_client_id,
+    }
+}
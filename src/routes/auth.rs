@@ -1,7 +1,7 @@
-use axum::extract::{Path, Query, State};
+use axum::extract::{Form, Path, Query, State};
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::{IntoResponse, Redirect, Response};
-use axum::routing::{get, post};
+use axum::routing::{delete, get, post};
 use axum::{Json, Router};
 use chrono::Utc;
 use oauth2::{AuthorizationCode, CsrfToken, Scope, TokenResponse};
@@ -11,34 +11,64 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::auth::middleware::AuthUser;
-use crate::auth::{extract_client_ip, jwt, oauth, password};
-use crate::entities::{auth_provider, refresh_token, user};
-use crate::error::AppError;
+use crate::auth::{
+    breach, cookies, devices, extract_client_ip, extract_user_agent, generate_verification_token,
+    hash_verification_token, jwt, login_events, oauth, oidc, password, totp,
+};
+use crate::classroom::STUDENT_PROVIDER;
+use crate::entities::{
+    auth_provider, classroom, classroom_student, email_change, refresh_token, user, user_totp,
+};
+use crate::error::{AppError, code};
+use crate::middleware::rate_limit::{self, RateLimitPolicy, RateLimiter};
 use crate::state::AppState;
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Router
 // ─────────────────────────────────────────────────────────────────────────────
 
-/// Build the auth route group: `/auth/...`
-pub fn router() -> Router<AppState> {
-    Router::new()
+/// Build the auth route group: `/auth/...`, with `credentials` limiting sign-in and signup.
+pub fn router(credentials: RateLimitPolicy) -> Router<AppState> {
+    let credentials = Router::new()
         .route("/signup/email", post(signup_email))
         .route("/signin/email", post(signin_email))
-        .route("/verify-email", post(verify_email))
-        .route("/resend-verification", post(resend_verification))
+        .route("/signin/classroom", post(signin_classroom))
         .route("/password-reset/request", post(password_reset_request))
         .route("/password-reset/confirm", post(password_reset_confirm))
+        .route("/2fa/verify", post(two_factor_verify))
+        .route("/guest", post(guest_signup))
+        .route_layer(axum::middleware::from_fn_with_state(
+            RateLimiter::new(credentials),
+            rate_limit::enforce,
+        ));
+
+    Router::new()
+        .merge(credentials)
+        .route("/guest/upgrade", post(guest_upgrade))
+        .route("/verify-email", post(verify_email))
+        .route("/email-change/confirm", post(email_change_confirm))
+        .route("/not-me", post(not_me))
+        .route("/resend-verification", post(resend_verification))
         .route("/password/change", post(password_change))
+        .route("/password-policy", get(password_policy))
+        .route("/2fa/totp/setup", post(totp_setup))
+        .route("/2fa/totp/confirm", post(totp_confirm))
+        .route("/2fa/totp", delete(totp_disable))
         .route("/oauth/google", get(oauth_google_initiate))
         .route("/oauth/google/callback", get(oauth_google_callback))
         .route("/oauth/github", get(oauth_github_initiate))
         .route("/oauth/github/callback", get(oauth_github_callback))
+        .route("/oauth/apple", get(oauth_apple_initiate))
+        .route("/oauth/apple/callback", post(oauth_apple_callback))
+        .route("/oidc", get(oidc_list_providers))
+        .route("/oidc/{provider}", get(oidc_initiate))
+        .route("/oidc/{provider}/callback", get(oidc_callback))
         .route(
             "/link/{provider}",
             post(link_provider).delete(unlink_provider),
         )
         .route("/refresh", post(refresh_token_handler))
+        .route("/csrf", get(csrf_token))
         .route("/signout", post(signout))
 }
 
@@ -53,18 +83,66 @@ pub struct SignupEmailRequest {
     pub password: String,
 }
 
+/// Either `email` + `password` or `provider` + `code`, optionally with a new `username`.
+#[derive(Deserialize)]
+pub struct GuestUpgradeRequest {
+    pub email: Option<String>,
+    pub password: Option<String>,
+    pub username: Option<String>,
+    pub provider: Option<String>,
+    pub code: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct SigninEmailRequest {
     pub email: String,
     pub password: String,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigninClassroomRequest {
+    pub class_code: String,
+    pub username: String,
+    pub password: String,
+}
+
+/// A signed-in user and their token pair. In cookie mode the tokens are sent as cookies and
+/// left out of the body, which carries the CSRF token instead; see
+/// [`AuthResponse::into_response_for`].
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthResponse {
     pub user: UserResponse,
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub token: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub refresh_token: String,
+    /// Value of the CSRF cookie, for a frontend on another origin that cannot read it.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub csrf_token: String,
+}
+
+impl AuthResponse {
+    /// Respond with `status`, moving the token pair into cookies when
+    /// [`Config::auth_cookies`](crate::config::Config::auth_cookies) is on.
+    pub fn into_response_for(mut self, state: &AppState, status: StatusCode) -> Response {
+        if !state.config.auth_cookies {
+            return (status, Json(self)).into_response();
+        }
+        let access_token = std::mem::take(&mut self.token);
+        let refresh_token = std::mem::take(&mut self.refresh_token);
+        self.csrf_token = cookies::generate_csrf_token();
+        let mut response = (status, Json(&self)).into_response();
+        cookies::set_tokens(
+            response.headers_mut(),
+            &state.config,
+            &access_token,
+            &refresh_token,
+            &self.csrf_token,
+        );
+        response
+    }
 }
 
 #[derive(Serialize)]
@@ -106,6 +184,8 @@ pub struct PasswordChangeBody {
     pub new_password: String,
 }
 
+/// Body of `POST /auth/refresh` and `POST /auth/signout`. In cookie mode the refresh token
+/// may come from its cookie instead, and the body can be omitted.
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RefreshRequestBody {
@@ -119,10 +199,12 @@ pub struct RefreshResponse {
     pub refresh_token: String,
 }
 
-#[derive(Deserialize)]
+/// The CSRF token of a cookie session, which a frontend on another origin cannot read from
+/// its cookie.
+#[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct SignoutRequestBody {
-    pub refresh_token: String,
+pub struct CsrfResponse {
+    pub csrf_token: String,
 }
 
 #[derive(Deserialize)]
@@ -150,11 +232,57 @@ pub struct OAuthCallbackQuery {
     pub state: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcProviderResponse {
+    pub name: String,
+    pub display_name: String,
+}
+
+/// Fields Apple posts to the callback (`response_mode=form_post`).
+#[derive(Deserialize)]
+pub struct AppleCallbackForm {
+    pub code: Option<String>,
+    pub state: String,
+    /// JSON with the user's name and email, sent on first authorization only.
+    pub user: Option<String>,
+    pub error: Option<String>,
+}
+
 #[derive(Serialize)]
 pub struct MessageResponse {
     pub message: String,
 }
 
+/// Returned instead of tokens when the account has two-factor authentication enabled.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoFactorChallengeResponse {
+    pub two_factor_required: bool,
+    /// Exchanged with a code at `POST /auth/2fa/verify`.
+    pub challenge_token: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoFactorVerifyRequest {
+    pub challenge_token: String,
+    pub code: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpSetupResponse {
+    /// Base32 secret, for manual entry.
+    pub secret: String,
+    pub provisioning_uri: String,
+}
+
+#[derive(Deserialize)]
+pub struct TotpCodeRequest {
+    pub code: String,
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Helpers
 // ─────────────────────────────────────────────────────────────────────────────
@@ -174,13 +302,20 @@ fn user_response(u: &user::Model) -> UserResponse {
     }
 }
 
-/// Store a new refresh token record in the database.
+/// Store a new refresh token record in the database, along with the client it was issued to.
+///
+/// `signed_in_at` is the original sign-in time when the token replaces a rotated one; a new
+/// sign-in passes `None`, and also has its device recorded (see [`devices::record_signin`]).
 async fn store_refresh_token(
-    db: &sea_orm::DatabaseConnection,
+    state: &AppState,
     user_id: Uuid,
     token_pair: &jwt::TokenPair,
+    headers: &HeaderMap,
+    signed_in_at: Option<sea_orm::prelude::DateTimeWithTimeZone>,
 ) -> Result<(), AppError> {
     let now = Utc::now().fixed_offset();
+    let user_agent = extract_user_agent(headers);
+    let client_ip = extract_client_ip(headers);
 
     let record = refresh_token::ActiveModel {
         id: Set(token_pair.refresh_jti),
@@ -189,28 +324,289 @@ async fn store_refresh_token(
         expires_at: Set(token_pair.refresh_expires_at.fixed_offset()),
         revoked_at: Set(None),
         created_at: Set(now),
+        user_agent: Set(user_agent.clone()),
+        ip_address: Set(client_ip.clone()),
+        signed_in_at: Set(Some(signed_in_at.unwrap_or(now))),
     };
 
     record
-        .insert(db)
+        .insert(&state.db)
         .await
         .map_err(|e| AppError::Internal(e.into()))?;
+
+    if signed_in_at.is_none() {
+        devices::record_signin(
+            &state.db,
+            &state.config,
+            &state.notification_hub,
+            user_id,
+            user_agent.as_deref(),
+            client_ip.as_deref(),
+            token_pair.refresh_jti,
+        )
+        .await?;
+    }
     Ok(())
 }
 
-/// Generate a random verification/reset token.
-fn generate_verification_token() -> String {
-    Uuid::new_v4().to_string()
+/// Record the sign-in, both on the user and as a successful `attempt`, and issue a token pair.
+pub async fn complete_signin(
+    state: &AppState,
+    attempt: &login_events::Attempt<'_>,
+    user_model: user::Model,
+) -> Result<AuthResponse, AppError> {
+    let headers = attempt.headers;
+    attempt
+        .record(&state.db, Some(user_model.id), "success")
+        .await;
+
+    let client_ip = extract_client_ip(headers);
+    let now = Utc::now().fixed_offset();
+    let mut active_user: user::ActiveModel = user_model.into();
+    active_user.last_login_at = Set(Some(now));
+    active_user.last_login_ip = Set(client_ip);
+    active_user.updated_at = Set(now);
+    let user_model = active_user
+        .update(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    let token_pair = jwt::generate_token_pair(user_model.id, &user_model.role, &state.jwt_keys)?;
+    store_refresh_token(state, user_model.id, &token_pair, headers, None).await?;
+
+    Ok(AuthResponse {
+        user: user_response(&user_model),
+        token: token_pair.access_token,
+        refresh_token: token_pair.refresh_token,
+        csrf_token: String::new(),
+    })
+}
+
+/// Issue a two-factor challenge token if the user has confirmed TOTP enrollment.
+async fn two_factor_challenge(state: &AppState, user_id: Uuid) -> Result<Option<String>, AppError> {
+    let enrolled = user_totp::Entity::find_by_id(user_id)
+        .filter(user_totp::Column::ConfirmedAt.is_not_null())
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?
+        .is_some();
+
+    if enrolled {
+        Ok(Some(jwt::generate_two_factor_challenge(
+            user_id,
+            &state.jwt_keys,
+        )?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Check a TOTP code and consume it, so it cannot be replayed.
+///
+/// Returns `false` if the code is wrong or was already used.
+async fn consume_totp_code(
+    db: &sea_orm::DatabaseConnection,
+    enrollment: &user_totp::Model,
+    code: &str,
+) -> Result<bool, AppError> {
+    let Some(step) = totp::verify(&enrollment.secret, code, Utc::now().timestamp()) else {
+        return Ok(false);
+    };
+
+    // Conditional update so concurrent requests cannot both use the same code
+    let consumed = user_totp::Entity::update_many()
+        .col_expr(
+            user_totp::Column::LastUsedStep,
+            sea_orm::sea_query::Expr::value(step),
+        )
+        .col_expr(
+            user_totp::Column::FailedAttempts,
+            sea_orm::sea_query::Expr::value(0),
+        )
+        .col_expr(
+            user_totp::Column::LastFailedAt,
+            sea_orm::sea_query::Expr::value(Option::<chrono::DateTime<chrono::FixedOffset>>::None),
+        )
+        .filter(user_totp::Column::UserId.eq(enrollment.user_id))
+        .filter(
+            sea_orm::Condition::any()
+                .add(user_totp::Column::LastUsedStep.is_null())
+                .add(user_totp::Column::LastUsedStep.lt(step)),
+        )
+        .exec(db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    Ok(consumed.rows_affected > 0)
+}
+
+/// Sign-in codes a user may try within [`TWO_FACTOR_ATTEMPT_WINDOW_MINUTES`] of the last one
+/// before `/2fa/verify` refuses their challenges.
+const MAX_TWO_FACTOR_ATTEMPTS: i32 = 5;
+
+/// How long tried codes count towards [`MAX_TWO_FACTOR_ATTEMPTS`]; as long as a challenge
+/// lives, so a challenge that runs out of attempts is never usable again.
+const TWO_FACTOR_ATTEMPT_WINDOW_MINUTES: i64 = 5;
+
+/// Count a sign-in code attempt against `user_id` before it is checked, returning `false`
+/// instead once [`MAX_TWO_FACTOR_ATTEMPTS`] have been tried. Accepting a code resets the count
+/// (see [`consume_totp_code`]).
+async fn reserve_two_factor_attempt(
+    db: &sea_orm::DatabaseConnection,
+    user_id: Uuid,
+) -> Result<bool, AppError> {
+    use sea_orm::sea_query::Expr;
+
+    let now = Utc::now().fixed_offset();
+    let stale = sea_orm::Condition::any()
+        .add(user_totp::Column::LastFailedAt.is_null())
+        .add(
+            user_totp::Column::LastFailedAt
+                .lt(now - chrono::Duration::minutes(TWO_FACTOR_ATTEMPT_WINDOW_MINUTES)),
+        );
+
+    // Conditional update so concurrent requests cannot try more codes than allowed
+    let reserved = user_totp::Entity::update_many()
+        .col_expr(
+            user_totp::Column::FailedAttempts,
+            Expr::case(stale.clone(), 1)
+                .finally(Expr::col(user_totp::Column::FailedAttempts).add(1))
+                .into(),
+        )
+        .col_expr(user_totp::Column::LastFailedAt, Expr::value(now))
+        .filter(user_totp::Column::UserId.eq(user_id))
+        .filter(
+            sea_orm::Condition::any()
+                .add(stale)
+                .add(user_totp::Column::FailedAttempts.lt(MAX_TWO_FACTOR_ATTEMPTS)),
+        )
+        .exec(db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    Ok(reserved.rows_affected > 0)
+}
+
+/// Send an OAuth sign-in that still needs a second factor back to the frontend (or API client)
+/// with a challenge token instead of the token pair.
+fn two_factor_redirect(
+    redirect_uri: Option<&str>,
+    provider: &str,
+    challenge_token: String,
+) -> Response {
+    match redirect_uri {
+        Some(redirect_uri) => Redirect::to(&format!(
+            "{redirect_uri}?provider={provider}&twoFactorRequired=true&challengeToken={}",
+            urlencoding::encode(&challenge_token)
+        ))
+        .into_response(),
+        None => Json(TwoFactorChallengeResponse {
+            two_factor_required: true,
+            challenge_token,
+        })
+        .into_response(),
+    }
+}
+
+/// Finish an OAuth sign-in: send accounts with two-factor authentication to `/2fa/verify`,
+/// otherwise issue the token pair, redirecting to the frontend when it started the flow.
+async fn finish_oauth_signin(
+    state: &AppState,
+    headers: &HeaderMap,
+    user_model: user::Model,
+    redirect_uri: Option<String>,
+    provider: &str,
+) -> Result<Response, AppError> {
+    let attempt = login_events::Attempt::new(provider, headers);
+    if let Some(challenge_token) = two_factor_challenge(state, user_model.id).await? {
+        attempt
+            .record(&state.db, Some(user_model.id), "two_factor_required")
+            .await;
+        return Ok(two_factor_redirect(
+            redirect_uri.as_deref(),
+            provider,
+            challenge_token,
+        ));
+    }
+
+    attempt
+        .record(&state.db, Some(user_model.id), "success")
+        .await;
+    let token_pair = jwt::generate_token_pair(user_model.id, &user_model.role, &state.jwt_keys)?;
+    store_refresh_token(state, user_model.id, &token_pair, headers, None).await?;
+
+    let auth_response = AuthResponse {
+        user: user_response(&user_model),
+        token: token_pair.access_token,
+        refresh_token: token_pair.refresh_token,
+        csrf_token: String::new(),
+    };
+
+    // If redirect_uri was provided, redirect to frontend with auth data
+    if let Some(redirect_uri) = redirect_uri {
+        let user_json =
+            serde_json::to_string(&auth_response.user).unwrap_or_else(|_| "{}".to_string());
+        // In cookie mode the tokens travel as cookies rather than in the URL
+        if state.config.auth_cookies {
+            let location = format!(
+                "{redirect_uri}?provider={provider}&user={}",
+                urlencoding::encode(&user_json)
+            );
+            let mut response = Redirect::to(&location).into_response();
+            cookies::set_tokens(
+                response.headers_mut(),
+                &state.config,
+                &auth_response.token,
+                &auth_response.refresh_token,
+                &cookies::generate_csrf_token(),
+            );
+            return Ok(response);
+        }
+        let location = format!(
+            "{}?provider={provider}&token={}&refreshToken={}&user={}",
+            redirect_uri,
+            urlencoding::encode(&auth_response.token),
+            urlencoding::encode(&auth_response.refresh_token),
+            urlencoding::encode(&user_json)
+        );
+        return Ok(Redirect::to(&location).into_response());
+    }
+
+    // Fallback: return JSON for API clients
+    Ok(auth_response.into_response_for(state, StatusCode::OK))
+}
+
+/// Reject a new password found in a known breach, when `HIBP_ENABLED` is set.
+///
+/// If the breach API cannot be reached the password is allowed, so an outage does not block
+/// signups.
+async fn ensure_password_not_breached(state: &AppState, password: &str) -> Result<(), AppError> {
+    if !state.config.hibp_enabled {
+        return Ok(());
+    }
+    match breach::is_breached(&state.config.hibp_api_url, password).await {
+        Ok(true) => Err(AppError::Unprocessable(
+            code::PASSWORD_BREACHED,
+            "This password has appeared in a data breach. Please choose a different one."
+                .to_string(),
+        )),
+        Ok(false) => Ok(()),
+        Err(e) => {
+            tracing::warn!("Skipping breached password check: {e:#}");
+            Ok(())
+        }
+    }
 }
 
-/// Generate a unique username from a display name by adding a random suffix.
-fn generate_username_from_name(name: &str) -> String {
+/// Generate a unique username from a display name by adding a random suffix. Names too short
+/// or on the blocklist fall back to `user`.
+fn generate_username_from_name(name: &str, blocklist: &password::UsernameBlocklist) -> String {
     let base: String = name
         .chars()
         .filter(|c| c.is_alphanumeric() || *c == '_')
         .take(40)
         .collect();
-    let base = if base.len() < 3 {
+    let base = if base.len() < 3 || blocklist.blocks(&base) {
         "user".to_string()
     } else {
         base
@@ -300,7 +696,10 @@ async fn oauth_create_new_user(
 
     let now = Utc::now().fixed_offset();
     let user_id = Uuid::new_v4();
-    let username = generate_username_from_name(params.display_name.as_deref().unwrap_or("user"));
+    let username = generate_username_from_name(
+        params.display_name.as_deref().unwrap_or("user"),
+        &state.config.username_blocklist,
+    );
 
     let txn = state
         .db
@@ -315,7 +714,16 @@ async fn oauth_create_new_user(
         display_name: Set(params.display_name.clone()),
         avatar_url: Set(params.avatar_url.clone()),
         bio: Set(None),
+        location: Set(None),
+        website_url: Set(None),
+        pronouns: Set(None),
+        social_links: Set(None),
         email_verified: Set(params.email_verified),
+        verified: Set(false),
+        hide_from_search: Set(false),
+        hide_games: Set(false),
+        hide_activity: Set(false),
+        shadow_banned: Set(false),
         role: Set("user".to_string()),
         subscription_plan: Set("free".to_string()),
         subscription_expires_at: Set(None),
@@ -326,6 +734,8 @@ async fn oauth_create_new_user(
         created_at: Set(now),
         updated_at: Set(now),
         deleted_at: Set(None),
+        deletion_scheduled_at: Set(None),
+        deletion_game_policy: Set(None),
     };
     let user_model = new_user
         .insert(&txn)
@@ -359,8 +769,10 @@ async fn oauth_create_new_user(
 // ─────────────────────────────────────────────────────────────────────────────
 
 /// `POST /api/v1/auth/signup/email`
+#[allow(clippy::too_many_lines)]
 async fn signup_email(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(body): Json<SignupEmailRequest>,
 ) -> Result<Response, AppError> {
     let email = body.email.trim().to_lowercase();
@@ -368,8 +780,11 @@ async fn signup_email(
 
     // Validate input
     password::validate_email(&email).map_err(AppError::BadRequest)?;
-    password::validate_username(&username).map_err(AppError::BadRequest)?;
-    password::validate_password(&body.password).map_err(AppError::BadRequest)?;
+    password::validate_username(&username, &state.config.username_blocklist)
+        .map_err(AppError::BadRequest)?;
+    password::validate_password(&body.password, &state.config.password_policy)
+        .map_err(AppError::BadRequest)?;
+    ensure_password_not_breached(&state, &body.password).await?;
 
     // Check for existing user with same email
     let existing_email = user::Entity::find()
@@ -416,7 +831,16 @@ async fn signup_email(
         display_name: Set(None),
         avatar_url: Set(None),
         bio: Set(None),
+        location: Set(None),
+        website_url: Set(None),
+        pronouns: Set(None),
+        social_links: Set(None),
         email_verified: Set(false),
+        verified: Set(false),
+        hide_from_search: Set(false),
+        hide_games: Set(false),
+        hide_activity: Set(false),
+        shadow_banned: Set(false),
         role: Set("user".to_string()),
         subscription_plan: Set("free".to_string()),
         subscription_expires_at: Set(None),
@@ -427,6 +851,8 @@ async fn signup_email(
         created_at: Set(now),
         updated_at: Set(now),
         deleted_at: Set(None),
+        deletion_scheduled_at: Set(None),
+        deletion_game_policy: Set(None),
     };
     let user_model = new_user
         .insert(&txn)
@@ -440,7 +866,7 @@ async fn signup_email(
         provider_id: Set(email.clone()),
         password_hash: Set(Some(password_hash)),
         provider_email: Set(Some(email.clone())),
-        verification_token: Set(Some(verification_token.clone())),
+        verification_token: Set(Some(hash_verification_token(&verification_token))),
         token_expires_at: Set(Some(token_expires_at.fixed_offset())),
         created_at: Set(now),
     };
@@ -461,16 +887,261 @@ async fn signup_email(
     );
 
     // Generate tokens
-    let token_pair = jwt::generate_token_pair(user_id, &user_model.role, &state.config)?;
-    store_refresh_token(&state.db, user_id, &token_pair).await?;
+    let token_pair = jwt::generate_token_pair(user_id, &user_model.role, &state.jwt_keys)?;
+    store_refresh_token(&state, user_id, &token_pair, &headers, None).await?;
+
+    let response = AuthResponse {
+        user: user_response(&user_model),
+        token: token_pair.access_token,
+        refresh_token: token_pair.refresh_token,
+        csrf_token: String::new(),
+    };
+
+    Ok(response.into_response_for(&state, StatusCode::CREATED))
+}
+
+/// `POST /api/v1/auth/guest`
+///
+/// Creates a guest account and signs it in. Guests have no email or password and can't
+/// publish, but can host sessions and keep drafts; `POST /api/v1/auth/guest/upgrade` turns
+/// them into a full account without losing either.
+async fn guest_signup(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let now = Utc::now().fixed_offset();
+    let user_id = Uuid::new_v4();
+    let simple = user_id.simple().to_string();
+
+    let user_model = user::ActiveModel {
+        id: Set(user_id),
+        email: Set(format!("guest-{simple}@guest.invalid")),
+        username: Set(format!("guest_{}", &simple[..12])),
+        display_name: Set(None),
+        avatar_url: Set(None),
+        bio: Set(None),
+        location: Set(None),
+        website_url: Set(None),
+        pronouns: Set(None),
+        social_links: Set(None),
+        email_verified: Set(false),
+        verified: Set(false),
+        hide_from_search: Set(false),
+        hide_games: Set(false),
+        hide_activity: Set(false),
+        shadow_banned: Set(false),
+        role: Set("guest".to_string()),
+        subscription_plan: Set("free".to_string()),
+        subscription_expires_at: Set(None),
+        account_status: Set("active".to_string()),
+        suspension_reason: Set(None),
+        last_login_at: Set(Some(now)),
+        last_login_ip: Set(extract_client_ip(&headers)),
+        created_at: Set(now),
+        updated_at: Set(now),
+        deleted_at: Set(None),
+        deletion_scheduled_at: Set(None),
+        deletion_game_policy: Set(None),
+    }
+    .insert(&state.db)
+    .await
+    .map_err(|e| AppError::Internal(e.into()))?;
+
+    let token_pair = jwt::generate_token_pair(user_id, &user_model.role, &state.jwt_keys)?;
+    store_refresh_token(&state, user_id, &token_pair, &headers, None).await?;
 
     let response = AuthResponse {
         user: user_response(&user_model),
         token: token_pair.access_token,
         refresh_token: token_pair.refresh_token,
+        csrf_token: String::new(),
     };
 
-    Ok((StatusCode::CREATED, Json(response)).into_response())
+    Ok(response.into_response_for(&state, StatusCode::CREATED))
+}
+
+/// `POST /api/v1/auth/guest/upgrade`
+///
+/// Turns the signed-in guest into a full account by attaching either an email and password or
+/// an `OAuth` provider (`provider` + `code`, as for `POST /api/v1/auth/link/{provider}`). The
+/// account keeps its id, so its games and sessions carry over. Returns fresh tokens.
+async fn guest_upgrade(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+    headers: HeaderMap,
+    Json(body): Json<GuestUpgradeRequest>,
+) -> Result<Response, AppError> {
+    if user_model.role != "guest" {
+        return Err(AppError::Conflict(
+            "Account is not a guest account.".to_string(),
+        ));
+    }
+
+    let username = match body.username.as_deref().map(str::trim) {
+        Some(username) => {
+            password::validate_username(username, &state.config.username_blocklist)
+                .map_err(AppError::BadRequest)?;
+            let taken = user::Entity::find()
+                .filter(user::Column::Username.eq(username))
+                .one(&state.db)
+                .await
+                .map_err(|e| AppError::Internal(e.into()))?;
+            if taken.is_some() {
+                return Err(AppError::Conflict("Username already taken.".to_string()));
+            }
+            Some(username.to_string())
+        }
+        None => None,
+    };
+
+    let upgrade = guest_credentials(&state, user_model.id, body).await?;
+
+    let txn = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    upgrade
+        .provider
+        .insert(&txn)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    let mut active_user: user::ActiveModel = user_model.into();
+    active_user.email = Set(upgrade.email.clone());
+    active_user.email_verified = Set(upgrade.email_verified);
+    active_user.role = Set("user".to_string());
+    if let Some(username) = username {
+        active_user.username = Set(username);
+    }
+    active_user.updated_at = Set(Utc::now().fixed_offset());
+    let user_model = active_user
+        .update(&txn)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    if let Some(verification_token) = upgrade.verification_token {
+        tracing::info!(
+            email = %upgrade.email,
+            token = %verification_token,
+            "Email verification token generated (email sending not yet implemented)"
+        );
+    }
+
+    let token_pair = jwt::generate_token_pair(user_model.id, &user_model.role, &state.jwt_keys)?;
+    store_refresh_token(&state, user_model.id, &token_pair, &headers, None).await?;
+
+    Ok(AuthResponse {
+        user: user_response(&user_model),
+        token: token_pair.access_token,
+        refresh_token: token_pair.refresh_token,
+        csrf_token: String::new(),
+    }
+    .into_response_for(&state, StatusCode::OK))
+}
+
+/// What a guest upgrade attaches to the account.
+struct GuestCredentials {
+    provider: auth_provider::ActiveModel,
+    email: String,
+    email_verified: bool,
+    /// Set when the email still has to be verified.
+    verification_token: Option<String>,
+}
+
+/// Check the credentials in a guest upgrade request and build the auth provider to attach.
+async fn guest_credentials(
+    state: &AppState,
+    user_id: Uuid,
+    body: GuestUpgradeRequest,
+) -> Result<GuestCredentials, AppError> {
+    let now = Utc::now().fixed_offset();
+
+    let credentials = match (body.email, body.password, body.provider, body.code) {
+        (Some(email), Some(new_password), None, None) => {
+            let email = email.trim().to_lowercase();
+            password::validate_email(&email).map_err(AppError::BadRequest)?;
+            password::validate_password(&new_password, &state.config.password_policy)
+                .map_err(AppError::BadRequest)?;
+            ensure_password_not_breached(state, &new_password).await?;
+
+            let verification_token = generate_verification_token();
+            let token_expires_at = Utc::now() + chrono::Duration::hours(24);
+            GuestCredentials {
+                provider: auth_provider::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    user_id: Set(user_id),
+                    provider: Set("email".to_string()),
+                    provider_id: Set(email.clone()),
+                    password_hash: Set(Some(password::hash_password(&new_password)?)),
+                    provider_email: Set(Some(email.clone())),
+                    verification_token: Set(Some(hash_verification_token(&verification_token))),
+                    token_expires_at: Set(Some(token_expires_at.fixed_offset())),
+                    created_at: Set(now),
+                },
+                email,
+                email_verified: false,
+                verification_token: Some(verification_token),
+            }
+        }
+        (None, None, Some(provider), Some(code)) => {
+            let (provider_id, provider_email) = linked_identity(state, &provider, code).await?;
+            let email = provider_email
+                .map(|e| e.trim().to_lowercase())
+                .ok_or_else(|| {
+                    AppError::BadRequest(format!("{provider} did not share an email address."))
+                })?;
+
+            let linked = auth_provider::Entity::find()
+                .filter(auth_provider::Column::ProviderId.eq(&provider_id))
+                .one(&state.db)
+                .await
+                .map_err(|e| AppError::Internal(e.into()))?;
+            if linked.is_some() {
+                return Err(AppError::Conflict(
+                    "This provider account is linked to a different user.".to_string(),
+                ));
+            }
+
+            GuestCredentials {
+                provider: auth_provider::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    user_id: Set(user_id),
+                    provider: Set(provider),
+                    provider_id: Set(provider_id),
+                    password_hash: Set(None),
+                    provider_email: Set(Some(email.clone())),
+                    verification_token: Set(None),
+                    token_expires_at: Set(None),
+                    created_at: Set(now),
+                },
+                email,
+                email_verified: true,
+                verification_token: None,
+            }
+        }
+        _ => {
+            return Err(AppError::BadRequest(
+                "Provide either email and password, or provider and code.".to_string(),
+            ));
+        }
+    };
+
+    let existing_email = user::Entity::find()
+        .filter(user::Column::Email.eq(&credentials.email))
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+    if existing_email.is_some() {
+        return Err(AppError::Conflict("Email already registered.".to_string()));
+    }
+
+    Ok(credentials)
 }
 
 /// `POST /api/v1/auth/signin/email`
@@ -478,68 +1149,302 @@ async fn signin_email(
     State(state): State<AppState>,
     headers: HeaderMap,
     Json(body): Json<SigninEmailRequest>,
-) -> Result<Json<AuthResponse>, AppError> {
+) -> Result<Response, AppError> {
     let email = body.email.trim().to_lowercase();
+    let attempt = login_events::Attempt::new("email", &headers).identified_by(&email);
 
     // Find user by email
-    let user_model = user::Entity::find()
+    let Some(user_model) = user::Entity::find()
         .filter(user::Column::Email.eq(&email))
         .filter(user::Column::DeletedAt.is_null())
         .one(&state.db)
         .await
         .map_err(|e| AppError::Internal(e.into()))?
-        .ok_or_else(|| AppError::Unauthorized("Invalid email or password.".to_string()))?;
+    else {
+        attempt.record(&state.db, None, "unknown_account").await;
+        return Err(AppError::Unauthorized(
+            "Invalid email or password.".to_string(),
+        ));
+    };
 
     // Check account status
     if user_model.account_status == "suspended" {
+        attempt
+            .record(&state.db, Some(user_model.id), "suspended")
+            .await;
         return Err(AppError::Forbidden("Account is suspended.".to_string()));
     }
     if user_model.account_status == "deactivated" {
+        attempt
+            .record(&state.db, Some(user_model.id), "deactivated")
+            .await;
         return Err(AppError::Forbidden("Account is deactivated.".to_string()));
     }
 
-    // Find email auth provider
-    let provider = auth_provider::Entity::find()
-        .filter(auth_provider::Column::UserId.eq(user_model.id))
-        .filter(auth_provider::Column::Provider.eq("email"))
+    // Find email auth provider and verify password
+    let provider = auth_provider::Entity::find()
+        .filter(auth_provider::Column::UserId.eq(user_model.id))
+        .filter(auth_provider::Column::Provider.eq("email"))
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+    let valid = match provider.as_ref().and_then(|p| p.password_hash.as_deref()) {
+        Some(hash) => password::verify_password(&body.password, hash)?,
+        None => false,
+    };
+    if !valid {
+        attempt
+            .record(&state.db, Some(user_model.id), "invalid_credentials")
+            .await;
+        return Err(AppError::Unauthorized(
+            "Invalid email or password.".to_string(),
+        ));
+    }
+
+    // Accounts with two-factor authentication finish at /2fa/verify
+    if let Some(challenge_token) = two_factor_challenge(&state, user_model.id).await? {
+        attempt
+            .record(&state.db, Some(user_model.id), "two_factor_required")
+            .await;
+        return Ok(Json(TwoFactorChallengeResponse {
+            two_factor_required: true,
+            challenge_token,
+        })
+        .into_response());
+    }
+
+    Ok(complete_signin(&state, &attempt, user_model)
+        .await?
+        .into_response_for(&state, StatusCode::OK))
+}
+
+/// `POST /api/v1/auth/signin/classroom` — Sign a managed student account in with its class
+/// code, username and the password its teacher handed out.
+async fn signin_classroom(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<SigninClassroomRequest>,
+) -> Result<Response, AppError> {
+    let username = body.username.trim().to_lowercase();
+    let attempt = login_events::Attempt::new(STUDENT_PROVIDER, &headers).identified_by(&username);
+    let invalid =
+        || AppError::Unauthorized("Invalid class code, username or password.".to_string());
+
+    let class = classroom::Entity::find()
+        .filter(classroom::Column::Code.eq(body.class_code.trim().to_uppercase()))
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+    let user_model = user::Entity::find()
+        .filter(user::Column::Username.eq(&username))
+        .filter(user::Column::DeletedAt.is_null())
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+    let membership = match &user_model {
+        Some(u) => classroom_student::Entity::find_by_id(u.id)
+            .one(&state.db)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?,
+        None => None,
+    };
+    let in_class = class
+        .zip(membership)
+        .is_some_and(|(class, m)| m.classroom_id == class.id);
+    let Some(user_model) = user_model.filter(|_| in_class) else {
+        attempt.record(&state.db, None, "unknown_account").await;
+        return Err(invalid());
+    };
+
+    if user_model.account_status == "suspended" {
+        attempt
+            .record(&state.db, Some(user_model.id), "suspended")
+            .await;
+        return Err(AppError::Forbidden("Account is suspended.".to_string()));
+    }
+
+    let provider = auth_provider::Entity::find()
+        .filter(auth_provider::Column::UserId.eq(user_model.id))
+        .filter(auth_provider::Column::Provider.eq(STUDENT_PROVIDER))
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+    let valid = match provider.as_ref().and_then(|p| p.password_hash.as_deref()) {
+        Some(hash) => password::verify_password(&body.password, hash)?,
+        None => false,
+    };
+    if !valid {
+        attempt
+            .record(&state.db, Some(user_model.id), "invalid_credentials")
+            .await;
+        return Err(invalid());
+    }
+
+    Ok(complete_signin(&state, &attempt, user_model)
+        .await?
+        .into_response_for(&state, StatusCode::OK))
+}
+
+/// `POST /api/v1/auth/2fa/verify` — Second sign-in step: exchange a challenge token and a
+/// TOTP code for the token pair.
+async fn two_factor_verify(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<TwoFactorVerifyRequest>,
+) -> Result<Response, AppError> {
+    let user_id = jwt::validate_two_factor_challenge(&body.challenge_token, &state.jwt_keys)
+        .map_err(|_| AppError::Unauthorized("Invalid or expired challenge.".to_string()))?;
+    let attempt = login_events::Attempt::new("totp", &headers);
+
+    let user_model = user::Entity::find_by_id(user_id)
+        .filter(user::Column::DeletedAt.is_null())
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?
+        .ok_or_else(|| AppError::Unauthorized("Invalid or expired challenge.".to_string()))?;
+
+    if user_model.account_status == "suspended" {
+        return Err(AppError::Forbidden("Account is suspended.".to_string()));
+    }
+    if user_model.account_status == "deactivated" {
+        return Err(AppError::Forbidden("Account is deactivated.".to_string()));
+    }
+
+    let enrollment = user_totp::Entity::find_by_id(user_id)
+        .filter(user_totp::Column::ConfirmedAt.is_not_null())
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?
+        .ok_or_else(|| AppError::Unauthorized("Invalid or expired challenge.".to_string()))?;
+
+    if !reserve_two_factor_attempt(&state.db, user_id).await? {
+        attempt
+            .record(&state.db, Some(user_id), "two_factor_locked")
+            .await;
+        return Err(AppError::TooManyRequests(
+            "Too many invalid two-factor codes. Sign in again in a few minutes.".to_string(),
+        ));
+    }
+    if !consume_totp_code(&state.db, &enrollment, &body.code).await? {
+        attempt
+            .record(&state.db, Some(user_id), "invalid_two_factor")
+            .await;
+        return Err(AppError::Unauthorized(
+            "Invalid two-factor code.".to_string(),
+        ));
+    }
+
+    Ok(complete_signin(&state, &attempt, user_model)
+        .await?
+        .into_response_for(&state, StatusCode::OK))
+}
+
+/// `POST /api/v1/auth/2fa/totp/setup` — Start TOTP enrollment with a fresh secret.
+///
+/// Two-factor sign-in is only enforced once a code is confirmed; calling this again before
+/// then replaces the pending secret.
+async fn totp_setup(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+) -> Result<Json<TotpSetupResponse>, AppError> {
+    let existing = user_totp::Entity::find_by_id(user_model.id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+    if existing.as_ref().is_some_and(|t| t.confirmed_at.is_some()) {
+        return Err(AppError::Conflict(
+            "Two-factor authentication is already enabled.".to_string(),
+        ));
+    }
+
+    let secret = totp::generate_secret();
+    let enrollment = user_totp::ActiveModel {
+        user_id: Set(user_model.id),
+        created_at: Set(Utc::now().fixed_offset()),
+        secret: Set(secret.clone()),
+        confirmed_at: Set(None),
+        last_used_step: Set(None),
+        failed_attempts: Set(0),
+        last_failed_at: Set(None),
+    };
+    if existing.is_some() {
+        enrollment.update(&state.db).await
+    } else {
+        enrollment.insert(&state.db).await
+    }
+    .map_err(|e| AppError::Internal(e.into()))?;
+
+    Ok(Json(TotpSetupResponse {
+        provisioning_uri: totp::provisioning_uri(&secret, &user_model.email),
+        secret,
+    }))
+}
+
+/// `POST /api/v1/auth/2fa/totp/confirm` — Finish enrollment with a code from the
+/// authenticator app.
+async fn totp_confirm(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+    Json(body): Json<TotpCodeRequest>,
+) -> Result<Json<MessageResponse>, AppError> {
+    let enrollment = user_totp::Entity::find_by_id(user_model.id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?
+        .ok_or_else(|| {
+            AppError::BadRequest("Two-factor setup has not been started.".to_string())
+        })?;
+    if enrollment.confirmed_at.is_some() {
+        return Err(AppError::Conflict(
+            "Two-factor authentication is already enabled.".to_string(),
+        ));
+    }
+
+    if !consume_totp_code(&state.db, &enrollment, &body.code).await? {
+        return Err(AppError::BadRequest("Invalid two-factor code.".to_string()));
+    }
+
+    user_totp::Entity::update_many()
+        .col_expr(
+            user_totp::Column::ConfirmedAt,
+            sea_orm::sea_query::Expr::value(Utc::now().fixed_offset()),
+        )
+        .filter(user_totp::Column::UserId.eq(user_model.id))
+        .exec(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    Ok(Json(MessageResponse {
+        message: "Two-factor authentication enabled.".to_string(),
+    }))
+}
+
+/// `DELETE /api/v1/auth/2fa/totp` — Turn off two-factor authentication; requires a current
+/// code.
+async fn totp_disable(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+    Json(body): Json<TotpCodeRequest>,
+) -> Result<StatusCode, AppError> {
+    let enrollment = user_totp::Entity::find_by_id(user_model.id)
+        .filter(user_totp::Column::ConfirmedAt.is_not_null())
         .one(&state.db)
         .await
         .map_err(|e| AppError::Internal(e.into()))?
-        .ok_or_else(|| AppError::Unauthorized("Invalid email or password.".to_string()))?;
+        .ok_or_else(|| {
+            AppError::NotFound("Two-factor authentication is not enabled.".to_string())
+        })?;
 
-    // Verify password
-    let hash = provider
-        .password_hash
-        .as_deref()
-        .ok_or_else(|| AppError::Unauthorized("Invalid email or password.".to_string()))?;
-    let valid = password::verify_password(&body.password, hash)?;
-    if !valid {
-        return Err(AppError::Unauthorized(
-            "Invalid email or password.".to_string(),
-        ));
+    if !consume_totp_code(&state.db, &enrollment, &body.code).await? {
+        return Err(AppError::BadRequest("Invalid two-factor code.".to_string()));
     }
 
-    // Update last login info
-    let client_ip = extract_client_ip(&headers);
-    let now = Utc::now().fixed_offset();
-    let mut active_user: user::ActiveModel = user_model.clone().into();
-    active_user.last_login_at = Set(Some(now));
-    active_user.last_login_ip = Set(client_ip);
-    active_user.updated_at = Set(now);
-    let user_model = active_user
-        .update(&state.db)
+    user_totp::Entity::delete_by_id(user_model.id)
+        .exec(&state.db)
         .await
         .map_err(|e| AppError::Internal(e.into()))?;
 
-    // Generate tokens
-    let token_pair = jwt::generate_token_pair(user_model.id, &user_model.role, &state.config)?;
-    store_refresh_token(&state.db, user_model.id, &token_pair).await?;
-
-    Ok(Json(AuthResponse {
-        user: user_response(&user_model),
-        token: token_pair.access_token,
-        refresh_token: token_pair.refresh_token,
-    }))
+    Ok(StatusCode::NO_CONTENT)
 }
 
 /// `POST /api/v1/auth/verify-email`
@@ -549,7 +1454,7 @@ async fn verify_email(
 ) -> Result<Json<MessageResponse>, AppError> {
     // Find auth provider by verification token
     let provider = auth_provider::Entity::find()
-        .filter(auth_provider::Column::VerificationToken.eq(&body.token))
+        .filter(auth_provider::Column::VerificationToken.eq(hash_verification_token(&body.token)))
         .one(&state.db)
         .await
         .map_err(|e| AppError::Internal(e.into()))?
@@ -594,6 +1499,120 @@ async fn verify_email(
     }))
 }
 
+/// `POST /api/v1/auth/email-change/confirm`
+///
+/// Applies an email change staged by `PATCH /api/v1/users/me/email`, using the token sent to the
+/// account's current address. The verification token already sent to the new address then
+/// verifies it through `POST /api/v1/auth/verify-email`.
+async fn email_change_confirm(
+    State(state): State<AppState>,
+    Json(body): Json<VerifyEmailRequest>,
+) -> Result<Json<MessageResponse>, AppError> {
+    let change = email_change::Entity::find()
+        .filter(email_change::Column::ConfirmationToken.eq(hash_verification_token(&body.token)))
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?
+        .ok_or_else(|| AppError::BadRequest("Invalid confirmation token.".to_string()))?;
+
+    let now = Utc::now().fixed_offset();
+    if change.expires_at < now {
+        email_change::Entity::delete_by_id(change.id)
+            .exec(&state.db)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+        return Err(AppError::BadRequest(
+            "Confirmation token has expired.".to_string(),
+        ));
+    }
+
+    // The address may have been taken while the change was pending
+    let taken = user::Entity::find()
+        .filter(user::Column::Email.eq(&change.new_email))
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+    if taken.is_some() {
+        email_change::Entity::delete_by_id(change.id)
+            .exec(&state.db)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+        return Err(AppError::Conflict("Email is already in use.".to_string()));
+    }
+
+    let user_model = user::Entity::find_by_id(change.user_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?
+        .ok_or_else(|| AppError::NotFound("User not found.".to_string()))?;
+
+    let txn = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    let mut active_user: user::ActiveModel = user_model.into();
+    active_user.email = Set(change.new_email.clone());
+    active_user.email_verified = Set(false);
+    active_user.updated_at = Set(now);
+    active_user
+        .update(&txn)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    let email_provider = auth_provider::Entity::find()
+        .filter(auth_provider::Column::UserId.eq(change.user_id))
+        .filter(auth_provider::Column::Provider.eq("email"))
+        .one(&txn)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+    if let Some(provider) = email_provider {
+        let token_expires_at = Utc::now() + chrono::Duration::hours(24);
+        let mut active_provider: auth_provider::ActiveModel = provider.into();
+        active_provider.provider_id = Set(change.new_email.clone());
+        active_provider.provider_email = Set(Some(change.new_email.clone()));
+        active_provider.verification_token = Set(Some(change.verification_token.clone()));
+        active_provider.token_expires_at = Set(Some(token_expires_at.fixed_offset()));
+        active_provider
+            .update(&txn)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+    }
+
+    email_change::Entity::delete_by_id(change.id)
+        .exec(&txn)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    Ok(Json(MessageResponse {
+        message: "Email changed. Verify the new address using the link sent to it.".to_string(),
+    }))
+}
+
+/// `POST /api/v1/auth/not-me`
+///
+/// Target of the "this wasn't me" link in a new-device sign-in alert: signs that device out.
+/// Works without a session, since the owner may have been locked out of theirs.
+async fn not_me(
+    State(state): State<AppState>,
+    Json(body): Json<VerifyEmailRequest>,
+) -> Result<Json<MessageResponse>, AppError> {
+    if !devices::revoke_unrecognized(&state.db, &body.token).await? {
+        return Err(AppError::BadRequest(
+            "Invalid, used or expired link.".to_string(),
+        ));
+    }
+
+    Ok(Json(MessageResponse {
+        message: "The device has been signed out. Change your password to keep it out.".to_string(),
+    }))
+}
+
 /// `POST /api/v1/auth/resend-verification`
 async fn resend_verification(
     State(state): State<AppState>,
@@ -619,7 +1638,7 @@ async fn resend_verification(
     let token_expires_at = Utc::now() + chrono::Duration::hours(24);
 
     let mut active_provider: auth_provider::ActiveModel = provider.into();
-    active_provider.verification_token = Set(Some(verification_token.clone()));
+    active_provider.verification_token = Set(Some(hash_verification_token(&verification_token)));
     active_provider.token_expires_at = Set(Some(token_expires_at.fixed_offset()));
     active_provider
         .update(&state.db)
@@ -666,7 +1685,7 @@ async fn password_reset_request(
             let token_expires_at = Utc::now() + chrono::Duration::hours(1);
 
             let mut active_provider: auth_provider::ActiveModel = provider.into();
-            active_provider.verification_token = Set(Some(reset_token.clone()));
+            active_provider.verification_token = Set(Some(hash_verification_token(&reset_token)));
             active_provider.token_expires_at = Set(Some(token_expires_at.fixed_offset()));
             active_provider
                 .update(&state.db)
@@ -693,7 +1712,7 @@ async fn password_reset_confirm(
 ) -> Result<Json<MessageResponse>, AppError> {
     // Find auth provider by token
     let provider = auth_provider::Entity::find()
-        .filter(auth_provider::Column::VerificationToken.eq(&body.token))
+        .filter(auth_provider::Column::VerificationToken.eq(hash_verification_token(&body.token)))
         .filter(auth_provider::Column::Provider.eq("email"))
         .one(&state.db)
         .await
@@ -712,7 +1731,9 @@ async fn password_reset_confirm(
     }
 
     // Validate new password
-    password::validate_password(&body.new_password).map_err(AppError::BadRequest)?;
+    password::validate_password(&body.new_password, &state.config.password_policy)
+        .map_err(AppError::BadRequest)?;
+    ensure_password_not_breached(&state, &body.new_password).await?;
 
     // Hash and update
     let new_hash = password::hash_password(&body.new_password)?;
@@ -758,7 +1779,9 @@ async fn password_change(
     }
 
     // Validate and hash new password
-    password::validate_password(&body.new_password).map_err(AppError::BadRequest)?;
+    password::validate_password(&body.new_password, &state.config.password_policy)
+        .map_err(AppError::BadRequest)?;
+    ensure_password_not_breached(&state, &body.new_password).await?;
     let new_hash = password::hash_password(&body.new_password)?;
 
     let mut active_provider: auth_provider::ActiveModel = provider.into();
@@ -786,7 +1809,7 @@ async fn oauth_google_initiate(
 
     let client = oauth::google_client(&state.config)?;
     let state_token =
-        jwt::generate_oauth_state(&state.config.jwt_secret, query.redirect_uri.as_deref())?;
+        jwt::generate_oauth_state(&state.jwt_keys, query.redirect_uri.as_deref(), None)?;
 
     let (auth_url, _csrf) = client
         .authorize_url(|| CsrfToken::new(state_token))
@@ -804,7 +1827,7 @@ async fn oauth_google_callback(
     headers: HeaderMap,
     Query(query): Query<OAuthCallbackQuery>,
 ) -> Result<Response, AppError> {
-    let state_claims = jwt::validate_oauth_state(&query.state, &state.config.jwt_secret)
+    let state_claims = jwt::validate_oauth_state(&query.state, &state.jwt_keys)
         .map_err(|_| AppError::BadRequest("Invalid or expired OAuth state.".to_string()))?;
 
     let client = oauth::google_client(&state.config)?;
@@ -831,31 +1854,14 @@ async fn oauth_google_callback(
     )
     .await?;
 
-    let token_pair = jwt::generate_token_pair(user_model.id, &user_model.role, &state.config)?;
-    store_refresh_token(&state.db, user_model.id, &token_pair).await?;
-
-    let auth_response = AuthResponse {
-        user: user_response(&user_model),
-        token: token_pair.access_token,
-        refresh_token: token_pair.refresh_token,
-    };
-
-    // If redirect_uri was provided, redirect to frontend with auth data
-    if let Some(redirect_uri) = state_claims.redirect_uri {
-        let user_json =
-            serde_json::to_string(&auth_response.user).unwrap_or_else(|_| "{}".to_string());
-        let redirect_url = format!(
-            "{}?provider=google&token={}&refreshToken={}&user={}",
-            redirect_uri,
-            urlencoding::encode(&auth_response.token),
-            urlencoding::encode(&auth_response.refresh_token),
-            urlencoding::encode(&user_json)
-        );
-        return Ok(Redirect::to(&redirect_url).into_response());
-    }
-
-    // Fallback: return JSON for API clients
-    Ok(Json(auth_response).into_response())
+    finish_oauth_signin(
+        &state,
+        &headers,
+        user_model,
+        state_claims.redirect_uri,
+        "google",
+    )
+    .await
 }
 
 /// `GET /api/v1/auth/oauth/github`
@@ -871,7 +1877,7 @@ async fn oauth_github_initiate(
 
     let client = oauth::github_client(&state.config)?;
     let state_token =
-        jwt::generate_oauth_state(&state.config.jwt_secret, query.redirect_uri.as_deref())?;
+        jwt::generate_oauth_state(&state.jwt_keys, query.redirect_uri.as_deref(), None)?;
 
     let (auth_url, _csrf) = client
         .authorize_url(|| CsrfToken::new(state_token))
@@ -887,7 +1893,7 @@ async fn oauth_github_callback(
     headers: HeaderMap,
     Query(query): Query<OAuthCallbackQuery>,
 ) -> Result<Response, AppError> {
-    let state_claims = jwt::validate_oauth_state(&query.state, &state.config.jwt_secret)
+    let state_claims = jwt::validate_oauth_state(&query.state, &state.jwt_keys)
         .map_err(|_| AppError::BadRequest("Invalid or expired OAuth state.".to_string()))?;
 
     let client = oauth::github_client(&state.config)?;
@@ -921,63 +1927,215 @@ async fn oauth_github_callback(
     )
     .await?;
 
-    let token_pair = jwt::generate_token_pair(user_model.id, &user_model.role, &state.config)?;
-    store_refresh_token(&state.db, user_model.id, &token_pair).await?;
-
-    let auth_response = AuthResponse {
-        user: user_response(&user_model),
-        token: token_pair.access_token,
-        refresh_token: token_pair.refresh_token,
-    };
+    finish_oauth_signin(
+        &state,
+        &headers,
+        user_model,
+        state_claims.redirect_uri,
+        "github",
+    )
+    .await
+}
 
-    // If redirect_uri was provided, redirect to frontend with auth data
-    if let Some(redirect_uri) = state_claims.redirect_uri {
-        let user_json =
-            serde_json::to_string(&auth_response.user).unwrap_or_else(|_| "{}".to_string());
-        let redirect_url = format!(
-            "{}?provider=github&token={}&refreshToken={}&user={}",
-            redirect_uri,
-            urlencoding::encode(&auth_response.token),
-            urlencoding::encode(&auth_response.refresh_token),
-            urlencoding::encode(&user_json)
-        );
-        return Ok(Redirect::to(&redirect_url).into_response());
+/// `GET /api/v1/auth/oauth/apple`
+async fn oauth_apple_initiate(
+    State(state): State<AppState>,
+    Query(query): Query<OAuthInitiateQuery>,
+) -> Result<Response, AppError> {
+    if !oauth::apple_configured(&state.config) {
+        return Err(AppError::UnprocessableEntity(
+            "Apple sign-in is not configured.".to_string(),
+        ));
     }
 
-    // Fallback: return JSON for API clients
-    Ok(Json(auth_response).into_response())
+    let state_token =
+        jwt::generate_oauth_state(&state.jwt_keys, query.redirect_uri.as_deref(), None)?;
+    Ok(Redirect::to(&oauth::apple_authorize_url(&state.config, &state_token)).into_response())
 }
 
-/// `POST /api/v1/auth/link/{provider}`
-async fn link_provider(
+/// `POST /api/v1/auth/oauth/apple/callback`
+///
+/// Apple only shares the user's name on their first authorization, so it is captured here
+/// when the account is created.
+async fn oauth_apple_callback(
     State(state): State<AppState>,
-    AuthUser(user_model): AuthUser,
-    Path(provider): Path<String>,
-    Json(body): Json<LinkProviderRequest>,
+    headers: HeaderMap,
+    Form(form): Form<AppleCallbackForm>,
 ) -> Result<Response, AppError> {
-    if provider != "google" && provider != "github" {
+    let state_claims = jwt::validate_oauth_state(&form.state, &state.jwt_keys)
+        .map_err(|_| AppError::BadRequest("Invalid or expired OAuth state.".to_string()))?;
+
+    if let Some(error) = form.error {
         return Err(AppError::BadRequest(format!(
-            "Unsupported provider: {provider}"
+            "Apple sign-in failed: {error}"
         )));
     }
+    let code = form
+        .code
+        .ok_or_else(|| AppError::BadRequest("Missing authorization code.".to_string()))?;
+
+    let claims = oauth::verify_apple_code(&state.config, &code)
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Failed to verify Apple sign-in: {e}")))?;
+    let apple_user = form
+        .user
+        .as_deref()
+        .and_then(|user| serde_json::from_str::<oauth::AppleUser>(user).ok());
+
+    let email_verified = claims.email_verified();
+    let email = claims
+        .email
+        .or_else(|| apple_user.as_ref().and_then(|u| u.email.clone()))
+        .ok_or_else(|| AppError::BadRequest("Apple did not share an email address.".to_string()))?;
+
+    let user_model = oauth_find_or_create_user(
+        &state,
+        &headers,
+        OAuthUserParams {
+            provider_name: "apple".to_string(),
+            provider_id: claims.sub,
+            email: email.to_lowercase(),
+            email_verified,
+            display_name: apple_user.as_ref().and_then(oauth::AppleUser::full_name),
+            avatar_url: None,
+        },
+    )
+    .await?;
+
+    finish_oauth_signin(
+        &state,
+        &headers,
+        user_model,
+        state_claims.redirect_uri,
+        "apple",
+    )
+    .await
+}
+
+/// `GET /api/v1/auth/password-policy` — The rules new passwords must follow, so frontends can
+/// validate as the user types. The banned list is not included.
+async fn password_policy(State(state): State<AppState>) -> Json<password::PasswordPolicy> {
+    Json(state.config.password_policy)
+}
+
+/// `GET /api/v1/auth/oidc` — The configured `OpenID` Connect providers, for sign-in buttons.
+async fn oidc_list_providers(State(state): State<AppState>) -> Json<Vec<OidcProviderResponse>> {
+    Json(
+        state
+            .config
+            .oidc_providers
+            .iter()
+            .map(|p| OidcProviderResponse {
+                name: p.name.clone(),
+                display_name: p.display_name.clone(),
+            })
+            .collect(),
+    )
+}
+
+/// `GET /api/v1/auth/oidc/{provider}`
+async fn oidc_initiate(
+    State(state): State<AppState>,
+    Path(provider): Path<String>,
+    Query(query): Query<OAuthInitiateQuery>,
+) -> Result<Response, AppError> {
+    let provider = oidc::find_provider(&state.config, &provider)
+        .ok_or_else(|| AppError::NotFound(format!("Unknown sign-in provider: {provider}")))?;
+
+    let document = oidc::discover(provider).await?;
+    // Ties the ID token to this sign-in, so one issued for another cannot be replayed here
+    let nonce = Uuid::new_v4().to_string();
+    let state_token =
+        jwt::generate_oauth_state(&state.jwt_keys, query.redirect_uri.as_deref(), Some(&nonce))?;
+
+    Ok(Redirect::to(&oidc::authorize_url(
+        provider,
+        &document,
+        &state_token,
+        &nonce,
+    )?)
+    .into_response())
+}
+
+/// `GET /api/v1/auth/oidc/{provider}/callback`
+async fn oidc_callback(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(provider): Path<String>,
+    Query(query): Query<OAuthCallbackQuery>,
+) -> Result<Response, AppError> {
+    let provider = oidc::find_provider(&state.config, &provider)
+        .ok_or_else(|| AppError::NotFound(format!("Unknown sign-in provider: {provider}")))?;
+    let state_claims = jwt::validate_oauth_state(&query.state, &state.jwt_keys)
+        .map_err(|_| AppError::BadRequest("Invalid or expired OAuth state.".to_string()))?;
+
+    let nonce = state_claims
+        .nonce
+        .as_deref()
+        .ok_or_else(|| AppError::BadRequest("Invalid or expired OAuth state.".to_string()))?;
+    let claims = oidc::verify_code(provider, &query.code, Some(nonce))
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Failed to verify sign-in: {e}")))?;
+    let email = claims.email.ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "{} did not share an email address.",
+            provider.display_name
+        ))
+    })?;
+
+    let user_model = oauth_find_or_create_user(
+        &state,
+        &headers,
+        OAuthUserParams {
+            provider_name: provider.name.clone(),
+            provider_id: claims.sub,
+            email: email.to_lowercase(),
+            email_verified: claims.email_verified.unwrap_or(false),
+            display_name: claims.name.or(claims.preferred_username),
+            avatar_url: claims.picture,
+        },
+    )
+    .await?;
+
+    finish_oauth_signin(
+        &state,
+        &headers,
+        user_model,
+        state_claims.redirect_uri,
+        &provider.name,
+    )
+    .await
+}
+
+/// Exchange an authorization code from `provider` for the account's provider ID and email.
+async fn linked_identity(
+    state: &AppState,
+    provider: &str,
+    code: String,
+) -> Result<(String, Option<String>), AppError> {
+    if let Some(oidc_provider) = oidc::find_provider(&state.config, provider) {
+        let claims = oidc::verify_code(oidc_provider, &code, None)
+            .await
+            .map_err(|e| AppError::BadRequest(format!("Invalid authorization code: {e}")))?;
+        return Ok((claims.sub, claims.email));
+    }
 
-    // Exchange code and get user info based on provider
-    let (provider_id, provider_email) = match provider.as_str() {
+    match provider {
         "google" => {
             let client = oauth::google_client(&state.config)?;
             let token_result = client
-                .exchange_code(AuthorizationCode::new(body.code))
+                .exchange_code(AuthorizationCode::new(code))
                 .request_async(&reqwest::Client::new())
                 .await
                 .map_err(|e| AppError::BadRequest(format!("Invalid authorization code: {e}")))?;
             let access_token = token_result.access_token().secret().clone();
             let info = oauth::fetch_google_userinfo(&access_token).await?;
-            (info.sub, Some(info.email))
+            Ok((info.sub, Some(info.email)))
         }
         "github" => {
             let client = oauth::github_client(&state.config)?;
             let token_result = client
-                .exchange_code(AuthorizationCode::new(body.code))
+                .exchange_code(AuthorizationCode::new(code))
                 .request_async(&reqwest::Client::new())
                 .await
                 .map_err(|e| AppError::BadRequest(format!("Invalid authorization code: {e}")))?;
@@ -988,10 +2146,28 @@ async fn link_provider(
             } else {
                 oauth::fetch_github_primary_email(&access_token).await.ok()
             };
-            (info.id.to_string(), email)
+            Ok((info.id.to_string(), email))
         }
-        _ => return Err(AppError::BadRequest("Unsupported provider.".to_string())),
-    };
+        "apple" => {
+            let claims = oauth::verify_apple_code(&state.config, &code)
+                .await
+                .map_err(|e| AppError::BadRequest(format!("Invalid authorization code: {e}")))?;
+            Ok((claims.sub, claims.email))
+        }
+        _ => Err(AppError::BadRequest(format!(
+            "Unsupported provider: {provider}"
+        ))),
+    }
+}
+
+/// `POST /api/v1/auth/link/{provider}`
+async fn link_provider(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+    Path(provider): Path<String>,
+    Json(body): Json<LinkProviderRequest>,
+) -> Result<Response, AppError> {
+    let (provider_id, provider_email) = linked_identity(&state, &provider, body.code).await?;
 
     // Check if provider_id is already linked to another user
     let existing = auth_provider::Entity::find()
@@ -1089,13 +2265,32 @@ async fn unlink_provider(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// The refresh token from the request body, or in cookie mode from its cookie.
+fn request_refresh_token(
+    state: &AppState,
+    headers: &HeaderMap,
+    body: Option<Json<RefreshRequestBody>>,
+) -> Option<String> {
+    body.map(|Json(body)| body.refresh_token).or_else(|| {
+        state
+            .config
+            .auth_cookies
+            .then(|| cookies::get(headers, cookies::REFRESH_COOKIE).map(str::to_string))
+            .flatten()
+    })
+}
+
 /// `POST /api/v1/auth/refresh`
 async fn refresh_token_handler(
     State(state): State<AppState>,
-    Json(body): Json<RefreshRequestBody>,
-) -> Result<Json<RefreshResponse>, AppError> {
+    headers: HeaderMap,
+    body: Option<Json<RefreshRequestBody>>,
+) -> Result<Response, AppError> {
+    let presented = request_refresh_token(&state, &headers, body)
+        .ok_or_else(|| AppError::Unauthorized("Missing refresh token.".to_string()))?;
+
     // Validate refresh token JWT
-    let claims = jwt::validate_refresh_token(&body.refresh_token, &state.config.jwt_secret)
+    let claims = jwt::validate_refresh_token(&presented, &state.jwt_keys)
         .map_err(|_| AppError::Unauthorized("Invalid or expired refresh token.".to_string()))?;
 
     // Look up refresh token record in DB
@@ -1137,32 +2332,70 @@ async fn refresh_token_handler(
         .map_err(|e| AppError::Internal(e.into()))?
         .ok_or_else(|| AppError::Unauthorized("User not found.".to_string()))?;
 
-    // Check account status
-    if user_model.account_status == "suspended" {
-        return Err(AppError::Forbidden("Account is suspended.".to_string()));
-    }
+    // Check account status. Suspended accounts keep their session so they can appeal; the
+    // auth middleware refuses them every other endpoint.
     if user_model.account_status == "deactivated" {
         return Err(AppError::Forbidden("Account is deactivated.".to_string()));
     }
 
     // Generate new token pair
-    let token_pair = jwt::generate_token_pair(user_model.id, &user_model.role, &state.config)?;
-    store_refresh_token(&state.db, user_model.id, &token_pair).await?;
+    let token_pair = jwt::generate_token_pair(user_model.id, &user_model.role, &state.jwt_keys)?;
+    store_refresh_token(
+        &state,
+        user_model.id,
+        &token_pair,
+        &headers,
+        token_record.signed_in_at.or(Some(token_record.created_at)),
+    )
+    .await?;
+
+    if state.config.auth_cookies {
+        let csrf_token = cookies::generate_csrf_token();
+        let mut response = Json(CsrfResponse {
+            csrf_token: csrf_token.clone(),
+        })
+        .into_response();
+        cookies::set_tokens(
+            response.headers_mut(),
+            &state.config,
+            &token_pair.access_token,
+            &token_pair.refresh_token,
+            &csrf_token,
+        );
+        return Ok(response);
+    }
 
     Ok(Json(RefreshResponse {
         token: token_pair.access_token,
         refresh_token: token_pair.refresh_token,
-    }))
+    })
+    .into_response())
+}
+
+/// `GET /api/v1/auth/csrf` — The CSRF token of the caller's cookie session, e.g. after a page
+/// reload or an OAuth redirect, where no response body carried it.
+async fn csrf_token(headers: HeaderMap) -> Result<Json<CsrfResponse>, AppError> {
+    cookies::get(&headers, cookies::CSRF_COOKIE)
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            Json(CsrfResponse {
+                csrf_token: token.to_string(),
+            })
+        })
+        .ok_or_else(|| AppError::Unauthorized("No cookie session.".to_string()))
 }
 
 /// `POST /api/v1/auth/signout`
 async fn signout(
     State(state): State<AppState>,
     AuthUser(_user): AuthUser,
-    Json(body): Json<SignoutRequestBody>,
-) -> Result<StatusCode, AppError> {
+    headers: HeaderMap,
+    body: Option<Json<RefreshRequestBody>>,
+) -> Result<Response, AppError> {
+    let presented = request_refresh_token(&state, &headers, body).unwrap_or_default();
+
     // Try to decode the refresh token to get the jti
-    if let Ok(claims) = jwt::validate_refresh_token(&body.refresh_token, &state.config.jwt_secret)
+    if let Ok(claims) = jwt::validate_refresh_token(&presented, &state.jwt_keys)
         && let Ok(jti) = claims.jti.parse::<Uuid>()
     {
         let token_record = refresh_token::Entity::find_by_id(jti)
@@ -1181,5 +2414,9 @@ async fn signout(
         }
     }
 
-    Ok(StatusCode::NO_CONTENT)
+    let mut response = StatusCode::NO_CONTENT.into_response();
+    if state.config.auth_cookies {
+        cookies::clear_tokens(response.headers_mut());
+    }
+    Ok(response)
 }
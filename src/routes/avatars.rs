@@ -0,0 +1,64 @@
+use axum::{
+    Router,
+    extract::Query,
+    http::header,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use serde::Deserialize;
+
+use crate::{
+    avatars::{self, Style},
+    error::AppError,
+    state::AppState,
+};
+
+/// Generated avatars never change for a seed, so clients may cache them for a year.
+const CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// Avatar router: `/avatars/...`
+pub fn router() -> Router<AppState> {
+    Router::new().route("/generate", get(generate_avatar))
+}
+
+#[derive(Debug, Deserialize)]
+struct GenerateAvatarQuery {
+    seed: String,
+    /// `"initials"` (the default) or `"identicon"`.
+    style: Option<String>,
+}
+
+/// `GET /avatars/generate?seed=` — A deterministic SVG avatar for `seed`, used in place of an
+/// uploaded one.
+///
+/// # Errors
+///
+/// Returns [`AppError::BadRequest`] if the seed is empty or too long, or the style is unknown.
+async fn generate_avatar(Query(query): Query<GenerateAvatarQuery>) -> Result<Response, AppError> {
+    let seed = query.seed.trim();
+    if seed.is_empty() || seed.chars().count() > avatars::MAX_SEED_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "seed must be between 1 and {} characters",
+            avatars::MAX_SEED_LENGTH
+        )));
+    }
+    let style = match query.style.as_deref() {
+        None | Some("initials") => Style::Initials,
+        Some("identicon") => Style::Identicon,
+        Some(_) => {
+            return Err(AppError::BadRequest(
+                "style must be one of: initials, identicon".to_string(),
+            ));
+        }
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/svg+xml"),
+            (header::CACHE_CONTROL, CACHE_CONTROL),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+        ],
+        avatars::svg(seed, style),
+    )
+        .into_response())
+}
@@ -0,0 +1,121 @@
+use axum::{
+    Json, Router,
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::post,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::middleware::AuthUser,
+    billing::{self, PRO_PLAN},
+    error::AppError,
+    state::AppState,
+};
+
+/// Billing router: `/billing/...`
+pub fn router() -> Router<AppState> {
+    Router::new().route("/checkout", post(create_checkout))
+}
+
+/// Webhook router: `/webhooks/...`
+pub fn webhooks_router() -> Router<AppState> {
+    Router::new().route("/stripe", post(stripe_webhook))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CheckoutRequest {
+    /// The plan to subscribe to; only `"pro"` is sold.
+    plan: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CheckoutResponse {
+    /// Stripe-hosted checkout page to send the user to.
+    url: String,
+}
+
+/// `POST /billing/checkout` — Start a Stripe Checkout session for the authenticated user to
+/// subscribe to a plan.
+///
+/// # Errors
+///
+/// Returns [`AppError::UnprocessableEntity`] if billing is not configured,
+/// [`AppError::BadRequest`] for an unknown plan, [`AppError::Forbidden`] for guest accounts,
+/// [`AppError::Conflict`] if the user already has the plan, or [`AppError`] if Stripe or the
+/// database fails.
+async fn create_checkout(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(body): Json<CheckoutRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if state.config.stripe_secret_key.is_empty() {
+        return Err(AppError::UnprocessableEntity(
+            "Billing is not configured.".to_string(),
+        ));
+    }
+    if body.plan != PRO_PLAN {
+        return Err(AppError::BadRequest(format!(
+            "Unknown plan '{}'.",
+            body.plan
+        )));
+    }
+    if user.role == "guest" {
+        return Err(AppError::Forbidden(
+            "Guest accounts cannot subscribe.".to_string(),
+        ));
+    }
+    if user.subscription_plan == PRO_PLAN {
+        return Err(AppError::Conflict(
+            "You are already subscribed to this plan.".to_string(),
+        ));
+    }
+
+    let url = billing::create_checkout_session(&state.db, &state.config, &user).await?;
+    Ok(Json(CheckoutResponse { url }))
+}
+
+/// `POST /webhooks/stripe` — Subscription lifecycle events from Stripe.
+///
+/// Only requests signed with the webhook secret are accepted. Stripe retries anything that
+/// does not get a 2xx response, so events are acknowledged once applied.
+///
+/// # Errors
+///
+/// Returns [`AppError::UnprocessableEntity`] if billing is not configured,
+/// [`AppError::BadRequest`] if the signature or payload is invalid, or [`AppError`] if the
+/// database fails.
+async fn stripe_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode, AppError> {
+    if state.config.stripe_webhook_secret.is_empty() {
+        return Err(AppError::UnprocessableEntity(
+            "Billing is not configured.".to_string(),
+        ));
+    }
+
+    let signature = headers
+        .get("stripe-signature")
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| AppError::BadRequest("Missing Stripe-Signature header.".to_string()))?;
+    billing::verify_signature(
+        &body,
+        signature,
+        &state.config.stripe_webhook_secret,
+        Utc::now().timestamp(),
+    )
+    .map_err(AppError::BadRequest)?;
+
+    let event: billing::Event = serde_json::from_slice(&body)
+        .map_err(|e| AppError::BadRequest(format!("Invalid event: {e}")))?;
+    billing::apply_event(&state.db, &event).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,518 @@
+use std::collections::HashSet;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{delete, get, post},
+};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, Condition, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, TransactionTrait,
+    sea_query::Expr,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::{middleware::AuthUser, password},
+    classroom::{STUDENT_PROVIDER, STUDENT_ROLE, generate_class_code, generate_password},
+    entities::{auth_provider, classroom, classroom_student, game, refresh_token, user},
+    error::AppError,
+    jobs::account_deletion,
+    routes::games::{GameSummaryResponse, to_game_summary},
+    state::AppState,
+};
+
+/// Most student accounts created in a single request.
+const MAX_STUDENTS_PER_REQUEST: usize = 50;
+
+/// Longest class or student name accepted.
+const MAX_NAME_LEN: usize = 100;
+
+/// Classroom router: `/classrooms/...`
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_classrooms).post(create_classroom))
+        .route("/mine", get(get_my_classroom))
+        .route("/{id}", get(get_classroom))
+        .route("/{id}/students", post(create_students))
+        .route("/{id}/students/{user_id}", delete(delete_student))
+        .route(
+            "/{id}/students/{user_id}/password",
+            post(reset_student_password),
+        )
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateClassroomRequest {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateStudentsRequest {
+    /// Display names of the students to create, one account each.
+    names: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ClassroomResponse {
+    id: Uuid,
+    name: String,
+    code: String,
+    student_count: u64,
+    created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ClassroomsResponse {
+    data: Vec<ClassroomResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StudentResponse {
+    user_id: Uuid,
+    username: String,
+    display_name: Option<String>,
+    last_login_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ClassroomDetailResponse {
+    #[serde(flatten)]
+    classroom: ClassroomResponse,
+    students: Vec<StudentResponse>,
+}
+
+/// A new or reset student login. The password is only ever shown here.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StudentCredentialsResponse {
+    user_id: Uuid,
+    username: String,
+    display_name: Option<String>,
+    password: String,
+}
+
+#[derive(Debug, Serialize)]
+struct StudentCredentialsListResponse {
+    data: Vec<StudentCredentialsResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MyClassroomResponse {
+    id: Uuid,
+    name: String,
+    teacher_username: String,
+    teacher_display_name: Option<String>,
+    /// The class's published games and the student's own.
+    games: Vec<GameSummaryResponse>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `POST /classrooms` — Create a class taught by the caller.
+///
+/// # Errors
+///
+/// Returns [`AppError::Forbidden`] for guest and student accounts, [`AppError::BadRequest`]
+/// for an invalid name, or [`AppError`] if the database fails.
+async fn create_classroom(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(req): Json<CreateClassroomRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if user.role == "guest" || user.role == STUDENT_ROLE {
+        return Err(AppError::Forbidden(
+            "Guest and student accounts cannot create classes.".to_string(),
+        ));
+    }
+    let name = validate_name(&req.name)?;
+    let code = unique_class_code(&state.db).await?;
+
+    let created = classroom::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        teacher_id: ActiveValue::Set(user.id),
+        name: ActiveValue::Set(name),
+        code: ActiveValue::Set(code),
+        created_at: ActiveValue::Set(Utc::now().fixed_offset()),
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(to_classroom_response(created, 0))))
+}
+
+/// `GET /classrooms` — The classes the caller teaches.
+async fn list_classrooms(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    let classrooms = classroom::Entity::find()
+        .filter(classroom::Column::TeacherId.eq(user.id))
+        .order_by_asc(classroom::Column::CreatedAt)
+        .all(&state.db)
+        .await?;
+
+    let mut data = Vec::with_capacity(classrooms.len());
+    for c in classrooms {
+        let student_count = student_count(&state.db, c.id).await?;
+        data.push(to_classroom_response(c, student_count));
+    }
+
+    Ok(Json(ClassroomsResponse { data }))
+}
+
+/// `GET /classrooms/:id` — A class and its students (teacher only).
+async fn get_classroom(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let classroom = find_classroom_as_teacher(&state.db, id, &user).await?;
+
+    let students = user::Entity::find()
+        .filter(user::Column::Id.in_subquery(student_ids(classroom.id)))
+        .filter(user::Column::DeletedAt.is_null())
+        .order_by_asc(user::Column::Username)
+        .all(&state.db)
+        .await?;
+    let students: Vec<StudentResponse> = students
+        .into_iter()
+        .map(|u| StudentResponse {
+            user_id: u.id,
+            username: u.username,
+            display_name: u.display_name,
+            last_login_at: u.last_login_at.map(|t| t.to_string()),
+        })
+        .collect();
+
+    Ok(Json(ClassroomDetailResponse {
+        classroom: to_classroom_response(classroom, students.len() as u64),
+        students,
+    }))
+}
+
+/// `POST /classrooms/:id/students` — Create student accounts in a class (teacher only).
+/// Returns each account's username and generated password.
+///
+/// # Errors
+///
+/// Returns [`AppError::BadRequest`] for an empty or oversized batch or an invalid name,
+/// [`AppError::Forbidden`] if the caller does not teach the class, or [`AppError`] if the
+/// database fails.
+async fn create_students(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+    Json(req): Json<CreateStudentsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let classroom = find_classroom_as_teacher(&state.db, id, &user).await?;
+    if req.names.is_empty() || req.names.len() > MAX_STUDENTS_PER_REQUEST {
+        return Err(AppError::BadRequest(format!(
+            "Provide 1-{MAX_STUDENTS_PER_REQUEST} student names"
+        )));
+    }
+    let names = req
+        .names
+        .iter()
+        .map(|name| validate_name(name))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Usernames are `<class code>_<n>`, skipping any already taken
+    let prefix = format!("{}_", classroom.code.to_lowercase());
+    let taken: HashSet<String> = user::Entity::find()
+        .select_only()
+        .column(user::Column::Username)
+        .filter(user::Column::Username.starts_with(&prefix))
+        .into_tuple()
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .collect();
+    let mut usernames = (1..)
+        .map(|n| format!("{prefix}{n}"))
+        .filter(|username| !taken.contains(username));
+
+    let now = Utc::now().fixed_offset();
+    let txn = state.db.begin().await?;
+    let mut data = Vec::with_capacity(names.len());
+    for (name, username) in names.into_iter().zip(&mut usernames) {
+        let user_id = Uuid::new_v4();
+        let plain_password = generate_password();
+
+        user::ActiveModel {
+            id: ActiveValue::Set(user_id),
+            email: ActiveValue::Set(format!("student-{}@student.invalid", user_id.simple())),
+            username: ActiveValue::Set(username.clone()),
+            display_name: ActiveValue::Set(Some(name.clone())),
+            avatar_url: ActiveValue::Set(None),
+            bio: ActiveValue::Set(None),
+            location: ActiveValue::Set(None),
+            website_url: ActiveValue::Set(None),
+            pronouns: ActiveValue::Set(None),
+            social_links: ActiveValue::Set(None),
+            email_verified: ActiveValue::Set(false),
+            verified: ActiveValue::Set(false),
+            // Students are kept out of the public site entirely
+            hide_from_search: ActiveValue::Set(true),
+            hide_games: ActiveValue::Set(true),
+            hide_activity: ActiveValue::Set(true),
+            shadow_banned: ActiveValue::Set(false),
+            role: ActiveValue::Set(STUDENT_ROLE.to_string()),
+            subscription_plan: ActiveValue::Set("free".to_string()),
+            subscription_expires_at: ActiveValue::Set(None),
+            account_status: ActiveValue::Set("active".to_string()),
+            suspension_reason: ActiveValue::Set(None),
+            last_login_at: ActiveValue::Set(None),
+            last_login_ip: ActiveValue::Set(None),
+            created_at: ActiveValue::Set(now),
+            updated_at: ActiveValue::Set(now),
+            deleted_at: ActiveValue::Set(None),
+            deletion_scheduled_at: ActiveValue::Set(None),
+            deletion_game_policy: ActiveValue::Set(None),
+        }
+        .insert(&txn)
+        .await?;
+        auth_provider::ActiveModel {
+            id: ActiveValue::Set(Uuid::new_v4()),
+            user_id: ActiveValue::Set(user_id),
+            provider: ActiveValue::Set(STUDENT_PROVIDER.to_string()),
+            provider_id: ActiveValue::Set(user_id.to_string()),
+            password_hash: ActiveValue::Set(Some(password::hash_password(&plain_password)?)),
+            provider_email: ActiveValue::Set(None),
+            verification_token: ActiveValue::Set(None),
+            token_expires_at: ActiveValue::Set(None),
+            created_at: ActiveValue::Set(now),
+        }
+        .insert(&txn)
+        .await?;
+        classroom_student::ActiveModel {
+            user_id: ActiveValue::Set(user_id),
+            classroom_id: ActiveValue::Set(classroom.id),
+            created_at: ActiveValue::Set(now),
+        }
+        .insert(&txn)
+        .await?;
+
+        data.push(StudentCredentialsResponse {
+            user_id,
+            username,
+            display_name: Some(name),
+            password: plain_password,
+        });
+    }
+    txn.commit().await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(StudentCredentialsListResponse { data }),
+    ))
+}
+
+/// `POST /classrooms/:id/students/:user_id/password` — Give a student a new generated
+/// password and sign them out everywhere (teacher only).
+async fn reset_student_password(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path((id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let classroom = find_classroom_as_teacher(&state.db, id, &user).await?;
+    let student = find_student(&state.db, &classroom, user_id).await?;
+
+    let plain_password = generate_password();
+    let now = Utc::now().fixed_offset();
+    auth_provider::Entity::update_many()
+        .col_expr(
+            auth_provider::Column::PasswordHash,
+            Expr::value(password::hash_password(&plain_password)?),
+        )
+        .filter(auth_provider::Column::UserId.eq(student.id))
+        .filter(auth_provider::Column::Provider.eq(STUDENT_PROVIDER))
+        .exec(&state.db)
+        .await?;
+    refresh_token::Entity::update_many()
+        .col_expr(refresh_token::Column::RevokedAt, Expr::value(now))
+        .filter(refresh_token::Column::UserId.eq(student.id))
+        .filter(refresh_token::Column::RevokedAt.is_null())
+        .exec(&state.db)
+        .await?;
+
+    Ok(Json(StudentCredentialsResponse {
+        user_id: student.id,
+        username: student.username,
+        display_name: student.display_name,
+        password: plain_password,
+    }))
+}
+
+/// `DELETE /classrooms/:id/students/:user_id` — Delete a student account and its games
+/// (teacher only).
+async fn delete_student(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path((id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let classroom = find_classroom_as_teacher(&state.db, id, &user).await?;
+    let student = find_student(&state.db, &classroom, user_id).await?;
+
+    let txn = state.db.begin().await?;
+    classroom_student::Entity::delete_by_id(student.id)
+        .exec(&txn)
+        .await?;
+    account_deletion::delete_account(&txn, student, Utc::now()).await?;
+    txn.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GET /classrooms/mine` — The caller's class, as a student: its teacher and the games they
+/// can see.
+async fn get_my_classroom(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    let classroom = crate::classroom::student_classroom(&state.db, user.id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("You are not in a class".to_string()))?;
+    let teacher = user::Entity::find_by_id(classroom.teacher_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Teacher not found".to_string()))?;
+
+    let games = game::Entity::find()
+        .filter(
+            Condition::any()
+                .add(game::Column::OwnerId.eq(classroom.teacher_id))
+                .add(game::Column::OwnerId.in_subquery(student_ids(classroom.id))),
+        )
+        .filter(game::Column::DeletedAt.is_null())
+        .filter(
+            Condition::any().add(game::Column::OwnerId.eq(user.id)).add(
+                Condition::all()
+                    .add(game::Column::Status.eq("published"))
+                    .add(game::Column::Visibility.ne("private")),
+            ),
+        )
+        .order_by_desc(game::Column::UpdatedAt)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(MyClassroomResponse {
+        id: classroom.id,
+        name: classroom.name,
+        teacher_username: teacher.username,
+        teacher_display_name: teacher.display_name,
+        games: games.into_iter().map(to_game_summary).collect(),
+    }))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Load a class, ensuring `user` teaches it. Only teachers manage their students.
+async fn find_classroom_as_teacher(
+    db: &DatabaseConnection,
+    id: Uuid,
+    user: &user::Model,
+) -> Result<classroom::Model, AppError> {
+    let classroom = classroom::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Class not found".to_string()))?;
+
+    if classroom.teacher_id != user.id {
+        return Err(AppError::Forbidden(
+            "You are not the teacher of this class".to_string(),
+        ));
+    }
+
+    Ok(classroom)
+}
+
+async fn find_student(
+    db: &DatabaseConnection,
+    classroom: &classroom::Model,
+    user_id: Uuid,
+) -> Result<user::Model, AppError> {
+    let not_found = || AppError::NotFound("Student not found".to_string());
+    classroom_student::Entity::find_by_id(user_id)
+        .filter(classroom_student::Column::ClassroomId.eq(classroom.id))
+        .one(db)
+        .await?
+        .ok_or_else(not_found)?;
+    user::Entity::find_by_id(user_id)
+        .filter(user::Column::DeletedAt.is_null())
+        .one(db)
+        .await?
+        .ok_or_else(not_found)
+}
+
+/// Subquery selecting the user IDs of a class's students.
+fn student_ids(classroom_id: Uuid) -> sea_orm::sea_query::SelectStatement {
+    classroom_student::Entity::find()
+        .select_only()
+        .column(classroom_student::Column::UserId)
+        .filter(classroom_student::Column::ClassroomId.eq(classroom_id))
+        .into_query()
+}
+
+async fn student_count(db: &DatabaseConnection, classroom_id: Uuid) -> Result<u64, AppError> {
+    Ok(classroom_student::Entity::find()
+        .filter(classroom_student::Column::ClassroomId.eq(classroom_id))
+        .count(db)
+        .await?)
+}
+
+/// Generate a class code no other class uses, retrying on collisions.
+async fn unique_class_code(db: &DatabaseConnection) -> Result<String, AppError> {
+    for _ in 0..20 {
+        let code = generate_class_code();
+        let existing = classroom::Entity::find()
+            .filter(classroom::Column::Code.eq(&code))
+            .one(db)
+            .await?;
+        if existing.is_none() {
+            return Ok(code);
+        }
+    }
+
+    Err(AppError::Internal(anyhow::anyhow!(
+        "Failed to generate unique class code after 20 attempts"
+    )))
+}
+
+fn validate_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        return Err(AppError::BadRequest(format!(
+            "Names must be 1-{MAX_NAME_LEN} characters"
+        )));
+    }
+    Ok(name.to_string())
+}
+
+fn to_classroom_response(c: classroom::Model, student_count: u64) -> ClassroomResponse {
+    ClassroomResponse {
+        id: c.id,
+        name: c.name,
+        code: c.code,
+        student_count,
+        created_at: c.created_at.to_string(),
+    }
+}
@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, patch},
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::middleware::AuthUser,
+    entities::{game, game_collaborator, user},
+    error::AppError,
+    routes::games::{GameAccess, check_access, find_active_game},
+    state::AppState,
+};
+
+/// Allowed values for `game_collaborator.role`.
+const ROLES: [&str; 2] = ["editor", "viewer"];
+
+/// Collaborator routes, nested under `/games` alongside the main game router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/{id}/collaborators",
+            get(list_collaborators).post(add_collaborator),
+        )
+        .route(
+            "/{id}/collaborators/{user_id}",
+            patch(update_collaborator).delete(remove_collaborator),
+        )
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AddCollaboratorRequest {
+    username: String,
+    role: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateCollaboratorRequest {
+    role: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CollaboratorResponse {
+    user_id: Uuid,
+    username: String,
+    display_name: Option<String>,
+    avatar_url: Option<String>,
+    role: String,
+    invited_by: Uuid,
+    added_at: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CollaboratorsResponse {
+    data: Vec<CollaboratorResponse>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `GET /games/:id/collaborators` — List a game's collaborators (creator and collaborators only).
+async fn list_collaborators(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+
+    if check_access(&state.db, &game, Some(user.id)).await? < GameAccess::Viewer {
+        return Err(AppError::Forbidden(
+            "You do not have access to this game".to_string(),
+        ));
+    }
+
+    let collaborators = game_collaborator::Entity::find()
+        .filter(game_collaborator::Column::GameId.eq(id))
+        .order_by_asc(game_collaborator::Column::CreatedAt)
+        .all(&state.db)
+        .await?;
+
+    let user_ids: Vec<Uuid> = collaborators.iter().map(|c| c.user_id).collect();
+    let users: HashMap<Uuid, user::Model> = user::Entity::find()
+        .filter(user::Column::Id.is_in(user_ids))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u))
+        .collect();
+
+    let data = collaborators
+        .into_iter()
+        .filter_map(|c| {
+            let u = users.get(&c.user_id)?;
+            Some(to_collaborator_response(c, u))
+        })
+        .collect();
+
+    Ok(Json(CollaboratorsResponse { data }))
+}
+
+/// `POST /games/:id/collaborators` — Invite a user by username as an `editor` or `viewer`.
+async fn add_collaborator(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+    Json(req): Json<AddCollaboratorRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_game_as_owner(&state.db, id, &user).await?;
+    let role = validate_role(&req.role)?;
+
+    let invitee = user::Entity::find()
+        .filter(user::Column::Username.eq(req.username.trim()))
+        .filter(user::Column::DeletedAt.is_null())
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    if invitee.id == game.owner_id {
+        return Err(AppError::BadRequest(
+            "The game creator cannot be added as a collaborator".to_string(),
+        ));
+    }
+
+    let existing = game_collaborator::Entity::find_by_id((game.id, invitee.id))
+        .one(&state.db)
+        .await?;
+    if existing.is_some() {
+        return Err(AppError::Conflict(
+            "This user is already a collaborator".to_string(),
+        ));
+    }
+
+    let created = game_collaborator::ActiveModel {
+        game_id: ActiveValue::Set(game.id),
+        user_id: ActiveValue::Set(invitee.id),
+        role: ActiveValue::Set(role),
+        invited_by: ActiveValue::Set(user.id),
+        created_at: ActiveValue::Set(chrono::Utc::now().into()),
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(to_collaborator_response(created, &invitee)),
+    ))
+}
+
+/// `PATCH /games/:id/collaborators/:user_id` — Change a collaborator's role.
+async fn update_collaborator(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path((id, user_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<UpdateCollaboratorRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_game_as_owner(&state.db, id, &user).await?;
+    let role = validate_role(&req.role)?;
+
+    let collaborator = find_collaborator(&state.db, game.id, user_id).await?;
+    let member = user::Entity::find_by_id(user_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    let mut active: game_collaborator::ActiveModel = collaborator.into();
+    active.role = ActiveValue::Set(role);
+    let updated = active.update(&state.db).await?;
+
+    Ok(Json(to_collaborator_response(updated, &member)))
+}
+
+/// `DELETE /games/:id/collaborators/:user_id` — Remove a collaborator. Collaborators may also
+/// remove themselves.
+async fn remove_collaborator(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path((id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+
+    if game.owner_id != user.id && user_id != user.id {
+        return Err(AppError::Forbidden(
+            "You are not the creator of this game".to_string(),
+        ));
+    }
+
+    find_collaborator(&state.db, game.id, user_id).await?;
+    game_collaborator::Entity::delete_by_id((game.id, user_id))
+        .exec(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Load a live game, ensuring `user` is its creator. Only creators manage collaborators.
+async fn find_game_as_owner(
+    db: &DatabaseConnection,
+    id: Uuid,
+    user: &user::Model,
+) -> Result<game::Model, AppError> {
+    let game = find_active_game(db, id).await?;
+
+    if game.owner_id != user.id {
+        return Err(AppError::Forbidden(
+            "You are not the creator of this game".to_string(),
+        ));
+    }
+
+    Ok(game)
+}
+
+async fn find_collaborator(
+    db: &DatabaseConnection,
+    game_id: Uuid,
+    user_id: Uuid,
+) -> Result<game_collaborator::Model, AppError> {
+    game_collaborator::Entity::find_by_id((game_id, user_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Collaborator not found".to_string()))
+}
+
+fn validate_role(role: &str) -> Result<String, AppError> {
+    if ROLES.contains(&role) {
+        Ok(role.to_string())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Invalid role '{role}'. Expected one of: editor, viewer"
+        )))
+    }
+}
+
+fn to_collaborator_response(c: game_collaborator::Model, u: &user::Model) -> CollaboratorResponse {
+    CollaboratorResponse {
+        user_id: c.user_id,
+        username: u.username.clone(),
+        display_name: u.display_name.clone(),
+        avatar_url: u.avatar_url.clone(),
+        role: c.role,
+        invited_by: c.invited_by,
+        added_at: c.created_at.to_string(),
+    }
+}
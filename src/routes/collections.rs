@@ -0,0 +1,385 @@
+use std::collections::HashMap;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{delete, get, post},
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, Condition, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, sea_query::OnConflict,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::middleware::AuthUser,
+    entities::{collection, collection_game, game},
+    error::AppError,
+    routes::games::{
+        GameSummaryResponse, OptionalAuth, PaginatedResponse, PaginationQuery, check_visibility,
+        find_active_game, to_game_summary,
+    },
+    state::AppState,
+};
+
+/// Maximum length (in characters) of a collection title.
+const MAX_TITLE_LENGTH: usize = 100;
+
+/// Allowed values for `collection.visibility`.
+const VISIBILITIES: [&str; 3] = ["public", "unlisted", "private"];
+
+/// Public collection router: `/collections/...`
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/{id}", get(get_collection))
+        .route("/{id}/games", post(add_collection_game))
+        .route("/{id}/games/{game_id}", delete(remove_collection_game))
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCollectionRequest {
+    title: String,
+    description: Option<String>,
+    visibility: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCollectionRequest {
+    title: Option<String>,
+    description: Option<String>,
+    visibility: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AddGameRequest {
+    game_id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CollectionResponse {
+    id: Uuid,
+    created_at: String,
+    updated_at: String,
+    owner_id: Uuid,
+    title: String,
+    description: Option<String>,
+    visibility: String,
+    game_count: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    games: Option<Vec<GameSummaryResponse>>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `GET /users/me/collections` — List the authenticated user's collections.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the database query fails.
+pub async fn list_my_collections(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let find = collection::Entity::find().filter(collection::Column::OwnerId.eq(user.id));
+
+    let total = find.clone().count(&state.db).await?;
+
+    let collections = find
+        .order_by_desc(collection::Column::UpdatedAt)
+        .offset(pagination.offset)
+        .limit(pagination.limit)
+        .all(&state.db)
+        .await?;
+
+    let ids: Vec<Uuid> = collections.iter().map(|c| c.id).collect();
+    let counts = count_games(&state.db, ids).await?;
+
+    Ok(Json(PaginatedResponse {
+        data: collections
+            .into_iter()
+            .map(|c| {
+                let count = counts.get(&c.id).copied().unwrap_or_default();
+                to_collection_response(c, count, None)
+            })
+            .collect(),
+        total,
+        offset: pagination.offset,
+        limit: pagination.limit,
+    }))
+}
+
+/// `POST /users/me/collections` — Create a collection.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if validation or the database insert fails.
+pub async fn create_my_collection(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(req): Json<CreateCollectionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let title = validate_title(&req.title)?;
+    let visibility = validate_visibility(req.visibility.as_deref().unwrap_or("private"))?;
+
+    let now = chrono::Utc::now();
+    let created = collection::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        created_at: ActiveValue::Set(now.into()),
+        updated_at: ActiveValue::Set(now.into()),
+        owner_id: ActiveValue::Set(user.id),
+        title: ActiveValue::Set(title),
+        description: ActiveValue::Set(req.description),
+        visibility: ActiveValue::Set(visibility),
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(to_collection_response(created, 0, Some(vec![]))),
+    ))
+}
+
+/// `PATCH /users/me/collections/:id` — Update a collection's details.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the collection is not owned by the user or validation fails.
+pub async fn update_my_collection(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UpdateCollectionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let existing = find_owned_collection(&state.db, id, user.id).await?;
+
+    let mut active: collection::ActiveModel = existing.into();
+    if let Some(title) = req.title {
+        active.title = ActiveValue::Set(validate_title(&title)?);
+    }
+    if let Some(description) = req.description {
+        active.description = ActiveValue::Set(Some(description));
+    }
+    if let Some(visibility) = req.visibility {
+        active.visibility = ActiveValue::Set(validate_visibility(&visibility)?);
+    }
+    active.updated_at = ActiveValue::Set(chrono::Utc::now().into());
+    let updated = active.update(&state.db).await?;
+
+    let count = count_games(&state.db, vec![updated.id])
+        .await?
+        .get(&updated.id)
+        .copied()
+        .unwrap_or_default();
+
+    Ok(Json(to_collection_response(updated, count, None)))
+}
+
+/// `DELETE /users/me/collections/:id` — Delete a collection.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the collection is not owned by the user.
+pub async fn delete_my_collection(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    find_owned_collection(&state.db, id, user.id).await?;
+
+    collection::Entity::delete_by_id(id).exec(&state.db).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GET /collections/:id` — View a collection and its games (private ones only by the owner).
+async fn get_collection(
+    State(state): State<AppState>,
+    OptionalAuth(opt_user): OptionalAuth,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let viewer_id = opt_user.as_ref().map(|u| u.id);
+
+    let found = collection::Entity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .filter(|c| c.visibility != "private" || viewer_id == Some(c.owner_id))
+        .ok_or_else(|| AppError::NotFound("Collection not found".to_string()))?;
+
+    // Hide games that were deleted or made private since they were added
+    let mut visible = Condition::any().add(game::Column::Visibility.ne("private"));
+    if let Some(uid) = viewer_id {
+        visible = visible.add(game::Column::OwnerId.eq(uid));
+    }
+
+    let games = game::Entity::find()
+        .inner_join(collection_game::Entity)
+        .filter(collection_game::Column::CollectionId.eq(found.id))
+        .filter(game::Column::DeletedAt.is_null())
+        .filter(visible)
+        .order_by_asc(collection_game::Column::AddedAt)
+        .all(&state.db)
+        .await?;
+
+    let count = games.len() as u64;
+    let games = games.into_iter().map(to_game_summary).collect();
+
+    Ok(Json(to_collection_response(found, count, Some(games))))
+}
+
+/// `POST /collections/:id/games` — Add a game to one of the user's collections.
+async fn add_collection_game(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+    Json(req): Json<AddGameRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let owned = find_owned_collection(&state.db, id, user.id).await?;
+
+    let game = find_active_game(&state.db, req.game_id).await?;
+    check_visibility(&game, Some(user.id))?;
+
+    let entry = collection_game::ActiveModel {
+        collection_id: ActiveValue::Set(owned.id),
+        game_id: ActiveValue::Set(game.id),
+        added_at: ActiveValue::Set(chrono::Utc::now().into()),
+    };
+
+    // Adding a game twice is a no-op
+    collection_game::Entity::insert(entry)
+        .on_conflict(
+            OnConflict::columns([
+                collection_game::Column::CollectionId,
+                collection_game::Column::GameId,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .exec_without_returning(&state.db)
+        .await?;
+
+    touch(&state.db, owned).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `DELETE /collections/:id/games/:game_id` — Remove a game from one of the user's collections.
+async fn remove_collection_game(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path((id, game_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let owned = find_owned_collection(&state.db, id, user.id).await?;
+
+    collection_game::Entity::delete_by_id((owned.id, game_id))
+        .exec(&state.db)
+        .await?;
+
+    touch(&state.db, owned).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Load a collection owned by `owner_id`. Other users' collections are reported as not found.
+async fn find_owned_collection(
+    db: &DatabaseConnection,
+    id: Uuid,
+    owner_id: Uuid,
+) -> Result<collection::Model, AppError> {
+    collection::Entity::find_by_id(id)
+        .filter(collection::Column::OwnerId.eq(owner_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Collection not found".to_string()))
+}
+
+/// Count games per collection for the given collection IDs.
+async fn count_games(
+    db: &DatabaseConnection,
+    ids: Vec<Uuid>,
+) -> Result<HashMap<Uuid, u64>, AppError> {
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows: Vec<(Uuid, i64)> = collection_game::Entity::find()
+        .select_only()
+        .column(collection_game::Column::CollectionId)
+        .column_as(collection_game::Column::GameId.count(), "count")
+        .filter(collection_game::Column::CollectionId.is_in(ids))
+        .group_by(collection_game::Column::CollectionId)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(id, count)| (id, u64::try_from(count).unwrap_or_default()))
+        .collect())
+}
+
+async fn touch(db: &DatabaseConnection, c: collection::Model) -> Result<(), AppError> {
+    let mut active: collection::ActiveModel = c.into();
+    active.updated_at = ActiveValue::Set(chrono::Utc::now().into());
+    active.update(db).await?;
+    Ok(())
+}
+
+fn validate_title(title: &str) -> Result<String, AppError> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(AppError::BadRequest("Title is required".to_string()));
+    }
+    if title.chars().count() > MAX_TITLE_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Title must be at most {MAX_TITLE_LENGTH} characters"
+        )));
+    }
+    Ok(title.to_string())
+}
+
+fn validate_visibility(visibility: &str) -> Result<String, AppError> {
+    if VISIBILITIES.contains(&visibility) {
+        Ok(visibility.to_string())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Invalid visibility '{visibility}'. Expected one of: public, unlisted, private"
+        )))
+    }
+}
+
+fn to_collection_response(
+    c: collection::Model,
+    game_count: u64,
+    games: Option<Vec<GameSummaryResponse>>,
+) -> CollectionResponse {
+    CollectionResponse {
+        id: c.id,
+        created_at: c.created_at.to_string(),
+        updated_at: c.updated_at.to_string(),
+        owner_id: c.owner_id,
+        title: c.title,
+        description: c.description,
+        visibility: c.visibility,
+        game_count,
+        games,
+    }
+}
@@ -0,0 +1,182 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    auth::middleware::AuthUser, entities::data_export, error::AppError, jobs, state::AppState,
+    storage,
+};
+
+// ============================================================================
+// Response Types
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportResponse {
+    id: Uuid,
+    /// `"pending"`, `"ready"` or `"failed"`.
+    status: String,
+    size_bytes: Option<i64>,
+    created_at: String,
+    completed_at: Option<String>,
+    expires_at: Option<String>,
+    /// Set once the archive is ready.
+    download_url: Option<String>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `GET /users/me/export` — Get the user's data export, starting one if needed.
+///
+/// Returns the pending or still-downloadable export if there is one (`202` while pending,
+/// `200` once ready). Otherwise any old exports are discarded and a new archive of the user's
+/// profile, games, versions, asset metadata, sessions and reviews is assembled in the
+/// background (`202`); poll `GET /users/me/export/{id}` until it is ready.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the database operation fails.
+pub async fn request_my_export(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    let now = Utc::now().fixed_offset();
+
+    let latest = data_export::Entity::find()
+        .filter(data_export::Column::UserId.eq(user.id))
+        .order_by_desc(data_export::Column::CreatedAt)
+        .one(&state.db)
+        .await?;
+    if let Some(export) = latest {
+        if export.status == "pending" {
+            return Ok((StatusCode::ACCEPTED, Json(to_export_response(export))));
+        }
+        if export.status == "ready" && export.expires_at.is_some_and(|t| t > now) {
+            return Ok((StatusCode::OK, Json(to_export_response(export))));
+        }
+    }
+
+    jobs::data_export::delete_for_user(&state.db, &state.config, user.id).await?;
+
+    let export = data_export::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        user_id: ActiveValue::Set(user.id),
+        status: ActiveValue::Set("pending".to_string()),
+        storage_backend: ActiveValue::Set(None),
+        storage_key: ActiveValue::Set(None),
+        size_bytes: ActiveValue::Set(None),
+        created_at: ActiveValue::Set(now),
+        completed_at: ActiveValue::Set(None),
+        expires_at: ActiveValue::Set(None),
+    }
+    .insert(&state.db)
+    .await?;
+
+    jobs::data_export::spawn(state.db.clone(), state.config.clone(), export.id);
+
+    Ok((StatusCode::ACCEPTED, Json(to_export_response(export))))
+}
+
+/// `GET /users/me/export/{id}` — Check on a data export.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if the export does not exist or belongs to someone else, or
+/// [`AppError`] if the database query fails.
+pub async fn get_my_export(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let export = find_my_export(&state, user.id, id).await?;
+    Ok(Json(to_export_response(export)))
+}
+
+/// `GET /users/me/export/{id}/download` — Download a finished export as a zip archive.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if the export does not exist, belongs to someone else or has
+/// expired, [`AppError::Conflict`] if it is not ready, or [`AppError`] if reading the archive
+/// fails.
+pub async fn download_my_export(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let export = find_my_export(&state, user.id, id).await?;
+    if export.status != "ready" {
+        return Err(AppError::Conflict(format!(
+            "Export is {}, not ready for download",
+            export.status
+        )));
+    }
+    if export.expires_at.is_none_or(|t| t <= Utc::now()) {
+        return Err(AppError::NotFound("Export has expired".to_string()));
+    }
+
+    let (Some(backend), Some(key)) = (export.storage_backend, export.storage_key) else {
+        return Err(AppError::Internal(anyhow::anyhow!(
+            "Ready export {id} has no stored archive"
+        )));
+    };
+    let storage = storage::by_name(&backend, &state.config, &state.db).ok_or_else(|| {
+        AppError::Internal(anyhow::anyhow!("Unknown storage backend '{backend}'"))
+    })?;
+    let archive = storage
+        .get(&key)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Export has expired".to_string()))?;
+
+    let disposition = format!(
+        "attachment; filename=\"aircade-export-{}.zip\"",
+        export.created_at.format("%Y-%m-%d")
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        archive,
+    ))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+async fn find_my_export(
+    state: &AppState,
+    user_id: Uuid,
+    id: Uuid,
+) -> Result<data_export::Model, AppError> {
+    data_export::Entity::find_by_id(id)
+        .filter(data_export::Column::UserId.eq(user_id))
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Export not found".to_string()))
+}
+
+fn to_export_response(export: data_export::Model) -> ExportResponse {
+    let download_url = (export.status == "ready")
+        .then(|| format!("/api/v1/users/me/export/{}/download", export.id));
+    ExportResponse {
+        id: export.id,
+        status: export.status,
+        size_bytes: export.size_bytes,
+        created_at: export.created_at.to_rfc3339(),
+        completed_at: export.completed_at.map(|t| t.to_rfc3339()),
+        expires_at: export.expires_at.map(|t| t.to_rfc3339()),
+        download_url,
+    }
+}
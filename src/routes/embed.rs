@@ -0,0 +1,295 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    config::Config,
+    entities::{game, user},
+    error::AppError,
+    media::{DEFAULT_THUMBNAIL_SIZE, THUMBNAIL_SIZES},
+    state::AppState,
+};
+
+/// Name reported as the oEmbed provider.
+const PROVIDER_NAME: &str = "AirCade";
+
+/// Default size of the embedded player (16:9).
+const DEFAULT_EMBED_WIDTH: u32 = 640;
+const DEFAULT_EMBED_HEIGHT: u32 = 360;
+
+/// Longest description included in embed metadata, in characters.
+const MAX_EMBED_DESCRIPTION_LENGTH: usize = 200;
+
+/// How long consumers may cache oEmbed responses, in seconds.
+const OEMBED_CACHE_AGE: u32 = 3600;
+
+/// Embed card route, nested under `/games` alongside the main game router.
+pub fn router() -> Router<AppState> {
+    Router::new().route("/{id}/embed", get(get_game_embed))
+}
+
+/// oEmbed provider endpoint: `GET /api/v1/oembed`.
+pub fn api_router() -> Router<AppState> {
+    Router::new().route("/oembed", get(oembed))
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EmbedCreator {
+    username: String,
+    display_name: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GameEmbedResponse {
+    id: Uuid,
+    title: String,
+    /// Plain-text excerpt of the game's description.
+    description: Option<String>,
+    thumbnail_url: Option<String>,
+    creator: EmbedCreator,
+    min_players: i32,
+    max_players: i32,
+    play_count: i64,
+    /// The game's page on the website.
+    url: String,
+    /// The player page meant to be loaded in an iframe.
+    embed_url: String,
+    width: u32,
+    height: u32,
+    /// Ready-to-paste iframe markup.
+    html: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OEmbedQuery {
+    url: String,
+    maxwidth: Option<u32>,
+    maxheight: Option<u32>,
+    format: Option<String>,
+}
+
+/// A `rich` oEmbed response, as defined by <https://oembed.com>.
+#[derive(Debug, Serialize)]
+struct OEmbedResponse {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    version: &'static str,
+    title: String,
+    author_name: String,
+    author_url: String,
+    provider_name: &'static str,
+    provider_url: String,
+    cache_age: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thumbnail_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thumbnail_width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thumbnail_height: Option<u32>,
+    html: String,
+    width: u32,
+    height: u32,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `GET /games/:id/embed` — Public metadata for an embeddable game card.
+///
+/// Only published public or unlisted games can be embedded.
+async fn get_game_embed(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_embeddable_game(&state.db, GameRef::Id(id)).await?;
+    let creator = find_creator(&state.db, game.owner_id).await?;
+
+    let embed_url = embed_url(&state.config, game.id);
+    let (width, height) = (DEFAULT_EMBED_WIDTH, DEFAULT_EMBED_HEIGHT);
+
+    Ok(Json(GameEmbedResponse {
+        id: game.id,
+        url: game_url(&state.config, game.id),
+        html: iframe_html(&embed_url, &game.title, width, height),
+        embed_url,
+        width,
+        height,
+        description: game.description.as_deref().and_then(excerpt),
+        title: game.title,
+        thumbnail_url: game.thumbnail,
+        creator: EmbedCreator {
+            username: creator.username,
+            display_name: creator.display_name,
+        },
+        min_players: game.min_players,
+        max_players: game.max_players,
+        play_count: game.play_count,
+    }))
+}
+
+/// `GET /oembed?url=` — oEmbed provider for game page and player URLs.
+///
+/// Only the JSON format is supported; other formats get `501 Not Implemented` as the spec
+/// requires.
+async fn oembed(
+    State(state): State<AppState>,
+    Query(query): Query<OEmbedQuery>,
+) -> Result<Response, AppError> {
+    if query.format.as_deref().is_some_and(|f| f != "json") {
+        return Ok(StatusCode::NOT_IMPLEMENTED.into_response());
+    }
+
+    let game_ref = parse_game_url(&state.config, &query.url)
+        .ok_or_else(|| AppError::NotFound("URL does not point to a game".to_string()))?;
+    let game = find_embeddable_game(&state.db, game_ref).await?;
+    let creator = find_creator(&state.db, game.owner_id).await?;
+
+    let (width, height) = fit_embed(query.maxwidth, query.maxheight);
+    let embed_url = embed_url(&state.config, game.id);
+    let frontend = frontend_base(&state.config);
+    let thumbnail_size = THUMBNAIL_SIZES
+        .iter()
+        .find(|s| s.name == DEFAULT_THUMBNAIL_SIZE)
+        .filter(|_| game.thumbnail.is_some());
+
+    let body = OEmbedResponse {
+        kind: "rich",
+        version: "1.0",
+        html: iframe_html(&embed_url, &game.title, width, height),
+        title: game.title,
+        author_name: creator
+            .display_name
+            .unwrap_or_else(|| creator.username.clone()),
+        author_url: format!("{frontend}/users/{}", creator.username),
+        provider_name: PROVIDER_NAME,
+        provider_url: frontend.to_string(),
+        cache_age: OEMBED_CACHE_AGE,
+        thumbnail_url: game.thumbnail,
+        thumbnail_width: thumbnail_size.map(|s| s.width),
+        thumbnail_height: thumbnail_size.map(|s| s.height),
+        width,
+        height,
+    };
+
+    Ok((
+        [(
+            header::CACHE_CONTROL,
+            format!("public, max-age={OEMBED_CACHE_AGE}"),
+        )],
+        Json(body),
+    )
+        .into_response())
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// A game referenced from a URL, by ID or slug.
+enum GameRef {
+    Id(Uuid),
+    Slug(String),
+}
+
+async fn find_embeddable_game(
+    db: &DatabaseConnection,
+    game_ref: GameRef,
+) -> Result<game::Model, AppError> {
+    let find = match game_ref {
+        GameRef::Id(id) => game::Entity::find_by_id(id),
+        GameRef::Slug(slug) => game::Entity::find().filter(game::Column::Slug.eq(slug)),
+    };
+
+    find.filter(game::Column::DeletedAt.is_null())
+        .filter(game::Column::Status.eq("published"))
+        .filter(game::Column::Visibility.ne("private"))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Game not found".to_string()))
+}
+
+async fn find_creator(db: &DatabaseConnection, user_id: Uuid) -> Result<user::Model, AppError> {
+    user::Entity::find_by_id(user_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Creator not found".to_string()))
+}
+
+fn frontend_base(config: &Config) -> &str {
+    config.frontend_url.trim_end_matches('/')
+}
+
+fn game_url(config: &Config, id: Uuid) -> String {
+    format!("{}/games/{id}", frontend_base(config))
+}
+
+fn embed_url(config: &Config, id: Uuid) -> String {
+    format!("{}/embed/{id}", frontend_base(config))
+}
+
+/// Recognize `{FRONTEND_URL}/games/{id-or-slug}` and `{FRONTEND_URL}/embed/{id-or-slug}`.
+fn parse_game_url(config: &Config, url: &str) -> Option<GameRef> {
+    let path = url.strip_prefix(frontend_base(config))?;
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let segment = path
+        .strip_prefix("/games/")
+        .or_else(|| path.strip_prefix("/embed/"))?
+        .trim_end_matches('/');
+
+    if segment.is_empty() || segment.contains('/') {
+        return None;
+    }
+    Some(Uuid::parse_str(segment).map_or_else(|_| GameRef::Slug(segment.to_string()), GameRef::Id))
+}
+
+/// Largest 16:9 player that fits within the requested bounds.
+fn fit_embed(max_width: Option<u32>, max_height: Option<u32>) -> (u32, u32) {
+    let width = max_width
+        .unwrap_or(DEFAULT_EMBED_WIDTH)
+        .min(DEFAULT_EMBED_WIDTH)
+        .min(max_height.map_or(u32::MAX, |h| h.saturating_mul(16) / 9))
+        .max(1);
+    (width, (width * 9 / 16).max(1))
+}
+
+fn iframe_html(src: &str, title: &str, width: u32, height: u32) -> String {
+    format!(
+        r#"<iframe src="{}" width="{width}" height="{height}" title="{}" frameborder="0" allow="autoplay; fullscreen" allowfullscreen></iframe>"#,
+        ammonia::clean_text(src),
+        ammonia::clean_text(title),
+    )
+}
+
+/// Collapse a description to a single line of plain text, shortened to
+/// [`MAX_EMBED_DESCRIPTION_LENGTH`] characters.
+fn excerpt(description: &str) -> Option<String> {
+    let text = description.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        return None;
+    }
+    if text.chars().count() <= MAX_EMBED_DESCRIPTION_LENGTH {
+        return Some(text);
+    }
+
+    let mut short: String = text
+        .chars()
+        .take(MAX_EMBED_DESCRIPTION_LENGTH - 1)
+        .collect();
+    short.truncate(short.trim_end().len());
+    short.push('…');
+    Some(short)
+}
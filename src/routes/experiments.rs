@@ -0,0 +1,360 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::get,
+};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    analytics::{EXPERIMENT_EXPOSURE, MAX_ANONYMOUS_ID_LENGTH},
+    audit,
+    auth::middleware::AdminUser,
+    entities::{analytics_event, experiment},
+    error::AppError,
+    experiments::{self, RUNNING, STATUSES, Variant},
+    routes::games::OptionalAuth,
+    state::AppState,
+};
+
+/// Longest experiment key, in characters.
+const MAX_KEY_LENGTH: usize = 64;
+
+/// Longest description, in characters.
+const MAX_DESCRIPTION_LENGTH: usize = 1000;
+
+/// Public experiments router: `/experiments`
+pub fn router() -> Router<AppState> {
+    Router::new().route("/assignments", get(get_assignments))
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AssignmentsQuery {
+    /// The client's own identifier, used when not signed in.
+    anonymous_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct AssignmentResponse {
+    experiment: String,
+    variant: String,
+}
+
+/// A new experiment.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateExperimentRequest {
+    /// Lowercase letters, digits, `-` and `_`.
+    key: String,
+    #[serde(flatten)]
+    experiment: ExperimentRequest,
+}
+
+/// Everything about an experiment but its key, for both creating and replacing one.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExperimentRequest {
+    description: Option<String>,
+    variants: Vec<Variant>,
+    /// `draft`, `running` or `stopped`. Defaults to `draft`.
+    status: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExperimentResponse {
+    id: Uuid,
+    key: String,
+    description: Option<String>,
+    variants: serde_json::Value,
+    status: String,
+    created_by: Option<Uuid>,
+    created_at: String,
+    updated_at: String,
+}
+
+impl From<experiment::Model> for ExperimentResponse {
+    fn from(e: experiment::Model) -> Self {
+        Self {
+            id: e.id,
+            key: e.key,
+            description: e.description,
+            variants: e.variants,
+            status: e.status,
+            created_by: e.created_by,
+            created_at: e.created_at.to_rfc3339(),
+            updated_at: e.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ListResponse<T> {
+    data: Vec<T>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `GET /experiments/assignments` — The caller's variant of every running experiment.
+///
+/// Works without signing in, given an `anonymousId`. Signed-in users are assigned by their
+/// user ID, so they keep their variants across devices. Every assignment returned is
+/// logged as an `experiment_exposure` analytics event.
+async fn get_assignments(
+    State(state): State<AppState>,
+    OptionalAuth(opt_user): OptionalAuth,
+    Query(query): Query<AssignmentsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let anonymous_id = query
+        .anonymous_id
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty());
+    if anonymous_id
+        .as_ref()
+        .is_some_and(|id| id.chars().count() > MAX_ANONYMOUS_ID_LENGTH)
+    {
+        return Err(AppError::BadRequest(format!(
+            "anonymousId must be at most {MAX_ANONYMOUS_ID_LENGTH} characters"
+        )));
+    }
+
+    let user_id = opt_user.map(|u| u.id);
+    let subject = match (user_id, &anonymous_id) {
+        (Some(id), _) => id.to_string(),
+        (None, Some(id)) => id.clone(),
+        (None, None) => {
+            return Err(AppError::BadRequest(
+                "anonymousId is required when not signed in".to_string(),
+            ));
+        }
+    };
+
+    let running = experiment::Entity::find()
+        .filter(experiment::Column::Status.eq(RUNNING))
+        .order_by_asc(experiment::Column::Key)
+        .all(&state.db)
+        .await?;
+
+    let assignments: Vec<AssignmentResponse> = running
+        .into_iter()
+        .filter_map(|e| {
+            let variants: Vec<Variant> = serde_json::from_value(e.variants).ok()?;
+            let variant = experiments::assign(&e.key, &subject, &variants)?;
+            Some(AssignmentResponse {
+                variant: variant.name.clone(),
+                experiment: e.key,
+            })
+        })
+        .collect();
+
+    let now = Utc::now().fixed_offset();
+    let exposures = assignments
+        .iter()
+        .map(|a| analytics_event::Model {
+            id: Uuid::new_v4(),
+            name: EXPERIMENT_EXPOSURE.to_string(),
+            user_id,
+            anonymous_id: if user_id.is_some() {
+                None
+            } else {
+                anonymous_id.clone()
+            },
+            game_id: None,
+            session_id: None,
+            properties: json!({ "experiment": a.experiment, "variant": a.variant }),
+            occurred_at: now,
+            received_at: now,
+        })
+        .collect();
+    state.analytics.record(&state.db, exposures);
+
+    Ok(Json(ListResponse { data: assignments }))
+}
+
+/// `GET /admin/experiments` — Every experiment, by key.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the database query fails.
+pub async fn list_experiments(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+) -> Result<impl IntoResponse, AppError> {
+    let experiments = experiment::Entity::find()
+        .order_by_asc(experiment::Column::Key)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(ListResponse {
+        data: experiments
+            .into_iter()
+            .map(ExperimentResponse::from)
+            .collect(),
+    }))
+}
+
+/// `POST /admin/experiments` — Create an experiment.
+///
+/// # Errors
+///
+/// Returns [`AppError::BadRequest`] if the experiment is invalid, [`AppError::Conflict`] if
+/// its key is taken, or [`AppError`] if a database operation fails.
+pub async fn create_experiment(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    headers: HeaderMap,
+    Json(req): Json<CreateExperimentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let key = req.key.trim().to_string();
+    let valid_key = (1..=MAX_KEY_LENGTH).contains(&key.len())
+        && key
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_');
+    if !valid_key {
+        return Err(AppError::BadRequest(format!(
+            "Key must be 1 to {MAX_KEY_LENGTH} lowercase letters, digits, '-' or '_'."
+        )));
+    }
+    let valid = validate(req.experiment)?;
+
+    let taken = experiment::Entity::find()
+        .filter(experiment::Column::Key.eq(&key))
+        .one(&state.db)
+        .await?
+        .is_some();
+    if taken {
+        return Err(AppError::Conflict(format!(
+            "An experiment with key '{key}' already exists."
+        )));
+    }
+
+    let id = Uuid::new_v4();
+    let now = Utc::now().fixed_offset();
+    let created = experiment::ActiveModel {
+        id: Set(id),
+        key: Set(key),
+        description: Set(valid.description),
+        variants: Set(valid.variants),
+        status: Set(valid.status),
+        created_by: Set(Some(admin.id)),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(&state.db)
+    .await?;
+
+    audit::Event::new("experiment.create", "experiment", id)
+        .after(snapshot(&created))
+        .record(&state.db, &admin, &headers)
+        .await;
+
+    Ok((StatusCode::CREATED, Json(ExperimentResponse::from(created))))
+}
+
+/// `PUT /admin/experiments/{id}` — Replace an experiment's description, variants and
+/// status. Its key cannot be changed.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if there is no experiment with that ID,
+/// [`AppError::BadRequest`] if the experiment is invalid, or [`AppError`] if a database
+/// operation fails.
+pub async fn update_experiment(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<ExperimentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let existing = find_experiment(&state.db, id).await?;
+    let valid = validate(req)?;
+    let before = snapshot(&existing);
+
+    let mut active: experiment::ActiveModel = existing.into();
+    active.description = Set(valid.description);
+    active.variants = Set(valid.variants);
+    active.status = Set(valid.status);
+    active.updated_at = Set(Utc::now().fixed_offset());
+    let updated = active.update(&state.db).await?;
+
+    audit::Event::new("experiment.update", "experiment", id)
+        .before(before)
+        .after(snapshot(&updated))
+        .record(&state.db, &admin, &headers)
+        .await;
+
+    Ok(Json(ExperimentResponse::from(updated)))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// An [`ExperimentRequest`] that passed validation.
+struct ValidExperiment {
+    description: Option<String>,
+    variants: serde_json::Value,
+    status: String,
+}
+
+fn validate(req: ExperimentRequest) -> Result<ValidExperiment, AppError> {
+    let description = req
+        .description
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty());
+    if description
+        .as_ref()
+        .is_some_and(|d| d.chars().count() > MAX_DESCRIPTION_LENGTH)
+    {
+        return Err(AppError::BadRequest(format!(
+            "Description must be at most {MAX_DESCRIPTION_LENGTH} characters."
+        )));
+    }
+
+    experiments::validate_variants(&req.variants).map_err(AppError::BadRequest)?;
+
+    let status = req.status.unwrap_or_else(|| STATUSES[0].to_string());
+    if !STATUSES.contains(&status.as_str()) {
+        return Err(AppError::BadRequest(format!(
+            "Invalid status '{status}'. Expected one of: {}",
+            STATUSES.join(", ")
+        )));
+    }
+
+    Ok(ValidExperiment {
+        description,
+        variants: serde_json::to_value(&req.variants).map_err(|e| AppError::Internal(e.into()))?,
+        status,
+    })
+}
+
+async fn find_experiment(db: &DatabaseConnection, id: Uuid) -> Result<experiment::Model, AppError> {
+    experiment::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Experiment not found.".to_string()))
+}
+
+/// The audited fields of an experiment.
+fn snapshot(e: &experiment::Model) -> serde_json::Value {
+    json!({
+        "key": e.key,
+        "variants": e.variants,
+        "status": e.status,
+    })
+}
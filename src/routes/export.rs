@@ -0,0 +1,425 @@
+use std::io::{Cursor, Read, Write};
+
+use axum::{
+    Json, Router,
+    extract::{DefaultBodyLimit, Multipart, Path, State},
+    http::{StatusCode, header},
+    response::IntoResponse,
+    routing::{get, post},
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, QueryFilter, QueryOrder,
+    TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use zip::{CompressionMethod, ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    auth::middleware::AuthUser,
+    entities::{game, game_asset, game_tag, tag},
+    error::AppError,
+    routes::games::{
+        ASSET_TYPES, check_storage_quota, find_active_game, store_blob, to_game_response,
+        unique_slug,
+    },
+    state::AppState,
+    storage,
+};
+
+/// Version of the archive layout written by [`export_game`].
+const FORMAT_VERSION: u32 = 1;
+
+/// Largest archive accepted by [`import_game`].
+const MAX_ARCHIVE_SIZE: usize = 50 * 1024 * 1024; // 50 MB
+
+/// Largest single file (code or asset) read from an archive.
+const MAX_ENTRY_SIZE: u64 = 10 * 1024 * 1024; // 10 MB
+
+const MANIFEST_PATH: &str = "game.json";
+const GAME_SCREEN_PATH: &str = "game-screen.js";
+const CONTROLLER_SCREEN_PATH: &str = "controller-screen.js";
+
+/// Export / import routes, nested under `/games` alongside the main game router.
+pub fn router() -> Router<AppState> {
+    Router::new().route("/{id}/export", get(export_game)).route(
+        "/import",
+        post(import_game).layer(DefaultBodyLimit::max(MAX_ARCHIVE_SIZE)),
+    )
+}
+
+// ============================================================================
+// Archive Types
+// ============================================================================
+
+/// `game.json` at the root of an exported archive.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+    format_version: u32,
+    title: String,
+    description: Option<String>,
+    technology: String,
+    min_players: i32,
+    max_players: i32,
+    /// Tag slugs; tags unknown to the importing environment are skipped.
+    #[serde(default)]
+    tags: Vec<String>,
+    /// File name of the asset used as the game's thumbnail.
+    thumbnail: Option<String>,
+    #[serde(default)]
+    assets: Vec<ManifestAsset>,
+    /// The game's draft controller manifest (controls, orientation, player counts).
+    #[serde(default)]
+    controller: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ManifestAsset {
+    /// Path of the file inside the archive.
+    path: String,
+    file_name: String,
+    file_type: String,
+}
+
+/// The contents of an uploaded archive, read and checked.
+struct ImportedArchive {
+    manifest: Manifest,
+    game_screen_code: Option<String>,
+    controller_screen_code: Option<String>,
+    assets: Vec<(ManifestAsset, Vec<u8>)>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `GET /games/:id/export` — Download a game's metadata, code and assets as a zip archive.
+async fn export_game(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+
+    if game.owner_id != user.id {
+        return Err(AppError::Forbidden(
+            "You are not the creator of this game".to_string(),
+        ));
+    }
+
+    let tags: Vec<String> = tag::Entity::find()
+        .inner_join(game_tag::Entity)
+        .filter(game_tag::Column::GameId.eq(game.id))
+        .order_by_asc(tag::Column::Slug)
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|t| t.slug)
+        .collect();
+
+    let assets = game_asset::Entity::find()
+        .filter(game_asset::Column::GameId.eq(game.id))
+        .filter(game_asset::Column::DeletedAt.is_null())
+        .order_by_asc(game_asset::Column::CreatedAt)
+        .all(&state.db)
+        .await?;
+
+    let mut files = Vec::with_capacity(assets.len());
+    for asset in assets {
+        let data = storage::read_asset(&asset, &state.config, &state.db).await?;
+        let entry = ManifestAsset {
+            path: format!("assets/{}-{}", asset.id, asset.file_name),
+            file_name: asset.file_name,
+            file_type: asset.file_type,
+        };
+        files.push((entry, data));
+    }
+
+    // `game.thumbnail` is the storage URL of one of the game's assets
+    let thumbnail = game.thumbnail.as_deref().and_then(|url| {
+        let file_name = url.strip_prefix(&format!("assets/{}/", game.id))?;
+        files
+            .iter()
+            .any(|(a, _)| a.file_name == file_name)
+            .then(|| file_name.to_string())
+    });
+
+    let manifest = Manifest {
+        format_version: FORMAT_VERSION,
+        title: game.title,
+        description: game.description,
+        technology: game.technology,
+        min_players: game.min_players,
+        max_players: game.max_players,
+        tags,
+        thumbnail,
+        assets: Vec::new(),
+        controller: game.manifest,
+    };
+    let code = (game.game_screen_code, game.controller_screen_code);
+
+    let archive = tokio::task::spawn_blocking(move || write_archive(manifest, code, files))
+        .await
+        .map_err(|e| AppError::Internal(e.into()))??;
+
+    let disposition = format!("attachment; filename=\"{}.zip\"", game.slug);
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        archive,
+    ))
+}
+
+/// `POST /games/import` — Create a private draft game from an archive produced by the export
+/// endpoint (multipart `file` field).
+async fn import_game(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    multipart: Multipart,
+) -> Result<impl IntoResponse, AppError> {
+    let data = read_archive_upload(multipart).await?;
+
+    let archive = tokio::task::spawn_blocking(move || read_archive(&data))
+        .await
+        .map_err(|e| AppError::Internal(e.into()))??;
+    let manifest = &archive.manifest;
+
+    let total_size = archive.assets.iter().map(|(_, data)| data.len()).sum();
+    check_storage_quota(&state, &user, total_size).await?;
+
+    let tags = tag::Entity::find()
+        .filter(tag::Column::Slug.is_in(manifest.tags.iter().map(String::as_str)))
+        .filter(tag::Column::RetiredAt.is_null())
+        .all(&state.db)
+        .await?;
+
+    let now = chrono::Utc::now();
+    let id = Uuid::new_v4();
+
+    // Write the blobs up front so the transaction below only touches rows
+    let mut stored = Vec::with_capacity(archive.assets.len());
+    for (entry, data) in archive.assets {
+        let asset_id = Uuid::new_v4();
+        let file_size = i32::try_from(data.len()).unwrap_or(i32::MAX);
+        let blob = store_blob(&state, user.id, id, asset_id, data, &entry.file_type).await?;
+        stored.push((asset_id, entry, file_size, blob));
+    }
+
+    let thumbnail = manifest
+        .thumbnail
+        .as_ref()
+        .filter(|name| stored.iter().any(|(_, a, _, _)| &a.file_name == *name))
+        .map(|name| format!("assets/{id}/{name}"));
+
+    let txn = state.db.begin().await?;
+
+    let game = game::ActiveModel {
+        id: ActiveValue::Set(id),
+        created_at: ActiveValue::Set(now.into()),
+        updated_at: ActiveValue::Set(now.into()),
+        owner_id: ActiveValue::Set(user.id),
+        title: ActiveValue::Set(manifest.title.clone()),
+        slug: ActiveValue::Set(unique_slug(&manifest.title, id)),
+        description: ActiveValue::Set(manifest.description.clone()),
+        thumbnail: ActiveValue::Set(thumbnail),
+        technology: ActiveValue::Set(manifest.technology.clone()),
+        min_players: ActiveValue::Set(manifest.min_players),
+        max_players: ActiveValue::Set(manifest.max_players),
+        status: ActiveValue::Set("draft".to_string()),
+        visibility: ActiveValue::Set("private".to_string()),
+        game_screen_code: ActiveValue::Set(archive.game_screen_code),
+        controller_screen_code: ActiveValue::Set(archive.controller_screen_code),
+        manifest: ActiveValue::Set(manifest.controller.clone()),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+
+    for (asset_id, entry, file_size, blob) in stored {
+        game_asset::ActiveModel {
+            id: ActiveValue::Set(asset_id),
+            created_at: ActiveValue::Set(now.into()),
+            game_id: ActiveValue::Set(id),
+            storage_url: ActiveValue::Set(format!("assets/{id}/{}", entry.file_name)),
+            file_name: ActiveValue::Set(entry.file_name),
+            file_type: ActiveValue::Set(entry.file_type),
+            file_size: ActiveValue::Set(file_size),
+            file_data: ActiveValue::Set(Vec::new()),
+            storage_backend: ActiveValue::Set(blob.backend),
+            storage_key: ActiveValue::Set(Some(blob.key)),
+            content_hash: ActiveValue::Set(Some(blob.content_hash)),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+    }
+
+    for t in &tags {
+        game_tag::ActiveModel {
+            game_id: ActiveValue::Set(id),
+            tag_id: ActiveValue::Set(t.id),
+        }
+        .insert(&txn)
+        .await?;
+    }
+
+    txn.commit().await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(to_game_response(game, None, None, true)),
+    ))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Build the zip archive for an export. CPU-bound; call it from `spawn_blocking`.
+fn write_archive(
+    mut manifest: Manifest,
+    (game_screen_code, controller_screen_code): (Option<String>, Option<String>),
+    files: Vec<(ManifestAsset, Vec<u8>)>,
+) -> Result<Vec<u8>, AppError> {
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    // Images, audio and fonts are already compressed
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let mut write = |path: &str, options: SimpleFileOptions, data: &[u8]| -> anyhow::Result<()> {
+        zip.start_file(path, options)?;
+        zip.write_all(data)?;
+        Ok(())
+    };
+
+    if let Some(code) = game_screen_code {
+        write(GAME_SCREEN_PATH, deflated, code.as_bytes())?;
+    }
+    if let Some(code) = controller_screen_code {
+        write(CONTROLLER_SCREEN_PATH, deflated, code.as_bytes())?;
+    }
+    for (entry, data) in files {
+        write(&entry.path, stored, &data)?;
+        manifest.assets.push(entry);
+    }
+    let manifest = serde_json::to_vec_pretty(&manifest)?;
+    write(MANIFEST_PATH, deflated, &manifest)?;
+
+    let archive = zip.finish().map_err(|e| AppError::Internal(e.into()))?;
+    Ok(archive.into_inner())
+}
+
+/// Read the `file` field of a multipart archive upload.
+async fn read_archive_upload(mut multipart: Multipart) -> Result<Vec<u8>, AppError> {
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Multipart error: {e}")))?
+    {
+        if field.name() == Some("file") {
+            let bytes = field
+                .bytes()
+                .await
+                .map_err(|e| AppError::BadRequest(format!("Could not read file: {e}")))?;
+            return Ok(bytes.to_vec());
+        }
+    }
+
+    Err(AppError::BadRequest("No file provided".to_string()))
+}
+
+/// Parse and check an uploaded archive. CPU-bound; call it from `spawn_blocking`.
+fn read_archive(data: &[u8]) -> Result<ImportedArchive, AppError> {
+    let mut zip = ZipArchive::new(Cursor::new(data))
+        .map_err(|e| AppError::BadRequest(format!("Invalid archive: {e}")))?;
+
+    let manifest = read_entry(&mut zip, MANIFEST_PATH)?
+        .ok_or_else(|| AppError::BadRequest(format!("Archive is missing {MANIFEST_PATH}")))?;
+    let mut manifest: Manifest = serde_json::from_slice(&manifest)
+        .map_err(|e| AppError::BadRequest(format!("Invalid {MANIFEST_PATH}: {e}")))?;
+
+    if manifest.format_version != FORMAT_VERSION {
+        return Err(AppError::BadRequest(format!(
+            "Unsupported archive format version {}",
+            manifest.format_version
+        )));
+    }
+    manifest.title = manifest.title.trim().to_string();
+    if manifest.title.is_empty() {
+        return Err(AppError::BadRequest("Title is required".to_string()));
+    }
+    if manifest.max_players < manifest.min_players {
+        return Err(AppError::BadRequest(
+            "maxPlayers must be >= minPlayers".to_string(),
+        ));
+    }
+
+    let game_screen_code = read_code(&mut zip, GAME_SCREEN_PATH)?;
+    let controller_screen_code = read_code(&mut zip, CONTROLLER_SCREEN_PATH)?;
+
+    let mut assets = Vec::with_capacity(manifest.assets.len());
+    for entry in std::mem::take(&mut manifest.assets) {
+        let is_thumbnail = entry.file_type == "image/webp";
+        if !ASSET_TYPES.contains(&entry.file_type.as_str()) && !is_thumbnail {
+            return Err(AppError::BadRequest(format!(
+                "Unsupported file type: {}",
+                entry.file_type
+            )));
+        }
+        if entry.file_name.is_empty() || entry.file_name.contains(['/', '\\']) {
+            return Err(AppError::BadRequest(format!(
+                "Invalid asset file name '{}'",
+                entry.file_name
+            )));
+        }
+        let data = read_entry(&mut zip, &entry.path)?
+            .ok_or_else(|| AppError::BadRequest(format!("Archive is missing {}", entry.path)))?;
+        assets.push((entry, data));
+    }
+
+    Ok(ImportedArchive {
+        manifest,
+        game_screen_code,
+        controller_screen_code,
+        assets,
+    })
+}
+
+/// Read a UTF-8 code file, if the archive has one.
+fn read_code(zip: &mut ZipArchive<Cursor<&[u8]>>, path: &str) -> Result<Option<String>, AppError> {
+    read_entry(zip, path)?
+        .map(|data| {
+            String::from_utf8(data)
+                .map_err(|_| AppError::BadRequest(format!("{path} is not valid UTF-8")))
+        })
+        .transpose()
+}
+
+/// Read one archive entry, enforcing [`MAX_ENTRY_SIZE`] on the decompressed bytes.
+fn read_entry(
+    zip: &mut ZipArchive<Cursor<&[u8]>>,
+    path: &str,
+) -> Result<Option<Vec<u8>>, AppError> {
+    let file = match zip.by_name(path) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(AppError::BadRequest(format!("Invalid archive: {e}"))),
+    };
+
+    // The declared size can lie, so cap the reader as well
+    let mut data = Vec::new();
+    file.take(MAX_ENTRY_SIZE + 1)
+        .read_to_end(&mut data)
+        .map_err(|e| AppError::BadRequest(format!("Could not read {path}: {e}")))?;
+    if data.len() as u64 > MAX_ENTRY_SIZE {
+        return Err(AppError::PayloadTooLarge(format!(
+            "{path} exceeds the 10 MB size limit"
+        )));
+    }
+
+    Ok(Some(data))
+}
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    response::IntoResponse,
+};
+use chrono::{DateTime, FixedOffset};
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    auth::middleware::AuthUser,
+    avatars,
+    entities::{collection, game, game_version, user},
+    error::AppError,
+    routes::games::{
+        GameSummaryResponse, PaginatedResponse, PaginationQuery, not_shadow_banned, to_game_summary,
+    },
+    routes::users::active_follows,
+    state::AppState,
+};
+
+// ============================================================================
+// Response Types
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FeedEventResponse {
+    /// `"game_published"`, `"version_published"` or `"collection_created"`.
+    kind: &'static str,
+    created_at: String,
+    creator: CreatorResponse,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    game: Option<GameSummaryResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version_number: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    collection: Option<FeedCollectionResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CreatorResponse {
+    id: Uuid,
+    username: String,
+    display_name: Option<String>,
+    avatar_url: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FeedCollectionResponse {
+    id: Uuid,
+    title: String,
+    description: Option<String>,
+}
+
+/// Something a followed creator did, before it is rendered.
+enum Event {
+    Version(game_version::Model, Box<game::Model>),
+    Collection(collection::Model),
+}
+
+impl Event {
+    const fn created_at(&self) -> DateTime<FixedOffset> {
+        match self {
+            Self::Version(v, _) => v.created_at,
+            Self::Collection(c) => c.created_at,
+        }
+    }
+
+    const fn creator_id(&self) -> Uuid {
+        match self {
+            Self::Version(_, g) => g.owner_id,
+            Self::Collection(c) => c.owner_id,
+        }
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `GET /users/me/feed` — Recent activity of the creators the authenticated user follows,
+/// newest first: games published, new versions and new collections.
+///
+/// The feed is assembled on read from the versions and collections tables; only public games
+/// and collections appear, and creators who hide their activity are left out.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if a database query fails.
+pub async fn get_my_feed(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let creator_ids: Vec<Uuid> = active_follows(user.id, false)
+        .filter(user::Column::HideActivity.eq(false))
+        .filter(not_shadow_banned(user::Column::Id, None))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|f| f.followee_id)
+        .collect();
+
+    // Both sources are read up to the end of the requested page, then merged
+    let window = pagination.offset + pagination.limit;
+
+    let versions = game_version::Entity::find()
+        .find_also_related(game::Entity)
+        .filter(game::Column::OwnerId.is_in(creator_ids.clone()))
+        .filter(game::Column::DeletedAt.is_null())
+        .filter(game::Column::Status.eq("published"))
+        .filter(game::Column::Visibility.eq("public"));
+    let collections = collection::Entity::find()
+        .filter(collection::Column::OwnerId.is_in(creator_ids))
+        .filter(collection::Column::Visibility.eq("public"));
+
+    let total =
+        versions.clone().count(&state.db).await? + collections.clone().count(&state.db).await?;
+
+    let mut events: Vec<Event> = versions
+        .order_by_desc(game_version::Column::CreatedAt)
+        .limit(window)
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .filter_map(|(v, g)| g.map(|g| Event::Version(v, Box::new(g))))
+        .collect();
+    events.extend(
+        collections
+            .order_by_desc(collection::Column::CreatedAt)
+            .limit(window)
+            .all(&state.db)
+            .await?
+            .into_iter()
+            .map(Event::Collection),
+    );
+    events.sort_by_key(|e| std::cmp::Reverse(e.created_at()));
+
+    #[allow(clippy::cast_possible_truncation)]
+    let page: Vec<Event> = events
+        .into_iter()
+        .skip(pagination.offset as usize)
+        .take(pagination.limit as usize)
+        .collect();
+    let creators = load_creators(&state.db, page.iter().map(Event::creator_id).collect()).await?;
+
+    Ok(Json(PaginatedResponse {
+        data: page
+            .into_iter()
+            .filter_map(|e| {
+                let creator = creators.get(&e.creator_id())?;
+                Some(to_feed_event(e, creator))
+            })
+            .collect(),
+        total,
+        offset: pagination.offset,
+        limit: pagination.limit,
+    }))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Load the users behind a page of events, keyed by id.
+async fn load_creators(
+    db: &DatabaseConnection,
+    ids: Vec<Uuid>,
+) -> Result<HashMap<Uuid, user::Model>, AppError> {
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    Ok(user::Entity::find()
+        .filter(user::Column::Id.is_in(ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u))
+        .collect())
+}
+
+fn to_feed_event(event: Event, creator: &user::Model) -> FeedEventResponse {
+    let created_at = event.created_at().to_rfc3339();
+    let creator = CreatorResponse {
+        id: creator.id,
+        avatar_url: avatars::url_or_fallback(creator.avatar_url.clone(), &creator.username),
+        username: creator.username.clone(),
+        display_name: creator.display_name.clone(),
+    };
+    match event {
+        Event::Version(v, g) => FeedEventResponse {
+            kind: if v.version_number == 1 {
+                "game_published"
+            } else {
+                "version_published"
+            },
+            created_at,
+            creator,
+            game: Some(to_game_summary(*g)),
+            version_number: Some(v.version_number),
+            collection: None,
+        },
+        Event::Collection(c) => FeedEventResponse {
+            kind: "collection_created",
+            created_at,
+            creator,
+            game: None,
+            version_number: None,
+            collection: Some(FeedCollectionResponse {
+                id: c.id,
+                title: c.title,
+                description: c.description,
+            }),
+        },
+    }
+}
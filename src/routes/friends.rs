@@ -0,0 +1,383 @@
+use std::collections::HashMap;
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait, sea_query::JoinType,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    auth::middleware::AuthUser,
+    avatars,
+    entities::{friendship, user},
+    error::AppError,
+    notifications,
+    routes::games::{PaginatedResponse, PaginationQuery},
+    routes::users::find_public_user,
+    state::AppState,
+};
+
+/// `friendship.status` of a request the addressee has not answered yet.
+const PENDING: &str = "pending";
+
+/// `friendship.status` of an accepted request.
+const ACCEPTED: &str = "accepted";
+
+// ============================================================================
+// Response Types
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FriendUserResponse {
+    id: Uuid,
+    username: String,
+    display_name: Option<String>,
+    avatar_url: String,
+}
+
+impl From<user::Model> for FriendUserResponse {
+    fn from(u: user::Model) -> Self {
+        Self {
+            id: u.id,
+            avatar_url: avatars::url_or_fallback(u.avatar_url, &u.username),
+            username: u.username,
+            display_name: u.display_name,
+        }
+    }
+}
+
+/// A friend request, described from the viewer's side: `user` is the other party.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FriendRequestResponse {
+    id: Uuid,
+    status: String,
+    user: FriendUserResponse,
+    created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FriendRequestsResponse {
+    /// Requests other users sent the viewer.
+    incoming: Vec<FriendRequestResponse>,
+    /// Requests the viewer sent that are still pending.
+    outgoing: Vec<FriendRequestResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FriendResponse {
+    id: Uuid,
+    username: String,
+    display_name: Option<String>,
+    avatar_url: String,
+    friends_since: String,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `POST /users/{username}/friend-request` — Send a friend request.
+///
+/// If `username` already sent the caller a request, that request is accepted instead.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if the user does not exist, [`AppError::BadRequest`] for the
+/// caller's own username, [`AppError::Conflict`] if they are already friends or a request is
+/// already pending, or [`AppError`] if a database operation fails.
+pub async fn send_friend_request(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(username): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let other = find_public_user(&state.db, &username).await?;
+    if other.id == user.id {
+        return Err(AppError::BadRequest(
+            "You cannot send a friend request to yourself.".to_string(),
+        ));
+    }
+
+    if let Some(existing) = find_between(&state.db, user.id, other.id).await? {
+        if existing.status == ACCEPTED {
+            return Err(AppError::Conflict("You are already friends.".to_string()));
+        }
+        if existing.requester_id == user.id {
+            return Err(AppError::Conflict(
+                "Friend request already sent.".to_string(),
+            ));
+        }
+
+        // They asked first: asking back accepts
+        let accepted = accept(&state.db, existing).await?;
+        notifications::friend_request_accepted(&state, other.id, &user).await;
+        return Ok((StatusCode::OK, Json(request_response(accepted, other))));
+    }
+
+    let created = friendship::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        requester_id: Set(user.id),
+        addressee_id: Set(other.id),
+        status: Set(PENDING.to_string()),
+        created_at: Set(Utc::now().fixed_offset()),
+        accepted_at: Set(None),
+    }
+    .insert(&state.db)
+    .await?;
+    notifications::friend_request_received(&state, other.id, &user).await;
+
+    Ok((StatusCode::CREATED, Json(request_response(created, other))))
+}
+
+/// `GET /users/me/friend-requests` — Pending friend requests to and from the authenticated
+/// user, newest first.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the database query fails.
+pub async fn list_my_friend_requests(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    let incoming = pending_requests(&state.db, user.id, true).await?;
+    let outgoing = pending_requests(&state.db, user.id, false).await?;
+    Ok(Json(FriendRequestsResponse { incoming, outgoing }))
+}
+
+/// `POST /users/me/friend-requests/{id}/accept` — Accept a friend request sent to the
+/// authenticated user.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if no pending request with that ID was sent to the caller,
+/// or [`AppError`] if a database operation fails.
+pub async fn accept_friend_request(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let request = friendship::Entity::find_by_id(id)
+        .filter(friendship::Column::AddresseeId.eq(user.id))
+        .filter(friendship::Column::Status.eq(PENDING))
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Friend request not found.".to_string()))?;
+
+    let requester_id = request.requester_id;
+    accept(&state.db, request).await?;
+    notifications::friend_request_accepted(&state, requester_id, &user).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `POST /users/me/friend-requests/{id}/decline` — Decline a friend request sent to the
+/// authenticated user, or withdraw one they sent.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if no pending request with that ID involves the caller, or
+/// [`AppError`] if the database operation fails.
+pub async fn decline_friend_request(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let deleted = friendship::Entity::delete_many()
+        .filter(friendship::Column::Id.eq(id))
+        .filter(friendship::Column::Status.eq(PENDING))
+        .filter(involving(user.id))
+        .exec(&state.db)
+        .await?;
+    if deleted.rows_affected == 0 {
+        return Err(AppError::NotFound("Friend request not found.".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GET /users/me/friends` — The authenticated user's friends, by username.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the database query fails.
+pub async fn list_my_friends(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let friends_since: HashMap<Uuid, String> = friendship::Entity::find()
+        .filter(involving(user.id))
+        .filter(friendship::Column::Status.eq(ACCEPTED))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|f| {
+            let other = if f.requester_id == user.id {
+                f.addressee_id
+            } else {
+                f.requester_id
+            };
+            let since = f.accepted_at.unwrap_or(f.created_at).to_rfc3339();
+            (other, since)
+        })
+        .collect();
+
+    let find = user::Entity::find()
+        .filter(user::Column::Id.is_in(friends_since.keys().copied()))
+        .filter(user::Column::DeletedAt.is_null())
+        .filter(user::Column::AccountStatus.eq("active"));
+    let total = find.clone().count(&state.db).await?;
+
+    let friends = find
+        .order_by_asc(user::Column::Username)
+        .offset(pagination.offset)
+        .limit(pagination.limit)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(PaginatedResponse {
+        data: friends
+            .into_iter()
+            .map(|u| FriendResponse {
+                friends_since: friends_since.get(&u.id).cloned().unwrap_or_default(),
+                id: u.id,
+                avatar_url: avatars::url_or_fallback(u.avatar_url, &u.username),
+                username: u.username,
+                display_name: u.display_name,
+            })
+            .collect(),
+        total,
+        offset: pagination.offset,
+        limit: pagination.limit,
+    }))
+}
+
+/// `DELETE /users/me/friends/{username}` — Remove a friend.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if the caller is not friends with `username`, or
+/// [`AppError`] if a database operation fails.
+pub async fn remove_friend(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(username): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let not_found = || AppError::NotFound("Friend not found.".to_string());
+    let other = user::Entity::find()
+        .filter(user::Column::Username.eq(&username))
+        .one(&state.db)
+        .await?
+        .ok_or_else(not_found)?;
+
+    let friendship = find_between(&state.db, user.id, other.id)
+        .await?
+        .filter(|f| f.status == ACCEPTED)
+        .ok_or_else(not_found)?;
+    friendship::Entity::delete_by_id(friendship.id)
+        .exec(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Friendships `user_id` is either side of.
+fn involving(user_id: Uuid) -> Condition {
+    Condition::any()
+        .add(friendship::Column::RequesterId.eq(user_id))
+        .add(friendship::Column::AddresseeId.eq(user_id))
+}
+
+/// The request or friendship between two users, whichever of them sent it.
+async fn find_between(
+    db: &DatabaseConnection,
+    a: Uuid,
+    b: Uuid,
+) -> Result<Option<friendship::Model>, AppError> {
+    Ok(friendship::Entity::find()
+        .filter(
+            Condition::any()
+                .add(
+                    Condition::all()
+                        .add(friendship::Column::RequesterId.eq(a))
+                        .add(friendship::Column::AddresseeId.eq(b)),
+                )
+                .add(
+                    Condition::all()
+                        .add(friendship::Column::RequesterId.eq(b))
+                        .add(friendship::Column::AddresseeId.eq(a)),
+                ),
+        )
+        .one(db)
+        .await?)
+}
+
+async fn accept(
+    db: &DatabaseConnection,
+    request: friendship::Model,
+) -> Result<friendship::Model, AppError> {
+    let mut active: friendship::ActiveModel = request.into();
+    active.status = Set(ACCEPTED.to_string());
+    active.accepted_at = Set(Some(Utc::now().fixed_offset()));
+    Ok(active.update(db).await?)
+}
+
+/// Pending requests sent to `user_id` when `incoming` is set, otherwise sent by them, whose
+/// other party is an active user.
+async fn pending_requests(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    incoming: bool,
+) -> Result<Vec<FriendRequestResponse>, AppError> {
+    let (by, other) = if incoming {
+        (
+            friendship::Column::AddresseeId,
+            friendship::Relation::Requester,
+        )
+    } else {
+        (
+            friendship::Column::RequesterId,
+            friendship::Relation::Addressee,
+        )
+    };
+
+    let rows = friendship::Entity::find()
+        .filter(by.eq(user_id))
+        .filter(friendship::Column::Status.eq(PENDING))
+        .join(JoinType::InnerJoin, other.def())
+        .filter(user::Column::DeletedAt.is_null())
+        .filter(user::Column::AccountStatus.eq("active"))
+        .select_also(user::Entity)
+        .order_by_desc(friendship::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(f, u)| u.map(|u| request_response(f, u)))
+        .collect())
+}
+
+fn request_response(request: friendship::Model, other: user::Model) -> FriendRequestResponse {
+    FriendRequestResponse {
+        id: request.id,
+        status: request.status,
+        user: other.into(),
+        created_at: request.created_at.to_rfc3339(),
+    }
+}
@@ -1,26 +1,87 @@
 use axum::{
-    extract::{Multipart, Path, Query, State}, http::StatusCode,
-    response::IntoResponse,
+    Json, Router,
+    body::Body,
+    extract::{Multipart, Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
     routing::{get, post, put},
-    Json,
-    Router,
 };
 use sea_orm::{
-    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
-    QueryFilter, QueryOrder, QuerySelect,
+    ActiveModelTrait, ActiveValue, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection,
+    DbBackend, EntityTrait, FromQueryResult, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+    Select, Statement, TransactionTrait,
+    sea_query::{OnConflict, Query as SeaQuery},
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::{
+    achievements, audit,
     auth::middleware::AuthUser,
-    entities::{game, game_asset, game_tag, game_version, tag, user},
-    error::AppError,
+    avatars, classroom,
+    config::Config,
+    entities::{
+        favorite, game, game_asset, game_collaborator, game_tag, game_template, game_version,
+        scheduled_publish, tag, user,
+    },
+    error::{AppError, code},
+    markdown, media,
+    routes::{legal, organizations, takedowns},
     state::AppState,
+    storage, text_moderation,
+    validation::{self, Diagnostic},
+    xp,
 };
 
+/// Maximum number of tags returned by the suggestion endpoint.
+const MAX_TAG_SUGGESTIONS: usize = 5;
+
+/// How many similar games are sampled when scoring tag co-occurrence.
+const SIMILAR_GAMES_SAMPLE: u64 = 200;
+
+/// Maximum number of games returned by the related games endpoint.
+const MAX_RELATED_GAMES: usize = 6;
+
+/// Ancestors followed at most when walking a fork chain (guards against cycles).
+const MAX_LINEAGE_DEPTH: usize = 50;
+
+/// Most games a single bulk request may touch.
+const MAX_BULK_GAMES: usize = 100;
+
+/// Visibilities a game may be set to.
+const VISIBILITIES: [&str; 3] = ["public", "unlisted", "private"];
+
+/// `game.owner_type` of games owned by their creator alone.
+pub(crate) const OWNER_TYPE_USER: &str = "user";
+
+/// `game.owner_type` of games in an organization's catalog.
+pub(crate) const OWNER_TYPE_ORGANIZATION: &str = "organization";
+
+/// `game.status` of a game a moderator took down. Only an approved appeal brings it back.
+pub(crate) const REMOVED_BY_MODERATION: &str = "removed_by_moderation";
+
+/// How long an editing lock lasts without being refreshed.
+const LOCK_DURATION_SECS: i64 = 5 * 60;
+
+/// File name prefix of generated thumbnail assets (`thumbnail-small.webp`, ...).
+const THUMBNAIL_FILE_PREFIX: &str = "thumbnail-";
+
+/// MIME types accepted for uploaded game assets.
+pub(crate) const ASSET_TYPES: &[&str] = &[
+    "image/png",
+    "image/jpeg",
+    "image/svg+xml",
+    "image/gif",
+    "audio/mpeg",
+    "audio/wav",
+    "audio/ogg",
+    "font/ttf",
+    "font/woff2",
+];
+
 /// Wraps an optional authenticated user (bearer token is optional for some routes).
-struct OptionalAuth(Option<user::Model>);
+pub(crate) struct OptionalAuth(pub(crate) Option<user::Model>);
 
 impl axum::extract::FromRequestParts<AppState> for OptionalAuth {
     type Rejection = AppError;
@@ -39,23 +100,47 @@ impl axum::extract::FromRequestParts<AppState> for OptionalAuth {
 /// Game management router.
 pub fn router() -> Router<AppState> {
     Router::new()
-        .route("/", post(create_game))
+        .route("/", get(list_games).post(create_game))
         .route(
             "/{id}",
             get(get_game).patch(update_game).delete(delete_game),
         )
-        .route("/{id}/publish", post(publish_game))
+        .route("/{id}/lock", post(lock_game).delete(unlock_game))
+        .route(
+            "/{id}/publish",
+            post(publish_game).delete(cancel_scheduled_publish),
+        )
+        .route("/{id}/validate", post(validate_game))
         .route("/{id}/archive", post(archive_game))
         .route("/{id}/unarchive", post(unarchive_game))
+        .route("/{id}/restore", post(restore_game))
         .route("/{id}/fork", post(fork_game))
+        .route("/{id}/forks", get(list_forks))
+        .route("/{id}/lineage", get(get_lineage))
         .route("/{id}/versions", get(list_versions))
+        .route("/{id}/changelog", get(get_changelog))
         .route("/{id}/versions/{version_number}", get(get_version))
+        .route(
+            "/{id}/versions/{version_number}/restore",
+            post(restore_version),
+        )
         .route("/{id}/assets", post(upload_asset).get(list_assets))
+        .route("/{id}/thumbnail", post(upload_thumbnail))
         .route(
             "/{id}/assets/{asset_id}",
             get(get_asset).delete(delete_asset),
         )
+        .route("/{id}/assets/{asset_id}/file", get(get_asset_file))
         .route("/{id}/tags", put(set_game_tags).get(get_game_tags))
+        .route("/{id}/tags/suggestions", get(suggest_game_tags))
+        .route("/{id}/related", get(list_related_games))
+        .route("/{id}/takedown", get(takedowns::get_takedown))
+        .route("/{id}/appeal", post(takedowns::appeal_takedown))
+        .route("/{id}/counter-notice", post(legal::file_counter_notice))
+        .route(
+            "/{id}/favorite",
+            post(favorite_game).delete(unfavorite_game),
+        )
 }
 
 /// Tags router.
@@ -63,6 +148,16 @@ pub fn tags_router() -> Router<AppState> {
     Router::new().route("/", get(list_tags))
 }
 
+/// Starter templates router.
+pub fn templates_router() -> Router<AppState> {
+    Router::new().route("/", get(list_templates))
+}
+
+/// Search router.
+pub fn search_router() -> Router<AppState> {
+    Router::new().route("/games", get(search_games))
+}
+
 // ============================================================================
 // Request / Response Types
 // ============================================================================
@@ -75,6 +170,10 @@ struct CreateGameRequest {
     technology: Option<String>,
     min_players: Option<i32>,
     max_players: Option<i32>,
+    /// Starter template whose code and player counts pre-fill the new game.
+    template_id: Option<Uuid>,
+    /// Organization whose catalog the game joins; the caller must be a member.
+    organization_id: Option<Uuid>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -88,12 +187,24 @@ struct UpdateGameRequest {
     visibility: Option<String>,
     game_screen_code: Option<String>,
     controller_screen_code: Option<String>,
+    /// Draft controller manifest; checked when the game is published.
+    manifest: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct PublishGameRequest {
     changelog: Option<String>,
+    /// Publish later instead of now; the scheduler creates the version at this time.
+    publish_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RestoreVersionRequest {
+    #[serde(default)]
+    republish: bool,
+    changelog: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -105,9 +216,9 @@ struct SetTagsRequest {
 #[derive(Debug, Deserialize)]
 pub struct PaginationQuery {
     #[serde(default = "default_offset")]
-    offset: u64,
+    pub(crate) offset: u64,
     #[serde(default = "default_limit")]
-    limit: u64,
+    pub(crate) limit: u64,
 }
 
 const fn default_offset() -> u64 {
@@ -118,6 +229,15 @@ const fn default_limit() -> u64 {
     20
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ListGamesQuery {
+    #[serde(default = "default_offset")]
+    offset: u64,
+    #[serde(default = "default_limit")]
+    limit: u64,
+    sort: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct MyGamesQuery {
     #[serde(default = "default_offset")]
@@ -127,14 +247,42 @@ pub struct MyGamesQuery {
     status: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkGamesRequest {
+    game_ids: Vec<Uuid>,
+    action: BulkAction,
+    /// Required for `set_visibility`.
+    visibility: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum BulkAction {
+    Archive,
+    Unarchive,
+    Delete,
+    SetVisibility,
+}
+
 #[derive(Debug, Deserialize)]
 struct TagCategoryQuery {
     category: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct SearchQuery {
+    #[serde(default)]
+    q: String,
+    #[serde(default = "default_offset")]
+    offset: u64,
+    #[serde(default = "default_limit")]
+    limit: u64,
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-struct GameResponse {
+pub(crate) struct GameResponse {
     id: Uuid,
     created_at: String,
     updated_at: String,
@@ -154,18 +302,28 @@ struct GameResponse {
     game_screen_code: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     controller_screen_code: Option<String>,
+    manifest: Option<serde_json::Value>,
     published_version_id: Option<Uuid>,
     play_count: i64,
     total_play_time: i64,
     avg_rating: f32,
     review_count: i64,
+    /// Collaborator currently holding the editing lock.
+    locked_by: Option<Uuid>,
+    lock_expires_at: Option<String>,
+    featured_at: Option<String>,
+    /// `"user"` or `"organization"`.
+    owner_type: String,
+    organization_id: Option<Uuid>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tags: Option<Vec<TagResponse>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_favorited: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-struct GameSummaryResponse {
+pub(crate) struct GameSummaryResponse {
     id: Uuid,
     created_at: String,
     updated_at: String,
@@ -182,25 +340,72 @@ struct GameSummaryResponse {
     play_count: i64,
     avg_rating: f32,
     review_count: i64,
+    featured_at: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-struct CreatorInfo {
+pub(crate) struct CreatorInfo {
     username: String,
     display_name: Option<String>,
-    avatar_url: Option<String>,
+    avatar_url: String,
+    /// Whether the creator carries the verified badge.
+    verified: bool,
 }
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-struct TagResponse {
+pub(crate) struct TagResponse {
     id: Uuid,
     name: String,
     slug: String,
     category: String,
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TrashedGameResponse {
+    #[serde(flatten)]
+    game: GameSummaryResponse,
+    deleted_at: String,
+    /// When the game stops being restorable.
+    restorable_until: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BulkItemResult {
+    game_id: Uuid,
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LineageEntryResponse {
+    id: Uuid,
+    /// `None` when the ancestor was deleted or is private to the caller.
+    title: Option<String>,
+    /// Kept even for unavailable ancestors so original creators are still credited.
+    creator: Option<CreatorInfo>,
+    available: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TemplateResponse {
+    id: Uuid,
+    slug: String,
+    name: String,
+    description: String,
+    technology: String,
+    min_players: i32,
+    max_players: i32,
+    game_screen_code: String,
+    controller_screen_code: String,
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct VersionSummaryResponse {
@@ -222,6 +427,8 @@ struct VersionDetailResponse {
     controller_screen_code: Option<String>,
     changelog: Option<String>,
     published_by_id: Option<Uuid>,
+    scan_findings: Option<serde_json::Value>,
+    manifest: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -237,11 +444,69 @@ struct AssetResponse {
 }
 
 #[derive(Debug, Serialize)]
-struct PaginatedResponse<T> {
-    data: Vec<T>,
-    total: u64,
-    offset: u64,
-    limit: u64,
+#[serde(rename_all = "camelCase")]
+struct TagSuggestionResponse {
+    #[serde(flatten)]
+    tag: TagResponse,
+    /// Why the tag was suggested: `keyword` and/or `similarGames`.
+    reasons: Vec<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReleaseNoteResponse {
+    version_number: i32,
+    published_at: String,
+    /// The creator's Markdown source.
+    changelog: Option<String>,
+    /// `changelog` rendered to sanitized HTML.
+    changelog_html: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScheduledPublishResponse {
+    game_id: Uuid,
+    publish_at: String,
+    requested_by: Uuid,
+    changelog: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LockResponse {
+    locked_by: Uuid,
+    lock_expires_at: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ValidationResponse {
+    valid: bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RestoreVersionResponse {
+    game: GameResponse,
+    /// The newly published version, when `republish` was requested.
+    version: Option<VersionSummaryResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ThumbnailResponse {
+    thumbnail_url: String,
+    sizes: Vec<AssetResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct PaginatedResponse<T> {
+    pub(crate) data: Vec<T>,
+    pub(crate) total: u64,
+    pub(crate) offset: u64,
+    pub(crate) limit: u64,
 }
 
 // ============================================================================
@@ -258,14 +523,48 @@ async fn create_game(
         return Err(AppError::BadRequest("Title is required".to_string()));
     }
 
-    let min = req.min_players.unwrap_or(1);
-    let max = req.max_players.unwrap_or(4);
+    let template = match req.template_id {
+        Some(template_id) => Some(
+            game_template::Entity::find_by_id(template_id)
+                .one(&state.db)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Template not found".to_string()))?,
+        ),
+        None => None,
+    };
+
+    // Explicit request values win over the template's
+    let min = req
+        .min_players
+        .or_else(|| template.as_ref().map(|t| t.min_players))
+        .unwrap_or(1);
+    let max = req
+        .max_players
+        .or_else(|| template.as_ref().map(|t| t.max_players))
+        .unwrap_or(4);
+    let technology = req
+        .technology
+        .or_else(|| template.as_ref().map(|t| t.technology.clone()))
+        .unwrap_or_else(|| "p5js".to_string());
+    let (game_screen_code, controller_screen_code) = template
+        .map(|t| (t.game_screen_code, t.controller_screen_code))
+        .unzip();
     if max < min {
         return Err(AppError::BadRequest(
             "maxPlayers must be >= minPlayers".to_string(),
         ));
     }
 
+    if let Some(organization_id) = req.organization_id
+        && organizations::accepted_role(&state.db, organization_id, user.id)
+            .await?
+            .is_none()
+    {
+        return Err(AppError::Forbidden(
+            "You are not a member of this organization".to_string(),
+        ));
+    }
+
     let now = chrono::Utc::now();
     let id = Uuid::new_v4();
     let slug = unique_slug(&req.title, id);
@@ -278,11 +577,22 @@ async fn create_game(
         title: ActiveValue::Set(req.title),
         slug: ActiveValue::Set(slug),
         description: ActiveValue::Set(req.description),
-        technology: ActiveValue::Set(req.technology.unwrap_or_else(|| "p5js".to_string())),
+        technology: ActiveValue::Set(technology),
         min_players: ActiveValue::Set(min),
         max_players: ActiveValue::Set(max),
         status: ActiveValue::Set("draft".to_string()),
         visibility: ActiveValue::Set("private".to_string()),
+        game_screen_code: ActiveValue::Set(game_screen_code),
+        controller_screen_code: ActiveValue::Set(controller_screen_code),
+        owner_type: ActiveValue::Set(
+            if req.organization_id.is_some() {
+                OWNER_TYPE_ORGANIZATION
+            } else {
+                OWNER_TYPE_USER
+            }
+            .to_string(),
+        ),
+        organization_id: ActiveValue::Set(req.organization_id),
         ..Default::default()
     };
 
@@ -303,19 +613,29 @@ async fn get_game(
     let game = find_active_game(&state.db, id).await?;
 
     let user_id = opt_user.as_ref().map(|u| u.id);
-    check_visibility(&game, user_id)?;
-
-    let is_creator = user_id == Some(game.owner_id);
+    let access = check_access(&state.db, &game, user_id).await?;
 
     let creator = load_creator(&state.db, game.owner_id).await?;
     let tags = load_game_tags(&state.db, game.id).await?;
+    let is_favorited = match user_id {
+        Some(uid) => Some(
+            favorite::Entity::find_by_id((uid, game.id))
+                .one(&state.db)
+                .await?
+                .is_some(),
+        ),
+        None => None,
+    };
 
-    Ok(Json(to_game_response(
+    let mut response = to_game_response(
         game,
         Some(creator),
         Some(tags),
-        is_creator,
-    )))
+        access >= GameAccess::Viewer,
+    );
+    response.is_favorited = is_favorited;
+
+    Ok(Json(response))
 }
 
 /// `PATCH /games/:id` — Update game metadata or code.
@@ -327,9 +647,13 @@ async fn update_game(
 ) -> Result<impl IntoResponse, AppError> {
     let game = find_active_game(&state.db, id).await?;
 
-    if game.owner_id != user.id {
-        return Err(AppError::Forbidden(
-            "You are not the creator of this game".to_string(),
+    require_editor(&state.db, &game, &user).await?;
+
+    if let Some((holder, _)) = active_lock(&game)
+        && holder != user.id
+    {
+        return Err(AppError::Conflict(
+            "Game is locked by another collaborator".to_string(),
         ));
     }
 
@@ -377,6 +701,9 @@ async fn update_game(
     if let Some(code) = req.controller_screen_code {
         active.controller_screen_code = ActiveValue::Set(Some(code));
     }
+    if let Some(manifest) = req.manifest {
+        active.manifest = ActiveValue::Set(Some(manifest));
+    }
 
     let game = active.update(&state.db).await?;
     Ok(Json(to_game_response(game, None, None, true)))
@@ -387,6 +714,7 @@ async fn delete_game(
     State(state): State<AppState>,
     AuthUser(user): AuthUser,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, AppError> {
     let game = find_active_game(&state.db, id).await?;
 
@@ -396,27 +724,56 @@ async fn delete_game(
         ));
     }
 
-    let now = chrono::Utc::now();
+    let moderated = game.owner_id != user.id;
+    soft_delete_game(&state.db, game).await?;
 
-    // Soft-delete the game
-    let mut active: game::ActiveModel = game.into();
-    active.deleted_at = ActiveValue::Set(Some(now.into()));
-    active.update(&state.db).await?;
+    if moderated {
+        audit::Event::new("game.delete", "game", id)
+            .before(serde_json::json!({ "deletedAt": null }))
+            .after(serde_json::json!({ "deletedAt": chrono::Utc::now().to_rfc3339() }))
+            .record(&state.db, &user, &headers)
+            .await;
+    }
 
-    // Soft-delete all associated assets
-    let assets = game_asset::Entity::find()
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `POST /games/:id/restore` — Bring a deleted game (and its assets) back out of the trash.
+async fn restore_game(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = game::Entity::find_by_id(id)
+        .filter(game::Column::OwnerId.eq(user.id))
+        .filter(game::Column::DeletedAt.gt(trash_cutoff(state.config.deleted_retention_days)))
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Game not found in trash".to_string()))?;
+    let Some(deleted_at) = game.deleted_at else {
+        return Err(AppError::NotFound("Game not found in trash".to_string()));
+    };
+
+    let txn = state.db.begin().await?;
+
+    game_asset::Entity::update_many()
+        .col_expr(
+            game_asset::Column::DeletedAt,
+            sea_orm::sea_query::Expr::value(Option::<sea_orm::prelude::DateTimeWithTimeZone>::None),
+        )
         .filter(game_asset::Column::GameId.eq(id))
-        .filter(game_asset::Column::DeletedAt.is_null())
-        .all(&state.db)
+        .filter(game_asset::Column::DeletedAt.gte(deleted_at))
+        .exec(&txn)
         .await?;
 
-    for asset in assets {
-        let mut a: game_asset::ActiveModel = asset.into();
-        a.deleted_at = ActiveValue::Set(Some(now.into()));
-        a.update(&state.db).await?;
-    }
+    let mut active: game::ActiveModel = game.into();
+    active.deleted_at = ActiveValue::Set(None);
+    active.updated_at = ActiveValue::Set(chrono::Utc::now().into());
+    let game = active.update(&txn).await?;
 
-    Ok(StatusCode::NO_CONTENT)
+    txn.commit().await?;
+
+    Ok(Json(to_game_response(game, None, None, true)))
 }
 
 /// `POST /games/:id/publish` — Publish a game by creating an immutable version snapshot.
@@ -427,71 +784,38 @@ async fn publish_game(
     Path(id): Path<Uuid>,
     Json(req): Json<PublishGameRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    if !user.email_verified {
-        return Err(AppError::Unprocessable(
-            "EMAIL_NOT_VERIFIED".to_string(),
-            "Email must be verified to publish games".to_string(),
-        ));
-    }
+    ensure_can_publish(&user)?;
 
     let game = find_active_game(&state.db, id).await?;
 
-    if game.owner_id != user.id {
-        return Err(AppError::Forbidden(
-            "You are not the creator of this game".to_string(),
-        ));
-    }
-
-    if game.title.trim().is_empty() {
-        return Err(AppError::Unprocessable(
-            "INVALID_GAME".to_string(),
-            "Game must have a title".to_string(),
-        ));
-    }
-
-    let screen_empty = game
-        .game_screen_code
-        .as_deref()
-        .is_none_or(|c| c.trim().is_empty());
-    let ctrl_empty = game
-        .controller_screen_code
-        .as_deref()
-        .is_none_or(|c| c.trim().is_empty());
-    if screen_empty && ctrl_empty {
-        return Err(AppError::Unprocessable(
-            "INVALID_GAME".to_string(),
-            "Game must have at least one non-empty canvas code".to_string(),
-        ));
+    require_editor(&state.db, &game, &user).await?;
+
+    ensure_publishable(&game)?;
+    let scan_findings = ensure_valid_code(&game, &state.config)?;
+    let flag = text_moderation::screen(
+        &state.config.text_moderation,
+        &[
+            ("title", &game.title),
+            (
+                "description",
+                game.description.as_deref().unwrap_or_default(),
+            ),
+            ("changelog", req.changelog.as_deref().unwrap_or_default()),
+        ],
+    )
+    .await?;
+
+    if let Some(publish_at) = req.publish_at {
+        let scheduled = schedule_publish(&state, &game, &user, publish_at, req.changelog).await?;
+        text_moderation::report(&state.db, flag, "game", game.id).await;
+        return Ok(scheduled.into_response());
     }
 
-    // Determine next version number
-    let version_count = game_version::Entity::find()
-        .filter(game_version::Column::GameId.eq(game.id))
-        .count(&state.db)
-        .await?;
-
-    #[allow(clippy::cast_possible_truncation)]
-    let version_number = (version_count + 1) as i32;
-
-    let version = game_version::ActiveModel {
-        id: ActiveValue::Set(Uuid::new_v4()),
-        created_at: ActiveValue::Set(chrono::Utc::now().into()),
-        game_id: ActiveValue::Set(game.id),
-        version_number: ActiveValue::Set(version_number),
-        game_screen_code: ActiveValue::Set(game.game_screen_code.clone()),
-        controller_screen_code: ActiveValue::Set(game.controller_screen_code.clone()),
-        changelog: ActiveValue::Set(req.changelog),
-        published_by_id: ActiveValue::Set(Some(user.id)),
-        change_log: ActiveValue::NotSet,
-    };
-
-    let version = version.insert(&state.db).await?;
-
-    let mut active: game::ActiveModel = game.into();
-    active.status = ActiveValue::Set("published".to_string());
-    active.published_version_id = ActiveValue::Set(Some(version.id));
-    active.updated_at = ActiveValue::Set(chrono::Utc::now().into());
-    let game = active.update(&state.db).await?;
+    let (game, version) =
+        publish_new_version(&state.db, game, user.id, req.changelog, scan_findings).await?;
+    text_moderation::report(&state.db, flag, "game", game.id).await;
+    achievements::game_published(&state.db, &state.notification_hub, game.owner_id).await;
+    xp::game_published(&state.db, game.owner_id, game.id).await;
 
     #[derive(Serialize)]
     #[serde(rename_all = "camelCase")]
@@ -518,65 +842,301 @@ async fn publish_game(
                 published_version_id: game.published_version_id,
             },
         }),
+    )
+        .into_response())
+}
+
+/// Record (or replace) the game's pending publish for the scheduler.
+async fn schedule_publish(
+    state: &AppState,
+    game: &game::Model,
+    user: &user::Model,
+    publish_at: chrono::DateTime<chrono::Utc>,
+    changelog: Option<String>,
+) -> Result<impl IntoResponse, AppError> {
+    if publish_at <= chrono::Utc::now() {
+        return Err(AppError::BadRequest(
+            "publishAt must be in the future".to_string(),
+        ));
+    }
+
+    let pending = scheduled_publish::ActiveModel {
+        game_id: ActiveValue::Set(game.id),
+        created_at: ActiveValue::Set(chrono::Utc::now().into()),
+        publish_at: ActiveValue::Set(publish_at.into()),
+        requested_by: ActiveValue::Set(user.id),
+        changelog: ActiveValue::Set(changelog),
+    };
+
+    scheduled_publish::Entity::insert(pending)
+        .on_conflict(
+            OnConflict::column(scheduled_publish::Column::GameId)
+                .update_columns([
+                    scheduled_publish::Column::CreatedAt,
+                    scheduled_publish::Column::PublishAt,
+                    scheduled_publish::Column::RequestedBy,
+                    scheduled_publish::Column::Changelog,
+                ])
+                .to_owned(),
+        )
+        .exec_without_returning(&state.db)
+        .await?;
+
+    let scheduled = scheduled_publish::Entity::find_by_id(game.id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Scheduled publish vanished after insert"))?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ScheduledPublishResponse {
+            game_id: scheduled.game_id,
+            publish_at: scheduled.publish_at.to_string(),
+            requested_by: scheduled.requested_by,
+            changelog: scheduled.changelog,
+        }),
     ))
 }
 
-/// `POST /games/:id/archive` — Archive a game.
-async fn archive_game(
+/// `DELETE /games/:id/publish` — Cancel a scheduled publish.
+async fn cancel_scheduled_publish(
     State(state): State<AppState>,
     AuthUser(user): AuthUser,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, AppError> {
     let game = find_active_game(&state.db, id).await?;
+    require_editor(&state.db, &game, &user).await?;
 
-    if game.owner_id != user.id && user.role != "moderator" && user.role != "admin" {
-        return Err(AppError::Forbidden(
-            "You are not authorized to archive this game".to_string(),
-        ));
-    }
-
-    if game.status == "archived" {
-        return Err(AppError::Unprocessable(
-            "ALREADY_ARCHIVED".to_string(),
-            "Game is already archived".to_string(),
+    let result = scheduled_publish::Entity::delete_by_id(game.id)
+        .exec(&state.db)
+        .await?;
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound(
+            "No publish is scheduled for this game".to_string(),
         ));
     }
 
-    let mut active: game::ActiveModel = game.into();
-    active.status = ActiveValue::Set("archived".to_string());
-    active.updated_at = ActiveValue::Set(chrono::Utc::now().into());
-    let game = active.update(&state.db).await?;
-
-    Ok(Json(to_game_response(game, None, None, false)))
+    Ok(StatusCode::NO_CONTENT)
 }
 
-/// `POST /games/:id/unarchive` — Restore an archived game.
-async fn unarchive_game(
+/// `POST /games/:id/validate` — Check the game's code without publishing it.
+async fn validate_game(
     State(state): State<AppState>,
     AuthUser(user): AuthUser,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, AppError> {
     let game = find_active_game(&state.db, id).await?;
 
-    if game.owner_id != user.id {
-        return Err(AppError::Forbidden(
-            "You are not the creator of this game".to_string(),
-        ));
-    }
+    require_editor(&state.db, &game, &user).await?;
 
-    if game.status != "archived" {
-        return Err(AppError::Unprocessable(
-            "NOT_ARCHIVED".to_string(),
-            "Game is not currently archived".to_string(),
+    let diagnostics = run_validation(&game, &state.config);
+
+    Ok(Json(ValidationResponse {
+        valid: !validation::has_errors(&diagnostics),
+        diagnostics,
+    }))
+}
+
+/// `POST /games/:id/versions/:versionNumber/restore` — Copy a version's code back into the draft.
+///
+/// With `republish: true` the restored code is also published as a new version, giving
+/// creators a one-step rollback after a bad release.
+async fn restore_version(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path((id, version_number)): Path<(Uuid, i32)>,
+    req: Option<Json<RestoreVersionRequest>>,
+) -> Result<impl IntoResponse, AppError> {
+    let req = req.map(|Json(r)| r).unwrap_or_default();
+    let game = find_active_game(&state.db, id).await?;
+
+    if game.owner_id != user.id {
+        return Err(AppError::Forbidden(
+            "You are not the creator of this game".to_string(),
         ));
     }
 
-    let new_status = if game.published_version_id.is_some() {
-        "published"
+    if req.republish {
+        ensure_can_publish(&user)?;
+    }
+
+    let source = game_version::Entity::find()
+        .filter(game_version::Column::GameId.eq(id))
+        .filter(game_version::Column::VersionNumber.eq(version_number))
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Version not found".to_string()))?;
+
+    let txn = state.db.begin().await?;
+
+    let mut active: game::ActiveModel = game.into();
+    active.game_screen_code = ActiveValue::Set(source.game_screen_code);
+    active.controller_screen_code = ActiveValue::Set(source.controller_screen_code);
+    active.manifest = ActiveValue::Set(source.manifest);
+    active.updated_at = ActiveValue::Set(chrono::Utc::now().into());
+    let game = active.update(&txn).await?;
+
+    let (game, version) = if req.republish {
+        ensure_publishable(&game)?;
+        let scan_findings = ensure_valid_code(&game, &state.config)?;
+        let changelog = req
+            .changelog
+            .or_else(|| Some(format!("Restored from version {version_number}")));
+        let (game, version) =
+            publish_new_version(&txn, game, user.id, changelog, scan_findings).await?;
+        (game, Some(to_version_summary(version)))
     } else {
-        "draft"
+        (game, None)
     };
 
+    txn.commit().await?;
+    if version.is_some() {
+        achievements::game_published(&state.db, &state.notification_hub, game.owner_id).await;
+        xp::game_published(&state.db, game.owner_id, game.id).await;
+    }
+
+    Ok(Json(RestoreVersionResponse {
+        game: to_game_response(game, None, None, true),
+        version,
+    }))
+}
+
+/// `POST /games/:id/archive` — Archive a game.
+async fn archive_game(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+
+    if game.owner_id != user.id && user.role != "moderator" && user.role != "admin" {
+        return Err(AppError::Forbidden(
+            "You are not authorized to archive this game".to_string(),
+        ));
+    }
+
+    if game.status == REMOVED_BY_MODERATION {
+        return Err(removed_by_moderation());
+    }
+    if game.status == "archived" {
+        return Err(AppError::Unprocessable(
+            code::ALREADY_ARCHIVED,
+            "Game is already archived".to_string(),
+        ));
+    }
+
+    let previous_status = game.status.clone();
+    let mut active: game::ActiveModel = game.into();
+    active.status = ActiveValue::Set("archived".to_string());
+    active.updated_at = ActiveValue::Set(chrono::Utc::now().into());
+    let game = active.update(&state.db).await?;
+
+    if game.owner_id != user.id {
+        audit::Event::new("game.archive", "game", id)
+            .before(serde_json::json!({ "status": previous_status }))
+            .after(serde_json::json!({ "status": game.status }))
+            .record(&state.db, &user, &headers)
+            .await;
+    }
+
+    Ok(Json(to_game_response(game, None, None, false)))
+}
+
+/// `POST /games/:id/lock` — Take (or refresh) the editing lock so other collaborators' edits
+/// are rejected until it is released or expires.
+async fn lock_game(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+    require_editor(&state.db, &game, &user).await?;
+
+    let now = chrono::Utc::now();
+    let expires_at = now + chrono::Duration::seconds(LOCK_DURATION_SECS);
+
+    // Conditional update so two collaborators racing for the lock cannot both win
+    let result = game::Entity::update_many()
+        .col_expr(
+            game::Column::LockedBy,
+            sea_orm::sea_query::Expr::value(user.id),
+        )
+        .col_expr(
+            game::Column::LockExpiresAt,
+            sea_orm::sea_query::Expr::value(expires_at),
+        )
+        .filter(game::Column::Id.eq(id))
+        .filter(
+            Condition::any()
+                .add(game::Column::LockedBy.is_null())
+                .add(game::Column::LockedBy.eq(user.id))
+                .add(game::Column::LockExpiresAt.lt(now)),
+        )
+        .exec(&state.db)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(AppError::Conflict(
+            "Game is locked by another collaborator".to_string(),
+        ));
+    }
+
+    Ok(Json(LockResponse {
+        locked_by: user.id,
+        lock_expires_at: expires_at.fixed_offset().to_string(),
+    }))
+}
+
+/// `DELETE /games/:id/lock` — Release the editing lock (the holder, or the creator at any time).
+async fn unlock_game(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+    require_editor(&state.db, &game, &user).await?;
+
+    if let Some((holder, _)) = active_lock(&game)
+        && holder != user.id
+        && game.owner_id != user.id
+    {
+        return Err(AppError::Forbidden(
+            "Only the lock holder or the game creator can release the lock".to_string(),
+        ));
+    }
+
+    let mut active: game::ActiveModel = game.into();
+    active.locked_by = ActiveValue::Set(None);
+    active.lock_expires_at = ActiveValue::Set(None);
+    active.update(&state.db).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `POST /games/:id/unarchive` — Restore an archived game.
+async fn unarchive_game(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+
+    if game.owner_id != user.id {
+        return Err(AppError::Forbidden(
+            "You are not the creator of this game".to_string(),
+        ));
+    }
+
+    if game.status != "archived" {
+        return Err(AppError::Unprocessable(
+            code::NOT_ARCHIVED,
+            "Game is not currently archived".to_string(),
+        ));
+    }
+
+    let new_status = unarchived_status(&game);
+
     let mut active: game::ActiveModel = game.into();
     active.status = ActiveValue::Set(new_status.to_string());
     active.updated_at = ActiveValue::Set(chrono::Utc::now().into());
@@ -595,7 +1155,7 @@ async fn fork_game(
 
     let pub_version_id = source.published_version_id.ok_or_else(|| {
         AppError::Unprocessable(
-            "NO_PUBLISHED_VERSION".to_string(),
+            code::NO_PUBLISHED_VERSION,
             "The source game has no published version".to_string(),
         )
     })?;
@@ -624,6 +1184,7 @@ async fn fork_game(
         visibility: ActiveValue::Set("private".to_string()),
         game_screen_code: ActiveValue::Set(published_version.game_screen_code),
         controller_screen_code: ActiveValue::Set(published_version.controller_screen_code),
+        manifest: ActiveValue::Set(published_version.manifest),
         forked_from_id: ActiveValue::Set(Some(source.id)),
         ..Default::default()
     };
@@ -636,6 +1197,85 @@ async fn fork_game(
     ))
 }
 
+/// `GET /games/:id/forks` — List a game's direct public forks (paginated, newest first).
+async fn list_forks(
+    State(state): State<AppState>,
+    OptionalAuth(opt_user): OptionalAuth,
+    Path(id): Path<Uuid>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+    check_access(&state.db, &game, opt_user.as_ref().map(|u| u.id)).await?;
+
+    let find =
+        listed_games(opt_user.as_ref().map(|u| u.id)).filter(game::Column::ForkedFromId.eq(id));
+    let total = find.clone().count(&state.db).await?;
+
+    let forks = find
+        .order_by_desc(game::Column::CreatedAt)
+        .offset(pagination.offset)
+        .limit(pagination.limit)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(PaginatedResponse {
+        data: forks.into_iter().map(to_game_summary).collect(),
+        total,
+        offset: pagination.offset,
+        limit: pagination.limit,
+    }))
+}
+
+/// `GET /games/:id/lineage` — The chain of games this one was forked from, parent first.
+#[allow(clippy::items_after_statements)]
+async fn get_lineage(
+    State(state): State<AppState>,
+    OptionalAuth(opt_user): OptionalAuth,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+    let user_id = opt_user.as_ref().map(|u| u.id);
+    check_access(&state.db, &game, user_id).await?;
+
+    let mut data: Vec<LineageEntryResponse> = Vec::new();
+    let mut seen = vec![game.id];
+    let mut next = game.forked_from_id;
+
+    while let Some(ancestor_id) = next
+        && !seen.contains(&ancestor_id)
+        && data.len() < MAX_LINEAGE_DEPTH
+    {
+        seen.push(ancestor_id);
+        let Some(ancestor) = game::Entity::find_by_id(ancestor_id).one(&state.db).await? else {
+            break;
+        };
+        next = ancestor.forked_from_id;
+
+        let available = ancestor.deleted_at.is_none()
+            && (ancestor.visibility != "private"
+                || game_access(&state.db, &ancestor, user_id).await? > GameAccess::None);
+        let creator = match load_creator(&state.db, ancestor.owner_id).await {
+            Ok(creator) => Some(creator),
+            Err(AppError::NotFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+
+        data.push(LineageEntryResponse {
+            id: ancestor.id,
+            title: available.then_some(ancestor.title),
+            creator,
+            available,
+        });
+    }
+
+    #[derive(Serialize)]
+    struct LineageResponse {
+        data: Vec<LineageEntryResponse>,
+    }
+
+    Ok(Json(LineageResponse { data }))
+}
+
 /// `GET /games/:id/versions` — List all published versions (paginated).
 async fn list_versions(
     State(state): State<AppState>,
@@ -646,7 +1286,7 @@ async fn list_versions(
     let game = find_active_game(&state.db, id).await?;
 
     let user_id = opt_user.as_ref().map(|u| u.id);
-    check_visibility(&game, user_id)?;
+    check_access(&state.db, &game, user_id).await?;
 
     let total = game_version::Entity::find()
         .filter(game_version::Column::GameId.eq(id))
@@ -669,6 +1309,34 @@ async fn list_versions(
     }))
 }
 
+/// `GET /games/:id/changelog` — Release notes of every published version, newest first.
+async fn get_changelog(
+    State(state): State<AppState>,
+    OptionalAuth(opt_user): OptionalAuth,
+    Path(id): Path<Uuid>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+    check_access(&state.db, &game, opt_user.as_ref().map(|u| u.id)).await?;
+
+    let find = game_version::Entity::find().filter(game_version::Column::GameId.eq(id));
+    let total = find.clone().count(&state.db).await?;
+
+    let versions = find
+        .order_by_desc(game_version::Column::VersionNumber)
+        .offset(pagination.offset)
+        .limit(pagination.limit)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(PaginatedResponse {
+        data: versions.into_iter().map(to_release_note).collect(),
+        total,
+        offset: pagination.offset,
+        limit: pagination.limit,
+    }))
+}
+
 /// `GET /games/:id/versions/:versionNumber` — Get a specific version with full code.
 async fn get_version(
     State(state): State<AppState>,
@@ -678,7 +1346,7 @@ async fn get_version(
     let game = find_active_game(&state.db, id).await?;
 
     let user_id = opt_user.as_ref().map(|u| u.id);
-    check_visibility(&game, user_id)?;
+    check_access(&state.db, &game, user_id).await?;
 
     let version = game_version::Entity::find()
         .filter(game_version::Column::GameId.eq(id))
@@ -696,97 +1364,123 @@ async fn get_version(
         controller_screen_code: version.controller_screen_code,
         changelog: version.changelog,
         published_by_id: version.published_by_id,
+        scan_findings: version.scan_findings,
+        manifest: version.manifest,
     }))
 }
 
 /// `POST /games/:id/assets` — Upload a file asset.
-#[allow(clippy::items_after_statements)]
+///
+/// Sending a `purpose=thumbnail` form field alongside an image also regenerates the game's
+/// thumbnails from it.
 async fn upload_asset(
     State(state): State<AppState>,
     AuthUser(user): AuthUser,
     Path(id): Path<Uuid>,
-    mut multipart: Multipart,
+    multipart: Multipart,
 ) -> Result<impl IntoResponse, AppError> {
     let game = find_active_game(&state.db, id).await?;
 
-    if game.owner_id != user.id {
-        return Err(AppError::Forbidden(
-            "You are not the creator of this game".to_string(),
-        ));
-    }
-
-    const MAX_FILE_SIZE: usize = 10 * 1024 * 1024; // 10 MB
-    const ALLOWED_TYPES: &[&str] = &[
-        "image/png",
-        "image/jpeg",
-        "image/svg+xml",
-        "image/gif",
-        "audio/mpeg",
-        "audio/wav",
-        "audio/ogg",
-        "font/ttf",
-        "font/woff2",
-    ];
-
-    let mut found_file_name = String::new();
-    let mut found_data: Vec<u8> = Vec::new();
-    let mut found_file_type = String::new();
-
-    while let Some(field) = multipart
-        .next_field()
-        .await
-        .map_err(|e| AppError::BadRequest(format!("Multipart error: {e}")))?
-    {
-        if field.name() == Some("file") {
-            found_file_name = field.file_name().unwrap_or("upload").to_string();
-            found_file_type = field
-                .content_type()
-                .unwrap_or("application/octet-stream")
-                .to_string();
-            let bytes = field
-                .bytes()
-                .await
-                .map_err(|e| AppError::BadRequest(format!("Could not read file: {e}")))?;
-            found_data = bytes.to_vec();
-        }
-    }
+    require_editor(&state.db, &game, &user).await?;
 
-    if found_data.is_empty() {
-        return Err(AppError::BadRequest("No file provided".to_string()));
-    }
+    let upload = read_upload(multipart).await?;
 
-    if found_data.len() > MAX_FILE_SIZE {
-        return Err(AppError::PayloadTooLarge(
-            "File exceeds the 10 MB size limit".to_string(),
-        ));
+    if !ASSET_TYPES.contains(&upload.file_type.as_str()) {
+        return Err(AppError::BadRequest(format!(
+            "Unsupported file type: {}",
+            upload.file_type
+        )));
     }
 
-    if !ALLOWED_TYPES.contains(&found_file_type.as_str()) {
+    let is_thumbnail = upload.purpose.as_deref() == Some("thumbnail");
+    if is_thumbnail && !media::THUMBNAIL_SOURCE_TYPES.contains(&upload.file_type.as_str()) {
         return Err(AppError::BadRequest(format!(
-            "Unsupported file type: {found_file_type}"
+            "Unsupported thumbnail type: {}",
+            upload.file_type
         )));
     }
 
+    // Assets count against the game owner's quota, whoever uploads them
+    let owner = if game.owner_id == user.id {
+        user
+    } else {
+        user::Entity::find_by_id(game.owner_id)
+            .one(&state.db)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Game owner not found".to_string()))?
+    };
+    check_storage_quota(&state, &owner, upload.data.len()).await?;
+
     let asset_id = Uuid::new_v4();
-    let storage_url = format!("assets/{id}/{found_file_name}");
+    let storage_url = format!("assets/{id}/{}", upload.file_name);
+    let thumbnail_source = is_thumbnail.then(|| upload.data.clone());
+    let file_size = i32::try_from(upload.data.len()).unwrap_or(i32::MAX);
+
+    let blob = store_blob(
+        &state,
+        owner.id,
+        id,
+        asset_id,
+        upload.data,
+        &upload.file_type,
+    )
+    .await?;
 
     let asset = game_asset::ActiveModel {
         id: ActiveValue::Set(asset_id),
         created_at: ActiveValue::Set(chrono::Utc::now().into()),
         game_id: ActiveValue::Set(id),
-        file_name: ActiveValue::Set(found_file_name),
-        file_type: ActiveValue::Set(found_file_type),
-        file_size: ActiveValue::Set(i32::try_from(found_data.len()).unwrap_or(i32::MAX)),
-        file_data: ActiveValue::Set(found_data),
+        file_name: ActiveValue::Set(upload.file_name),
+        file_type: ActiveValue::Set(upload.file_type),
+        file_size: ActiveValue::Set(file_size),
+        file_data: ActiveValue::Set(Vec::new()),
         storage_url: ActiveValue::Set(storage_url),
+        storage_backend: ActiveValue::Set(blob.backend),
+        storage_key: ActiveValue::Set(Some(blob.key)),
+        content_hash: ActiveValue::Set(Some(blob.content_hash)),
         ..Default::default()
     };
 
     let asset = asset.insert(&state.db).await?;
 
+    if let Some(source) = thumbnail_source {
+        store_thumbnails(&state, game, source).await?;
+    }
+
     Ok((StatusCode::CREATED, Json(to_asset_response(asset))))
 }
 
+/// `POST /games/:id/thumbnail` — Upload a thumbnail image, generating all standard sizes.
+async fn upload_thumbnail(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+    multipart: Multipart,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+
+    require_editor(&state.db, &game, &user).await?;
+
+    let upload = read_upload(multipart).await?;
+
+    if !media::THUMBNAIL_SOURCE_TYPES.contains(&upload.file_type.as_str()) {
+        return Err(AppError::BadRequest(format!(
+            "Unsupported thumbnail type: {}",
+            upload.file_type
+        )));
+    }
+
+    let (thumbnail_url, sizes) = store_thumbnails(&state, game, upload.data).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ThumbnailResponse {
+            thumbnail_url,
+            sizes: sizes.into_iter().map(to_asset_response).collect(),
+        }),
+    ))
+}
+
 /// `GET /games/:id/assets` — List all assets for a game.
 async fn list_assets(
     State(state): State<AppState>,
@@ -795,9 +1489,9 @@ async fn list_assets(
 ) -> Result<impl IntoResponse, AppError> {
     let game = find_active_game(&state.db, id).await?;
 
-    if game.owner_id != user.id {
+    if game_access(&state.db, &game, Some(user.id)).await? < GameAccess::Viewer {
         return Err(AppError::Forbidden(
-            "You are not the creator of this game".to_string(),
+            "You do not have access to this game".to_string(),
         ));
     }
 
@@ -825,9 +1519,9 @@ async fn get_asset(
 ) -> Result<impl IntoResponse, AppError> {
     let game = find_active_game(&state.db, id).await?;
 
-    if game.owner_id != user.id {
+    if game_access(&state.db, &game, Some(user.id)).await? < GameAccess::Viewer {
         return Err(AppError::Forbidden(
-            "You are not the creator of this game".to_string(),
+            "You do not have access to this game".to_string(),
         ));
     }
 
@@ -841,19 +1535,18 @@ async fn get_asset(
     Ok(Json(to_asset_response(asset)))
 }
 
-/// `DELETE /games/:id/assets/:assetId` — Soft-delete an asset.
-async fn delete_asset(
+/// `GET /games/:id/assets/:asset_id/file` — Serve the raw asset bytes.
+///
+/// Assets are immutable once uploaded, so the asset ID doubles as a strong `ETag` and
+/// responses can be cached for a year.
+async fn get_asset_file(
     State(state): State<AppState>,
-    AuthUser(user): AuthUser,
+    OptionalAuth(opt_user): OptionalAuth,
     Path((id, asset_id)): Path<(Uuid, Uuid)>,
-) -> Result<impl IntoResponse, AppError> {
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
     let game = find_active_game(&state.db, id).await?;
-
-    if game.owner_id != user.id {
-        return Err(AppError::Forbidden(
-            "You are not the creator of this game".to_string(),
-        ));
-    }
+    check_access(&state.db, &game, opt_user.as_ref().map(|u| u.id)).await?;
 
     let asset = game_asset::Entity::find_by_id(asset_id)
         .filter(game_asset::Column::GameId.eq(id))
@@ -862,20 +1555,250 @@ async fn delete_asset(
         .await?
         .ok_or_else(|| AppError::NotFound("Asset not found".to_string()))?;
 
-    let mut a: game_asset::ActiveModel = asset.into();
-    a.deleted_at = ActiveValue::Set(Some(chrono::Utc::now().into()));
-    a.update(&state.db).await?;
+    let etag = format!("\"{}\"", asset.id);
+    // Private games must not end up in shared caches
+    let cache_control = if game.visibility == "private" {
+        "private, max-age=31536000, immutable"
+    } else {
+        "public, max-age=31536000, immutable"
+    };
+
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|t| t.trim() == etag || t.trim() == "*"));
+
+    let builder = Response::builder()
+        .header(header::ETAG, &etag)
+        .header(header::CACHE_CONTROL, cache_control);
+
+    let response = if not_modified {
+        builder.status(StatusCode::NOT_MODIFIED).body(Body::empty())
+    } else {
+        let data = storage::read_asset(&asset, &state.config, &state.db).await?;
+        builder
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, &asset.file_type)
+            .header(header::CONTENT_LENGTH, data.len())
+            .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+            // Uploaded SVGs must not be able to run scripts on the API origin
+            .header(
+                header::CONTENT_SECURITY_POLICY,
+                "default-src 'none'; style-src 'unsafe-inline'; sandbox",
+            )
+            .body(Body::from(data))
+    };
+
+    response.map_err(|e| AppError::Internal(e.into()))
+}
+
+/// `DELETE /games/:id/assets/:assetId` — Soft-delete an asset.
+async fn delete_asset(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path((id, asset_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+
+    require_editor(&state.db, &game, &user).await?;
+
+    let asset = game_asset::Entity::find_by_id(asset_id)
+        .filter(game_asset::Column::GameId.eq(id))
+        .filter(game_asset::Column::DeletedAt.is_null())
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Asset not found".to_string()))?;
+
+    let mut a: game_asset::ActiveModel = asset.into();
+    a.deleted_at = ActiveValue::Set(Some(chrono::Utc::now().into()));
+    a.update(&state.db).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// `GET /games/:id/tags/suggestions` — Propose tags from the game's title and description and
+/// from the tags of games that share them.
+#[allow(clippy::items_after_statements)]
+async fn suggest_game_tags(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+    require_editor(&state.db, &game, &user).await?;
+
+    let catalog = tag::Entity::find()
+        .filter(tag::Column::RetiredAt.is_null())
+        .all(&state.db)
+        .await?;
+    let current: Vec<Uuid> = game_tag::Entity::find()
+        .filter(game_tag::Column::GameId.eq(id))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|gt| gt.tag_id)
+        .collect();
+
+    // Keyword matches weigh more in the title than in the description
+    let title_words = keywords(&game.title);
+    let description_words = keywords(game.description.as_deref().unwrap_or_default());
+    let mut scores: HashMap<Uuid, (u32, Vec<&'static str>)> = HashMap::new();
+    for t in &catalog {
+        let score = 3 * u32::from(tag_matches(t, &title_words))
+            + 2 * u32::from(tag_matches(t, &description_words));
+        if score > 0 {
+            scores.insert(t.id, (score, vec!["keyword"]));
+        }
+    }
+
+    // Tags that co-occur with the current and keyword-matched tags on other live games
+    let seeds: Vec<Uuid> = current.iter().chain(scores.keys()).copied().collect();
+    if !seeds.is_empty() {
+        let similar: Vec<Uuid> = listed_games(None)
+            .select_only()
+            .column(game::Column::Id)
+            .inner_join(game_tag::Entity)
+            .filter(game_tag::Column::TagId.is_in(seeds))
+            .filter(game::Column::Id.ne(id))
+            .distinct()
+            .limit(SIMILAR_GAMES_SAMPLE)
+            .into_tuple()
+            .all(&state.db)
+            .await?;
+
+        if !similar.is_empty() {
+            for gt in game_tag::Entity::find()
+                .filter(game_tag::Column::GameId.is_in(similar))
+                .all(&state.db)
+                .await?
+            {
+                let (score, reasons) = scores.entry(gt.tag_id).or_default();
+                *score += 1;
+                if !reasons.contains(&"similarGames") {
+                    reasons.push("similarGames");
+                }
+            }
+        }
+    }
+
+    let mut ranked: Vec<(u32, Vec<&'static str>, tag::Model)> = catalog
+        .into_iter()
+        .filter(|t| !current.contains(&t.id))
+        .filter_map(|t| {
+            let (score, reasons) = scores.remove(&t.id)?;
+            Some((score, reasons, t))
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.2.name.cmp(&b.2.name)));
+
+    #[derive(Serialize)]
+    struct SuggestionsResponse {
+        data: Vec<TagSuggestionResponse>,
+    }
+
+    Ok(Json(SuggestionsResponse {
+        data: ranked
+            .into_iter()
+            .take(MAX_TAG_SUGGESTIONS)
+            .map(|(_, reasons, t)| TagSuggestionResponse {
+                tag: to_tag_response(t),
+                reasons,
+            })
+            .collect(),
+    }))
+}
+
+/// `GET /games/:id/related` — Published games sharing tags with this one, ranked by tag
+/// overlap (plus a bonus for an overlapping player range), then by play count.
+#[allow(clippy::items_after_statements)]
+async fn list_related_games(
+    State(state): State<AppState>,
+    OptionalAuth(opt_user): OptionalAuth,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+    check_access(&state.db, &game, opt_user.as_ref().map(|u| u.id)).await?;
+
+    #[derive(Serialize)]
+    struct RelatedGamesResponse {
+        data: Vec<GameSummaryResponse>,
+    }
+
+    let tag_ids: Vec<Uuid> = game_tag::Entity::find()
+        .filter(game_tag::Column::GameId.eq(id))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|gt| gt.tag_id)
+        .collect();
+    if tag_ids.is_empty() {
+        return Ok(Json(RelatedGamesResponse { data: Vec::new() }));
+    }
+
+    let mut shared: HashMap<Uuid, u32> = HashMap::new();
+    for gt in game_tag::Entity::find()
+        .filter(game_tag::Column::TagId.is_in(tag_ids))
+        .filter(game_tag::Column::GameId.ne(id))
+        .all(&state.db)
+        .await?
+    {
+        *shared.entry(gt.game_id).or_default() += 1;
+    }
+
+    let candidates = listed_games(opt_user.as_ref().map(|u| u.id))
+        .filter(game::Column::Id.is_in(shared.keys().copied()))
+        .all(&state.db)
+        .await?;
+
+    let mut ranked: Vec<(u32, game::Model)> = candidates
+        .into_iter()
+        .map(|g| {
+            let overlap = shared.get(&g.id).copied().unwrap_or_default();
+            let players_match =
+                g.min_players <= game.max_players && game.min_players <= g.max_players;
+            (overlap + u32::from(players_match), g)
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then_with(|| b.1.play_count.cmp(&a.1.play_count))
+            .then_with(|| a.1.title.cmp(&b.1.title))
+    });
+
+    Ok(Json(RelatedGamesResponse {
+        data: ranked
+            .into_iter()
+            .take(MAX_RELATED_GAMES)
+            .map(|(_, g)| to_game_summary(g))
+            .collect(),
+    }))
+}
+
+/// `GET /templates` — List the starter templates available when creating a game.
+#[allow(clippy::items_after_statements)]
+async fn list_templates(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
+    let templates = game_template::Entity::find()
+        .order_by_asc(game_template::Column::Name)
+        .all(&state.db)
+        .await?;
+
+    #[derive(Serialize)]
+    struct TemplatesResponse {
+        data: Vec<TemplateResponse>,
+    }
+
+    Ok(Json(TemplatesResponse {
+        data: templates.into_iter().map(to_template_response).collect(),
+    }))
+}
+
 /// `GET /tags` — List all platform tags (optionally filtered by category).
 #[allow(clippy::items_after_statements)]
 async fn list_tags(
     State(state): State<AppState>,
     Query(query): Query<TagCategoryQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let mut find = tag::Entity::find();
+    let mut find = tag::Entity::find().filter(tag::Column::RetiredAt.is_null());
     if let Some(cat) = query.category {
         find = find.filter(tag::Column::Category.eq(cat));
     }
@@ -919,6 +1842,11 @@ async fn set_game_tags(
             "One or more tag IDs do not exist".to_string(),
         ));
     }
+    if found_tags.iter().any(|t| t.retired_at.is_some()) {
+        return Err(AppError::BadRequest(
+            "One or more tags have been retired".to_string(),
+        ));
+    }
 
     // Replace: delete existing, then insert new
     game_tag::Entity::delete_many()
@@ -968,6 +1896,114 @@ async fn get_game_tags(
     Ok(Json(TagsResponse { tags }))
 }
 
+/// `POST /games/:id/favorite` — Add a game to the authenticated user's favorites.
+async fn favorite_game(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+    check_visibility(&game, Some(user.id))?;
+
+    let fav = favorite::ActiveModel {
+        user_id: ActiveValue::Set(user.id),
+        game_id: ActiveValue::Set(game.id),
+        created_at: ActiveValue::Set(chrono::Utc::now().into()),
+    };
+
+    // Favoriting twice is a no-op
+    favorite::Entity::insert(fav)
+        .on_conflict(
+            OnConflict::columns([favorite::Column::UserId, favorite::Column::GameId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `DELETE /games/:id/favorite` — Remove a game from the authenticated user's favorites.
+async fn unfavorite_game(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    favorite::Entity::delete_by_id((user.id, id))
+        .exec(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GET /games?sort=` — List published public games (`recent`, `popular` or `trending`).
+async fn list_games(
+    State(state): State<AppState>,
+    OptionalAuth(viewer): OptionalAuth,
+    Query(query): Query<ListGamesQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let find = listed_games(viewer.map(|v| v.id));
+
+    let total = find.clone().count(&state.db).await?;
+
+    let games = apply_game_sort(find, query.sort.as_deref())?
+        .offset(query.offset)
+        .limit(query.limit)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(PaginatedResponse {
+        data: games.into_iter().map(to_game_summary).collect(),
+        total,
+        offset: query.offset,
+        limit: query.limit,
+    }))
+}
+
+/// `GET /search/games?q=` — Full-text search over published public games, ranked by relevance.
+async fn search_games(
+    State(state): State<AppState>,
+    OptionalAuth(viewer): OptionalAuth,
+    Query(query): Query<SearchQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let q = query.q.trim();
+    if q.is_empty() {
+        return Err(AppError::BadRequest("Search query is required".to_string()));
+    }
+    if q.chars().count() > MAX_SEARCH_QUERY_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Search query must be at most {MAX_SEARCH_QUERY_LENGTH} characters"
+        )));
+    }
+
+    let viewer = viewer.map(|v| v.id);
+    let (ranked_ids, total) = if state.db.get_database_backend() == DbBackend::Postgres {
+        search_game_ids_postgres(&state.db, q, viewer, query.offset, query.limit).await?
+    } else {
+        search_game_ids_fallback(&state.db, q, viewer, query.offset, query.limit).await?
+    };
+
+    let mut games_by_id: HashMap<Uuid, game::Model> = game::Entity::find()
+        .filter(game::Column::Id.is_in(ranked_ids.clone()))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|g| (g.id, g))
+        .collect();
+
+    Ok(Json(PaginatedResponse {
+        data: ranked_ids
+            .iter()
+            .filter_map(|id| games_by_id.remove(id))
+            .map(to_game_summary)
+            .collect(),
+        total,
+        offset: query.offset,
+        limit: query.limit,
+    }))
+}
+
 /// `GET /users/me/games` — List authenticated user's games.
 ///
 /// # Errors
@@ -1003,130 +2039,1115 @@ pub async fn list_my_games(
     }))
 }
 
-/// `GET /users/:username/games` — List a user's public games.
+/// `GET /users/me/games/trash` — List the authenticated user's deleted games that can still be
+/// restored, most recently deleted first.
 ///
 /// # Errors
 ///
-/// Returns [`AppError`] if the user is not found or the database query fails.
-pub async fn list_user_games(
+/// Returns [`AppError`] if the database query fails.
+pub async fn list_my_trash(
     State(state): State<AppState>,
-    Path(username): Path<String>,
+    AuthUser(user): AuthUser,
     Query(pagination): Query<PaginationQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let user = user::Entity::find()
-        .filter(user::Column::Username.eq(&username))
-        .filter(user::Column::DeletedAt.is_null())
-        .one(&state.db)
-        .await?
-        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
-
-    let total = game::Entity::find()
+    let find = game::Entity::find()
         .filter(game::Column::OwnerId.eq(user.id))
-        .filter(game::Column::DeletedAt.is_null())
-        .filter(game::Column::Status.eq("published"))
-        .filter(game::Column::Visibility.eq("public"))
-        .count(&state.db)
-        .await?;
+        .filter(game::Column::DeletedAt.gt(trash_cutoff(state.config.deleted_retention_days)));
 
-    let games = game::Entity::find()
-        .filter(game::Column::OwnerId.eq(user.id))
-        .filter(game::Column::DeletedAt.is_null())
-        .filter(game::Column::Status.eq("published"))
-        .filter(game::Column::Visibility.eq("public"))
-        .order_by_desc(game::Column::UpdatedAt)
+    let total = find.clone().count(&state.db).await?;
+
+    let games = find
+        .order_by_desc(game::Column::DeletedAt)
         .offset(pagination.offset)
         .limit(pagination.limit)
         .all(&state.db)
         .await?;
 
     Ok(Json(PaginatedResponse {
-        data: games.into_iter().map(to_game_summary).collect(),
+        data: games
+            .into_iter()
+            .filter_map(|g| {
+                let deleted_at = g.deleted_at?;
+                let restorable_until =
+                    deleted_at + chrono::Duration::days(state.config.deleted_retention_days);
+                Some(TrashedGameResponse {
+                    game: to_game_summary(g),
+                    deleted_at: deleted_at.to_string(),
+                    restorable_until: restorable_until.to_string(),
+                })
+            })
+            .collect(),
         total,
         offset: pagination.offset,
         limit: pagination.limit,
     }))
 }
 
-// ============================================================================
-// Helpers
-// ============================================================================
+/// `POST /users/me/games/bulk` — Archive, unarchive, delete or change the visibility of many of
+/// the authenticated user's games at once.
+///
+/// Every game gets its own result; games that cannot be changed are reported and skipped while
+/// the rest are applied in a single transaction.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the request is malformed or a database query fails.
+#[allow(clippy::items_after_statements)]
+pub async fn bulk_update_my_games(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(req): Json<BulkGamesRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if req.game_ids.is_empty() {
+        return Err(AppError::BadRequest(
+            "gameIds must not be empty".to_string(),
+        ));
+    }
+    if req.game_ids.len() > MAX_BULK_GAMES {
+        return Err(AppError::BadRequest(format!(
+            "At most {MAX_BULK_GAMES} games can be changed at once"
+        )));
+    }
+    let visibility = match (req.action, req.visibility) {
+        (BulkAction::SetVisibility, Some(v)) if VISIBILITIES.contains(&v.as_str()) => Some(v),
+        (BulkAction::SetVisibility, _) => {
+            return Err(AppError::BadRequest(
+                "visibility must be one of: public, unlisted, private".to_string(),
+            ));
+        }
+        _ => None,
+    };
 
-async fn find_active_game(db: &DatabaseConnection, id: Uuid) -> Result<game::Model, AppError> {
-    game::Entity::find_by_id(id)
+    let txn = state.db.begin().await?;
+
+    let mut games: HashMap<Uuid, game::Model> = game::Entity::find()
+        .filter(game::Column::Id.is_in(req.game_ids.clone()))
+        .filter(game::Column::OwnerId.eq(user.id))
         .filter(game::Column::DeletedAt.is_null())
-        .one(db)
+        .all(&txn)
         .await?
-        .ok_or_else(|| AppError::NotFound("Game not found".to_string()))
-}
+        .into_iter()
+        .map(|g| (g.id, g))
+        .collect();
 
-fn check_visibility(game: &game::Model, user_id: Option<Uuid>) -> Result<(), AppError> {
-    if game.visibility == "private" {
-        match user_id {
-            Some(uid) if uid == game.owner_id => Ok(()),
-            _ => Err(AppError::NotFound("Game not found".to_string())),
-        }
-    } else {
-        // public or unlisted
-        Ok(())
-    }
-}
+    let now = chrono::Utc::now();
+    let mut data = Vec::with_capacity(req.game_ids.len());
+
+    for game_id in req.game_ids {
+        let Some(game) = games.remove(&game_id) else {
+            data.push(BulkItemResult {
+                game_id,
+                success: false,
+                error: Some("Game not found".to_string()),
+            });
+            continue;
+        };
 
-async fn load_creator(db: &DatabaseConnection, user_id: Uuid) -> Result<CreatorInfo, AppError> {
-    let u = user::Entity::find_by_id(user_id)
-        .one(db)
-        .await?
-        .ok_or_else(|| AppError::NotFound("Creator not found".to_string()))?;
+        let error = match req.action {
+            BulkAction::Archive if game.status == REMOVED_BY_MODERATION => {
+                Some("Game was removed by a moderator")
+            }
+            BulkAction::Archive if game.status == "archived" => Some("Game is already archived"),
+            BulkAction::Unarchive if game.status != "archived" => {
+                Some("Game is not currently archived")
+            }
+            BulkAction::Delete => {
+                soft_delete_game(&txn, game).await?;
+                None
+            }
+            action => {
+                let status = match action {
+                    BulkAction::Archive => Some("archived"),
+                    BulkAction::Unarchive => Some(unarchived_status(&game)),
+                    _ => None,
+                };
+                let mut active: game::ActiveModel = game.into();
+                if let Some(status) = status {
+                    active.status = ActiveValue::Set(status.to_string());
+                }
+                if let Some(visibility) = &visibility {
+                    active.visibility = ActiveValue::Set(visibility.clone());
+                }
+                active.updated_at = ActiveValue::Set(now.into());
+                active.update(&txn).await?;
+                None
+            }
+        };
 
-    Ok(CreatorInfo {
-        username: u.username,
-        display_name: u.display_name,
-        avatar_url: u.avatar_url,
-    })
-}
+        data.push(BulkItemResult {
+            game_id,
+            success: error.is_none(),
+            error: error.map(str::to_string),
+        });
+    }
 
-async fn load_game_tags(
-    db: &DatabaseConnection,
-    game_id: Uuid,
-) -> Result<Vec<TagResponse>, AppError> {
-    let game_tags = game_tag::Entity::find()
-        .filter(game_tag::Column::GameId.eq(game_id))
-        .all(db)
-        .await?;
+    txn.commit().await?;
 
-    if game_tags.is_empty() {
-        return Ok(Vec::new());
+    #[derive(Serialize)]
+    struct BulkResponse {
+        data: Vec<BulkItemResult>,
     }
 
-    let tag_ids: Vec<Uuid> = game_tags.iter().map(|gt| gt.tag_id).collect();
-    let tags = tag::Entity::find()
-        .filter(tag::Column::Id.is_in(tag_ids))
-        .all(db)
-        .await?;
-
-    Ok(tags.into_iter().map(to_tag_response).collect())
+    Ok(Json(BulkResponse { data }))
 }
 
-/// Generate a URL-safe slug suffixed with the game ID to guarantee uniqueness.
-fn unique_slug(title: &str, id: Uuid) -> String {
-    let base: String = title
-        .to_lowercase()
-        .chars()
-        .map(|c| if c.is_alphanumeric() { c } else { '-' })
-        .collect::<String>()
-        .split('-')
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<_>>()
-        .join("-");
-
+/// `GET /users/me/favorites` — List the authenticated user's favorite games, most recent first.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the database query fails.
+pub async fn list_my_favorites(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let find = game::Entity::find()
+        .inner_join(favorite::Entity)
+        .filter(favorite::Column::UserId.eq(user.id))
+        .filter(game::Column::DeletedAt.is_null())
+        .filter(
+            Condition::any()
+                .add(game::Column::Visibility.ne("private"))
+                .add(game::Column::OwnerId.eq(user.id)),
+        );
+
+    let total = find.clone().count(&state.db).await?;
+
+    let games = find
+        .order_by_desc(favorite::Column::CreatedAt)
+        .offset(pagination.offset)
+        .limit(pagination.limit)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(PaginatedResponse {
+        data: games.into_iter().map(to_game_summary).collect(),
+        total,
+        offset: pagination.offset,
+        limit: pagination.limit,
+    }))
+}
+
+/// `GET /users/:username/games` — List a user's public games. Only the user can list them
+/// while they hide their games.
+///
+/// # Errors
+///
+/// Returns [`AppError::Forbidden`] if the user hides their games from the caller, or
+/// [`AppError`] if the user is not found or the database query fails.
+pub(crate) async fn list_user_games(
+    State(state): State<AppState>,
+    OptionalAuth(viewer): OptionalAuth,
+    Path(username): Path<String>,
+    Query(query): Query<ListGamesQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = user::Entity::find()
+        .filter(user::Column::Username.eq(&username))
+        .filter(user::Column::DeletedAt.is_null())
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    if user.hide_games && viewer.as_ref().is_none_or(|v| v.id != user.id) {
+        return Err(AppError::Forbidden(
+            "This user's games are private".to_string(),
+        ));
+    }
+
+    let find =
+        listed_games(viewer.as_ref().map(|v| v.id)).filter(game::Column::OwnerId.eq(user.id));
+
+    let total = find.clone().count(&state.db).await?;
+
+    let games = apply_game_sort(find, query.sort.as_deref())?
+        .offset(query.offset)
+        .limit(query.limit)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(PaginatedResponse {
+        data: games.into_iter().map(to_game_summary).collect(),
+        total,
+        offset: query.offset,
+        limit: query.limit,
+    }))
+}
+
+/// `GET /organizations/:slug/games` — An organization's catalog. Members see every game,
+/// including drafts and private ones; everyone else sees its published public games.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the organization is not found or the database query fails.
+pub(crate) async fn list_organization_games(
+    State(state): State<AppState>,
+    OptionalAuth(viewer): OptionalAuth,
+    Path(slug): Path<String>,
+    Query(query): Query<ListGamesQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let organization = organizations::find_organization(&state.db, &slug).await?;
+    let is_member = match &viewer {
+        Some(viewer) => organizations::accepted_role(&state.db, organization.id, viewer.id)
+            .await?
+            .is_some(),
+        None => false,
+    };
+
+    let find = if is_member {
+        game::Entity::find().filter(game::Column::DeletedAt.is_null())
+    } else {
+        listed_games(viewer.as_ref().map(|v| v.id))
+    }
+    .filter(game::Column::OrganizationId.eq(organization.id));
+
+    let total = find.clone().count(&state.db).await?;
+
+    let games = apply_game_sort(find, query.sort.as_deref())?
+        .offset(query.offset)
+        .limit(query.limit)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(PaginatedResponse {
+        data: games.into_iter().map(to_game_summary).collect(),
+        total,
+        offset: query.offset,
+        limit: query.limit,
+    }))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+pub(crate) async fn find_active_game(
+    db: &DatabaseConnection,
+    id: Uuid,
+) -> Result<game::Model, AppError> {
+    game::Entity::find_by_id(id)
+        .filter(game::Column::DeletedAt.is_null())
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Game not found".to_string()))
+}
+
+/// Games anyone may find in public listings: live, published and public, and not owned by a
+/// shadow-banned user other than `viewer`.
+pub(crate) fn listed_games(viewer: Option<Uuid>) -> Select<game::Entity> {
+    game::Entity::find()
+        .filter(game::Column::DeletedAt.is_null())
+        .filter(game::Column::Status.eq("published"))
+        .filter(game::Column::Visibility.eq("public"))
+        .filter(not_shadow_banned(game::Column::OwnerId, viewer))
+}
+
+/// Rows whose user `column` is not shadow-banned, or is `viewer`: shadow-banned users keep
+/// seeing their own content, so they cannot tell they are banned.
+pub(crate) fn not_shadow_banned(column: impl ColumnTrait, viewer: Option<Uuid>) -> Condition {
+    let mut condition = Condition::any().add(
+        column.not_in_subquery(
+            SeaQuery::select()
+                .column(user::Column::Id)
+                .from(user::Entity)
+                .and_where(user::Column::ShadowBanned.eq(true))
+                .to_owned(),
+        ),
+    );
+    if let Some(viewer) = viewer {
+        condition = condition.add(column.eq(viewer));
+    }
+    condition
+}
+
+/// Order a public game listing by the requested `sort` key (defaults to `recent`).
+fn apply_game_sort(
+    find: Select<game::Entity>,
+    sort: Option<&str>,
+) -> Result<Select<game::Entity>, AppError> {
+    match sort.unwrap_or("recent") {
+        "recent" => Ok(find.order_by_desc(game::Column::UpdatedAt)),
+        "popular" => Ok(find
+            .order_by_desc(game::Column::PlayCount)
+            .order_by_desc(game::Column::UpdatedAt)),
+        "trending" => Ok(find
+            .order_by_desc(game::Column::TrendingScore)
+            .order_by_desc(game::Column::PlayCount)
+            .order_by_desc(game::Column::UpdatedAt)),
+        other => Err(AppError::BadRequest(format!(
+            "Invalid sort '{other}'. Expected one of: recent, popular, trending"
+        ))),
+    }
+}
+
+pub(crate) fn check_visibility(game: &game::Model, user_id: Option<Uuid>) -> Result<(), AppError> {
+    if game.visibility == "private" || game.status == REMOVED_BY_MODERATION {
+        match user_id {
+            Some(uid) if uid == game.owner_id => Ok(()),
+            _ => Err(AppError::NotFound("Game not found".to_string())),
+        }
+    } else {
+        // public or unlisted
+        Ok(())
+    }
+}
+
+/// The unexpired editing lock on a game, as (holder, expiry).
+fn active_lock(game: &game::Model) -> Option<(Uuid, chrono::DateTime<chrono::FixedOffset>)> {
+    let holder = game.locked_by?;
+    let expires_at = game.lock_expires_at?;
+    (expires_at > chrono::Utc::now()).then_some((holder, expires_at))
+}
+
+/// Lowercased alphanumeric words of `text`.
+fn keywords(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether `words` name the tag: its slug as a phrase (`turn based`), or a word of at least
+/// four letters the slug starts with (`draw` for `drawing`).
+fn tag_matches(t: &tag::Model, words: &[String]) -> bool {
+    let phrase = format!(" {} ", keywords(&t.slug).join(" "));
+    let text = format!(" {} ", words.join(" "));
+
+    text.contains(&phrase)
+        || words
+            .iter()
+            .any(|w| w.len() >= 4 && t.slug.starts_with(w.as_str()))
+}
+
+/// What a user may do with a game, from least to most privileged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum GameAccess {
+    None,
+    Viewer,
+    Editor,
+    Owner,
+}
+
+/// Resolve a user's access to `game` from ownership, collaborator roles and, for organization
+/// games, membership of the owning organization. Every organization role may edit its games.
+pub(crate) async fn game_access(
+    db: &DatabaseConnection,
+    game: &game::Model,
+    user_id: Option<Uuid>,
+) -> Result<GameAccess, AppError> {
+    let Some(uid) = user_id else {
+        return Ok(GameAccess::None);
+    };
+    if uid == game.owner_id {
+        return Ok(GameAccess::Owner);
+    }
+    if let Some(organization_id) = game.organization_id
+        && organizations::accepted_role(db, organization_id, uid)
+            .await?
+            .is_some()
+    {
+        return Ok(GameAccess::Editor);
+    }
+
+    let collaborator = game_collaborator::Entity::find_by_id((game.id, uid))
+        .one(db)
+        .await?;
+
+    Ok(match collaborator.as_ref().map(|c| c.role.as_str()) {
+        Some("editor") => GameAccess::Editor,
+        Some("viewer") => GameAccess::Viewer,
+        _ => GameAccess::None,
+    })
+}
+
+/// Like [`check_visibility`], but collaborators can also see private games, and students only
+/// see their class's games. Games removed by moderation are hidden like private ones. Returns
+/// the user's access level.
+pub(crate) async fn check_access(
+    db: &DatabaseConnection,
+    game: &game::Model,
+    user_id: Option<Uuid>,
+) -> Result<GameAccess, AppError> {
+    let access = game_access(db, game, user_id).await?;
+    if (game.visibility == "private" || game.status == REMOVED_BY_MODERATION)
+        && access == GameAccess::None
+    {
+        return Err(AppError::NotFound("Game not found".to_string()));
+    }
+    if let Some(uid) = user_id
+        && let Some(class) = classroom::student_classroom(db, uid).await?
+        && !classroom::is_class_game(db, &class, game).await?
+    {
+        return Err(AppError::NotFound("Game not found".to_string()));
+    }
+    Ok(access)
+}
+
+/// Ensure `user` is the game's creator or an editor collaborator.
+pub(crate) async fn require_editor(
+    db: &DatabaseConnection,
+    game: &game::Model,
+    user: &user::Model,
+) -> Result<(), AppError> {
+    if game_access(db, game, Some(user.id)).await? < GameAccess::Editor {
+        return Err(AppError::Forbidden(
+            "You do not have permission to edit this game".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Games deleted before this instant have left the trash (and are due to be purged).
+fn trash_cutoff(retention_days: i64) -> sea_orm::prelude::DateTimeWithTimeZone {
+    (chrono::Utc::now() - chrono::Duration::days(retention_days)).into()
+}
+
+/// The error for changing the status of a game a moderator took down.
+fn removed_by_moderation() -> AppError {
+    AppError::Unprocessable(
+        code::REMOVED_BY_MODERATION,
+        "This game was removed by a moderator and stays unpublished unless an appeal is approved"
+            .to_string(),
+    )
+}
+
+/// Status a game returns to when unarchived.
+const fn unarchived_status(game: &game::Model) -> &'static str {
+    if game.published_version_id.is_some() {
+        "published"
+    } else {
+        "draft"
+    }
+}
+
+/// Soft-delete a game along with its assets.
+///
+/// Assets get the same `deleted_at` as the game so [`restore_game`] can bring them back together.
+async fn soft_delete_game<C: ConnectionTrait>(db: &C, game: game::Model) -> Result<(), AppError> {
+    let now: sea_orm::prelude::DateTimeWithTimeZone = chrono::Utc::now().into();
+    let id = game.id;
+
+    let mut active: game::ActiveModel = game.into();
+    active.deleted_at = ActiveValue::Set(Some(now));
+    active.update(db).await?;
+
+    game_asset::Entity::update_many()
+        .col_expr(
+            game_asset::Column::DeletedAt,
+            sea_orm::sea_query::Expr::value(now),
+        )
+        .filter(game_asset::Column::GameId.eq(id))
+        .filter(game_asset::Column::DeletedAt.is_null())
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+async fn load_creator(db: &DatabaseConnection, user_id: Uuid) -> Result<CreatorInfo, AppError> {
+    let u = user::Entity::find_by_id(user_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Creator not found".to_string()))?;
+
+    Ok(CreatorInfo {
+        avatar_url: avatars::url_or_fallback(u.avatar_url, &u.username),
+        username: u.username,
+        display_name: u.display_name,
+        verified: u.verified,
+    })
+}
+
+async fn load_game_tags(
+    db: &DatabaseConnection,
+    game_id: Uuid,
+) -> Result<Vec<TagResponse>, AppError> {
+    let game_tags = game_tag::Entity::find()
+        .filter(game_tag::Column::GameId.eq(game_id))
+        .all(db)
+        .await?;
+
+    if game_tags.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let tag_ids: Vec<Uuid> = game_tags.iter().map(|gt| gt.tag_id).collect();
+    let tags = tag::Entity::find()
+        .filter(tag::Column::Id.is_in(tag_ids))
+        .all(db)
+        .await?;
+
+    Ok(tags.into_iter().map(to_tag_response).collect())
+}
+
+const MAX_SEARCH_QUERY_LENGTH: usize = 200;
+
+/// Matching published public games, ranked by `ts_rank` (title > description > tags).
+///
+/// Matches against `game.search_vector`, the generated column behind the
+/// `idx_game_search_vector` GIN index. `$2` is the searching user, whose own games are found
+/// even if they are shadow-banned.
+const SEARCH_MATCHES_CTE: &str = "\
+    WITH q AS (SELECT websearch_to_tsquery('english', $1) AS query), \
+    matches AS ( \
+        SELECT g.id, g.play_count, ts_rank(g.search_vector, q.query) AS rank \
+        FROM game g \
+        CROSS JOIN q \
+        WHERE g.search_vector @@ q.query \
+            AND g.deleted_at IS NULL \
+            AND g.status = 'published' \
+            AND g.visibility = 'public' \
+            AND NOT EXISTS ( \
+                SELECT 1 FROM \"user\" u WHERE u.id = g.owner_id AND u.hide_from_search \
+            ) \
+            AND NOT EXISTS ( \
+                SELECT 1 FROM \"user\" u \
+                WHERE u.id = g.owner_id AND u.shadow_banned AND u.id IS DISTINCT FROM $2 \
+            ) \
+    ) ";
+
+#[derive(Debug, FromQueryResult)]
+struct SearchHit {
+    id: Uuid,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct SearchCount {
+    total: i64,
+}
+
+/// Rank matching game IDs with `PostgreSQL` full-text search.
+async fn search_game_ids_postgres(
+    db: &DatabaseConnection,
+    q: &str,
+    viewer: Option<Uuid>,
+    offset: u64,
+    limit: u64,
+) -> Result<(Vec<Uuid>, u64), AppError> {
+    let count = SearchCount::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        format!("{SEARCH_MATCHES_CTE} SELECT COUNT(*) AS total FROM matches"),
+        [q.into(), viewer.into()],
+    ))
+    .one(db)
+    .await?
+    .map_or(0, |c| u64::try_from(c.total).unwrap_or(0));
+
+    let hits = SearchHit::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        format!(
+            "{SEARCH_MATCHES_CTE} SELECT id FROM matches \
+             ORDER BY rank DESC, play_count DESC, id LIMIT $3 OFFSET $4"
+        ),
+        [
+            q.into(),
+            viewer.into(),
+            i64::try_from(limit).unwrap_or(i64::MAX).into(),
+            i64::try_from(offset).unwrap_or(i64::MAX).into(),
+        ],
+    ))
+    .all(db)
+    .await?;
+
+    Ok((hits.into_iter().map(|h| h.id).collect(), count))
+}
+
+/// Rank matching game IDs with case-insensitive substring matching.
+///
+/// Used on backends without full-text search (`SQLite` in tests and local tooling). Each query
+/// term scores 3 for a title hit, 2 for a tag hit, and 1 for a description hit.
+async fn search_game_ids_fallback(
+    db: &DatabaseConnection,
+    q: &str,
+    viewer: Option<Uuid>,
+    offset: u64,
+    limit: u64,
+) -> Result<(Vec<Uuid>, u64), AppError> {
+    let terms: Vec<String> = q.split_whitespace().map(str::to_lowercase).collect();
+
+    let mut tag_condition = Condition::any();
+    for term in &terms {
+        tag_condition = tag_condition.add(tag::Column::Name.like(format!("%{term}%")));
+    }
+    let matching_tag_ids: Vec<Uuid> = tag::Entity::find()
+        .filter(tag_condition)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|t| t.id)
+        .collect();
+    let tagged_game_ids: Vec<Uuid> = game_tag::Entity::find()
+        .filter(game_tag::Column::TagId.is_in(matching_tag_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|gt| gt.game_id)
+        .collect();
+
+    let mut text_condition = Condition::any().add(game::Column::Id.is_in(tagged_game_ids));
+    for term in &terms {
+        text_condition = text_condition
+            .add(game::Column::Title.like(format!("%{term}%")))
+            .add(game::Column::Description.like(format!("%{term}%")));
+    }
+
+    let candidates = listed_games(viewer)
+        .filter(
+            game::Column::OwnerId.not_in_subquery(
+                SeaQuery::select()
+                    .column(user::Column::Id)
+                    .from(user::Entity)
+                    .and_where(user::Column::HideFromSearch.eq(true))
+                    .to_owned(),
+            ),
+        )
+        .filter(text_condition)
+        .all(db)
+        .await?;
+
+    let mut tag_names = candidate_tag_names(db, &candidates).await?;
+    let mut scored = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        let tag_names = tag_names.remove(&candidate.id).unwrap_or_default();
+        let title = candidate.title.to_lowercase();
+        let description = candidate.description.unwrap_or_default().to_lowercase();
+
+        let score: u32 = terms
+            .iter()
+            .map(|term| {
+                let mut s = 0;
+                if title.contains(term.as_str()) {
+                    s += 3;
+                }
+                if tag_names.iter().any(|n| n.contains(term.as_str())) {
+                    s += 2;
+                }
+                if description.contains(term.as_str()) {
+                    s += 1;
+                }
+                s
+            })
+            .sum();
+        scored.push((score, candidate.play_count, candidate.id));
+    }
+
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.cmp(&a.1)).then(a.2.cmp(&b.2)));
+
+    let total = u64::try_from(scored.len()).unwrap_or(0);
+    let ids = scored
+        .into_iter()
+        .skip(usize::try_from(offset).unwrap_or(usize::MAX))
+        .take(usize::try_from(limit).unwrap_or(usize::MAX))
+        .map(|(_, _, id)| id)
+        .collect();
+
+    Ok((ids, total))
+}
+
+/// Lowercased tag names of each of `games`, loaded in two queries.
+async fn candidate_tag_names(
+    db: &DatabaseConnection,
+    games: &[game::Model],
+) -> Result<HashMap<Uuid, Vec<String>>, AppError> {
+    let game_tags = game_tag::Entity::find()
+        .filter(game_tag::Column::GameId.is_in(games.iter().map(|g| g.id)))
+        .all(db)
+        .await?;
+    let names: HashMap<Uuid, String> = tag::Entity::find()
+        .filter(tag::Column::Id.is_in(game_tags.iter().map(|gt| gt.tag_id)))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|t| (t.id, t.name.to_lowercase()))
+        .collect();
+
+    let mut by_game: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for gt in game_tags {
+        if let Some(name) = names.get(&gt.tag_id) {
+            by_game.entry(gt.game_id).or_default().push(name.clone());
+        }
+    }
+    Ok(by_game)
+}
+
+fn run_validation(game: &game::Model, config: &Config) -> Vec<Diagnostic> {
+    validation::validate_game(
+        &game.technology,
+        game.game_screen_code.as_deref(),
+        game.controller_screen_code.as_deref(),
+        &config.code_scan_rules,
+    )
+}
+
+/// Reject publishing by guest accounts and accounts without a verified email.
+fn ensure_can_publish(user: &user::Model) -> Result<(), AppError> {
+    if user.role == "guest" {
+        return Err(AppError::Unprocessable(
+            code::GUEST_ACCOUNT,
+            "Guest accounts must be upgraded to publish games".to_string(),
+        ));
+    }
+    if !user.email_verified {
+        return Err(AppError::Unprocessable(
+            code::EMAIL_NOT_VERIFIED,
+            "Email must be verified to publish games".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Reject publishing a game without a title or any canvas code.
+pub(crate) fn ensure_publishable(game: &game::Model) -> Result<(), AppError> {
+    if game.status == REMOVED_BY_MODERATION {
+        return Err(removed_by_moderation());
+    }
+
+    if game.title.trim().is_empty() {
+        return Err(AppError::Unprocessable(
+            code::INVALID_GAME,
+            "Game must have a title".to_string(),
+        ));
+    }
+
+    let screen_empty = game
+        .game_screen_code
+        .as_deref()
+        .is_none_or(|c| c.trim().is_empty());
+    let ctrl_empty = game
+        .controller_screen_code
+        .as_deref()
+        .is_none_or(|c| c.trim().is_empty());
+    if screen_empty && ctrl_empty {
+        return Err(AppError::Unprocessable(
+            code::INVALID_GAME,
+            "Game must have at least one non-empty canvas code".to_string(),
+        ));
+    }
+
+    if let Some(manifest) = &game.manifest {
+        validation::parse_manifest(manifest, game.min_players, game.max_players)
+            .map_err(|message| AppError::Unprocessable(code::INVALID_MANIFEST, message))?;
+    }
+
+    Ok(())
+}
+
+/// Reject publishing code with validation errors, quoting the first one.
+///
+/// Returns the safety scanner warnings to record on the new version.
+pub(crate) fn ensure_valid_code(
+    game: &game::Model,
+    config: &Config,
+) -> Result<Vec<Diagnostic>, AppError> {
+    let diagnostics = run_validation(game, config);
+    let mut errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == validation::Severity::Error);
+
+    if let Some(first) = errors.next() {
+        let code = if first.code == validation::UNSAFE_API {
+            code::UNSAFE_GAME_CODE
+        } else {
+            code::INVALID_GAME_CODE
+        };
+        return Err(AppError::Unprocessable(
+            code,
+            format!(
+                "Game code has {} error(s), first: {}",
+                errors.count() + 1,
+                first.message
+            ),
+        ));
+    }
+
+    Ok(diagnostics
+        .into_iter()
+        .filter(|d| d.code == validation::UNSAFE_API)
+        .collect())
+}
+
+/// Snapshot the game's current code as the next version and mark the game published.
+pub(crate) async fn publish_new_version<C: ConnectionTrait>(
+    db: &C,
+    game: game::Model,
+    published_by: Uuid,
+    changelog: Option<String>,
+    scan_findings: Vec<Diagnostic>,
+) -> anyhow::Result<(game::Model, game_version::Model)> {
+    // Determine next version number
+    let version_count = game_version::Entity::find()
+        .filter(game_version::Column::GameId.eq(game.id))
+        .count(db)
+        .await?;
+
+    #[allow(clippy::cast_possible_truncation)]
+    let version_number = (version_count + 1) as i32;
+
+    let manifest = game
+        .manifest
+        .as_ref()
+        .map(|m| validation::parse_manifest(m, game.min_players, game.max_players))
+        .transpose()
+        .map_err(anyhow::Error::msg)?;
+
+    let version = game_version::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        created_at: ActiveValue::Set(chrono::Utc::now().into()),
+        game_id: ActiveValue::Set(game.id),
+        version_number: ActiveValue::Set(version_number),
+        game_screen_code: ActiveValue::Set(game.game_screen_code.clone()),
+        controller_screen_code: ActiveValue::Set(game.controller_screen_code.clone()),
+        changelog: ActiveValue::Set(changelog),
+        published_by_id: ActiveValue::Set(Some(published_by)),
+        change_log: ActiveValue::NotSet,
+        scan_findings: ActiveValue::Set(Some(serde_json::to_value(scan_findings)?)),
+        manifest: ActiveValue::Set(manifest.map(serde_json::to_value).transpose()?),
+    };
+
+    let version = version.insert(db).await?;
+
+    let mut active: game::ActiveModel = game.into();
+    active.status = ActiveValue::Set("published".to_string());
+    active.published_version_id = ActiveValue::Set(Some(version.id));
+    active.updated_at = ActiveValue::Set(chrono::Utc::now().into());
+    let game = active.update(db).await?;
+
+    Ok((game, version))
+}
+
+/// A file read from a multipart upload.
+struct Upload {
+    file_name: String,
+    file_type: String,
+    data: Vec<u8>,
+    purpose: Option<String>,
+}
+
+/// Read the `file` (and optional `purpose`) fields of a multipart asset upload.
+async fn read_upload(mut multipart: Multipart) -> Result<Upload, AppError> {
+    const MAX_FILE_SIZE: usize = 10 * 1024 * 1024; // 10 MB
+
+    let mut upload = Upload {
+        file_name: String::new(),
+        file_type: String::new(),
+        data: Vec::new(),
+        purpose: None,
+    };
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Multipart error: {e}")))?
+    {
+        match field.name() {
+            Some("file") => {
+                upload.file_name = field.file_name().unwrap_or("upload").to_string();
+                upload.file_type = field
+                    .content_type()
+                    .unwrap_or("application/octet-stream")
+                    .to_string();
+                let bytes = field
+                    .bytes()
+                    .await
+                    .map_err(|e| AppError::BadRequest(format!("Could not read file: {e}")))?;
+                upload.data = bytes.to_vec();
+            }
+            Some("purpose") => {
+                let text = field
+                    .text()
+                    .await
+                    .map_err(|e| AppError::BadRequest(format!("Multipart error: {e}")))?;
+                upload.purpose = Some(text);
+            }
+            _ => {}
+        }
+    }
+
+    if upload.data.is_empty() {
+        return Err(AppError::BadRequest("No file provided".to_string()));
+    }
+
+    if upload.data.len() > MAX_FILE_SIZE {
+        return Err(AppError::PayloadTooLarge(
+            "File exceeds the 10 MB size limit".to_string(),
+        ));
+    }
+
+    Ok(upload)
+}
+
+/// Generate every thumbnail size from `source`, replace the game's previous thumbnail assets
+/// and point `game.thumbnail` at the default size. Returns the new URL and the stored assets.
+async fn store_thumbnails(
+    state: &AppState,
+    game: game::Model,
+    source: Vec<u8>,
+) -> Result<(String, Vec<game_asset::Model>), AppError> {
+    let thumbnails = tokio::task::spawn_blocking(move || media::generate_thumbnails(&source))
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?
+        .map_err(|e| AppError::BadRequest(format!("Invalid image: {e}")))?;
+
+    // Write the blobs up front so the transaction below only touches rows
+    let mut stored = Vec::with_capacity(thumbnails.len());
+    for thumb in thumbnails {
+        let asset_id = Uuid::new_v4();
+        let file_size = i32::try_from(thumb.data.len()).unwrap_or(i32::MAX);
+        let blob = store_blob(
+            state,
+            game.owner_id,
+            game.id,
+            asset_id,
+            thumb.data,
+            "image/webp",
+        )
+        .await?;
+        stored.push((asset_id, blob, file_size, thumb.size));
+    }
+
+    let now = chrono::Utc::now();
+    let txn = state.db.begin().await?;
+
+    // Retire the previous generation
+    game_asset::Entity::update_many()
+        .col_expr(
+            game_asset::Column::DeletedAt,
+            sea_orm::sea_query::Expr::value(now),
+        )
+        .filter(game_asset::Column::GameId.eq(game.id))
+        .filter(game_asset::Column::DeletedAt.is_null())
+        .filter(game_asset::Column::FileName.starts_with(THUMBNAIL_FILE_PREFIX))
+        .exec(&txn)
+        .await?;
+
+    let mut assets = Vec::with_capacity(stored.len());
+    let mut thumbnail_url = String::new();
+    for (asset_id, blob, file_size, size) in stored {
+        let file_name = format!("{THUMBNAIL_FILE_PREFIX}{}.webp", size.name);
+        let storage_url = format!("assets/{}/{file_name}", game.id);
+        if size.name == media::DEFAULT_THUMBNAIL_SIZE {
+            thumbnail_url.clone_from(&storage_url);
+        }
+
+        let asset = game_asset::ActiveModel {
+            id: ActiveValue::Set(asset_id),
+            created_at: ActiveValue::Set(now.into()),
+            game_id: ActiveValue::Set(game.id),
+            file_name: ActiveValue::Set(file_name),
+            file_type: ActiveValue::Set("image/webp".to_string()),
+            file_size: ActiveValue::Set(file_size),
+            file_data: ActiveValue::Set(Vec::new()),
+            storage_url: ActiveValue::Set(storage_url),
+            storage_backend: ActiveValue::Set(blob.backend),
+            storage_key: ActiveValue::Set(Some(blob.key)),
+            content_hash: ActiveValue::Set(Some(blob.content_hash)),
+            ..Default::default()
+        };
+        assets.push(asset.insert(&txn).await?);
+    }
+
+    let mut active: game::ActiveModel = game.into();
+    active.thumbnail = ActiveValue::Set(Some(thumbnail_url.clone()));
+    active.updated_at = ActiveValue::Set(now.into());
+    active.update(&txn).await?;
+
+    txn.commit().await?;
+
+    Ok((thumbnail_url, assets))
+}
+
+/// Reject an upload of `size` bytes that would take `user` past their plan's storage quota.
+pub(crate) async fn check_storage_quota(
+    state: &AppState,
+    user: &user::Model,
+    size: usize,
+) -> Result<(), AppError> {
+    let limit = storage::quota::limit_for_plan(&user.subscription_plan);
+    let used_bytes = storage::quota::usage(&state.db, user.id).await?;
+    let size = u64::try_from(size).unwrap_or(u64::MAX);
+
+    if used_bytes.saturating_add(size) > limit {
+        return Err(AppError::Unprocessable(
+            code::QUOTA_EXCEEDED,
+            format!(
+                "Storage quota exceeded: {used_bytes} of {limit} bytes used on the {} plan",
+                user.subscription_plan
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Where a new asset's bytes are stored.
+pub(crate) struct StoredBlob {
+    pub(crate) backend: String,
+    pub(crate) key: String,
+    pub(crate) content_hash: String,
+}
+
+/// Store the bytes of a new asset, reusing the blob of an identical live asset on any of
+/// `owner_id`'s games instead of writing a second copy.
+pub(crate) async fn store_blob(
+    state: &AppState,
+    owner_id: Uuid,
+    game_id: Uuid,
+    asset_id: Uuid,
+    data: Vec<u8>,
+    content_type: &str,
+) -> Result<StoredBlob, AppError> {
+    let content_hash = storage::content_hash(&data);
+
+    let existing = game_asset::Entity::find()
+        .inner_join(game::Entity)
+        .filter(game::Column::OwnerId.eq(owner_id))
+        .filter(game_asset::Column::ContentHash.eq(content_hash.as_str()))
+        .filter(game_asset::Column::StorageKey.is_not_null())
+        .filter(game_asset::Column::DeletedAt.is_null())
+        .one(&state.db)
+        .await?;
+
+    // Soft-deleting an asset never removes its blob, so sharing the key is safe
+    if let Some((backend, Some(key))) = existing.map(|a| (a.storage_backend, a.storage_key)) {
+        return Ok(StoredBlob {
+            backend,
+            key,
+            content_hash,
+        });
+    }
+
+    let storage = storage::configured(&state.config, &state.db);
+    let key = storage::asset_key(game_id, asset_id);
+    storage.put(&key, data, content_type).await?;
+
+    Ok(StoredBlob {
+        backend: storage.name().to_string(),
+        key,
+        content_hash,
+    })
+}
+
+/// Generate a URL-safe slug suffixed with the game ID to guarantee uniqueness.
+pub(crate) fn unique_slug(title: &str, id: Uuid) -> String {
+    let base: String = title
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+
     format!("{base}-{id}")
 }
 
-fn to_game_response(
+pub(crate) fn to_game_response(
     game: game::Model,
     creator: Option<CreatorInfo>,
     tags: Option<Vec<TagResponse>>,
     include_code: bool,
 ) -> GameResponse {
+    let lock = active_lock(&game);
     GameResponse {
         id: game.id,
         created_at: game.created_at.to_string(),
@@ -1152,16 +3173,23 @@ fn to_game_response(
         } else {
             None
         },
+        manifest: game.manifest,
         published_version_id: game.published_version_id,
         play_count: game.play_count,
         total_play_time: game.total_play_time,
         avg_rating: game.avg_rating,
         review_count: game.review_count,
+        locked_by: lock.map(|(holder, _)| holder),
+        lock_expires_at: lock.map(|(_, expires_at)| expires_at.to_string()),
+        featured_at: game.featured_at.map(|t| t.to_string()),
+        owner_type: game.owner_type,
+        organization_id: game.organization_id,
         tags,
+        is_favorited: None,
     }
 }
 
-fn to_game_summary(game: game::Model) -> GameSummaryResponse {
+pub(crate) fn to_game_summary(game: game::Model) -> GameSummaryResponse {
     GameSummaryResponse {
         id: game.id,
         created_at: game.created_at.to_string(),
@@ -1179,6 +3207,7 @@ fn to_game_summary(game: game::Model) -> GameSummaryResponse {
         play_count: game.play_count,
         avg_rating: game.avg_rating,
         review_count: game.review_count,
+        featured_at: game.featured_at.map(|t| t.to_string()),
     }
 }
 
@@ -1204,6 +3233,35 @@ fn to_asset_response(a: game_asset::Model) -> AssetResponse {
     }
 }
 
+fn to_release_note(v: game_version::Model) -> ReleaseNoteResponse {
+    // Versions published before `changelog` existed only have the legacy column
+    let changelog = v
+        .changelog
+        .or(v.change_log)
+        .filter(|c| !c.trim().is_empty());
+
+    ReleaseNoteResponse {
+        version_number: v.version_number,
+        published_at: v.created_at.to_string(),
+        changelog_html: changelog.as_deref().map(markdown::render),
+        changelog,
+    }
+}
+
+fn to_template_response(t: game_template::Model) -> TemplateResponse {
+    TemplateResponse {
+        id: t.id,
+        slug: t.slug,
+        name: t.name,
+        description: t.description,
+        technology: t.technology,
+        min_players: t.min_players,
+        max_players: t.max_players,
+        game_screen_code: t.game_screen_code,
+        controller_screen_code: t.controller_screen_code,
+    }
+}
+
 fn to_tag_response(t: tag::Model) -> TagResponse {
     TagResponse {
         id: t.id,
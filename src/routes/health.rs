@@ -1,10 +1,12 @@
+use std::collections::BTreeMap;
+
 use axum::extract::State;
 use axum::routing::get;
 use axum::{Json, Router};
-use sea_orm::{ConnectionTrait, DbBackend, Statement};
 use serde::Serialize;
 
 use crate::error::AppError;
+use crate::health::{self, ComponentHealth};
 use crate::state::AppState;
 
 /// Root-level health check router: `GET /health`.
@@ -16,7 +18,8 @@ pub fn root_router() -> Router<AppState> {
 
 /// API-level health check router: `GET /api/v1/health`.
 ///
-/// Returns server status, version, and database connectivity.
+/// Returns server status, version, database connectivity, and the status of each component
+/// the API depends on.
 pub fn api_router() -> Router<AppState> {
     Router::new().route("/health", get(health_detailed))
 }
@@ -40,6 +43,8 @@ struct DetailedHealth {
     status: &'static str,
     version: &'static str,
     database: DatabaseStatus,
+    /// `database`, `migrations`, `storage` and `email`.
+    components: BTreeMap<&'static str, ComponentHealth>,
 }
 
 #[derive(Serialize)]
@@ -51,43 +56,24 @@ struct DatabaseStatus {
     error: Option<String>,
 }
 
-/// Detailed health check — verifies database connectivity with a `SELECT 1` query.
+/// Detailed health check — verifies database connectivity, that no migrations are pending,
+/// and that the storage backend answers (see [`health::check_components`]).
 async fn health_detailed(State(state): State<AppState>) -> Result<Json<DetailedHealth>, AppError> {
-    let start = std::time::Instant::now();
-
-    let db_result = state
-        .db
-        .execute(Statement::from_string(
-            DbBackend::Postgres,
-            "SELECT 1".to_string(),
-        ))
-        .await;
-
-    let elapsed = start.elapsed();
+    let components = health::check_components(&state.db, &state.config).await;
 
-    let database = match db_result {
-        Ok(_) => DatabaseStatus {
-            connected: true,
-            latency_ms: Some(elapsed.as_millis()),
-            error: None,
-        },
-        Err(e) => {
-            tracing::warn!("Health check database ping failed: {e}");
-            DatabaseStatus {
-                connected: false,
-                latency_ms: None,
-                error: Some(e.to_string()),
-            }
-        }
+    let database = components.get("database");
+    let connected = database.is_some_and(|d| d.status == health::UP);
+    let database_status = DatabaseStatus {
+        connected,
+        latency_ms: database.and_then(|d| d.latency_ms).filter(|_| connected),
+        error: database.and_then(|d| d.detail.clone()),
     };
+    let healthy = components.values().all(|c| c.status != health::DOWN);
 
     Ok(Json(DetailedHealth {
-        status: if database.connected {
-            "healthy"
-        } else {
-            "degraded"
-        },
+        status: if healthy { "healthy" } else { "degraded" },
         version: env!("CARGO_PKG_VERSION"),
-        database,
+        database: database_status,
+        components,
     }))
 }
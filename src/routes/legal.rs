@@ -0,0 +1,665 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::post,
+};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    audit,
+    auth::{
+        extract_client_ip,
+        middleware::{AdminUser, AuthUser},
+        password::validate_email,
+    },
+    entities::{copyright_notice, game},
+    error::AppError,
+    notifications,
+    routes::{
+        games::{PaginatedResponse, REMOVED_BY_MODERATION, find_active_game},
+        takedowns::remove_game,
+    },
+    state::AppState,
+};
+
+/// `copyright_notice.status` of a notice no admin has picked up yet.
+pub const RECEIVED: &str = "received";
+
+/// `copyright_notice.status` of a notice an admin is reviewing.
+pub const UNDER_REVIEW: &str = "under_review";
+
+/// `copyright_notice.status` of a notice the game was removed for.
+pub const ACTIONED: &str = "actioned";
+
+/// `copyright_notice.status` of a notice that did not hold up; the game stays.
+pub const REJECTED: &str = "rejected";
+
+/// `copyright_notice.status` once the creator answers with a counter-notice; the game is
+/// restored when `restore_after` passes.
+pub const COUNTER_NOTICED: &str = "counter_noticed";
+
+/// `copyright_notice.status` once the game is back after a counter-notice.
+pub const RESTORED: &str = "restored";
+
+/// `copyright_notice.status` once the claimant reports a court action over the game, which
+/// keeps it removed.
+pub const COURT_ACTION: &str = "court_action";
+
+/// How long after a counter-notice the game is restored, unless the claimant reports a court
+/// action first. The law allows 10 to 14 business days; two weeks errs on the short side.
+pub const COUNTER_NOTICE_WINDOW: chrono::Duration = chrono::Duration::days(14);
+
+/// Longest name or signature, in characters.
+const MAX_NAME_LENGTH: usize = 200;
+
+/// Longest postal address, in characters.
+const MAX_ADDRESS_LENGTH: usize = 500;
+
+/// Longest phone number, in characters.
+const MAX_PHONE_LENGTH: usize = 50;
+
+/// Longest description of the copyrighted work, in characters.
+const MAX_WORK_LENGTH: usize = 2000;
+
+/// Shortest counter-notice statement, in characters.
+const MIN_STATEMENT_LENGTH: usize = 20;
+
+/// Longest counter-notice statement, in characters.
+const MAX_STATEMENT_LENGTH: usize = 2000;
+
+/// Longest note an admin may leave on a decision, in characters.
+const MAX_REVIEW_NOTE_LENGTH: usize = 1000;
+
+/// Notices a single IP address may send per hour.
+const NOTICES_PER_HOUR: u64 = 5;
+
+/// Legal router: `/legal`
+pub fn router() -> Router<AppState> {
+    Router::new().route("/takedown", post(submit_notice))
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+/// A takedown notice, with everything the law requires of one.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(clippy::struct_excessive_bools)]
+struct NoticeRequest {
+    /// The allegedly infringing game.
+    game_id: Uuid,
+    claimant_name: String,
+    claimant_email: String,
+    claimant_address: String,
+    claimant_phone: Option<String>,
+    /// The work the game infringes, or a representative list of works.
+    copyrighted_work: String,
+    /// The claimant believes in good faith the use is not authorized.
+    #[serde(default)]
+    good_faith: bool,
+    /// The notice is accurate and, under penalty of perjury, the claimant may act for the
+    /// rights holder.
+    #[serde(default)]
+    accurate_under_penalty: bool,
+    /// The claimant's full name as their electronic signature.
+    signature: String,
+}
+
+/// A creator's counter-notice, with everything the law requires of one.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CounterNoticeRequest {
+    name: String,
+    address: String,
+    /// Why the game was removed by mistake or misidentification.
+    statement: String,
+    /// The creator states the above under penalty of perjury.
+    #[serde(default)]
+    under_penalty: bool,
+    /// The creator consents to the jurisdiction of the courts for their address, and to
+    /// service of process from the claimant.
+    #[serde(default)]
+    consent_to_jurisdiction: bool,
+    /// The creator's full name as their electronic signature.
+    signature: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecideNoticeRequest {
+    /// Why the notice was actioned or rejected; kept for the record.
+    note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoticesQuery {
+    /// Only notices with this status; defaults to notices still awaiting a decision.
+    status: Option<String>,
+    game_id: Option<Uuid>,
+    #[serde(default)]
+    offset: u64,
+    #[serde(default = "default_notices_limit")]
+    limit: u64,
+}
+
+const fn default_notices_limit() -> u64 {
+    50
+}
+
+/// What a claimant gets back for their notice.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NoticeReceipt {
+    id: Uuid,
+    status: String,
+    created_at: String,
+}
+
+/// What a creator gets back for their counter-notice.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CounterNoticeResponse {
+    id: Uuid,
+    game_id: Option<Uuid>,
+    status: String,
+    counter_noticed_at: Option<String>,
+    restore_after: Option<String>,
+}
+
+/// A notice as admins see it.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AdminNoticeResponse {
+    id: Uuid,
+    status: String,
+    game_id: Option<Uuid>,
+    claimant_name: String,
+    claimant_email: String,
+    claimant_address: String,
+    claimant_phone: Option<String>,
+    copyrighted_work: String,
+    signature: String,
+    reviewer_id: Option<Uuid>,
+    review_note: Option<String>,
+    review_started_at: Option<String>,
+    decided_at: Option<String>,
+    takedown_id: Option<Uuid>,
+    counter_name: Option<String>,
+    counter_address: Option<String>,
+    counter_statement: Option<String>,
+    counter_signature: Option<String>,
+    counter_noticed_at: Option<String>,
+    restore_after: Option<String>,
+    restored_at: Option<String>,
+    created_at: String,
+}
+
+impl From<copyright_notice::Model> for AdminNoticeResponse {
+    fn from(n: copyright_notice::Model) -> Self {
+        Self {
+            id: n.id,
+            status: n.status,
+            game_id: n.game_id,
+            claimant_name: n.claimant_name,
+            claimant_email: n.claimant_email,
+            claimant_address: n.claimant_address,
+            claimant_phone: n.claimant_phone,
+            copyrighted_work: n.copyrighted_work,
+            signature: n.signature,
+            reviewer_id: n.reviewer_id,
+            review_note: n.review_note,
+            review_started_at: n.review_started_at.map(|t| t.to_rfc3339()),
+            decided_at: n.decided_at.map(|t| t.to_rfc3339()),
+            takedown_id: n.takedown_id,
+            counter_name: n.counter_name,
+            counter_address: n.counter_address,
+            counter_statement: n.counter_statement,
+            counter_signature: n.counter_signature,
+            counter_noticed_at: n.counter_noticed_at.map(|t| t.to_rfc3339()),
+            restore_after: n.restore_after.map(|t| t.to_rfc3339()),
+            restored_at: n.restored_at.map(|t| t.to_rfc3339()),
+            created_at: n.created_at.to_rfc3339(),
+        }
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `POST /legal/takedown` — Send a copyright takedown notice against a game.
+///
+/// Works without signing in; senders are rate limited by IP address. The notice joins the
+/// admins' queue as `received`.
+async fn submit_notice(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<NoticeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let claimant_name = required("claimantName", &req.claimant_name, MAX_NAME_LENGTH)?;
+    let claimant_email = req.claimant_email.trim().to_lowercase();
+    validate_email(&claimant_email).map_err(AppError::BadRequest)?;
+    let claimant_address = required("claimantAddress", &req.claimant_address, MAX_ADDRESS_LENGTH)?;
+    let claimant_phone = req
+        .claimant_phone
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty());
+    if claimant_phone
+        .as_ref()
+        .is_some_and(|p| p.chars().count() > MAX_PHONE_LENGTH)
+    {
+        return Err(AppError::BadRequest(format!(
+            "claimantPhone must be at most {MAX_PHONE_LENGTH} characters."
+        )));
+    }
+    let copyrighted_work = required("copyrightedWork", &req.copyrighted_work, MAX_WORK_LENGTH)?;
+    let signature = required("signature", &req.signature, MAX_NAME_LENGTH)?;
+    if !req.good_faith || !req.accurate_under_penalty {
+        return Err(AppError::BadRequest(
+            "A notice must include the good faith and accuracy statements.".to_string(),
+        ));
+    }
+
+    find_active_game(&state.db, req.game_id).await?;
+
+    let client_ip = extract_client_ip(&headers);
+    check_rate_limit(&state.db, client_ip.as_deref()).await?;
+
+    let now = Utc::now().fixed_offset();
+    let notice = copyright_notice::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        status: Set(RECEIVED.to_string()),
+        game_id: Set(Some(req.game_id)),
+        claimant_name: Set(claimant_name),
+        claimant_email: Set(claimant_email),
+        claimant_address: Set(claimant_address),
+        claimant_phone: Set(claimant_phone),
+        claimant_ip: Set(client_ip),
+        copyrighted_work: Set(copyrighted_work),
+        signature: Set(signature),
+        reviewer_id: Set(None),
+        review_note: Set(None),
+        review_started_at: Set(None),
+        decided_at: Set(None),
+        takedown_id: Set(None),
+        counter_name: Set(None),
+        counter_address: Set(None),
+        counter_statement: Set(None),
+        counter_signature: Set(None),
+        counter_noticed_at: Set(None),
+        restore_after: Set(None),
+        restored_at: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(NoticeReceipt {
+            id: notice.id,
+            status: notice.status,
+            created_at: notice.created_at.to_rfc3339(),
+        }),
+    ))
+}
+
+/// `POST /games/{id}/counter-notice` — Answer the copyright notice the caller's game was
+/// removed for.
+///
+/// The claimant is sent the counter-notice, and the game is restored once
+/// [`COUNTER_NOTICE_WINDOW`] passes unless they report a court action first.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if the game does not exist, is not the caller's, or was not
+/// removed for a copyright notice, [`AppError::Conflict`] if the notice was already answered,
+/// [`AppError::BadRequest`] if the counter-notice is incomplete, or [`AppError`] if a
+/// database operation fails.
+pub async fn file_counter_notice(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+    Json(req): Json<CounterNoticeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let name = required("name", &req.name, MAX_NAME_LENGTH)?;
+    let address = required("address", &req.address, MAX_ADDRESS_LENGTH)?;
+    let statement = req.statement.trim().to_string();
+    if !(MIN_STATEMENT_LENGTH..=MAX_STATEMENT_LENGTH).contains(&statement.chars().count()) {
+        return Err(AppError::BadRequest(format!(
+            "statement must be between {MIN_STATEMENT_LENGTH} and {MAX_STATEMENT_LENGTH} \
+             characters."
+        )));
+    }
+    let signature = required("signature", &req.signature, MAX_NAME_LENGTH)?;
+    if !req.under_penalty || !req.consent_to_jurisdiction {
+        return Err(AppError::BadRequest(
+            "A counter-notice must include the perjury and jurisdiction statements.".to_string(),
+        ));
+    }
+
+    let game = find_active_game(&state.db, id).await?;
+    if game.owner_id != user.id {
+        return Err(AppError::NotFound("Game not found".to_string()));
+    }
+    let notice = copyright_notice::Entity::find()
+        .filter(copyright_notice::Column::GameId.eq(game.id))
+        .filter(copyright_notice::Column::TakedownId.is_not_null())
+        .order_by_desc(copyright_notice::Column::DecidedAt)
+        .one(&state.db)
+        .await?
+        .filter(|_| game.status == REMOVED_BY_MODERATION)
+        .ok_or_else(|| {
+            AppError::NotFound("This game was not removed for a copyright notice.".to_string())
+        })?;
+    if notice.status != ACTIONED {
+        return Err(AppError::Conflict(
+            "This notice has already been answered.".to_string(),
+        ));
+    }
+
+    let now = Utc::now();
+    let mut active: copyright_notice::ActiveModel = notice.into();
+    active.status = Set(COUNTER_NOTICED.to_string());
+    active.counter_name = Set(Some(name));
+    active.counter_address = Set(Some(address));
+    active.counter_statement = Set(Some(statement));
+    active.counter_signature = Set(Some(signature));
+    active.counter_noticed_at = Set(Some(now.fixed_offset()));
+    active.restore_after = Set(Some((now + COUNTER_NOTICE_WINDOW).fixed_offset()));
+    active.updated_at = Set(now.fixed_offset());
+    let answered = active.update(&state.db).await?;
+
+    tracing::info!(
+        email = %answered.claimant_email,
+        notice_id = %answered.id,
+        restore_after = ?answered.restore_after,
+        "Counter-notice forwarded to claimant (email sending not yet implemented)"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CounterNoticeResponse {
+            id: answered.id,
+            game_id: answered.game_id,
+            status: answered.status,
+            counter_noticed_at: answered.counter_noticed_at.map(|t| t.to_rfc3339()),
+            restore_after: answered.restore_after.map(|t| t.to_rfc3339()),
+        }),
+    ))
+}
+
+/// `GET /admin/copyright-notices` — Copyright notices, oldest first. Lists notices still
+/// awaiting a decision unless `status` says otherwise.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the database query fails.
+pub async fn list_notices(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    Query(query): Query<NoticesQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let mut find = copyright_notice::Entity::find();
+    find = match query.status {
+        Some(status) => find.filter(copyright_notice::Column::Status.eq(status)),
+        None => find.filter(copyright_notice::Column::Status.is_in([RECEIVED, UNDER_REVIEW])),
+    };
+    if let Some(game_id) = query.game_id {
+        find = find.filter(copyright_notice::Column::GameId.eq(game_id));
+    }
+    let total = find.clone().count(&state.db).await?;
+
+    let notices = find
+        .order_by_asc(copyright_notice::Column::CreatedAt)
+        .offset(query.offset)
+        .limit(query.limit)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(PaginatedResponse {
+        data: notices.into_iter().map(AdminNoticeResponse::from).collect(),
+        total,
+        offset: query.offset,
+        limit: query.limit,
+    }))
+}
+
+/// `POST /admin/copyright-notices/{id}/review` — Start reviewing a received notice.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if there is no notice with that ID, [`AppError::Conflict`]
+/// if it is not `received`, or [`AppError`] if a database operation fails.
+pub async fn review_notice(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let notice = find_notice(&state.db, id).await?;
+    ensure_status(&notice, RECEIVED)?;
+
+    let now = Utc::now().fixed_offset();
+    let mut active: copyright_notice::ActiveModel = notice.into();
+    active.status = Set(UNDER_REVIEW.to_string());
+    active.reviewer_id = Set(Some(admin.id));
+    active.review_started_at = Set(Some(now));
+    active.updated_at = Set(now);
+    let reviewing = active.update(&state.db).await?;
+
+    audit::Event::new("copyright_notice.review", "copyright_notice", id)
+        .before(serde_json::json!({ "status": RECEIVED }))
+        .after(serde_json::json!({ "status": reviewing.status }))
+        .record(&state.db, &admin, &headers)
+        .await;
+    Ok(Json(AdminNoticeResponse::from(reviewing)))
+}
+
+/// `POST /admin/copyright-notices/{id}/action` — Uphold a notice under review and remove the
+/// game. Its creator is told they can file a counter-notice.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if there is no notice with that ID, [`AppError::Conflict`]
+/// if it is not under review or its game is already gone, [`AppError::BadRequest`] if the
+/// note is too long, or [`AppError`] if a database operation fails.
+pub async fn action_notice(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(body): Json<DecideNoticeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let note = review_note(body.note)?;
+
+    let txn = state.db.begin().await?;
+    let notice = find_notice(&txn, id).await?;
+    ensure_status(&notice, UNDER_REVIEW)?;
+    let game = match notice.game_id {
+        Some(game_id) => {
+            game::Entity::find_by_id(game_id)
+                .filter(game::Column::DeletedAt.is_null())
+                .one(&txn)
+                .await?
+        }
+        None => None,
+    }
+    .filter(|g| g.status != REMOVED_BY_MODERATION)
+    .ok_or_else(|| {
+        AppError::Conflict("This game is already gone; reject the notice instead.".to_string())
+    })?;
+
+    let (game, takedown) = remove_game(
+        &txn,
+        game,
+        admin.id,
+        "Removed in response to a copyright notice.".to_string(),
+    )
+    .await?;
+    let mut active: copyright_notice::ActiveModel = notice.into();
+    active.status = Set(ACTIONED.to_string());
+    active.review_note = Set(note);
+    active.decided_at = Set(Some(takedown.created_at));
+    active.takedown_id = Set(Some(takedown.id));
+    active.updated_at = Set(takedown.created_at);
+    let actioned = active.update(&txn).await?;
+    txn.commit().await?;
+
+    audit::Event::new("copyright_notice.action", "copyright_notice", id)
+        .before(
+            serde_json::json!({ "status": UNDER_REVIEW, "gameStatus": takedown.previous_status }),
+        )
+        .after(serde_json::json!({ "status": actioned.status, "gameStatus": game.status }))
+        .record(&state.db, &admin, &headers)
+        .await;
+    notifications::copyright_removal(&state, &game).await;
+    Ok(Json(AdminNoticeResponse::from(actioned)))
+}
+
+/// `POST /admin/copyright-notices/{id}/reject` — Turn down a notice under review. The game
+/// stays up.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if there is no notice with that ID, [`AppError::Conflict`]
+/// if it is not under review, [`AppError::BadRequest`] if the note is too long, or
+/// [`AppError`] if a database operation fails.
+pub async fn reject_notice(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(body): Json<DecideNoticeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let note = review_note(body.note)?;
+    let notice = find_notice(&state.db, id).await?;
+    ensure_status(&notice, UNDER_REVIEW)?;
+
+    let now = Utc::now().fixed_offset();
+    let mut active: copyright_notice::ActiveModel = notice.into();
+    active.status = Set(REJECTED.to_string());
+    active.review_note = Set(note);
+    active.decided_at = Set(Some(now));
+    active.updated_at = Set(now);
+    let rejected = active.update(&state.db).await?;
+
+    audit::Event::new("copyright_notice.reject", "copyright_notice", id)
+        .before(serde_json::json!({ "status": UNDER_REVIEW }))
+        .after(serde_json::json!({ "status": rejected.status, "note": rejected.review_note }))
+        .record(&state.db, &admin, &headers)
+        .await;
+    Ok(Json(AdminNoticeResponse::from(rejected)))
+}
+
+/// `POST /admin/copyright-notices/{id}/court-action` — Record that the claimant took the
+/// creator to court after a counter-notice. The game stays removed instead of being restored.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if there is no notice with that ID, [`AppError::Conflict`]
+/// if it is not awaiting restoration after a counter-notice, or [`AppError`] if a database
+/// operation fails.
+pub async fn record_court_action(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let notice = find_notice(&state.db, id).await?;
+    ensure_status(&notice, COUNTER_NOTICED)?;
+
+    let restore_after = notice.restore_after.map(|t| t.to_rfc3339());
+    let mut active: copyright_notice::ActiveModel = notice.into();
+    active.status = Set(COURT_ACTION.to_string());
+    active.restore_after = Set(None);
+    active.updated_at = Set(Utc::now().fixed_offset());
+    let held = active.update(&state.db).await?;
+
+    audit::Event::new("copyright_notice.court_action", "copyright_notice", id)
+        .before(serde_json::json!({ "status": COUNTER_NOTICED, "restoreAfter": restore_after }))
+        .after(serde_json::json!({ "status": held.status }))
+        .record(&state.db, &admin, &headers)
+        .await;
+    Ok(Json(AdminNoticeResponse::from(held)))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// `value` trimmed, or an error naming `field` if it is empty or longer than `max` characters.
+fn required(field: &str, value: &str, max: usize) -> Result<String, AppError> {
+    let value = value.trim();
+    if !(1..=max).contains(&value.chars().count()) {
+        return Err(AppError::BadRequest(format!(
+            "{field} must be between 1 and {max} characters."
+        )));
+    }
+    Ok(value.to_string())
+}
+
+fn review_note(note: Option<String>) -> Result<Option<String>, AppError> {
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if note
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_REVIEW_NOTE_LENGTH)
+    {
+        return Err(AppError::BadRequest(format!(
+            "Note must be at most {MAX_REVIEW_NOTE_LENGTH} characters."
+        )));
+    }
+    Ok(note)
+}
+
+async fn find_notice<C: ConnectionTrait>(
+    db: &C,
+    id: Uuid,
+) -> Result<copyright_notice::Model, AppError> {
+    copyright_notice::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Copyright notice not found.".to_string()))
+}
+
+/// Refuse to move `notice` on unless it is in `expected`.
+fn ensure_status(notice: &copyright_notice::Model, expected: &str) -> Result<(), AppError> {
+    if notice.status == expected {
+        Ok(())
+    } else {
+        Err(AppError::Conflict(format!(
+            "This notice is {}, not {expected}.",
+            notice.status
+        )))
+    }
+}
+
+/// Cap how many notices an IP address sends per hour.
+async fn check_rate_limit(db: &DatabaseConnection, ip: Option<&str>) -> Result<(), AppError> {
+    let since = (Utc::now() - chrono::Duration::hours(1)).fixed_offset();
+    // Senders without a known IP share one bucket
+    let find = copyright_notice::Entity::find()
+        .filter(copyright_notice::Column::CreatedAt.gte(since))
+        .filter(ip.map_or_else(
+            || copyright_notice::Column::ClaimantIp.is_null(),
+            |ip| copyright_notice::Column::ClaimantIp.eq(ip),
+        ));
+
+    if find.count(db).await? >= NOTICES_PER_HOUR {
+        return Err(AppError::TooManyRequests(
+            "Too many notices, please try again later".to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use axum::{
+    Json,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    audit,
+    auth::middleware::AdminUser,
+    error::AppError,
+    logging::{self, FilterOverride},
+    state::AppState,
+};
+
+/// How long an override lasts when the request does not say.
+const DEFAULT_TTL_SECS: u64 = 15 * 60;
+
+/// Longest an override may last, so a forgotten one does not flood the logs for days.
+const MAX_TTL_SECS: u64 = 4 * 60 * 60;
+
+/// Longest directives string, in characters.
+const MAX_DIRECTIVES_LENGTH: usize = 500;
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+/// The log filter to use for a while.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogLevelRequest {
+    /// `EnvFilter` directives, e.g. `aircade_api=debug` or `aircade_api=debug,sqlx=debug`.
+    directives: String,
+    /// Seconds until the default filter is restored. Defaults to 15 minutes.
+    ttl_secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogLevelResponse {
+    /// The directives the server started with.
+    default_directives: String,
+    /// The override in effect, if any.
+    #[serde(rename = "override")]
+    filter_override: Option<OverrideResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OverrideResponse {
+    id: Uuid,
+    directives: String,
+    /// When the default filter is restored.
+    expires_at: String,
+}
+
+impl From<FilterOverride> for OverrideResponse {
+    fn from(o: FilterOverride) -> Self {
+        Self {
+            id: o.id,
+            directives: o.directives,
+            expires_at: o.expires_at.to_rfc3339(),
+        }
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `GET /admin/log-level` — The log filter in effect.
+///
+/// # Errors
+///
+/// Returns [`AppError::Internal`] if the log filter is not reloadable.
+pub async fn get_log_level(AdminUser(_admin): AdminUser) -> Result<impl IntoResponse, AppError> {
+    let (default_directives, filter_override) = filter_state()?;
+    Ok(Json(LogLevelResponse {
+        default_directives,
+        filter_override: filter_override.map(OverrideResponse::from),
+    }))
+}
+
+/// `PUT /admin/log-level` — Replace the log filter until `ttlSecs` pass, e.g. to turn on
+/// debug logging while diagnosing a production issue. Replaces any override already in
+/// effect.
+///
+/// # Errors
+///
+/// Returns [`AppError::BadRequest`] if the directives do not parse or the TTL is out of
+/// range, or [`AppError::Internal`] if the log filter is not reloadable.
+pub async fn set_log_level(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    headers: HeaderMap,
+    Json(req): Json<LogLevelRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let directives = req.directives.trim();
+    if !(1..=MAX_DIRECTIVES_LENGTH).contains(&directives.chars().count()) {
+        return Err(AppError::BadRequest(format!(
+            "Directives must be between 1 and {MAX_DIRECTIVES_LENGTH} characters."
+        )));
+    }
+    logging::validate_directives(directives)
+        .map_err(|e| AppError::BadRequest(format!("Invalid directives: {e}")))?;
+    let ttl_secs = req.ttl_secs.unwrap_or(DEFAULT_TTL_SECS);
+    if !(1..=MAX_TTL_SECS).contains(&ttl_secs) {
+        return Err(AppError::BadRequest(format!(
+            "ttlSecs must be between 1 and {MAX_TTL_SECS}."
+        )));
+    }
+
+    let (default_directives, previous) = filter_state()?;
+    let filter_override = logging::override_filter(directives, Duration::from_secs(ttl_secs))
+        .map_err(AppError::Internal)?;
+
+    let mut event = audit::Event::new("log_level.set", "log_filter", filter_override.id)
+        .after(snapshot(&filter_override));
+    if let Some(previous) = previous {
+        event = event.before(snapshot(&previous));
+    }
+    event.record(&state.db, &admin, &headers).await;
+
+    Ok(Json(LogLevelResponse {
+        default_directives,
+        filter_override: Some(filter_override.into()),
+    }))
+}
+
+/// `DELETE /admin/log-level` — Restore the default log filter now.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if no override is in effect, or [`AppError::Internal`] if
+/// the log filter is not reloadable.
+pub async fn reset_log_level(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let ended = logging::reset_filter()
+        .map_err(AppError::Internal)?
+        .ok_or_else(|| AppError::NotFound("No log filter override is in effect.".to_string()))?;
+
+    audit::Event::new("log_level.reset", "log_filter", ended.id)
+        .before(snapshot(&ended))
+        .record(&state.db, &admin, &headers)
+        .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn filter_state() -> Result<(String, Option<FilterOverride>), AppError> {
+    logging::filter_state().ok_or_else(|| {
+        AppError::Internal(anyhow::anyhow!(
+            "the log filter is not reloadable in this process"
+        ))
+    })
+}
+
+/// The audited fields of an override.
+fn snapshot(o: &FilterOverride) -> serde_json::Value {
+    serde_json::json!({
+        "directives": o.directives,
+        "expiresAt": o.expires_at.to_rfc3339(),
+    })
+}
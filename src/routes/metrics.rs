@@ -0,0 +1,29 @@
+use axum::Json;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use serde::Serialize;
+
+use crate::auth::middleware::AdminUser;
+use crate::db::{self, PoolMetrics};
+use crate::sessions::SessionMetrics;
+use crate::state::AppState;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MetricsResponse {
+    sessions: SessionMetrics,
+    /// `None` for databases without a connection pool.
+    database: Option<PoolMetrics>,
+}
+
+/// `GET /api/v1/admin/metrics` — Current relay gauges and counters, and database pool
+/// utilization, for capacity planning.
+pub async fn get_metrics(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+) -> impl IntoResponse {
+    Json(MetricsResponse {
+        sessions: state.session_manager.metrics(),
+        database: db::pool_metrics(&state.db).await,
+    })
+}
@@ -1,33 +1,163 @@
+mod account_merge;
+mod admin;
+mod analytics;
+mod announcements;
+mod api_tokens;
 mod auth;
+mod avatars;
+mod billing;
+mod classrooms;
+mod collaborators;
+mod collections;
+mod data_export;
+mod embed;
+mod experiments;
+mod export;
+mod feed;
+mod friends;
 pub mod games;
 mod health;
+pub mod legal;
+mod log_level;
+mod metrics;
+mod moderation;
+mod notifications;
+mod oauth_server;
+mod organizations;
+mod preferences;
+mod reports;
+mod reviews;
+mod seed;
+mod session_invites;
 mod sessions;
+mod stats;
+mod status;
+mod suspension_appeals;
+mod takedowns;
 mod users;
+mod verification;
+mod webauthn;
+mod well_known;
 
 use axum::Router;
 
+use crate::config::Config;
+use crate::middleware::{self, csrf};
 use crate::state::AppState;
+use crate::{alerting, error_reporting};
 
 /// Build the complete application router.
 ///
 /// Structure:
 /// - `GET /health` — lightweight health check (used by Railway)
-/// - `GET /api/v1/health` — detailed health check with database connectivity
+/// - `GET /.well-known/jwks.json` — public keys for verifying access tokens
+/// - `GET /api/v1/health` — detailed health check of the database, migrations, storage and email
+/// - `GET /api/v1/status` — public status page: component health, the ongoing incident and uptime
+/// - `/api/v1/admin/...` — admin-only catalog management, featured games, impersonation, the
+///   sign-in and admin action audit logs and verification requests, plus the moderators'
+///   report queue, game takedowns and takedown appeals, platform announcements, the
+///   copyright notice queue, force-ending live sessions, user account details with
+///   moderators' notes, suspension appeals, platform-wide daily stats, A/B experiments, the
+///   status page incident, the runtime log filter, runtime relay and database pool metrics, and
+///   seeding fake data outside production
+/// - `/api/v1/analytics/events` — batched product analytics events from clients
+/// - `/api/v1/announcements/active` — platform banners currently in their display window
+/// - `/api/v1/experiments/assignments` — the caller's variants of running A/B experiments
 /// - `/api/v1/auth/...` — authentication endpoints
+/// - `/api/v1/auth/webauthn/...` — passkey registration and sign-in
+/// - `/api/v1/auth/appeal` — suspended accounts appealing their suspension
+/// - `/api/v1/oauth/...` — `OAuth2` provider for third-party tools
 /// - `/api/v1/users/...` — user profile and management endpoints
+/// - `/api/v1/users/me/hosted-sessions` — sessions a user has hosted and how they went
+/// - `/api/v1/users/me/tokens/...` — personal access tokens for scripts and CI
+/// - `/api/v1/users/me/export/...` — downloadable archives of a user's personal data
+/// - `/api/v1/users/me/notifications/...` — in-app notifications and their `WebSocket` push channel
+/// - `/api/v1/users/me/notification-preferences` — which non-transactional emails a user gets
+/// - `/api/v1/users/me/feed` — recent activity of followed creators
+/// - `/api/v1/users/me/verification-request` — applying for the verified creator badge
+/// - `/api/v1/users/me/merge` — merging a duplicate account into the authenticated one
+/// - `/api/v1/users/me/preferences` — client settings synced across a user's devices
+/// - `/api/v1/users/me/privacy` — hiding a user from search, their games and their activity
+/// - `/api/v1/users/me/friends`, `/api/v1/users/me/friend-requests/...` — friends and friend
+///   requests
 /// - `/api/v1/games/...` — game management endpoints
+/// - `/api/v1/games/{id}/counter-notice` — answering a copyright notice against a game
+/// - `/api/v1/games/{id}/reviews/...` — game reviews and creator replies
+/// - `/api/v1/games/{id}/collaborators/...` — editor / viewer collaborators
+/// - `/api/v1/games/{id}/export`, `/api/v1/games/import` — game archives
+/// - `/api/v1/games/{id}/stats` — creator analytics
+/// - `/api/v1/games/{id}/embed`, `/api/v1/oembed` — embeddable game cards
 /// - `/api/v1/tags` — platform tag listing
+/// - `/api/v1/templates` — starter game templates
+/// - `/api/v1/classrooms/...` — teachers' classes and their managed student accounts
+/// - `/api/v1/collections/...` — user-curated game collections
+/// - `/api/v1/organizations/...` — organizations, their members and the games they own
+/// - `/api/v1/search/...` — game discovery search
+/// - `/api/v1/reports` — abuse reports for moderators
+/// - `/api/v1/legal/takedown` — copyright takedown notices from rights holders
+/// - `/api/v1/avatars/generate` — generated fallback avatars
+/// - `/api/v1/billing/checkout` — starting a Stripe subscription checkout
+/// - `/api/v1/webhooks/stripe` — Stripe subscription lifecycle events
 /// - `/api/v1/sessions/...` — game session management and `WebSocket` relay
-pub fn router() -> Router<AppState> {
+/// - `/api/v1/sessions/{id}/invites/...` — inviting friends and followers into a session
+///
+/// Every `/api/v1` route checks the CSRF token of mutating requests authenticated by cookie
+/// (see [`csrf::enforce`]). Internal errors of every route are reported to Sentry when it is
+/// configured (see [`error_reporting::capture`]) and counted towards error-rate alerts (see
+/// [`alerting::track`]), and the route is recorded on the request's
+/// log span (see [`middleware::route::record`]). Sign-in, joins and analytics are rate limited
+/// with `config.rate_limits`.
+pub fn router(config: &Config) -> Router<AppState> {
     let api_v1 = Router::new()
         .merge(health::api_router())
-        .nest("/auth", auth::router())
+        .merge(status::api_router())
+        .merge(embed::api_router())
+        .nest("/admin", admin::router())
+        .nest(
+            "/analytics",
+            analytics::router(config.rate_limits.analytics),
+        )
+        .nest("/announcements", announcements::router())
+        .nest("/experiments", experiments::router())
+        .nest(
+            "/auth",
+            auth::router(config.rate_limits.credentials)
+                .merge(webauthn::router(config.rate_limits.credentials))
+                .merge(suspension_appeals::router()),
+        )
+        .nest("/oauth", oauth_server::router())
         .nest("/users", users::router())
-        .nest("/games", games::router())
+        .nest(
+            "/games",
+            games::router()
+                .merge(reviews::router())
+                .merge(collaborators::router())
+                .merge(export::router())
+                .merge(stats::router())
+                .merge(embed::router()),
+        )
         .nest("/tags", games::tags_router())
-        .nest("/sessions", sessions::router());
+        .nest("/templates", games::templates_router())
+        .nest("/classrooms", classrooms::router())
+        .nest("/collections", collections::router())
+        .nest("/organizations", organizations::router())
+        .nest("/search", games::search_router())
+        .nest("/reports", reports::router())
+        .nest("/legal", legal::router())
+        .nest("/avatars", avatars::router())
+        .nest("/billing", billing::router())
+        .nest("/webhooks", billing::webhooks_router())
+        .nest(
+            "/sessions",
+            sessions::router(config.rate_limits.join).merge(session_invites::router()),
+        )
+        .layer(axum::middleware::from_fn(csrf::enforce));
 
     Router::new()
         .merge(health::root_router())
+        .merge(well_known::root_router())
         .nest("/api/v1", api_v1)
+        .layer(axum::middleware::from_fn(error_reporting::capture))
+        .layer(axum::middleware::from_fn(alerting::track))
+        .layer(axum::middleware::from_fn(middleware::route::record))
 }
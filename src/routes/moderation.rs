@@ -0,0 +1,885 @@
+use std::collections::HashMap;
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+    sea_query::Expr,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    audit,
+    auth::middleware::ModeratorUser,
+    entities::{content_report, game, game_takedown, review, user, user_note},
+    error::AppError,
+    notifications,
+    routes::{
+        games::{PaginatedResponse, REMOVED_BY_MODERATION},
+        reviews::refresh_rating_stats,
+        takedowns::remove_game,
+    },
+    state::AppState,
+};
+
+/// `content_report.status` of a report no moderator has resolved yet.
+const OPEN: &str = "open";
+
+/// `content_report.status` of a resolved report; `resolution` says how.
+const RESOLVED: &str = "resolved";
+
+/// The report did not break the rules; nothing was done to the content.
+const DISMISSED: &str = "dismissed";
+
+/// The content's owner was warned but the content stays up.
+const WARNED: &str = "warned";
+
+/// The content was removed: games are taken down, reviews deleted and users suspended.
+const TAKEN_DOWN: &str = "taken_down";
+
+/// Longest note a moderator may leave on a resolution, in characters.
+const MAX_RESOLUTION_NOTE_LENGTH: usize = 1000;
+
+/// Longest note a moderator may leave on a user account, in characters.
+const MAX_USER_NOTE_LENGTH: usize = 2000;
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+/// Filters for `GET /admin/reports`; all optional and combined with AND.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportsQuery {
+    /// Only reports with this status; defaults to open reports.
+    status: Option<String>,
+    target_type: Option<String>,
+    /// Only reports claimed by this moderator.
+    assigned_to: Option<Uuid>,
+    /// Only reports nobody has claimed; ignored when `assigned_to` is given.
+    #[serde(default)]
+    unassigned: bool,
+    #[serde(default)]
+    offset: u64,
+    #[serde(default = "default_reports_limit")]
+    limit: u64,
+}
+
+const fn default_reports_limit() -> u64 {
+    50
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignReportRequest {
+    /// The moderator to hand the report to, or `null` to release it back to the queue.
+    moderator_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveReportRequest {
+    /// Recorded on the report and, for warnings and takedowns, shown to the content's owner.
+    note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateUserNoteRequest {
+    body: String,
+}
+
+/// A user account as moderators see it, with the notes moderators have left on it.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AdminUserResponse {
+    id: Uuid,
+    email: String,
+    username: String,
+    display_name: Option<String>,
+    avatar_url: Option<String>,
+    role: String,
+    email_verified: bool,
+    verified: bool,
+    subscription_plan: String,
+    account_status: String,
+    suspension_reason: Option<String>,
+    shadow_banned: bool,
+    last_login_at: Option<String>,
+    last_login_ip: Option<String>,
+    created_at: String,
+    deleted_at: Option<String>,
+    /// Newest first.
+    notes: Vec<UserNoteResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UserNoteResponse {
+    id: Uuid,
+    /// `None` once the author's account is purged.
+    author_id: Option<Uuid>,
+    author_username: Option<String>,
+    body: String,
+    created_at: String,
+}
+
+/// A report as moderators see it, with the reported content inlined.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AdminReportResponse {
+    id: Uuid,
+    created_at: String,
+    reporter_id: Option<Uuid>,
+    target_type: String,
+    target_id: Uuid,
+    reason: String,
+    details: Option<String>,
+    status: String,
+    assigned_to: Option<Uuid>,
+    assigned_at: Option<String>,
+    resolved_by: Option<Uuid>,
+    resolution: Option<String>,
+    resolution_note: Option<String>,
+    resolved_at: Option<String>,
+    /// `None` if the content has since been purged.
+    content: Option<ReportedContent>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+enum ReportedContent {
+    Game(ReportedGame),
+    Review(ReportedReview),
+    User(ReportedUser),
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportedGame {
+    id: Uuid,
+    title: String,
+    slug: String,
+    description: Option<String>,
+    owner_id: Uuid,
+    status: String,
+    visibility: String,
+    deleted_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportedReview {
+    id: Uuid,
+    game_id: Uuid,
+    user_id: Uuid,
+    rating: i32,
+    body: Option<String>,
+    deleted_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportedUser {
+    id: Uuid,
+    username: String,
+    display_name: Option<String>,
+    avatar_url: Option<String>,
+    bio: Option<String>,
+    account_status: String,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `GET /admin/reports` — The moderation queue, oldest first. Lists open reports unless
+/// `status` says otherwise.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if a database query fails.
+pub async fn list_reports(
+    State(state): State<AppState>,
+    ModeratorUser(_moderator): ModeratorUser,
+    Query(query): Query<ReportsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let status = query.status.unwrap_or_else(|| OPEN.to_string());
+    let mut find = content_report::Entity::find().filter(content_report::Column::Status.eq(status));
+    if let Some(target_type) = query.target_type {
+        find = find.filter(content_report::Column::TargetType.eq(target_type));
+    }
+    if let Some(moderator_id) = query.assigned_to {
+        find = find.filter(content_report::Column::AssignedTo.eq(moderator_id));
+    } else if query.unassigned {
+        find = find.filter(content_report::Column::AssignedTo.is_null());
+    }
+    let total = find.clone().count(&state.db).await?;
+
+    let reports = find
+        .order_by_asc(content_report::Column::CreatedAt)
+        .offset(query.offset)
+        .limit(query.limit)
+        .all(&state.db)
+        .await?;
+    let content = load_content(&state.db, &reports).await?;
+
+    Ok(Json(PaginatedResponse {
+        data: reports
+            .into_iter()
+            .map(|report| {
+                let reported = content.get(&report.target_id).cloned();
+                to_admin_report_response(report, reported)
+            })
+            .collect(),
+        total,
+        offset: query.offset,
+        limit: query.limit,
+    }))
+}
+
+/// `POST /admin/reports/{id}/claim` — Take an open report, so other moderators leave it to
+/// the caller.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if there is no open report with that ID,
+/// [`AppError::Conflict`] if another moderator has claimed it, or [`AppError`] if a database
+/// operation fails.
+pub async fn claim_report(
+    State(state): State<AppState>,
+    ModeratorUser(moderator): ModeratorUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let report = find_open_report(&state.db, id).await?;
+    ensure_not_claimed_by_other(&report, &moderator)?;
+
+    let claimed = assign(&state.db, report, Some(moderator.id)).await?;
+    audit::Event::new("report.claim", "report", id)
+        .after(serde_json::json!({ "assignedTo": claimed.assigned_to }))
+        .record(&state.db, &moderator, &headers)
+        .await;
+    Ok(Json(report_response(&state.db, claimed).await?))
+}
+
+/// `POST /admin/reports/{id}/assign` — Hand an open report to a moderator, or release it.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if there is no open report with that ID,
+/// [`AppError::BadRequest`] if the assignee is not a moderator, or [`AppError`] if a database
+/// operation fails.
+pub async fn assign_report(
+    State(state): State<AppState>,
+    ModeratorUser(moderator): ModeratorUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(body): Json<AssignReportRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let report = find_open_report(&state.db, id).await?;
+
+    if let Some(moderator_id) = body.moderator_id {
+        let is_moderator = user::Entity::find_by_id(moderator_id)
+            .filter(user::Column::DeletedAt.is_null())
+            .one(&state.db)
+            .await?
+            .is_some_and(|u| u.role == "moderator" || u.role == "admin");
+        if !is_moderator {
+            return Err(AppError::BadRequest(
+                "Reports can only be assigned to moderators.".to_string(),
+            ));
+        }
+    }
+
+    let previous = report.assigned_to;
+    let assigned = assign(&state.db, report, body.moderator_id).await?;
+    audit::Event::new("report.assign", "report", id)
+        .before(serde_json::json!({ "assignedTo": previous }))
+        .after(serde_json::json!({ "assignedTo": assigned.assigned_to }))
+        .record(&state.db, &moderator, &headers)
+        .await;
+    Ok(Json(report_response(&state.db, assigned).await?))
+}
+
+/// `POST /admin/reports/{id}/dismiss` — Resolve a report without acting on the content.
+///
+/// # Errors
+///
+/// See [`resolve`].
+pub async fn dismiss_report(
+    State(state): State<AppState>,
+    ModeratorUser(moderator): ModeratorUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(body): Json<ResolveReportRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    resolve(&state, id, &moderator, &headers, DISMISSED, body.note).await
+}
+
+/// `POST /admin/reports/{id}/warn` — Resolve a report by warning the content's owner.
+///
+/// # Errors
+///
+/// See [`resolve`].
+pub async fn warn_report(
+    State(state): State<AppState>,
+    ModeratorUser(moderator): ModeratorUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(body): Json<ResolveReportRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    resolve(&state, id, &moderator, &headers, WARNED, body.note).await
+}
+
+/// `POST /admin/reports/{id}/takedown` — Resolve a report by removing the content: the game
+/// is taken down, the review deleted, or the user suspended.
+///
+/// # Errors
+///
+/// See [`resolve`].
+pub async fn takedown_report(
+    State(state): State<AppState>,
+    ModeratorUser(moderator): ModeratorUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(body): Json<ResolveReportRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    resolve(&state, id, &moderator, &headers, TAKEN_DOWN, body.note).await
+}
+
+/// `POST /admin/users/{id}/shadow-ban` — Hide a user from everyone else without telling them:
+/// their games and reviews drop out of public listings, search and rating averages, and their
+/// reviews, follows, friend requests and invites stop notifying anyone. The user keeps seeing
+/// their own content as usual. Shadow-banning a shadow-banned user is a no-op.
+///
+/// # Errors
+///
+/// See [`set_shadow_banned`].
+pub async fn shadow_ban_user(
+    State(state): State<AppState>,
+    ModeratorUser(moderator): ModeratorUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    set_shadow_banned(&state, &moderator, &headers, id, true).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `DELETE /admin/users/{id}/shadow-ban` — Lift a user's shadow ban.
+///
+/// # Errors
+///
+/// See [`set_shadow_banned`].
+pub async fn shadow_unban_user(
+    State(state): State<AppState>,
+    ModeratorUser(moderator): ModeratorUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    set_shadow_banned(&state, &moderator, &headers, id, false).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GET /admin/users/{id}` — A user account with moderators' notes on it. Deleted accounts
+/// are included until they are purged.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if the user does not exist, or [`AppError`] if a database
+/// query fails.
+pub async fn get_user(
+    State(state): State<AppState>,
+    ModeratorUser(_moderator): ModeratorUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let target = user::Entity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found.".to_string()))?;
+
+    let notes = user_note::Entity::find()
+        .filter(user_note::Column::UserId.eq(id))
+        .order_by_desc(user_note::Column::CreatedAt)
+        .all(&state.db)
+        .await?;
+    let authors: HashMap<Uuid, String> = user::Entity::find()
+        .filter(user::Column::Id.is_in(notes.iter().filter_map(|n| n.author_id)))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u.username))
+        .collect();
+
+    Ok(Json(AdminUserResponse {
+        id: target.id,
+        email: target.email,
+        username: target.username,
+        display_name: target.display_name,
+        avatar_url: target.avatar_url,
+        role: target.role,
+        email_verified: target.email_verified,
+        verified: target.verified,
+        subscription_plan: target.subscription_plan,
+        account_status: target.account_status,
+        suspension_reason: target.suspension_reason,
+        shadow_banned: target.shadow_banned,
+        last_login_at: target.last_login_at.map(|t| t.to_rfc3339()),
+        last_login_ip: target.last_login_ip,
+        created_at: target.created_at.to_rfc3339(),
+        deleted_at: target.deleted_at.map(|t| t.to_rfc3339()),
+        notes: notes
+            .into_iter()
+            .map(|note| to_user_note_response(note, &authors))
+            .collect(),
+    }))
+}
+
+/// `POST /admin/users/{id}/notes` — Leave a note on a user account for other moderators, e.g.
+/// about a warning given outside the report queue. Notes cannot be edited or deleted.
+///
+/// # Errors
+///
+/// Returns [`AppError::BadRequest`] if the note is empty or too long,
+/// [`AppError::NotFound`] if the user does not exist, or [`AppError`] if a database operation
+/// fails.
+pub async fn create_user_note(
+    State(state): State<AppState>,
+    ModeratorUser(moderator): ModeratorUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(body): Json<CreateUserNoteRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let text = body.body.trim();
+    if text.is_empty() {
+        return Err(AppError::BadRequest("Note must not be empty.".to_string()));
+    }
+    if text.chars().count() > MAX_USER_NOTE_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Note must be at most {MAX_USER_NOTE_LENGTH} characters."
+        )));
+    }
+    if user::Entity::find_by_id(id).one(&state.db).await?.is_none() {
+        return Err(AppError::NotFound("User not found.".to_string()));
+    }
+
+    let note = user_note::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(id),
+        author_id: Set(Some(moderator.id)),
+        body: Set(text.to_string()),
+        created_at: Set(Utc::now().fixed_offset()),
+    }
+    .insert(&state.db)
+    .await?;
+
+    audit::Event::new("user.note", "user", id)
+        .after(serde_json::json!({ "noteId": note.id, "body": note.body }))
+        .record(&state.db, &moderator, &headers)
+        .await;
+
+    let authors = HashMap::from([(moderator.id, moderator.username)]);
+    Ok((
+        StatusCode::CREATED,
+        Json(to_user_note_response(note, &authors)),
+    ))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Resolve open report `id` with `resolution`, along with every other open report against the
+/// same content, and tell the content's owner about warnings and takedowns.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if there is no open report with that ID,
+/// [`AppError::Conflict`] if another moderator has claimed it, [`AppError::BadRequest`] if the
+/// note is too long, or [`AppError`] if a database operation fails.
+async fn resolve(
+    state: &AppState,
+    id: Uuid,
+    moderator: &user::Model,
+    headers: &HeaderMap,
+    resolution: &str,
+    note: Option<String>,
+) -> Result<Json<AdminReportResponse>, AppError> {
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if note
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_RESOLUTION_NOTE_LENGTH)
+    {
+        return Err(AppError::BadRequest(format!(
+            "Note must be at most {MAX_RESOLUTION_NOTE_LENGTH} characters."
+        )));
+    }
+
+    let report = find_open_report(&state.db, id).await?;
+    ensure_not_claimed_by_other(&report, moderator)?;
+    let owner = content_owner(&state.db, &report).await?;
+
+    let txn = state.db.begin().await?;
+    let removed_game = if resolution == TAKEN_DOWN {
+        take_down(&txn, &report, moderator, note.as_deref()).await?
+    } else {
+        None
+    };
+
+    let now = Utc::now().fixed_offset();
+    content_report::Entity::update_many()
+        .col_expr(content_report::Column::Status, Expr::value(RESOLVED))
+        .col_expr(
+            content_report::Column::ResolvedBy,
+            Expr::value(moderator.id),
+        )
+        .col_expr(content_report::Column::Resolution, Expr::value(resolution))
+        .col_expr(
+            content_report::Column::ResolutionNote,
+            Expr::value(note.clone()),
+        )
+        .col_expr(content_report::Column::ResolvedAt, Expr::value(now))
+        .filter(content_report::Column::TargetType.eq(&report.target_type))
+        .filter(content_report::Column::TargetId.eq(report.target_id))
+        .filter(content_report::Column::Status.eq(OPEN))
+        .exec(&txn)
+        .await?;
+    txn.commit().await?;
+
+    let resolved = content_report::Entity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Report not found.".to_string()))?;
+
+    let action = match resolution {
+        DISMISSED => "report.dismiss",
+        WARNED => "report.warn",
+        _ => "report.takedown",
+    };
+    audit::Event::new(action, "report", id)
+        .before(serde_json::json!({ "status": report.status, "assignedTo": report.assigned_to }))
+        .after(serde_json::json!({
+            "status": resolved.status,
+            "resolution": resolved.resolution,
+            "resolutionNote": resolved.resolution_note,
+        }))
+        .record(&state.db, moderator, headers)
+        .await;
+
+    if let Some((game, takedown)) = removed_game {
+        notifications::game_removed(state, &game, &takedown).await;
+    } else if resolution != DISMISSED
+        && let Some((owner_id, description)) = owner
+    {
+        notifications::moderation_outcome(state, owner_id, &resolved, &description).await;
+    }
+
+    Ok(Json(report_response(&state.db, resolved).await?))
+}
+
+/// Shadow-ban or unban user `id`, then recompute the ratings of the games they reviewed, which
+/// leave out reviews by shadow-banned users.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if the user does not exist, or [`AppError`] if a database
+/// operation fails.
+async fn set_shadow_banned(
+    state: &AppState,
+    moderator: &user::Model,
+    headers: &HeaderMap,
+    id: Uuid,
+    banned: bool,
+) -> Result<(), AppError> {
+    let target = user::Entity::find_by_id(id)
+        .filter(user::Column::DeletedAt.is_null())
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found.".to_string()))?;
+    if target.shadow_banned == banned {
+        return Ok(());
+    }
+
+    let txn = state.db.begin().await?;
+    let mut active: user::ActiveModel = target.into();
+    active.shadow_banned = Set(banned);
+    active.updated_at = Set(Utc::now().fixed_offset());
+    active.update(&txn).await?;
+
+    let reviewed: Vec<Uuid> = review::Entity::find()
+        .select_only()
+        .column(review::Column::GameId)
+        .filter(review::Column::UserId.eq(id))
+        .filter(review::Column::DeletedAt.is_null())
+        .into_tuple()
+        .all(&txn)
+        .await?;
+    for game in game::Entity::find()
+        .filter(game::Column::Id.is_in(reviewed))
+        .all(&txn)
+        .await?
+    {
+        refresh_rating_stats(&txn, game).await?;
+    }
+    txn.commit().await?;
+
+    let action = if banned {
+        "user.shadow_ban"
+    } else {
+        "user.shadow_unban"
+    };
+    audit::Event::new(action, "user", id)
+        .before(serde_json::json!({ "shadowBanned": !banned }))
+        .after(serde_json::json!({ "shadowBanned": banned }))
+        .record(&state.db, moderator, headers)
+        .await;
+    Ok(())
+}
+
+fn to_user_note_response(
+    note: user_note::Model,
+    authors: &HashMap<Uuid, String>,
+) -> UserNoteResponse {
+    UserNoteResponse {
+        id: note.id,
+        author_id: note.author_id,
+        author_username: note.author_id.and_then(|a| authors.get(&a).cloned()),
+        body: note.body,
+        created_at: note.created_at.to_rfc3339(),
+    }
+}
+
+/// Remove the reported content. Content that is already gone is left alone. Returns the game
+/// and its takedown when a game was removed, so its creator can be told how to appeal.
+async fn take_down<C: ConnectionTrait>(
+    db: &C,
+    report: &content_report::Model,
+    moderator: &user::Model,
+    note: Option<&str>,
+) -> Result<Option<(game::Model, game_takedown::Model)>, AppError> {
+    let reason = note.map_or_else(
+        || format!("Removed by moderation ({}).", report.reason),
+        ToString::to_string,
+    );
+    match report.target_type.as_str() {
+        "game" => {
+            if let Some(game) = game::Entity::find_by_id(report.target_id)
+                .filter(game::Column::DeletedAt.is_null())
+                .filter(game::Column::Status.ne(REMOVED_BY_MODERATION))
+                .one(db)
+                .await?
+            {
+                return Ok(Some(remove_game(db, game, moderator.id, reason).await?));
+            }
+        }
+        "review" => {
+            if let Some(review) = review::Entity::find_by_id(report.target_id)
+                .filter(review::Column::DeletedAt.is_null())
+                .one(db)
+                .await?
+            {
+                let game_id = review.game_id;
+                let mut active: review::ActiveModel = review.into();
+                active.deleted_at = Set(Some(Utc::now().fixed_offset()));
+                active.update(db).await?;
+
+                if let Some(game) = game::Entity::find_by_id(game_id).one(db).await? {
+                    refresh_rating_stats(db, game).await?;
+                }
+            }
+        }
+        _ => {
+            if let Some(target) = user::Entity::find_by_id(report.target_id)
+                .filter(user::Column::DeletedAt.is_null())
+                .one(db)
+                .await?
+            {
+                let mut active: user::ActiveModel = target.into();
+                active.account_status = Set("suspended".to_string());
+                active.suspension_reason = Set(Some(reason));
+                active.updated_at = Set(Utc::now().fixed_offset());
+                active.update(db).await?;
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// The account responsible for the reported content, and how to refer to the content when
+/// telling them about it.
+async fn content_owner(
+    db: &DatabaseConnection,
+    report: &content_report::Model,
+) -> Result<Option<(Uuid, String)>, AppError> {
+    Ok(match report.target_type.as_str() {
+        "game" => game::Entity::find_by_id(report.target_id)
+            .one(db)
+            .await?
+            .map(|g| (g.owner_id, format!("your game {}", g.title))),
+        "review" => review::Entity::find_by_id(report.target_id)
+            .one(db)
+            .await?
+            .map(|r| (r.user_id, "your review".to_string())),
+        _ => user::Entity::find_by_id(report.target_id)
+            .one(db)
+            .await?
+            .map(|u| (u.id, "your account".to_string())),
+    })
+}
+
+async fn find_open_report(
+    db: &DatabaseConnection,
+    id: Uuid,
+) -> Result<content_report::Model, AppError> {
+    content_report::Entity::find_by_id(id)
+        .filter(content_report::Column::Status.eq(OPEN))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Report not found.".to_string()))
+}
+
+/// Claimed reports are left to the moderator who claimed them until they release or reassign
+/// them.
+fn ensure_not_claimed_by_other(
+    report: &content_report::Model,
+    moderator: &user::Model,
+) -> Result<(), AppError> {
+    match report.assigned_to {
+        Some(assignee) if assignee != moderator.id => Err(AppError::Conflict(
+            "This report is claimed by another moderator.".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+async fn assign(
+    db: &DatabaseConnection,
+    report: content_report::Model,
+    moderator_id: Option<Uuid>,
+) -> Result<content_report::Model, AppError> {
+    let mut active: content_report::ActiveModel = report.into();
+    active.assigned_to = Set(moderator_id);
+    active.assigned_at = Set(moderator_id.map(|_| Utc::now().fixed_offset()));
+    Ok(active.update(db).await?)
+}
+
+/// The reported content of each report, keyed by target ID.
+async fn load_content(
+    db: &DatabaseConnection,
+    reports: &[content_report::Model],
+) -> Result<HashMap<Uuid, ReportedContent>, AppError> {
+    let targets = |target_type: &str| -> Vec<Uuid> {
+        reports
+            .iter()
+            .filter(|r| r.target_type == target_type)
+            .map(|r| r.target_id)
+            .collect()
+    };
+    let mut content = HashMap::new();
+
+    let game_ids = targets("game");
+    if !game_ids.is_empty() {
+        for g in game::Entity::find()
+            .filter(game::Column::Id.is_in(game_ids))
+            .all(db)
+            .await?
+        {
+            content.insert(
+                g.id,
+                ReportedContent::Game(ReportedGame {
+                    id: g.id,
+                    title: g.title,
+                    slug: g.slug,
+                    description: g.description,
+                    owner_id: g.owner_id,
+                    status: g.status,
+                    visibility: g.visibility,
+                    deleted_at: g.deleted_at.map(|t| t.to_rfc3339()),
+                }),
+            );
+        }
+    }
+
+    let review_ids = targets("review");
+    if !review_ids.is_empty() {
+        for r in review::Entity::find()
+            .filter(review::Column::Id.is_in(review_ids))
+            .all(db)
+            .await?
+        {
+            content.insert(
+                r.id,
+                ReportedContent::Review(ReportedReview {
+                    id: r.id,
+                    game_id: r.game_id,
+                    user_id: r.user_id,
+                    rating: r.rating,
+                    body: r.body,
+                    deleted_at: r.deleted_at.map(|t| t.to_rfc3339()),
+                }),
+            );
+        }
+    }
+
+    let user_ids = targets("user");
+    if !user_ids.is_empty() {
+        for u in user::Entity::find()
+            .filter(user::Column::Id.is_in(user_ids))
+            .all(db)
+            .await?
+        {
+            content.insert(
+                u.id,
+                ReportedContent::User(ReportedUser {
+                    id: u.id,
+                    username: u.username,
+                    display_name: u.display_name,
+                    avatar_url: u.avatar_url,
+                    bio: u.bio,
+                    account_status: u.account_status,
+                }),
+            );
+        }
+    }
+
+    Ok(content)
+}
+
+async fn report_response(
+    db: &DatabaseConnection,
+    report: content_report::Model,
+) -> Result<AdminReportResponse, AppError> {
+    let content = load_content(db, std::slice::from_ref(&report))
+        .await?
+        .remove(&report.target_id);
+    Ok(to_admin_report_response(report, content))
+}
+
+fn to_admin_report_response(
+    report: content_report::Model,
+    content: Option<ReportedContent>,
+) -> AdminReportResponse {
+    AdminReportResponse {
+        id: report.id,
+        created_at: report.created_at.to_rfc3339(),
+        reporter_id: report.reporter_id,
+        target_type: report.target_type,
+        target_id: report.target_id,
+        reason: report.reason,
+        details: report.details,
+        status: report.status,
+        assigned_to: report.assigned_to,
+        assigned_at: report.assigned_at.map(|t| t.to_rfc3339()),
+        resolved_by: report.resolved_by,
+        resolution: report.resolution,
+        resolution_note: report.resolution_note,
+        resolved_at: report.resolved_at.map(|t| t.to_rfc3339()),
+        content,
+    }
+}
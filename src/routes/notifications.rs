@@ -0,0 +1,285 @@
+use axum::{
+    Json,
+    extract::{
+        Path, Query, State, WebSocketUpgrade,
+        ws::{Message, WebSocket},
+    },
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use futures_util::{SinkExt, StreamExt};
+use sea_orm::ActiveValue::Set;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    auth::{jwt, middleware::AuthUser},
+    email,
+    entities::{notification, notification_preference, user},
+    error::AppError,
+    notifications::NotificationView,
+    state::AppState,
+};
+
+/// Most notifications returned by the list endpoint.
+const MAX_LISTED: u64 = 50;
+
+// ============================================================================
+// Request Types
+// ============================================================================
+
+/// Categories to turn on or off; omitted ones keep their current setting.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePreferencesRequest {
+    product_updates: Option<bool>,
+    review_alerts: Option<bool>,
+    security_alerts: Option<bool>,
+    session_summaries: Option<bool>,
+    session_invites: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationsWsQuery {
+    /// Access token, for clients that cannot set headers on a `WebSocket` handshake.
+    token: Option<String>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `GET /users/me/notifications` — List the authenticated user's most recent notifications,
+/// newest first.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the database query fails.
+pub async fn list_my_notifications(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    let notifications = notification::Entity::find()
+        .filter(notification::Column::UserId.eq(user.id))
+        .order_by_desc(notification::Column::CreatedAt)
+        .limit(MAX_LISTED)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(
+        notifications
+            .into_iter()
+            .map(NotificationView::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// `POST /users/me/notifications/{id}/read` — Mark a notification as read.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if the notification does not exist or belongs to someone
+/// else, or [`AppError`] if the database operation fails.
+pub async fn mark_notification_read(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let found = notification::Entity::find_by_id(id)
+        .filter(notification::Column::UserId.eq(user.id))
+        .one(&state.db)
+        .await?;
+    if found.is_none() {
+        return Err(AppError::NotFound("Notification not found".to_string()));
+    }
+
+    notification::Entity::update_many()
+        .col_expr(
+            notification::Column::ReadAt,
+            Expr::value(Utc::now().fixed_offset()),
+        )
+        .filter(notification::Column::Id.eq(id))
+        .filter(notification::Column::ReadAt.is_null())
+        .exec(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `POST /users/me/notifications/read-all` — Mark all of the authenticated user's
+/// notifications as read.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the database operation fails.
+pub async fn mark_all_notifications_read(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    notification::Entity::update_many()
+        .col_expr(
+            notification::Column::ReadAt,
+            Expr::value(Utc::now().fixed_offset()),
+        )
+        .filter(notification::Column::UserId.eq(user.id))
+        .filter(notification::Column::ReadAt.is_null())
+        .exec(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GET /users/me/notification-preferences` — The categories of non-transactional email the
+/// authenticated user receives.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the database query fails.
+pub async fn get_my_notification_preferences(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(Json(email::preferences(&state.db, user.id).await?))
+}
+
+/// `PATCH /users/me/notification-preferences` — Turn categories of non-transactional email on
+/// or off, returning the resulting preferences.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the database operation fails.
+pub async fn update_my_notification_preferences(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(body): Json<UpdatePreferencesRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let current = email::preferences(&state.db, user.id).await?;
+    let updated = email::Preferences {
+        product_updates: body.product_updates.unwrap_or(current.product_updates),
+        review_alerts: body.review_alerts.unwrap_or(current.review_alerts),
+        security_alerts: body.security_alerts.unwrap_or(current.security_alerts),
+        session_summaries: body.session_summaries.unwrap_or(current.session_summaries),
+        session_invites: body.session_invites.unwrap_or(current.session_invites),
+    };
+
+    notification_preference::Entity::insert(notification_preference::ActiveModel {
+        user_id: Set(user.id),
+        product_updates: Set(updated.product_updates),
+        review_alerts: Set(updated.review_alerts),
+        security_alerts: Set(updated.security_alerts),
+        session_summaries: Set(updated.session_summaries),
+        session_invites: Set(updated.session_invites),
+        updated_at: Set(Utc::now().fixed_offset()),
+    })
+    .on_conflict(
+        OnConflict::column(notification_preference::Column::UserId)
+            .update_columns([
+                notification_preference::Column::ProductUpdates,
+                notification_preference::Column::ReviewAlerts,
+                notification_preference::Column::SecurityAlerts,
+                notification_preference::Column::SessionSummaries,
+                notification_preference::Column::SessionInvites,
+                notification_preference::Column::UpdatedAt,
+            ])
+            .to_owned(),
+    )
+    .exec(&state.db)
+    .await?;
+
+    Ok(Json(updated))
+}
+
+/// `GET /users/me/notifications/ws` — Upgrade to a `WebSocket` that receives the user's new
+/// notifications as they are created.
+///
+/// Authenticates like any other endpoint, or with an access token in the `token` query
+/// parameter. The socket first receives a `connected` message with the unread count, then a
+/// `notification` message per new notification; anything the client sends is ignored.
+///
+/// # Errors
+///
+/// Returns [`AppError::Unauthorized`] if neither the request nor the `token` parameter
+/// authenticates an active user, or [`AppError`] if a database query fails.
+pub async fn notifications_ws(
+    State(state): State<AppState>,
+    auth: Result<AuthUser, AppError>,
+    Query(params): Query<NotificationsWsQuery>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, AppError> {
+    let user_id = match params.token {
+        Some(token) => query_token_user(&state, &token).await?,
+        None => auth?.0.id,
+    };
+
+    let unread = notification::Entity::find()
+        .filter(notification::Column::UserId.eq(user_id))
+        .filter(notification::Column::ReadAt.is_null())
+        .count(&state.db)
+        .await?;
+
+    Ok(ws.on_upgrade(move |socket| handle_ws_connection(state, user_id, unread, socket)))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Resolve the access token passed as a query parameter to an active user.
+///
+/// Impersonation tokens are refused here: their requests must go through the header, where
+/// they are audited.
+async fn query_token_user(state: &AppState, token: &str) -> Result<Uuid, AppError> {
+    let invalid = || AppError::Unauthorized("Invalid or expired token.".to_string());
+    let claims = jwt::validate_access_token(token, &state.jwt_keys).map_err(|_| invalid())?;
+    if claims.act.is_some() {
+        return Err(invalid());
+    }
+    let user_id: Uuid = claims.sub.parse().map_err(|_| invalid())?;
+
+    user::Entity::find_by_id(user_id)
+        .filter(user::Column::DeletedAt.is_null())
+        .filter(user::Column::AccountStatus.eq("active"))
+        .one(&state.db)
+        .await?
+        .map(|u| u.id)
+        .ok_or_else(|| AppError::Unauthorized("User not found.".to_string()))
+}
+
+/// Forward the user's notifications to the socket until it closes.
+async fn handle_ws_connection(state: AppState, user_id: Uuid, unread: u64, socket: WebSocket) {
+    let (mut ws_sink, mut ws_stream) = socket.split();
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+
+    let connection_id = state.notification_hub.register(user_id, tx);
+
+    let connected_msg = serde_json::json!({
+        "type": "connected",
+        "payload": {
+            "unreadCount": unread,
+        }
+    });
+    let _ = ws_sink
+        .send(Message::Text(connected_msg.to_string().into()))
+        .await;
+
+    let send_task = tokio::spawn(async move {
+        while let Some(msg) = rx.recv().await {
+            if ws_sink.send(Message::Text(msg.into())).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    // Inbound messages are ignored; reading just notices the close
+    while let Some(Ok(msg)) = ws_stream.next().await {
+        if matches!(msg, Message::Close(_)) {
+            break;
+        }
+    }
+
+    send_task.abort();
+    state.notification_hub.unregister(user_id, connection_id);
+}
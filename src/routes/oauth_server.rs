@@ -0,0 +1,540 @@
+use axum::{
+    Form, Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    auth::{middleware::AuthUser, oauth_server, personal_token},
+    entities::{api_token, oauth_authorization_code, oauth_client},
+    error::AppError,
+    state::AppState,
+};
+
+/// Maximum length (in characters) of a client name.
+const MAX_NAME_LENGTH: usize = 100;
+
+/// Redirect URIs a client can register.
+const MAX_REDIRECT_URIS: usize = 10;
+
+/// Clients a single developer can register.
+const MAX_CLIENTS_PER_USER: u64 = 20;
+
+/// How long an authorization code can be exchanged for.
+const CODE_TTL: chrono::Duration = chrono::Duration::minutes(10);
+
+/// `OAuth2` provider router: `/oauth/...`
+///
+/// Third-party tools register a client, send users to the frontend consent screen (which
+/// reads `GET /authorize` and submits `POST /authorize`), then exchange the code at
+/// `POST /token`. The access tokens issued are personal access tokens tied to the client, so
+/// their scopes are enforced the same way and users can revoke them from
+/// `/users/me/tokens`.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/clients", get(list_my_clients).post(create_client))
+        .route("/clients/{id}", delete(delete_client))
+        .route("/authorize", get(get_authorization).post(authorize))
+        .route("/token", post(token))
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateClientRequest {
+    name: String,
+    redirect_uris: Vec<String>,
+    /// Confidential clients (servers) get a secret; public clients (desktop and browser
+    /// tools) must use PKCE instead.
+    #[serde(default = "default_confidential")]
+    confidential: bool,
+}
+
+const fn default_confidential() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ClientResponse {
+    client_id: Uuid,
+    name: String,
+    redirect_uris: Vec<String>,
+    confidential: bool,
+    created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CreatedClientResponse {
+    #[serde(flatten)]
+    client: ClientResponse,
+    /// Only returned here, for confidential clients.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_secret: Option<String>,
+}
+
+/// Authorization request parameters, named as in RFC 6749 since the frontend passes them
+/// through from the tool's URL unchanged.
+#[derive(Debug, Deserialize)]
+struct AuthorizeParams {
+    response_type: String,
+    client_id: Uuid,
+    redirect_uri: Option<String>,
+    scope: String,
+    state: Option<String>,
+    code_challenge: Option<String>,
+    code_challenge_method: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AuthorizeDecision {
+    #[serde(flatten)]
+    params: AuthorizeParams,
+    approve: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConsentClient {
+    id: Uuid,
+    name: String,
+}
+
+/// What the consent screen shows the user.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConsentResponse {
+    client: ConsentClient,
+    scopes: Vec<String>,
+    redirect_uri: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthorizeResponse {
+    /// Where the frontend should send the browser next.
+    redirect_to: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenRequest {
+    grant_type: String,
+    code: Option<String>,
+    redirect_uri: Option<String>,
+    client_id: Option<Uuid>,
+    client_secret: Option<String>,
+    code_verifier: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct TokenResponse {
+    access_token: String,
+    token_type: &'static str,
+    scope: String,
+}
+
+/// A validated authorization request.
+struct Authorization {
+    client: oauth_client::Model,
+    redirect_uri: String,
+    scopes: String,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `GET /api/v1/oauth/clients` — List the clients the user has registered.
+async fn list_my_clients(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    let clients = oauth_client::Entity::find()
+        .filter(oauth_client::Column::OwnerId.eq(user.id))
+        .order_by_desc(oauth_client::Column::CreatedAt)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(
+        clients
+            .into_iter()
+            .map(to_client_response)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// `POST /api/v1/oauth/clients` — Register a client.
+async fn create_client(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(body): Json<CreateClientRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let name = body.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Name must be between 1 and {MAX_NAME_LENGTH} characters."
+        )));
+    }
+    if body.redirect_uris.is_empty() || body.redirect_uris.len() > MAX_REDIRECT_URIS {
+        return Err(AppError::BadRequest(format!(
+            "Between 1 and {MAX_REDIRECT_URIS} redirect URIs are required."
+        )));
+    }
+    for uri in &body.redirect_uris {
+        oauth_server::validate_redirect_uri(uri).map_err(AppError::BadRequest)?;
+    }
+
+    let existing = oauth_client::Entity::find()
+        .filter(oauth_client::Column::OwnerId.eq(user.id))
+        .count(&state.db)
+        .await?;
+    if existing >= MAX_CLIENTS_PER_USER {
+        return Err(AppError::UnprocessableEntity(format!(
+            "You can register at most {MAX_CLIENTS_PER_USER} clients; delete one first."
+        )));
+    }
+
+    let client_secret = body.confidential.then(oauth_server::generate_client_secret);
+    let client = oauth_client::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        owner_id: ActiveValue::Set(user.id),
+        name: ActiveValue::Set(name.to_string()),
+        client_secret_hash: ActiveValue::Set(client_secret.as_deref().map(personal_token::hash)),
+        redirect_uris: ActiveValue::Set(body.redirect_uris.join(" ")),
+        created_at: ActiveValue::Set(Utc::now().fixed_offset()),
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedClientResponse {
+            client: to_client_response(client),
+            client_secret,
+        }),
+    ))
+}
+
+/// `DELETE /api/v1/oauth/clients/{id}` — Delete a client and revoke every token issued to it.
+async fn delete_client(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let client = oauth_client::Entity::find_by_id(id)
+        .filter(oauth_client::Column::OwnerId.eq(user.id))
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Client not found.".to_string()))?;
+
+    api_token::Entity::delete_many()
+        .filter(api_token::Column::OauthClientId.eq(client.id))
+        .exec(&state.db)
+        .await?;
+    oauth_client::Entity::delete_by_id(client.id)
+        .exec(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GET /api/v1/oauth/authorize` — Validate an authorization request and return what the
+/// consent screen should show.
+async fn get_authorization(
+    State(state): State<AppState>,
+    AuthUser(_user): AuthUser,
+    Query(params): Query<AuthorizeParams>,
+) -> Result<impl IntoResponse, AppError> {
+    let authorization = validate_authorization(&state, &params).await?;
+
+    Ok(Json(ConsentResponse {
+        client: ConsentClient {
+            id: authorization.client.id,
+            name: authorization.client.name,
+        },
+        scopes: authorization
+            .scopes
+            .split_whitespace()
+            .map(String::from)
+            .collect(),
+        redirect_uri: authorization.redirect_uri,
+    }))
+}
+
+/// `POST /api/v1/oauth/authorize` — Record the user's decision and return the redirect back
+/// to the client, carrying either a code or `error=access_denied`.
+async fn authorize(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(body): Json<AuthorizeDecision>,
+) -> Result<impl IntoResponse, AppError> {
+    let authorization = validate_authorization(&state, &body.params).await?;
+
+    let mut redirect_to = reqwest::Url::parse(&authorization.redirect_uri)
+        .map_err(|e| anyhow::anyhow!("Registered redirect URI is invalid: {e}"))?;
+
+    let code = if body.approve {
+        let code = oauth_server::generate_code();
+        let now = Utc::now().fixed_offset();
+        oauth_authorization_code::ActiveModel {
+            id: ActiveValue::Set(Uuid::new_v4()),
+            code_hash: ActiveValue::Set(personal_token::hash(&code)),
+            client_id: ActiveValue::Set(authorization.client.id),
+            user_id: ActiveValue::Set(user.id),
+            redirect_uri: ActiveValue::Set(authorization.redirect_uri),
+            redirect_uri_supplied: ActiveValue::Set(body.params.redirect_uri.is_some()),
+            scopes: ActiveValue::Set(authorization.scopes),
+            code_challenge: ActiveValue::Set(body.params.code_challenge),
+            expires_at: ActiveValue::Set(now + CODE_TTL),
+            created_at: ActiveValue::Set(now),
+        }
+        .insert(&state.db)
+        .await?;
+        Some(code)
+    } else {
+        None
+    };
+
+    {
+        let mut query = redirect_to.query_pairs_mut();
+        match &code {
+            Some(code) => query.append_pair("code", code),
+            None => query.append_pair("error", "access_denied"),
+        };
+        if let Some(client_state) = &body.params.state {
+            query.append_pair("state", client_state);
+        }
+    }
+
+    Ok(Json(AuthorizeResponse {
+        redirect_to: redirect_to.into(),
+    }))
+}
+
+/// `POST /api/v1/oauth/token` — Exchange an authorization code for an access token.
+///
+/// Takes a form body and answers with RFC 6749 error responses, as OAuth client libraries
+/// expect. Confidential clients authenticate with HTTP Basic or `client_secret` in the body.
+async fn token(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Form(body): Form<TokenRequest>,
+) -> Result<Response, AppError> {
+    if body.grant_type != "authorization_code" {
+        return Ok(token_error(
+            StatusCode::BAD_REQUEST,
+            "unsupported_grant_type",
+            "Only the authorization_code grant is supported.",
+        ));
+    }
+
+    let (client_id, client_secret) = match basic_credentials(&headers) {
+        Some((id, secret)) => (id.parse::<Uuid>().ok(), Some(secret)),
+        None => (body.client_id, body.client_secret.clone()),
+    };
+    let client = match client_id {
+        Some(id) => oauth_client::Entity::find_by_id(id).one(&state.db).await?,
+        None => None,
+    };
+    // Public clients have no secret to check
+    let secret_hash = client_secret.as_deref().map(personal_token::hash);
+    let Some(client) =
+        client.filter(|c| c.client_secret_hash.is_none() || c.client_secret_hash == secret_hash)
+    else {
+        return Ok(token_error(
+            StatusCode::UNAUTHORIZED,
+            "invalid_client",
+            "Unknown client or wrong client secret.",
+        ));
+    };
+
+    let invalid_grant = |description: &str| {
+        Ok(token_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_grant",
+            description,
+        ))
+    };
+
+    let Some(code) = body.code.as_deref() else {
+        return invalid_grant("Missing code.");
+    };
+    let Some(record) = oauth_authorization_code::Entity::find()
+        .filter(oauth_authorization_code::Column::CodeHash.eq(personal_token::hash(code)))
+        .one(&state.db)
+        .await?
+    else {
+        return invalid_grant("Invalid or expired code.");
+    };
+
+    // Codes are single-use: whoever deletes the row gets to exchange it
+    let consumed = oauth_authorization_code::Entity::delete_by_id(record.id)
+        .exec(&state.db)
+        .await?;
+    if consumed.rows_affected == 0
+        || record.client_id != client.id
+        || record.expires_at <= Utc::now().fixed_offset()
+    {
+        return invalid_grant("Invalid or expired code.");
+    }
+    // Only required if the authorization request included it (RFC 6749 section 4.1.3)
+    let redirect_uri_matches = body
+        .redirect_uri
+        .as_deref()
+        .map_or(!record.redirect_uri_supplied, |uri| {
+            uri == record.redirect_uri
+        });
+    if !redirect_uri_matches {
+        return invalid_grant("redirect_uri does not match the authorization request.");
+    }
+    if let Some(challenge) = &record.code_challenge {
+        let verified = body
+            .code_verifier
+            .as_deref()
+            .is_some_and(|verifier| oauth_server::verify_pkce(verifier, challenge));
+        if !verified {
+            return invalid_grant("Invalid code_verifier.");
+        }
+    }
+
+    let generated = personal_token::generate();
+    api_token::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        user_id: ActiveValue::Set(record.user_id),
+        name: ActiveValue::Set(client.name),
+        token_hash: ActiveValue::Set(generated.hash),
+        token_prefix: ActiveValue::Set(generated.display_prefix),
+        scopes: ActiveValue::Set(record.scopes.clone()),
+        expires_at: ActiveValue::Set(None),
+        last_used_at: ActiveValue::Set(None),
+        created_at: ActiveValue::Set(Utc::now().fixed_offset()),
+        oauth_client_id: ActiveValue::Set(Some(client.id)),
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok((
+        [(header::CACHE_CONTROL, "no-store")],
+        Json(TokenResponse {
+            access_token: generated.token,
+            token_type: "Bearer",
+            scope: record.scopes,
+        }),
+    )
+        .into_response())
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Check an authorization request against the client's registration.
+async fn validate_authorization(
+    state: &AppState,
+    params: &AuthorizeParams,
+) -> Result<Authorization, AppError> {
+    if params.response_type != "code" {
+        return Err(AppError::BadRequest(
+            "response_type must be 'code'.".to_string(),
+        ));
+    }
+
+    let client = oauth_client::Entity::find_by_id(params.client_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("Unknown client_id.".to_string()))?;
+
+    let registered: Vec<&str> = client.redirect_uris.split_whitespace().collect();
+    let redirect_uri = match (&params.redirect_uri, registered.as_slice()) {
+        (Some(uri), _) if registered.contains(&uri.as_str()) => uri.clone(),
+        // May be omitted when the client registered exactly one
+        (None, [only]) => (*only).to_string(),
+        _ => {
+            return Err(AppError::BadRequest(
+                "redirect_uri is not registered for this client.".to_string(),
+            ));
+        }
+    };
+
+    let scopes = personal_token::normalize_scopes(params.scope.split_whitespace())
+        .map_err(|unknown| AppError::BadRequest(format!("Invalid scope '{unknown}'.")))?;
+
+    match (
+        &params.code_challenge,
+        params.code_challenge_method.as_deref(),
+    ) {
+        (Some(_), Some("S256")) => {}
+        (Some(_), _) => {
+            return Err(AppError::BadRequest(
+                "code_challenge_method must be 'S256'.".to_string(),
+            ));
+        }
+        (None, _) if client.client_secret_hash.is_none() => {
+            return Err(AppError::BadRequest(
+                "Public clients must use PKCE (code_challenge).".to_string(),
+            ));
+        }
+        (None, _) => {}
+    }
+
+    Ok(Authorization {
+        client,
+        redirect_uri,
+        scopes,
+    })
+}
+
+/// Client credentials from an `Authorization: Basic` header (RFC 6749 section 2.3.1).
+fn basic_credentials(headers: &HeaderMap) -> Option<(String, String)> {
+    let encoded = headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Basic ")?;
+    let decoded = data_encoding::BASE64
+        .decode(encoded.trim().as_bytes())
+        .ok()?;
+    let decoded = String::from_utf8(decoded).ok()?;
+    let (id, secret) = decoded.split_once(':')?;
+    Some((
+        urlencoding::decode(id).ok()?.into_owned(),
+        urlencoding::decode(secret).ok()?.into_owned(),
+    ))
+}
+
+fn token_error(status: StatusCode, error: &str, description: &str) -> Response {
+    (
+        status,
+        [(header::CACHE_CONTROL, "no-store")],
+        Json(json!({ "error": error, "error_description": description })),
+    )
+        .into_response()
+}
+
+fn to_client_response(c: oauth_client::Model) -> ClientResponse {
+    ClientResponse {
+        client_id: c.id,
+        name: c.name,
+        redirect_uris: c
+            .redirect_uris
+            .split_whitespace()
+            .map(String::from)
+            .collect(),
+        confidential: c.client_secret_hash.is_some(),
+        created_at: c.created_at.to_rfc3339(),
+    }
+}
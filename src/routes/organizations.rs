@@ -0,0 +1,405 @@
+use std::collections::HashMap;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::middleware::AuthUser,
+    entities::{organization, organization_member, user},
+    error::AppError,
+    notifications,
+    routes::games,
+    state::AppState,
+};
+
+/// Roles a member may be invited with. Each organization has a single `owner`, its founder.
+const INVITE_ROLES: [&str; 2] = ["admin", "editor"];
+
+/// Roles that may invite members.
+const MANAGER_ROLES: [&str; 2] = ["owner", "admin"];
+
+/// Longest organization name or slug accepted.
+const MAX_NAME_LEN: usize = 100;
+
+/// Organization router: `/organizations/...`
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", post(create_organization))
+        .route("/{slug}", get(get_organization))
+        .route("/{slug}/members", get(list_members).post(invite_member))
+        .route("/{slug}/invite/accept", post(accept_invite))
+        .route("/{slug}/invite/decline", post(decline_invite))
+        .route("/{slug}/games", get(games::list_organization_games))
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateOrganizationRequest {
+    name: String,
+    /// URL handle: lowercase letters, digits and hyphens.
+    slug: String,
+    description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InviteMemberRequest {
+    username: String,
+    role: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OrganizationResponse {
+    id: Uuid,
+    name: String,
+    slug: String,
+    description: Option<String>,
+    member_count: u64,
+    created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MemberResponse {
+    user_id: Uuid,
+    username: String,
+    display_name: Option<String>,
+    avatar_url: Option<String>,
+    role: String,
+    invited_by: Option<Uuid>,
+    invited_at: String,
+    /// `None` while the invite is pending.
+    accepted_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct MembersResponse {
+    data: Vec<MemberResponse>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `POST /organizations` — Create an organization, with the caller as its owner.
+///
+/// # Errors
+///
+/// Returns [`AppError::BadRequest`] for an invalid name or slug, [`AppError::Forbidden`] for
+/// guest accounts, [`AppError::Conflict`] if the slug is taken, or [`AppError`] if the
+/// database fails.
+async fn create_organization(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(req): Json<CreateOrganizationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if user.role == "guest" {
+        return Err(AppError::Forbidden(
+            "Guest accounts cannot create organizations.".to_string(),
+        ));
+    }
+    let name = req.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        return Err(AppError::BadRequest(format!(
+            "Name must be 1-{MAX_NAME_LEN} characters"
+        )));
+    }
+    validate_slug(&req.slug)?;
+
+    let taken = organization::Entity::find()
+        .filter(organization::Column::Slug.eq(&req.slug))
+        .one(&state.db)
+        .await?;
+    if taken.is_some() {
+        return Err(AppError::Conflict(
+            "This organization slug is taken".to_string(),
+        ));
+    }
+
+    let now = chrono::Utc::now().fixed_offset();
+    let txn = state.db.begin().await?;
+    let created = organization::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        name: ActiveValue::Set(name.to_string()),
+        slug: ActiveValue::Set(req.slug),
+        description: ActiveValue::Set(req.description),
+        created_at: ActiveValue::Set(now),
+        updated_at: ActiveValue::Set(now),
+    }
+    .insert(&txn)
+    .await?;
+    organization_member::ActiveModel {
+        organization_id: ActiveValue::Set(created.id),
+        user_id: ActiveValue::Set(user.id),
+        role: ActiveValue::Set("owner".to_string()),
+        invited_by: ActiveValue::Set(None),
+        created_at: ActiveValue::Set(now),
+        accepted_at: ActiveValue::Set(Some(now)),
+    }
+    .insert(&txn)
+    .await?;
+    txn.commit().await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(to_organization_response(created, 1)),
+    ))
+}
+
+/// `GET /organizations/:slug` — Get an organization.
+async fn get_organization(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let organization = find_organization(&state.db, &slug).await?;
+    let member_count = organization_member::Entity::find()
+        .filter(organization_member::Column::OrganizationId.eq(organization.id))
+        .filter(organization_member::Column::AcceptedAt.is_not_null())
+        .count(&state.db)
+        .await?;
+
+    Ok(Json(to_organization_response(organization, member_count)))
+}
+
+/// `GET /organizations/:slug/members` — List an organization's members (members only).
+/// Owners and admins also see pending invites.
+async fn list_members(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let organization = find_organization(&state.db, &slug).await?;
+    let role = accepted_role(&state.db, organization.id, user.id)
+        .await?
+        .ok_or_else(|| {
+            AppError::Forbidden("You are not a member of this organization".to_string())
+        })?;
+
+    let mut find = organization_member::Entity::find()
+        .filter(organization_member::Column::OrganizationId.eq(organization.id));
+    if !MANAGER_ROLES.contains(&role.as_str()) {
+        find = find.filter(organization_member::Column::AcceptedAt.is_not_null());
+    }
+    let members = find
+        .order_by_asc(organization_member::Column::CreatedAt)
+        .all(&state.db)
+        .await?;
+
+    let user_ids: Vec<Uuid> = members.iter().map(|m| m.user_id).collect();
+    let users: HashMap<Uuid, user::Model> = user::Entity::find()
+        .filter(user::Column::Id.is_in(user_ids))
+        .filter(user::Column::DeletedAt.is_null())
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u))
+        .collect();
+
+    let data = members
+        .into_iter()
+        .filter_map(|m| {
+            let u = users.get(&m.user_id)?;
+            Some(to_member_response(m, u))
+        })
+        .collect();
+
+    Ok(Json(MembersResponse { data }))
+}
+
+/// `POST /organizations/:slug/members` — Invite a user by username as an `admin` or `editor`
+/// (owners and admins only). The invitee becomes a member once they accept.
+async fn invite_member(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(slug): Path<String>,
+    Json(req): Json<InviteMemberRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let organization = find_organization(&state.db, &slug).await?;
+    let role = accepted_role(&state.db, organization.id, user.id).await?;
+    if !role.is_some_and(|r| MANAGER_ROLES.contains(&r.as_str())) {
+        return Err(AppError::Forbidden(
+            "Only organization owners and admins can invite members".to_string(),
+        ));
+    }
+    if !INVITE_ROLES.contains(&req.role.as_str()) {
+        return Err(AppError::BadRequest(format!(
+            "Invalid role '{}'. Expected one of: admin, editor",
+            req.role
+        )));
+    }
+
+    let invitee = user::Entity::find()
+        .filter(user::Column::Username.eq(req.username.trim()))
+        .filter(user::Column::DeletedAt.is_null())
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    let existing = organization_member::Entity::find_by_id((organization.id, invitee.id))
+        .one(&state.db)
+        .await?;
+    if existing.is_some() {
+        return Err(AppError::Conflict(
+            "This user is already a member or has a pending invite".to_string(),
+        ));
+    }
+
+    let invite = organization_member::ActiveModel {
+        organization_id: ActiveValue::Set(organization.id),
+        user_id: ActiveValue::Set(invitee.id),
+        role: ActiveValue::Set(req.role),
+        invited_by: ActiveValue::Set(Some(user.id)),
+        created_at: ActiveValue::Set(chrono::Utc::now().fixed_offset()),
+        accepted_at: ActiveValue::Set(None),
+    }
+    .insert(&state.db)
+    .await?;
+
+    notifications::organization_invite(&state, invitee.id, &user, &organization).await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(to_member_response(invite, &invitee)),
+    ))
+}
+
+/// `POST /organizations/:slug/invite/accept` — Accept the caller's pending invite.
+async fn accept_invite(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let organization = find_organization(&state.db, &slug).await?;
+    let invite = find_pending_invite(&state.db, organization.id, user.id).await?;
+
+    let mut active: organization_member::ActiveModel = invite.into();
+    active.accepted_at = ActiveValue::Set(Some(chrono::Utc::now().fixed_offset()));
+    let member = active.update(&state.db).await?;
+
+    Ok(Json(to_member_response(member, &user)))
+}
+
+/// `POST /organizations/:slug/invite/decline` — Decline the caller's pending invite.
+async fn decline_invite(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let organization = find_organization(&state.db, &slug).await?;
+    find_pending_invite(&state.db, organization.id, user.id).await?;
+
+    organization_member::Entity::delete_by_id((organization.id, user.id))
+        .exec(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Load an organization by its slug.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if no organization has the slug, or [`AppError`] if the
+/// database query fails.
+pub async fn find_organization(
+    db: &DatabaseConnection,
+    slug: &str,
+) -> Result<organization::Model, AppError> {
+    organization::Entity::find()
+        .filter(organization::Column::Slug.eq(slug))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))
+}
+
+/// `user_id`'s role in an organization, if they are a member. Pending invites do not count.
+///
+/// # Errors
+///
+/// Returns [`DbErr`] if the database query fails.
+pub async fn accepted_role(
+    db: &DatabaseConnection,
+    organization_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<String>, DbErr> {
+    Ok(
+        organization_member::Entity::find_by_id((organization_id, user_id))
+            .filter(organization_member::Column::AcceptedAt.is_not_null())
+            .one(db)
+            .await?
+            .map(|m| m.role),
+    )
+}
+
+async fn find_pending_invite(
+    db: &DatabaseConnection,
+    organization_id: Uuid,
+    user_id: Uuid,
+) -> Result<organization_member::Model, AppError> {
+    organization_member::Entity::find_by_id((organization_id, user_id))
+        .filter(organization_member::Column::AcceptedAt.is_null())
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Invite not found".to_string()))
+}
+
+fn validate_slug(slug: &str) -> Result<(), AppError> {
+    let valid = !slug.is_empty()
+        && slug.len() <= MAX_NAME_LEN
+        && !slug.starts_with('-')
+        && !slug.ends_with('-')
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(
+            "Slug must be lowercase letters, digits and inner hyphens".to_string(),
+        ))
+    }
+}
+
+fn to_organization_response(o: organization::Model, member_count: u64) -> OrganizationResponse {
+    OrganizationResponse {
+        id: o.id,
+        name: o.name,
+        slug: o.slug,
+        description: o.description,
+        member_count,
+        created_at: o.created_at.to_string(),
+    }
+}
+
+fn to_member_response(m: organization_member::Model, u: &user::Model) -> MemberResponse {
+    MemberResponse {
+        user_id: m.user_id,
+        username: u.username.clone(),
+        display_name: u.display_name.clone(),
+        avatar_url: u.avatar_url.clone(),
+        role: m.role,
+        invited_by: m.invited_by,
+        invited_at: m.created_at.to_string(),
+        accepted_at: m.accepted_at.map(|t| t.to_string()),
+    }
+}
@@ -0,0 +1,61 @@
+use axum::{Json, extract::State, response::IntoResponse};
+use chrono::Utc;
+use sea_orm::{ActiveValue::Set, EntityTrait, sea_query::OnConflict};
+use serde_json::Value;
+
+use crate::{
+    auth::middleware::AuthUser, entities::user_preference, error::AppError, preferences,
+    state::AppState,
+};
+
+/// `GET /users/me/preferences` — The authenticated user's synced client preferences, `{}` if
+/// none are set.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the database query fails.
+pub async fn get_my_preferences(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    let document = user_preference::Entity::find_by_id(user.id)
+        .one(&state.db)
+        .await?
+        .map_or_else(|| Value::Object(serde_json::Map::new()), |p| p.data);
+
+    Ok(Json(document))
+}
+
+/// `PUT /users/me/preferences` — Replace the authenticated user's preferences with the given
+/// document, returning it.
+///
+/// # Errors
+///
+/// Returns [`AppError::BadRequest`] if the document has keys outside
+/// [`preferences::KEYS`], values of the wrong type or is larger than
+/// [`preferences::MAX_BYTES`], or [`AppError`] if the database operation fails.
+pub async fn put_my_preferences(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(document): Json<Value>,
+) -> Result<impl IntoResponse, AppError> {
+    preferences::validate(&document).map_err(AppError::BadRequest)?;
+
+    user_preference::Entity::insert(user_preference::ActiveModel {
+        user_id: Set(user.id),
+        data: Set(document.clone()),
+        updated_at: Set(Utc::now().fixed_offset()),
+    })
+    .on_conflict(
+        OnConflict::column(user_preference::Column::UserId)
+            .update_columns([
+                user_preference::Column::Data,
+                user_preference::Column::UpdatedAt,
+            ])
+            .to_owned(),
+    )
+    .exec(&state.db)
+    .await?;
+
+    Ok(Json(document))
+}
@@ -0,0 +1,244 @@
+use axum::{
+    Json, Router,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::post,
+};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+    QueryFilter,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::extract_client_ip,
+    entities::{content_report, game, review, user},
+    error::AppError,
+    routes::games::OptionalAuth,
+    state::AppState,
+};
+
+/// Kinds of content that can be reported.
+const TARGET_TYPES: [&str; 3] = ["game", "review", "user"];
+
+/// Allowed values for `content_report.reason`.
+const REASONS: [&str; 7] = [
+    "spam",
+    "harassment",
+    "hate",
+    "sexual",
+    "violence",
+    "copyright",
+    "other",
+];
+
+/// Maximum length (in characters) of the free-text details.
+const MAX_DETAILS_LENGTH: usize = 2000;
+
+/// Reports a signed-in user may file per hour.
+const REPORTS_PER_HOUR: u64 = 20;
+
+/// Reports a single IP address may file per hour while logged out.
+const ANONYMOUS_REPORTS_PER_HOUR: u64 = 5;
+
+/// Abuse report router: `/reports`
+pub fn router() -> Router<AppState> {
+    Router::new().route("/", post(create_report))
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateReportRequest {
+    target_type: String,
+    target_id: Uuid,
+    reason: String,
+    details: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportResponse {
+    id: Uuid,
+    created_at: String,
+    target_type: String,
+    target_id: Uuid,
+    reason: String,
+    details: Option<String>,
+    status: String,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `POST /reports` — Report a game, review or user to the moderators.
+///
+/// Works without signing in; logged-out reporters are rate limited by IP address.
+async fn create_report(
+    State(state): State<AppState>,
+    OptionalAuth(opt_user): OptionalAuth,
+    headers: HeaderMap,
+    Json(req): Json<CreateReportRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if !TARGET_TYPES.contains(&req.target_type.as_str()) {
+        return Err(AppError::BadRequest(format!(
+            "Invalid targetType '{}'. Expected one of: game, review, user",
+            req.target_type
+        )));
+    }
+    if !REASONS.contains(&req.reason.as_str()) {
+        return Err(AppError::BadRequest(format!(
+            "Invalid reason '{}'. Expected one of: {}",
+            req.reason,
+            REASONS.join(", ")
+        )));
+    }
+    let details = req
+        .details
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty());
+    if details
+        .as_ref()
+        .is_some_and(|d| d.chars().count() > MAX_DETAILS_LENGTH)
+    {
+        return Err(AppError::BadRequest(format!(
+            "Details must be at most {MAX_DETAILS_LENGTH} characters"
+        )));
+    }
+
+    ensure_target_exists(&state.db, &req.target_type, req.target_id).await?;
+
+    let reporter = opt_user.map(|u| u.id);
+    let client_ip = if reporter.is_some() {
+        None
+    } else {
+        extract_client_ip(&headers)
+    };
+    check_rate_limit(&state.db, reporter, client_ip.as_deref()).await?;
+
+    if let Some(reporter_id) = reporter {
+        let already_reported = content_report::Entity::find()
+            .filter(content_report::Column::ReporterId.eq(reporter_id))
+            .filter(content_report::Column::TargetType.eq(&req.target_type))
+            .filter(content_report::Column::TargetId.eq(req.target_id))
+            .filter(content_report::Column::Status.eq("open"))
+            .one(&state.db)
+            .await?
+            .is_some();
+        if already_reported {
+            return Err(AppError::Conflict(
+                "You have already reported this content".to_string(),
+            ));
+        }
+    }
+
+    let report = content_report::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        created_at: ActiveValue::Set(Utc::now().into()),
+        reporter_id: ActiveValue::Set(reporter),
+        reporter_ip: ActiveValue::Set(client_ip),
+        target_type: ActiveValue::Set(req.target_type),
+        target_id: ActiveValue::Set(req.target_id),
+        reason: ActiveValue::Set(req.reason),
+        details: ActiveValue::Set(details),
+        status: ActiveValue::Set("open".to_string()),
+        assigned_to: ActiveValue::Set(None),
+        assigned_at: ActiveValue::Set(None),
+        resolved_by: ActiveValue::Set(None),
+        resolution: ActiveValue::Set(None),
+        resolution_note: ActiveValue::Set(None),
+        resolved_at: ActiveValue::Set(None),
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(to_report_response(report))))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Reject reports against content that does not exist (or is already deleted).
+async fn ensure_target_exists(
+    db: &DatabaseConnection,
+    target_type: &str,
+    target_id: Uuid,
+) -> Result<(), AppError> {
+    let exists = match target_type {
+        "game" => {
+            game::Entity::find_by_id(target_id)
+                .filter(game::Column::DeletedAt.is_null())
+                .count(db)
+                .await?
+        }
+        "review" => {
+            review::Entity::find_by_id(target_id)
+                .filter(review::Column::DeletedAt.is_null())
+                .count(db)
+                .await?
+        }
+        _ => {
+            user::Entity::find_by_id(target_id)
+                .filter(user::Column::DeletedAt.is_null())
+                .count(db)
+                .await?
+        }
+    } > 0;
+
+    if exists {
+        Ok(())
+    } else {
+        Err(AppError::NotFound("Reported content not found".to_string()))
+    }
+}
+
+/// Cap how many reports an account, or a logged-out IP address, files per hour.
+async fn check_rate_limit(
+    db: &DatabaseConnection,
+    user_id: Option<Uuid>,
+    ip: Option<&str>,
+) -> Result<(), AppError> {
+    let since = (Utc::now() - chrono::Duration::hours(1)).fixed_offset();
+    let mut find =
+        content_report::Entity::find().filter(content_report::Column::CreatedAt.gte(since));
+
+    let limit = if let Some(id) = user_id {
+        find = find.filter(content_report::Column::ReporterId.eq(id));
+        REPORTS_PER_HOUR
+    } else {
+        // Reporters without a known IP share one bucket
+        find = find.filter(content_report::Column::ReporterId.is_null());
+        find = match ip {
+            Some(ip) => find.filter(content_report::Column::ReporterIp.eq(ip)),
+            None => find.filter(content_report::Column::ReporterIp.is_null()),
+        };
+        ANONYMOUS_REPORTS_PER_HOUR
+    };
+
+    if find.count(db).await? >= limit {
+        return Err(AppError::TooManyRequests(
+            "Too many reports, please try again later".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn to_report_response(r: content_report::Model) -> ReportResponse {
+    ReportResponse {
+        id: r.id,
+        created_at: r.created_at.to_string(),
+        target_type: r.target_type,
+        target_id: r.target_id,
+        reason: r.reason,
+        details: r.details,
+        status: r.status,
+    }
+}
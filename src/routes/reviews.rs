@@ -0,0 +1,411 @@
+use std::collections::HashMap;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::middleware::AuthUser,
+    entities::{game, review, user},
+    error::AppError,
+    notifications,
+    routes::games::{
+        OptionalAuth, PaginatedResponse, PaginationQuery, check_visibility, find_active_game,
+        not_shadow_banned,
+    },
+    state::AppState,
+    text_moderation, xp,
+};
+
+/// Maximum length (in characters) of a review body or a creator reply.
+const MAX_REVIEW_TEXT_LENGTH: usize = 2000;
+
+/// Review routes, nested under `/games` alongside the main game router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/{id}/reviews", get(list_reviews).post(create_review))
+        .route(
+            "/{id}/reviews/{review_id}/reply",
+            post(create_reply).patch(update_reply).delete(delete_reply),
+        )
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateReviewRequest {
+    rating: i32,
+    body: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReplyRequest {
+    body: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewResponse {
+    id: Uuid,
+    created_at: String,
+    updated_at: String,
+    game_id: Uuid,
+    author: ReviewAuthor,
+    rating: i32,
+    body: Option<String>,
+    creator_reply: Option<CreatorReply>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewAuthor {
+    id: Uuid,
+    username: String,
+    display_name: Option<String>,
+    avatar_url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CreatorReply {
+    body: String,
+    replied_at: String,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `GET /games/:id/reviews` — List reviews of a game, newest first.
+async fn list_reviews(
+    State(state): State<AppState>,
+    OptionalAuth(opt_user): OptionalAuth,
+    Path(id): Path<Uuid>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+    check_visibility(&game, opt_user.as_ref().map(|u| u.id))?;
+
+    let find = review::Entity::find()
+        .filter(review::Column::GameId.eq(id))
+        .filter(review::Column::DeletedAt.is_null())
+        .filter(not_shadow_banned(
+            review::Column::UserId,
+            opt_user.as_ref().map(|u| u.id),
+        ));
+
+    let total = find.clone().count(&state.db).await?;
+
+    let reviews = find
+        .order_by_desc(review::Column::CreatedAt)
+        .offset(pagination.offset)
+        .limit(pagination.limit)
+        .all(&state.db)
+        .await?;
+
+    let author_ids: Vec<Uuid> = reviews.iter().map(|r| r.user_id).collect();
+    let authors: HashMap<Uuid, user::Model> = user::Entity::find()
+        .filter(user::Column::Id.is_in(author_ids))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u))
+        .collect();
+
+    let data = reviews
+        .into_iter()
+        .filter_map(|r| {
+            let author = authors.get(&r.user_id)?;
+            Some(to_review_response(r, author))
+        })
+        .collect();
+
+    Ok(Json(PaginatedResponse {
+        data,
+        total,
+        offset: pagination.offset,
+        limit: pagination.limit,
+    }))
+}
+
+/// `POST /games/:id/reviews` — Review a published game (one review per user).
+async fn create_review(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+    Json(req): Json<CreateReviewRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+    check_visibility(&game, Some(user.id))?;
+
+    if game.status != "published" {
+        return Err(AppError::BadRequest(
+            "Only published games can be reviewed".to_string(),
+        ));
+    }
+    if game.owner_id == user.id {
+        return Err(AppError::Forbidden(
+            "You cannot review your own game".to_string(),
+        ));
+    }
+    if !(1..=5).contains(&req.rating) {
+        return Err(AppError::BadRequest(
+            "rating must be between 1 and 5".to_string(),
+        ));
+    }
+    let body = normalize_text(req.body, "Review")?;
+    let flag = text_moderation::screen(
+        &state.config.text_moderation,
+        &[("review", body.as_deref().unwrap_or_default())],
+    )
+    .await?;
+
+    let existing = review::Entity::find()
+        .filter(review::Column::GameId.eq(id))
+        .filter(review::Column::UserId.eq(user.id))
+        .filter(review::Column::DeletedAt.is_null())
+        .one(&state.db)
+        .await?;
+    if existing.is_some() {
+        return Err(AppError::Conflict(
+            "You have already reviewed this game".to_string(),
+        ));
+    }
+
+    let now = chrono::Utc::now();
+    let created = review::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        created_at: ActiveValue::Set(now.into()),
+        updated_at: ActiveValue::Set(now.into()),
+        deleted_at: ActiveValue::Set(None),
+        game_id: ActiveValue::Set(id),
+        user_id: ActiveValue::Set(user.id),
+        rating: ActiveValue::Set(req.rating),
+        body: ActiveValue::Set(body),
+        creator_reply: ActiveValue::Set(None),
+        creator_replied_at: ActiveValue::Set(None),
+    }
+    .insert(&state.db)
+    .await?;
+
+    text_moderation::report(&state.db, flag, "review", created.id).await;
+    notifications::review_posted(&state, &game, &user, created.rating).await;
+    xp::review_received(&state.db, game.owner_id, created.id).await;
+    refresh_rating_stats(&state.db, game).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(to_review_response(created, &user)),
+    ))
+}
+
+/// `POST /games/:id/reviews/:review_id/reply` — Post the creator's reply to a review.
+async fn create_reply(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path((id, review_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<ReplyRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let review = find_review_as_creator(&state.db, &user, id, review_id).await?;
+
+    if review.creator_reply.is_some() {
+        return Err(AppError::Conflict(
+            "This review already has a reply".to_string(),
+        ));
+    }
+
+    let body = require_reply_body(req.body)?;
+    let flag = text_moderation::screen(&state.config.text_moderation, &[("reply", &body)]).await?;
+    let updated = set_reply(&state.db, review, Some(body)).await?;
+    text_moderation::report(&state.db, flag, "review", updated.id).await;
+    let author = load_author(&state.db, updated.user_id).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(to_review_response(updated, &author)),
+    ))
+}
+
+/// `PATCH /games/:id/reviews/:review_id/reply` — Edit the creator's reply.
+async fn update_reply(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path((id, review_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<ReplyRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let review = find_review_as_creator(&state.db, &user, id, review_id).await?;
+
+    if review.creator_reply.is_none() {
+        return Err(AppError::NotFound("Reply not found".to_string()));
+    }
+
+    let body = require_reply_body(req.body)?;
+    let flag = text_moderation::screen(&state.config.text_moderation, &[("reply", &body)]).await?;
+    let updated = set_reply(&state.db, review, Some(body)).await?;
+    text_moderation::report(&state.db, flag, "review", updated.id).await;
+    let author = load_author(&state.db, updated.user_id).await?;
+
+    Ok(Json(to_review_response(updated, &author)))
+}
+
+/// `DELETE /games/:id/reviews/:review_id/reply` — Remove the creator's reply.
+async fn delete_reply(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path((id, review_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let review = find_review_as_creator(&state.db, &user, id, review_id).await?;
+
+    if review.creator_reply.is_none() {
+        return Err(AppError::NotFound("Reply not found".to_string()));
+    }
+
+    set_reply(&state.db, review, None).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Load a live review of game `game_id`, ensuring `user` is the game's creator.
+async fn find_review_as_creator(
+    db: &DatabaseConnection,
+    user: &user::Model,
+    game_id: Uuid,
+    review_id: Uuid,
+) -> Result<review::Model, AppError> {
+    let game = find_active_game(db, game_id).await?;
+
+    if game.owner_id != user.id {
+        return Err(AppError::Forbidden(
+            "Only the game creator can reply to reviews".to_string(),
+        ));
+    }
+
+    review::Entity::find_by_id(review_id)
+        .filter(review::Column::GameId.eq(game_id))
+        .filter(review::Column::DeletedAt.is_null())
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Review not found".to_string()))
+}
+
+async fn set_reply(
+    db: &DatabaseConnection,
+    review: review::Model,
+    reply: Option<String>,
+) -> Result<review::Model, AppError> {
+    let now = chrono::Utc::now();
+    let replied_at = reply.as_ref().map(|_| now.into());
+
+    let mut active: review::ActiveModel = review.into();
+    active.creator_reply = ActiveValue::Set(reply);
+    active.creator_replied_at = ActiveValue::Set(replied_at);
+    Ok(active.update(db).await?)
+}
+
+/// Recompute `avg_rating` and `review_count` on the game from its live reviews, leaving out
+/// those by shadow-banned users.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if a database operation fails.
+pub async fn refresh_rating_stats<C: ConnectionTrait>(
+    db: &C,
+    game: game::Model,
+) -> Result<(), AppError> {
+    let ratings: Vec<i32> = review::Entity::find()
+        .select_only()
+        .column(review::Column::Rating)
+        .filter(review::Column::GameId.eq(game.id))
+        .filter(review::Column::DeletedAt.is_null())
+        .filter(not_shadow_banned(review::Column::UserId, None))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let count = ratings.len();
+    #[allow(clippy::cast_precision_loss)]
+    let avg = if count == 0 {
+        0.0
+    } else {
+        ratings.iter().map(|r| f64::from(*r)).sum::<f64>() / count as f64
+    };
+
+    let mut active: game::ActiveModel = game.into();
+    #[allow(clippy::cast_possible_truncation)]
+    {
+        active.avg_rating = ActiveValue::Set(avg as f32);
+    }
+    active.review_count = ActiveValue::Set(i64::try_from(count).unwrap_or(i64::MAX));
+    active.update(db).await?;
+
+    Ok(())
+}
+
+async fn load_author(db: &DatabaseConnection, user_id: Uuid) -> Result<user::Model, AppError> {
+    user::Entity::find_by_id(user_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Review author not found".to_string()))
+}
+
+/// Trim optional free text, mapping blanks to `None` and enforcing the length limit.
+fn normalize_text(text: Option<String>, what: &str) -> Result<Option<String>, AppError> {
+    let Some(text) = text.map(|t| t.trim().to_string()).filter(|t| !t.is_empty()) else {
+        return Ok(None);
+    };
+    if text.chars().count() > MAX_REVIEW_TEXT_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "{what} must be at most {MAX_REVIEW_TEXT_LENGTH} characters"
+        )));
+    }
+    Ok(Some(text))
+}
+
+fn require_reply_body(body: String) -> Result<String, AppError> {
+    normalize_text(Some(body), "Reply")?
+        .ok_or_else(|| AppError::BadRequest("Reply body is required".to_string()))
+}
+
+fn to_review_response(r: review::Model, author: &user::Model) -> ReviewResponse {
+    let creator_reply = match (r.creator_reply, r.creator_replied_at) {
+        (Some(body), Some(at)) => Some(CreatorReply {
+            body,
+            replied_at: at.to_string(),
+        }),
+        _ => None,
+    };
+
+    ReviewResponse {
+        id: r.id,
+        created_at: r.created_at.to_string(),
+        updated_at: r.updated_at.to_string(),
+        game_id: r.game_id,
+        author: ReviewAuthor {
+            id: author.id,
+            username: author.username.clone(),
+            display_name: author.display_name.clone(),
+            avatar_url: author.avatar_url.clone(),
+        },
+        rating: r.rating,
+        body: r.body,
+        creator_reply,
+    }
+}
@@ -0,0 +1,609 @@
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use rand::{Rng, seq::SliceRandom};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseTransaction, EntityTrait,
+    IntoActiveModel, QueryFilter, QuerySelect, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::{middleware::AdminUser, password},
+    config::Environment,
+    entities::{
+        auth_provider, game, game_play, game_tag, game_template, game_version, player, review,
+        session, tag, user,
+    },
+    error::AppError,
+    routes::{
+        games::{OWNER_TYPE_USER, unique_slug},
+        sessions::SESSION_CODE_CHARS,
+    },
+    state::AppState,
+};
+
+/// Most accounts one seed request may create.
+const MAX_USERS: u32 = 1000;
+
+/// Most games one seed request may create.
+const MAX_GAMES: u32 = 2000;
+
+/// Most reviews one seeded game may get.
+const MAX_REVIEWS_PER_GAME: u32 = 50;
+
+/// Most historical sessions one seeded game may get.
+const MAX_SESSIONS_PER_GAME: u32 = 20;
+
+/// How far back seeded accounts, games and sessions are dated.
+const HISTORY_DAYS: i64 = 180;
+
+/// Seeded session codes are longer than live ones, so they never take a code a host could get.
+const SEED_SESSION_CODE_LENGTH: usize = 8;
+
+/// Rows per `INSERT`, well under the bind parameter limits of `SQLite` and Postgres.
+const INSERT_CHUNK: usize = 100;
+
+/// Countries seeded players join from.
+const COUNTRIES: &[&str] = &["US", "GB", "DE", "FR", "BR", "JP", "CA", "AU", "IN", "NL"];
+
+const ADJECTIVES: &[&str] = &[
+    "brave", "cosmic", "dizzy", "electric", "fuzzy", "golden", "happy", "jolly", "lucky", "mighty",
+    "neon", "pixel", "quiet", "rapid", "sneaky", "turbo", "wild", "zesty",
+];
+
+const NOUNS: &[&str] = &[
+    "badger", "comet", "dragon", "falcon", "gecko", "koala", "llama", "otter", "panda", "pirate",
+    "robot", "rocket", "tiger", "walrus", "wizard", "yeti",
+];
+
+const GAME_NOUNS: &[&str] = &[
+    "Arena", "Blast", "Brawl", "Dash", "Derby", "Heist", "Party", "Quest", "Rally", "Royale",
+    "Rumble", "Showdown", "Trivia", "Tussle",
+];
+
+const BIOS: &[&str] = &[
+    "Making party games for my friends.",
+    "Weekend game jammer.",
+    "Teacher by day, game designer by night.",
+    "I like games with chaos and confetti.",
+    "Learning to code one game at a time.",
+];
+
+const REVIEW_BODIES: &[&str] = &[
+    "Great with a big group, we played it all evening.",
+    "Fun idea, but the controls take a while to get used to.",
+    "My kids keep asking to play this again.",
+    "Rounds are a bit long for my taste.",
+    "Simple, fast and chaotic. Love it.",
+    "Would be perfect with a few more levels.",
+];
+
+/// Game screen of seeded games when there are no starter templates to copy code from.
+const FALLBACK_GAME_SCREEN_CODE: &str = r"function setup() {
+  createCanvas(800, 600);
+}
+
+function draw() {
+  background(20);
+  fill(255);
+  textAlign(CENTER, CENTER);
+  text('Waiting for players', width / 2, height / 2);
+}
+";
+
+/// Controller of seeded games when there are no starter templates to copy code from.
+const FALLBACK_CONTROLLER_SCREEN_CODE: &str = r"function setup() {
+  createCanvas(windowWidth, windowHeight);
+}
+
+function draw() {
+  background(30);
+  circle(width / 2, height / 2, 120);
+}
+";
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeedRequest {
+    #[serde(default = "default_users")]
+    users: u32,
+    #[serde(default = "default_games")]
+    games: u32,
+    #[serde(default = "default_reviews_per_game")]
+    reviews_per_game: u32,
+    #[serde(default = "default_sessions_per_game")]
+    sessions_per_game: u32,
+    /// Password for every seeded account; without one they can't sign in.
+    password: Option<String>,
+}
+
+const fn default_users() -> u32 {
+    20
+}
+
+const fn default_games() -> u32 {
+    40
+}
+
+const fn default_reviews_per_game() -> u32 {
+    5
+}
+
+const fn default_sessions_per_game() -> u32 {
+    3
+}
+
+/// How many rows of each kind a seed request created.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SeedResponse {
+    users: usize,
+    games: usize,
+    reviews: usize,
+    sessions: usize,
+    players: usize,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `POST /admin/seed` — Fill the database with fake accounts, published games built from
+/// the starter templates, tags, reviews and ended sessions spread over the last months.
+/// Refused in production.
+///
+/// # Errors
+///
+/// Returns [`AppError::Forbidden`] in production, [`AppError::BadRequest`] if a volume is out
+/// of range, or [`AppError`] if a database operation fails.
+pub async fn seed(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Json(req): Json<SeedRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if state.config.environment == Environment::Production {
+        return Err(AppError::Forbidden(
+            "Seeding is disabled in production.".to_string(),
+        ));
+    }
+    if !(1..=MAX_USERS).contains(&req.users) {
+        return Err(AppError::BadRequest(format!(
+            "users must be between 1 and {MAX_USERS}."
+        )));
+    }
+    if req.games > MAX_GAMES
+        || req.reviews_per_game > MAX_REVIEWS_PER_GAME
+        || req.sessions_per_game > MAX_SESSIONS_PER_GAME
+    {
+        return Err(AppError::BadRequest(format!(
+            "At most {MAX_GAMES} games, {MAX_REVIEWS_PER_GAME} reviews and \
+             {MAX_SESSIONS_PER_GAME} sessions per game."
+        )));
+    }
+    let password_hash = req
+        .password
+        .as_deref()
+        .map(password::hash_password)
+        .transpose()
+        .map_err(AppError::Internal)?;
+
+    let templates = game_template::Entity::find().all(&state.db).await?;
+    let tags: Vec<Uuid> = tag::Entity::find()
+        .select_only()
+        .column(tag::Column::Id)
+        .filter(tag::Column::RetiredAt.is_null())
+        .into_tuple()
+        .all(&state.db)
+        .await?;
+
+    let data = generate(&req, &templates, &tags, password_hash.as_deref());
+    let created = SeedResponse {
+        users: data.users.len(),
+        games: data.games.len(),
+        reviews: data.reviews.len(),
+        sessions: data.sessions.len(),
+        players: data.players.len(),
+    };
+
+    let txn = state.db.begin().await?;
+    insert_all(&txn, data.users).await?;
+    insert_all(&txn, data.auth_providers).await?;
+    insert_all(&txn, data.games).await?;
+    insert_all(&txn, data.versions).await?;
+    insert_all(&txn, data.game_tags).await?;
+    insert_all(&txn, data.reviews).await?;
+    insert_all(&txn, data.sessions).await?;
+    insert_all(&txn, data.players).await?;
+    insert_all(&txn, data.plays).await?;
+    txn.commit().await?;
+
+    tracing::info!(
+        admin_id = %admin.id,
+        users = created.users,
+        games = created.games,
+        reviews = created.reviews,
+        sessions = created.sessions,
+        "Seeded fake data"
+    );
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Rows to insert, in foreign key order.
+#[derive(Default)]
+struct SeedData {
+    users: Vec<user::ActiveModel>,
+    auth_providers: Vec<auth_provider::ActiveModel>,
+    games: Vec<game::ActiveModel>,
+    versions: Vec<game_version::ActiveModel>,
+    game_tags: Vec<game_tag::ActiveModel>,
+    reviews: Vec<review::ActiveModel>,
+    sessions: Vec<session::ActiveModel>,
+    players: Vec<player::ActiveModel>,
+    plays: Vec<game_play::ActiveModel>,
+}
+
+/// A seeded game, as its reviews and sessions refer to it.
+struct SeededGame {
+    id: Uuid,
+    version_id: Uuid,
+    owner_id: Uuid,
+    max_players: i32,
+    created_at: DateTime<FixedOffset>,
+}
+
+/// A seeded account, as later rows refer to it.
+struct SeededUser {
+    id: Uuid,
+    username: String,
+    created_at: DateTime<FixedOffset>,
+}
+
+/// Build every row up front, so the random generator never lives across an `.await`.
+fn generate(
+    req: &SeedRequest,
+    templates: &[game_template::Model],
+    tags: &[Uuid],
+    password_hash: Option<&str>,
+) -> SeedData {
+    let mut rng = rand::thread_rng();
+    let now = Utc::now().fixed_offset();
+    let mut data = SeedData::default();
+
+    let users: Vec<SeededUser> = (0..req.users)
+        .map(|_| fake_user(&mut rng, &mut data, now, password_hash))
+        .collect();
+    for _ in 0..req.games {
+        fake_game(&mut rng, &mut data, req, &users, templates, tags, now);
+    }
+    data
+}
+
+fn fake_user(
+    rng: &mut impl Rng,
+    data: &mut SeedData,
+    now: DateTime<FixedOffset>,
+    password_hash: Option<&str>,
+) -> SeededUser {
+    let id = Uuid::new_v4();
+    let adjective = pick(rng, ADJECTIVES);
+    let noun = pick(rng, NOUNS);
+    let username = format!("{adjective}_{noun}_{}", &id.simple().to_string()[..8]);
+    let email = format!("{username}@example.com");
+    let created_at = now - Duration::minutes(rng.gen_range(0..HISTORY_DAYS * 24 * 60));
+
+    data.users.push(user::ActiveModel {
+        id: Set(id),
+        email: Set(email.clone()),
+        username: Set(username.clone()),
+        display_name: Set(Some(format!(
+            "{} {}",
+            capitalize(adjective),
+            capitalize(noun)
+        ))),
+        avatar_url: Set(None),
+        bio: Set(rng.gen_bool(0.6).then(|| pick(rng, BIOS).to_string())),
+        location: Set(None),
+        website_url: Set(None),
+        pronouns: Set(None),
+        social_links: Set(None),
+        email_verified: Set(true),
+        verified: Set(false),
+        hide_from_search: Set(false),
+        hide_games: Set(false),
+        hide_activity: Set(false),
+        shadow_banned: Set(false),
+        role: Set("user".to_string()),
+        subscription_plan: Set("free".to_string()),
+        subscription_expires_at: Set(None),
+        account_status: Set("active".to_string()),
+        suspension_reason: Set(None),
+        last_login_at: Set(None),
+        last_login_ip: Set(None),
+        created_at: Set(created_at),
+        updated_at: Set(created_at),
+        deleted_at: Set(None),
+        deletion_scheduled_at: Set(None),
+        deletion_game_policy: Set(None),
+    });
+    if let Some(hash) = password_hash {
+        data.auth_providers.push(auth_provider::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            user_id: Set(id),
+            provider: Set("email".to_string()),
+            provider_id: Set(email.clone()),
+            password_hash: Set(Some(hash.to_string())),
+            provider_email: Set(Some(email)),
+            verification_token: Set(None),
+            token_expires_at: Set(None),
+            created_at: Set(created_at),
+        });
+    }
+
+    SeededUser {
+        id,
+        username,
+        created_at,
+    }
+}
+
+/// A published game with its first version, tags, reviews and play history.
+fn fake_game(
+    rng: &mut impl Rng,
+    data: &mut SeedData,
+    req: &SeedRequest,
+    users: &[SeededUser],
+    templates: &[game_template::Model],
+    tags: &[Uuid],
+    now: DateTime<FixedOffset>,
+) {
+    let id = Uuid::new_v4();
+    let version_id = Uuid::new_v4();
+    let Some(owner) = users.choose(rng) else {
+        return;
+    };
+    let created_at = between(rng, owner.created_at, now);
+    let title = format!(
+        "{} {}",
+        capitalize(pick(rng, ADJECTIVES)),
+        pick(rng, GAME_NOUNS)
+    );
+    let template = templates.choose(rng);
+    let (technology, game_screen_code, controller_screen_code) = template.map_or_else(
+        || {
+            (
+                "p5js".to_string(),
+                FALLBACK_GAME_SCREEN_CODE.to_string(),
+                FALLBACK_CONTROLLER_SCREEN_CODE.to_string(),
+            )
+        },
+        |t| {
+            (
+                t.technology.clone(),
+                t.game_screen_code.clone(),
+                t.controller_screen_code.clone(),
+            )
+        },
+    );
+    let (min_players, max_players) = template.map_or((1, 8), |t| (t.min_players, t.max_players));
+
+    let tag_count = rng.gen_range(1..=3);
+    for tag_id in tags.choose_multiple(rng, tag_count) {
+        data.game_tags.push(game_tag::ActiveModel {
+            game_id: Set(id),
+            tag_id: Set(*tag_id),
+        });
+    }
+
+    let seeded = SeededGame {
+        id,
+        version_id,
+        owner_id: owner.id,
+        max_players,
+        created_at,
+    };
+    let ratings = fake_reviews(rng, data, req, users, &seeded, now);
+    let (plays, play_seconds) = fake_sessions(rng, data, req, users, &seeded, now);
+
+    data.versions.push(game_version::ActiveModel {
+        id: Set(version_id),
+        created_at: Set(created_at),
+        game_id: Set(id),
+        version_number: Set(1),
+        game_screen_code: Set(Some(game_screen_code.clone())),
+        controller_screen_code: Set(Some(controller_screen_code.clone())),
+        change_log: Set(None),
+        changelog: Set(Some("Initial release.".to_string())),
+        published_by_id: Set(Some(owner.id)),
+        scan_findings: Set(None),
+        manifest: Set(None),
+    });
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    let avg_rating = if ratings.is_empty() {
+        0.0
+    } else {
+        (ratings.iter().map(|r| f64::from(*r)).sum::<f64>() / ratings.len() as f64) as f32
+    };
+    data.games.push(game::ActiveModel {
+        id: Set(id),
+        created_at: Set(created_at),
+        updated_at: Set(created_at),
+        owner_id: Set(owner.id),
+        title: Set(title.clone()),
+        slug: Set(unique_slug(&title, id)),
+        description: Set(template.map(|t| t.description.clone())),
+        technology: Set(technology),
+        status: Set("published".to_string()),
+        visibility: Set("public".to_string()),
+        min_players: Set(min_players),
+        max_players: Set(max_players),
+        published_version_id: Set(Some(version_id)),
+        game_screen_code: Set(Some(game_screen_code)),
+        controller_screen_code: Set(Some(controller_screen_code)),
+        play_count: Set(plays),
+        total_play_time: Set(play_seconds),
+        avg_rating: Set(avg_rating),
+        review_count: Set(i64::try_from(ratings.len()).unwrap_or(i64::MAX)),
+        owner_type: Set(OWNER_TYPE_USER.to_string()),
+        ..Default::default()
+    });
+}
+
+/// Reviews of a game by distinct seeded users other than its owner; returns their ratings.
+fn fake_reviews(
+    rng: &mut impl Rng,
+    data: &mut SeedData,
+    req: &SeedRequest,
+    users: &[SeededUser],
+    game: &SeededGame,
+    now: DateTime<FixedOffset>,
+) -> Vec<i32> {
+    let reviewers: Vec<&SeededUser> = users.iter().filter(|u| u.id != game.owner_id).collect();
+    let count = usize::try_from(req.reviews_per_game).unwrap_or(usize::MAX);
+    let mut ratings = Vec::new();
+    for reviewer in reviewers.choose_multiple(rng, count) {
+        let rating = *[5, 5, 5, 4, 4, 4, 3, 2, 1].choose(rng).unwrap_or(&4);
+        let created_at = between(rng, game.created_at, now);
+        data.reviews.push(review::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            created_at: Set(created_at),
+            updated_at: Set(created_at),
+            deleted_at: Set(None),
+            game_id: Set(game.id),
+            user_id: Set(reviewer.id),
+            rating: Set(rating),
+            body: Set(rng
+                .gen_bool(0.7)
+                .then(|| pick(rng, REVIEW_BODIES).to_string())),
+            creator_reply: Set(None),
+            creator_replied_at: Set(None),
+        });
+        ratings.push(rating);
+    }
+    ratings
+}
+
+/// Ended sessions of a game hosted and joined by seeded users; returns the number of plays
+/// and the seconds played in total.
+fn fake_sessions(
+    rng: &mut impl Rng,
+    data: &mut SeedData,
+    req: &SeedRequest,
+    users: &[SeededUser],
+    game: &SeededGame,
+    now: DateTime<FixedOffset>,
+) -> (i64, i64) {
+    let mut total_seconds = 0;
+    for _ in 0..req.sessions_per_game {
+        let Some(host) = users.choose(rng) else {
+            break;
+        };
+        let session_id = Uuid::new_v4();
+        let created_at = between(rng, game.created_at, now - Duration::hours(2));
+        let started_at = created_at + Duration::seconds(rng.gen_range(30..300));
+        let play_seconds = rng.gen_range(300..5400);
+        let ended_at = started_at + Duration::seconds(play_seconds);
+        total_seconds += play_seconds;
+
+        let player_count = rng.gen_range(1..=usize::try_from(game.max_players).unwrap_or(1).max(1));
+        let players: Vec<&SeededUser> = users.choose_multiple(rng, player_count).collect();
+        let player_count = i32::try_from(players.len()).unwrap_or(i32::MAX);
+        for joined in &players {
+            data.players.push(player::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                created_at: Set(created_at),
+                session_id: Set(session_id),
+                user_id: Set(Some(joined.id)),
+                display_name: Set(joined.username.clone()),
+                avatar_url: Set(None),
+                connection_status: Set("disconnected".to_string()),
+                left_at: Set(Some(ended_at)),
+                // Some players join and never pick up their controller
+                first_input_at: Set(rng.gen_bool(0.85).then_some(started_at)),
+                country: Set(COUNTRIES.choose(rng).map(|c| (*c).to_string())),
+            });
+        }
+
+        data.sessions.push(session::ActiveModel {
+            id: Set(session_id),
+            created_at: Set(created_at),
+            updated_at: Set(ended_at),
+            ended_at: Set(Some(ended_at)),
+            host_id: Set(host.id),
+            game_id: Set(Some(game.id)),
+            game_version_id: Set(Some(game.version_id)),
+            session_code: Set(seed_session_code(rng)),
+            status: Set("ended".to_string()),
+            max_players: Set(game.max_players),
+            playing_started_at: Set(None),
+            duration_seconds: Set(Some((ended_at - created_at).num_seconds())),
+            peak_players: Set(Some(player_count)),
+            messages_relayed: Set(Some(
+                play_seconds * i64::from(player_count) * rng.gen_range(5..20),
+            )),
+            disconnects: Set(Some(rng.gen_range(0..=player_count))),
+        });
+        data.plays.push(game_play::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            game_id: Set(game.id),
+            session_id: Set(session_id),
+            started_at: Set(started_at),
+            ended_at: Set(Some(ended_at)),
+            play_seconds: Set(play_seconds),
+            player_count: Set(player_count),
+        });
+    }
+    (i64::from(req.sessions_per_game), total_seconds)
+}
+
+/// Insert `models` in chunks of [`INSERT_CHUNK`] rows.
+async fn insert_all<A>(txn: &DatabaseTransaction, models: Vec<A>) -> Result<(), AppError>
+where
+    A: ActiveModelTrait + Clone + Send + Sync,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+{
+    for chunk in models.chunks(INSERT_CHUNK) {
+        A::Entity::insert_many(chunk.to_vec())
+            .exec_without_returning(txn)
+            .await?;
+    }
+    Ok(())
+}
+
+/// A random instant between `from` and `to`, or `from` if `to` is not later.
+fn between(
+    rng: &mut impl Rng,
+    from: DateTime<FixedOffset>,
+    to: DateTime<FixedOffset>,
+) -> DateTime<FixedOffset> {
+    let span = (to - from).num_seconds();
+    if span <= 0 {
+        return from;
+    }
+    from + Duration::seconds(rng.gen_range(0..span))
+}
+
+fn seed_session_code(rng: &mut impl Rng) -> String {
+    (0..SEED_SESSION_CODE_LENGTH)
+        .map(|_| char::from(*SESSION_CODE_CHARS.choose(rng).unwrap_or(&b'A')))
+        .collect()
+}
+
+fn pick<'a>(rng: &mut impl Rng, words: &[&'a str]) -> &'a str {
+    words.choose(rng).copied().unwrap_or_default()
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
@@ -0,0 +1,331 @@
+use std::collections::HashSet;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, DatabaseConnection, EntityTrait,
+    QueryFilter, QueryOrder, QuerySelect, RelationTrait, sea_query::Expr, sea_query::JoinType,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::middleware::AuthUser,
+    avatars, email,
+    entities::{follow, friendship, session, session_invite, user},
+    error::AppError,
+    notifications,
+    state::AppState,
+};
+
+/// Most users one request can invite.
+const MAX_INVITES_PER_REQUEST: usize = 50;
+
+/// Session invite router, merged into `/sessions/...`.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/{session_id}/invites",
+            get(list_invites).post(create_invites),
+        )
+        .route("/{session_id}/invites/decline", post(decline_invite))
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateInvitesRequest {
+    /// Friends or followers of the host to invite.
+    user_ids: Vec<Uuid>,
+    /// Also email the invite, to invitees whose preferences allow it.
+    #[serde(default)]
+    email: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InviteeResponse {
+    id: Uuid,
+    username: String,
+    display_name: Option<String>,
+    avatar_url: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionInviteResponse {
+    id: Uuid,
+    user: InviteeResponse,
+    status: String,
+    emailed: bool,
+    created_at: String,
+    responded_at: Option<String>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `POST /sessions/{session_id}/invites` — Invite friends or followers into a session (host
+/// only), returning every invite the session has.
+///
+/// Each invitee gets an in-app notification with the session code and a join link, and an
+/// email too when `email` is set. Users already invited are skipped.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if the session does not exist, [`AppError::Forbidden`] if
+/// the caller is not its host, [`AppError::BadRequest`] if it has ended or an invitee is not a
+/// friend or follower of the host, or [`AppError`] if a database operation fails.
+async fn create_invites(
+    State(state): State<AppState>,
+    AuthUser(host): AuthUser,
+    Path(session_id): Path<Uuid>,
+    Json(body): Json<CreateInvitesRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let sess = find_hosted_session(&state.db, session_id, host.id).await?;
+    if sess.status == "ended" {
+        return Err(AppError::BadRequest("Session has ended.".to_string()));
+    }
+
+    let requested: HashSet<Uuid> = body.user_ids.into_iter().collect();
+    if requested.is_empty() || requested.len() > MAX_INVITES_PER_REQUEST {
+        return Err(AppError::BadRequest(format!(
+            "Invite between 1 and {MAX_INVITES_PER_REQUEST} users at a time."
+        )));
+    }
+    let eligible = eligible_invitees(&state.db, host.id, &requested).await?;
+    if eligible.len() != requested.len() {
+        return Err(AppError::BadRequest(
+            "You can only invite your friends and followers.".to_string(),
+        ));
+    }
+
+    let already_invited: HashSet<Uuid> = session_invite::Entity::find()
+        .filter(session_invite::Column::SessionId.eq(sess.id))
+        .filter(session_invite::Column::InviteeId.is_in(requested.iter().copied()))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|i| i.invitee_id)
+        .collect();
+
+    for invitee_id in requested.difference(&already_invited) {
+        let invite =
+            notifications::session_invite(&state, *invitee_id, &host, &sess.session_code).await;
+        // Invites from shadow-banned hosts are recorded but never delivered
+        let emailed = if body.email && !host.shadow_banned {
+            let text = format!("{} {}", invite.body, invite.link.unwrap_or_default());
+            email::send_to_user(
+                &state.db,
+                *invitee_id,
+                email::Category::SessionInvites,
+                &invite.title,
+                &text,
+            )
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!(error = %e, %invitee_id, "Failed to email session invite");
+                false
+            })
+        } else {
+            false
+        };
+
+        session_invite::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            session_id: Set(sess.id),
+            inviter_id: Set(host.id),
+            invitee_id: Set(*invitee_id),
+            status: Set("pending".to_string()),
+            emailed: Set(emailed),
+            created_at: Set(Utc::now().fixed_offset()),
+            responded_at: Set(None),
+        }
+        .insert(&state.db)
+        .await?;
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        Json(list_for_session(&state.db, sess.id).await?),
+    ))
+}
+
+/// `GET /sessions/{session_id}/invites` — Who the host invited into a session and how each
+/// invite stands, newest first (host only).
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if the session does not exist, [`AppError::Forbidden`] if
+/// the caller is not its host, or [`AppError`] if the database query fails.
+async fn list_invites(
+    State(state): State<AppState>,
+    AuthUser(host): AuthUser,
+    Path(session_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let sess = find_hosted_session(&state.db, session_id, host.id).await?;
+    Ok(Json(list_for_session(&state.db, sess.id).await?))
+}
+
+/// `POST /sessions/{session_id}/invites/decline` — Decline the authenticated user's pending
+/// invite into a session.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if the caller has no pending invite into the session, or
+/// [`AppError`] if the database operation fails.
+async fn decline_invite(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(session_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let declined = respond(&state.db, session_id, user.id, "declined").await?;
+    if !declined {
+        return Err(AppError::NotFound("Invite not found.".to_string()));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Move `invitee_id`'s pending invite into `session_id` to `status`, returning whether there
+/// was one.
+///
+/// # Errors
+///
+/// Returns an error if the database operation fails.
+pub async fn respond(
+    db: &DatabaseConnection,
+    session_id: Uuid,
+    invitee_id: Uuid,
+    status: &str,
+) -> Result<bool, AppError> {
+    let updated = session_invite::Entity::update_many()
+        .col_expr(session_invite::Column::Status, Expr::value(status))
+        .col_expr(
+            session_invite::Column::RespondedAt,
+            Expr::value(Utc::now().fixed_offset()),
+        )
+        .filter(session_invite::Column::SessionId.eq(session_id))
+        .filter(session_invite::Column::InviteeId.eq(invitee_id))
+        .filter(session_invite::Column::Status.eq("pending"))
+        .exec(db)
+        .await?;
+    Ok(updated.rows_affected > 0)
+}
+
+async fn find_hosted_session(
+    db: &DatabaseConnection,
+    session_id: Uuid,
+    host_id: Uuid,
+) -> Result<session::Model, AppError> {
+    let sess = session::Entity::find_by_id(session_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Session not found.".to_string()))?;
+    if sess.host_id != host_id {
+        return Err(AppError::Forbidden(
+            "Only the session host can manage invites.".to_string(),
+        ));
+    }
+    Ok(sess)
+}
+
+/// The users in `candidates` who are active and either friends with `host_id` or follow them.
+async fn eligible_invitees(
+    db: &DatabaseConnection,
+    host_id: Uuid,
+    candidates: &HashSet<Uuid>,
+) -> Result<HashSet<Uuid>, AppError> {
+    let ids = || candidates.iter().copied();
+
+    let mut related: HashSet<Uuid> = friendship::Entity::find()
+        .filter(friendship::Column::Status.eq("accepted"))
+        .filter(
+            Condition::any()
+                .add(
+                    Condition::all()
+                        .add(friendship::Column::RequesterId.eq(host_id))
+                        .add(friendship::Column::AddresseeId.is_in(ids())),
+                )
+                .add(
+                    Condition::all()
+                        .add(friendship::Column::AddresseeId.eq(host_id))
+                        .add(friendship::Column::RequesterId.is_in(ids())),
+                ),
+        )
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|f| {
+            if f.requester_id == host_id {
+                f.addressee_id
+            } else {
+                f.requester_id
+            }
+        })
+        .collect();
+    related.extend(
+        follow::Entity::find()
+            .filter(follow::Column::FolloweeId.eq(host_id))
+            .filter(follow::Column::FollowerId.is_in(ids()))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|f| f.follower_id),
+    );
+
+    Ok(user::Entity::find()
+        .filter(user::Column::Id.is_in(related))
+        .filter(user::Column::DeletedAt.is_null())
+        .filter(user::Column::AccountStatus.eq("active"))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|u| u.id)
+        .collect())
+}
+
+async fn list_for_session(
+    db: &DatabaseConnection,
+    session_id: Uuid,
+) -> Result<Vec<SessionInviteResponse>, AppError> {
+    let rows = session_invite::Entity::find()
+        .filter(session_invite::Column::SessionId.eq(session_id))
+        .join(JoinType::InnerJoin, session_invite::Relation::Invitee.def())
+        .select_also(user::Entity)
+        .order_by_desc(session_invite::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(invite, u)| {
+            u.map(|u| SessionInviteResponse {
+                id: invite.id,
+                user: InviteeResponse {
+                    id: u.id,
+                    avatar_url: avatars::url_or_fallback(u.avatar_url, &u.username),
+                    username: u.username,
+                    display_name: u.display_name,
+                },
+                status: invite.status,
+                emailed: invite.emailed,
+                created_at: invite.created_at.to_rfc3339(),
+                responded_at: invite.responded_at.map(|t| t.to_rfc3339()),
+            })
+        })
+        .collect())
+}
@@ -1,33 +1,75 @@
+use std::collections::HashMap;
+
 use axum::extract::ws::{Message, WebSocket};
 use axum::extract::{Path, Query, State, WebSocketUpgrade};
-use axum::http::StatusCode;
-use axum::response::Response;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
 use axum::routing::{get, post};
-use axum::{Json, Router};
-use chrono::Utc;
+use axum::{Extension, Json, Router};
+use chrono::{DateTime, FixedOffset, Utc};
 use futures_util::{SinkExt, StreamExt};
 use rand::Rng;
 use sea_orm::ActiveValue::Set;
-use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DatabaseTransaction, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::auth::middleware::AuthUser;
-use crate::entities::{game, game_version, player, session};
+use crate::achievements;
+use crate::audit;
+use crate::auth::extract_client_ip;
+use crate::auth::middleware::{AuthUser, ModeratorUser};
+use crate::avatars;
+use crate::entities::{game, game_play, game_version, player, session};
 use crate::error::AppError;
-use crate::sessions::ClientRole;
+use crate::geo;
+use crate::middleware::rate_limit::{self, RateLimitPolicy, RateLimiter};
+use crate::routes::games::{OptionalAuth, PaginatedResponse, PaginationQuery};
+use crate::routes::session_invites;
+use crate::sessions::{ClientRole, StateSnapshot, joining};
 use crate::state::AppState;
+use crate::xp;
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Router
 // ─────────────────────────────────────────────────────────────────────────────
 
-/// Build the session route group: `/sessions/...`
-pub fn router() -> Router<AppState> {
+/// Values of `session.status`.
+const SESSION_STATUSES: [&str; 3] = ["lobby", "playing", "ended"];
+
+/// Longest reason a moderator may give for ending a session, in characters.
+const MAX_TERMINATION_REASON_LENGTH: usize = 500;
+
+/// Build the session route group: `/sessions/...`, with `join` limiting joins.
+///
+/// The per-IP bucket of `join` is enforced by the route's layer and the per-account bucket by
+/// [`join_session`] once it knows who is joining, so a join body cannot name someone else's
+/// bucket.
+pub fn router(join: RateLimitPolicy) -> Router<AppState> {
+    let per_ip = RateLimitPolicy {
+        per_account: None,
+        ..join
+    };
+    let per_account = RateLimitPolicy {
+        per_ip: None,
+        ..join
+    };
+
     Router::new()
         .route("/", post(create_session))
         .route("/{session_code}", get(get_session))
-        .route("/{session_code}/join", post(join_session))
+        .route(
+            "/{session_code}/join",
+            post(join_session)
+                .route_layer(axum::middleware::from_fn_with_state(
+                    RateLimiter::new(per_ip),
+                    rate_limit::enforce,
+                ))
+                .layer(Extension(RateLimiter::new(per_account))),
+        )
         .route("/{session_id}/players", get(list_players))
         .route("/{session_id}/end", post(end_session))
         .route("/{session_id}/game", post(load_game))
@@ -66,8 +108,10 @@ struct PlayerResponse {
     id: Uuid,
     created_at: String,
     display_name: String,
-    avatar_url: Option<String>,
+    avatar_url: String,
     connection_status: String,
+    /// The signed-in user's level, or `None` for a guest.
+    level: Option<i32>,
 }
 
 #[derive(Deserialize)]
@@ -108,12 +152,112 @@ struct LoadGameResponse {
     status: String,
 }
 
+/// Filters for `GET /admin/sessions`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminSessionsQuery {
+    /// Only sessions with this status; defaults to every session that has not ended.
+    status: Option<String>,
+    #[serde(default)]
+    offset: u64,
+    #[serde(default = "default_admin_sessions_limit")]
+    limit: u64,
+}
+
+const fn default_admin_sessions_limit() -> u64 {
+    50
+}
+
+/// A session as moderators see it.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminSessionResponse {
+    id: Uuid,
+    created_at: String,
+    updated_at: String,
+    ended_at: Option<String>,
+    host_id: Uuid,
+    game_id: Option<Uuid>,
+    session_code: String,
+    status: String,
+    player_count: u64,
+    /// Whether the host currently has the session's `WebSocket` open.
+    host_connected: bool,
+}
+
+/// A session in its host's history.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostedSessionResponse {
+    id: Uuid,
+    created_at: String,
+    ended_at: Option<String>,
+    game_id: Option<Uuid>,
+    session_code: String,
+    status: String,
+    /// Players who joined, connected or not.
+    player_count: u64,
+    /// How the session went; `None` until it ends.
+    summary: Option<SessionSummaryResponse>,
+}
+
+/// Telemetry recorded when a session ended.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSummaryResponse {
+    duration_seconds: i64,
+    peak_players: i32,
+    messages_relayed: i64,
+    disconnects: i32,
+}
+
+#[derive(Deserialize)]
+pub struct ForceEndRequest {
+    /// Shown to the session's clients.
+    reason: String,
+}
+
 #[derive(Deserialize)]
 struct WsQueryParams {
     role: String,
     #[serde(rename = "playerId")]
     player_id: Option<Uuid>,
     token: Option<String>,
+    /// Optional payload compression (`"deflate"` or `"none"`), chosen per connection.
+    compression: Option<String>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Payload compression
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Outbound messages at or above this size are deflated when the client opted in.
+const COMPRESSION_THRESHOLD_BYTES: usize = 1024;
+
+/// Build the outbound frame for a relay message.
+///
+/// This is not the `permessage-deflate` extension (RFC 7692): `tungstenite` cannot negotiate
+/// it or set the RSV1 bit its frames carry, so a browser's offer is declined and the browser
+/// connects uncompressed. Clients that connect with `?compression=deflate` instead receive
+/// messages of [`COMPRESSION_THRESHOLD_BYTES`] or more as binary frames holding the raw
+/// deflate stream (RFC 1951, no zlib header) of the JSON, which they inflate themselves, e.g.
+/// with `DecompressionStream("deflate-raw")`. Smaller messages stay plain text frames, and
+/// clients always send text frames.
+fn outbound_frame(message: String, compress: bool) -> Message {
+    if !compress || message.len() < COMPRESSION_THRESHOLD_BYTES {
+        return Message::Text(message.into());
+    }
+
+    let mut encoder = flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::fast());
+    match std::io::Write::write_all(&mut encoder, message.as_bytes())
+        .and_then(|()| encoder.finish())
+    {
+        Ok(compressed) => Message::Binary(compressed.into()),
+        Err(e) => {
+            tracing::warn!("Failed to deflate WebSocket payload: {e}");
+            Message::Text(message.into())
+        }
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -121,7 +265,7 @@ struct WsQueryParams {
 // ─────────────────────────────────────────────────────────────────────────────
 
 /// Characters used for session codes — excludes ambiguous chars (0/O, 1/I/L).
-const SESSION_CODE_CHARS: &[u8] = b"ABCDEFGHJKMNPQRSTUVWXYZ23456789";
+pub const SESSION_CODE_CHARS: &[u8] = b"ABCDEFGHJKMNPQRSTUVWXYZ23456789";
 const SESSION_CODE_LENGTH: usize = 5;
 
 /// Generate a random session code string (not yet validated for uniqueness).
@@ -166,8 +310,12 @@ async fn generate_session_code(db: &sea_orm::DatabaseConnection) -> Result<Strin
 // Helpers
 // ─────────────────────────────────────────────────────────────────────────────
 
-/// Build a `SessionResponse` from a session model and its players.
-fn build_session_response(sess: &session::Model, players: Vec<player::Model>) -> SessionResponse {
+/// Build a `SessionResponse` from a session model and its players, given their levels.
+fn build_session_response(
+    sess: &session::Model,
+    players: Vec<player::Model>,
+    levels: &HashMap<Uuid, i32>,
+) -> SessionResponse {
     SessionResponse {
         id: sess.id,
         created_at: sess.created_at.to_rfc3339(),
@@ -179,21 +327,127 @@ fn build_session_response(sess: &session::Model, players: Vec<player::Model>) ->
         session_code: sess.session_code.clone(),
         status: sess.status.clone(),
         max_players: sess.max_players,
-        players: players.into_iter().map(build_player_response).collect(),
+        players: players
+            .into_iter()
+            .map(|p| build_player_response(p, levels))
+            .collect(),
     }
 }
 
-/// Build a `PlayerResponse` from a player model.
-fn build_player_response(p: player::Model) -> PlayerResponse {
+/// Build a `PlayerResponse` from a player model, given the levels of signed-in players.
+fn build_player_response(p: player::Model, levels: &HashMap<Uuid, i32>) -> PlayerResponse {
     PlayerResponse {
+        level: p.user_id.and_then(|id| levels.get(&id).copied()),
         id: p.id,
         created_at: p.created_at.to_rfc3339(),
+        avatar_url: avatars::url_or_fallback(p.avatar_url, &p.display_name),
         display_name: p.display_name,
-        avatar_url: p.avatar_url,
         connection_status: p.connection_status,
     }
 }
 
+/// The levels of the signed-in users among `players`.
+async fn player_levels(
+    db: &DatabaseConnection,
+    players: &[player::Model],
+) -> Result<HashMap<Uuid, i32>, AppError> {
+    xp::levels(db, players.iter().filter_map(|p| p.user_id))
+        .await
+        .map_err(|e| AppError::Internal(e.into()))
+}
+
+/// End `sess`, settling its play time and recording its telemetry summary, then tell
+/// connected clients (with `notice` first, if given) and disconnect them.
+///
+/// # Errors
+///
+/// Returns [`AppError::BadRequest`] if the session has already ended, or [`AppError`] if a
+/// database operation fails.
+async fn close_session(
+    state: &AppState,
+    sess: &session::Model,
+    notice: Option<serde_json::Value>,
+) -> Result<(), AppError> {
+    if sess.status == "ended" {
+        return Err(AppError::BadRequest(
+            "Session is already ended.".to_string(),
+        ));
+    }
+
+    let now = Utc::now().fixed_offset();
+    let telemetry = state.session_manager.telemetry(sess.id);
+    let txn = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    // Conditional update so concurrent end requests only accrue play time once
+    let ended = session::Entity::update_many()
+        .col_expr(session::Column::Status, Expr::value("ended"))
+        .col_expr(session::Column::EndedAt, Expr::value(now))
+        .col_expr(session::Column::UpdatedAt, Expr::value(now))
+        .col_expr(
+            session::Column::PlayingStartedAt,
+            Expr::value(Option::<DateTime<FixedOffset>>::None),
+        )
+        .col_expr(
+            session::Column::DurationSeconds,
+            Expr::value((now - sess.created_at).num_seconds().max(0)),
+        )
+        .col_expr(
+            session::Column::PeakPlayers,
+            Expr::value(i32::try_from(telemetry.peak_players).unwrap_or(i32::MAX)),
+        )
+        .col_expr(
+            session::Column::MessagesRelayed,
+            Expr::value(i64::try_from(telemetry.messages_relayed).unwrap_or(i64::MAX)),
+        )
+        .col_expr(
+            session::Column::Disconnects,
+            Expr::value(i32::try_from(telemetry.disconnects).unwrap_or(i32::MAX)),
+        )
+        .filter(session::Column::Id.eq(sess.id))
+        .filter(session::Column::Status.ne("ended"))
+        .exec(&txn)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    if ended.rows_affected == 0 {
+        return Err(AppError::BadRequest(
+            "Session is already ended.".to_string(),
+        ));
+    }
+
+    if let (Some(game_id), Some(started_at)) = (sess.game_id, sess.playing_started_at) {
+        accrue_play_time(&txn, sess.id, game_id, started_at, now).await?;
+    }
+    close_play(&txn, sess.id, now).await?;
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    // Broadcast `notice` and session_status_change, then close all connections
+    if let Some(notice) = notice {
+        state
+            .session_manager
+            .broadcast(sess.id, &notice.to_string());
+    }
+    let status_msg = serde_json::json!({
+        "type": "session_status_change",
+        "payload": {
+            "status": "ended",
+            "previousStatus": "lobby"
+        }
+    });
+    state
+        .session_manager
+        .broadcast(sess.id, &status_msg.to_string());
+    state.session_manager.remove_session(sess.id);
+    Ok(())
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Handlers
 // ─────────────────────────────────────────────────────────────────────────────
@@ -206,7 +460,7 @@ async fn create_session(
 ) -> Result<(StatusCode, Json<SessionResponse>), AppError> {
     let session_code = generate_session_code(&state.db).await?;
     let now = Utc::now().fixed_offset();
-    let max_players = body.max_players.unwrap_or(8).clamp(1, 32);
+    let max_players = joining::max_players(body.max_players);
 
     let sess = session::ActiveModel {
         id: Set(Uuid::new_v4()),
@@ -219,6 +473,11 @@ async fn create_session(
         session_code: Set(session_code),
         status: Set("lobby".to_string()),
         max_players: Set(max_players),
+        playing_started_at: Set(None),
+        duration_seconds: Set(None),
+        peak_players: Set(None),
+        messages_relayed: Set(None),
+        disconnects: Set(None),
     };
 
     let inserted = sess
@@ -226,7 +485,9 @@ async fn create_session(
         .await
         .map_err(|e| AppError::Internal(e.into()))?;
 
-    let response = build_session_response(&inserted, vec![]);
+    achievements::session_hosted(&state.db, &state.notification_hub, host.id).await;
+
+    let response = build_session_response(&inserted, vec![], &HashMap::new());
     Ok((StatusCode::CREATED, Json(response)))
 }
 
@@ -251,15 +512,29 @@ async fn get_session(
         .await
         .map_err(|e| AppError::Internal(e.into()))?;
 
-    Ok(Json(build_session_response(&sess, players)))
+    let levels = player_levels(&state.db, &players).await?;
+    Ok(Json(build_session_response(&sess, players, &levels)))
 }
 
 /// `POST /api/v1/sessions/{sessionCode}/join` — Join a session by code.
+///
+/// Guests join anonymously. A signed-in caller joins as themselves, so their level is shown and
+/// their plays earn XP, and their pending invite into the session is marked accepted.
+/// Signed-in callers are rate limited per account as well as per IP.
 async fn join_session(
     State(state): State<AppState>,
+    Extension(limiter): Extension<RateLimiter>,
+    OptionalAuth(viewer): OptionalAuth,
     Path(session_code): Path<String>,
+    headers: HeaderMap,
     Json(body): Json<JoinSessionRequest>,
-) -> Result<(StatusCode, Json<JoinResponse>), AppError> {
+) -> Result<Response, AppError> {
+    if let Some(viewer) = &viewer
+        && let Err(wait) = limiter.try_acquire(&format!("user:{}", viewer.id))
+    {
+        return Ok(rate_limit::too_many_requests(wait));
+    }
+
     let code_upper = session_code.to_uppercase();
 
     let sess = session::Entity::find()
@@ -269,42 +544,33 @@ async fn join_session(
         .map_err(|e| AppError::Internal(e.into()))?
         .ok_or_else(|| AppError::NotFound("Session not found.".to_string()))?;
 
-    // Validate session is joinable
-    if sess.status == "ended" {
-        return Err(AppError::BadRequest("Session has ended.".to_string()));
-    }
-
-    // Count active players
     let active_players = player::Entity::find()
         .filter(player::Column::SessionId.eq(sess.id))
         .filter(player::Column::LeftAt.is_null())
-        .all(&state.db)
+        .count(&state.db)
         .await
         .map_err(|e| AppError::Internal(e.into()))?;
-
-    let max = usize::try_from(sess.max_players).unwrap_or(8);
-    if active_players.len() >= max {
-        return Err(AppError::BadRequest("Session is full.".to_string()));
-    }
-
-    // Validate display name
-    let display_name = body.display_name.trim().to_string();
-    if display_name.is_empty() || display_name.len() > 100 {
-        return Err(AppError::BadRequest(
-            "Display name must be between 1 and 100 characters.".to_string(),
-        ));
-    }
+    joining::check_joinable(
+        &sess.status,
+        sess.max_players,
+        usize::try_from(active_players).unwrap_or(usize::MAX),
+    )?;
+    let display_name = joining::display_name(&body.display_name)?;
 
     let now = Utc::now().fixed_offset();
     let player_model = player::ActiveModel {
         id: Set(Uuid::new_v4()),
         created_at: Set(now),
         session_id: Set(sess.id),
-        user_id: Set(None), // Anonymous guest
+        user_id: Set(viewer.as_ref().map(|v| v.id)),
         display_name: Set(display_name),
         avatar_url: Set(body.avatar_url),
         connection_status: Set("connected".to_string()),
         left_at: Set(None),
+        first_input_at: Set(None),
+        country: Set(extract_client_ip(&headers)
+            .as_deref()
+            .and_then(geo::country_of)),
     };
 
     let inserted_player = player_model
@@ -312,14 +578,22 @@ async fn join_session(
         .await
         .map_err(|e| AppError::Internal(e.into()))?;
 
+    if let Some(viewer) = viewer {
+        session_invites::respond(&state.db, sess.id, viewer.id, "accepted").await?;
+    }
+
+    let levels = player_levels(&state.db, std::slice::from_ref(&inserted_player)).await?;
+    let player_resp = build_player_response(inserted_player, &levels);
+
     // Broadcast player_joined to all connected clients
     let joined_msg = serde_json::json!({
         "type": "player_joined",
         "payload": {
             "player": {
-                "id": inserted_player.id,
-                "displayName": inserted_player.display_name,
-                "avatarUrl": inserted_player.avatar_url,
+                "id": player_resp.id,
+                "displayName": player_resp.display_name,
+                "avatarUrl": player_resp.avatar_url,
+                "level": player_resp.level,
             }
         }
     });
@@ -327,8 +601,6 @@ async fn join_session(
         .session_manager
         .broadcast(sess.id, &joined_msg.to_string());
 
-    let player_resp = build_player_response(inserted_player);
-
     Ok((
         StatusCode::CREATED,
         Json(JoinResponse {
@@ -340,7 +612,8 @@ async fn join_session(
                 host_id: sess.host_id,
             },
         }),
-    ))
+    )
+        .into_response())
 }
 
 /// `GET /api/v1/sessions/{sessionId}/players` — List all players in a session.
@@ -361,8 +634,12 @@ async fn list_players(
         .await
         .map_err(|e| AppError::Internal(e.into()))?;
 
+    let levels = player_levels(&state.db, &players).await?;
     Ok(Json(
-        players.into_iter().map(build_player_response).collect(),
+        players
+            .into_iter()
+            .map(|p| build_player_response(p, &levels))
+            .collect(),
     ))
 }
 
@@ -384,34 +661,187 @@ async fn end_session(
         ));
     }
 
-    if sess.status == "ended" {
-        return Err(AppError::BadRequest(
-            "Session is already ended.".to_string(),
-        ));
-    }
+    close_session(&state, &sess, None).await?;
 
-    let now = Utc::now().fixed_offset();
-    let mut active: session::ActiveModel = sess.into();
-    active.status = Set("ended".to_string());
-    active.ended_at = Set(Some(now));
-    active.updated_at = Set(now);
-    active
-        .update(&state.db)
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GET /api/v1/admin/sessions?status=` — Sessions with `status` (`lobby`, `playing` or
+/// `ended`), most recently active first. Lists every session that has not ended when no
+/// status is given.
+///
+/// # Errors
+///
+/// Returns [`AppError::BadRequest`] for an unknown status, or [`AppError`] if a database query
+/// fails.
+pub async fn list_sessions(
+    State(state): State<AppState>,
+    ModeratorUser(_moderator): ModeratorUser,
+    Query(query): Query<AdminSessionsQuery>,
+) -> Result<Json<PaginatedResponse<AdminSessionResponse>>, AppError> {
+    let mut find = session::Entity::find();
+    find = match query.status.as_deref() {
+        None => find.filter(session::Column::Status.ne("ended")),
+        Some(status) if SESSION_STATUSES.contains(&status) => {
+            find.filter(session::Column::Status.eq(status))
+        }
+        Some(_) => {
+            return Err(AppError::BadRequest(format!(
+                "Status must be one of: {}.",
+                SESSION_STATUSES.join(", ")
+            )));
+        }
+    };
+    let total = find
+        .clone()
+        .count(&state.db)
         .await
         .map_err(|e| AppError::Internal(e.into()))?;
 
-    // Broadcast session_status_change and close all connections
-    let status_msg = serde_json::json!({
-        "type": "session_status_change",
-        "payload": {
-            "status": "ended",
-            "previousStatus": "lobby"
-        }
+    let sessions = find
+        .order_by_desc(session::Column::UpdatedAt)
+        .offset(query.offset)
+        .limit(query.limit)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    let mut player_counts: HashMap<Uuid, u64> = HashMap::new();
+    for p in player::Entity::find()
+        .filter(player::Column::SessionId.is_in(sessions.iter().map(|s| s.id)))
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?
+    {
+        *player_counts.entry(p.session_id).or_default() += 1;
+    }
+
+    Ok(Json(PaginatedResponse {
+        data: sessions
+            .into_iter()
+            .map(|sess| AdminSessionResponse {
+                player_count: player_counts.get(&sess.id).copied().unwrap_or_default(),
+                host_connected: state
+                    .session_manager
+                    .is_connected(sess.id, &ClientRole::Host),
+                id: sess.id,
+                created_at: sess.created_at.to_rfc3339(),
+                updated_at: sess.updated_at.to_rfc3339(),
+                ended_at: sess.ended_at.map(|t| t.to_rfc3339()),
+                host_id: sess.host_id,
+                game_id: sess.game_id,
+                session_code: sess.session_code,
+                status: sess.status,
+            })
+            .collect(),
+        total,
+        offset: query.offset,
+        limit: query.limit,
+    }))
+}
+
+/// `GET /api/v1/users/me/hosted-sessions` — The sessions the user has hosted, newest first,
+/// with how each one went once it ended.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if a database query fails.
+pub async fn list_hosted_sessions(
+    State(state): State<AppState>,
+    AuthUser(host): AuthUser,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<HostedSessionResponse>>, AppError> {
+    let find = session::Entity::find().filter(session::Column::HostId.eq(host.id));
+    let total = find.clone().count(&state.db).await?;
+    let sessions = find
+        .order_by_desc(session::Column::CreatedAt)
+        .offset(pagination.offset)
+        .limit(pagination.limit)
+        .all(&state.db)
+        .await?;
+
+    let mut player_counts: HashMap<Uuid, u64> = HashMap::new();
+    for session_id in player::Entity::find()
+        .select_only()
+        .column(player::Column::SessionId)
+        .filter(player::Column::SessionId.is_in(sessions.iter().map(|s| s.id)))
+        .into_tuple::<Uuid>()
+        .all(&state.db)
+        .await?
+    {
+        *player_counts.entry(session_id).or_default() += 1;
+    }
+
+    Ok(Json(PaginatedResponse {
+        data: sessions
+            .into_iter()
+            .map(|sess| HostedSessionResponse {
+                player_count: player_counts.get(&sess.id).copied().unwrap_or_default(),
+                summary: sess
+                    .duration_seconds
+                    .map(|duration_seconds| SessionSummaryResponse {
+                        duration_seconds,
+                        peak_players: sess.peak_players.unwrap_or_default(),
+                        messages_relayed: sess.messages_relayed.unwrap_or_default(),
+                        disconnects: sess.disconnects.unwrap_or_default(),
+                    }),
+                id: sess.id,
+                created_at: sess.created_at.to_rfc3339(),
+                ended_at: sess.ended_at.map(|t| t.to_rfc3339()),
+                game_id: sess.game_id,
+                session_code: sess.session_code,
+                status: sess.status,
+            })
+            .collect(),
+        total,
+        offset: pagination.offset,
+        limit: pagination.limit,
+    }))
+}
+
+/// `POST /api/v1/admin/sessions/{sessionId}/end` — End any session, e.g. one hosting abusive
+/// content. Connected clients are sent a `session_terminated` message with the reason before
+/// they are disconnected.
+///
+/// # Errors
+///
+/// Returns [`AppError::BadRequest`] if the reason is missing or too long or the session has
+/// already ended, [`AppError::NotFound`] if there is no such session, or [`AppError`] if a
+/// database operation fails.
+pub async fn force_end_session(
+    State(state): State<AppState>,
+    ModeratorUser(moderator): ModeratorUser,
+    Path(session_id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(body): Json<ForceEndRequest>,
+) -> Result<StatusCode, AppError> {
+    let reason = body.reason.trim();
+    if reason.is_empty() {
+        return Err(AppError::BadRequest("A reason is required.".to_string()));
+    }
+    if reason.chars().count() > MAX_TERMINATION_REASON_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Reason must be at most {MAX_TERMINATION_REASON_LENGTH} characters."
+        )));
+    }
+
+    let sess = session::Entity::find_by_id(session_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?
+        .ok_or_else(|| AppError::NotFound("Session not found.".to_string()))?;
+
+    let terminated_msg = serde_json::json!({
+        "type": "session_terminated",
+        "payload": { "reason": reason }
     });
-    state
-        .session_manager
-        .broadcast(session_id, &status_msg.to_string());
-    state.session_manager.remove_session(session_id);
+    close_session(&state, &sess, Some(terminated_msg)).await?;
+
+    audit::Event::new("session.terminate", "session", session_id)
+        .before(serde_json::json!({ "status": sess.status }))
+        .after(serde_json::json!({ "status": "ended", "reason": reason }))
+        .record(&state.db, &moderator, &headers)
+        .await;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -489,15 +919,11 @@ async fn load_game(
 
     // Update session with game info and transition to playing
     let now = Utc::now().fixed_offset();
-    let mut active: session::ActiveModel = sess.into();
-    active.game_id = Set(Some(found_game.id));
-    active.game_version_id = Set(Some(version.id));
-    active.status = Set("playing".to_string());
-    active.updated_at = Set(now);
-    active
-        .update(&state.db)
-        .await
-        .map_err(|e| AppError::Internal(e.into()))?;
+    let new_play = start_play_segment(&state.db, &sess, found_game.id, version.id, now).await?;
+    if let Some(play_id) = new_play {
+        xp::play_started(&state.db, host.id, session_id, play_id).await;
+    }
+    achievements::game_played(&state.db, &state.notification_hub, found_game.id).await;
 
     // Send game_loaded to host with gameScreenCode
     let host_msg = serde_json::json!({
@@ -506,6 +932,7 @@ async fn load_game(
             "gameId": found_game.id,
             "gameVersionId": version.id,
             "gameScreenCode": version.game_screen_code,
+            "manifest": version.manifest,
         }
     });
     state
@@ -519,6 +946,7 @@ async fn load_game(
             "gameId": found_game.id,
             "gameVersionId": version.id,
             "controllerScreenCode": version.controller_screen_code,
+            "manifest": version.manifest,
         }
     });
     state
@@ -545,11 +973,157 @@ async fn load_game(
     }))
 }
 
+/// Switch the session to `game_id`, closing out the previous game's play segment.
+///
+/// Bumps the game's play count and records a new play unless the same game is simply being
+/// reloaded, returning the new play's ID.
+async fn start_play_segment(
+    db: &DatabaseConnection,
+    sess: &session::Model,
+    game_id: Uuid,
+    version_id: Uuid,
+    now: DateTime<FixedOffset>,
+) -> Result<Option<Uuid>, AppError> {
+    let txn = db.begin().await.map_err(|e| AppError::Internal(e.into()))?;
+
+    // Only claim the transition if the play segment we read is still current, so a
+    // concurrent load or end cannot make us count the same segment twice.
+    let segment_unchanged = sess.playing_started_at.map_or_else(
+        || session::Column::PlayingStartedAt.is_null(),
+        |started_at| session::Column::PlayingStartedAt.eq(started_at),
+    );
+    let claimed = session::Entity::update_many()
+        .col_expr(session::Column::GameId, Expr::value(game_id))
+        .col_expr(session::Column::GameVersionId, Expr::value(version_id))
+        .col_expr(session::Column::Status, Expr::value("playing"))
+        .col_expr(session::Column::PlayingStartedAt, Expr::value(now))
+        .col_expr(session::Column::UpdatedAt, Expr::value(now))
+        .filter(session::Column::Id.eq(sess.id))
+        .filter(session::Column::Status.ne("ended"))
+        .filter(segment_unchanged)
+        .exec(&txn)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    if claimed.rows_affected == 0 {
+        return Err(AppError::Conflict(
+            "Session changed while loading the game, please retry.".to_string(),
+        ));
+    }
+
+    // Close out the previous game's segment before starting the new one
+    if let (Some(prev_game_id), Some(started_at)) = (sess.game_id, sess.playing_started_at) {
+        accrue_play_time(&txn, sess.id, prev_game_id, started_at, now).await?;
+    }
+
+    // Reloading the game that is already running is not a new play
+    let new_play = if sess.game_id != Some(game_id) || sess.playing_started_at.is_none() {
+        game::Entity::update_many()
+            .col_expr(
+                game::Column::PlayCount,
+                Expr::col(game::Column::PlayCount).add(1),
+            )
+            .filter(game::Column::Id.eq(game_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        close_play(&txn, sess.id, now).await?;
+
+        let player_count = player::Entity::find()
+            .filter(player::Column::SessionId.eq(sess.id))
+            .filter(player::Column::LeftAt.is_null())
+            .count(&txn)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+        let play = game_play::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            game_id: Set(game_id),
+            session_id: Set(sess.id),
+            started_at: Set(now),
+            ended_at: Set(None),
+            play_seconds: Set(0),
+            player_count: Set(i32::try_from(player_count).unwrap_or(i32::MAX)),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+        Some(play.id)
+    } else {
+        None
+    };
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    Ok(new_play)
+}
+
+/// Add the seconds elapsed since `started_at` to the game's `total_play_time` and to the
+/// session's open play of it.
+async fn accrue_play_time(
+    txn: &DatabaseTransaction,
+    session_id: Uuid,
+    game_id: Uuid,
+    started_at: DateTime<FixedOffset>,
+    now: DateTime<FixedOffset>,
+) -> Result<(), AppError> {
+    let elapsed = (now - started_at).num_seconds().max(0);
+    if elapsed == 0 {
+        return Ok(());
+    }
+
+    game::Entity::update_many()
+        .col_expr(
+            game::Column::TotalPlayTime,
+            Expr::col(game::Column::TotalPlayTime).add(elapsed),
+        )
+        .filter(game::Column::Id.eq(game_id))
+        .exec(txn)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    game_play::Entity::update_many()
+        .col_expr(
+            game_play::Column::PlaySeconds,
+            Expr::col(game_play::Column::PlaySeconds).add(elapsed),
+        )
+        .filter(game_play::Column::SessionId.eq(session_id))
+        .filter(game_play::Column::GameId.eq(game_id))
+        .filter(game_play::Column::EndedAt.is_null())
+        .exec(txn)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    Ok(())
+}
+
+/// Mark the session's open play, if any, as ended at `now`.
+async fn close_play(
+    txn: &DatabaseTransaction,
+    session_id: Uuid,
+    now: DateTime<FixedOffset>,
+) -> Result<(), AppError> {
+    game_play::Entity::update_many()
+        .col_expr(game_play::Column::EndedAt, Expr::value(now))
+        .filter(game_play::Column::SessionId.eq(session_id))
+        .filter(game_play::Column::EndedAt.is_null())
+        .exec(txn)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    Ok(())
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // WebSocket
 // ─────────────────────────────────────────────────────────────────────────────
 
 /// `GET /api/v1/sessions/{sessionId}/ws` — Upgrade to `WebSocket`.
+///
+/// `?compression=deflate` opts in to the application-level compression described on
+/// [`outbound_frame`]; the `permessage-deflate` extension is never negotiated.
 async fn ws_upgrade(
     State(state): State<AppState>,
     Path(session_id): Path<Uuid>,
@@ -567,15 +1141,22 @@ async fn ws_upgrade(
         return Err(AppError::BadRequest("Session has ended.".to_string()));
     }
 
+    let compress = match params.compression.as_deref() {
+        None | Some("none") => false,
+        Some("deflate") => true,
+        Some(_) => {
+            return Err(AppError::BadRequest(
+                "Invalid compression. Must be 'deflate' or 'none'.".to_string(),
+            ));
+        }
+    };
+
     let role = match params.role.as_str() {
         "host" => {
             // Validate host identity via token
             if let Some(token) = &params.token {
-                let claims =
-                    crate::auth::jwt::validate_access_token(token, &state.config.jwt_secret)
-                        .map_err(|_| {
-                            AppError::Unauthorized("Invalid or expired token.".to_string())
-                        })?;
+                let claims = crate::auth::jwt::validate_access_token(token, &state.jwt_keys)
+                    .map_err(|_| AppError::Unauthorized("Invalid or expired token.".to_string()))?;
                 let user_id: Uuid = claims
                     .sub
                     .parse()
@@ -630,7 +1211,9 @@ async fn ws_upgrade(
 
     let ws_state = state.clone();
 
-    Ok(ws.on_upgrade(move |socket| handle_ws_connection(ws_state, session_id, role, socket)))
+    Ok(ws.on_upgrade(move |socket| {
+        handle_ws_connection(ws_state, session_id, role, compress, socket)
+    }))
 }
 
 /// Handle a single `WebSocket` connection for message relay.
@@ -638,6 +1221,7 @@ async fn handle_ws_connection(
     state: AppState,
     session_id: Uuid,
     role: ClientRole,
+    compress: bool,
     socket: WebSocket,
 ) {
     let (mut ws_sink, mut ws_stream) = socket.split();
@@ -668,10 +1252,19 @@ async fn handle_ws_connection(
         .send(Message::Text(connected_msg.to_string().into()))
         .await;
 
+    // Late-joining players start from the latest keyframe so they can apply deltas
+    if matches!(role, ClientRole::Player(_))
+        && let Some(snapshot) = state.session_manager.latest_snapshot(session_id)
+    {
+        let _ = ws_sink
+            .send(outbound_frame(snapshot.message, compress))
+            .await;
+    }
+
     // Spawn task to forward outbound messages to the WebSocket
     let send_task = tokio::spawn(async move {
         while let Some(msg) = rx.recv().await {
-            if ws_sink.send(Message::Text(msg.into())).await.is_err() {
+            if ws_sink.send(outbound_frame(msg, compress)).await.is_err() {
                 break;
             }
         }
@@ -727,10 +1320,14 @@ fn handle_ws_message(state: &AppState, session_id: Uuid, role: &ClientRole, text
         .get("type")
         .and_then(serde_json::Value::as_str)
         .unwrap_or_default();
+    state.session_manager.record_received(msg_type, text.len());
 
     match (msg_type, role) {
         // Player sends input → relay to host with playerId attached
         ("player_input", ClientRole::Player(player_id)) => {
+            if state.session_manager.record_input(session_id, *player_id) {
+                tokio::spawn(record_first_input(state.db.clone(), *player_id));
+            }
             let relay_msg = serde_json::json!({
                 "type": "player_input_event",
                 "payload": {
@@ -743,18 +1340,82 @@ fn handle_ws_message(state: &AppState, session_id: Uuid, role: &ClientRole, text
                 .session_manager
                 .send_to_host(session_id, &relay_msg.to_string());
         }
-        // Host broadcasts game state → relay to all players
+        // Host broadcasts a full game state keyframe → relay to all players and cache it
         ("game_state_update", ClientRole::Host) => {
-            let relay_msg = serde_json::json!({
+            let seq = parsed.get("seq").and_then(serde_json::Value::as_u64);
+            let mut relay_msg = serde_json::json!({
                 "type": "game_state",
                 "payload": parsed["payload"],
             });
+            if let Some(seq) = seq {
+                relay_msg["seq"] = seq.into();
+            }
+            let relay_msg = relay_msg.to_string();
+            state
+                .session_manager
+                .broadcast_to_players(session_id, &relay_msg);
+            state.session_manager.store_snapshot(
+                session_id,
+                StateSnapshot {
+                    seq,
+                    message: relay_msg,
+                },
+            );
+        }
+        // Host broadcasts a diff against an earlier keyframe/delta → relay to all players
+        ("game_state_delta", ClientRole::Host) => {
+            let (Some(seq), Some(base_seq)) = (
+                parsed.get("seq").and_then(serde_json::Value::as_u64),
+                parsed.get("baseSeq").and_then(serde_json::Value::as_u64),
+            ) else {
+                return;
+            };
+            let relay_msg = serde_json::json!({
+                "type": "game_state_delta",
+                "seq": seq,
+                "baseSeq": base_seq,
+                "payload": parsed["payload"],
+            });
             state
                 .session_manager
                 .broadcast_to_players(session_id, &relay_msg.to_string());
         }
+        // Player lost track of the delta chain → resend the latest keyframe, or ask the host
+        ("state_resync", ClientRole::Player(player_id)) => {
+            if let Some(snapshot) = state.session_manager.latest_snapshot(session_id) {
+                state
+                    .session_manager
+                    .send_to_player(session_id, *player_id, &snapshot.message);
+            } else {
+                let request_msg = serde_json::json!({
+                    "type": "state_resync_requested",
+                    "payload": {
+                        "playerId": player_id,
+                    }
+                });
+                state
+                    .session_manager
+                    .send_to_host(session_id, &request_msg.to_string());
+            }
+        }
         _ => {
             // Unknown message types are silently ignored
         }
     }
 }
+
+/// Stamp `player.first_input_at`, unless an earlier connection already did.
+async fn record_first_input(db: sea_orm::DatabaseConnection, player_id: Uuid) {
+    let result = player::Entity::update_many()
+        .col_expr(
+            player::Column::FirstInputAt,
+            Expr::value(Utc::now().fixed_offset()),
+        )
+        .filter(player::Column::Id.eq(player_id))
+        .filter(player::Column::FirstInputAt.is_null())
+        .exec(&db)
+        .await;
+    if let Err(e) = result {
+        tracing::warn!(error = %e, %player_id, "Failed to record first player input");
+    }
+}
@@ -0,0 +1,508 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    response::IntoResponse,
+    routing::get,
+};
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Datelike, Months, NaiveDate, NaiveTime, Utc};
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+    prelude::DateTimeWithTimeZone,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::middleware::{AdminUser, AuthUser},
+    entities::{game_daily_stats, game_play, login_event, platform_daily_stats, player},
+    error::AppError,
+    routes::games::find_active_game,
+    state::AppState,
+};
+
+/// Days covered when no `from` is given.
+const DEFAULT_RANGE_DAYS: u64 = 30;
+
+/// Longest range that may be requested in one call.
+const MAX_RANGE_DAYS: i64 = 366;
+
+/// Creator analytics routes, nested under `/games` alongside the main game router.
+pub fn router() -> Router<AppState> {
+    Router::new().route("/{id}/stats", get(get_game_stats))
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Bucket {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+impl Bucket {
+    /// First day of the bucket containing `day`. Weeks start on Monday.
+    fn start(self, day: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Day => day,
+            Self::Week => day - chrono::Days::new(u64::from(day.weekday().num_days_from_monday())),
+            Self::Month => day.with_day(1).unwrap_or(day),
+        }
+    }
+
+    const fn next(self, start: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Self::Day => start.checked_add_days(chrono::Days::new(1)),
+            Self::Week => start.checked_add_days(chrono::Days::new(7)),
+            Self::Month => start.checked_add_months(Months::new(1)),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StatsQuery {
+    #[serde(default)]
+    bucket: Bucket,
+    /// First day included (UTC). Defaults to 30 days before `to`.
+    from: Option<NaiveDate>,
+    /// Last day included (UTC). Defaults to today.
+    to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StatsSummary {
+    plays: i64,
+    /// Summed per day, so a session playing on several days counts once per day.
+    unique_sessions: i64,
+    play_seconds: i64,
+    average_session_seconds: i64,
+    average_players: f64,
+    peak_players: i32,
+    /// Summed per day, so a player playing on several days counts once per day.
+    unique_players: i64,
+    /// Times clients reported opening the game.
+    opens: i64,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PlatformStatsSummary {
+    sessions: i64,
+    session_seconds: i64,
+    plays: i64,
+    play_seconds: i64,
+    /// Summed per day, so a player playing on several days counts once per day.
+    unique_players: i64,
+    /// Summed per day, like `uniquePlayers`.
+    games_played: i64,
+    events: i64,
+}
+
+/// How the players of a game's sessions engaged, over the whole requested range.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FunnelSummary {
+    /// Distinct sessions that played the game.
+    sessions: i64,
+    /// Players who joined those sessions.
+    joined_players: i64,
+    /// Of those, players who sent any controller input.
+    active_players: i64,
+    /// `activePlayers / joinedPlayers`.
+    join_to_play_conversion: f64,
+    /// Share of signed-in players who played in more than one of those sessions. Guests
+    /// cannot be recognised across sessions and are left out.
+    repeat_player_rate: f64,
+    average_players_per_session: f64,
+}
+
+/// Players from one country; `country` is `None` for those whose country is unknown.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CountryPlayers {
+    country: Option<String>,
+    players: i64,
+}
+
+/// Players who joined and accounts that signed in from one country over the requested range.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CountryActivity {
+    country: Option<String>,
+    players: i64,
+    sign_ins: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StatsBucketResponse<T> {
+    start: NaiveDate,
+    #[serde(flatten)]
+    stats: T,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StatsResponse<T> {
+    bucket: Bucket,
+    from: NaiveDate,
+    to: NaiveDate,
+    totals: T,
+    data: Vec<StatsBucketResponse<T>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GameStatsResponse {
+    #[serde(flatten)]
+    stats: StatsResponse<StatsSummary>,
+    funnel: FunnelSummary,
+    /// Where the players of the funnel's sessions joined from, most players first.
+    countries: Vec<CountryPlayers>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PlatformStatsResponse {
+    #[serde(flatten)]
+    stats: StatsResponse<PlatformStatsSummary>,
+    /// Most players first.
+    countries: Vec<CountryActivity>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `GET /games/:id/stats` — Plays, opens, sessions, play time and player counts over time, for
+/// the game's creator.
+///
+/// Figures come from the periodic daily rollup, so the latest plays may take a few minutes to
+/// appear. Every bucket in the range is returned, including empty ones. The `funnel` covers
+/// the whole range: how many players who joined actually played, and how many came back.
+/// `countries` breaks the same players down by the country they joined from.
+async fn get_game_stats(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+    Query(query): Query<StatsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+    if game.owner_id != user.id {
+        return Err(AppError::Forbidden(
+            "Only the creator can view this game's stats".to_string(),
+        ));
+    }
+
+    let (from, to) = date_range(&query)?;
+    let days = game_daily_stats::Entity::find()
+        .filter(game_daily_stats::Column::GameId.eq(game.id))
+        .filter(game_daily_stats::Column::Day.gte(from))
+        .filter(game_daily_stats::Column::Day.lte(to))
+        .order_by_asc(game_daily_stats::Column::Day)
+        .all(&state.db)
+        .await?;
+
+    let (sessions, players) = session_players(&state.db, game.id, from, to).await?;
+    Ok(Json(GameStatsResponse {
+        stats: stats_response(query.bucket, from, to, &days, |d| d.day, summarize),
+        funnel: funnel(&sessions, &players),
+        countries: count_by_country(players.iter().map(|p| p.country.clone()))
+            .into_iter()
+            .map(|(country, players)| CountryPlayers { country, players })
+            .collect(),
+    }))
+}
+
+/// `GET /admin/stats` — Sessions, plays, players and analytics events across the platform
+/// over time.
+///
+/// Takes the same `bucket`, `from` and `to` parameters as the creator stats. Figures come
+/// from the periodic daily rollup, so the latest activity may take a few minutes to appear.
+/// `countries` is computed live: players who joined sessions and successful sign-ins over the
+/// whole range, by country.
+pub async fn get_platform_stats(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    Query(query): Query<StatsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let (from, to) = date_range(&query)?;
+    let days = platform_daily_stats::Entity::find()
+        .filter(platform_daily_stats::Column::Day.gte(from))
+        .filter(platform_daily_stats::Column::Day.lte(to))
+        .order_by_asc(platform_daily_stats::Column::Day)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(PlatformStatsResponse {
+        stats: stats_response(query.bucket, from, to, &days, |d| d.day, summarize_platform),
+        countries: platform_countries(&state.db, from, to).await?,
+    }))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// The requested range: `to` defaults to today and `from` to 30 days before it.
+fn date_range(query: &StatsQuery) -> Result<(NaiveDate, NaiveDate), AppError> {
+    let to = query.to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = query.from.unwrap_or_else(|| {
+        to.checked_sub_days(chrono::Days::new(DEFAULT_RANGE_DAYS - 1))
+            .unwrap_or(to)
+    });
+    if from > to {
+        return Err(AppError::BadRequest(
+            "'from' must not be after 'to'".to_string(),
+        ));
+    }
+    if (to - from).num_days() >= MAX_RANGE_DAYS {
+        return Err(AppError::BadRequest(format!(
+            "Range may cover at most {MAX_RANGE_DAYS} days"
+        )));
+    }
+    Ok((from, to))
+}
+
+/// A player of one of the sessions [`session_players`] found.
+struct SessionPlayer {
+    session_id: Uuid,
+    user_id: Option<Uuid>,
+    first_input_at: Option<DateTimeWithTimeZone>,
+    country: Option<String>,
+}
+
+/// The sessions that played `game_id` between `from` and `to`, and their players.
+///
+/// A session counts if any play of the game started in the range, and all of its players
+/// count towards the game, whichever game they sent input in.
+async fn session_players(
+    db: &DatabaseConnection,
+    game_id: Uuid,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<(HashSet<Uuid>, Vec<SessionPlayer>), DbErr> {
+    let end = to.checked_add_days(chrono::Days::new(1)).unwrap_or(to);
+    let sessions: HashSet<Uuid> = game_play::Entity::find()
+        .select_only()
+        .column(game_play::Column::SessionId)
+        .filter(game_play::Column::GameId.eq(game_id))
+        .filter(game_play::Column::StartedAt.gte(day_start(from)))
+        .filter(game_play::Column::StartedAt.lt(day_start(end)))
+        .into_tuple()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+    if sessions.is_empty() {
+        return Ok((sessions, Vec::new()));
+    }
+
+    let players = player::Entity::find()
+        .select_only()
+        .column(player::Column::SessionId)
+        .column(player::Column::UserId)
+        .column(player::Column::FirstInputAt)
+        .column(player::Column::Country)
+        .filter(player::Column::SessionId.is_in(sessions.iter().copied()))
+        .into_tuple()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(
+            |(session_id, user_id, first_input_at, country)| SessionPlayer {
+                session_id,
+                user_id,
+                first_input_at,
+                country,
+            },
+        )
+        .collect();
+    Ok((sessions, players))
+}
+
+/// Join-to-play conversion and repeat players of `sessions`.
+fn funnel(sessions: &HashSet<Uuid>, players: &[SessionPlayer]) -> FunnelSummary {
+    if sessions.is_empty() {
+        return FunnelSummary::default();
+    }
+
+    let active = players
+        .iter()
+        .filter(|p| p.first_input_at.is_some())
+        .count();
+    let mut sessions_per_user: HashMap<Uuid, HashSet<Uuid>> = HashMap::new();
+    for p in players {
+        if let Some(user_id) = p.user_id {
+            sessions_per_user
+                .entry(user_id)
+                .or_default()
+                .insert(p.session_id);
+        }
+    }
+    let repeat = sessions_per_user.values().filter(|s| s.len() > 1).count();
+
+    FunnelSummary {
+        sessions: i64::try_from(sessions.len()).unwrap_or(i64::MAX),
+        joined_players: i64::try_from(players.len()).unwrap_or(i64::MAX),
+        active_players: i64::try_from(active).unwrap_or(i64::MAX),
+        join_to_play_conversion: ratio(active, players.len(), 1000.0),
+        repeat_player_rate: ratio(repeat, sessions_per_user.len(), 1000.0),
+        average_players_per_session: ratio(players.len(), sessions.len(), 10.0),
+    }
+}
+
+/// Players who joined sessions and successful sign-ins between `from` and `to`, by country.
+async fn platform_countries(
+    db: &DatabaseConnection,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<CountryActivity>, DbErr> {
+    let end = to.checked_add_days(chrono::Days::new(1)).unwrap_or(to);
+    let players: Vec<Option<String>> = player::Entity::find()
+        .select_only()
+        .column(player::Column::Country)
+        .filter(player::Column::CreatedAt.gte(day_start(from)))
+        .filter(player::Column::CreatedAt.lt(day_start(end)))
+        .into_tuple()
+        .all(db)
+        .await?;
+    let sign_ins: Vec<Option<String>> = login_event::Entity::find()
+        .select_only()
+        .column(login_event::Column::Country)
+        .filter(login_event::Column::Outcome.eq("success"))
+        .filter(login_event::Column::CreatedAt.gte(day_start(from)))
+        .filter(login_event::Column::CreatedAt.lt(day_start(end)))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let sign_ins: HashMap<Option<String>, i64> = count_by_country(sign_ins).into_iter().collect();
+    let mut countries: Vec<CountryActivity> = count_by_country(players)
+        .into_iter()
+        .map(|(country, players)| CountryActivity {
+            sign_ins: sign_ins.get(&country).copied().unwrap_or_default(),
+            country,
+            players,
+        })
+        .collect();
+    for (country, count) in sign_ins {
+        if !countries.iter().any(|c| c.country == country) {
+            countries.push(CountryActivity {
+                country,
+                players: 0,
+                sign_ins: count,
+            });
+        }
+    }
+    Ok(countries)
+}
+
+/// How often each country occurs, most frequent first, then alphabetically with unknown last.
+fn count_by_country(
+    countries: impl IntoIterator<Item = Option<String>>,
+) -> Vec<(Option<String>, i64)> {
+    let mut counts: HashMap<Option<String>, i64> = HashMap::new();
+    for country in countries {
+        *counts.entry(country).or_default() += 1;
+    }
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|(a, a_count), (b, b_count)| {
+        b_count
+            .cmp(a_count)
+            .then_with(|| a.is_none().cmp(&b.is_none()))
+            .then_with(|| a.cmp(b))
+    });
+    counts
+}
+
+/// `part / whole` rounded to `1 / precision`, or 0 when `whole` is 0.
+#[allow(clippy::cast_precision_loss)]
+fn ratio(part: usize, whole: usize, precision: f64) -> f64 {
+    if whole == 0 {
+        return 0.0;
+    }
+    (part as f64 / whole as f64 * precision).round() / precision
+}
+
+fn day_start(day: NaiveDate) -> DateTimeWithTimeZone {
+    day.and_time(NaiveTime::MIN).and_utc().fixed_offset()
+}
+
+/// Summarize daily rows into totals and every bucket in the range, including empty ones.
+fn stats_response<R, T>(
+    bucket: Bucket,
+    from: NaiveDate,
+    to: NaiveDate,
+    days: &[R],
+    day_of: impl Fn(&R) -> NaiveDate,
+    summarize: impl Fn(&[&R]) -> T,
+) -> StatsResponse<T> {
+    let mut data = Vec::new();
+    let mut start = Some(bucket.start(from));
+    while let Some(current) = start.filter(|s| *s <= to) {
+        let next = bucket.next(current);
+        let rows: Vec<&R> = days
+            .iter()
+            .filter(|d| day_of(d) >= current && next.is_none_or(|n| day_of(d) < n))
+            .collect();
+        data.push(StatsBucketResponse {
+            start: current,
+            stats: summarize(&rows),
+        });
+        start = next;
+    }
+
+    StatsResponse {
+        bucket,
+        from,
+        to,
+        totals: summarize(&days.iter().collect::<Vec<_>>()),
+        data,
+    }
+}
+
+fn summarize(days: &[&game_daily_stats::Model]) -> StatsSummary {
+    let mut summary = StatsSummary::default();
+    let mut players = 0;
+    for day in days {
+        summary.plays += day.plays;
+        summary.unique_sessions += day.sessions;
+        summary.play_seconds += day.play_seconds;
+        summary.peak_players = summary.peak_players.max(day.peak_players);
+        summary.unique_players += day.unique_players;
+        summary.opens += day.opens;
+        players += day.players;
+    }
+
+    if summary.unique_sessions > 0 {
+        summary.average_session_seconds = summary.play_seconds / summary.unique_sessions;
+    }
+    if summary.plays > 0 {
+        #[allow(clippy::cast_precision_loss)]
+        let average = players as f64 / summary.plays as f64;
+        summary.average_players = (average * 10.0).round() / 10.0;
+    }
+    summary
+}
+
+fn summarize_platform(days: &[&platform_daily_stats::Model]) -> PlatformStatsSummary {
+    let mut summary = PlatformStatsSummary::default();
+    for day in days {
+        summary.sessions += day.sessions;
+        summary.session_seconds += day.session_seconds;
+        summary.plays += day.plays;
+        summary.play_seconds += day.play_seconds;
+        summary.unique_players += day.unique_players;
+        summary.games_played += day.games_played;
+        summary.events += day.events;
+    }
+    summary
+}
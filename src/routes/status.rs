@@ -0,0 +1,347 @@
+use std::collections::BTreeMap;
+
+use axum::{
+    Json, Router,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, EntityTrait,
+    FromQueryResult, QueryFilter, QueryOrder, QuerySelect, sea_query::Expr,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    audit,
+    auth::middleware::AdminUser,
+    entities::{health_check, status_incident},
+    error::AppError,
+    health,
+    state::AppState,
+};
+
+/// Allowed values for `status_incident.impact`, least to most severe.
+const IMPACTS: [&str; 3] = ["maintenance", "minor", "major"];
+
+/// Longest incident title, in characters.
+const MAX_TITLE_LENGTH: usize = 100;
+
+/// Longest incident message, in characters.
+const MAX_MESSAGE_LENGTH: usize = 2000;
+
+/// Windows uptime is reported over, by name.
+const UPTIME_WINDOWS: [(&str, chrono::Duration); 3] = [
+    ("24h", chrono::Duration::hours(24)),
+    ("7d", chrono::Duration::days(7)),
+    ("30d", chrono::Duration::days(30)),
+];
+
+/// Public status router: `GET /api/v1/status`.
+pub fn api_router() -> Router<AppState> {
+    Router::new().route("/status", get(get_status))
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+/// The incident to show on the status page.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncidentRequest {
+    title: String,
+    message: String,
+    /// `maintenance`, `minor` or `major`.
+    impact: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StatusResponse {
+    /// `operational`, or `degraded` if a component is down.
+    status: &'static str,
+    /// When the components were last checked.
+    checked_at: String,
+    components: BTreeMap<String, ComponentStatus>,
+    /// The ongoing incident, if admins have posted one.
+    incident: Option<IncidentResponse>,
+    /// Percentage of checks each component passed, by window (`24h`, `7d` and `30d`).
+    /// `None` for windows without checks.
+    uptime: BTreeMap<String, BTreeMap<&'static str, Option<f64>>>,
+}
+
+#[derive(Debug, Serialize)]
+struct ComponentStatus {
+    /// `up`, `down` or `disabled`.
+    status: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IncidentResponse {
+    id: Uuid,
+    title: String,
+    message: String,
+    impact: String,
+    started_at: String,
+    updated_at: String,
+}
+
+impl From<status_incident::Model> for IncidentResponse {
+    fn from(i: status_incident::Model) -> Self {
+        Self {
+            id: i.id,
+            title: i.title,
+            message: i.message,
+            impact: i.impact,
+            started_at: i.started_at.to_rfc3339(),
+            updated_at: i.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `GET /status` — Everything a public status page shows: the health of each component
+/// as of the last recorded check, the ongoing incident, and recent uptime.
+///
+/// Before the first check has been recorded the components are checked on the spot.
+async fn get_status(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
+    let now = Utc::now();
+    let (checked_at, components) = if let Some(latest) = latest_checks(&state.db).await? {
+        latest
+    } else {
+        let components = health::check_components(&state.db, &state.config)
+            .await
+            .into_iter()
+            .map(|(name, c)| {
+                (
+                    name.to_string(),
+                    ComponentStatus {
+                        status: c.status.to_string(),
+                    },
+                )
+            })
+            .collect();
+        (now, components)
+    };
+    let degraded = components.values().any(|c| c.status == health::DOWN);
+
+    let incident = find_ongoing_incident(&state.db).await?;
+    let uptime = uptime(&state.db, now).await?;
+
+    Ok(Json(StatusResponse {
+        status: if degraded { "degraded" } else { "operational" },
+        checked_at: checked_at.to_rfc3339(),
+        components,
+        incident: incident.map(IncidentResponse::from),
+        uptime,
+    }))
+}
+
+/// `PUT /admin/status/incident` — Post the ongoing incident, or update it if there is one.
+///
+/// # Errors
+///
+/// Returns [`AppError::BadRequest`] if the incident is invalid, or [`AppError`] if a
+/// database operation fails.
+pub async fn set_incident(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    headers: HeaderMap,
+    Json(req): Json<IncidentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let title = req.title.trim().to_string();
+    if !(1..=MAX_TITLE_LENGTH).contains(&title.chars().count()) {
+        return Err(AppError::BadRequest(format!(
+            "Title must be between 1 and {MAX_TITLE_LENGTH} characters."
+        )));
+    }
+    let message = req.message.trim().to_string();
+    if !(1..=MAX_MESSAGE_LENGTH).contains(&message.chars().count()) {
+        return Err(AppError::BadRequest(format!(
+            "Message must be between 1 and {MAX_MESSAGE_LENGTH} characters."
+        )));
+    }
+    if !IMPACTS.contains(&req.impact.as_str()) {
+        return Err(AppError::BadRequest(format!(
+            "Invalid impact '{}'. Expected one of: {}",
+            req.impact,
+            IMPACTS.join(", ")
+        )));
+    }
+
+    let now = Utc::now().fixed_offset();
+    let (incident, before) = if let Some(existing) = find_ongoing_incident(&state.db).await? {
+        let before = snapshot(&existing);
+        let mut active: status_incident::ActiveModel = existing.into();
+        active.title = Set(title);
+        active.message = Set(message);
+        active.impact = Set(req.impact);
+        active.updated_at = Set(now);
+        (active.update(&state.db).await?, Some(before))
+    } else {
+        let created = status_incident::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            title: Set(title),
+            message: Set(message),
+            impact: Set(req.impact),
+            started_at: Set(now),
+            resolved_at: Set(None),
+            created_by: Set(Some(admin.id)),
+            updated_at: Set(now),
+        }
+        .insert(&state.db)
+        .await?;
+        (created, None)
+    };
+
+    let mut event = audit::Event::new("status_incident.set", "status_incident", incident.id)
+        .after(snapshot(&incident));
+    if let Some(before) = before {
+        event = event.before(before);
+    }
+    event.record(&state.db, &admin, &headers).await;
+
+    Ok(Json(IncidentResponse::from(incident)))
+}
+
+/// `DELETE /admin/status/incident` — Resolve the ongoing incident, removing it from the
+/// status page.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if there is no ongoing incident, or [`AppError`] if a
+/// database operation fails.
+pub async fn resolve_incident(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let existing = find_ongoing_incident(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("There is no ongoing incident.".to_string()))?;
+    let id = existing.id;
+    let before = snapshot(&existing);
+
+    let now = Utc::now().fixed_offset();
+    let mut active: status_incident::ActiveModel = existing.into();
+    active.resolved_at = Set(Some(now));
+    active.updated_at = Set(now);
+    active.update(&state.db).await?;
+
+    audit::Event::new("status_incident.resolve", "status_incident", id)
+        .before(before)
+        .record(&state.db, &admin, &headers)
+        .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// When the components were last checked and their status then, or `None` if no check has
+/// been recorded.
+async fn latest_checks(
+    db: &DatabaseConnection,
+) -> Result<Option<(DateTime<Utc>, BTreeMap<String, ComponentStatus>)>, AppError> {
+    let Some(latest) = health_check::Entity::find()
+        .order_by_desc(health_check::Column::CheckedAt)
+        .one(db)
+        .await?
+    else {
+        return Ok(None);
+    };
+
+    let checks = health_check::Entity::find()
+        .filter(health_check::Column::CheckedAt.eq(latest.checked_at))
+        .all(db)
+        .await?;
+    let components = checks
+        .into_iter()
+        .map(|c| (c.component, ComponentStatus { status: c.status }))
+        .collect();
+    Ok(Some((latest.checked_at.with_timezone(&Utc), components)))
+}
+
+#[derive(Debug, FromQueryResult)]
+struct StatusCount {
+    component: String,
+    status: String,
+    count: i64,
+}
+
+/// Percentage of checks each component passed in each of [`UPTIME_WINDOWS`]. Components
+/// that were disabled count as up.
+#[allow(clippy::cast_precision_loss)]
+async fn uptime(
+    db: &DatabaseConnection,
+    now: DateTime<Utc>,
+) -> Result<BTreeMap<String, BTreeMap<&'static str, Option<f64>>>, AppError> {
+    let mut uptime: BTreeMap<String, BTreeMap<&'static str, Option<f64>>> = BTreeMap::new();
+    for (window, length) in UPTIME_WINDOWS {
+        let counts = health_check::Entity::find()
+            .select_only()
+            .column(health_check::Column::Component)
+            .column(health_check::Column::Status)
+            .column_as(Expr::cust("CAST(COUNT(*) AS BIGINT)"), "count")
+            .filter(health_check::Column::CheckedAt.gte((now - length).fixed_offset()))
+            .group_by(health_check::Column::Component)
+            .group_by(health_check::Column::Status)
+            .into_model::<StatusCount>()
+            .all(db)
+            .await?;
+
+        // (passed, total) per component
+        let mut totals: BTreeMap<String, (i64, i64)> = BTreeMap::new();
+        for row in counts {
+            let entry = totals.entry(row.component).or_default();
+            if row.status != health::DOWN {
+                entry.0 += row.count;
+            }
+            entry.1 += row.count;
+        }
+        for (component, (passed, total)) in totals {
+            let percent = (passed as f64 * 10_000.0 / total as f64).round() / 100.0;
+            uptime
+                .entry(component)
+                .or_default()
+                .insert(window, Some(percent));
+        }
+    }
+
+    // Windows without checks, so every component reports every window
+    for windows in uptime.values_mut() {
+        for (window, _) in UPTIME_WINDOWS {
+            windows.entry(window).or_insert(None);
+        }
+    }
+    Ok(uptime)
+}
+
+async fn find_ongoing_incident(
+    db: &DatabaseConnection,
+) -> Result<Option<status_incident::Model>, AppError> {
+    Ok(status_incident::Entity::find()
+        .filter(status_incident::Column::ResolvedAt.is_null())
+        .order_by_desc(status_incident::Column::StartedAt)
+        .one(db)
+        .await?)
+}
+
+/// The audited fields of an incident.
+fn snapshot(i: &status_incident::Model) -> serde_json::Value {
+    serde_json::json!({
+        "title": i.title,
+        "impact": i.impact,
+        "startedAt": i.started_at.to_rfc3339(),
+    })
+}
@@ -0,0 +1,352 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::get,
+};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, RelationTrait, TransactionTrait, sea_query::JoinType,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    audit,
+    auth::middleware::{AdminUser, AuthUser},
+    entities::{suspension_appeal, user},
+    error::AppError,
+    notifications,
+    routes::games::PaginatedResponse,
+    state::AppState,
+};
+
+/// `suspension_appeal.status` of an appeal no admin has decided yet.
+const PENDING: &str = "pending";
+
+/// `suspension_appeal.status` of an appeal that lifted the suspension.
+const APPROVED: &str = "approved";
+
+/// `suspension_appeal.status` of an appeal that left the suspension in place.
+const DENIED: &str = "denied";
+
+/// Shortest statement a suspended user may send, in characters.
+const MIN_STATEMENT_LENGTH: usize = 20;
+
+/// Longest statement a suspended user may send, in characters.
+const MAX_STATEMENT_LENGTH: usize = 2000;
+
+/// Longest note a reviewer may leave on an appeal, in characters.
+const MAX_REVIEW_NOTE_LENGTH: usize = 1000;
+
+/// Appeal routes, nested under `/auth`. Suspended accounts may use them; the auth middleware
+/// refuses them everything else.
+pub fn router() -> Router<AppState> {
+    Router::new().route("/appeal", get(get_my_appeal).post(submit_appeal))
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct SubmitAppealRequest {
+    /// The user's case for lifting the suspension.
+    statement: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewSuspensionAppealRequest {
+    /// Shown to the user, e.g. why the appeal was denied.
+    note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuspensionAppealsQuery {
+    /// Only appeals with this status; defaults to pending appeals.
+    status: Option<String>,
+    #[serde(default)]
+    offset: u64,
+    #[serde(default = "default_suspension_appeals_limit")]
+    limit: u64,
+}
+
+const fn default_suspension_appeals_limit() -> u64 {
+    50
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AppealResponse {
+    id: Uuid,
+    suspension_reason: Option<String>,
+    statement: String,
+    status: String,
+    review_note: Option<String>,
+    reviewed_at: Option<String>,
+    created_at: String,
+}
+
+impl From<suspension_appeal::Model> for AppealResponse {
+    fn from(appeal: suspension_appeal::Model) -> Self {
+        Self {
+            id: appeal.id,
+            suspension_reason: appeal.suspension_reason,
+            statement: appeal.statement,
+            status: appeal.status,
+            review_note: appeal.review_note,
+            reviewed_at: appeal.reviewed_at.map(|t| t.to_rfc3339()),
+            created_at: appeal.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AppealUserResponse {
+    id: Uuid,
+    username: String,
+    email: String,
+    account_status: String,
+}
+
+/// An appeal as admins see it, with the appealing user and who decided it.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AdminAppealResponse {
+    #[serde(flatten)]
+    appeal: AppealResponse,
+    user: AppealUserResponse,
+    reviewer_id: Option<Uuid>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `POST /auth/appeal` — Ask an admin to lift the caller's suspension. A user may have one
+/// pending appeal at a time.
+async fn submit_appeal(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(body): Json<SubmitAppealRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let statement = body.statement.trim().to_string();
+    let length = statement.chars().count();
+    if !(MIN_STATEMENT_LENGTH..=MAX_STATEMENT_LENGTH).contains(&length) {
+        return Err(AppError::BadRequest(format!(
+            "Statement must be between {MIN_STATEMENT_LENGTH} and {MAX_STATEMENT_LENGTH} \
+             characters."
+        )));
+    }
+    if user.account_status != "suspended" {
+        return Err(AppError::Conflict(
+            "Only suspended accounts can appeal.".to_string(),
+        ));
+    }
+
+    let pending = suspension_appeal::Entity::find()
+        .filter(suspension_appeal::Column::UserId.eq(user.id))
+        .filter(suspension_appeal::Column::Status.eq(PENDING))
+        .count(&state.db)
+        .await?
+        > 0;
+    if pending {
+        return Err(AppError::Conflict(
+            "An appeal is already awaiting review.".to_string(),
+        ));
+    }
+
+    let appeal = suspension_appeal::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user.id),
+        suspension_reason: Set(user.suspension_reason),
+        statement: Set(statement),
+        status: Set(PENDING.to_string()),
+        reviewer_id: Set(None),
+        review_note: Set(None),
+        reviewed_at: Set(None),
+        created_at: Set(Utc::now().fixed_offset()),
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(AppealResponse::from(appeal))))
+}
+
+/// `GET /auth/appeal` — The caller's most recent suspension appeal and its outcome.
+async fn get_my_appeal(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    let appeal = suspension_appeal::Entity::find()
+        .filter(suspension_appeal::Column::UserId.eq(user.id))
+        .order_by_desc(suspension_appeal::Column::CreatedAt)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("No appeal found.".to_string()))?;
+
+    Ok(Json(AppealResponse::from(appeal)))
+}
+
+/// `GET /admin/suspension-appeals` — Suspension appeals, oldest first. Lists pending appeals
+/// unless `status` says otherwise.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the database query fails.
+pub async fn list_suspension_appeals(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    Query(query): Query<SuspensionAppealsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let status = query.status.unwrap_or_else(|| PENDING.to_string());
+    let find = suspension_appeal::Entity::find()
+        .filter(suspension_appeal::Column::Status.eq(status))
+        .join(JoinType::InnerJoin, suspension_appeal::Relation::User.def())
+        .select_also(user::Entity);
+    let total = find.clone().count(&state.db).await?;
+
+    let rows = find
+        .order_by_asc(suspension_appeal::Column::CreatedAt)
+        .offset(query.offset)
+        .limit(query.limit)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(PaginatedResponse {
+        data: rows
+            .into_iter()
+            .filter_map(|(appeal, user)| user.map(|u| to_admin_appeal_response(appeal, u)))
+            .collect(),
+        total,
+        offset: query.offset,
+        limit: query.limit,
+    }))
+}
+
+/// `POST /admin/suspension-appeals/{id}/approve` — Approve a pending appeal, lifting the
+/// user's suspension.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if there is no pending appeal with that ID,
+/// [`AppError::BadRequest`] if the note is too long, or [`AppError`] if a database operation
+/// fails.
+pub async fn approve_suspension_appeal(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(body): Json<ReviewSuspensionAppealRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    decide(&state, id, &admin, &headers, APPROVED, body.note).await
+}
+
+/// `POST /admin/suspension-appeals/{id}/deny` — Deny a pending appeal. The account stays
+/// suspended, and the user may appeal again.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if there is no pending appeal with that ID,
+/// [`AppError::BadRequest`] if the note is too long, or [`AppError`] if a database operation
+/// fails.
+pub async fn deny_suspension_appeal(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(body): Json<ReviewSuspensionAppealRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    decide(&state, id, &admin, &headers, DENIED, body.note).await
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Move pending appeal `id` to `status`, reinstating the account when approved, and tell the
+/// user the outcome.
+async fn decide(
+    state: &AppState,
+    id: Uuid,
+    admin: &user::Model,
+    headers: &HeaderMap,
+    status: &str,
+    note: Option<String>,
+) -> Result<Json<AdminAppealResponse>, AppError> {
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if note
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_REVIEW_NOTE_LENGTH)
+    {
+        return Err(AppError::BadRequest(format!(
+            "Note must be at most {MAX_REVIEW_NOTE_LENGTH} characters."
+        )));
+    }
+
+    let txn = state.db.begin().await?;
+    let appeal = suspension_appeal::Entity::find_by_id(id)
+        .filter(suspension_appeal::Column::Status.eq(PENDING))
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Appeal not found.".to_string()))?;
+    let mut appellant = user::Entity::find_by_id(appeal.user_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found.".to_string()))?;
+
+    let mut active: suspension_appeal::ActiveModel = appeal.into();
+    active.status = Set(status.to_string());
+    active.reviewer_id = Set(Some(admin.id));
+    active.review_note = Set(note);
+    active.reviewed_at = Set(Some(Utc::now().fixed_offset()));
+    let decided = active.update(&txn).await?;
+
+    if status == APPROVED && appellant.account_status == "suspended" {
+        let mut active: user::ActiveModel = appellant.into();
+        active.account_status = Set("active".to_string());
+        active.suspension_reason = Set(None);
+        active.updated_at = Set(Utc::now().fixed_offset());
+        appellant = active.update(&txn).await?;
+    }
+
+    txn.commit().await?;
+
+    let action = if status == APPROVED {
+        "suspension_appeal.approve"
+    } else {
+        "suspension_appeal.deny"
+    };
+    audit::Event::new(action, "suspension_appeal", id)
+        .before(serde_json::json!({ "status": PENDING }))
+        .after(serde_json::json!({
+            "status": decided.status,
+            "reviewNote": decided.review_note,
+            "accountStatus": appellant.account_status,
+        }))
+        .record(&state.db, admin, headers)
+        .await;
+    notifications::suspension_appeal_reviewed(state, &appellant, &decided).await;
+    Ok(Json(to_admin_appeal_response(decided, appellant)))
+}
+
+fn to_admin_appeal_response(
+    appeal: suspension_appeal::Model,
+    user: user::Model,
+) -> AdminAppealResponse {
+    AdminAppealResponse {
+        reviewer_id: appeal.reviewer_id,
+        appeal: appeal.into(),
+        user: AppealUserResponse {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            account_status: user.account_status,
+        },
+    }
+}
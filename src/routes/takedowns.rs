@@ -0,0 +1,471 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    EntityTrait, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait,
+    TransactionTrait, sea_query::JoinType,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    audit,
+    auth::middleware::{AdminUser, AuthUser, ModeratorUser},
+    entities::{copyright_notice, game, game_takedown, scheduled_publish, user},
+    error::{AppError, code},
+    notifications,
+    routes::games::{PaginatedResponse, REMOVED_BY_MODERATION, find_active_game},
+    state::AppState,
+};
+
+/// `game_takedown.appeal_status` of an appeal no admin has decided yet.
+const PENDING: &str = "pending";
+
+/// Longest takedown reason a moderator may give, in characters.
+const MAX_REASON_LENGTH: usize = 1000;
+
+/// Shortest appeal a creator may send, in characters.
+const MIN_APPEAL_LENGTH: usize = 20;
+
+/// Longest appeal a creator may send, in characters.
+const MAX_APPEAL_LENGTH: usize = 2000;
+
+/// Longest note a reviewer may leave on an appeal, in characters.
+const MAX_REVIEW_NOTE_LENGTH: usize = 1000;
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TakedownRequest {
+    /// Why the game is being removed, shown to its creator.
+    reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppealRequest {
+    /// The creator's case for restoring the game.
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewAppealRequest {
+    /// Shown to the creator, e.g. why the appeal was denied.
+    note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppealsQuery {
+    /// Only appeals with this status; defaults to pending appeals.
+    status: Option<String>,
+    #[serde(default)]
+    offset: u64,
+    #[serde(default = "default_appeals_limit")]
+    limit: u64,
+}
+
+const fn default_appeals_limit() -> u64 {
+    50
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TakedownResponse {
+    id: Uuid,
+    game_id: Uuid,
+    reason: String,
+    created_at: String,
+    appeal_message: Option<String>,
+    appealed_at: Option<String>,
+    appeal_status: Option<String>,
+    appeal_review_note: Option<String>,
+    appeal_reviewed_at: Option<String>,
+}
+
+impl From<game_takedown::Model> for TakedownResponse {
+    fn from(takedown: game_takedown::Model) -> Self {
+        Self {
+            id: takedown.id,
+            game_id: takedown.game_id,
+            reason: takedown.reason,
+            created_at: takedown.created_at.to_rfc3339(),
+            appeal_message: takedown.appeal_message,
+            appealed_at: takedown.appealed_at.map(|t| t.to_rfc3339()),
+            appeal_status: takedown.appeal_status,
+            appeal_review_note: takedown.appeal_review_note,
+            appeal_reviewed_at: takedown.appeal_reviewed_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TakedownGameResponse {
+    id: Uuid,
+    title: String,
+    slug: String,
+    owner_id: Uuid,
+}
+
+/// A takedown as admins see it, with the game and who decided what.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AdminTakedownResponse {
+    #[serde(flatten)]
+    takedown: TakedownResponse,
+    game: TakedownGameResponse,
+    moderator_id: Option<Uuid>,
+    appeal_reviewer_id: Option<Uuid>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `POST /admin/games/{id}/takedown` — Remove a game from the platform. It stays in its
+/// creator's library but cannot be played, listed or re-published unless an appeal is
+/// approved.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if the game does not exist, [`AppError::Conflict`] if it is
+/// already removed, [`AppError::BadRequest`] if the reason is missing or too long, or
+/// [`AppError`] if a database operation fails.
+pub async fn take_down_game(
+    State(state): State<AppState>,
+    ModeratorUser(moderator): ModeratorUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(body): Json<TakedownRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let reason = body.reason.trim().to_string();
+    let length = reason.chars().count();
+    if !(1..=MAX_REASON_LENGTH).contains(&length) {
+        return Err(AppError::BadRequest(format!(
+            "Reason must be between 1 and {MAX_REASON_LENGTH} characters."
+        )));
+    }
+
+    let game = find_active_game(&state.db, id).await?;
+    if game.status == REMOVED_BY_MODERATION {
+        return Err(AppError::Conflict(
+            "This game has already been removed.".to_string(),
+        ));
+    }
+
+    let txn = state.db.begin().await?;
+    let (game, takedown) = remove_game(&txn, game, moderator.id, reason).await?;
+    txn.commit().await?;
+
+    audit::Event::new("game.takedown", "game", id)
+        .before(serde_json::json!({ "status": takedown.previous_status }))
+        .after(serde_json::json!({ "status": game.status, "reason": takedown.reason }))
+        .record(&state.db, &moderator, &headers)
+        .await;
+    notifications::game_removed(&state, &game, &takedown).await;
+    Ok((StatusCode::CREATED, Json(TakedownResponse::from(takedown))))
+}
+
+/// `GET /games/{id}/takedown` — Why the caller's game was removed, and where its appeal
+/// stands.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if the game does not exist, is not the caller's, or was
+/// never removed, or [`AppError`] if a database query fails.
+pub async fn get_takedown(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let game = find_active_game(&state.db, id).await?;
+    if game.owner_id != user.id {
+        return Err(AppError::NotFound("Game not found".to_string()));
+    }
+
+    let takedown = latest_takedown(&state.db, &game)
+        .await?
+        .ok_or_else(|| AppError::NotFound("This game has not been removed.".to_string()))?;
+    Ok(Json(TakedownResponse::from(takedown)))
+}
+
+/// `POST /games/{id}/appeal` — Ask an admin to restore the caller's removed game. Each
+/// takedown can be appealed once.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if the game does not exist, is not the caller's, or is not
+/// currently removed, [`AppError::Conflict`] if the takedown was already appealed,
+/// [`AppError::Unprocessable`] if the game was removed for a copyright notice, which is
+/// answered with a counter-notice instead, [`AppError::BadRequest`] if the message is too
+/// short or too long, or [`AppError`] if a database operation fails.
+pub async fn appeal_takedown(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<AppealRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let message = body.message.trim().to_string();
+    let length = message.chars().count();
+    if !(MIN_APPEAL_LENGTH..=MAX_APPEAL_LENGTH).contains(&length) {
+        return Err(AppError::BadRequest(format!(
+            "Message must be between {MIN_APPEAL_LENGTH} and {MAX_APPEAL_LENGTH} characters."
+        )));
+    }
+
+    let game = find_active_game(&state.db, id).await?;
+    if game.owner_id != user.id {
+        return Err(AppError::NotFound("Game not found".to_string()));
+    }
+    let takedown = if game.status == REMOVED_BY_MODERATION {
+        latest_takedown(&state.db, &game).await?
+    } else {
+        None
+    }
+    .ok_or_else(|| AppError::NotFound("This game has not been removed.".to_string()))?;
+    if takedown.appeal_status.is_some() {
+        return Err(AppError::Conflict(
+            "This takedown has already been appealed.".to_string(),
+        ));
+    }
+    let copyright = copyright_notice::Entity::find()
+        .filter(copyright_notice::Column::TakedownId.eq(takedown.id))
+        .count(&state.db)
+        .await?
+        > 0;
+    if copyright {
+        return Err(AppError::Unprocessable(
+            code::COUNTER_NOTICE_REQUIRED,
+            "Games removed for a copyright notice are restored through a counter-notice."
+                .to_string(),
+        ));
+    }
+
+    let mut active: game_takedown::ActiveModel = takedown.into();
+    active.appeal_message = Set(Some(message));
+    active.appealed_at = Set(Some(Utc::now().fixed_offset()));
+    active.appeal_status = Set(Some(PENDING.to_string()));
+    let appealed = active.update(&state.db).await?;
+
+    Ok((StatusCode::CREATED, Json(TakedownResponse::from(appealed))))
+}
+
+/// `GET /admin/appeals` — Takedown appeals, oldest first. Lists pending appeals unless
+/// `status` says otherwise.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the database query fails.
+pub async fn list_appeals(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    Query(query): Query<AppealsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let status = query.status.unwrap_or_else(|| PENDING.to_string());
+    let find = game_takedown::Entity::find()
+        .filter(game_takedown::Column::AppealStatus.eq(status))
+        .join(JoinType::InnerJoin, game_takedown::Relation::Game.def())
+        .select_also(game::Entity);
+    let total = find.clone().count(&state.db).await?;
+
+    let rows = find
+        .order_by_asc(game_takedown::Column::AppealedAt)
+        .offset(query.offset)
+        .limit(query.limit)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(PaginatedResponse {
+        data: rows
+            .into_iter()
+            .filter_map(|(takedown, game)| game.map(|g| to_admin_takedown_response(takedown, g)))
+            .collect(),
+        total,
+        offset: query.offset,
+        limit: query.limit,
+    }))
+}
+
+/// `POST /admin/appeals/{id}/approve` — Approve a pending appeal, restoring the game to the
+/// status it had before the takedown.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if there is no pending appeal with that takedown ID,
+/// [`AppError::BadRequest`] if the note is too long, or [`AppError`] if a database operation
+/// fails.
+pub async fn approve_appeal(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(body): Json<ReviewAppealRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    decide(&state, id, &admin, &headers, "approved", body.note).await
+}
+
+/// `POST /admin/appeals/{id}/deny` — Deny a pending appeal. The game stays removed.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if there is no pending appeal with that takedown ID,
+/// [`AppError::BadRequest`] if the note is too long, or [`AppError`] if a database operation
+/// fails.
+pub async fn deny_appeal(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(body): Json<ReviewAppealRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    decide(&state, id, &admin, &headers, "denied", body.note).await
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Mark `game` removed by `moderator_id` for `reason`, cancelling any scheduled publish.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if a database operation fails.
+pub async fn remove_game<C: ConnectionTrait>(
+    db: &C,
+    game: game::Model,
+    moderator_id: Uuid,
+    reason: String,
+) -> Result<(game::Model, game_takedown::Model), AppError> {
+    let now = Utc::now().fixed_offset();
+    let takedown = game_takedown::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        game_id: Set(game.id),
+        moderator_id: Set(Some(moderator_id)),
+        reason: Set(reason),
+        previous_status: Set(game.status.clone()),
+        created_at: Set(now),
+        appeal_message: Set(None),
+        appealed_at: Set(None),
+        appeal_status: Set(None),
+        appeal_reviewer_id: Set(None),
+        appeal_review_note: Set(None),
+        appeal_reviewed_at: Set(None),
+    }
+    .insert(db)
+    .await?;
+
+    scheduled_publish::Entity::delete_by_id(game.id)
+        .exec(db)
+        .await?;
+
+    let mut active: game::ActiveModel = game.into();
+    active.status = Set(REMOVED_BY_MODERATION.to_string());
+    active.updated_at = Set(now);
+    let game = active.update(db).await?;
+
+    Ok((game, takedown))
+}
+
+/// The most recent takedown of `game`, if it was ever removed.
+async fn latest_takedown(
+    db: &DatabaseConnection,
+    game: &game::Model,
+) -> Result<Option<game_takedown::Model>, AppError> {
+    Ok(game
+        .find_related(game_takedown::Entity)
+        .order_by_desc(game_takedown::Column::CreatedAt)
+        .one(db)
+        .await?)
+}
+
+/// Move the pending appeal of takedown `id` to `status`, restoring the game when approved.
+async fn decide(
+    state: &AppState,
+    id: Uuid,
+    admin: &user::Model,
+    headers: &HeaderMap,
+    status: &str,
+    note: Option<String>,
+) -> Result<Json<AdminTakedownResponse>, AppError> {
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if note
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_REVIEW_NOTE_LENGTH)
+    {
+        return Err(AppError::BadRequest(format!(
+            "Note must be at most {MAX_REVIEW_NOTE_LENGTH} characters."
+        )));
+    }
+
+    let txn = state.db.begin().await?;
+    let takedown = game_takedown::Entity::find_by_id(id)
+        .filter(game_takedown::Column::AppealStatus.eq(PENDING))
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Appeal not found.".to_string()))?;
+    let mut game = game::Entity::find_by_id(takedown.game_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Game not found".to_string()))?;
+
+    let previous_status = takedown.previous_status.clone();
+    let mut active: game_takedown::ActiveModel = takedown.into();
+    active.appeal_status = Set(Some(status.to_string()));
+    active.appeal_reviewer_id = Set(Some(admin.id));
+    active.appeal_review_note = Set(note);
+    active.appeal_reviewed_at = Set(Some(Utc::now().fixed_offset()));
+    let decided = active.update(&txn).await?;
+
+    if status == "approved" && game.status == REMOVED_BY_MODERATION {
+        let mut active: game::ActiveModel = game.into();
+        active.status = Set(previous_status);
+        active.updated_at = Set(Utc::now().fixed_offset());
+        game = active.update(&txn).await?;
+    }
+
+    txn.commit().await?;
+
+    let action = if status == "approved" {
+        "appeal.approve"
+    } else {
+        "appeal.deny"
+    };
+    audit::Event::new(action, "takedown", id)
+        .before(serde_json::json!({ "appealStatus": PENDING }))
+        .after(serde_json::json!({
+            "appealStatus": decided.appeal_status,
+            "appealReviewNote": decided.appeal_review_note,
+            "gameStatus": game.status,
+        }))
+        .record(&state.db, admin, headers)
+        .await;
+    notifications::appeal_reviewed(state, &game, &decided).await;
+    Ok(Json(to_admin_takedown_response(decided, game)))
+}
+
+fn to_admin_takedown_response(
+    takedown: game_takedown::Model,
+    game: game::Model,
+) -> AdminTakedownResponse {
+    AdminTakedownResponse {
+        moderator_id: takedown.moderator_id,
+        appeal_reviewer_id: takedown.appeal_reviewer_id,
+        takedown: takedown.into(),
+        game: TakedownGameResponse {
+            id: game.id,
+            title: game.title,
+            slug: game.slug,
+            owner_id: game.owner_id,
+        },
+    }
+}
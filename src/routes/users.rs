@@ -1,20 +1,38 @@
-use axum::extract::{Multipart, Path, State};
+use std::collections::BTreeMap;
+
+use axum::extract::{Multipart, Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
-use axum::routing::{get, patch, post};
+use axum::routing::{delete, get, patch, post};
 use axum::{Json, Router};
 use chrono::Utc;
 use sea_orm::ActiveValue::Set;
-use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter};
+use sea_orm::sea_query::{Expr, JoinType, OnConflict};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait, Select,
+};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::auth::middleware::AuthUser;
-use crate::auth::password;
-use crate::entities::{auth_provider, user};
+use crate::auth::{generate_verification_token, hash_verification_token, password};
+use crate::avatars;
+use crate::entities::{
+    achievement, auth_provider, email_change, follow, game, login_event, refresh_token, user,
+    user_achievement,
+};
 use crate::error::AppError;
-use crate::routes::games;
+use crate::jobs::account_deletion;
+use crate::routes::games::{OptionalAuth, PaginatedResponse, PaginationQuery};
+use crate::routes::{
+    account_merge, api_tokens, collections, data_export, feed, friends, games, notifications,
+    preferences, sessions, verification,
+};
 use crate::state::AppState;
+use crate::storage;
+use crate::text_moderation;
+use crate::xp;
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Router
@@ -27,12 +45,99 @@ pub fn router() -> Router<AppState> {
             "/me",
             get(get_me).patch(update_me).delete(deactivate_account),
         )
+        .route("/me/delete", post(schedule_account_deletion))
+        .route("/me/delete/cancel", post(cancel_account_deletion))
+        .route("/me/merge", post(account_merge::merge_account))
         .route("/me/avatar", post(upload_avatar).delete(delete_avatar))
         .route("/me/username", patch(change_username))
         .route("/me/email", patch(change_email))
         .route("/me/games", get(games::list_my_games))
+        .route("/me/games/bulk", post(games::bulk_update_my_games))
+        .route("/me/games/trash", get(games::list_my_trash))
+        .route("/me/favorites", get(games::list_my_favorites))
+        .route("/me/storage", get(get_my_storage))
+        .route("/me/xp", get(get_my_xp))
+        .route("/me/privacy", get(get_my_privacy).patch(update_my_privacy))
+        .route(
+            "/me/preferences",
+            get(preferences::get_my_preferences).put(preferences::put_my_preferences),
+        )
+        .route(
+            "/me/verification-request",
+            get(verification::get_my_verification_request).post(verification::request_verification),
+        )
+        .route(
+            "/me/sessions",
+            get(list_my_sessions).delete(revoke_all_my_sessions),
+        )
+        .route("/me/sessions/{id}", delete(revoke_my_session))
+        .route("/me/hosted-sessions", get(sessions::list_hosted_sessions))
+        .route("/me/security/events", get(list_my_security_events))
+        .route(
+            "/me/tokens",
+            get(api_tokens::list_my_tokens).post(api_tokens::create_my_token),
+        )
+        .route(
+            "/me/tokens/{id}",
+            patch(api_tokens::update_my_token).delete(api_tokens::delete_my_token),
+        )
+        .route("/me/export", get(data_export::request_my_export))
+        .route("/me/export/{id}", get(data_export::get_my_export))
+        .route(
+            "/me/export/{id}/download",
+            get(data_export::download_my_export),
+        )
+        .route("/me/feed", get(feed::get_my_feed))
+        .route("/me/friends", get(friends::list_my_friends))
+        .route("/me/friends/{username}", delete(friends::remove_friend))
+        .route("/me/friend-requests", get(friends::list_my_friend_requests))
+        .route(
+            "/me/friend-requests/{id}/accept",
+            post(friends::accept_friend_request),
+        )
+        .route(
+            "/me/friend-requests/{id}/decline",
+            post(friends::decline_friend_request),
+        )
+        .route(
+            "/me/notifications",
+            get(notifications::list_my_notifications),
+        )
+        .route(
+            "/me/notifications/{id}/read",
+            post(notifications::mark_notification_read),
+        )
+        .route(
+            "/me/notifications/read-all",
+            post(notifications::mark_all_notifications_read),
+        )
+        .route("/me/notifications/ws", get(notifications::notifications_ws))
+        .route(
+            "/me/notification-preferences",
+            get(notifications::get_my_notification_preferences)
+                .patch(notifications::update_my_notification_preferences),
+        )
+        .route(
+            "/me/collections",
+            get(collections::list_my_collections).post(collections::create_my_collection),
+        )
+        .route(
+            "/me/collections/{id}",
+            patch(collections::update_my_collection).delete(collections::delete_my_collection),
+        )
         .route("/{username}", get(get_public_profile))
         .route("/{username}/games", get(games::list_user_games))
+        .route(
+            "/{username}/follow",
+            post(follow_user).delete(unfollow_user),
+        )
+        .route(
+            "/{username}/friend-request",
+            post(friends::send_friend_request),
+        )
+        .route("/{username}/followers", get(list_followers))
+        .route("/{username}/following", get(list_following))
+        .route("/{username}/achievements", get(list_achievements))
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -50,12 +155,18 @@ struct MeResponse {
     display_name: Option<String>,
     avatar_url: Option<String>,
     bio: Option<String>,
+    location: Option<String>,
+    website_url: Option<String>,
+    pronouns: Option<String>,
+    social_links: Option<serde_json::Value>,
     email_verified: bool,
+    verified: bool,
     role: String,
     subscription_plan: String,
     subscription_expires_at: Option<String>,
     account_status: String,
     last_login_at: Option<String>,
+    deletion_scheduled_at: Option<String>,
     auth_providers: Vec<AuthProviderInfo>,
 }
 
@@ -73,6 +184,12 @@ struct UpdateMeRequest {
     display_name: Option<String>,
     bio: Option<String>,
     avatar_url: Option<String>,
+    /// An empty string clears the location, as it does the website and pronouns.
+    location: Option<String>,
+    website_url: Option<String>,
+    pronouns: Option<String>,
+    /// Platform name → profile URL; replaces all existing links, and `{}` clears them.
+    social_links: Option<BTreeMap<String, String>>,
 }
 
 #[derive(Serialize)]
@@ -81,10 +198,18 @@ struct PublicProfileResponse {
     id: Uuid,
     username: String,
     display_name: Option<String>,
-    avatar_url: Option<String>,
+    avatar_url: String,
     bio: Option<String>,
+    location: Option<String>,
+    website_url: Option<String>,
+    pronouns: Option<String>,
+    social_links: Option<serde_json::Value>,
+    verified: bool,
     created_at: String,
-    stats: PublicStats,
+    follower_count: u64,
+    level: i32,
+    /// `None` when the user hides their games from others.
+    stats: Option<PublicStats>,
 }
 
 #[derive(Serialize)]
@@ -92,6 +217,9 @@ struct PublicProfileResponse {
 struct PublicStats {
     games_published: u64,
     total_play_count: u64,
+    followers: u64,
+    /// Average rating across the reviews of all published games; `None` without reviews.
+    avg_rating: Option<f64>,
 }
 
 #[derive(Serialize)]
@@ -124,6 +252,8 @@ struct ChangeEmailResponse {
     message: String,
     email: String,
     email_verified: bool,
+    /// Address the account will switch to once the current address confirms the change.
+    pending_email: String,
 }
 
 #[derive(Deserialize)]
@@ -131,6 +261,119 @@ struct DeactivateRequest {
     password: Option<String>,
 }
 
+#[derive(Deserialize)]
+struct ScheduleDeletionRequest {
+    password: Option<String>,
+    /// `"delete"` removes the user's games with the account; `"keep"` leaves them up, credited
+    /// to the anonymized account.
+    games: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeletionScheduleResponse {
+    deletion_scheduled_at: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StorageUsageResponse {
+    plan: String,
+    used_bytes: u64,
+    quota_bytes: u64,
+}
+
+/// A signed-in device, backed by its current (unrevoked) refresh token.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionResponse {
+    id: Uuid,
+    user_agent: Option<String>,
+    ip_address: Option<String>,
+    signed_in_at: String,
+    last_refreshed_at: String,
+    expires_at: String,
+}
+
+/// A user in a follower or following listing.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FollowResponse {
+    id: Uuid,
+    username: String,
+    display_name: Option<String>,
+    avatar_url: String,
+    followed_at: String,
+}
+
+/// The authenticated user's XP, level and progress to the next level.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct XpResponse {
+    xp: i64,
+    level: i32,
+    /// Total XP the current level started at.
+    current_level_xp: i64,
+    /// Total XP the next level starts at, or `None` at the top level.
+    next_level_xp: Option<i64>,
+    breakdown: xp::Breakdown,
+}
+
+/// Who can see what of the authenticated user.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(clippy::struct_excessive_bools)]
+struct PrivacyResponse {
+    /// Keep the profile out of search engines and the user's games out of search.
+    hide_from_search: bool,
+    /// Hide the published-game list and game stats from everyone else.
+    hide_games: bool,
+    /// Keep the user's new games and collections out of followers' feeds.
+    hide_activity: bool,
+}
+
+impl From<&user::Model> for PrivacyResponse {
+    fn from(user: &user::Model) -> Self {
+        Self {
+            hide_from_search: user.hide_from_search,
+            hide_games: user.hide_games,
+            hide_activity: user.hide_activity,
+        }
+    }
+}
+
+/// Fields left out are unchanged.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(clippy::struct_field_names)]
+struct UpdatePrivacyRequest {
+    hide_from_search: Option<bool>,
+    hide_games: Option<bool>,
+    hide_activity: Option<bool>,
+}
+
+/// An achievement a user has earned.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AchievementResponse {
+    id: String,
+    name: String,
+    description: String,
+    awarded_at: String,
+}
+
+/// A sign-in attempt on the user's account.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SecurityEventResponse {
+    id: Uuid,
+    provider: String,
+    outcome: String,
+    ip_address: Option<String>,
+    user_agent: Option<String>,
+    created_at: String,
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Helpers
 // ─────────────────────────────────────────────────────────────────────────────
@@ -164,12 +407,18 @@ async fn build_me_response(
         display_name: user_model.display_name.clone(),
         avatar_url: user_model.avatar_url.clone(),
         bio: user_model.bio.clone(),
+        location: user_model.location.clone(),
+        website_url: user_model.website_url.clone(),
+        pronouns: user_model.pronouns.clone(),
+        social_links: user_model.social_links.clone(),
         email_verified: user_model.email_verified,
+        verified: user_model.verified,
         role: user_model.role.clone(),
         subscription_plan: user_model.subscription_plan.clone(),
         subscription_expires_at: user_model.subscription_expires_at.map(|t| t.to_rfc3339()),
         account_status: user_model.account_status.clone(),
         last_login_at: user_model.last_login_at.map(|t| t.to_rfc3339()),
+        deletion_scheduled_at: user_model.deletion_scheduled_at.map(|t| t.to_rfc3339()),
         auth_providers,
     })
 }
@@ -190,6 +439,167 @@ fn validate_bio(bio: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Most links a profile can list in `social_links`.
+const MAX_SOCIAL_LINKS: usize = 10;
+
+/// Validate an optional profile text field's length.
+fn validate_profile_text(value: &str, what: &str, max_chars: usize) -> Result<(), String> {
+    if value.chars().count() > max_chars {
+        return Err(format!("{what} must be at most {max_chars} characters."));
+    }
+    Ok(())
+}
+
+/// Validate a link shown on a profile: an absolute `http` or `https` URL.
+fn validate_profile_url(url: &str, what: &str) -> Result<(), String> {
+    let valid = url.len() <= 2048
+        && reqwest::Url::parse(url)
+            .is_ok_and(|u| matches!(u.scheme(), "http" | "https") && u.host_str().is_some());
+    if !valid {
+        return Err(format!("{what} must be an http(s) URL."));
+    }
+    Ok(())
+}
+
+/// Validate `social_links`: a few links keyed by lowercase platform names such as `"github"`.
+fn validate_social_links(links: &BTreeMap<String, String>) -> Result<(), String> {
+    if links.len() > MAX_SOCIAL_LINKS {
+        return Err(format!(
+            "At most {MAX_SOCIAL_LINKS} social links are allowed."
+        ));
+    }
+    for (platform, url) in links {
+        let valid_name = (1..=32).contains(&platform.len())
+            && platform
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-');
+        if !valid_name {
+            return Err(format!(
+                "Invalid social platform name \"{platform}\": use 1-32 lowercase letters, digits \
+                 or hyphens."
+            ));
+        }
+        validate_profile_url(url, &format!("The {platform} link"))?;
+    }
+    Ok(())
+}
+
+/// Trim an optional profile field, mapping blanks to `None`.
+fn blank_to_none(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Find an active user by username, as shown on public pages.
+pub async fn find_public_user(
+    db: &sea_orm::DatabaseConnection,
+    username: &str,
+) -> Result<user::Model, AppError> {
+    user::Entity::find()
+        .filter(user::Column::Username.eq(username))
+        .filter(user::Column::DeletedAt.is_null())
+        .filter(user::Column::AccountStatus.eq("active"))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found.".to_string()))
+}
+
+/// Follows of `user_id` whose other side is an active user: its followers when `followers` is
+/// set, otherwise the creators it follows.
+#[must_use]
+pub fn active_follows(user_id: Uuid, followers: bool) -> Select<follow::Entity> {
+    let (by, other) = if followers {
+        (follow::Column::FolloweeId, follow::Relation::Follower)
+    } else {
+        (follow::Column::FollowerId, follow::Relation::Followee)
+    };
+    follow::Entity::find()
+        .filter(by.eq(user_id))
+        .join(JoinType::InnerJoin, other.def())
+        .filter(user::Column::DeletedAt.is_null())
+        .filter(user::Column::AccountStatus.eq("active"))
+}
+
+/// One page of [`active_follows`], most recent first.
+async fn list_follows(
+    db: &sea_orm::DatabaseConnection,
+    user_id: Uuid,
+    followers: bool,
+    pagination: PaginationQuery,
+) -> Result<PaginatedResponse<FollowResponse>, AppError> {
+    let find = active_follows(user_id, followers);
+    let total = find.clone().count(db).await?;
+
+    let rows = find
+        .select_also(user::Entity)
+        .order_by_desc(follow::Column::CreatedAt)
+        .offset(pagination.offset)
+        .limit(pagination.limit)
+        .all(db)
+        .await?;
+
+    Ok(PaginatedResponse {
+        data: rows
+            .into_iter()
+            .filter_map(|(f, u)| {
+                u.map(|u| FollowResponse {
+                    id: u.id,
+                    avatar_url: avatars::url_or_fallback(u.avatar_url, &u.username),
+                    username: u.username,
+                    display_name: u.display_name,
+                    followed_at: f.created_at.to_rfc3339(),
+                })
+            })
+            .collect(),
+        total,
+        offset: pagination.offset,
+        limit: pagination.limit,
+    })
+}
+
+/// Profile stats of `user_id`, aggregated over the counters cached on their public published
+/// games rather than over individual plays and reviews.
+async fn public_stats(
+    db: &sea_orm::DatabaseConnection,
+    user_id: Uuid,
+    followers: u64,
+) -> Result<PublicStats, AppError> {
+    let (games, plays, reviews, rating_total): (i64, i64, i64, f64) = game::Entity::find()
+        .select_only()
+        .column_as(game::Column::Id.count(), "games")
+        .column_as(
+            Expr::cust("CAST(COALESCE(SUM(play_count), 0) AS BIGINT)"),
+            "plays",
+        )
+        .column_as(
+            Expr::cust("CAST(COALESCE(SUM(review_count), 0) AS BIGINT)"),
+            "reviews",
+        )
+        .column_as(
+            Expr::cust("CAST(COALESCE(SUM(avg_rating * review_count), 0) AS DOUBLE PRECISION)"),
+            "rating_total",
+        )
+        .filter(game::Column::OwnerId.eq(user_id))
+        .filter(game::Column::Status.eq("published"))
+        .filter(game::Column::Visibility.eq("public"))
+        .filter(game::Column::DeletedAt.is_null())
+        .into_tuple()
+        .one(db)
+        .await?
+        .unwrap_or_default();
+
+    // Weighted by review count, so this is the mean of every review's rating
+    #[allow(clippy::cast_precision_loss)]
+    let avg_rating = (reviews > 0).then(|| rating_total / reviews as f64);
+
+    Ok(PublicStats {
+        games_published: u64::try_from(games).unwrap_or_default(),
+        total_play_count: u64::try_from(plays).unwrap_or_default(),
+        followers,
+        avg_rating,
+    })
+}
+
 /// Check if the user has an email auth provider and verify password when required.
 async fn verify_account_ownership(
     db: &sea_orm::DatabaseConnection,
@@ -243,8 +653,14 @@ async fn update_me(
 ) -> Result<Json<MeResponse>, AppError> {
     let mut active: user::ActiveModel = user_model.clone().into();
 
+    let mut flag = None;
     if let Some(ref display_name) = body.display_name {
         validate_display_name(display_name).map_err(AppError::BadRequest)?;
+        flag = text_moderation::screen(
+            &state.config.text_moderation,
+            &[("display name", display_name)],
+        )
+        .await?;
         active.display_name = Set(Some(display_name.clone()));
     }
 
@@ -257,7 +673,37 @@ async fn update_me(
         active.avatar_url = Set(Some(avatar_url.clone()));
     }
 
-    let changed = body.display_name.is_some() || body.bio.is_some() || body.avatar_url.is_some();
+    if let Some(ref location) = body.location {
+        validate_profile_text(location, "Location", 100).map_err(AppError::BadRequest)?;
+        active.location = Set(blank_to_none(location));
+    }
+
+    if let Some(ref website_url) = body.website_url {
+        let website_url = blank_to_none(website_url);
+        if let Some(ref url) = website_url {
+            validate_profile_url(url, "Website URL").map_err(AppError::BadRequest)?;
+        }
+        active.website_url = Set(website_url);
+    }
+
+    if let Some(ref pronouns) = body.pronouns {
+        validate_profile_text(pronouns, "Pronouns", 40).map_err(AppError::BadRequest)?;
+        active.pronouns = Set(blank_to_none(pronouns));
+    }
+
+    if let Some(ref social_links) = body.social_links {
+        validate_social_links(social_links).map_err(AppError::BadRequest)?;
+        active.social_links =
+            Set((!social_links.is_empty()).then(|| serde_json::json!(social_links)));
+    }
+
+    let changed = body.display_name.is_some()
+        || body.bio.is_some()
+        || body.avatar_url.is_some()
+        || body.location.is_some()
+        || body.website_url.is_some()
+        || body.pronouns.is_some()
+        || body.social_links.is_some();
 
     let updated_user = if changed {
         let now = Utc::now().fixed_offset();
@@ -269,6 +715,7 @@ async fn update_me(
     } else {
         user_model
     };
+    text_moderation::report(&state.db, flag, "user", updated_user.id).await;
 
     let response = build_me_response(&state.db, &updated_user).await?;
     Ok(Json(response))
@@ -371,7 +818,8 @@ async fn change_username(
 ) -> Result<Json<UsernameResponse>, AppError> {
     let new_username = body.new_username.trim().to_string();
 
-    password::validate_username(&new_username).map_err(AppError::BadRequest)?;
+    password::validate_username(&new_username, &state.config.username_blocklist)
+        .map_err(AppError::BadRequest)?;
 
     // Check uniqueness
     let existing = user::Entity::find()
@@ -400,16 +848,30 @@ async fn change_username(
     }))
 }
 
+/// How long a staged email change waits for confirmation.
+const EMAIL_CHANGE_TTL_HOURS: i64 = 24;
+
 /// `PATCH /api/v1/users/me/email`
+///
+/// Stages the change rather than applying it: a confirmation token goes to the current address
+/// and a verification token to the new one, and the login email only switches once the current
+/// address confirms via `POST /api/v1/auth/email-change/confirm`. A new request replaces any
+/// pending one.
 async fn change_email(
     State(state): State<AppState>,
     AuthUser(user_model): AuthUser,
     Json(body): Json<ChangeEmailRequest>,
-) -> Result<Json<ChangeEmailResponse>, AppError> {
+) -> Result<(StatusCode, Json<ChangeEmailResponse>), AppError> {
     let new_email = body.new_email.trim().to_lowercase();
 
     password::validate_email(&new_email).map_err(AppError::BadRequest)?;
 
+    if new_email == user_model.email {
+        return Err(AppError::BadRequest(
+            "That is already your email address.".to_string(),
+        ));
+    }
+
     // Check uniqueness
     let existing = user::Entity::find()
         .filter(user::Column::Email.eq(&new_email))
@@ -417,9 +879,7 @@ async fn change_email(
         .await
         .map_err(|e| AppError::Internal(e.into()))?;
 
-    if let Some(existing_user) = existing
-        && existing_user.id != user_model.id
-    {
+    if existing.is_some() {
         return Err(AppError::Conflict("Email is already in use.".to_string()));
     }
 
@@ -427,54 +887,228 @@ async fn change_email(
     let user_id = user_model.id;
     verify_account_ownership(&state.db, user_id, body.password.as_deref()).await?;
 
-    // Update email and reset verification
-    let now = Utc::now().fixed_offset();
-    let verification_token = Uuid::new_v4().to_string();
-    let token_expires_at = Utc::now() + chrono::Duration::hours(24);
-
-    let mut active: user::ActiveModel = user_model.into();
-    active.email = Set(new_email.clone());
-    active.email_verified = Set(false);
-    active.updated_at = Set(now);
-    active
-        .update(&state.db)
+    email_change::Entity::delete_many()
+        .filter(email_change::Column::UserId.eq(user_id))
+        .exec(&state.db)
         .await
         .map_err(|e| AppError::Internal(e.into()))?;
 
-    // Update verification token on email provider (if exists)
-    let email_provider = auth_provider::Entity::find()
-        .filter(auth_provider::Column::UserId.eq(user_id))
-        .filter(auth_provider::Column::Provider.eq("email"))
-        .one(&state.db)
-        .await
-        .map_err(|e| AppError::Internal(e.into()))?;
-
-    if let Some(provider) = email_provider {
-        let mut active_provider: auth_provider::ActiveModel = provider.into();
-        active_provider.provider_id = Set(new_email.clone());
-        active_provider.provider_email = Set(Some(new_email.clone()));
-        active_provider.verification_token = Set(Some(verification_token.clone()));
-        active_provider.token_expires_at = Set(Some(token_expires_at.fixed_offset()));
-        active_provider
-            .update(&state.db)
-            .await
-            .map_err(|e| AppError::Internal(e.into()))?;
+    let now = Utc::now();
+    let confirmation_token = generate_verification_token();
+    let verification_token = generate_verification_token();
+
+    email_change::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id),
+        new_email: Set(new_email.clone()),
+        confirmation_token: Set(hash_verification_token(&confirmation_token)),
+        verification_token: Set(hash_verification_token(&verification_token)),
+        expires_at: Set((now + chrono::Duration::hours(EMAIL_CHANGE_TTL_HOURS)).fixed_offset()),
+        created_at: Set(now.fixed_offset()),
     }
+    .insert(&state.db)
+    .await
+    .map_err(|e| AppError::Internal(e.into()))?;
 
+    tracing::info!(
+        email = %user_model.email,
+        token = %confirmation_token,
+        "Email change confirmation pending (email sending not yet implemented)"
+    );
     tracing::info!(
         email = %new_email,
         token = %verification_token,
-        "Email changed, verification email pending (email sending not yet implemented)"
+        "Email change verification pending (email sending not yet implemented)"
     );
 
-    Ok(Json(ChangeEmailResponse {
-        message: "Email updated. A verification email has been sent to the new address."
-            .to_string(),
-        email: new_email,
-        email_verified: false,
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ChangeEmailResponse {
+            message: "A confirmation link has been sent to your current address. \
+                      Your email will change once it is confirmed."
+                .to_string(),
+            email: user_model.email,
+            email_verified: user_model.email_verified,
+            pending_email: new_email,
+        }),
+    ))
+}
+
+/// `GET /api/v1/users/me/xp` — The authenticated user's XP by source and the level it earns.
+async fn get_my_xp(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+) -> Result<Json<XpResponse>, AppError> {
+    let breakdown = xp::breakdown(&state.db, user_model.id).await?;
+    let total = breakdown.total();
+    let level = xp::level_for(total);
+
+    Ok(Json(XpResponse {
+        xp: total,
+        level,
+        current_level_xp: xp::level_xp(level).unwrap_or(0),
+        next_level_xp: xp::level_xp(level + 1),
+        breakdown,
     }))
 }
 
+/// `GET /api/v1/users/me/privacy`
+async fn get_my_privacy(AuthUser(user_model): AuthUser) -> Json<PrivacyResponse> {
+    Json(PrivacyResponse::from(&user_model))
+}
+
+/// `PATCH /api/v1/users/me/privacy`
+async fn update_my_privacy(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+    Json(body): Json<UpdatePrivacyRequest>,
+) -> Result<Json<PrivacyResponse>, AppError> {
+    let mut active: user::ActiveModel = user_model.into();
+    if let Some(hide_from_search) = body.hide_from_search {
+        active.hide_from_search = Set(hide_from_search);
+    }
+    if let Some(hide_games) = body.hide_games {
+        active.hide_games = Set(hide_games);
+    }
+    if let Some(hide_activity) = body.hide_activity {
+        active.hide_activity = Set(hide_activity);
+    }
+    active.updated_at = Set(Utc::now().fixed_offset());
+    let updated = active.update(&state.db).await?;
+
+    Ok(Json(PrivacyResponse::from(&updated)))
+}
+
+/// `GET /api/v1/users/me/storage`
+async fn get_my_storage(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+) -> Result<Json<StorageUsageResponse>, AppError> {
+    let used_bytes = storage::quota::usage(&state.db, user_model.id)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    Ok(Json(StorageUsageResponse {
+        quota_bytes: storage::quota::limit_for_plan(&user_model.subscription_plan),
+        plan: user_model.subscription_plan,
+        used_bytes,
+    }))
+}
+
+/// `GET /api/v1/users/me/sessions`
+///
+/// Lists the devices the user is signed in on, most recently refreshed first.
+async fn list_my_sessions(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+) -> Result<Json<Vec<SessionResponse>>, AppError> {
+    let now = Utc::now().fixed_offset();
+    let tokens = refresh_token::Entity::find()
+        .filter(refresh_token::Column::UserId.eq(user_model.id))
+        .filter(refresh_token::Column::RevokedAt.is_null())
+        .filter(refresh_token::Column::ExpiresAt.gt(now))
+        .order_by_desc(refresh_token::Column::CreatedAt)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(
+        tokens
+            .into_iter()
+            .map(|t| SessionResponse {
+                id: t.id,
+                user_agent: t.user_agent,
+                ip_address: t.ip_address,
+                signed_in_at: t.signed_in_at.unwrap_or(t.created_at).to_rfc3339(),
+                last_refreshed_at: t.created_at.to_rfc3339(),
+                expires_at: t.expires_at.to_rfc3339(),
+            })
+            .collect(),
+    ))
+}
+
+/// `GET /api/v1/users/me/security/events`
+///
+/// Lists sign-in attempts on the user's account, successful or not, newest first. Events are
+/// kept for [`crate::auth::login_events::RETENTION_DAYS`] days.
+async fn list_my_security_events(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<SecurityEventResponse>>, AppError> {
+    let find = login_event::Entity::find().filter(login_event::Column::UserId.eq(user_model.id));
+    let total = find.clone().count(&state.db).await?;
+
+    let events = find
+        .order_by_desc(login_event::Column::CreatedAt)
+        .offset(pagination.offset)
+        .limit(pagination.limit)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(PaginatedResponse {
+        data: events
+            .into_iter()
+            .map(|e| SecurityEventResponse {
+                id: e.id,
+                provider: e.provider,
+                outcome: e.outcome,
+                ip_address: e.ip_address,
+                user_agent: e.user_agent,
+                created_at: e.created_at.to_rfc3339(),
+            })
+            .collect(),
+        total,
+        offset: pagination.offset,
+        limit: pagination.limit,
+    }))
+}
+
+/// `DELETE /api/v1/users/me/sessions/{id}`
+///
+/// Signs a device out by revoking its refresh token. Access tokens already issued to it stay
+/// valid until they expire.
+async fn revoke_my_session(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    let revoked = refresh_token::Entity::update_many()
+        .col_expr(
+            refresh_token::Column::RevokedAt,
+            sea_orm::sea_query::Expr::value(Utc::now().fixed_offset()),
+        )
+        .filter(refresh_token::Column::Id.eq(id))
+        .filter(refresh_token::Column::UserId.eq(user_model.id))
+        .filter(refresh_token::Column::RevokedAt.is_null())
+        .exec(&state.db)
+        .await?;
+
+    if revoked.rows_affected == 0 {
+        return Err(AppError::NotFound("Session not found.".to_string()));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `DELETE /api/v1/users/me/sessions`
+///
+/// Signs out everywhere by revoking every refresh token the user holds.
+async fn revoke_all_my_sessions(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+) -> Result<StatusCode, AppError> {
+    refresh_token::Entity::update_many()
+        .col_expr(
+            refresh_token::Column::RevokedAt,
+            sea_orm::sea_query::Expr::value(Utc::now().fixed_offset()),
+        )
+        .filter(refresh_token::Column::UserId.eq(user_model.id))
+        .filter(refresh_token::Column::RevokedAt.is_null())
+        .exec(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// `DELETE /api/v1/users/me`
 async fn deactivate_account(
     State(state): State<AppState>,
@@ -497,35 +1131,212 @@ async fn deactivate_account(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// `POST /api/v1/users/me/delete`
+///
+/// Schedules permanent deletion of the account once the grace period has passed; until then
+/// it can be cancelled. The deletion itself is carried out by the account deletion job.
+async fn schedule_account_deletion(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+    Json(body): Json<ScheduleDeletionRequest>,
+) -> Result<(StatusCode, Json<DeletionScheduleResponse>), AppError> {
+    if !account_deletion::GAME_POLICIES.contains(&body.games.as_str()) {
+        return Err(AppError::BadRequest(
+            "games must be \"delete\" or \"keep\".".to_string(),
+        ));
+    }
+    if user_model.deletion_scheduled_at.is_some() {
+        return Err(AppError::Conflict(
+            "Account deletion is already scheduled.".to_string(),
+        ));
+    }
+
+    verify_account_ownership(&state.db, user_model.id, body.password.as_deref()).await?;
+
+    let now = Utc::now().fixed_offset();
+    let scheduled_at = now + chrono::Duration::days(account_deletion::GRACE_PERIOD_DAYS);
+    let mut active: user::ActiveModel = user_model.into();
+    active.deletion_scheduled_at = Set(Some(scheduled_at));
+    active.deletion_game_policy = Set(Some(body.games));
+    active.updated_at = Set(now);
+    active.update(&state.db).await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(DeletionScheduleResponse {
+            deletion_scheduled_at: scheduled_at.to_rfc3339(),
+        }),
+    ))
+}
+
+/// `POST /api/v1/users/me/delete/cancel`
+async fn cancel_account_deletion(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+) -> Result<StatusCode, AppError> {
+    if user_model.deletion_scheduled_at.is_none() {
+        return Err(AppError::NotFound(
+            "No account deletion is scheduled.".to_string(),
+        ));
+    }
+
+    let mut active: user::ActiveModel = user_model.into();
+    active.deletion_scheduled_at = Set(None);
+    active.deletion_game_policy = Set(None);
+    active.updated_at = Set(Utc::now().fixed_offset());
+    active.update(&state.db).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// `GET /api/v1/users/{username}`
+///
+/// Game stats are left out for everyone but the user when they hide their games, and profiles
+/// hidden from search carry `X-Robots-Tag: noindex`.
 async fn get_public_profile(
     State(state): State<AppState>,
+    OptionalAuth(viewer): OptionalAuth,
     Path(username): Path<String>,
 ) -> Result<Response, AppError> {
-    let user_model = user::Entity::find()
-        .filter(user::Column::Username.eq(&username))
-        .filter(user::Column::DeletedAt.is_null())
-        .filter(user::Column::AccountStatus.eq("active"))
-        .one(&state.db)
-        .await
-        .map_err(|e| AppError::Internal(e.into()))?
-        .ok_or_else(|| AppError::NotFound("User not found.".to_string()))?;
+    let user_model = find_public_user(&state.db, &username).await?;
+    let follower_count = active_follows(user_model.id, true).count(&state.db).await?;
 
-    // Game stats are stubbed until the Game entity is implemented (M0.4.0)
-    let profile_stats = PublicStats {
-        games_published: 0,
-        total_play_count: 0,
+    let is_self = viewer.is_some_and(|v| v.id == user_model.id);
+    let profile_stats = if user_model.hide_games && !is_self {
+        None
+    } else {
+        Some(public_stats(&state.db, user_model.id, follower_count).await?)
     };
+    let noindex = user_model.hide_from_search;
+    let level = xp::level_for(xp::breakdown(&state.db, user_model.id).await?.total());
 
     let response = PublicProfileResponse {
         id: user_model.id,
+        avatar_url: avatars::url_or_fallback(user_model.avatar_url, &user_model.username),
         username: user_model.username,
         display_name: user_model.display_name,
-        avatar_url: user_model.avatar_url,
         bio: user_model.bio,
+        location: user_model.location,
+        website_url: user_model.website_url,
+        pronouns: user_model.pronouns,
+        social_links: user_model.social_links,
+        verified: user_model.verified,
         created_at: user_model.created_at.to_rfc3339(),
+        follower_count,
+        level,
         stats: profile_stats,
     };
 
+    if noindex {
+        return Ok(([("x-robots-tag", "noindex")], Json(response)).into_response());
+    }
     Ok(Json(response).into_response())
 }
+
+/// `POST /api/v1/users/{username}/follow`
+async fn follow_user(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+    Path(username): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let followee = find_public_user(&state.db, &username).await?;
+    if followee.id == user_model.id {
+        return Err(AppError::BadRequest(
+            "You cannot follow yourself.".to_string(),
+        ));
+    }
+
+    let new_follow = follow::ActiveModel {
+        follower_id: Set(user_model.id),
+        followee_id: Set(followee.id),
+        created_at: Set(Utc::now().fixed_offset()),
+    };
+
+    // Following twice is a no-op
+    let inserted = follow::Entity::insert(new_follow)
+        .on_conflict(
+            OnConflict::columns([follow::Column::FollowerId, follow::Column::FolloweeId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(&state.db)
+        .await?;
+    if inserted > 0 {
+        crate::notifications::follower_gained(&state, followee.id, &user_model).await;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `DELETE /api/v1/users/{username}/follow`
+async fn unfollow_user(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+    Path(username): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let followee = user::Entity::find()
+        .filter(user::Column::Username.eq(&username))
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found.".to_string()))?;
+
+    follow::Entity::delete_by_id((user_model.id, followee.id))
+        .exec(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GET /api/v1/users/{username}/followers`
+async fn list_followers(
+    State(state): State<AppState>,
+    Path(username): Path<String>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<FollowResponse>>, AppError> {
+    let user_model = find_public_user(&state.db, &username).await?;
+    Ok(Json(
+        list_follows(&state.db, user_model.id, true, pagination).await?,
+    ))
+}
+
+/// `GET /api/v1/users/{username}/following`
+async fn list_following(
+    State(state): State<AppState>,
+    Path(username): Path<String>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<FollowResponse>>, AppError> {
+    let user_model = find_public_user(&state.db, &username).await?;
+    Ok(Json(
+        list_follows(&state.db, user_model.id, false, pagination).await?,
+    ))
+}
+
+/// `GET /api/v1/users/{username}/achievements` — The achievements a user has earned, in
+/// catalog order.
+async fn list_achievements(
+    State(state): State<AppState>,
+    Path(username): Path<String>,
+) -> Result<Json<Vec<AchievementResponse>>, AppError> {
+    let user_model = find_public_user(&state.db, &username).await?;
+
+    let earned = user_achievement::Entity::find()
+        .filter(user_achievement::Column::UserId.eq(user_model.id))
+        .find_also_related(achievement::Entity)
+        .order_by_asc(achievement::Column::SortOrder)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(
+        earned
+            .into_iter()
+            .filter_map(|(awarded, a)| {
+                a.map(|a| AchievementResponse {
+                    id: a.id,
+                    name: a.name,
+                    description: a.description,
+                    awarded_at: awarded.awarded_at.to_rfc3339(),
+                })
+            })
+            .collect(),
+    ))
+}
@@ -0,0 +1,365 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait, TransactionTrait,
+    sea_query::JoinType,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    audit,
+    auth::middleware::{AdminUser, AuthUser},
+    entities::{user, verification_request},
+    error::AppError,
+    notifications,
+    routes::games::PaginatedResponse,
+    state::AppState,
+};
+
+/// `verification_request.status` of a request no admin has decided yet.
+const PENDING: &str = "pending";
+
+/// Shortest justification an application may have, in characters.
+const MIN_JUSTIFICATION_LENGTH: usize = 20;
+
+/// Longest justification an application may have, in characters.
+const MAX_JUSTIFICATION_LENGTH: usize = 2000;
+
+/// Longest note a reviewer may leave, in characters.
+const MAX_REVIEW_NOTE_LENGTH: usize = 1000;
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateVerificationRequest {
+    /// Why the applicant should be verified, e.g. who they are and what they have made.
+    justification: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewVerificationRequest {
+    /// Shown to the applicant, e.g. why the request was denied.
+    note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationRequestsQuery {
+    /// Only requests with this status; defaults to pending requests.
+    status: Option<String>,
+    #[serde(default)]
+    offset: u64,
+    #[serde(default = "default_verification_requests_limit")]
+    limit: u64,
+}
+
+const fn default_verification_requests_limit() -> u64 {
+    50
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VerificationRequestResponse {
+    id: Uuid,
+    justification: String,
+    status: String,
+    review_note: Option<String>,
+    created_at: String,
+    reviewed_at: Option<String>,
+}
+
+impl From<verification_request::Model> for VerificationRequestResponse {
+    fn from(request: verification_request::Model) -> Self {
+        Self {
+            id: request.id,
+            justification: request.justification,
+            status: request.status,
+            review_note: request.review_note,
+            created_at: request.created_at.to_rfc3339(),
+            reviewed_at: request.reviewed_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ApplicantResponse {
+    id: Uuid,
+    username: String,
+    display_name: Option<String>,
+    avatar_url: Option<String>,
+}
+
+/// A request as admins see it, with who sent it.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AdminVerificationRequestResponse {
+    #[serde(flatten)]
+    request: VerificationRequestResponse,
+    user: ApplicantResponse,
+    reviewer_id: Option<Uuid>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `POST /users/me/verification-request` — Apply for the verified creator badge.
+///
+/// # Errors
+///
+/// Returns [`AppError::Forbidden`] for guest accounts, [`AppError::Conflict`] if the caller is
+/// already verified or has a pending request, [`AppError::BadRequest`] if the justification is
+/// too short or too long, or [`AppError`] if a database operation fails.
+pub async fn request_verification(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(body): Json<CreateVerificationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if user.role == "guest" {
+        return Err(AppError::Forbidden(
+            "Guest accounts cannot apply for verification.".to_string(),
+        ));
+    }
+    if user.verified {
+        return Err(AppError::Conflict("You are already verified.".to_string()));
+    }
+
+    let justification = body.justification.trim().to_string();
+    let length = justification.chars().count();
+    if !(MIN_JUSTIFICATION_LENGTH..=MAX_JUSTIFICATION_LENGTH).contains(&length) {
+        return Err(AppError::BadRequest(format!(
+            "Justification must be between {MIN_JUSTIFICATION_LENGTH} and \
+             {MAX_JUSTIFICATION_LENGTH} characters."
+        )));
+    }
+
+    let pending = verification_request::Entity::find()
+        .filter(verification_request::Column::UserId.eq(user.id))
+        .filter(verification_request::Column::Status.eq(PENDING))
+        .count(&state.db)
+        .await?;
+    if pending > 0 {
+        return Err(AppError::Conflict(
+            "You already have a pending verification request.".to_string(),
+        ));
+    }
+
+    let created = verification_request::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user.id),
+        justification: Set(justification),
+        status: Set(PENDING.to_string()),
+        reviewer_id: Set(None),
+        review_note: Set(None),
+        created_at: Set(Utc::now().fixed_offset()),
+        reviewed_at: Set(None),
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(VerificationRequestResponse::from(created)),
+    ))
+}
+
+/// `GET /users/me/verification-request` — The authenticated user's latest verification
+/// request.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if the caller never applied, or [`AppError`] if the database
+/// query fails.
+pub async fn get_my_verification_request(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    let latest = verification_request::Entity::find()
+        .filter(verification_request::Column::UserId.eq(user.id))
+        .order_by_desc(verification_request::Column::CreatedAt)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("No verification request found.".to_string()))?;
+
+    Ok(Json(VerificationRequestResponse::from(latest)))
+}
+
+/// `GET /admin/verification-requests` — Verification requests, oldest first so the queue is
+/// worked in order. Lists pending requests unless `status` says otherwise.
+///
+/// # Errors
+///
+/// Returns [`AppError`] if the database query fails.
+pub async fn list_verification_requests(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    Query(query): Query<VerificationRequestsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let status = query.status.unwrap_or_else(|| PENDING.to_string());
+    let find = verification_request::Entity::find()
+        .filter(verification_request::Column::Status.eq(status))
+        .join(
+            JoinType::InnerJoin,
+            verification_request::Relation::Applicant.def(),
+        )
+        .select_also(user::Entity);
+    let total = find.clone().count(&state.db).await?;
+
+    let rows = find
+        .order_by_asc(verification_request::Column::CreatedAt)
+        .offset(query.offset)
+        .limit(query.limit)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(PaginatedResponse {
+        data: rows
+            .into_iter()
+            .filter_map(|(request, applicant)| {
+                applicant.map(|u| AdminVerificationRequestResponse {
+                    reviewer_id: request.reviewer_id,
+                    request: request.into(),
+                    user: ApplicantResponse {
+                        id: u.id,
+                        username: u.username,
+                        display_name: u.display_name,
+                        avatar_url: u.avatar_url,
+                    },
+                })
+            })
+            .collect(),
+        total,
+        offset: query.offset,
+        limit: query.limit,
+    }))
+}
+
+/// `POST /admin/verification-requests/{id}/approve` — Approve a pending request, giving the
+/// applicant the verified badge.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if there is no pending request with that ID,
+/// [`AppError::BadRequest`] if the note is too long, or [`AppError`] if a database operation
+/// fails.
+pub async fn approve_verification_request(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(body): Json<ReviewVerificationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let decided = decide(&state.db, id, &admin, "approved", body.note).await?;
+    audit_decision(
+        &state.db,
+        "verification.approve",
+        &decided,
+        &admin,
+        &headers,
+    )
+    .await;
+    notifications::verification_reviewed(&state, &decided).await;
+    Ok(Json(VerificationRequestResponse::from(decided)))
+}
+
+/// `POST /admin/verification-requests/{id}/deny` — Deny a pending request.
+///
+/// # Errors
+///
+/// Returns [`AppError::NotFound`] if there is no pending request with that ID,
+/// [`AppError::BadRequest`] if the note is too long, or [`AppError`] if a database operation
+/// fails.
+pub async fn deny_verification_request(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(body): Json<ReviewVerificationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let decided = decide(&state.db, id, &admin, "denied", body.note).await?;
+    audit_decision(&state.db, "verification.deny", &decided, &admin, &headers).await;
+    notifications::verification_reviewed(&state, &decided).await;
+    Ok(Json(VerificationRequestResponse::from(decided)))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Record an admin's decision on a verification request in the audit log.
+async fn audit_decision(
+    db: &DatabaseConnection,
+    action: &str,
+    decided: &verification_request::Model,
+    admin: &user::Model,
+    headers: &HeaderMap,
+) {
+    audit::Event::new(action, "verification_request", decided.id)
+        .before(serde_json::json!({ "status": PENDING }))
+        .after(serde_json::json!({
+            "status": decided.status,
+            "reviewNote": decided.review_note,
+        }))
+        .record(db, admin, headers)
+        .await;
+}
+
+/// Move pending request `id` to `status`, verifying the applicant when it is approved.
+async fn decide(
+    db: &DatabaseConnection,
+    id: Uuid,
+    admin: &user::Model,
+    status: &str,
+    note: Option<String>,
+) -> Result<verification_request::Model, AppError> {
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if note
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_REVIEW_NOTE_LENGTH)
+    {
+        return Err(AppError::BadRequest(format!(
+            "Note must be at most {MAX_REVIEW_NOTE_LENGTH} characters."
+        )));
+    }
+
+    let txn = db.begin().await?;
+    let request = verification_request::Entity::find_by_id(id)
+        .filter(verification_request::Column::Status.eq(PENDING))
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Verification request not found.".to_string()))?;
+
+    let applicant_id = request.user_id;
+    let mut active: verification_request::ActiveModel = request.into();
+    active.status = Set(status.to_string());
+    active.reviewer_id = Set(Some(admin.id));
+    active.review_note = Set(note);
+    active.reviewed_at = Set(Some(Utc::now().fixed_offset()));
+    let decided = active.update(&txn).await?;
+
+    if status == "approved" {
+        user::ActiveModel {
+            id: Set(applicant_id),
+            verified: Set(true),
+            updated_at: Set(Utc::now().fixed_offset()),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await?;
+    }
+
+    txn.commit().await?;
+    Ok(decided)
+}
@@ -0,0 +1,527 @@
+use std::time::Duration;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+};
+use chrono::Utc;
+use data_encoding::BASE64URL_NOPAD;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, sea_query::Expr,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::{
+        login_events,
+        middleware::AuthUser,
+        webauthn::{self, CeremonyError, RelyingParty},
+    },
+    entities::{user, webauthn_challenge, webauthn_credential},
+    error::AppError,
+    middleware::rate_limit::{self, RateLimitPolicy, RateLimiter},
+    routes::auth::complete_signin,
+    state::AppState,
+};
+
+/// Name shown by browsers when creating a passkey.
+const RP_NAME: &str = "AirCade";
+
+/// How long a ceremony may take before its challenge expires.
+const CHALLENGE_TTL: Duration = Duration::from_mins(5);
+
+/// Longest passkey label, in characters.
+const MAX_NAME_LENGTH: usize = 64;
+
+/// Passkey routes, merged into the `/auth` group, with `credentials` limiting sign-in.
+pub fn router(credentials: RateLimitPolicy) -> Router<AppState> {
+    let authenticate = Router::new()
+        .route("/webauthn/authenticate/options", post(authenticate_options))
+        .route("/webauthn/authenticate/verify", post(authenticate_verify))
+        .route_layer(axum::middleware::from_fn_with_state(
+            RateLimiter::new(credentials),
+            rate_limit::enforce,
+        ));
+
+    Router::new()
+        .merge(authenticate)
+        .route("/webauthn/register/options", post(register_options))
+        .route("/webauthn/register/verify", post(register_verify))
+        .route("/webauthn/credentials", get(list_credentials))
+        .route("/webauthn/credentials/{id}", delete(delete_credential))
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+/// `type` of every credential in the `WebAuthn` API.
+const PUBLIC_KEY: &str = "public-key";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RegistrationOptionsResponse {
+    challenge_id: Uuid,
+    /// `PublicKeyCredentialCreationOptions`, to pass to `navigator.credentials.create()`.
+    public_key: CreationOptions,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CreationOptions {
+    challenge: String,
+    rp: RpEntity,
+    user: UserEntity,
+    pub_key_cred_params: Vec<CredentialParameters>,
+    timeout: u128,
+    attestation: &'static str,
+    authenticator_selection: AuthenticatorSelection,
+    exclude_credentials: Vec<CredentialDescriptor>,
+}
+
+#[derive(Debug, Serialize)]
+struct RpEntity {
+    id: String,
+    name: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UserEntity {
+    /// The user's ID, base64url-encoded; authenticators return it as `userHandle`.
+    id: String,
+    name: String,
+    display_name: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CredentialParameters {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    alg: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthenticatorSelection {
+    resident_key: &'static str,
+    user_verification: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct CredentialDescriptor {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthenticationOptionsResponse {
+    challenge_id: Uuid,
+    /// `PublicKeyCredentialRequestOptions`, to pass to `navigator.credentials.get()`.
+    public_key: RequestOptions,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RequestOptions {
+    challenge: String,
+    rp_id: String,
+    timeout: u128,
+    user_verification: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RegisterVerifyRequest {
+    challenge_id: Uuid,
+    name: Option<String>,
+    credential: RegistrationCredential,
+}
+
+/// A `PublicKeyCredential` from `navigator.credentials.create()`, binary fields base64url-encoded.
+#[derive(Debug, Deserialize)]
+struct RegistrationCredential {
+    response: AttestationResponse,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AttestationResponse {
+    #[serde(rename = "clientDataJSON")]
+    client_data_json: String,
+    attestation_object: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthenticateVerifyRequest {
+    challenge_id: Uuid,
+    credential: AuthenticationCredential,
+}
+
+/// A `PublicKeyCredential` from `navigator.credentials.get()`, binary fields base64url-encoded.
+#[derive(Debug, Deserialize)]
+struct AuthenticationCredential {
+    id: String,
+    response: AssertionResponse,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AssertionResponse {
+    #[serde(rename = "clientDataJSON")]
+    client_data_json: String,
+    authenticator_data: String,
+    signature: String,
+    user_handle: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CredentialResponse {
+    id: Uuid,
+    name: String,
+    created_at: String,
+    last_used_at: Option<String>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// `POST /auth/webauthn/register/options` — Start registering a passkey for the current user.
+async fn register_options(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+) -> Result<Json<RegistrationOptionsResponse>, AppError> {
+    let rp = relying_party(&state)?;
+
+    let existing = webauthn_credential::Entity::find()
+        .filter(webauthn_credential::Column::UserId.eq(user_model.id))
+        .all(&state.db)
+        .await?;
+    let (challenge_id, challenge) =
+        create_challenge(&state.db, "register", Some(user_model.id)).await?;
+
+    Ok(Json(RegistrationOptionsResponse {
+        challenge_id,
+        public_key: CreationOptions {
+            challenge,
+            rp: RpEntity {
+                id: rp.id,
+                name: RP_NAME,
+            },
+            user: UserEntity {
+                id: BASE64URL_NOPAD.encode(user_model.id.as_bytes()),
+                display_name: user_model
+                    .display_name
+                    .unwrap_or_else(|| user_model.username.clone()),
+                name: user_model.email,
+            },
+            pub_key_cred_params: vec![CredentialParameters {
+                kind: PUBLIC_KEY,
+                alg: webauthn::COSE_ALG_ES256,
+            }],
+            timeout: CHALLENGE_TTL.as_millis(),
+            attestation: "none",
+            authenticator_selection: AuthenticatorSelection {
+                resident_key: "required",
+                user_verification: "required",
+            },
+            exclude_credentials: existing
+                .into_iter()
+                .map(|c| CredentialDescriptor {
+                    kind: PUBLIC_KEY,
+                    id: c.credential_id,
+                })
+                .collect(),
+        },
+    }))
+}
+
+/// `POST /auth/webauthn/register/verify` — Finish registering a passkey.
+async fn register_verify(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+    Json(req): Json<RegisterVerifyRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let rp = relying_party(&state)?;
+    let name = req
+        .name
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| "Passkey".to_string());
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Name must be at most {MAX_NAME_LENGTH} characters"
+        )));
+    }
+
+    let challenge =
+        consume_challenge(&state.db, req.challenge_id, "register", Some(user_model.id)).await?;
+
+    let response = req.credential.response;
+    let new_credential = webauthn::decode_base64url(&response.client_data_json)
+        .and_then(|client_data| {
+            let attestation = webauthn::decode_base64url(&response.attestation_object)?;
+            webauthn::verify_registration(&rp, &challenge, &client_data, &attestation)
+        })
+        .map_err(|CeremonyError(msg)| AppError::BadRequest(msg.to_string()))?;
+
+    let taken = webauthn_credential::Entity::find()
+        .filter(webauthn_credential::Column::CredentialId.eq(&new_credential.credential_id))
+        .one(&state.db)
+        .await?
+        .is_some();
+    if taken {
+        return Err(AppError::Conflict(
+            "This passkey is already registered".to_string(),
+        ));
+    }
+
+    let credential = webauthn_credential::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        user_id: ActiveValue::Set(user_model.id),
+        credential_id: ActiveValue::Set(new_credential.credential_id),
+        public_key: ActiveValue::Set(new_credential.public_key),
+        sign_count: ActiveValue::Set(i64::from(new_credential.sign_count)),
+        name: ActiveValue::Set(name),
+        created_at: ActiveValue::Set(Utc::now().into()),
+        last_used_at: ActiveValue::Set(None),
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(to_credential_response(credential)),
+    ))
+}
+
+/// `POST /auth/webauthn/authenticate/options` — Start a passwordless sign-in.
+///
+/// No user is named up front: the browser offers the passkeys it holds for this site.
+async fn authenticate_options(
+    State(state): State<AppState>,
+) -> Result<Json<AuthenticationOptionsResponse>, AppError> {
+    let rp = relying_party(&state)?;
+    let (challenge_id, challenge) = create_challenge(&state.db, "authenticate", None).await?;
+
+    Ok(Json(AuthenticationOptionsResponse {
+        challenge_id,
+        public_key: RequestOptions {
+            challenge,
+            rp_id: rp.id,
+            timeout: CHALLENGE_TTL.as_millis(),
+            user_verification: "required",
+        },
+    }))
+}
+
+/// `POST /auth/webauthn/authenticate/verify` — Finish a passwordless sign-in and issue the
+/// token pair.
+///
+/// A passkey already combines possession with user verification, so accounts with TOTP
+/// enabled are not asked for a code.
+async fn authenticate_verify(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<AuthenticateVerifyRequest>,
+) -> Result<Response, AppError> {
+    let rp = relying_party(&state)?;
+    let challenge = consume_challenge(&state.db, req.challenge_id, "authenticate", None).await?;
+    let unauthorized = |CeremonyError(msg)| AppError::Unauthorized(msg.to_string());
+
+    let credential_id = webauthn::decode_base64url(&req.credential.id).map_err(unauthorized)?;
+    let credential = webauthn_credential::Entity::find()
+        .filter(
+            webauthn_credential::Column::CredentialId.eq(BASE64URL_NOPAD.encode(&credential_id)),
+        )
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::Unauthorized("Unknown passkey".to_string()))?;
+
+    let response = req.credential.response;
+    if let Some(user_handle) = &response.user_handle {
+        let user_handle = webauthn::decode_base64url(user_handle).map_err(unauthorized)?;
+        if user_handle != credential.user_id.as_bytes() {
+            return Err(AppError::Unauthorized(
+                "Passkey does not belong to this user".to_string(),
+            ));
+        }
+    }
+
+    let client_data =
+        webauthn::decode_base64url(&response.client_data_json).map_err(unauthorized)?;
+    let authenticator_data =
+        webauthn::decode_base64url(&response.authenticator_data).map_err(unauthorized)?;
+    let signature = webauthn::decode_base64url(&response.signature).map_err(unauthorized)?;
+    let sign_count = webauthn::verify_authentication(
+        &rp,
+        &challenge,
+        &credential.public_key,
+        &client_data,
+        &authenticator_data,
+        &signature,
+    )
+    .map_err(unauthorized)?;
+
+    let stored_count = u32::try_from(credential.sign_count).unwrap_or(u32::MAX);
+    if !webauthn::sign_count_is_valid(stored_count, sign_count) {
+        tracing::warn!(credential = %credential.id, "Passkey signature counter went backwards");
+        return Err(AppError::Unauthorized(
+            "Passkey signature counter is out of date".to_string(),
+        ));
+    }
+
+    // Conditional on the counter we checked, so two concurrent sign-ins cannot both pass
+    let updated = webauthn_credential::Entity::update_many()
+        .col_expr(
+            webauthn_credential::Column::SignCount,
+            Expr::value(i64::from(sign_count)),
+        )
+        .col_expr(
+            webauthn_credential::Column::LastUsedAt,
+            Expr::value(Utc::now().fixed_offset()),
+        )
+        .filter(webauthn_credential::Column::Id.eq(credential.id))
+        .filter(webauthn_credential::Column::SignCount.eq(credential.sign_count))
+        .exec(&state.db)
+        .await?;
+    if updated.rows_affected == 0 {
+        return Err(AppError::Unauthorized(
+            "Passkey signature counter is out of date".to_string(),
+        ));
+    }
+
+    let user_model = user::Entity::find_by_id(credential.user_id)
+        .filter(user::Column::DeletedAt.is_null())
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::Unauthorized("Unknown passkey".to_string()))?;
+    if user_model.account_status == "suspended" {
+        return Err(AppError::Forbidden("Account is suspended.".to_string()));
+    }
+    if user_model.account_status == "deactivated" {
+        return Err(AppError::Forbidden("Account is deactivated.".to_string()));
+    }
+
+    let attempt = login_events::Attempt::new("webauthn", &headers);
+    Ok(complete_signin(&state, &attempt, user_model)
+        .await?
+        .into_response_for(&state, StatusCode::OK))
+}
+
+/// `GET /auth/webauthn/credentials` — List the current user's passkeys.
+async fn list_credentials(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+) -> Result<Json<Vec<CredentialResponse>>, AppError> {
+    let credentials = webauthn_credential::Entity::find()
+        .filter(webauthn_credential::Column::UserId.eq(user_model.id))
+        .order_by_asc(webauthn_credential::Column::CreatedAt)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(
+        credentials
+            .into_iter()
+            .map(to_credential_response)
+            .collect(),
+    ))
+}
+
+/// `DELETE /auth/webauthn/credentials/:id` — Remove one of the current user's passkeys.
+async fn delete_credential(
+    State(state): State<AppState>,
+    AuthUser(user_model): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    let deleted = webauthn_credential::Entity::delete_many()
+        .filter(webauthn_credential::Column::Id.eq(id))
+        .filter(webauthn_credential::Column::UserId.eq(user_model.id))
+        .exec(&state.db)
+        .await?;
+
+    if deleted.rows_affected == 0 {
+        return Err(AppError::NotFound("Passkey not found".to_string()));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn relying_party(state: &AppState) -> Result<RelyingParty, AppError> {
+    RelyingParty::from_frontend_url(&state.config.frontend_url)
+        .ok_or_else(|| AppError::Internal(anyhow::anyhow!("FRONTEND_URL is not a valid URL")))
+}
+
+/// Store a fresh challenge for `ceremony`, clearing out expired ones first.
+async fn create_challenge(
+    db: &DatabaseConnection,
+    ceremony: &str,
+    user_id: Option<Uuid>,
+) -> Result<(Uuid, String), AppError> {
+    let now = Utc::now();
+    webauthn_challenge::Entity::delete_many()
+        .filter(webauthn_challenge::Column::ExpiresAt.lt(now.fixed_offset()))
+        .exec(db)
+        .await?;
+
+    let challenge = webauthn_challenge::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        user_id: ActiveValue::Set(user_id),
+        ceremony: ActiveValue::Set(ceremony.to_string()),
+        challenge: ActiveValue::Set(webauthn::generate_challenge()),
+        created_at: ActiveValue::Set(now.into()),
+        expires_at: ActiveValue::Set((now + CHALLENGE_TTL).into()),
+    }
+    .insert(db)
+    .await?;
+
+    Ok((challenge.id, challenge.challenge))
+}
+
+/// Look up an unexpired challenge and delete it, so it can only be answered once.
+async fn consume_challenge(
+    db: &DatabaseConnection,
+    id: Uuid,
+    ceremony: &str,
+    user_id: Option<Uuid>,
+) -> Result<String, AppError> {
+    let invalid = || AppError::BadRequest("Invalid or expired challenge".to_string());
+
+    let challenge = webauthn_challenge::Entity::find_by_id(id)
+        .filter(webauthn_challenge::Column::Ceremony.eq(ceremony))
+        .filter(webauthn_challenge::Column::ExpiresAt.gt(Utc::now().fixed_offset()))
+        .one(db)
+        .await?
+        .filter(|c| c.user_id == user_id)
+        .ok_or_else(invalid)?;
+
+    let deleted = webauthn_challenge::Entity::delete_by_id(challenge.id)
+        .exec(db)
+        .await?;
+    if deleted.rows_affected == 0 {
+        return Err(invalid());
+    }
+    Ok(challenge.challenge)
+}
+
+fn to_credential_response(c: webauthn_credential::Model) -> CredentialResponse {
+    CredentialResponse {
+        id: c.id,
+        name: c.name,
+        created_at: c.created_at.to_string(),
+        last_used_at: c.last_used_at.map(|t| t.to_string()),
+    }
+}
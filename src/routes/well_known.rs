@@ -0,0 +1,24 @@
+use axum::extract::State;
+use axum::routing::get;
+use axum::{Json, Router};
+use jsonwebtoken::jwk::JwkSet;
+
+use crate::error::AppError;
+use crate::state::AppState;
+
+/// Root-level discovery documents: `/.well-known/...`.
+pub fn root_router() -> Router<AppState> {
+    Router::new().route("/.well-known/jwks.json", get(jwks))
+}
+
+/// `GET /.well-known/jwks.json`
+///
+/// Public keys that verify our tokens, for services that validate them without sharing a
+/// secret. Only available when tokens are signed with `JWT_PRIVATE_KEY`.
+async fn jwks(State(state): State<AppState>) -> Result<Json<JwkSet>, AppError> {
+    state
+        .jwt_keys
+        .jwks()
+        .map(Json)
+        .ok_or_else(|| AppError::NotFound("Tokens are not signed with a public key.".to_string()))
+}
@@ -0,0 +1,112 @@
+//! Rules for creating and joining sessions, independent of how a request arrived.
+
+use crate::error::AppError;
+
+/// Player limit of a session whose host did not choose one.
+pub const DEFAULT_MAX_PLAYERS: i32 = 8;
+
+/// Highest player limit a host may choose.
+pub const MAX_PLAYERS_LIMIT: i32 = 32;
+
+/// Longest display name, in bytes.
+pub const MAX_DISPLAY_NAME_LENGTH: usize = 100;
+
+/// The player limit of a new session, from the one the host asked for.
+#[must_use]
+pub fn max_players(requested: Option<i32>) -> i32 {
+    requested
+        .unwrap_or(DEFAULT_MAX_PLAYERS)
+        .clamp(1, MAX_PLAYERS_LIMIT)
+}
+
+/// Check that a session with `status` and `max_players` can take another player, given
+/// the `active_players` who have not left.
+///
+/// # Errors
+///
+/// Returns [`AppError::BadRequest`] if the session has ended or is full, or
+/// [`AppError::Internal`] if `max_players` is outside `1..=MAX_PLAYERS_LIMIT`, which
+/// [`max_players`] never stores.
+pub fn check_joinable(
+    status: &str,
+    max_players: i32,
+    active_players: usize,
+) -> Result<(), AppError> {
+    if status == "ended" {
+        return Err(AppError::BadRequest("Session has ended.".to_string()));
+    }
+    let max = usize::try_from(max_players)
+        .ok()
+        .filter(|_| (1..=MAX_PLAYERS_LIMIT).contains(&max_players))
+        .ok_or_else(|| {
+            AppError::Internal(anyhow::anyhow!(
+                "session has an invalid player limit: {max_players}"
+            ))
+        })?;
+    if active_players >= max {
+        return Err(AppError::BadRequest("Session is full.".to_string()));
+    }
+    Ok(())
+}
+
+/// The display name a player joins with, trimmed.
+///
+/// # Errors
+///
+/// Returns [`AppError::BadRequest`] if it is empty or longer than
+/// [`MAX_DISPLAY_NAME_LENGTH`].
+pub fn display_name(requested: &str) -> Result<String, AppError> {
+    let display_name = requested.trim();
+    if display_name.is_empty() || display_name.len() > MAX_DISPLAY_NAME_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Display name must be between 1 and {MAX_DISPLAY_NAME_LENGTH} characters."
+        )));
+    }
+    Ok(display_name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn player_limits_default_and_are_clamped() {
+        assert_eq!(max_players(None), DEFAULT_MAX_PLAYERS);
+        assert_eq!(max_players(Some(4)), 4);
+        assert_eq!(max_players(Some(0)), 1);
+        assert_eq!(max_players(Some(100)), MAX_PLAYERS_LIMIT);
+    }
+
+    #[test]
+    fn ended_and_full_sessions_cannot_be_joined() {
+        assert!(check_joinable("lobby", 2, 1).is_ok());
+        assert!(check_joinable("playing", 2, 1).is_ok());
+        assert!(matches!(
+            check_joinable("lobby", 2, 2),
+            Err(AppError::BadRequest(message)) if message == "Session is full."
+        ));
+        assert!(matches!(
+            check_joinable("ended", 2, 0),
+            Err(AppError::BadRequest(message)) if message == "Session has ended."
+        ));
+    }
+
+    #[test]
+    fn invalid_player_limits_are_rejected() {
+        assert!(check_joinable("lobby", DEFAULT_MAX_PLAYERS, 0).is_ok());
+        for invalid in [-1, 0, MAX_PLAYERS_LIMIT + 1] {
+            assert!(matches!(
+                check_joinable("lobby", invalid, 0),
+                Err(AppError::Internal(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn display_names_are_trimmed_and_bounded() {
+        assert_eq!(display_name("  Ada ").ok().as_deref(), Some("Ada"));
+        assert!(display_name("   ").is_err());
+        assert!(display_name(&"a".repeat(MAX_DISPLAY_NAME_LENGTH + 1)).is_err());
+        assert!(display_name(&"a".repeat(MAX_DISPLAY_NAME_LENGTH)).is_ok());
+    }
+}
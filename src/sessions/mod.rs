@@ -2,13 +2,40 @@
 //!
 //! Tracks active `WebSocket` connections per session, supporting the host (one per session)
 //! and players (many per session). Provides broadcast and targeted message delivery.
+//!
+//! The manager also keeps the most recent full `game_state` keyframe per session so that
+//! players who join late (or lose track of a delta chain) can be resynchronised without
+//! waiting for the host's next keyframe. It also remembers which players have sent input, so
+//! the first input of each player is persisted only once, and tallies each session's relay
+//! activity for the summary stored when it ends. Connection and message counters for the whole
+//! relay are exposed via [`SessionManager::metrics`].
+//!
+//! The rules for who may create and join sessions live in [`joining`].
 
-use std::sync::Arc;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 
 use dashmap::DashMap;
+use serde::Serialize;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
+pub mod joining;
+
+/// Client message types counted separately in [`SessionMetrics::messages_received_by_type`];
+/// anything else is counted as `other`.
+pub const MESSAGE_TYPES: [&str; 4] = [
+    "player_input",
+    "game_state_update",
+    "game_state_delta",
+    "state_resync",
+];
+
+/// Upper bounds, in bytes, of the buckets of [`SessionMetrics::message_sizes`]; larger
+/// messages fall in a final unbounded bucket.
+const MESSAGE_SIZE_BUCKETS: [u64; 6] = [128, 512, 2_048, 8_192, 32_768, 131_072];
+
 /// A message destined for a specific `WebSocket` client.
 pub type WsTx = mpsc::UnboundedSender<String>;
 
@@ -19,11 +46,190 @@ pub enum ClientRole {
     Player(Uuid),
 }
 
+/// The latest full game state broadcast by a host, ready to be re-sent to players.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateSnapshot {
+    /// Host-assigned sequence number of the keyframe (if the host uses delta encoding).
+    pub seq: Option<u64>,
+    /// Serialized `game_state` relay message.
+    pub message: String,
+}
+
+/// Point-in-time view of relay activity, exposed via the metrics endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionMetrics {
+    /// Sessions with at least one connected client.
+    pub active_sessions: usize,
+    /// Connected host (Console) clients.
+    pub connected_hosts: usize,
+    /// Connected player (Controller) clients.
+    pub connected_players: usize,
+    /// Messages handed to client channels since startup.
+    pub messages_relayed_total: u64,
+    /// Messages handed to client channels during the last full second.
+    pub messages_relayed_per_second: u64,
+    /// Sends that failed because the client channel was already closed.
+    pub dropped_sends_total: u64,
+    /// `WebSocket` connections opened since startup.
+    pub connects_total: u64,
+    /// `WebSocket` connections closed since startup.
+    pub disconnects_total: u64,
+    /// Messages received from clients since startup, by `type` (see [`MESSAGE_TYPES`]).
+    pub messages_received_by_type: BTreeMap<String, u64>,
+    /// Sizes of the messages received from clients since startup.
+    pub message_sizes: SizeHistogram,
+}
+
+/// Distribution of message sizes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeHistogram {
+    /// Messages per size range, smallest first.
+    pub buckets: Vec<SizeBucket>,
+    pub count: u64,
+    pub sum_bytes: u64,
+}
+
+/// Messages no larger than `le_bytes` but larger than the previous bucket's bound.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeBucket {
+    /// `None` for the last bucket, which has no upper bound.
+    pub le_bytes: Option<u64>,
+    pub count: u64,
+}
+
+/// Relay activity of one session, recorded on the session when it ends.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SessionTelemetry {
+    /// Most players connected at the same time.
+    pub peak_players: usize,
+    /// Messages handed to the session's clients.
+    pub messages_relayed: u64,
+    /// Times a player's `WebSocket` closed.
+    pub disconnects: u64,
+}
+
+/// Per-second message counts used to derive the relay rate.
+#[derive(Debug, Default)]
+struct RateWindow {
+    /// Unix second the `current` bucket belongs to.
+    second: i64,
+    current: u64,
+    previous: u64,
+}
+
+/// Relay counters shared by all clones of the manager.
+#[derive(Debug, Default)]
+struct RelayCounters {
+    relayed: AtomicU64,
+    dropped: AtomicU64,
+    rate: Mutex<RateWindow>,
+    connects: AtomicU64,
+    disconnects: AtomicU64,
+    /// Indexed like [`MESSAGE_TYPES`], with `other` last.
+    received: [AtomicU64; MESSAGE_TYPES.len() + 1],
+    /// Indexed like [`MESSAGE_SIZE_BUCKETS`], with the unbounded bucket last.
+    sizes: [AtomicU64; MESSAGE_SIZE_BUCKETS.len() + 1],
+    size_sum: AtomicU64,
+}
+
+impl RelayCounters {
+    fn record_relayed(&self) {
+        self.relayed.fetch_add(1, Ordering::Relaxed);
+
+        let now = chrono::Utc::now().timestamp();
+        if let Ok(mut window) = self.rate.lock() {
+            if window.second == now {
+                window.current += 1;
+            } else {
+                window.previous = if window.second + 1 == now {
+                    window.current
+                } else {
+                    0
+                };
+                window.second = now;
+                window.current = 1;
+            }
+        }
+    }
+
+    fn record_dropped(&self) {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_received(&self, message_type: &str, size: usize) {
+        let kind = MESSAGE_TYPES
+            .iter()
+            .position(|known| *known == message_type)
+            .unwrap_or(MESSAGE_TYPES.len());
+        self.received[kind].fetch_add(1, Ordering::Relaxed);
+
+        let size = u64::try_from(size).unwrap_or(u64::MAX);
+        let bucket = MESSAGE_SIZE_BUCKETS
+            .iter()
+            .position(|bound| size <= *bound)
+            .unwrap_or(MESSAGE_SIZE_BUCKETS.len());
+        self.sizes[bucket].fetch_add(1, Ordering::Relaxed);
+        self.size_sum.fetch_add(size, Ordering::Relaxed);
+    }
+
+    fn received_by_type(&self) -> BTreeMap<String, u64> {
+        MESSAGE_TYPES
+            .iter()
+            .chain(&["other"])
+            .zip(&self.received)
+            .map(|(kind, count)| ((*kind).to_string(), count.load(Ordering::Relaxed)))
+            .collect()
+    }
+
+    fn message_sizes(&self) -> SizeHistogram {
+        let buckets: Vec<SizeBucket> = MESSAGE_SIZE_BUCKETS
+            .iter()
+            .map(|bound| Some(*bound))
+            .chain([None])
+            .zip(&self.sizes)
+            .map(|(le_bytes, count)| SizeBucket {
+                le_bytes,
+                count: count.load(Ordering::Relaxed),
+            })
+            .collect();
+        SizeHistogram {
+            count: buckets.iter().map(|bucket| bucket.count).sum(),
+            sum_bytes: self.size_sum.load(Ordering::Relaxed),
+            buckets,
+        }
+    }
+
+    /// Messages relayed during the last complete second.
+    fn per_second(&self) -> u64 {
+        let now = chrono::Utc::now().timestamp();
+        self.rate.lock().map_or(0, |window| {
+            if window.second == now {
+                window.previous
+            } else if window.second + 1 == now {
+                window.current
+            } else {
+                0
+            }
+        })
+    }
+}
+
 /// Tracks all active `WebSocket` connections across all sessions.
 #[derive(Debug, Clone, Default)]
 pub struct SessionManager {
     /// `session_id` → map of `ClientRole` → sender channel
     sessions: Arc<DashMap<Uuid, DashMap<ClientRole, WsTx>>>,
+    /// `session_id` → latest full game state keyframe
+    snapshots: Arc<DashMap<Uuid, StateSnapshot>>,
+    /// Relay throughput counters
+    counters: Arc<RelayCounters>,
+    /// `session_id` → players who have sent input
+    active_players: Arc<DashMap<Uuid, HashSet<Uuid>>>,
+    /// `session_id` → relay activity since the first connection
+    telemetry: Arc<DashMap<Uuid, SessionTelemetry>>,
 }
 
 impl SessionManager {
@@ -32,19 +238,54 @@ impl SessionManager {
     pub fn new() -> Self {
         Self {
             sessions: Arc::new(DashMap::new()),
+            snapshots: Arc::new(DashMap::new()),
+            counters: Arc::new(RelayCounters::default()),
+            active_players: Arc::new(DashMap::new()),
+            telemetry: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Hand a message to a client channel of a session, recording the outcome in the relay
+    /// counters.
+    fn deliver(&self, session_id: Uuid, tx: &WsTx, message: &str) {
+        if tx.send(message.to_string()).is_ok() {
+            self.counters.record_relayed();
+            if let Some(mut telemetry) = self.telemetry.get_mut(&session_id) {
+                telemetry.messages_relayed += 1;
+            }
+        } else {
+            self.counters.record_dropped();
         }
     }
 
     /// Register a client connection for a session.
     pub fn register(&self, session_id: Uuid, role: ClientRole, tx: WsTx) {
-        self.sessions
-            .entry(session_id)
-            .or_default()
-            .insert(role, tx);
+        self.counters.connects.fetch_add(1, Ordering::Relaxed);
+        let is_player = matches!(role, ClientRole::Player(_));
+        let clients = self.sessions.entry(session_id).or_default();
+        clients.insert(role, tx);
+        let players = if is_player {
+            clients
+                .iter()
+                .filter(|entry| matches!(entry.key(), ClientRole::Player(_)))
+                .count()
+        } else {
+            0
+        };
+        drop(clients);
+
+        let mut telemetry = self.telemetry.entry(session_id).or_default();
+        telemetry.peak_players = telemetry.peak_players.max(players);
     }
 
     /// Unregister a client connection from a session.
     pub fn unregister(&self, session_id: Uuid, role: &ClientRole) {
+        self.counters.disconnects.fetch_add(1, Ordering::Relaxed);
+        if matches!(role, ClientRole::Player(_))
+            && let Some(mut telemetry) = self.telemetry.get_mut(&session_id)
+        {
+            telemetry.disconnects += 1;
+        }
         if let Some(clients) = self.sessions.get(&session_id) {
             clients.remove(role);
             if clients.is_empty() {
@@ -59,7 +300,7 @@ impl SessionManager {
         if let Some(clients) = self.sessions.get(&session_id)
             && let Some(tx) = clients.get(&ClientRole::Host)
         {
-            let _ = tx.send(message.to_string());
+            self.deliver(session_id, &tx, message);
         }
     }
 
@@ -68,7 +309,7 @@ impl SessionManager {
         if let Some(clients) = self.sessions.get(&session_id)
             && let Some(tx) = clients.get(&ClientRole::Player(player_id))
         {
-            let _ = tx.send(message.to_string());
+            self.deliver(session_id, &tx, message);
         }
     }
 
@@ -76,7 +317,7 @@ impl SessionManager {
     pub fn broadcast(&self, session_id: Uuid, message: &str) {
         if let Some(clients) = self.sessions.get(&session_id) {
             for entry in clients.iter() {
-                let _ = entry.value().send(message.to_string());
+                self.deliver(session_id, entry.value(), message);
             }
         }
     }
@@ -86,7 +327,7 @@ impl SessionManager {
         if let Some(clients) = self.sessions.get(&session_id) {
             for entry in clients.iter() {
                 if matches!(entry.key(), ClientRole::Player(_)) {
-                    let _ = entry.value().send(message.to_string());
+                    self.deliver(session_id, entry.value(), message);
                 }
             }
         }
@@ -95,6 +336,41 @@ impl SessionManager {
     /// Remove all connections for a session (used when ending a session).
     pub fn remove_session(&self, session_id: Uuid) {
         self.sessions.remove(&session_id);
+        self.snapshots.remove(&session_id);
+        self.active_players.remove(&session_id);
+        self.telemetry.remove(&session_id);
+    }
+
+    /// Relay activity of a session so far; all zero if nobody has connected to it.
+    #[must_use]
+    pub fn telemetry(&self, session_id: Uuid) -> SessionTelemetry {
+        self.telemetry
+            .get(&session_id)
+            .map(|entry| *entry.value())
+            .unwrap_or_default()
+    }
+
+    /// Note that a player sent input. Returns `true` only the first time for each player of
+    /// a session.
+    #[must_use]
+    pub fn record_input(&self, session_id: Uuid, player_id: Uuid) -> bool {
+        self.active_players
+            .entry(session_id)
+            .or_default()
+            .insert(player_id)
+    }
+
+    /// Store the latest full game state keyframe for a session, replacing any previous one.
+    pub fn store_snapshot(&self, session_id: Uuid, snapshot: StateSnapshot) {
+        self.snapshots.insert(session_id, snapshot);
+    }
+
+    /// Get the latest full game state keyframe for a session, if the host has sent one.
+    #[must_use]
+    pub fn latest_snapshot(&self, session_id: Uuid) -> Option<StateSnapshot> {
+        self.snapshots
+            .get(&session_id)
+            .map(|entry| entry.value().clone())
     }
 
     /// Check if a specific client is connected.
@@ -114,4 +390,37 @@ impl SessionManager {
                 .any(|entry| matches!(entry.key(), ClientRole::Player(_)))
         })
     }
+
+    /// Count a message received from a client, by its `type` and size in bytes.
+    pub fn record_received(&self, message_type: &str, size: usize) {
+        self.counters.record_received(message_type, size);
+    }
+
+    /// Snapshot the current connection gauges and relay counters.
+    #[must_use]
+    pub fn metrics(&self) -> SessionMetrics {
+        let mut connected_hosts = 0;
+        let mut connected_players = 0;
+        for clients in self.sessions.iter() {
+            for entry in clients.iter() {
+                match entry.key() {
+                    ClientRole::Host => connected_hosts += 1,
+                    ClientRole::Player(_) => connected_players += 1,
+                }
+            }
+        }
+
+        SessionMetrics {
+            active_sessions: self.sessions.len(),
+            connected_hosts,
+            connected_players,
+            messages_relayed_total: self.counters.relayed.load(Ordering::Relaxed),
+            messages_relayed_per_second: self.counters.per_second(),
+            dropped_sends_total: self.counters.dropped.load(Ordering::Relaxed),
+            connects_total: self.counters.connects.load(Ordering::Relaxed),
+            disconnects_total: self.counters.disconnects.load(Ordering::Relaxed),
+            messages_received_by_type: self.counters.received_by_type(),
+            message_sizes: self.counters.message_sizes(),
+        }
+    }
 }
@@ -1,6 +1,11 @@
+use std::sync::Arc;
+
 use sea_orm::DatabaseConnection;
 
+use crate::analytics::AnalyticsWriter;
+use crate::auth::jwt::KeySet;
 use crate::config::Config;
+use crate::notifications::NotificationHub;
 use crate::sessions::SessionManager;
 
 /// Shared application state available to all request handlers via Axum's `State` extractor.
@@ -8,5 +13,9 @@ use crate::sessions::SessionManager;
 pub struct AppState {
     pub db: DatabaseConnection,
     pub config: Config,
+    /// Token signing keys, parsed from `config` at startup.
+    pub jwt_keys: Arc<KeySet>,
     pub session_manager: SessionManager,
+    pub notification_hub: NotificationHub,
+    pub analytics: AnalyticsWriter,
 }
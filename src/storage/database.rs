@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use sea_orm::{ActiveValue, DatabaseConnection, EntityTrait, sea_query::OnConflict};
+
+use super::Storage;
+use crate::entities::storage_object;
+
+pub(super) const NAME: &str = "database";
+
+/// Stores blobs in the `storage_object` table. Convenient for development; use the
+/// filesystem or S3 backend in production to keep the database small.
+pub struct DatabaseStorage {
+    db: DatabaseConnection,
+}
+
+impl DatabaseStorage {
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl Storage for DatabaseStorage {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    async fn put(&self, key: &str, data: Vec<u8>, content_type: &str) -> anyhow::Result<()> {
+        let object = storage_object::ActiveModel {
+            key: ActiveValue::Set(key.to_string()),
+            created_at: ActiveValue::Set(chrono::Utc::now().into()),
+            content_type: ActiveValue::Set(content_type.to_string()),
+            data: ActiveValue::Set(data),
+        };
+
+        storage_object::Entity::insert(object)
+            .on_conflict(
+                OnConflict::column(storage_object::Column::Key)
+                    .update_columns([
+                        storage_object::Column::ContentType,
+                        storage_object::Column::Data,
+                    ])
+                    .to_owned(),
+            )
+            .exec_without_returning(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(storage_object::Entity::find_by_id(key)
+            .one(&self.db)
+            .await?
+            .map(|o| o.data))
+    }
+
+    async fn delete(&self, key: &str) -> anyhow::Result<()> {
+        storage_object::Entity::delete_by_id(key)
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+
+use async_trait::async_trait;
+
+use super::Storage;
+
+pub(super) const NAME: &str = "filesystem";
+
+/// Stores blobs as files under `UPLOAD_DIR/assets`, one file per key.
+pub struct FilesystemStorage {
+    root: PathBuf,
+}
+
+impl FilesystemStorage {
+    #[must_use]
+    pub fn new(upload_dir: &str) -> Self {
+        Self {
+            root: Path::new(upload_dir).join("assets"),
+        }
+    }
+
+    /// Resolve a key to a path, rejecting anything that could escape the root.
+    fn path_for(&self, key: &str) -> anyhow::Result<PathBuf> {
+        let relative = Path::new(key);
+        if relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            anyhow::bail!("Invalid storage key '{key}'");
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl Storage for FilesystemStorage {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    async fn put(&self, key: &str, data: Vec<u8>, _content_type: &str) -> anyhow::Result<()> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        // Write to a temporary file first so readers never see a partial object
+        let tmp = path.with_extension("tmp");
+        tokio::fs::write(&tmp, data).await?;
+        tokio::fs::rename(&tmp, &path).await?;
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        match tokio::fs::read(self.path_for(key)?).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> anyhow::Result<()> {
+        match tokio::fs::remove_file(self.path_for(key)?).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Pluggable blob storage for uploaded game assets.
+//!
+//! The backend is chosen with `STORAGE_BACKEND` (see [`StorageBackend`]). Each `game_asset`
+//! row records the backend and key its bytes were written to, so assets stay readable after
+//! the configured backend changes. Rows written before this existed are `inline`: their
+//! bytes still live in `game_asset.file_data` until [`crate::jobs::storage_backfill`] moves them.
+
+mod database;
+mod filesystem;
+pub mod quota;
+mod s3;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sea_orm::DatabaseConnection;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+pub use database::DatabaseStorage;
+pub use filesystem::FilesystemStorage;
+pub use s3::S3Storage;
+
+use crate::config::{Config, StorageBackend};
+use crate::entities::game_asset;
+
+/// `game_asset.storage_backend` value for bytes kept in `game_asset.file_data`.
+pub const INLINE_BACKEND: &str = "inline";
+
+/// A key/value blob store.
+#[async_trait]
+pub trait Storage: Send + Sync {
+    /// Name recorded in `game_asset.storage_backend`.
+    fn name(&self) -> &'static str;
+
+    /// Store `data` under `key`, replacing any existing object.
+    async fn put(&self, key: &str, data: Vec<u8>, content_type: &str) -> anyhow::Result<()>;
+
+    /// Fetch the object stored under `key`, if any.
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>>;
+
+    /// Remove the object stored under `key`. Missing objects are not an error.
+    async fn delete(&self, key: &str) -> anyhow::Result<()>;
+}
+
+/// Build the storage backend selected in the configuration.
+#[must_use]
+pub fn configured(config: &Config, db: &DatabaseConnection) -> Arc<dyn Storage> {
+    match config.storage_backend {
+        StorageBackend::Database => Arc::new(DatabaseStorage::new(db.clone())),
+        StorageBackend::Filesystem => Arc::new(FilesystemStorage::new(&config.upload_dir)),
+        StorageBackend::S3 => Arc::new(S3Storage::new(config)),
+    }
+}
+
+/// Build the backend named by a `game_asset.storage_backend` value.
+///
+/// Returns `None` for `inline` and unknown names.
+#[must_use]
+pub fn by_name(name: &str, config: &Config, db: &DatabaseConnection) -> Option<Arc<dyn Storage>> {
+    match name {
+        database::NAME => Some(Arc::new(DatabaseStorage::new(db.clone()))),
+        filesystem::NAME => Some(Arc::new(FilesystemStorage::new(&config.upload_dir))),
+        s3::NAME => Some(Arc::new(S3Storage::new(config))),
+        _ => None,
+    }
+}
+
+/// Object key for an asset's bytes.
+#[must_use]
+pub fn asset_key(game_id: Uuid, asset_id: Uuid) -> String {
+    format!("games/{game_id}/{asset_id}")
+}
+
+/// Hex-encoded SHA-256 of `data`, stored in `game_asset.content_hash`.
+#[must_use]
+pub fn content_hash(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+/// Read an asset's bytes from wherever they are stored.
+///
+/// # Errors
+///
+/// Returns an error if the backend is unknown, the object is missing, or the read fails.
+pub async fn read_asset(
+    asset: &game_asset::Model,
+    config: &Config,
+    db: &DatabaseConnection,
+) -> anyhow::Result<Vec<u8>> {
+    if asset.storage_backend == INLINE_BACKEND {
+        return Ok(asset.file_data.clone());
+    }
+
+    let storage = by_name(&asset.storage_backend, config, db)
+        .ok_or_else(|| anyhow::anyhow!("Unknown storage backend '{}'", asset.storage_backend))?;
+    let key = asset
+        .storage_key
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("Asset {} has no storage key", asset.id))?;
+
+    storage
+        .get(key)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Object '{key}' is missing from {}", storage.name()))
+}
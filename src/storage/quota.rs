@@ -0,0 +1,55 @@
+use std::collections::HashSet;
+
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QuerySelect, RelationTrait,
+    sea_query::JoinType,
+};
+use uuid::Uuid;
+
+use crate::entities::{game, game_asset};
+
+const MB: u64 = 1024 * 1024;
+
+/// Total asset storage allowed on the free plan (also used for unknown plans).
+pub const FREE_QUOTA_BYTES: u64 = 50 * MB;
+
+/// Total asset storage allowed on the pro plan.
+pub const PRO_QUOTA_BYTES: u64 = 1024 * MB;
+
+/// Asset storage allowed for a `user.subscription_plan`.
+#[must_use]
+pub fn limit_for_plan(plan: &str) -> u64 {
+    match plan {
+        "pro" => PRO_QUOTA_BYTES,
+        _ => FREE_QUOTA_BYTES,
+    }
+}
+
+/// Bytes of asset storage used by the live assets of `owner_id`'s live games.
+///
+/// Blobs shared through content-hash deduplication are counted once.
+///
+/// # Errors
+///
+/// Returns [`DbErr`] if the query fails.
+pub async fn usage(db: &DatabaseConnection, owner_id: Uuid) -> Result<u64, DbErr> {
+    let rows: Vec<(Option<String>, i32)> = game_asset::Entity::find()
+        .select_only()
+        .column(game_asset::Column::StorageKey)
+        .column(game_asset::Column::FileSize)
+        .join(JoinType::InnerJoin, game_asset::Relation::Game.def())
+        .filter(game::Column::OwnerId.eq(owner_id))
+        .filter(game::Column::DeletedAt.is_null())
+        .filter(game_asset::Column::DeletedAt.is_null())
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let mut seen = HashSet::new();
+    Ok(rows
+        .into_iter()
+        // Inline assets have no key and are never shared
+        .filter(|(key, _)| key.as_ref().is_none_or(|k| seen.insert(k.clone())))
+        .map(|(_, size)| u64::try_from(size).unwrap_or_default())
+        .sum())
+}
@@ -0,0 +1,165 @@
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use reqwest::{Method, StatusCode, Url, header};
+use sha2::{Digest, Sha256};
+
+use super::Storage;
+use crate::config::Config;
+
+pub(super) const NAME: &str = "s3";
+
+/// Stores blobs in an S3-compatible bucket (AWS S3, Cloudflare R2, `MinIO`, ...).
+///
+/// Requests use path-style URLs (`{endpoint}/{bucket}/{key}`) signed with AWS Signature Version 4.
+pub struct S3Storage {
+    endpoint: String,
+    bucket: String,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+}
+
+impl S3Storage {
+    #[must_use]
+    pub fn new(config: &Config) -> Self {
+        Self {
+            endpoint: config.s3_endpoint.trim_end_matches('/').to_string(),
+            bucket: config.s3_bucket.clone(),
+            region: config.s3_region.clone(),
+            access_key_id: config.s3_access_key_id.clone(),
+            secret_access_key: config.s3_secret_access_key.clone(),
+        }
+    }
+
+    /// Send a signed request for `key` and return the response.
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        body: Vec<u8>,
+        content_type: Option<&str>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let encoded_key: Vec<String> = key
+            .split('/')
+            .map(|segment| urlencoding::encode(segment).into_owned())
+            .collect();
+        let url = Url::parse(&format!(
+            "{}/{}/{}",
+            self.endpoint,
+            urlencoding::encode(&self.bucket),
+            encoded_key.join("/")
+        ))?;
+
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            (Some(host), None) => host.to_string(),
+            (None, _) => anyhow::bail!("S3_ENDPOINT has no host"),
+        };
+
+        let now = chrono::Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = hex::encode(Sha256::digest(&body));
+
+        let canonical_request = format!(
+            "{method}\n{path}\n\nhost:{host}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:{amz_date}\n\n{SIGNED_HEADERS}\n{payload_hash}",
+            path = url.path(),
+        );
+        let scope = format!("{date}/{}/s3/aws4_request", self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+        let key = signing_key(&self.secret_access_key, &date, &self.region, "s3")?;
+        let signature = hex::encode(hmac_sha256(&key, string_to_sign.as_bytes())?);
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={SIGNED_HEADERS}, Signature={signature}",
+            self.access_key_id
+        );
+
+        let mut request = reqwest::Client::new()
+            .request(method, url)
+            .header(header::AUTHORIZATION, authorization)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date);
+        if let Some(content_type) = content_type {
+            request = request.header(header::CONTENT_TYPE, content_type);
+        }
+
+        Ok(request.body(body).send().await?)
+    }
+}
+
+/// Headers covered by the signature, in canonical (sorted) order.
+const SIGNED_HEADERS: &str = "host;x-amz-content-sha256;x-amz-date";
+
+#[async_trait]
+impl Storage for S3Storage {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    async fn put(&self, key: &str, data: Vec<u8>, content_type: &str) -> anyhow::Result<()> {
+        let response = self
+            .send(Method::PUT, key, data, Some(content_type))
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("S3 PUT {key} failed with {}", response.status());
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        let response = self.send(Method::GET, key, Vec::new(), None).await?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => Ok(Some(response.bytes().await?.to_vec())),
+            status => anyhow::bail!("S3 GET {key} failed with {status}"),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> anyhow::Result<()> {
+        let response = self.send(Method::DELETE, key, Vec::new(), None).await?;
+        if !response.status().is_success() && response.status() != StatusCode::NOT_FOUND {
+            anyhow::bail!("S3 DELETE {key} failed with {}", response.status());
+        }
+        Ok(())
+    }
+}
+
+/// Derive the `SigV4` signing key for a date, region and service.
+fn signing_key(secret: &str, date: &str, region: &str, service: &str) -> anyhow::Result<Vec<u8>> {
+    let k_date = hmac_sha256(format!("AWS4{secret}").as_bytes(), date.as_bytes())?;
+    let k_region = hmac_sha256(&k_date, region.as_bytes())?;
+    let k_service = hmac_sha256(&k_region, service.as_bytes())?;
+    hmac_sha256(&k_service, b"aws4_request")
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key)?;
+    mac.update(data);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signing_key_matches_aws_example() -> anyhow::Result<()> {
+        // https://docs.aws.amazon.com/IAM/latest/UserGuide/signing-elements.html
+        let key = signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        )?;
+
+        assert_eq!(
+            hex::encode(key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+        Ok(())
+    }
+}
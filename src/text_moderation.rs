@@ -0,0 +1,324 @@
+//! Automated moderation of user-written text.
+//!
+//! Game titles, descriptions and changelogs, reviews and display names are scored from 0 to
+//! 100 against an operator-configured wordlist and, optionally, an external moderation API.
+//! Text scoring at or above [`TextModeration::block_threshold`] is refused; text scoring at
+//! or above [`TextModeration::flag_threshold`] is accepted but reported to the moderators'
+//! queue, so a person makes the call on borderline cases.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait,
+    QueryFilter,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::entities::content_report;
+use crate::error::{AppError, code};
+
+/// Timeout for the moderation API; a slow API must not stall publishing.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Score of a wordlist entry without an explicit one.
+const DEFAULT_WORD_SEVERITY: u8 = 100;
+
+/// How text is moderated, loaded from `TEXT_MODERATION_*` environment variables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextModeration {
+    /// Words and phrases to look for, each with the score it gives text containing it.
+    pub words: Vec<ModeratedWord>,
+    /// Text scoring at least this much is refused.
+    pub block_threshold: u8,
+    /// Text scoring at least this much, but below the block threshold, is reported.
+    pub flag_threshold: u8,
+    /// Moderation API to score text with as well; empty to use the wordlist alone.
+    pub api_url: String,
+    /// Bearer token for the moderation API.
+    pub api_key: String,
+}
+
+impl Default for TextModeration {
+    /// No wordlist and no API, so nothing is flagged until an operator configures it.
+    fn default() -> Self {
+        Self {
+            words: Vec::new(),
+            block_threshold: 80,
+            flag_threshold: 40,
+            api_url: String::new(),
+            api_key: String::new(),
+        }
+    }
+}
+
+/// A wordlist entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModeratedWord {
+    /// Matched as whole words, ignoring case, punctuation and common leetspeak.
+    pub term: String,
+    pub severity: u8,
+}
+
+/// Text that scored high enough to report, and where it was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Flag {
+    /// The field the text came from, e.g. `"description"`.
+    pub field: &'static str,
+    pub severity: u8,
+}
+
+/// Parse a comma-separated wordlist of `term` or `term:severity` entries, e.g.
+/// `"scam:60,free robux:90"`. Entries without a severity score 100.
+///
+/// # Errors
+///
+/// Returns a descriptive message if a severity is not a number from 0 to 100.
+pub fn parse_words(list: &str) -> Result<Vec<ModeratedWord>, String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (term, severity) = match entry.rsplit_once(':') {
+                Some((term, severity)) => {
+                    let severity = severity
+                        .trim()
+                        .parse::<u8>()
+                        .ok()
+                        .filter(|s| *s <= 100)
+                        .ok_or_else(|| {
+                            format!("severity of '{term}' must be a number from 0 to 100")
+                        })?;
+                    (term, severity)
+                }
+                None => (entry, DEFAULT_WORD_SEVERITY),
+            };
+            Ok(ModeratedWord {
+                term: normalize(term),
+                severity,
+            })
+        })
+        .filter(|word| !matches!(word, Ok(w) if w.term.is_empty()))
+        .collect()
+}
+
+/// Score each of `fields` (name and text), refusing the request if any is blocked.
+///
+/// Returns the highest-scoring field at or above the flag threshold, for [`report`] to file
+/// once the content is saved.
+///
+/// # Errors
+///
+/// Returns [`AppError::Unprocessable`] with `CONTENT_BLOCKED` if a field scores at or above
+/// the block threshold.
+pub async fn screen(
+    config: &TextModeration,
+    fields: &[(&'static str, &str)],
+) -> Result<Option<Flag>, AppError> {
+    let mut flag: Option<Flag> = None;
+    for &(field, text) in fields {
+        if text.trim().is_empty() {
+            continue;
+        }
+        let severity = score(config, text).await;
+        if severity >= config.block_threshold {
+            return Err(AppError::Unprocessable(
+                code::CONTENT_BLOCKED,
+                format!("The {field} contains language that is not allowed."),
+            ));
+        }
+        if severity >= config.flag_threshold && flag.as_ref().is_none_or(|f| severity > f.severity)
+        {
+            flag = Some(Flag { field, severity });
+        }
+    }
+    Ok(flag)
+}
+
+/// File a report against `target_type` `target_id` for `flag`, unless the content already
+/// has an open automated report.
+///
+/// Reporting is a side effect of saving the content: failing to file one is logged rather
+/// than failing the request.
+pub async fn report<C: ConnectionTrait>(
+    db: &C,
+    flag: Option<Flag>,
+    target_type: &str,
+    target_id: Uuid,
+) {
+    let Some(flag) = flag else {
+        return;
+    };
+    if let Err(e) = file_report(db, &flag, target_type, target_id).await {
+        tracing::warn!(error = %e, target_type, %target_id, "Failed to file automated report");
+    }
+}
+
+async fn file_report<C: ConnectionTrait>(
+    db: &C,
+    flag: &Flag,
+    target_type: &str,
+    target_id: Uuid,
+) -> Result<(), sea_orm::DbErr> {
+    // Automated reports are the ones with neither a reporter nor an IP address
+    let already_reported = content_report::Entity::find()
+        .filter(content_report::Column::TargetType.eq(target_type))
+        .filter(content_report::Column::TargetId.eq(target_id))
+        .filter(content_report::Column::Status.eq("open"))
+        .filter(content_report::Column::ReporterId.is_null())
+        .filter(content_report::Column::ReporterIp.is_null())
+        .count(db)
+        .await?
+        > 0;
+    if already_reported {
+        return Ok(());
+    }
+
+    content_report::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        created_at: Set(Utc::now().fixed_offset()),
+        reporter_id: Set(None),
+        reporter_ip: Set(None),
+        target_type: Set(target_type.to_string()),
+        target_id: Set(target_id),
+        reason: Set("other".to_string()),
+        details: Set(Some(format!(
+            "Automated text moderation flagged the {} (severity {}).",
+            flag.field, flag.severity
+        ))),
+        status: Set("open".to_string()),
+        assigned_to: Set(None),
+        assigned_at: Set(None),
+        resolved_by: Set(None),
+        resolution: Set(None),
+        resolution_note: Set(None),
+        resolved_at: Set(None),
+    }
+    .insert(db)
+    .await?;
+    Ok(())
+}
+
+/// The higher of `text`'s wordlist and API scores.
+///
+/// If the API cannot be reached the wordlist score stands, so an outage does not block
+/// publishing.
+async fn score(config: &TextModeration, text: &str) -> u8 {
+    let listed = wordlist_score(&config.words, text);
+    if config.api_url.is_empty() || listed >= config.block_threshold {
+        return listed;
+    }
+    match api_score(config, text).await {
+        Ok(scored) => listed.max(scored),
+        Err(e) => {
+            tracing::warn!("Skipping text moderation API: {e:#}");
+            listed
+        }
+    }
+}
+
+/// The severity of the worst wordlist entry in `text`, or 0.
+fn wordlist_score(words: &[ModeratedWord], text: &str) -> u8 {
+    let text = format!(" {} ", normalize(text));
+    words
+        .iter()
+        .filter(|word| text.contains(&format!(" {} ", word.term)))
+        .map(|word| word.severity)
+        .max()
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiScore {
+    /// 0 (harmless) to 100.
+    severity: f64,
+}
+
+/// Ask the moderation API to score `text`: it is sent `{"text": ...}` and answers
+/// `{"severity": 0-100}`.
+async fn api_score(config: &TextModeration, text: &str) -> anyhow::Result<u8> {
+    let mut request = reqwest::Client::new()
+        .post(&config.api_url)
+        .json(&serde_json::json!({ "text": text }))
+        .timeout(REQUEST_TIMEOUT);
+    if !config.api_key.is_empty() {
+        request = request.bearer_auth(&config.api_key);
+    }
+    let scored: ApiScore = request
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| anyhow::anyhow!("Text moderation request failed: {e}"))?
+        .json()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to read text moderation score: {e}"))?;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Ok(scored.severity.clamp(0.0, 100.0).round() as u8)
+}
+
+/// Lower-case `text`, undo common leetspeak substitutions and reduce everything between
+/// words to a single space, so look-alike spellings of a listed word compare equal to it.
+fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| match c.to_ascii_lowercase() {
+            '0' => 'o',
+            '1' => 'i',
+            '3' => 'e',
+            '4' | '@' => 'a',
+            '5' | '$' => 's',
+            '7' => 't',
+            c if c.is_alphanumeric() => c,
+            _ => ' ',
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(words: &str) -> TextModeration {
+        TextModeration {
+            words: parse_words(words).unwrap_or_default(),
+            ..TextModeration::default()
+        }
+    }
+
+    #[test]
+    fn wordlist_entries_match_whole_words_despite_spelling_tricks() {
+        let words = config("scam:60,free robux").words;
+        assert_eq!(wordlist_score(&words, "Totally not a SC4M!"), 60);
+        assert_eq!(wordlist_score(&words, "Get FREE   r0bux here"), 100);
+        assert_eq!(wordlist_score(&words, "Scampi recipes"), 0);
+    }
+
+    #[test]
+    fn invalid_severities_are_rejected() {
+        assert!(parse_words("scam:101").is_err());
+        assert!(parse_words("scam:high").is_err());
+        assert_eq!(parse_words(" , ").map(|w| w.len()), Ok(0));
+    }
+
+    #[tokio::test]
+    async fn blocked_fields_fail_and_borderline_ones_are_flagged() {
+        let config = config("scam:60,free robux");
+        assert!(
+            screen(&config, &[("title", "Free Robux"), ("description", "")])
+                .await
+                .is_err()
+        );
+        let flag = screen(&config, &[("title", "Fine"), ("description", "a scam")]).await;
+        assert_eq!(
+            flag.ok().flatten(),
+            Some(Flag {
+                field: "description",
+                severity: 60
+            })
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+/// Most controls a single controller layout may declare.
+const MAX_CONTROLS: usize = 16;
+
+/// Longest allowed control `id` or `label`, in characters.
+const MAX_CONTROL_TEXT_LENGTH: usize = 40;
+
+/// Longest `maxLength` a text input may declare.
+const MAX_TEXT_INPUT_LENGTH: u32 = 500;
+
+/// What a game declares about its controller, so generic controller UIs can render it without
+/// loading the game's code.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct GameManifest {
+    #[serde(default)]
+    pub controls: Vec<Control>,
+    #[serde(default)]
+    pub orientation: Orientation,
+    /// Filled in from the game when omitted.
+    pub min_players: Option<i32>,
+    pub max_players: Option<i32>,
+}
+
+/// A single input on the controller screen.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", deny_unknown_fields)]
+pub enum Control {
+    Button {
+        id: String,
+        label: Option<String>,
+    },
+    Joystick {
+        id: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    TextInput {
+        id: String,
+        label: Option<String>,
+        max_length: Option<u32>,
+    },
+}
+
+impl Control {
+    fn id(&self) -> &str {
+        match self {
+            Self::Button { id, .. } | Self::Joystick { id } | Self::TextInput { id, .. } => id,
+        }
+    }
+
+    fn label(&self) -> Option<&str> {
+        match self {
+            Self::Button { label, .. } | Self::TextInput { label, .. } => label.as_deref(),
+            Self::Joystick { .. } => None,
+        }
+    }
+}
+
+/// Screen orientation the controller expects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Orientation {
+    #[default]
+    Any,
+    Portrait,
+    Landscape,
+}
+
+/// Parse and check a game's draft manifest against its player counts.
+///
+/// Omitted player counts are taken from the game; declared ones must match it.
+///
+/// # Errors
+///
+/// Returns a description of the first problem found.
+pub fn parse_manifest(
+    value: &serde_json::Value,
+    min_players: i32,
+    max_players: i32,
+) -> Result<GameManifest, String> {
+    let mut manifest: GameManifest =
+        serde_json::from_value(value.clone()).map_err(|e| format!("Invalid manifest: {e}"))?;
+
+    if manifest.controls.len() > MAX_CONTROLS {
+        return Err(format!(
+            "Manifest may declare at most {MAX_CONTROLS} controls"
+        ));
+    }
+
+    let mut ids = HashSet::new();
+    for control in &manifest.controls {
+        let id = control.id();
+        if id.trim().is_empty() {
+            return Err("Manifest control ids must not be empty".to_string());
+        }
+        if [Some(id), control.label()]
+            .into_iter()
+            .flatten()
+            .any(|text| text.chars().count() > MAX_CONTROL_TEXT_LENGTH)
+        {
+            return Err(format!(
+                "Manifest control ids and labels must be at most {MAX_CONTROL_TEXT_LENGTH} characters"
+            ));
+        }
+        if !ids.insert(id) {
+            return Err(format!("Duplicate manifest control id '{id}'"));
+        }
+        if let Control::TextInput {
+            max_length: Some(len),
+            ..
+        } = control
+            && !(1..=MAX_TEXT_INPUT_LENGTH).contains(len)
+        {
+            return Err(format!(
+                "Text input maxLength must be between 1 and {MAX_TEXT_INPUT_LENGTH}"
+            ));
+        }
+    }
+
+    if manifest.min_players.is_some_and(|n| n != min_players)
+        || manifest.max_players.is_some_and(|n| n != max_players)
+    {
+        return Err(format!(
+            "Manifest player counts must match the game ({min_players}-{max_players})"
+        ));
+    }
+    manifest.min_players = Some(min_players);
+    manifest.max_players = Some(max_players);
+
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn fills_player_counts_from_the_game() -> Result<(), String> {
+        let manifest = parse_manifest(
+            &json!({
+                "controls": [
+                    { "type": "joystick", "id": "move" },
+                    { "type": "button", "id": "fire", "label": "Fire" },
+                    { "type": "textInput", "id": "answer", "maxLength": 20 },
+                ],
+                "orientation": "landscape",
+            }),
+            2,
+            4,
+        )?;
+
+        assert_eq!(manifest.controls.len(), 3);
+        assert_eq!(manifest.orientation, Orientation::Landscape);
+        assert_eq!(
+            (manifest.min_players, manifest.max_players),
+            (Some(2), Some(4))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_invalid_manifests() {
+        let cases = [
+            json!({ "controls": [{ "type": "lever", "id": "x" }] }),
+            json!({ "controls": [{ "type": "button", "id": "a" }, { "type": "joystick", "id": "a" }] }),
+            json!({ "controls": [{ "type": "button", "id": " " }] }),
+            json!({ "controls": [{ "type": "textInput", "id": "t", "maxLength": 0 }] }),
+            json!({ "orientation": "sideways" }),
+            json!({ "maxPlayers": 8 }),
+            json!({ "theme": "dark" }),
+        ];
+
+        for case in cases {
+            assert!(parse_manifest(&case, 1, 4).is_err(), "{case}");
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{Program, Statement};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+use serde::Serialize;
+
+mod manifest;
+mod scan;
+
+pub use manifest::{Control, GameManifest, Orientation, parse_manifest};
+pub use scan::{ScanRule, apply_rule_overrides, default_scan_rules};
+
+/// Diagnostic code of safety scanner findings.
+pub const UNSAFE_API: &str = "UNSAFE_API";
+
+/// Functions p5.js calls on its own in global mode; a canvas must define at least one.
+const P5_ENTRY_POINTS: &[&str] = &["setup", "draw"];
+
+/// How serious a diagnostic is. Only errors block publishing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Which of the game's two canvases a diagnostic refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CodeFile {
+    GameScreen,
+    ControllerScreen,
+}
+
+/// A single finding about a game's code.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Stable machine-readable identifier, e.g. `SYNTAX_ERROR`.
+    pub code: &'static str,
+    pub message: String,
+    /// `None` for findings about the game as a whole.
+    pub file: Option<CodeFile>,
+    /// 1-based position of the problem, when known.
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl Diagnostic {
+    const fn new(
+        severity: Severity,
+        code: &'static str,
+        message: String,
+        file: Option<CodeFile>,
+    ) -> Self {
+        Self {
+            severity,
+            code,
+            message,
+            file,
+            line: None,
+            column: None,
+        }
+    }
+
+    /// Attach the position of byte `offset` in `source`, if known.
+    fn at(mut self, source: &str, offset: Option<usize>) -> Self {
+        if let Some(offset) = offset {
+            let (line, column) = line_column(source, offset);
+            self.line = Some(line);
+            self.column = Some(column);
+        }
+        self
+    }
+}
+
+/// Check a game's code before it is published.
+///
+/// Both canvases are parsed as classic scripts; each non-empty canvas must parse cleanly,
+/// define the entry points the runtime calls (for known technologies), and avoid the APIs
+/// denylisted by `scan_rules`.
+#[must_use]
+pub fn validate_game(
+    technology: &str,
+    game_screen_code: Option<&str>,
+    controller_screen_code: Option<&str>,
+    scan_rules: &[ScanRule],
+) -> Vec<Diagnostic> {
+    let canvases = [
+        (CodeFile::GameScreen, game_screen_code),
+        (CodeFile::ControllerScreen, controller_screen_code),
+    ];
+
+    let mut diagnostics = Vec::new();
+
+    if canvases
+        .iter()
+        .all(|(_, code)| code.is_none_or(|c| c.trim().is_empty()))
+    {
+        diagnostics.push(Diagnostic::new(
+            Severity::Error,
+            "EMPTY_CODE",
+            "Game must have at least one non-empty canvas code".to_string(),
+            None,
+        ));
+        return diagnostics;
+    }
+
+    if technology != "p5js" {
+        diagnostics.push(Diagnostic::new(
+            Severity::Warning,
+            "UNKNOWN_TECHNOLOGY",
+            format!("Entry points are not checked for technology '{technology}'"),
+            None,
+        ));
+    }
+
+    for (file, code) in canvases {
+        let Some(code) = code.filter(|c| !c.trim().is_empty()) else {
+            continue;
+        };
+        diagnostics.extend(check_canvas(technology, file, code, scan_rules));
+    }
+
+    diagnostics
+}
+
+/// Whether any diagnostic is severe enough to block publishing.
+#[must_use]
+pub fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics.iter().any(|d| d.severity == Severity::Error)
+}
+
+fn check_canvas(
+    technology: &str,
+    file: CodeFile,
+    code: &str,
+    scan_rules: &[ScanRule],
+) -> Vec<Diagnostic> {
+    let allocator = Allocator::default();
+    let parsed = Parser::new(&allocator, code, SourceType::cjs()).parse();
+
+    if !parsed.errors.is_empty() {
+        return parsed
+            .errors
+            .iter()
+            .map(|e| {
+                let offset = e
+                    .labels
+                    .as_ref()
+                    .and_then(|l| l.first())
+                    .map(|l| l.inner().offset());
+                Diagnostic::new(
+                    Severity::Error,
+                    "SYNTAX_ERROR",
+                    e.message.to_string(),
+                    Some(file),
+                )
+                .at(code, offset)
+            })
+            .collect();
+    }
+
+    let mut diagnostics = Vec::new();
+    if technology == "p5js" && !defines_any(&parsed.program, P5_ENTRY_POINTS) {
+        diagnostics.push(Diagnostic::new(
+            Severity::Error,
+            "MISSING_ENTRY_POINT",
+            "p5.js code must define a top-level setup() or draw() function".to_string(),
+            Some(file),
+        ));
+    }
+    diagnostics.extend(scan::scan_program(&parsed.program, code, file, scan_rules));
+    diagnostics
+}
+
+/// Whether the program declares any of `names` at the top level, either as a function
+/// declaration or as a variable (`const setup = () => ...`).
+fn defines_any(program: &Program<'_>, names: &[&str]) -> bool {
+    program.body.iter().any(|stmt| match stmt {
+        Statement::FunctionDeclaration(f) => {
+            f.id.as_ref()
+                .is_some_and(|id| names.contains(&id.name.as_str()))
+        }
+        Statement::VariableDeclaration(decl) => decl.declarations.iter().any(|d| {
+            d.id.get_identifier_name()
+                .is_some_and(|name| names.contains(&name.as_str()))
+        }),
+        _ => false,
+    })
+}
+
+/// Convert a byte offset into a 1-based line and column (in characters).
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = source.get(..offset).unwrap_or(source);
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_p5_code_has_no_diagnostics() {
+        let diagnostics = validate_game(
+            "p5js",
+            Some("function setup() { createCanvas(400, 400); }"),
+            Some("const draw = () => background(0);"),
+            &default_scan_rules(),
+        );
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn syntax_error_reports_position() {
+        let diagnostics = validate_game(
+            "p5js",
+            Some("function setup() {\n  let = ;\n}"),
+            None,
+            &default_scan_rules(),
+        );
+        assert!(has_errors(&diagnostics));
+        assert_eq!(diagnostics[0].code, "SYNTAX_ERROR");
+        assert_eq!(diagnostics[0].file, Some(CodeFile::GameScreen));
+        assert_eq!(diagnostics[0].line, Some(2));
+    }
+
+    #[test]
+    fn missing_entry_point_is_an_error() {
+        let diagnostics = validate_game("p5js", None, Some("let score = 0;"), &[]);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "MISSING_ENTRY_POINT");
+        assert_eq!(diagnostics[0].file, Some(CodeFile::ControllerScreen));
+    }
+
+    #[test]
+    fn denylisted_apis_are_reported_by_severity() {
+        let code = "function setup() {\n  window.eval('1');\n  fetch('/api/scores');\n  \
+                    fetch('https://evil.example');\n  let c = document.cookie;\n}";
+        let diagnostics = validate_game("p5js", Some(code), None, &default_scan_rules());
+
+        let found: Vec<(Severity, Option<usize>)> = diagnostics
+            .iter()
+            .filter(|d| d.code == "UNSAFE_API")
+            .map(|d| (d.severity, d.line))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (Severity::Error, Some(2)),
+                (Severity::Warning, Some(4)),
+                (Severity::Error, Some(5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn rule_overrides_change_severity() -> Result<(), String> {
+        let rules =
+            apply_rule_overrides(default_scan_rules(), "fetch=error, eval=off, alert=warning")?;
+
+        assert!(
+            rules
+                .iter()
+                .any(|r| r.pattern == "fetch" && r.severity == Severity::Error)
+        );
+        assert!(!rules.iter().any(|r| r.pattern == "eval"));
+        assert!(rules.iter().any(|r| r.pattern == "alert"));
+        assert!(apply_rule_overrides(vec![], "eval=fatal").is_err());
+        Ok(())
+    }
+}
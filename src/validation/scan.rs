@@ -0,0 +1,166 @@
+use oxc_ast::ast::{
+    Argument, CallExpression, Expression, IdentifierReference, Program, StaticMemberExpression,
+};
+use oxc_ast_visit::{Visit, walk};
+
+use super::{CodeFile, Diagnostic, Severity, UNSAFE_API};
+
+/// Global objects a denylisted API can be reached through (`window.eval`, ...).
+const GLOBAL_OBJECTS: &[&str] = &["window", "globalThis", "self"];
+
+/// A denylisted global or member path and what happens when game code uses it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanRule {
+    /// An identifier (`eval`) or dotted member path (`document.cookie`).
+    pub pattern: String,
+    pub severity: Severity,
+}
+
+/// Rules applied when `CODE_SCAN_RULES` does not override them.
+#[must_use]
+pub fn default_scan_rules() -> Vec<ScanRule> {
+    [
+        ("eval", Severity::Error),
+        ("Function", Severity::Error),
+        ("importScripts", Severity::Error),
+        ("document.cookie", Severity::Error),
+        ("window.parent", Severity::Error),
+        ("window.top", Severity::Error),
+        ("fetch", Severity::Warning),
+        ("XMLHttpRequest", Severity::Warning),
+        ("WebSocket", Severity::Warning),
+        ("localStorage", Severity::Warning),
+        ("sessionStorage", Severity::Warning),
+        ("indexedDB", Severity::Warning),
+    ]
+    .into_iter()
+    .map(|(pattern, severity)| ScanRule {
+        pattern: pattern.to_string(),
+        severity,
+    })
+    .collect()
+}
+
+/// Apply `CODE_SCAN_RULES`-style overrides (`eval=error,fetch=off,localStorage=warning`) to
+/// `rules`. Unknown patterns are added as new rules; `off` removes a rule.
+///
+/// # Errors
+///
+/// Returns a description of the first malformed entry.
+pub fn apply_rule_overrides(mut rules: Vec<ScanRule>, spec: &str) -> Result<Vec<ScanRule>, String> {
+    for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let (pattern, level) = entry
+            .split_once('=')
+            .ok_or_else(|| format!("expected pattern=severity, got '{entry}'"))?;
+        let pattern = pattern.trim();
+
+        rules.retain(|r| r.pattern != pattern);
+        let severity = match level.trim() {
+            "error" => Severity::Error,
+            "warning" => Severity::Warning,
+            "off" => continue,
+            other => return Err(format!("unknown severity '{other}' for '{pattern}'")),
+        };
+        rules.push(ScanRule {
+            pattern: pattern.to_string(),
+            severity,
+        });
+    }
+    Ok(rules)
+}
+
+/// Report every use of a denylisted API in a parsed canvas.
+pub(super) fn scan_program(
+    program: &Program<'_>,
+    source: &str,
+    file: CodeFile,
+    rules: &[ScanRule],
+) -> Vec<Diagnostic> {
+    let mut scanner = Scanner {
+        rules,
+        source,
+        file,
+        diagnostics: Vec::new(),
+    };
+    scanner.visit_program(program);
+    scanner.diagnostics
+}
+
+struct Scanner<'r> {
+    rules: &'r [ScanRule],
+    source: &'r str,
+    file: CodeFile,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Scanner<'_> {
+    fn check(&mut self, path: &str, offset: u32) {
+        let Some(rule) = self.rules.iter().find(|r| matches_rule(path, &r.pattern)) else {
+            return;
+        };
+
+        let diagnostic = Diagnostic::new(
+            rule.severity,
+            UNSAFE_API,
+            format!("Use of `{}` is not allowed in game code", rule.pattern),
+            Some(self.file),
+        )
+        .at(self.source, usize::try_from(offset).ok());
+        self.diagnostics.push(diagnostic);
+    }
+}
+
+impl<'a> Visit<'a> for Scanner<'_> {
+    fn visit_identifier_reference(&mut self, it: &IdentifierReference<'a>) {
+        self.check(it.name.as_str(), it.span.start);
+    }
+
+    fn visit_static_member_expression(&mut self, it: &StaticMemberExpression<'a>) {
+        if let Some(path) = member_path(&it.object) {
+            self.check(&format!("{path}.{}", it.property.name), it.span.start);
+        }
+        walk::walk_static_member_expression(self, it);
+    }
+
+    fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+        // Same-origin requests (`fetch("/api/...")`) are allowed; only the URL is inspected
+        if is_relative_fetch(it) {
+            self.visit_arguments(&it.arguments);
+            return;
+        }
+        walk::walk_call_expression(self, it);
+    }
+}
+
+/// Whether `path` refers to `pattern`, directly or through a global object.
+fn matches_rule(path: &str, pattern: &str) -> bool {
+    path == pattern
+        || GLOBAL_OBJECTS.iter().any(|global| {
+            path.strip_prefix(global)
+                .and_then(|rest| rest.strip_prefix('.'))
+                == Some(pattern)
+        })
+}
+
+/// Dotted path of an identifier or chain of static member accesses (`a.b.c`).
+fn member_path(expr: &Expression<'_>) -> Option<String> {
+    match expr {
+        Expression::Identifier(id) => Some(id.name.to_string()),
+        Expression::StaticMemberExpression(m) => {
+            Some(format!("{}.{}", member_path(&m.object)?, m.property.name))
+        }
+        _ => None,
+    }
+}
+
+fn is_relative_fetch(call: &CallExpression<'_>) -> bool {
+    let is_fetch = member_path(&call.callee).is_some_and(|p| matches_rule(&p, "fetch"));
+    let relative_url = match call.arguments.first() {
+        Some(Argument::StringLiteral(url)) => {
+            let url = url.value.as_str();
+            (url.starts_with('/') && !url.starts_with("//")) || url.starts_with("./")
+        }
+        _ => false,
+    };
+    is_fetch && relative_url
+}
@@ -0,0 +1,262 @@
+//! Platform XP and the levels it unlocks.
+//!
+//! XP is kept as a ledger in `xp_event`. Flows that earn XP call the matching hook here once
+//! their own work has succeeded. Each event is keyed by the play, game or review that earned
+//! it, so granting is idempotent. Levels are derived from a user's total.
+
+use std::collections::HashMap;
+
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ActiveValue::Set, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, FromQueryResult,
+    QueryFilter, QuerySelect,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::entities::{player, xp_event};
+
+/// What XP was earned for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// Hosting a session in which a game started.
+    Hosting,
+    /// Playing a game in someone's session while signed in.
+    Playing,
+    /// Publishing a game for the first time.
+    Publishing,
+    /// Receiving a review on one of your games.
+    Reviews,
+}
+
+impl Source {
+    /// The value stored in `xp_event.source`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Hosting => "hosting",
+            Self::Playing => "playing",
+            Self::Publishing => "publishing",
+            Self::Reviews => "reviews",
+        }
+    }
+
+    /// XP earned per event.
+    #[must_use]
+    pub const fn amount(self) -> i32 {
+        match self {
+            Self::Hosting => 20,
+            Self::Playing => 10,
+            Self::Publishing => 50,
+            Self::Reviews => 15,
+        }
+    }
+}
+
+/// Total XP needed for each level, starting at level 1.
+const LEVEL_THRESHOLDS: &[i64] = &[0, 100, 250, 500, 1_000, 2_000, 3_500, 5_000, 7_500, 10_000];
+
+/// The level a user with `xp` total XP is at.
+#[must_use]
+pub fn level_for(xp: i64) -> i32 {
+    let reached = LEVEL_THRESHOLDS.iter().filter(|&&t| xp >= t).count();
+    i32::try_from(reached.max(1)).unwrap_or(i32::MAX)
+}
+
+/// Total XP needed to reach `level`, or `None` past the top level.
+#[must_use]
+pub fn level_xp(level: i32) -> Option<i64> {
+    usize::try_from(level - 1)
+        .ok()
+        .and_then(|i| LEVEL_THRESHOLDS.get(i))
+        .copied()
+}
+
+/// A user's XP by source.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Breakdown {
+    pub hosting: i64,
+    pub playing: i64,
+    pub publishing: i64,
+    pub reviews: i64,
+}
+
+impl Breakdown {
+    /// Total XP across every source.
+    #[must_use]
+    pub const fn total(&self) -> i64 {
+        self.hosting + self.playing + self.publishing + self.reviews
+    }
+}
+
+#[derive(FromQueryResult)]
+struct SourceTotal {
+    source: String,
+    total: i64,
+}
+
+#[derive(FromQueryResult)]
+struct UserTotal {
+    user_id: Uuid,
+    total: i64,
+}
+
+/// Grant `user_id` the XP for `source`, earned by `reference_id`. Returns whether it was newly
+/// granted.
+///
+/// # Errors
+///
+/// Returns an error if the database operation fails.
+pub async fn grant<C: ConnectionTrait>(
+    db: &C,
+    user_id: Uuid,
+    source: Source,
+    reference_id: Uuid,
+) -> Result<bool, DbErr> {
+    let inserted = xp_event::Entity::insert(xp_event::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id),
+        source: Set(source.as_str().to_string()),
+        reference_id: Set(reference_id),
+        amount: Set(source.amount()),
+        created_at: Set(chrono::Utc::now().fixed_offset()),
+    })
+    .on_conflict(
+        OnConflict::columns([
+            xp_event::Column::UserId,
+            xp_event::Column::Source,
+            xp_event::Column::ReferenceId,
+        ])
+        .do_nothing()
+        .to_owned(),
+    )
+    .exec_without_returning(db)
+    .await?;
+    Ok(inserted > 0)
+}
+
+/// `user_id`'s XP by source.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub async fn breakdown<C: ConnectionTrait>(db: &C, user_id: Uuid) -> Result<Breakdown, DbErr> {
+    let rows = xp_event::Entity::find()
+        .select_only()
+        .column(xp_event::Column::Source)
+        .column_as(
+            Expr::cust("CAST(COALESCE(SUM(amount), 0) AS BIGINT)"),
+            "total",
+        )
+        .filter(xp_event::Column::UserId.eq(user_id))
+        .group_by(xp_event::Column::Source)
+        .into_model::<SourceTotal>()
+        .all(db)
+        .await?;
+
+    let mut breakdown = Breakdown::default();
+    for row in rows {
+        let slot = match row.source.as_str() {
+            "hosting" => &mut breakdown.hosting,
+            "playing" => &mut breakdown.playing,
+            "publishing" => &mut breakdown.publishing,
+            "reviews" => &mut breakdown.reviews,
+            _ => continue,
+        };
+        *slot = row.total;
+    }
+    Ok(breakdown)
+}
+
+/// Total XP for each of `user_ids`. Users with none are left out.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub async fn totals<C: ConnectionTrait>(
+    db: &C,
+    user_ids: impl IntoIterator<Item = Uuid>,
+) -> Result<HashMap<Uuid, i64>, DbErr> {
+    let rows = xp_event::Entity::find()
+        .select_only()
+        .column(xp_event::Column::UserId)
+        .column_as(
+            Expr::cust("CAST(COALESCE(SUM(amount), 0) AS BIGINT)"),
+            "total",
+        )
+        .filter(xp_event::Column::UserId.is_in(user_ids))
+        .group_by(xp_event::Column::UserId)
+        .into_model::<UserTotal>()
+        .all(db)
+        .await?;
+    Ok(rows.into_iter().map(|r| (r.user_id, r.total)).collect())
+}
+
+/// The level of each of `user_ids`, level 1 for users without XP.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub async fn levels<C: ConnectionTrait>(
+    db: &C,
+    user_ids: impl IntoIterator<Item = Uuid>,
+) -> Result<HashMap<Uuid, i32>, DbErr> {
+    let user_ids: Vec<Uuid> = user_ids.into_iter().collect();
+    let totals = totals(db, user_ids.iter().copied()).await?;
+    Ok(user_ids
+        .into_iter()
+        .map(|id| (id, level_for(totals.get(&id).copied().unwrap_or(0))))
+        .collect())
+}
+
+/// Hook for a game starting in a session: the host earns [`Source::Hosting`] and every
+/// signed-in player still in the session earns [`Source::Playing`], once per play.
+pub async fn play_started<C: ConnectionTrait>(
+    db: &C,
+    host_id: Uuid,
+    session_id: Uuid,
+    play_id: Uuid,
+) {
+    grant_best_effort(db, host_id, Source::Hosting, play_id).await;
+
+    let players = player::Entity::find()
+        .filter(player::Column::SessionId.eq(session_id))
+        .filter(player::Column::LeftAt.is_null())
+        .filter(player::Column::UserId.is_not_null())
+        .all(db)
+        .await;
+    match players {
+        Ok(players) => {
+            for user_id in players.into_iter().filter_map(|p| p.user_id) {
+                if user_id != host_id {
+                    grant_best_effort(db, user_id, Source::Playing, play_id).await;
+                }
+            }
+        }
+        Err(e) => tracing::warn!(error = %e, %session_id, "Failed to load players for XP"),
+    }
+}
+
+/// Hook for a published game: its owner earns [`Source::Publishing`], once per game.
+pub async fn game_published<C: ConnectionTrait>(db: &C, owner_id: Uuid, game_id: Uuid) {
+    grant_best_effort(db, owner_id, Source::Publishing, game_id).await;
+}
+
+/// Hook for a new review: the game's owner earns [`Source::Reviews`].
+pub async fn review_received<C: ConnectionTrait>(db: &C, owner_id: Uuid, review_id: Uuid) {
+    grant_best_effort(db, owner_id, Source::Reviews, review_id).await;
+}
+
+/// XP is a side effect of the flow that earns it: failing to grant it is logged rather than
+/// failing the request.
+async fn grant_best_effort<C: ConnectionTrait>(
+    db: &C,
+    user_id: Uuid,
+    source: Source,
+    reference_id: Uuid,
+) {
+    if let Err(e) = grant(db, user_id, source, reference_id).await {
+        tracing::warn!(error = %e, %user_id, source = source.as_str(), "Failed to grant XP");
+    }
+}
@@ -0,0 +1,271 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::{auth_provider, game, user};
+use aircade_api::jobs::account_deletion;
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+async fn signup_user(
+    app: &Router,
+    email: &str,
+    username: &str,
+    password: &str,
+) -> (String, String) {
+    let (status, body) = common::post_json(
+        app,
+        "/api/v1/auth/signup/email",
+        &json!({
+            "email": email,
+            "username": username,
+            "password": password,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "signup failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let token = json["token"].as_str().unwrap_or_default().to_string();
+    let refresh = json["refreshToken"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    (token, refresh)
+}
+
+async fn create_game(app: &Router, token: &str) -> String {
+    let (status, body) =
+        common::post_json_with_auth(app, "/api/v1/games", &json!({ "title": "Mine" }), token).await;
+    assert_eq!(status, StatusCode::CREATED, "create game failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    json["id"].as_str().unwrap_or_default().to_string()
+}
+
+async fn me(app: &Router, token: &str) -> serde_json::Value {
+    let (_, body) = common::get_with_auth(app, "/api/v1/users/me", token).await;
+    serde_json::from_str(&body).unwrap_or_default()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/v1/users/me/delete
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn schedule_deletion_after_grace_period() {
+    let (app, _) = test_app().await;
+    let (token, _) = signup_user(&app, "gone@example.com", "gone", "Password1").await;
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/users/me/delete",
+        &json!({ "password": "Password1", "games": "keep" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED, "{body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let scheduled = json["deletionScheduledAt"].as_str().unwrap_or_default();
+    let scheduled = chrono::DateTime::parse_from_rfc3339(scheduled).unwrap_or_default();
+    assert!(scheduled > Utc::now() + Duration::days(account_deletion::GRACE_PERIOD_DAYS - 1));
+
+    // Still usable during the grace window
+    let json = me(&app, &token).await;
+    assert_eq!(json["username"], "gone");
+    assert!(json["deletionScheduledAt"].is_string());
+
+    // Scheduling twice is rejected
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/users/me/delete",
+        &json!({ "password": "Password1", "games": "keep" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn schedule_deletion_requires_password_and_policy() {
+    let (app, _) = test_app().await;
+    let (token, _) = signup_user(&app, "gone@example.com", "gone", "Password1").await;
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/users/me/delete",
+        &json!({ "password": "WrongPass1", "games": "keep" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/users/me/delete",
+        &json!({ "password": "Password1", "games": "transfer" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    assert!(me(&app, &token).await["deletionScheduledAt"].is_null());
+}
+
+#[tokio::test]
+async fn cancel_scheduled_deletion() {
+    let (app, state) = test_app().await;
+    let (token, _) = signup_user(&app, "gone@example.com", "gone", "Password1").await;
+
+    let (status, _) =
+        common::post_json_with_auth(&app, "/api/v1/users/me/delete/cancel", &json!({}), &token)
+            .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    common::post_json_with_auth(
+        &app,
+        "/api/v1/users/me/delete",
+        &json!({ "password": "Password1", "games": "delete" }),
+        &token,
+    )
+    .await;
+    let (status, _) =
+        common::post_json_with_auth(&app, "/api/v1/users/me/delete/cancel", &json!({}), &token)
+            .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(me(&app, &token).await["deletionScheduledAt"].is_null());
+
+    // Nothing happens once the grace period would have passed
+    let later = Utc::now() + Duration::days(account_deletion::GRACE_PERIOD_DAYS + 1);
+    let deleted = account_deletion::run_due(&state.db, &state.config, later)
+        .await
+        .unwrap_or_default();
+    assert_eq!(deleted, 0);
+    assert_eq!(me(&app, &token).await["username"], "gone");
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Account deletion job
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn deletion_anonymizes_account_and_keeps_games() {
+    let (app, state) = test_app().await;
+    let (token, _) = signup_user(&app, "gone@example.com", "gone", "Password1").await;
+    let game_id = create_game(&app, &token).await;
+    let user_id = me(&app, &token).await["id"]
+        .as_str()
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .unwrap_or_default();
+
+    common::post_json_with_auth(
+        &app,
+        "/api/v1/users/me/delete",
+        &json!({ "password": "Password1", "games": "keep" }),
+        &token,
+    )
+    .await;
+
+    // Not yet due
+    let deleted = account_deletion::run_due(&state.db, &state.config, Utc::now())
+        .await
+        .unwrap_or_default();
+    assert_eq!(deleted, 0);
+
+    let later = Utc::now() + Duration::days(account_deletion::GRACE_PERIOD_DAYS + 1);
+    let deleted = account_deletion::run_due(&state.db, &state.config, later)
+        .await
+        .unwrap_or_default();
+    assert_eq!(deleted, 1);
+
+    // The user row is kept as an anonymized tombstone
+    let tombstone = user::Entity::find_by_id(user_id)
+        .one(&state.db)
+        .await
+        .unwrap_or_default();
+    assert!(tombstone.is_some_and(|u| {
+        u.account_status == "deleted"
+            && u.email.ends_with("@deleted.invalid")
+            && u.username.starts_with("deleted-")
+            && u.deleted_at.is_none()
+            && u.deletion_scheduled_at.is_none()
+    }));
+
+    let providers = auth_provider::Entity::find()
+        .filter(auth_provider::Column::UserId.eq(user_id))
+        .count(&state.db)
+        .await
+        .unwrap_or_default();
+    assert_eq!(providers, 0);
+
+    let game_id = Uuid::parse_str(&game_id).unwrap_or_default();
+    let game = game::Entity::find_by_id(game_id)
+        .one(&state.db)
+        .await
+        .unwrap_or_default();
+    assert!(game.is_some_and(|g| g.deleted_at.is_none() && g.owner_id == user_id));
+
+    // The old access token no longer works, and the credentials are gone
+    let (status, _) = common::get_with_auth(&app, "/api/v1/users/me", &token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = common::post_json(
+        &app,
+        "/api/v1/auth/signin/email",
+        &json!({ "email": "gone@example.com", "password": "Password1" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn deletion_with_delete_policy_removes_games() {
+    let (app, state) = test_app().await;
+    let (token, _) = signup_user(&app, "gone@example.com", "gone", "Password1").await;
+    let game_id = create_game(&app, &token).await;
+
+    common::post_json_with_auth(
+        &app,
+        "/api/v1/users/me/delete",
+        &json!({ "password": "Password1", "games": "delete" }),
+        &token,
+    )
+    .await;
+
+    let later = Utc::now() + Duration::days(account_deletion::GRACE_PERIOD_DAYS + 1);
+    let deleted = account_deletion::run_due(&state.db, &state.config, later)
+        .await
+        .unwrap_or_default();
+    assert_eq!(deleted, 1);
+
+    let game_id = Uuid::parse_str(&game_id).unwrap_or_default();
+    let game = game::Entity::find_by_id(game_id)
+        .one(&state.db)
+        .await
+        .unwrap_or_default();
+    assert!(game.is_some_and(|g| g.deleted_at.is_some()));
+
+    let (status, _) = common::get(&app, &format!("/api/v1/games/{game_id}")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // The username is free again
+    signup_user(&app, "new@example.com", "gone", "Password1").await;
+}
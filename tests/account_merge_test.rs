@@ -0,0 +1,188 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveModelTrait;
+use sea_orm::ActiveValue::Set;
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::{auth_provider, game, game_version};
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a published public game with one version owned by `owner_id` and return its ID.
+async fn create_published_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Merged Game".to_string()),
+        slug: Set(format!("merged-game-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("published".to_string()),
+        visibility: Set("public".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    game_version::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        created_at: Set(now),
+        game_id: Set(id),
+        version_number: Set(1),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+/// Link a `provider` sign-in to `user_id`.
+async fn add_provider(state: &AppState, user_id: Uuid, provider: &str) -> anyhow::Result<()> {
+    auth_provider::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id),
+        provider: Set(provider.to_string()),
+        provider_id: Set(format!("{provider}-{user_id}")),
+        password_hash: Set(None),
+        provider_email: Set(None),
+        verification_token: Set(None),
+        token_expires_at: Set(None),
+        created_at: Set(Utc::now().fixed_offset()),
+    }
+    .insert(&state.db)
+    .await?;
+    Ok(())
+}
+
+fn username(user_id: Uuid) -> String {
+    format!("user_{}", &user_id.to_string()[..8])
+}
+
+async fn follow(app: &Router, token: &str, user_id: Uuid) {
+    let (status, body) = common::post_json_with_auth(
+        app,
+        &format!("/api/v1/users/{}/follow", username(user_id)),
+        &json!({}),
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT, "{body}");
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/v1/users/me/merge
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn merge_moves_providers_games_and_follows() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (survivor_id, survivor_token) = common::create_user(&state, "user").await?;
+    let (merged_id, merged_token) = common::create_user(&state, "user").await?;
+    let (fan_id, fan_token) = common::create_user(&state, "user").await?;
+    add_provider(&state, survivor_id, "google").await?;
+    add_provider(&state, merged_id, "github").await?;
+    create_published_game(&state, merged_id).await?;
+
+    // The fan follows both accounts; the merged account follows the survivor
+    follow(&app, &fan_token, survivor_id).await;
+    follow(&app, &fan_token, merged_id).await;
+    follow(&app, &merged_token, survivor_id).await;
+    follow(&app, &merged_token, fan_id).await;
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/users/me/merge",
+        &json!({ "token": merged_token }),
+        &survivor_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["mergedUserId"], merged_id.to_string());
+    assert_eq!(v["authProviders"], 1);
+    assert_eq!(v["games"], 1);
+    assert_eq!(v["follows"], 1);
+
+    let (_, body) = common::get(
+        &app,
+        &format!("/api/v1/users/{}/games", username(survivor_id)),
+    )
+    .await;
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["total"], 1);
+
+    let (_, body) = common::get(&app, &format!("/api/v1/users/{}", username(survivor_id))).await;
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["followerCount"], 1);
+
+    let (_, body) = common::get(
+        &app,
+        &format!("/api/v1/users/{}/followers", username(fan_id)),
+    )
+    .await;
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["total"], 1);
+
+    // The merged account is gone
+    let (status, _) = common::get_with_auth(&app, "/api/v1/users/me", &merged_token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = common::get(&app, &format!("/api/v1/users/{}", username(merged_id))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[tokio::test]
+async fn merge_rejects_invalid_self_and_conflicting_accounts() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (survivor_id, survivor_token) = common::create_user(&state, "user").await?;
+    let (merged_id, merged_token) = common::create_user(&state, "user").await?;
+    add_provider(&state, survivor_id, "google").await?;
+    add_provider(&state, merged_id, "google").await?;
+
+    for (token, expected) in [
+        ("not-a-token", StatusCode::UNAUTHORIZED),
+        (survivor_token.as_str(), StatusCode::BAD_REQUEST),
+        (merged_token.as_str(), StatusCode::CONFLICT),
+    ] {
+        let (status, body) = common::post_json_with_auth(
+            &app,
+            "/api/v1/users/me/merge",
+            &json!({ "token": token }),
+            &survivor_token,
+        )
+        .await;
+        assert_eq!(status, expected, "{body}");
+    }
+
+    // Nothing moved, and the other account still works
+    let (status, _) = common::get_with_auth(&app, "/api/v1/users/me", &merged_token).await;
+    assert_eq!(status, StatusCode::OK);
+    Ok(())
+}
@@ -0,0 +1,201 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveModelTrait;
+use sea_orm::ActiveValue::Set;
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::achievements;
+
+use aircade_api::entities::game;
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a private draft game owned by `owner_id` and return its ID.
+async fn create_private_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Team Project".to_string()),
+        slug: Set(format!("team-project-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("draft".to_string()),
+        visibility: Set("private".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        game_screen_code: Set(Some(
+            "function setup() { createCanvas(400, 400); }".to_string(),
+        )),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+fn username_of(user_id: Uuid) -> String {
+    format!("user_{}", &user_id.to_string()[..8])
+}
+
+/// The IDs of the achievements `username` has earned, in listing order.
+async fn achievement_ids(app: &Router, username: &str) -> Vec<String> {
+    let (status, body) = common::get(app, &format!("/api/v1/users/{username}/achievements")).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let all: Vec<serde_json::Value> = serde_json::from_str(&body).unwrap_or_default();
+    all.iter()
+        .map(|a| a["id"].as_str().unwrap_or_default().to_string())
+        .collect()
+}
+
+/// How many achievement notifications the user has received.
+async fn unlocked_notifications(app: &Router, token: &str) -> usize {
+    let (_, body) = common::get_with_auth(app, "/api/v1/users/me/notifications", token).await;
+    let all: Vec<serde_json::Value> = serde_json::from_str(&body).unwrap_or_default();
+    all.iter()
+        .filter(|n| n["kind"] == "achievement_unlocked")
+        .count()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Awarding
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn publishing_awards_first_publish_once() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner_id, token) = common::create_user(&state, "user").await?;
+    let username = username_of(owner_id);
+    let game_id = create_private_game(&state, owner_id).await?;
+
+    assert!(achievement_ids(&app, &username).await.is_empty());
+
+    for _ in 0..2 {
+        let (status, body) = common::post_json_with_auth(
+            &app,
+            &format!("/api/v1/games/{game_id}/publish"),
+            &json!({}),
+            &token,
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED, "{body}");
+    }
+
+    assert_eq!(achievement_ids(&app, &username).await, ["first_publish"]);
+    assert_eq!(unlocked_notifications(&app, &token).await, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn hosting_ten_sessions_awards_host() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (host_id, token) = common::create_user(&state, "user").await?;
+    let username = username_of(host_id);
+
+    for hosted in 1..=10 {
+        assert!(
+            achievement_ids(&app, &username).await.is_empty(),
+            "after {hosted}"
+        );
+        let (status, body) =
+            common::post_json_with_auth(&app, "/api/v1/sessions", &json!({}), &token).await;
+        assert_eq!(status, StatusCode::CREATED, "{body}");
+    }
+
+    assert_eq!(
+        achievement_ids(&app, &username).await,
+        ["ten_sessions_hosted"]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn hundredth_play_awards_game_owner() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner_id, _) = common::create_user(&state, "user").await?;
+    let username = username_of(owner_id);
+    let game_id = create_private_game(&state, owner_id).await?;
+
+    for play_count in [99, 100] {
+        game::ActiveModel {
+            id: Set(game_id),
+            play_count: Set(play_count),
+            ..Default::default()
+        }
+        .update(&state.db)
+        .await?;
+        achievements::game_played(&state.db, &state.notification_hub, game_id).await;
+        if play_count < 100 {
+            assert!(achievement_ids(&app, &username).await.is_empty());
+        }
+    }
+
+    assert_eq!(achievement_ids(&app, &username).await, ["hundred_plays"]);
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/v1/users/{username}/achievements
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn achievements_are_listed_in_catalog_order() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (user_id, _) = common::create_user(&state, "user").await?;
+    for id in [
+        achievements::TEN_SESSIONS_HOSTED,
+        achievements::FIRST_PUBLISH,
+    ] {
+        assert!(achievements::award(&state.db, &state.notification_hub, user_id, id).await?);
+    }
+    assert!(
+        !achievements::award(
+            &state.db,
+            &state.notification_hub,
+            user_id,
+            achievements::FIRST_PUBLISH
+        )
+        .await?
+    );
+
+    let (status, body) = common::get(
+        &app,
+        &format!("/api/v1/users/{}/achievements", username_of(user_id)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let all: Vec<serde_json::Value> = serde_json::from_str(&body)?;
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[0]["id"], "first_publish");
+    assert_eq!(all[0]["name"], "Published!");
+    assert!(all[0]["awardedAt"].is_string());
+    assert_eq!(all[1]["id"], "ten_sessions_hosted");
+
+    let (status, _) = common::get(&app, "/api/v1/users/nobody/achievements").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    Ok(())
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use migration::{Migrator, MigratorTrait};
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::sessions::ClientRole;
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Create a session as `token`'s user and return its ID.
+async fn create_session(app: &Router, token: &str) -> anyhow::Result<Uuid> {
+    let (status, body) =
+        common::post_json_with_auth(app, "/api/v1/sessions", &json!({}), token).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    Ok(v["id"].as_str().unwrap_or_default().parse()?)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Admin Sessions
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn moderators_list_live_sessions() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, moderator_token) = common::create_user(&state, "moderator").await?;
+    let (_, host_token) = common::create_user(&state, "user").await?;
+    let live = create_session(&app, &host_token).await?;
+    let ended = create_session(&app, &host_token).await?;
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/sessions/{ended}/end"),
+        &json!({}),
+        &host_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = common::get_with_auth(&app, "/api/v1/admin/sessions", &host_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) =
+        common::get_with_auth(&app, "/api/v1/admin/sessions", &moderator_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["total"], 1);
+    assert_eq!(v["data"][0]["id"], live.to_string());
+    assert_eq!(v["data"][0]["status"], "lobby");
+
+    let (_, body) = common::get_with_auth(
+        &app,
+        "/api/v1/admin/sessions?status=ended",
+        &moderator_token,
+    )
+    .await;
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["data"][0]["id"], ended.to_string());
+
+    let (status, _) = common::get_with_auth(
+        &app,
+        "/api/v1/admin/sessions?status=paused",
+        &moderator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn force_ending_a_session_tells_clients_why() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, moderator_token) = common::create_user(&state, "moderator").await?;
+    let (_, host_token) = common::create_user(&state, "user").await?;
+    let session_id = create_session(&app, &host_token).await?;
+    let uri = format!("/api/v1/admin/sessions/{session_id}/end");
+
+    let (host_tx, mut host_rx) = tokio::sync::mpsc::unbounded_channel();
+    state
+        .session_manager
+        .register(session_id, ClientRole::Host, host_tx);
+
+    let (status, _) =
+        common::post_json_with_auth(&app, &uri, &json!({ "reason": "Abuse" }), &host_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) =
+        common::post_json_with_auth(&app, &uri, &json!({ "reason": " " }), &moderator_token).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &uri,
+        &json!({ "reason": "Hosting abusive content" }),
+        &moderator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT, "{body}");
+
+    let first: serde_json::Value = serde_json::from_str(&host_rx.try_recv()?)?;
+    assert_eq!(first["type"], "session_terminated");
+    assert_eq!(first["payload"]["reason"], "Hosting abusive content");
+    let second: serde_json::Value = serde_json::from_str(&host_rx.try_recv()?)?;
+    assert_eq!(second["payload"]["status"], "ended");
+    assert!(
+        !state
+            .session_manager
+            .is_connected(session_id, &ClientRole::Host)
+    );
+
+    let (status, _) =
+        common::post_json_with_auth(&app, &uri, &json!({ "reason": "Again" }), &moderator_token)
+            .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
@@ -0,0 +1,227 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter};
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::{game, game_tag};
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a draft game owned by `owner_id` carrying `tag_ids` and return its ID.
+async fn create_tagged_game(
+    state: &AppState,
+    owner_id: Uuid,
+    tag_ids: &[Uuid],
+) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Tagged Game".to_string()),
+        slug: Set(format!("tagged-game-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("draft".to_string()),
+        visibility: Set("private".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    for tag_id in tag_ids {
+        game_tag::ActiveModel {
+            game_id: Set(id),
+            tag_id: Set(*tag_id),
+        }
+        .insert(&state.db)
+        .await?;
+    }
+
+    Ok(id)
+}
+
+async fn create_tag(app: &Router, token: &str, name: &str, category: &str) -> anyhow::Result<Uuid> {
+    let (status, body) = common::post_json_with_auth(
+        app,
+        "/api/v1/admin/tags",
+        &json!({ "name": name, "category": category }),
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    Ok(v["id"].as_str().unwrap_or_default().parse()?)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tag Catalog
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn tag_admin_requires_admin_role() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, token) = common::create_user(&state, "user").await?;
+
+    let (status, _) = common::get_with_auth(&app, "/api/v1/admin/tags", &token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/admin/tags",
+        &json!({ "name": "Racing", "category": "genre" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_and_rename_tag() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, token) = common::create_user(&state, "admin").await?;
+
+    let id = create_tag(&app, &token, "Tower Defense", "genre").await?;
+
+    // Duplicate names and unknown categories are rejected
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/admin/tags",
+        &json!({ "name": "Tower Defense", "category": "genre" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/admin/tags",
+        &json!({ "name": "Spooky", "category": "vibe" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = common::patch_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/tags/{id}"),
+        &json!({ "name": "Tower Defence", "slug": "tower-defence" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["name"], "Tower Defence");
+    assert_eq!(v["slug"], "tower-defence");
+    assert_eq!(v["category"], "genre");
+
+    let (_, body) = common::get(&app, "/api/v1/tags").await;
+    assert!(body.contains("tower-defence"), "{body}");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn merge_tag_moves_games() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (admin_id, token) = common::create_user(&state, "admin").await?;
+
+    let source = create_tag(&app, &token, "Brain Teaser", "genre").await?;
+    let target = create_tag(&app, &token, "Logic", "genre").await?;
+    let only_source = create_tagged_game(&state, admin_id, &[source]).await?;
+    let both = create_tagged_game(&state, admin_id, &[source, target]).await?;
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/tags/{source}/merge"),
+        &json!({ "intoTagId": target }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["gameCount"], 2);
+
+    let rows = game_tag::Entity::find()
+        .filter(game_tag::Column::GameId.is_in([only_source, both]))
+        .all(&state.db)
+        .await?;
+    assert_eq!(rows.len(), 2);
+    assert!(rows.iter().all(|r| r.tag_id == target));
+
+    // The merged tag is gone
+    let (status, _) = common::patch_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/tags/{source}"),
+        &json!({ "name": "Brain Teasers" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn retired_tag_is_hidden_and_unassignable() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (admin_id, token) = common::create_user(&state, "admin").await?;
+
+    let id = create_tag(&app, &token, "Retro", "mood").await?;
+    let game_id = create_tagged_game(&state, admin_id, &[]).await?;
+
+    let (status, _) =
+        common::delete_with_auth(&app, &format!("/api/v1/admin/tags/{id}"), &token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, body) = common::get(&app, "/api/v1/tags").await;
+    assert!(!body.contains("\"retro\""), "{body}");
+
+    // Admins still see it, marked as retired
+    let (_, body) = common::get_with_auth(&app, "/api/v1/admin/tags", &token).await;
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    let retro = v["data"]
+        .as_array()
+        .and_then(|tags| tags.iter().find(|t| t["slug"] == "retro"))
+        .cloned()
+        .unwrap_or_default();
+    assert!(retro["retiredAt"].is_string());
+
+    let (status, _) = common::put_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/tags"),
+        &json!({ "tagIds": [id] }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
@@ -0,0 +1,185 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::analytics_event;
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Every stored event named `name`.
+async fn events_named(state: &AppState, name: &str) -> anyhow::Result<Vec<analytics_event::Model>> {
+    state.analytics.flush(&state.db).await;
+    Ok(analytics_event::Entity::find()
+        .filter(analytics_event::Column::Name.eq(name))
+        .all(&state.db)
+        .await?)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Ingestion
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn signed_in_events_are_written_with_the_user() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (user_id, token) = common::create_user(&state, "user").await?;
+    let game_id = Uuid::new_v4();
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/analytics/events",
+        &json!({
+            "anonymousId": "device-1",
+            "events": [
+                { "name": "game_opened", "gameId": game_id, "properties": { "source": "search" } },
+                { "name": "editor_action", "gameId": game_id, "properties": { "action": "save" } },
+            ],
+        }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED, "{body}");
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(json["accepted"], 2);
+
+    let opened = events_named(&state, "game_opened").await?;
+    assert_eq!(opened.len(), 1);
+    assert_eq!(opened[0].user_id, Some(user_id));
+    assert_eq!(opened[0].anonymous_id, None);
+    assert_eq!(opened[0].game_id, Some(game_id));
+    assert_eq!(opened[0].properties["source"], "search");
+
+    let actions = events_named(&state, "editor_action").await?;
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].properties["action"], "save");
+    Ok(())
+}
+
+#[tokio::test]
+async fn anonymous_events_keep_the_client_identifier() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let session_id = Uuid::new_v4();
+    let occurred_at = Utc::now() - chrono::Duration::hours(1);
+
+    let (status, body) = common::post_json(
+        &app,
+        "/api/v1/analytics/events",
+        &json!({
+            "anonymousId": "device-1",
+            "events": [{
+                "name": "controller_latency",
+                "sessionId": session_id,
+                "properties": { "latencyMs": 42.5 },
+                "occurredAt": occurred_at.to_rfc3339(),
+            }],
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED, "{body}");
+
+    let events = events_named(&state, "controller_latency").await?;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].user_id, None);
+    assert_eq!(events[0].anonymous_id.as_deref(), Some("device-1"));
+    assert_eq!(events[0].session_id, Some(session_id));
+    assert_eq!(events[0].properties["latencyMs"], 42.5);
+    assert_eq!(events[0].occurred_at.timestamp(), occurred_at.timestamp());
+    Ok(())
+}
+
+#[tokio::test]
+async fn invalid_events_reject_the_whole_batch() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let game_id = Uuid::new_v4();
+    let valid = json!({ "name": "game_opened", "gameId": game_id });
+
+    for event in [
+        json!({ "name": "page_viewed" }),
+        json!({ "name": "game_opened" }),
+        json!({ "name": "game_opened", "gameId": game_id, "properties": { "referrer": "x" } }),
+        json!({ "name": "controller_latency", "sessionId": Uuid::new_v4() }),
+        json!({
+            "name": "controller_latency",
+            "sessionId": Uuid::new_v4(),
+            "properties": { "latencyMs": 120_000 },
+        }),
+        json!({ "name": "editor_action", "gameId": game_id }),
+        json!({ "name": "editor_action", "gameId": game_id, "properties": { "action": 3 } }),
+        json!({
+            "name": "game_opened",
+            "gameId": game_id,
+            "occurredAt": (Utc::now() + chrono::Duration::hours(1)).to_rfc3339(),
+        }),
+    ] {
+        let (status, body) = common::post_json(
+            &app,
+            "/api/v1/analytics/events",
+            &json!({ "events": [valid, event] }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{event}: {body}");
+        assert!(body.contains("events[1]"), "{body}");
+    }
+
+    let (status, _) =
+        common::post_json(&app, "/api/v1/analytics/events", &json!({ "events": [] })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::post_json(
+        &app,
+        "/api/v1/analytics/events",
+        &json!({ "events": vec![valid; 101] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    assert!(events_named(&state, "game_opened").await?.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn batches_are_rate_limited_per_client() -> anyhow::Result<()> {
+    let (app, _state) = test_app().await;
+    let batch = |anonymous_id: &str| {
+        json!({
+            "anonymousId": anonymous_id,
+            "events": [{ "name": "game_opened", "gameId": Uuid::new_v4() }],
+        })
+    };
+
+    for _ in 0..30 {
+        let (status, _) =
+            common::post_json(&app, "/api/v1/analytics/events", &batch("device-1")).await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+    }
+    let (status, headers, _) =
+        common::post_json_raw(&app, "/api/v1/analytics/events", &batch("device-1"), &[]).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(headers.contains_key("retry-after"));
+
+    // Another client has its own budget
+    let (status, _) = common::post_json(&app, "/api/v1/analytics/events", &batch("device-2")).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    Ok(())
+}
@@ -0,0 +1,208 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use migration::{Migrator, MigratorTrait};
+use serde_json::json;
+
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Create an announcement as `token`'s admin and return its JSON.
+async fn create(
+    app: &Router,
+    token: &str,
+    announcement: &serde_json::Value,
+) -> anyhow::Result<serde_json::Value> {
+    let (status, body) =
+        common::post_json_with_auth(app, "/api/v1/admin/announcements", announcement, token).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    Ok(serde_json::from_str(&body)?)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// /api/v1/announcements/active
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn only_announcements_in_their_window_are_active() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+    let now = Utc::now();
+
+    create(
+        &app,
+        &admin_token,
+        &json!({
+            "title": "Maintenance finished",
+            "body": "Thanks for your patience.",
+            "severity": "info",
+            "startsAt": now - Duration::days(2),
+            "endsAt": now - Duration::days(1),
+        }),
+    )
+    .await?;
+    create(
+        &app,
+        &admin_token,
+        &json!({
+            "title": "Game jam next week",
+            "body": "Sign-ups open soon.",
+            "severity": "info",
+            "startsAt": now + Duration::days(1),
+        }),
+    )
+    .await?;
+    let info = create(
+        &app,
+        &admin_token,
+        &json!({
+            "title": "New templates",
+            "body": "Try the new starter templates.",
+            "severity": "info",
+            "linkUrl": "/templates",
+        }),
+    )
+    .await?;
+    let critical = create(
+        &app,
+        &admin_token,
+        &json!({
+            "title": "Scheduled maintenance",
+            "body": "Sessions will be unavailable for ten minutes.",
+            "severity": "critical",
+            "dismissible": false,
+            "dismissalKey": "maintenance-2026-10",
+            "startsAt": now - Duration::hours(1),
+            "endsAt": now + Duration::hours(1),
+        }),
+    )
+    .await?;
+
+    // Public, and the most urgent banner comes first
+    let (status, body) = common::get(&app, "/api/v1/announcements/active").await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    let active = v["data"].as_array().cloned().unwrap_or_default();
+    assert_eq!(active.len(), 2, "{body}");
+    assert_eq!(active[0]["id"], critical["id"]);
+    assert_eq!(active[0]["dismissible"], false);
+    assert_eq!(active[0]["dismissalKey"], "maintenance-2026-10");
+    assert_eq!(active[1]["id"], info["id"]);
+    assert_eq!(active[1]["dismissible"], true);
+    assert_eq!(active[1]["linkUrl"], "/templates");
+    assert!(
+        active[1]["dismissalKey"]
+            .as_str()
+            .is_some_and(|k| !k.is_empty())
+    );
+
+    // Admins see all of them, scheduled and expired included
+    let (status, body) =
+        common::get_with_auth(&app, "/api/v1/admin/announcements", &admin_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["data"].as_array().map(Vec::len), Some(4));
+
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// /api/v1/admin/announcements
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn admins_manage_announcements() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+    let (_, user_token) = common::create_user(&state, "user").await?;
+    let announcement = json!({
+        "title": "Scheduled maintenance",
+        "body": "Sessions will be unavailable for ten minutes.",
+        "severity": "warning",
+    });
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/admin/announcements",
+        &announcement,
+        &user_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    for invalid in [
+        json!({ "title": "Hi", "body": "There", "severity": "urgent" }),
+        json!({ "title": "Hi", "body": "There", "severity": "info", "linkUrl": "javascript:alert(1)" }),
+        json!({ "title": "Hi", "body": "There", "severity": "info", "dismissalKey": "has spaces" }),
+        json!({
+            "title": "Hi",
+            "body": "There",
+            "severity": "info",
+            "startsAt": Utc::now(),
+            "endsAt": Utc::now() - Duration::hours(1),
+        }),
+    ] {
+        let (status, body) = common::post_json_with_auth(
+            &app,
+            "/api/v1/admin/announcements",
+            &invalid,
+            &admin_token,
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{invalid}: {body}");
+    }
+
+    let created = create(&app, &admin_token, &announcement).await?;
+    let id = created["id"].as_str().unwrap_or_default();
+    let uri = format!("/api/v1/admin/announcements/{id}");
+
+    // Replacing without a dismissal key shows the banner again to everyone
+    let (status, body) = common::put_json_with_auth(
+        &app,
+        &uri,
+        &json!({
+            "title": "Maintenance extended",
+            "body": "Sessions will be unavailable for an hour.",
+            "severity": "critical",
+        }),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let updated: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(updated["title"], "Maintenance extended");
+    assert_eq!(updated["severity"], "critical");
+    assert_eq!(updated["startsAt"], created["startsAt"]);
+    assert_ne!(updated["dismissalKey"], created["dismissalKey"]);
+
+    let (status, _) = common::delete_with_auth(&app, &uri, &user_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = common::delete_with_auth(&app, &uri, &admin_token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = common::delete_with_auth(&app, &uri, &admin_token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = common::get(&app, "/api/v1/announcements/active").await;
+    assert_eq!(status, StatusCode::OK);
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["data"].as_array().map(Vec::len), Some(0));
+
+    Ok(())
+}
@@ -0,0 +1,188 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use migration::{Migrator, MigratorTrait};
+use serde_json::json;
+
+async fn test_app() -> Router {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    aircade_api::routes::router(&state.config).with_state(state)
+}
+
+/// Helper: sign up a user and return (`access_token`, `refresh_token`).
+async fn signup_user(
+    app: &Router,
+    email: &str,
+    username: &str,
+    password: &str,
+) -> (String, String) {
+    let (status, body) = common::post_json(
+        app,
+        "/api/v1/auth/signup/email",
+        &json!({
+            "email": email,
+            "username": username,
+            "password": password,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "signup failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let token = json["token"].as_str().unwrap_or_default().to_string();
+    let refresh = json["refreshToken"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    (token, refresh)
+}
+
+/// Helper: create a personal access token with `scopes` and return the response JSON.
+async fn create_token(app: &Router, token: &str, scopes: &[&str]) -> serde_json::Value {
+    let (status, body) = common::post_json_with_auth(
+        app,
+        "/api/v1/users/me/tokens",
+        &json!({ "name": "CI deploy", "scopes": scopes }),
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "create token failed: {body}");
+    serde_json::from_str(&body).unwrap_or_default()
+}
+
+#[tokio::test]
+async fn create_and_list_tokens() {
+    let app = test_app().await;
+    let (token, _refresh) = signup_user(&app, "ci@example.com", "ciuser", "Password123").await;
+
+    let created = create_token(&app, &token, &["games:write", "games:read", "games:read"]).await;
+    let secret = created["secret"].as_str().unwrap_or_default();
+    assert!(secret.starts_with("acd_pat_"));
+    assert_eq!(created["scopes"], json!(["games:read", "games:write"]));
+    assert!(secret.starts_with(created["tokenPrefix"].as_str().unwrap_or("-")));
+    assert!(created["expiresAt"].is_null());
+
+    let (status, body) = common::get_with_auth(&app, "/api/v1/users/me/tokens", &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(json.as_array().map(Vec::len), Some(1));
+    assert_eq!(json[0]["name"], "CI deploy");
+    assert!(json[0]["secret"].is_null());
+}
+
+#[tokio::test]
+async fn create_token_validation() {
+    let app = test_app().await;
+    let (token, _refresh) =
+        signup_user(&app, "invalid@example.com", "invaliduser", "Password123").await;
+
+    for body in [
+        json!({ "name": "x", "scopes": [] }),
+        json!({ "name": "x", "scopes": ["admin"] }),
+        json!({ "name": " ", "scopes": ["games:read"] }),
+        json!({ "name": "x", "scopes": ["games:read"], "expiresInDays": 0 }),
+        json!({ "name": "x", "scopes": ["games:read"], "expiresInDays": 1000 }),
+    ] {
+        let (status, _body) =
+            common::post_json_with_auth(&app, "/api/v1/users/me/tokens", &body, &token).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    }
+}
+
+#[tokio::test]
+async fn token_authenticates_within_its_scopes() {
+    let app = test_app().await;
+    let (token, _refresh) =
+        signup_user(&app, "scoped@example.com", "scopeduser", "Password123").await;
+    let created = create_token(&app, &token, &["games:read"]).await;
+    let pat = created["secret"].as_str().unwrap_or_default();
+
+    let (status, _body) = common::get_with_auth(&app, "/api/v1/users/me/games", pat).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Writing games needs games:write
+    let (status, _body) =
+        common::post_json_with_auth(&app, "/api/v1/games", &json!({ "title": "From CI" }), pat)
+            .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Account and token management are never reachable with a token
+    let (status, _body) = common::get_with_auth(&app, "/api/v1/users/me/tokens", pat).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _body) = common::get_with_auth(&app, "/api/v1/users/me", pat).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Widening the scopes lets the same token publish
+    let id = created["id"].as_str().unwrap_or_default();
+    let (status, _body) = common::patch_json_with_auth(
+        &app,
+        &format!("/api/v1/users/me/tokens/{id}"),
+        &json!({ "scopes": ["games:read", "games:write", "profile:read"] }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) =
+        common::post_json_with_auth(&app, "/api/v1/games", &json!({ "title": "From CI" }), pat)
+            .await;
+    assert_eq!(status, StatusCode::CREATED, "create game failed: {body}");
+    let (status, body) = common::get_with_auth(&app, "/api/v1/users/me", pat).await;
+    assert_eq!(status, StatusCode::OK);
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(json["username"], "scopeduser");
+
+    let (_status, body) = common::get_with_auth(&app, "/api/v1/users/me/tokens", &token).await;
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert!(json[0]["lastUsedAt"].is_string());
+}
+
+#[tokio::test]
+async fn deleted_token_is_rejected() {
+    let app = test_app().await;
+    let (token, _refresh) =
+        signup_user(&app, "revoked@example.com", "revokeduser", "Password123").await;
+    let created = create_token(&app, &token, &["games:read"]).await;
+    let pat = created["secret"].as_str().unwrap_or_default();
+    let id = created["id"].as_str().unwrap_or_default();
+
+    let (status, _body) =
+        common::delete_with_auth(&app, &format!("/api/v1/users/me/tokens/{id}"), &token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _body) = common::get_with_auth(&app, "/api/v1/users/me/games", pat).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _body) =
+        common::delete_with_auth(&app, &format!("/api/v1/users/me/tokens/{id}"), &token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn other_users_tokens_are_hidden() {
+    let app = test_app().await;
+    let (owner, _refresh) =
+        signup_user(&app, "owner@example.com", "owneruser", "Password123").await;
+    let (other, _refresh) =
+        signup_user(&app, "other@example.com", "otheruser", "Password123").await;
+    let created = create_token(&app, &owner, &["games:read"]).await;
+    let id = created["id"].as_str().unwrap_or_default();
+
+    let (status, _body) = common::patch_json_with_auth(
+        &app,
+        &format!("/api/v1/users/me/tokens/{id}"),
+        &json!({ "name": "mine now" }),
+        &other,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _body) =
+        common::delete_with_auth(&app, &format!("/api/v1/users/me/tokens/{id}"), &other).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
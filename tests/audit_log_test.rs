@@ -0,0 +1,153 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveModelTrait;
+use sea_orm::ActiveValue::Set;
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::game;
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a published public game owned by `owner_id` and return its ID.
+async fn create_published_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Audited Game".to_string()),
+        slug: Set(format!("audited-game-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("published".to_string()),
+        visibility: Set("public".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/v1/admin/audit-log
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn admin_actions_are_logged_with_before_and_after() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (admin_id, admin_token) = common::create_user(&state, "admin").await?;
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/admin/tags",
+        &json!({ "name": "Tabletop Drafting", "category": "genre" }),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let tag: serde_json::Value = serde_json::from_str(&body)?;
+    let tag_id = tag["id"].as_str().unwrap_or_default().to_string();
+
+    let (status, body) = common::patch_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/tags/{tag_id}"),
+        &json!({ "name": "Card Drafting" }),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let (status, body) = common::get_with_auth(
+        &app,
+        &format!("/api/v1/admin/audit-log?targetId={tag_id}"),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let log: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(log["total"], 2);
+
+    // Newest first
+    let update = &log["data"][0];
+    assert_eq!(update["action"], "tag.update");
+    assert_eq!(update["actorId"], admin_id.to_string());
+    assert_eq!(update["actorRole"], "admin");
+    assert_eq!(update["before"]["name"], "Tabletop Drafting");
+    assert_eq!(update["after"]["name"], "Card Drafting");
+    assert_eq!(log["data"][1]["action"], "tag.create");
+    Ok(())
+}
+
+#[tokio::test]
+async fn moderator_actions_are_logged_but_only_admins_read_the_log() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (creator_id, _) = common::create_user(&state, "user").await?;
+    let (_, reporter_token) = common::create_user(&state, "user").await?;
+    let (moderator_id, moderator_token) = common::create_user(&state, "moderator").await?;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+    let game_id = create_published_game(&state, creator_id).await?;
+
+    let (_, body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/reports",
+        &json!({ "targetType": "game", "targetId": game_id, "reason": "spam" }),
+        &reporter_token,
+    )
+    .await;
+    let report: serde_json::Value = serde_json::from_str(&body)?;
+    let report_id = report["id"].as_str().unwrap_or_default().to_string();
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/reports/{report_id}/dismiss"),
+        &json!({ "note": "Not spam." }),
+        &moderator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let (status, _) =
+        common::get_with_auth(&app, "/api/v1/admin/audit-log", &moderator_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = common::get_with_auth(
+        &app,
+        &format!("/api/v1/admin/audit-log?actorId={moderator_id}&action=report.dismiss"),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let log: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(log["total"], 1);
+    let entry = &log["data"][0];
+    assert_eq!(entry["targetType"], "report");
+    assert_eq!(entry["targetId"], report_id.as_str());
+    assert_eq!(entry["before"]["status"], "open");
+    assert_eq!(entry["after"]["resolution"], "dismissed");
+    assert_eq!(entry["after"]["resolutionNote"], "Not spam.");
+    Ok(())
+}
@@ -0,0 +1,268 @@
+mod common;
+
+use axum::Router;
+use axum::http::{HeaderMap, StatusCode, header};
+use migration::{Migrator, MigratorTrait};
+use serde_json::json;
+
+use aircade_api::config::{Config, Environment};
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The app with cookie-based auth turned on.
+async fn test_app() -> Router {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(
+        db,
+        Config {
+            auth_cookies: true,
+            ..common::test_config()
+        },
+    );
+
+    aircade_api::routes::router(&state.config).with_state(state)
+}
+
+/// The `name=value` pairs set by a response's `Set-Cookie` headers.
+fn set_cookies(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .filter_map(|v| v.split(';').next()?.split_once('='))
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+fn cookie(cookies: &[(String, String)], name: &str) -> String {
+    cookies
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, v)| v.clone())
+        .unwrap_or_default()
+}
+
+/// Sign up and return the cookies the response set.
+async fn signup(app: &Router) -> (HeaderMap, String, Vec<(String, String)>) {
+    let (status, headers, body) = common::post_json_raw(
+        app,
+        "/api/v1/auth/signup/email",
+        &json!({
+            "email": "cookie@example.com",
+            "username": "cookiemonster",
+            "password": "Password123",
+        }),
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "signup failed: {body}");
+    let cookies = set_cookies(&headers);
+    (headers, body, cookies)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn tokens_are_delivered_as_http_only_cookies() {
+    let app = test_app().await;
+    let (headers, body, cookies) = signup(&app).await;
+
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(json["user"]["username"], "cookiemonster");
+    assert!(json.get("token").is_none());
+    assert!(json.get("refreshToken").is_none());
+
+    let set: Vec<&str> = headers
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .collect();
+    assert!(set.iter().any(|c| c.starts_with("aircade_access=")
+        && c.contains("HttpOnly")
+        && c.contains("Secure")
+        && c.contains("SameSite=Lax")));
+    assert!(set.iter().any(|c| c.starts_with("aircade_refresh=")
+        && c.contains("Path=/api/v1/auth")
+        && c.contains("HttpOnly")));
+    assert!(
+        set.iter()
+            .any(|c| c.starts_with("aircade_csrf=") && !c.contains("HttpOnly"))
+    );
+
+    let access = format!("aircade_access={}", cookie(&cookies, "aircade_access"));
+    let (status, _headers, body) =
+        common::send_raw(&app, "GET", "/api/v1/users/me", &[("cookie", &access)]).await;
+    assert_eq!(status, StatusCode::OK, "cookie auth failed: {body}");
+}
+
+#[tokio::test]
+async fn cookie_authenticated_mutations_need_the_csrf_token() {
+    let app = test_app().await;
+    let (_headers, _body, cookies) = signup(&app).await;
+    let csrf = cookie(&cookies, "aircade_csrf");
+    let jar = format!(
+        "aircade_refresh={}; aircade_csrf={csrf}",
+        cookie(&cookies, "aircade_refresh")
+    );
+
+    let (status, _headers, _body) =
+        common::send_raw(&app, "POST", "/api/v1/auth/refresh", &[("cookie", &jar)]).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _headers, _body) = common::send_raw(
+        &app,
+        "POST",
+        "/api/v1/auth/refresh",
+        &[("cookie", &jar), ("x-csrf-token", "forged")],
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, headers, body) = common::send_raw(
+        &app,
+        "POST",
+        "/api/v1/auth/refresh",
+        &[("cookie", &jar), ("x-csrf-token", &csrf)],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "refresh failed: {body}");
+    let rotated = set_cookies(&headers);
+    assert!(!cookie(&rotated, "aircade_access").is_empty());
+    assert_ne!(
+        cookie(&rotated, "aircade_refresh"),
+        cookie(&cookies, "aircade_refresh")
+    );
+}
+
+#[tokio::test]
+async fn frontend_on_another_origin_gets_the_csrf_token_from_bodies() {
+    let app = test_app().await;
+    let (_headers, body, cookies) = signup(&app).await;
+
+    // The browser sends the API's cookies but the frontend's scripts cannot read them
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let csrf = json["csrfToken"].as_str().unwrap_or_default().to_string();
+    assert_eq!(csrf, cookie(&cookies, "aircade_csrf"));
+    let jar = format!(
+        "aircade_refresh={}; aircade_csrf={}",
+        cookie(&cookies, "aircade_refresh"),
+        cookie(&cookies, "aircade_csrf")
+    );
+
+    let (status, headers, body) = common::send_raw(
+        &app,
+        "POST",
+        "/api/v1/auth/refresh",
+        &[
+            ("origin", "http://localhost:3001"),
+            ("cookie", &jar),
+            ("x-csrf-token", &csrf),
+        ],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "refresh failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let rotated = cookie(&set_cookies(&headers), "aircade_csrf");
+    assert_eq!(json["csrfToken"], rotated.as_str());
+    assert!(json.get("token").is_none());
+
+    // After a reload or an OAuth redirect the frontend asks for it
+    let jar = format!("aircade_csrf={rotated}");
+    let (status, _headers, body) = common::send_raw(
+        &app,
+        "GET",
+        "/api/v1/auth/csrf",
+        &[("origin", "http://localhost:3001"), ("cookie", &jar)],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(json["csrfToken"], rotated.as_str());
+
+    let (status, _headers, _body) = common::send_raw(&app, "GET", "/api/v1/auth/csrf", &[]).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn signout_clears_the_cookies() {
+    let app = test_app().await;
+    let (_headers, _body, cookies) = signup(&app).await;
+    let csrf = cookie(&cookies, "aircade_csrf");
+    let jar = format!(
+        "aircade_access={}; aircade_refresh={}; aircade_csrf={csrf}",
+        cookie(&cookies, "aircade_access"),
+        cookie(&cookies, "aircade_refresh")
+    );
+
+    let (status, headers, body) = common::send_raw(
+        &app,
+        "POST",
+        "/api/v1/auth/signout",
+        &[("cookie", &jar), ("x-csrf-token", &csrf)],
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT, "signout failed: {body}");
+    let cleared = set_cookies(&headers);
+    assert_eq!(cleared.len(), 3);
+    assert!(cleared.iter().all(|(_, value)| value.is_empty()));
+
+    // The refresh token was revoked along with the cookies
+    let (status, _headers, _body) = common::send_raw(
+        &app,
+        "POST",
+        "/api/v1/auth/refresh",
+        &[("cookie", &jar), ("x-csrf-token", &csrf)],
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn frontend_preflight_allows_the_csrf_header_with_credentials() {
+    let config = Config {
+        environment: Environment::Production,
+        frontend_url: "https://aircade.example".to_string(),
+        auth_cookies: true,
+        ..common::test_config()
+    };
+    let app = test_app()
+        .await
+        .layer(aircade_api::middleware::cors::layer(&config));
+
+    let (status, headers, _body) = common::send_raw(
+        &app,
+        "OPTIONS",
+        "/api/v1/auth/refresh",
+        &[
+            ("origin", "https://aircade.example"),
+            ("access-control-request-method", "POST"),
+            (
+                "access-control-request-headers",
+                "content-type,x-csrf-token",
+            ),
+        ],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_lowercase()
+    };
+    assert_eq!(
+        header("access-control-allow-origin"),
+        "https://aircade.example"
+    );
+    assert_eq!(header("access-control-allow-credentials"), "true");
+    assert!(header("access-control-allow-headers").contains("x-csrf-token"));
+}
@@ -1,13 +1,15 @@
 mod common;
 
+use std::net::SocketAddr;
+use std::sync::Arc;
+
 use axum::Router;
+use axum::extract::connect_info::MockConnectInfo;
 use axum::http::StatusCode;
 use migration::{Migrator, MigratorTrait};
 use serde_json::json;
 
-use aircade_api::config::{Config, Environment};
-use aircade_api::sessions::SessionManager;
-use aircade_api::state::AppState;
+use aircade_api::middleware::proxy::{self, TrustedProxies};
 
 async fn test_app() -> Router {
     let db = sea_orm::Database::connect("sqlite::memory:")
@@ -15,30 +17,19 @@ async fn test_app() -> Router {
         .unwrap_or_default();
     Migrator::up(&db, None).await.unwrap_or_default();
 
-    let state = AppState {
-        db,
-        config: Config {
-            database_url: String::new(),
-            server_host: std::net::IpAddr::from([127, 0, 0, 1]),
-            server_port: 0,
-            environment: Environment::Development,
-            log_level: "warn".to_string(),
-            jwt_secret: "test-secret-key-for-testing-only-32chars".to_string(),
-            jwt_access_expiration_secs: 900,
-            jwt_refresh_expiration_secs: 604_800,
-            google_client_id: String::new(),
-            google_client_secret: String::new(),
-            google_redirect_uri: String::new(),
-            github_client_id: String::new(),
-            github_client_secret: String::new(),
-            github_redirect_uri: String::new(),
-            frontend_url: "http://localhost:3001".to_string(),
-            upload_dir: "test_uploads".to_string(),
-        },
-        session_manager: SessionManager::new(),
-    };
-
-    aircade_api::routes::router().with_state(state)
+    let state = common::test_state(db, common::test_config());
+
+    aircade_api::routes::router(&state.config).with_state(state)
+}
+
+/// `app` reached directly from `peer`, with the client address resolved the way `main` does.
+fn from_peer(app: &Router, peer: [u8; 4]) -> Router {
+    app.clone()
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::new(TrustedProxies::default()),
+            proxy::resolve,
+        ))
+        .layer(MockConnectInfo(SocketAddr::from((peer, 443))))
 }
 
 /// Helper: sign up a user and return (`access_token`, `refresh_token`).
@@ -159,6 +150,20 @@ async fn signup_email_weak_password() {
     assert_eq!(status, StatusCode::BAD_REQUEST);
 }
 
+#[tokio::test]
+async fn password_policy_is_published_without_banned_list() {
+    let app = test_app().await;
+    let (status, body) = common::get(&app, "/api/v1/auth/password-policy").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(json["minLength"], 8);
+    assert_eq!(json["maxLength"], 128);
+    assert_eq!(json["requireUppercase"], false);
+    assert_eq!(json["requireSymbol"], false);
+    assert!(json.get("banned").is_none());
+}
+
 #[tokio::test]
 async fn signup_email_invalid_email() {
     let app = test_app().await;
@@ -237,6 +242,84 @@ async fn signin_email_wrong_password() {
     assert_eq!(status, StatusCode::UNAUTHORIZED);
 }
 
+#[tokio::test]
+async fn signin_email_rate_limited_per_account() {
+    let app = test_app().await;
+    signup_user(&app, "target@example.com", "targetuser", "Password123").await;
+
+    // Signup took one token from the account's bucket of ten
+    for _ in 0..9 {
+        let (status, _headers, _body) = common::post_json_raw(
+            &app,
+            "/api/v1/auth/signin/email",
+            &json!({ "email": "target@example.com", "password": "WrongPassword" }),
+            &[],
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    let (status, headers, body) = common::post_json_raw(
+        &app,
+        "/api/v1/auth/signin/email",
+        &json!({ "email": "Target@Example.com", "password": "Password123" }),
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(body.contains("RATE_LIMITED"));
+    let retry_after = headers
+        .get("retry-after")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or_default();
+    assert!((1..=30).contains(&retry_after), "Retry-After {retry_after}");
+
+    // Other accounts are unaffected
+    let (status, _body) = common::post_json(
+        &app,
+        "/api/v1/auth/signin/email",
+        &json!({ "email": "other@example.com", "password": "Password123" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn credentials_rate_limited_per_ip_despite_forged_forwarded_for() {
+    let app = test_app().await;
+    let attacker = from_peer(&app, [203, 0, 113, 9]);
+
+    // Every request claims a different client; the bucket of twenty is still the peer's
+    for i in 0..20 {
+        let (status, _headers, _body) = common::post_json_raw(
+            &attacker,
+            "/api/v1/auth/password-reset/request",
+            &json!({ "email": format!("victim{i}@example.com") }),
+            &[("x-forwarded-for", &format!("10.0.0.{i}"))],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    let (status, _headers, _body) = common::post_json_raw(
+        &attacker,
+        "/api/v1/auth/password-reset/request",
+        &json!({ "email": "victim20@example.com" }),
+        &[("x-forwarded-for", "10.0.1.1")],
+    )
+    .await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+
+    let (status, _headers, _body) = common::post_json_raw(
+        &from_peer(&app, [198, 51, 100, 4]),
+        "/api/v1/auth/password-reset/request",
+        &json!({ "email": "victim20@example.com" }),
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
 #[tokio::test]
 async fn signin_email_nonexistent() {
     let app = test_app().await;
@@ -542,3 +625,37 @@ async fn oauth_github_unconfigured() {
     let (status, _body) = common::get(&app, "/api/v1/auth/oauth/github").await;
     assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
 }
+
+#[tokio::test]
+async fn oauth_apple_unconfigured() {
+    let app = test_app().await;
+    let (status, _body) = common::get(&app, "/api/v1/auth/oauth/apple").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn oauth_apple_callback_rejects_invalid_state() {
+    use axum::body::Body;
+    use axum::http::{Request, header};
+    use tower::ServiceExt;
+
+    let app = test_app().await;
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/auth/oauth/apple/callback")
+        .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body(Body::from("code=abc&state=forged"))
+        .unwrap_or_default();
+    let status = app
+        .oneshot(request)
+        .await
+        .map_or(StatusCode::INTERNAL_SERVER_ERROR, |r| r.status());
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn jwks_unavailable_with_shared_secret() {
+    let app = test_app().await;
+    let (status, _body) = common::get(&app, "/.well-known/jwks.json").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
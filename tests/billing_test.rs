@@ -0,0 +1,334 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use axum::routing::post;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, EntityTrait, PaginatorTrait};
+use serde_json::json;
+use sha2::Sha256;
+use uuid::Uuid;
+
+use aircade_api::config::Config;
+use aircade_api::entities::{stripe_event, user};
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+const WEBHOOK_SECRET: &str = "whsec_test";
+
+/// Serve a mock Stripe API on a random local port and return its base URL.
+async fn start_mock_stripe() -> anyhow::Result<String> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}", listener.local_addr()?);
+
+    let router = Router::new()
+        .route(
+            "/v1/customers",
+            post(|| async { axum::Json(json!({ "id": "cus_test" })) }),
+        )
+        .route(
+            "/v1/checkout/sessions",
+            post(|| async {
+                axum::Json(
+                    json!({ "id": "cs_test", "url": "https://checkout.stripe.test/cs_test" }),
+                )
+            }),
+        );
+    tokio::spawn(async move { axum::serve(listener, router).await });
+
+    Ok(url)
+}
+
+async fn test_app(stripe_api_url: &str) -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(
+        db,
+        Config {
+            stripe_secret_key: "sk_test".to_string(),
+            stripe_webhook_secret: WEBHOOK_SECRET.to_string(),
+            stripe_pro_price_id: "price_pro".to_string(),
+            stripe_api_url: stripe_api_url.to_string(),
+            ..common::test_config()
+        },
+    );
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Send `event` to the Stripe webhook, signed with `secret`.
+async fn send_event(
+    app: &Router,
+    event: &serde_json::Value,
+    secret: &str,
+) -> anyhow::Result<StatusCode> {
+    let timestamp = Utc::now().timestamp();
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())?;
+    mac.update(format!("{timestamp}.{event}").as_bytes());
+    let header = format!(
+        "t={timestamp},v1={}",
+        hex::encode(mac.finalize().into_bytes())
+    );
+
+    let (status, _, _) = common::post_json_raw(
+        app,
+        "/api/v1/webhooks/stripe",
+        event,
+        &[("stripe-signature", header.as_str())],
+    )
+    .await;
+    Ok(status)
+}
+
+/// A subscription event with ID `id`, created by Stripe at unix time `created`.
+fn subscription_event(
+    id: &str,
+    kind: &str,
+    status: &str,
+    period_end: i64,
+    created: i64,
+) -> serde_json::Value {
+    json!({
+        "id": id,
+        "type": kind,
+        "created": created,
+        "data": { "object": {
+            "id": "sub_test",
+            "customer": "cus_test",
+            "status": status,
+            "current_period_end": period_end,
+        } },
+    })
+}
+
+/// Start a pro checkout, which creates the user's Stripe customer.
+async fn start_checkout(app: &Router, token: &str) {
+    let (status, body) = common::post_json_with_auth(
+        app,
+        "/api/v1/billing/checkout",
+        &json!({ "plan": "pro" }),
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+}
+
+async fn me(app: &Router, token: &str) -> serde_json::Value {
+    let (_, body) = common::get_with_auth(app, "/api/v1/users/me", token).await;
+    serde_json::from_str(&body).unwrap_or_default()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/v1/billing/checkout
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn checkout_returns_the_stripe_session_url() -> anyhow::Result<()> {
+    let (app, state) = test_app(&start_mock_stripe().await?).await;
+    let (user_id, token) = common::create_user(&state, "user").await?;
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/billing/checkout",
+        &json!({ "plan": "platinum" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/billing/checkout",
+        &json!({ "plan": "pro" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["url"], "https://checkout.stripe.test/cs_test");
+
+    let sub = aircade_api::entities::subscription::Entity::find_by_id(user_id)
+        .one(&state.db)
+        .await?;
+    assert_eq!(
+        sub.map(|s| s.stripe_customer_id).as_deref(),
+        Some("cus_test")
+    );
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/v1/webhooks/stripe
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn webhook_events_drive_the_plan() -> anyhow::Result<()> {
+    let (app, state) = test_app(&start_mock_stripe().await?).await;
+    let (_, token) = common::create_user(&state, "user").await?;
+    start_checkout(&app, &token).await;
+
+    let now = Utc::now().timestamp();
+    let period_end = now + 30 * 86_400;
+    let event = subscription_event(
+        "evt_created",
+        "customer.subscription.created",
+        "active",
+        period_end,
+        now,
+    );
+
+    assert_eq!(
+        send_event(&app, &event, "whsec_wrong").await?,
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(me(&app, &token).await["subscriptionPlan"], "free");
+
+    assert_eq!(
+        send_event(&app, &event, WEBHOOK_SECRET).await?,
+        StatusCode::NO_CONTENT
+    );
+    let v = me(&app, &token).await;
+    assert_eq!(v["subscriptionPlan"], "pro");
+    assert!(v["subscriptionExpiresAt"].is_string());
+
+    let event = subscription_event(
+        "evt_deleted",
+        "customer.subscription.deleted",
+        "canceled",
+        period_end,
+        now + 1,
+    );
+    assert_eq!(
+        send_event(&app, &event, WEBHOOK_SECRET).await?,
+        StatusCode::NO_CONTENT
+    );
+    let v = me(&app, &token).await;
+    assert_eq!(v["subscriptionPlan"], "free");
+    assert!(v["subscriptionExpiresAt"].is_null());
+    Ok(())
+}
+
+#[tokio::test]
+async fn redelivered_events_are_applied_once() -> anyhow::Result<()> {
+    let (app, state) = test_app(&start_mock_stripe().await?).await;
+    let (_, token) = common::create_user(&state, "user").await?;
+    start_checkout(&app, &token).await;
+
+    // Created in the same second, so only the event ID tells the redelivery apart
+    let now = Utc::now().timestamp();
+    let period_end = now + 30 * 86_400;
+    let created = subscription_event(
+        "evt_created",
+        "customer.subscription.created",
+        "active",
+        period_end,
+        now,
+    );
+    let canceled = subscription_event(
+        "evt_canceled",
+        "customer.subscription.updated",
+        "canceled",
+        period_end,
+        now,
+    );
+    for event in [&created, &canceled, &created] {
+        assert_eq!(
+            send_event(&app, event, WEBHOOK_SECRET).await?,
+            StatusCode::NO_CONTENT
+        );
+    }
+    assert_eq!(me(&app, &token).await["subscriptionPlan"], "free");
+
+    let recorded = stripe_event::Entity::find().count(&state.db).await?;
+    assert_eq!(recorded, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn events_older_than_the_last_applied_are_ignored() -> anyhow::Result<()> {
+    let (app, state) = test_app(&start_mock_stripe().await?).await;
+    let (_, token) = common::create_user(&state, "user").await?;
+    start_checkout(&app, &token).await;
+
+    let now = Utc::now().timestamp();
+    let period_end = now + 30 * 86_400;
+    let deleted = subscription_event(
+        "evt_deleted",
+        "customer.subscription.deleted",
+        "canceled",
+        period_end,
+        now,
+    );
+    let late = subscription_event(
+        "evt_late",
+        "customer.subscription.updated",
+        "active",
+        period_end,
+        now - 60,
+    );
+    for event in [&deleted, &late] {
+        assert_eq!(
+            send_event(&app, event, WEBHOOK_SECRET).await?,
+            StatusCode::NO_CONTENT
+        );
+    }
+
+    let v = me(&app, &token).await;
+    assert_eq!(v["subscriptionPlan"], "free");
+    assert!(v["subscriptionExpiresAt"].is_null());
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Subscription expiry job
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn lapsed_plans_are_downgraded_after_the_grace_period() -> anyhow::Result<()> {
+    let (_app, state) = test_app("").await;
+    let (lapsed_id, _) = common::create_user(&state, "user").await?;
+    let (current_id, _) = common::create_user(&state, "user").await?;
+    let now = Utc::now();
+
+    for (id, expires_at) in [
+        (lapsed_id, now - chrono::Duration::days(2)),
+        (current_id, now + chrono::Duration::days(2)),
+    ] {
+        user::ActiveModel {
+            id: Set(id),
+            subscription_plan: Set("pro".to_string()),
+            subscription_expires_at: Set(Some(expires_at.fixed_offset())),
+            ..Default::default()
+        }
+        .update(&state.db)
+        .await?;
+    }
+
+    let downgraded = aircade_api::jobs::subscription_expiry::run_due(&state.db, now).await?;
+    assert_eq!(downgraded, 1);
+
+    let plan = |id: Uuid| {
+        let db = state.db.clone();
+        async move {
+            user::Entity::find_by_id(id)
+                .one(&db)
+                .await
+                .ok()
+                .flatten()
+                .map(|u| u.subscription_plan)
+        }
+    };
+    assert_eq!(plan(lapsed_id).await.as_deref(), Some("free"));
+    assert_eq!(plan(current_id).await.as_deref(), Some("pro"));
+    Ok(())
+}
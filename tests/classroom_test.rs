@@ -0,0 +1,212 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveModelTrait;
+use sea_orm::ActiveValue::Set;
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::{game, game_version};
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a published public game with one version owned by `owner_id` and return its ID.
+async fn create_published_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Class Game".to_string()),
+        slug: Set(format!("class-game-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("published".to_string()),
+        visibility: Set("public".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    game_version::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        created_at: Set(now),
+        game_id: Set(id),
+        version_number: Set(1),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+/// Create a class taught by the holder of `token` and return its (`id`, `code`).
+async fn create_classroom(app: &Router, token: &str) -> anyhow::Result<(String, String)> {
+    let (status, body) = common::post_json_with_auth(
+        app,
+        "/api/v1/classrooms",
+        &json!({ "name": "Period 3" }),
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    Ok((
+        v["id"].as_str().unwrap_or_default().to_string(),
+        v["code"].as_str().unwrap_or_default().to_string(),
+    ))
+}
+
+/// Sign a student in and return the response status and access token.
+async fn student_signin(
+    app: &Router,
+    code: &str,
+    username: &str,
+    password: &str,
+) -> (StatusCode, String) {
+    let (status, body) = common::post_json(
+        app,
+        "/api/v1/auth/signin/classroom",
+        &json!({ "classCode": code, "username": username, "password": password }),
+    )
+    .await;
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    (status, v["token"].as_str().unwrap_or_default().to_string())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// /api/v1/classrooms
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn students_sign_in_with_the_class_code_and_see_class_games() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (teacher_id, teacher_token) = common::create_user(&state, "user").await?;
+    let (outsider_id, _) = common::create_user(&state, "user").await?;
+    let class_game = create_published_game(&state, teacher_id).await?;
+    let other_game = create_published_game(&state, outsider_id).await?;
+    let (id, code) = create_classroom(&app, &teacher_token).await?;
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/classrooms/{id}/students"),
+        &json!({ "names": ["Ada", "Linus"] }),
+        &teacher_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    let username = v["data"][0]["username"].as_str().unwrap_or_default();
+    let password = v["data"][0]["password"].as_str().unwrap_or_default();
+    assert_eq!(v["data"][0]["displayName"], "Ada");
+
+    let (status, _) = student_signin(&app, &code, username, "wrong-password").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, token) = student_signin(&app, &code.to_lowercase(), username, password).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, body) = common::get_with_auth(&app, "/api/v1/classrooms/mine", &token).await;
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["games"].as_array().map(Vec::len), Some(1));
+    assert_eq!(v["games"][0]["id"], class_game.to_string());
+
+    let (status, _) =
+        common::get_with_auth(&app, &format!("/api/v1/games/{class_game}"), &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) =
+        common::get_with_auth(&app, &format!("/api/v1/games/{other_game}"), &token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // The rest of the API is off limits
+    let (status, _) = common::get_with_auth(&app, "/api/v1/users/me/tokens", &token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/classrooms",
+        &json!({ "name": "My Own Class" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    Ok(())
+}
+
+#[tokio::test]
+async fn teachers_reset_and_delete_student_accounts() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, teacher_token) = common::create_user(&state, "user").await?;
+    let (_, other_token) = common::create_user(&state, "user").await?;
+    let (id, code) = create_classroom(&app, &teacher_token).await?;
+
+    let (_, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/classrooms/{id}/students"),
+        &json!({ "names": ["Grace"] }),
+        &teacher_token,
+    )
+    .await;
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    let student_id = v["data"][0]["userId"].as_str().unwrap_or_default();
+    let username = v["data"][0]["username"].as_str().unwrap_or_default();
+    let old_password = v["data"][0]["password"].as_str().unwrap_or_default();
+
+    // Only the teacher manages the class
+    let (status, _) =
+        common::get_with_auth(&app, &format!("/api/v1/classrooms/{id}"), &other_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, body) =
+        common::get_with_auth(&app, &format!("/api/v1/classrooms/{id}"), &teacher_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["studentCount"], 1);
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/classrooms/{id}/students/{student_id}/password"),
+        &json!({}),
+        &teacher_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    let new_password = v["password"].as_str().unwrap_or_default();
+
+    let (status, _) = student_signin(&app, &code, username, old_password).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = student_signin(&app, &code, username, new_password).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = common::delete_with_auth(
+        &app,
+        &format!("/api/v1/classrooms/{id}/students/{student_id}"),
+        &teacher_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = student_signin(&app, &code, username, new_password).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    Ok(())
+}
@@ -0,0 +1,225 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveModelTrait;
+use sea_orm::ActiveValue::Set;
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::game;
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a private draft game owned by `owner_id` and return its ID.
+async fn create_private_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Team Project".to_string()),
+        slug: Set(format!("team-project-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("draft".to_string()),
+        visibility: Set("private".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        game_screen_code: Set(Some("function setup() {}".to_string())),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+fn username(user_id: Uuid) -> String {
+    format!("user_{}", &user_id.to_string()[..8])
+}
+
+async fn add_collaborator(
+    app: &Router,
+    game_id: Uuid,
+    owner_token: &str,
+    user_id: Uuid,
+    role: &str,
+) -> StatusCode {
+    let (status, _) = common::post_json_with_auth(
+        app,
+        &format!("/api/v1/games/{game_id}/collaborators"),
+        &json!({ "username": username(user_id), "role": role }),
+        owner_token,
+    )
+    .await;
+    status
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Collaborators
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn collaborator_roles_control_access() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner_id, owner_token) = common::create_user(&state, "user").await?;
+    let (editor_id, editor_token) = common::create_user(&state, "user").await?;
+    let (viewer_id, viewer_token) = common::create_user(&state, "user").await?;
+    let (_, stranger_token) = common::create_user(&state, "user").await?;
+    let game_id = create_private_game(&state, owner_id).await?;
+    let uri = format!("/api/v1/games/{game_id}");
+
+    assert_eq!(
+        add_collaborator(&app, game_id, &owner_token, editor_id, "editor").await,
+        StatusCode::CREATED
+    );
+    assert_eq!(
+        add_collaborator(&app, game_id, &owner_token, viewer_id, "viewer").await,
+        StatusCode::CREATED
+    );
+
+    // Viewers see the private game and its code, but cannot change it
+    let (status, body) = common::get_with_auth(&app, &uri, &viewer_token).await;
+    assert_eq!(status, StatusCode::OK);
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["gameScreenCode"], "function setup() {}");
+
+    let update = json!({ "gameScreenCode": "function draw() {}" });
+    let (status, _) = common::patch_json_with_auth(&app, &uri, &update, &viewer_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = common::get_with_auth(&app, &format!("{uri}/assets"), &viewer_token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Editors can change the code
+    let (status, body) = common::patch_json_with_auth(&app, &uri, &update, &editor_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["gameScreenCode"], "function draw() {}");
+
+    // Editors cannot manage collaborators
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("{uri}/collaborators"),
+        &json!({ "username": username(owner_id), "role": "viewer" }),
+        &editor_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Everyone else still gets a 404 for the private game
+    let (status, _) = common::get_with_auth(&app, &uri, &stranger_token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn manage_collaborators() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner_id, owner_token) = common::create_user(&state, "user").await?;
+    let (member_id, member_token) = common::create_user(&state, "user").await?;
+    let game_id = create_private_game(&state, owner_id).await?;
+    let uri = format!("/api/v1/games/{game_id}/collaborators");
+
+    assert_eq!(
+        add_collaborator(&app, game_id, &owner_token, member_id, "owner").await,
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        add_collaborator(&app, game_id, &owner_token, owner_id, "editor").await,
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        add_collaborator(&app, game_id, &owner_token, member_id, "viewer").await,
+        StatusCode::CREATED
+    );
+    assert_eq!(
+        add_collaborator(&app, game_id, &owner_token, member_id, "editor").await,
+        StatusCode::CONFLICT
+    );
+
+    let (status, body) = common::patch_json_with_auth(
+        &app,
+        &format!("{uri}/{member_id}"),
+        &json!({ "role": "editor" }),
+        &owner_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["role"], "editor");
+
+    let (status, body) = common::get_with_auth(&app, &uri, &member_token).await;
+    assert_eq!(status, StatusCode::OK);
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["data"][0]["username"], username(member_id));
+
+    // Collaborators can leave on their own
+    let (status, _) =
+        common::delete_with_auth(&app, &format!("{uri}/{member_id}"), &member_token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = common::get_with_auth(&app, &uri, &member_token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn editing_lock_blocks_other_collaborators() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner_id, owner_token) = common::create_user(&state, "user").await?;
+    let (editor_id, editor_token) = common::create_user(&state, "user").await?;
+    let game_id = create_private_game(&state, owner_id).await?;
+    add_collaborator(&app, game_id, &owner_token, editor_id, "editor").await;
+    let uri = format!("/api/v1/games/{game_id}");
+    let update = json!({ "gameScreenCode": "function draw() {}" });
+
+    let (status, body) =
+        common::post_json_with_auth(&app, &format!("{uri}/lock"), &json!({}), &editor_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let (_, body) = common::get_with_auth(&app, &uri, &owner_token).await;
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["lockedBy"], editor_id.to_string());
+
+    // The lock holder can edit; everyone else is turned away
+    let (status, _) = common::patch_json_with_auth(&app, &uri, &update, &owner_token).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) =
+        common::post_json_with_auth(&app, &format!("{uri}/lock"), &json!({}), &owner_token).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = common::patch_json_with_auth(&app, &uri, &update, &editor_token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = common::delete_with_auth(&app, &format!("{uri}/lock"), &editor_token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, body) = common::patch_json_with_auth(&app, &uri, &update, &owner_token).await;
+    assert_eq!(status, StatusCode::OK);
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert!(v["lockedBy"].is_null());
+
+    Ok(())
+}
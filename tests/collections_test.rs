@@ -0,0 +1,198 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveModelTrait;
+use sea_orm::ActiveValue::Set;
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::game;
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a published public game owned by `owner_id` and return its ID.
+async fn create_published_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Party Hit".to_string()),
+        slug: Set(format!("party-hit-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("published".to_string()),
+        visibility: Set("public".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Collections
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Create a collection for the token's user and return its ID.
+async fn create_collection(app: &Router, token: &str, visibility: &str) -> anyhow::Result<String> {
+    let (status, body) = common::post_json_with_auth(
+        app,
+        "/api/v1/users/me/collections",
+        &json!({ "title": "Party night", "visibility": visibility }),
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    Ok(v["id"].as_str().unwrap_or_default().to_string())
+}
+
+#[tokio::test]
+async fn collection_crud_and_games() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (user_id, token) = common::create_user(&state, "user").await?;
+    let game_id = create_published_game(&state, user_id).await?;
+    let collection_id = create_collection(&app, &token, "public").await?;
+
+    // Add a game (twice — idempotent)
+    for _ in 0..2 {
+        let (status, body) = common::post_json_with_auth(
+            &app,
+            &format!("/api/v1/collections/{collection_id}/games"),
+            &json!({ "gameId": game_id }),
+            &token,
+        )
+        .await;
+        assert_eq!(status, StatusCode::NO_CONTENT, "{body}");
+    }
+
+    // Listed with a game count
+    let (status, body) = common::get_with_auth(&app, "/api/v1/users/me/collections", &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["total"], 1);
+    assert_eq!(v["data"][0]["gameCount"], 1);
+
+    // Update
+    let (status, body) = common::patch_json_with_auth(
+        &app,
+        &format!("/api/v1/users/me/collections/{collection_id}"),
+        &json!({ "title": "Family night" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["title"], "Family night");
+
+    // Remove the game
+    let (status, _) = common::delete_with_auth(
+        &app,
+        &format!("/api/v1/collections/{collection_id}/games/{game_id}"),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // Delete
+    let (status, _) = common::delete_with_auth(
+        &app,
+        &format!("/api/v1/users/me/collections/{collection_id}"),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = common::get(&app, &format!("/api/v1/collections/{collection_id}")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[tokio::test]
+async fn shared_collection_is_publicly_viewable() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (user_id, token) = common::create_user(&state, "user").await?;
+    let game_id = create_published_game(&state, user_id).await?;
+    let collection_id = create_collection(&app, &token, "unlisted").await?;
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/collections/{collection_id}/games"),
+        &json!({ "gameId": game_id }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, body) = common::get(&app, &format!("/api/v1/collections/{collection_id}")).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["title"], "Party night");
+    assert_eq!(v["games"][0]["id"], game_id.to_string());
+    Ok(())
+}
+
+#[tokio::test]
+async fn private_collection_hidden_from_others() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, owner_token) = common::create_user(&state, "user").await?;
+    let (_, other_token) = common::create_user(&state, "user").await?;
+    let collection_id = create_collection(&app, &owner_token, "private").await?;
+    let uri = format!("/api/v1/collections/{collection_id}");
+
+    let (status, _) = common::get_with_auth(&app, &uri, &owner_token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = common::get_with_auth(&app, &uri, &other_token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Other users cannot modify it either
+    let (status, _) = common::delete_with_auth(
+        &app,
+        &format!("/api/v1/users/me/collections/{collection_id}"),
+        &other_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_collection_rejects_invalid_visibility() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, token) = common::create_user(&state, "user").await?;
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/users/me/collections",
+        &json!({ "title": "Oops", "visibility": "friends" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    Ok(())
+}
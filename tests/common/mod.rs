@@ -1,9 +1,144 @@
+use std::sync::Arc;
+
 use axum::Router;
 use axum::body::Body;
 use axum::http::{Request, StatusCode};
 use http_body_util::BodyExt;
 use tower::ServiceExt;
 
+use chrono::Utc;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, DatabaseConnection};
+use uuid::Uuid;
+
+use aircade_api::auth::jwt::{self, KeySet};
+use aircade_api::config::{Config, Environment};
+use aircade_api::entities::user;
+use aircade_api::state::AppState;
+
+#[allow(dead_code)]
+/// Test helper: configuration for an app on an in-memory database with every optional
+/// integration off. Override fields with `Config { field, ..common::test_config() }`.
+pub fn test_config() -> Config {
+    Config {
+        database_url: String::new(),
+        server_host: std::net::IpAddr::from([127, 0, 0, 1]),
+        server_port: 0,
+        environment: Environment::Development,
+        log_level: "warn".to_string(),
+        log_format: aircade_api::config::LogFormat::Text,
+        sentry_dsn: String::new(),
+        slow_query_threshold_ms: 500,
+        alert_webhook_url: String::new(),
+        alert_error_rate: 0.05,
+        alert_ws_failure_rate: 0.2,
+        geoip_database: String::new(),
+        jwt_secret: "test-secret-key-for-testing-only-32chars".to_string(),
+        jwt_previous_secrets: Vec::new(),
+        jwt_private_key: String::new(),
+        jwt_previous_public_keys: String::new(),
+        jwt_access_expiration_secs: 900,
+        jwt_refresh_expiration_secs: 604_800,
+        google_client_id: String::new(),
+        google_client_secret: String::new(),
+        google_redirect_uri: String::new(),
+        github_client_id: String::new(),
+        github_client_secret: String::new(),
+        github_redirect_uri: String::new(),
+        apple_client_id: String::new(),
+        apple_team_id: String::new(),
+        apple_key_id: String::new(),
+        apple_private_key: String::new(),
+        apple_redirect_uri: String::new(),
+        oidc_providers: Vec::new(),
+        frontend_url: "http://localhost:3001".to_string(),
+        upload_dir: "test_uploads".to_string(),
+        storage_backend: aircade_api::config::StorageBackend::Database,
+        s3_endpoint: String::new(),
+        s3_bucket: String::new(),
+        s3_region: String::new(),
+        s3_access_key_id: String::new(),
+        s3_secret_access_key: String::new(),
+        code_scan_rules: aircade_api::validation::default_scan_rules(),
+        deleted_retention_days: 30,
+        hibp_enabled: false,
+        hibp_api_url: String::new(),
+        allowed_ips: Vec::new(),
+        admin_allowed_ips: Vec::new(),
+        denied_ips: Vec::new(),
+        trusted_proxies: Vec::new(),
+        auth_cookies: false,
+        password_policy: aircade_api::auth::password::PasswordPolicy::default(),
+        username_blocklist: aircade_api::auth::password::UsernameBlocklist::default(),
+        text_moderation: aircade_api::text_moderation::TextModeration::default(),
+        rate_limits: aircade_api::middleware::rate_limit::RateLimits::default(),
+        stripe_secret_key: String::new(),
+        stripe_webhook_secret: String::new(),
+        stripe_pro_price_id: String::new(),
+        stripe_api_url: String::new(),
+    }
+}
+
+#[allow(dead_code)]
+/// Test helper: application state for `config`, built the way `main` builds it.
+pub fn test_state(db: DatabaseConnection, config: Config) -> AppState {
+    let Ok(jwt_keys) = KeySet::from_config(&config) else {
+        unreachable!("test configurations only sign with a secret");
+    };
+    AppState {
+        db,
+        config,
+        jwt_keys: Arc::new(jwt_keys),
+        session_manager: aircade_api::sessions::SessionManager::new(),
+        notification_hub: aircade_api::notifications::NotificationHub::new(),
+        analytics: aircade_api::analytics::AnalyticsWriter::new(),
+    }
+}
+
+#[allow(dead_code)]
+/// Test helper: insert a verified, active user with `role` and return (`user_id`,
+/// `access_token`).
+pub async fn create_user(state: &AppState, role: &str) -> anyhow::Result<(Uuid, String)> {
+    let now = Utc::now().fixed_offset();
+    let user_id = Uuid::new_v4();
+
+    user::ActiveModel {
+        id: Set(user_id),
+        email: Set(format!("{user_id}@test.com")),
+        username: Set(format!("user_{}", &user_id.to_string()[..8])),
+        display_name: Set(None),
+        avatar_url: Set(None),
+        bio: Set(None),
+        location: Set(None),
+        website_url: Set(None),
+        pronouns: Set(None),
+        social_links: Set(None),
+        email_verified: Set(true),
+        verified: Set(false),
+        hide_from_search: Set(false),
+        hide_games: Set(false),
+        hide_activity: Set(false),
+        shadow_banned: Set(false),
+        role: Set(role.to_string()),
+        subscription_plan: Set("free".to_string()),
+        subscription_expires_at: Set(None),
+        account_status: Set("active".to_string()),
+        suspension_reason: Set(None),
+        last_login_at: Set(None),
+        last_login_ip: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+        deleted_at: Set(None),
+        deletion_scheduled_at: Set(None),
+        deletion_game_policy: Set(None),
+    }
+    .insert(&state.db)
+    .await?;
+
+    let token_pair = jwt::generate_token_pair(user_id, role, &state.jwt_keys)?;
+    Ok((user_id, token_pair.access_token))
+}
+
 #[allow(dead_code)]
 /// Test helper: send a GET request to the app and return (status, body).
 pub async fn get(app: &Router, uri: &str) -> (StatusCode, String) {
@@ -218,3 +353,149 @@ pub async fn put_json_with_auth(
 
     (status, body_str)
 }
+
+#[allow(dead_code)]
+/// Test helper: send a multipart POST with a single `file` part plus extra text fields.
+pub async fn post_multipart_with_auth(
+    app: &Router,
+    uri: &str,
+    file_name: &str,
+    content_type: &str,
+    data: &[u8],
+    fields: &[(&str, &str)],
+    token: &str,
+) -> (StatusCode, String) {
+    const BOUNDARY: &str = "aircade-test-boundary";
+
+    let mut body = Vec::new();
+    for (name, value) in fields {
+        body.extend_from_slice(
+            format!(
+                "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n"
+            )
+            .as_bytes(),
+        );
+    }
+    body.extend_from_slice(
+        format!(
+            "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\nContent-Type: {content_type}\r\n\r\n"
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(data);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header(
+            "content-type",
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        )
+        .header("authorization", format!("Bearer {token}"))
+        .body(Body::from(body))
+        .unwrap_or_default();
+
+    let response = app.clone().oneshot(request).await.unwrap_or_default();
+
+    let status = response.status();
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .map(http_body_util::Collected::to_bytes)
+        .unwrap_or_default();
+    let body_str = String::from_utf8(body.to_vec()).unwrap_or_default();
+
+    (status, body_str)
+}
+
+#[allow(dead_code)]
+/// Test helper: send a GET request with extra headers and return (status, headers, raw body).
+pub async fn get_raw(
+    app: &Router,
+    uri: &str,
+    headers: &[(&str, &str)],
+) -> (StatusCode, axum::http::HeaderMap, Vec<u8>) {
+    let mut builder = Request::builder().method("GET").uri(uri);
+    for (name, value) in headers {
+        builder = builder.header(*name, *value);
+    }
+    let request = builder.body(Body::empty()).unwrap_or_default();
+
+    let response = app.clone().oneshot(request).await.unwrap_or_default();
+
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .map(http_body_util::Collected::to_bytes)
+        .unwrap_or_default();
+
+    (status, headers, body.to_vec())
+}
+
+#[allow(dead_code)]
+/// Test helper: send a JSON POST request with extra headers and return (status, headers, body).
+pub async fn post_json_raw(
+    app: &Router,
+    uri: &str,
+    body: &serde_json::Value,
+    headers: &[(&str, &str)],
+) -> (StatusCode, axum::http::HeaderMap, String) {
+    let mut builder = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json");
+    for (name, value) in headers {
+        builder = builder.header(*name, *value);
+    }
+    let request = builder
+        .body(Body::from(serde_json::to_string(body).unwrap_or_default()))
+        .unwrap_or_default();
+
+    let response = app.clone().oneshot(request).await.unwrap_or_default();
+
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .map(http_body_util::Collected::to_bytes)
+        .unwrap_or_default();
+    let body_str = String::from_utf8(body.to_vec()).unwrap_or_default();
+
+    (status, headers, body_str)
+}
+
+#[allow(dead_code)]
+/// Test helper: send a bodyless request with extra headers and return (status, headers, body).
+pub async fn send_raw(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    headers: &[(&str, &str)],
+) -> (StatusCode, axum::http::HeaderMap, String) {
+    let mut builder = Request::builder().method(method).uri(uri);
+    for (name, value) in headers {
+        builder = builder.header(*name, *value);
+    }
+    let request = builder.body(Body::empty()).unwrap_or_default();
+
+    let response = app.clone().oneshot(request).await.unwrap_or_default();
+
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .map(http_body_util::Collected::to_bytes)
+        .unwrap_or_default();
+    let body_str = String::from_utf8(body.to_vec()).unwrap_or_default();
+
+    (status, headers, body_str)
+}
@@ -0,0 +1,248 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, EntityTrait};
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::game;
+use aircade_api::jobs::copyright_restore;
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a published public game owned by `owner_id` and return its ID.
+async fn create_published_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Infringing Game".to_string()),
+        slug: Set(format!("infringing-game-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("published".to_string()),
+        visibility: Set("public".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+/// A complete takedown notice against `game_id`.
+fn notice(game_id: Uuid) -> serde_json::Value {
+    json!({
+        "gameId": game_id,
+        "claimantName": "Jamie Rights",
+        "claimantEmail": "legal@studio.example",
+        "claimantAddress": "1 Studio Way, Springfield",
+        "copyrightedWork": "The sprites and music of Space Race (2019)",
+        "goodFaith": true,
+        "accurateUnderPenalty": true,
+        "signature": "Jamie Rights",
+    })
+}
+
+/// A complete counter-notice.
+fn counter_notice() -> serde_json::Value {
+    json!({
+        "name": "Sam Creator",
+        "address": "2 Maker Lane, Shelbyville",
+        "statement": "All sprites and music in this game are my own original work.",
+        "underPenalty": true,
+        "consentToJurisdiction": true,
+        "signature": "Sam Creator",
+    })
+}
+
+/// Send a notice against `game_id` and return its ID.
+async fn submit(app: &Router, game_id: Uuid) -> anyhow::Result<String> {
+    let (status, body) = common::post_json(app, "/api/v1/legal/takedown", &notice(game_id)).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["status"], "received");
+    Ok(v["id"].as_str().unwrap_or_default().to_string())
+}
+
+/// Move notice `id` through review to `decision` (`action` or `reject`).
+async fn decide(app: &Router, token: &str, id: &str, decision: &str) -> anyhow::Result<()> {
+    let (status, body) = common::post_json_with_auth(
+        app,
+        &format!("/api/v1/admin/copyright-notices/{id}/review"),
+        &json!({}),
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let (status, body) = common::post_json_with_auth(
+        app,
+        &format!("/api/v1/admin/copyright-notices/{id}/{decision}"),
+        &json!({ "note": "Checked against the claimant's registration." }),
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    Ok(())
+}
+
+async fn game_status(state: &AppState, id: Uuid) -> anyhow::Result<String> {
+    Ok(game::Entity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .map(|g| g.status)
+        .unwrap_or_default())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// /api/v1/legal/takedown
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn actioned_notice_is_restored_after_the_counter_notice_window() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+    let (_, moderator_token) = common::create_user(&state, "moderator").await?;
+    let (creator_id, creator_token) = common::create_user(&state, "user").await?;
+    let (_, other_token) = common::create_user(&state, "user").await?;
+    let game_id = create_published_game(&state, creator_id).await?;
+
+    // Every statement the law requires must be there
+    let mut incomplete = notice(game_id);
+    incomplete["goodFaith"] = json!(false);
+    let (status, _) = common::post_json(&app, "/api/v1/legal/takedown", &incomplete).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let id = submit(&app, game_id).await?;
+
+    let (status, _) =
+        common::get_with_auth(&app, "/api/v1/admin/copyright-notices", &moderator_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, body) =
+        common::get_with_auth(&app, "/api/v1/admin/copyright-notices", &admin_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["total"], 1);
+    assert_eq!(v["data"][0]["claimantEmail"], "legal@studio.example");
+
+    // A notice is reviewed before it is decided
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/copyright-notices/{id}/action"),
+        &json!({}),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    decide(&app, &admin_token, &id, "action").await?;
+    assert_eq!(game_status(&state, game_id).await?, "removed_by_moderation");
+
+    // Copyright removals are answered with a counter-notice, not an appeal
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/appeal"),
+        &json!({ "message": "This is my own original work, please restore it." }),
+        &creator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+
+    let uri = format!("/api/v1/games/{game_id}/counter-notice");
+    let (status, _) =
+        common::post_json_with_auth(&app, &uri, &counter_notice(), &other_token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, body) =
+        common::post_json_with_auth(&app, &uri, &counter_notice(), &creator_token).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["status"], "counter_noticed");
+    let (status, _) =
+        common::post_json_with_auth(&app, &uri, &counter_notice(), &creator_token).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // The game comes back only once the window has passed
+    let hub = &state.notification_hub;
+    assert_eq!(
+        copyright_restore::run_due(&state.db, hub, Utc::now()).await?,
+        0
+    );
+    assert_eq!(game_status(&state, game_id).await?, "removed_by_moderation");
+    let later = Utc::now() + Duration::days(15);
+    assert_eq!(copyright_restore::run_due(&state.db, hub, later).await?, 1);
+    assert_eq!(game_status(&state, game_id).await?, "published");
+    assert_eq!(copyright_restore::run_due(&state.db, hub, later).await?, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn rejected_notices_and_court_actions_keep_the_game_where_it_is() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+    let (creator_id, creator_token) = common::create_user(&state, "user").await?;
+    let game_id = create_published_game(&state, creator_id).await?;
+
+    let rejected = submit(&app, game_id).await?;
+    decide(&app, &admin_token, &rejected, "reject").await?;
+    assert_eq!(game_status(&state, game_id).await?, "published");
+
+    let actioned = submit(&app, game_id).await?;
+    decide(&app, &admin_token, &actioned, "action").await?;
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/counter-notice"),
+        &counter_notice(),
+        &creator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/copyright-notices/{actioned}/court-action"),
+        &json!({}),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let later = Utc::now() + Duration::days(15);
+    assert_eq!(
+        copyright_restore::run_due(&state.db, &state.notification_hub, later).await?,
+        0
+    );
+    assert_eq!(game_status(&state, game_id).await?, "removed_by_moderation");
+
+    // Decided notices leave the queue
+    let (status, body) =
+        common::get_with_auth(&app, "/api/v1/admin/copyright-notices", &admin_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["total"], 0);
+
+    Ok(())
+}
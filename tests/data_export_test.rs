@@ -0,0 +1,158 @@
+mod common;
+
+use std::io::{Cursor, Read};
+
+use axum::Router;
+use axum::http::StatusCode;
+use migration::{Migrator, MigratorTrait};
+use serde_json::json;
+use zip::ZipArchive;
+
+async fn test_app() -> Router {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    aircade_api::routes::router(&state.config).with_state(state)
+}
+
+/// Helper: sign up a user and return (`access_token`, `refresh_token`).
+async fn signup_user(
+    app: &Router,
+    email: &str,
+    username: &str,
+    password: &str,
+) -> (String, String) {
+    let (status, body) = common::post_json(
+        app,
+        "/api/v1/auth/signup/email",
+        &json!({
+            "email": email,
+            "username": username,
+            "password": password,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "signup failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let token = json["token"].as_str().unwrap_or_default().to_string();
+    let refresh = json["refreshToken"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    (token, refresh)
+}
+
+/// Request an export and poll until it has finished; returns its final status JSON.
+async fn finished_export(app: &Router, token: &str) -> serde_json::Value {
+    let (status, body) = common::get_with_auth(app, "/api/v1/users/me/export", token).await;
+    assert_eq!(status, StatusCode::ACCEPTED, "{body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let id = json["id"].as_str().unwrap_or_default().to_string();
+
+    let mut json = json;
+    for _ in 0..100 {
+        if json["status"] != "pending" {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        let (_, body) =
+            common::get_with_auth(app, &format!("/api/v1/users/me/export/{id}"), token).await;
+        json = serde_json::from_str(&body).unwrap_or_default();
+    }
+    json
+}
+
+fn read_entry(archive: &[u8], path: &str) -> String {
+    let mut contents = String::new();
+    if let Ok(mut zip) = ZipArchive::new(Cursor::new(archive))
+        && let Ok(mut file) = zip.by_name(path)
+    {
+        file.read_to_string(&mut contents).unwrap_or_default();
+    }
+    contents
+}
+
+// ──────────────────────────────────────────────────────────────────────────────
+// GET /api/v1/users/me/export
+// ──────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn export_assembles_downloadable_archive() {
+    let app = test_app().await;
+    let (token, _) = signup_user(&app, "porter@example.com", "porter", "Password1").await;
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/games",
+        &json!({ "title": "Portable" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let export = finished_export(&app, &token).await;
+    assert_eq!(export["status"], "ready", "{export}");
+    assert!(export["expiresAt"].is_string());
+    let id = export["id"].as_str().unwrap_or_default();
+
+    // A ready export is handed back instead of starting another
+    let (status, body) = common::get_with_auth(&app, "/api/v1/users/me/export", &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let again: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(again["id"], id);
+
+    let download = export["downloadUrl"].as_str().unwrap_or_default();
+    let auth = format!("Bearer {token}");
+    let (status, headers, archive) =
+        common::get_raw(&app, download, &[("authorization", &auth)]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["content-type"], "application/zip");
+
+    let profile: serde_json::Value =
+        serde_json::from_str(&read_entry(&archive, "profile.json")).unwrap_or_default();
+    assert_eq!(profile["email"], "porter@example.com");
+    assert_eq!(profile["authProviders"][0]["provider"], "email");
+    assert!(profile.get("passwordHash").is_none());
+
+    let games: serde_json::Value =
+        serde_json::from_str(&read_entry(&archive, "games.json")).unwrap_or_default();
+    assert_eq!(games[0]["title"], "Portable");
+
+    let sessions: serde_json::Value =
+        serde_json::from_str(&read_entry(&archive, "sessions.json")).unwrap_or_default();
+    assert_eq!(sessions.as_array().map_or(0, Vec::len), 1);
+    assert_eq!(read_entry(&archive, "reviews.json"), "[]");
+}
+
+#[tokio::test]
+async fn export_is_only_visible_to_its_owner() {
+    let app = test_app().await;
+    let (token, _) = signup_user(&app, "porter@example.com", "porter", "Password1").await;
+    let (other, _) = signup_user(&app, "nosy@example.com", "nosy", "Password1").await;
+
+    let export = finished_export(&app, &token).await;
+    let id = export["id"].as_str().unwrap_or_default();
+
+    let (status, _) =
+        common::get_with_auth(&app, &format!("/api/v1/users/me/export/{id}"), &other).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let auth = format!("Bearer {other}");
+    let (status, _, _) = common::get_raw(
+        &app,
+        &format!("/api/v1/users/me/export/{id}/download"),
+        &[("authorization", &auth)],
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn export_requires_auth() {
+    let app = test_app().await;
+    let (status, _) = common::get(&app, "/api/v1/users/me/export").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,229 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use aircade_api::entities::known_device;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> Router {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    aircade_api::routes::router(&state.config).with_state(state)
+}
+
+/// Helper: sign up a user and return (`access_token`, `refresh_token`).
+async fn signup_user(
+    app: &Router,
+    email: &str,
+    username: &str,
+    password: &str,
+) -> (String, String) {
+    let (status, body) = common::post_json(
+        app,
+        "/api/v1/auth/signup/email",
+        &json!({
+            "email": email,
+            "username": username,
+            "password": password,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "signup failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let token = json["token"].as_str().unwrap_or_default().to_string();
+    let refresh = json["refreshToken"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    (token, refresh)
+}
+
+/// Sign in with a `User-Agent` from `ip` and return the refresh token.
+async fn signin_from(app: &Router, email: &str, user_agent: &str, ip: &str) -> String {
+    let (status, _headers, body) = common::post_json_raw(
+        app,
+        "/api/v1/auth/signin/email",
+        &json!({ "email": email, "password": "Password123" }),
+        &[("user-agent", user_agent), ("x-forwarded-for", ip)],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "signin failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    json["refreshToken"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string()
+}
+
+async fn list_notifications(app: &Router, token: &str) -> Vec<serde_json::Value> {
+    let (status, body) = common::get_with_auth(app, "/api/v1/users/me/notifications", token).await;
+    assert_eq!(status, StatusCode::OK, "list notifications failed: {body}");
+    serde_json::from_str(&body).unwrap_or_default()
+}
+
+/// The token in a notification's "this wasn't me" link.
+fn revoke_token(notification: &serde_json::Value) -> String {
+    notification["link"]
+        .as_str()
+        .and_then(|link| link.split("token=").nth(1))
+        .unwrap_or_default()
+        .to_string()
+}
+
+async fn refresh(app: &Router, refresh_token: &str) -> StatusCode {
+    let (status, _body) = common::post_json(
+        app,
+        "/api/v1/auth/refresh",
+        &json!({ "refreshToken": refresh_token }),
+    )
+    .await;
+    status
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn unfamiliar_device_triggers_one_alert() {
+    let app = test_app().await;
+    let (token, _) = signup_user(&app, "alerts@example.com", "alerted", "Password123").await;
+    assert!(list_notifications(&app, &token).await.is_empty());
+
+    signin_from(&app, "alerts@example.com", "Firefox/130", "203.0.113.7").await;
+    let notifications = list_notifications(&app, &token).await;
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0]["kind"], "new_device_signin");
+    assert!(notifications[0]["readAt"].is_null());
+    assert!(!revoke_token(&notifications[0]).is_empty());
+
+    // Same browser, same network: familiar
+    signin_from(&app, "alerts@example.com", "Firefox/130", "203.0.113.99").await;
+    assert_eq!(list_notifications(&app, &token).await.len(), 1);
+
+    // Same browser, different network: unfamiliar
+    signin_from(&app, "alerts@example.com", "Firefox/130", "198.51.100.4").await;
+    assert_eq!(list_notifications(&app, &token).await.len(), 2);
+}
+
+#[tokio::test]
+async fn not_me_link_signs_out_the_new_device() {
+    let app = test_app().await;
+    let (token, own_refresh) =
+        signup_user(&app, "victim@example.com", "victim", "Password123").await;
+
+    let intruder_refresh = signin_from(&app, "victim@example.com", "curl/8.0", "192.0.2.1").await;
+    let notifications = list_notifications(&app, &token).await;
+    let link_token = revoke_token(&notifications[0]);
+
+    let (status, body) =
+        common::post_json(&app, "/api/v1/auth/not-me", &json!({ "token": link_token })).await;
+    assert_eq!(status, StatusCode::OK, "not-me failed: {body}");
+
+    assert_eq!(
+        refresh(&app, &intruder_refresh).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(refresh(&app, &own_refresh).await, StatusCode::OK);
+
+    // The link is single-use
+    let (status, _body) =
+        common::post_json(&app, "/api/v1/auth/not-me", &json!({ "token": link_token })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn not_me_covers_rotated_refresh_tokens() {
+    let app = test_app().await;
+    let (token, _) = signup_user(&app, "rotate@example.com", "rotator", "Password123").await;
+
+    let first = signin_from(&app, "rotate@example.com", "curl/8.0", "192.0.2.1").await;
+    let (status, body) = common::post_json(
+        &app,
+        "/api/v1/auth/refresh",
+        &json!({ "refreshToken": first }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "refresh failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let rotated = json["refreshToken"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+
+    let notifications = list_notifications(&app, &token).await;
+    let (status, _body) = common::post_json(
+        &app,
+        "/api/v1/auth/not-me",
+        &json!({ "token": revoke_token(&notifications[0]) }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(refresh(&app, &rotated).await, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn notifications_can_be_marked_read_by_their_owner() {
+    let app = test_app().await;
+    let (token, _) = signup_user(&app, "reader@example.com", "reader", "Password123").await;
+    let (other, _) = signup_user(&app, "other@example.com", "other", "Password123").await;
+    signin_from(&app, "reader@example.com", "Safari/17", "203.0.113.7").await;
+
+    let notifications = list_notifications(&app, &token).await;
+    let id = notifications[0]["id"].as_str().unwrap_or_default();
+    let uri = format!("/api/v1/users/me/notifications/{id}/read");
+
+    let (status, _body) = common::post_json_with_auth(&app, &uri, &json!({}), &other).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _body) = common::post_json_with_auth(&app, &uri, &json!({}), &token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(list_notifications(&app, &token).await[0]["readAt"].is_string());
+}
+
+#[tokio::test]
+async fn not_me_links_are_stored_hashed_and_expire() -> anyhow::Result<()> {
+    let db = sea_orm::Database::connect("sqlite::memory:").await?;
+    Migrator::up(&db, None).await?;
+    let state = common::test_state(db, common::test_config());
+    let app = aircade_api::routes::router(&state.config).with_state(state.clone());
+    let (token, _) = signup_user(&app, "expiry@example.com", "expiring", "Password123").await;
+
+    signin_from(&app, "expiry@example.com", "curl/8.0", "192.0.2.1").await;
+    let link_token = revoke_token(&list_notifications(&app, &token).await[0]);
+    let device = known_device::Entity::find()
+        .filter(known_device::Column::RevokeToken.is_not_null())
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("the alerted device is not recorded"))?;
+    assert_eq!(
+        device.revoke_token.as_deref(),
+        Some(hex::encode(Sha256::digest(link_token.as_bytes())).as_str())
+    );
+
+    let mut active: known_device::ActiveModel = device.into();
+    active.revoke_token_expires_at = Set(Some(
+        (Utc::now() - chrono::Duration::minutes(1)).fixed_offset(),
+    ));
+    active.update(&state.db).await?;
+
+    let (status, _body) =
+        common::post_json(&app, "/api/v1/auth/not-me", &json!({ "token": link_token })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    Ok(())
+}
@@ -0,0 +1,243 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use aircade_api::entities::{auth_provider, email_change};
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Helper: sign up a user and return (`access_token`, `refresh_token`).
+async fn signup_user(
+    app: &Router,
+    email: &str,
+    username: &str,
+    password: &str,
+) -> (String, String) {
+    let (status, body) = common::post_json(
+        app,
+        "/api/v1/auth/signup/email",
+        &json!({
+            "email": email,
+            "username": username,
+            "password": password,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "signup failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let token = json["token"].as_str().unwrap_or_default().to_string();
+    let refresh = json["refreshToken"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    (token, refresh)
+}
+
+/// Hex-encoded SHA-256 of a token, as stored.
+fn digest(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Request a change to `new_email` and return the (confirmation, verification) tokens of the
+/// staged change.
+///
+/// Only digests of the mailed tokens are stored, so known tokens are planted in their place.
+async fn request_change(
+    app: &Router,
+    state: &AppState,
+    token: &str,
+    new_email: &str,
+) -> (String, String) {
+    let (status, body) = common::patch_json_with_auth(
+        app,
+        "/api/v1/users/me/email",
+        &json!({ "newEmail": new_email, "password": "Password123" }),
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED, "email change failed: {body}");
+
+    let Some(change) = email_change::Entity::find()
+        .filter(email_change::Column::NewEmail.eq(new_email))
+        .one(&state.db)
+        .await
+        .unwrap_or_default()
+    else {
+        return (String::new(), String::new());
+    };
+    for stored in [&change.confirmation_token, &change.verification_token] {
+        assert!(
+            stored.len() == 64 && stored.chars().all(|c| c.is_ascii_hexdigit()),
+            "token stored in plaintext: {stored}"
+        );
+    }
+
+    let confirmation = format!("confirm-{new_email}");
+    let verification = format!("verify-{new_email}");
+    let mut active: email_change::ActiveModel = change.into();
+    active.confirmation_token = Set(digest(&confirmation));
+    active.verification_token = Set(digest(&verification));
+    let _ = active.update(&state.db).await.ok();
+    (confirmation, verification)
+}
+
+async fn my_email(app: &Router, token: &str) -> serde_json::Value {
+    let (_status, body) = common::get_with_auth(app, "/api/v1/users/me", token).await;
+    serde_json::from_str(&body).unwrap_or_default()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn email_changes_only_after_old_address_confirms() {
+    let (app, state) = test_app().await;
+    let (token, _) = signup_user(&app, "old@example.com", "changer", "Password123").await;
+
+    let (confirmation, verification) =
+        request_change(&app, &state, &token, "new@example.com").await;
+
+    // Still signed up under the old address
+    assert_eq!(my_email(&app, &token).await["email"], "old@example.com");
+    let (status, _) = common::post_json(
+        &app,
+        "/api/v1/auth/verify-email",
+        &json!({ "token": verification }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = common::post_json(
+        &app,
+        "/api/v1/auth/email-change/confirm",
+        &json!({ "token": confirmation }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "confirm failed: {body}");
+
+    let me = my_email(&app, &token).await;
+    assert_eq!(me["email"], "new@example.com");
+    assert_eq!(me["emailVerified"], false);
+
+    // Only a digest of the verification token is kept at rest
+    let stored = auth_provider::Entity::find()
+        .filter(auth_provider::Column::ProviderId.eq("new@example.com"))
+        .one(&state.db)
+        .await
+        .unwrap_or_default()
+        .and_then(|p| p.verification_token);
+    assert_eq!(stored, Some(digest(&verification)));
+
+    // The token sent to the new address now verifies it
+    let (status, _) = common::post_json(
+        &app,
+        "/api/v1/auth/verify-email",
+        &json!({ "token": verification }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(my_email(&app, &token).await["emailVerified"], true);
+
+    let (status, _) = common::post_json(
+        &app,
+        "/api/v1/auth/signin/email",
+        &json!({ "email": "new@example.com", "password": "Password123" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Confirmation tokens are single-use
+    let (status, _) = common::post_json(
+        &app,
+        "/api/v1/auth/email-change/confirm",
+        &json!({ "token": confirmation }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn new_request_replaces_pending_change() {
+    let (app, state) = test_app().await;
+    let (token, _) = signup_user(&app, "first@example.com", "replacer", "Password123").await;
+
+    let (stale_token, _) = request_change(&app, &state, &token, "second@example.com").await;
+    request_change(&app, &state, &token, "third@example.com").await;
+
+    let (status, _) = common::post_json(
+        &app,
+        "/api/v1/auth/email-change/confirm",
+        &json!({ "token": stale_token }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(my_email(&app, &token).await["email"], "first@example.com");
+}
+
+#[tokio::test]
+async fn expired_confirmation_is_rejected() {
+    let (app, state) = test_app().await;
+    let (token, _) = signup_user(&app, "late@example.com", "latecomer", "Password123").await;
+
+    let (confirmation, _) = request_change(&app, &state, &token, "later@example.com").await;
+    let change = email_change::Entity::find()
+        .filter(email_change::Column::ConfirmationToken.eq(digest(&confirmation)))
+        .one(&state.db)
+        .await
+        .unwrap_or_default();
+    if let Some(change) = change {
+        let mut active: email_change::ActiveModel = change.into();
+        active.expires_at = Set((Utc::now() - Duration::minutes(1)).fixed_offset());
+        let _ = active.update(&state.db).await.ok();
+    }
+
+    let (status, _) = common::post_json(
+        &app,
+        "/api/v1/auth/email-change/confirm",
+        &json!({ "token": confirmation }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(my_email(&app, &token).await["email"], "late@example.com");
+}
+
+#[tokio::test]
+async fn confirm_fails_if_address_was_taken_meanwhile() {
+    let (app, state) = test_app().await;
+    let (token, _) = signup_user(&app, "slow@example.com", "slowpoke", "Password123").await;
+
+    let (confirmation, _) = request_change(&app, &state, &token, "wanted@example.com").await;
+    signup_user(&app, "wanted@example.com", "quick", "Password123").await;
+
+    let (status, _) = common::post_json(
+        &app,
+        "/api/v1/auth/email-change/confirm",
+        &json!({ "token": confirmation }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(my_email(&app, &token).await["email"], "slow@example.com");
+}
@@ -0,0 +1,297 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use serde_json::json;
+
+use aircade_api::analytics::EXPERIMENT_EXPOSURE;
+
+use aircade_api::entities::analytics_event;
+use aircade_api::experiments::{self, Variant};
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Create an experiment as `token`'s admin and return its JSON.
+async fn create(
+    app: &Router,
+    token: &str,
+    experiment: &serde_json::Value,
+) -> anyhow::Result<serde_json::Value> {
+    let (status, body) =
+        common::post_json_with_auth(app, "/api/v1/admin/experiments", experiment, token).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    Ok(serde_json::from_str(&body)?)
+}
+
+/// The `data` of a `GET /experiments/assignments` response, as `(experiment, variant)` pairs.
+fn assignments(body: &str) -> anyhow::Result<Vec<(String, String)>> {
+    let json: serde_json::Value = serde_json::from_str(body)?;
+    Ok(json["data"]
+        .as_array()
+        .map(|data| {
+            data.iter()
+                .map(|a| {
+                    (
+                        a["experiment"].as_str().unwrap_or_default().to_string(),
+                        a["variant"].as_str().unwrap_or_default().to_string(),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+fn two_variants() -> serde_json::Value {
+    json!([{ "name": "control", "weight": 1 }, { "name": "treatment", "weight": 1 }])
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// /api/v1/experiments/assignments
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn anonymous_clients_get_stable_variants_of_running_experiments() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+
+    create(
+        &app,
+        &admin_token,
+        &json!({ "key": "new-lobby", "variants": two_variants(), "status": "running" }),
+    )
+    .await?;
+    create(
+        &app,
+        &admin_token,
+        &json!({ "key": "unreleased", "variants": two_variants() }),
+    )
+    .await?;
+
+    let uri = "/api/v1/experiments/assignments?anonymousId=client-1";
+    let (status, body) = common::get(&app, uri).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let first = assignments(&body)?;
+    assert_eq!(first.len(), 1, "only running experiments assign: {body}");
+    assert_eq!(first[0].0, "new-lobby");
+
+    let (_, body) = common::get(&app, uri).await;
+    assert_eq!(assignments(&body)?, first);
+
+    let variants = vec![
+        Variant {
+            name: "control".to_string(),
+            weight: 1,
+        },
+        Variant {
+            name: "treatment".to_string(),
+            weight: 1,
+        },
+    ];
+    let expected = experiments::assign("new-lobby", "client-1", &variants)
+        .map(|v| v.name.clone())
+        .unwrap_or_default();
+    assert_eq!(first[0].1, expected);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn signed_in_users_are_assigned_by_user_id() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+    let (user_id, user_token) = common::create_user(&state, "user").await?;
+
+    create(
+        &app,
+        &admin_token,
+        &json!({
+            "key": "checkout-copy",
+            "variants": [
+                { "name": "a", "weight": 1 },
+                { "name": "b", "weight": 1 },
+                { "name": "c", "weight": 1 },
+            ],
+            "status": "running",
+        }),
+    )
+    .await?;
+
+    let variants: Vec<Variant> = ["a", "b", "c"]
+        .iter()
+        .map(|name| Variant {
+            name: (*name).to_string(),
+            weight: 1,
+        })
+        .collect();
+    let expected = experiments::assign("checkout-copy", &user_id.to_string(), &variants)
+        .map(|v| v.name.clone())
+        .unwrap_or_default();
+
+    // The anonymous identifier is ignored once signed in
+    for uri in [
+        "/api/v1/experiments/assignments",
+        "/api/v1/experiments/assignments?anonymousId=some-device",
+    ] {
+        let (status, body) = common::get_with_auth(&app, uri, &user_token).await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        assert_eq!(
+            assignments(&body)?,
+            vec![("checkout-copy".to_string(), expected.clone())]
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn assignments_are_logged_as_exposures() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+    let (user_id, user_token) = common::create_user(&state, "user").await?;
+
+    create(
+        &app,
+        &admin_token,
+        &json!({ "key": "new-lobby", "variants": two_variants(), "status": "running" }),
+    )
+    .await?;
+
+    let (_, body) = common::get(&app, "/api/v1/experiments/assignments?anonymousId=client-1").await;
+    let anonymous = assignments(&body)?;
+    let (_, body) =
+        common::get_with_auth(&app, "/api/v1/experiments/assignments", &user_token).await;
+    let signed_in = assignments(&body)?;
+    state.analytics.flush(&state.db).await;
+
+    let exposures = analytics_event::Entity::find()
+        .filter(analytics_event::Column::Name.eq(EXPERIMENT_EXPOSURE))
+        .all(&state.db)
+        .await?;
+    assert_eq!(exposures.len(), 2);
+
+    let by_client = exposures
+        .iter()
+        .find(|e| e.anonymous_id.as_deref() == Some("client-1"));
+    assert!(by_client.is_some_and(|e| e.user_id.is_none()
+        && e.properties == json!({ "experiment": "new-lobby", "variant": anonymous[0].1 })));
+
+    let by_user = exposures.iter().find(|e| e.user_id == Some(user_id));
+    assert!(by_user.is_some_and(|e| e.anonymous_id.is_none()
+        && e.properties == json!({ "experiment": "new-lobby", "variant": signed_in[0].1 })));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn anonymous_callers_must_identify_themselves() -> anyhow::Result<()> {
+    let (app, _) = test_app().await;
+
+    let (status, _) = common::get(&app, "/api/v1/experiments/assignments").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let too_long = "x".repeat(65);
+    let (status, _) = common::get(
+        &app,
+        &format!("/api/v1/experiments/assignments?anonymousId={too_long}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// /api/v1/admin/experiments
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn stopping_an_experiment_stops_assigning_it() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+
+    let created = create(
+        &app,
+        &admin_token,
+        &json!({ "key": "new-lobby", "variants": two_variants(), "status": "running" }),
+    )
+    .await?;
+    let id = created["id"].as_str().unwrap_or_default();
+
+    let (status, body) = common::put_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/experiments/{id}"),
+        &json!({ "variants": two_variants(), "status": "stopped" }),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let updated: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(updated["key"], "new-lobby");
+    assert_eq!(updated["status"], "stopped");
+
+    let (_, body) = common::get(&app, "/api/v1/experiments/assignments?anonymousId=client-1").await;
+    assert!(assignments(&body)?.is_empty());
+
+    let (status, body) =
+        common::get_with_auth(&app, "/api/v1/admin/experiments", &admin_token).await;
+    assert_eq!(status, StatusCode::OK);
+    let list: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(list["data"][0]["status"], "stopped");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn invalid_experiments_are_rejected() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+    let (_, user_token) = common::create_user(&state, "user").await?;
+
+    let valid = json!({ "key": "new-lobby", "variants": two_variants() });
+    let (status, _) =
+        common::post_json_with_auth(&app, "/api/v1/admin/experiments", &valid, &user_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    create(&app, &admin_token, &valid).await?;
+    let (status, _) =
+        common::post_json_with_auth(&app, "/api/v1/admin/experiments", &valid, &admin_token).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    for invalid in [
+        json!({ "key": "New Lobby", "variants": two_variants() }),
+        json!({ "key": "one-variant", "variants": [{ "name": "control", "weight": 1 }] }),
+        json!({
+            "key": "no-traffic",
+            "variants": [{ "name": "a", "weight": 0 }, { "name": "b", "weight": 0 }],
+        }),
+        json!({
+            "key": "repeated",
+            "variants": [{ "name": "a", "weight": 1 }, { "name": "a", "weight": 1 }],
+        }),
+        json!({ "key": "bad-status", "variants": two_variants(), "status": "paused" }),
+    ] {
+        let (status, body) =
+            common::post_json_with_auth(&app, "/api/v1/admin/experiments", &invalid, &admin_token)
+                .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{invalid}: {body}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,182 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, EntityTrait};
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::{collection, game, game_version, user};
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Look up the username of `user_id`.
+async fn username(state: &AppState, user_id: Uuid) -> anyhow::Result<String> {
+    user::Entity::find_by_id(user_id)
+        .one(&state.db)
+        .await?
+        .map(|u| u.username)
+        .ok_or_else(|| anyhow::anyhow!("no user {user_id}"))
+}
+
+/// Insert a game owned by `owner_id` with `versions` published versions, `minutes_ago` old.
+async fn create_game(
+    state: &AppState,
+    owner_id: Uuid,
+    visibility: &str,
+    versions: i32,
+    minutes_ago: i64,
+) -> anyhow::Result<Uuid> {
+    let now = (Utc::now() - Duration::minutes(minutes_ago)).fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Party Hit".to_string()),
+        slug: Set(format!("party-hit-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("published".to_string()),
+        visibility: Set(visibility.to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    for version_number in 1..=versions {
+        game_version::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            created_at: Set(now + Duration::seconds(i64::from(version_number))),
+            game_id: Set(id),
+            version_number: Set(version_number),
+            ..Default::default()
+        }
+        .insert(&state.db)
+        .await?;
+    }
+
+    Ok(id)
+}
+
+/// Insert a collection owned by `owner_id`, `minutes_ago` old.
+async fn create_collection(
+    state: &AppState,
+    owner_id: Uuid,
+    visibility: &str,
+    minutes_ago: i64,
+) -> anyhow::Result<Uuid> {
+    let now = (Utc::now() - Duration::minutes(minutes_ago)).fixed_offset();
+    let id = Uuid::new_v4();
+
+    collection::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Party night".to_string()),
+        description: Set(None),
+        visibility: Set(visibility.to_string()),
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+async fn follow(app: &Router, token: &str, username: &str) {
+    let (status, body) = common::post_json_with_auth(
+        app,
+        &format!("/api/v1/users/{username}/follow"),
+        &json!({}),
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT, "{body}");
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/v1/users/me/feed
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn feed_lists_followed_creators_newest_first() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, token) = common::create_user(&state, "user").await?;
+    let (creator_id, _) = common::create_user(&state, "user").await?;
+    let creator = username(&state, creator_id).await?;
+    let (stranger_id, _) = common::create_user(&state, "user").await?;
+
+    let game_id = create_game(&state, creator_id, "public", 2, 30).await?;
+    let collection_id = create_collection(&state, creator_id, "public", 10).await?;
+    create_game(&state, stranger_id, "public", 1, 5).await?;
+    follow(&app, &token, &creator).await;
+
+    let (status, body) = common::get_with_auth(&app, "/api/v1/users/me/feed", &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["total"], 3);
+    assert_eq!(v["data"][0]["kind"], "collection_created");
+    assert_eq!(v["data"][0]["collection"]["id"], collection_id.to_string());
+    assert_eq!(v["data"][0]["creator"]["username"], creator);
+    assert_eq!(v["data"][1]["kind"], "version_published");
+    assert_eq!(v["data"][1]["versionNumber"], 2);
+    assert_eq!(v["data"][2]["kind"], "game_published");
+    assert_eq!(v["data"][2]["game"]["id"], game_id.to_string());
+
+    // Pages merge both sources
+    let (_, body) =
+        common::get_with_auth(&app, "/api/v1/users/me/feed?offset=1&limit=1", &token).await;
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["total"], 3);
+    assert_eq!(v["data"].as_array().map(Vec::len), Some(1));
+    assert_eq!(v["data"][0]["kind"], "version_published");
+    Ok(())
+}
+
+#[tokio::test]
+async fn feed_hides_non_public_activity() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, token) = common::create_user(&state, "user").await?;
+    let (creator_id, _) = common::create_user(&state, "user").await?;
+    let creator = username(&state, creator_id).await?;
+
+    create_game(&state, creator_id, "private", 1, 10).await?;
+    create_game(&state, creator_id, "unlisted", 1, 10).await?;
+    create_collection(&state, creator_id, "private", 5).await?;
+    follow(&app, &token, &creator).await;
+
+    let (status, body) = common::get_with_auth(&app, "/api/v1/users/me/feed", &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["total"], 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn feed_requires_auth() {
+    let (app, _state) = test_app().await;
+    let (status, _) = common::get(&app, "/api/v1/users/me/feed").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,136 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use migration::{Migrator, MigratorTrait};
+use serde_json::json;
+
+async fn test_app() -> Router {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    aircade_api::routes::router(&state.config).with_state(state)
+}
+
+/// Helper: sign up a user and return (`access_token`, `refresh_token`).
+async fn signup_user(
+    app: &Router,
+    email: &str,
+    username: &str,
+    password: &str,
+) -> (String, String) {
+    let (status, body) = common::post_json(
+        app,
+        "/api/v1/auth/signup/email",
+        &json!({
+            "email": email,
+            "username": username,
+            "password": password,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "signup failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let token = json["token"].as_str().unwrap_or_default().to_string();
+    let refresh = json["refreshToken"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    (token, refresh)
+}
+
+fn parse(body: &str) -> serde_json::Value {
+    serde_json::from_str(body).unwrap_or_default()
+}
+
+// ──────────────────────────────────────────────────────────────────────────────
+// POST/DELETE /api/v1/users/{username}/follow
+// ──────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn follow_and_unfollow() {
+    let app = test_app().await;
+    let (fan, _) = signup_user(&app, "fan@example.com", "fan", "Password123").await;
+    signup_user(&app, "maker@example.com", "maker", "Password123").await;
+
+    // Following twice is a no-op
+    for _ in 0..2 {
+        let (status, body) =
+            common::post_json_with_auth(&app, "/api/v1/users/maker/follow", &json!({}), &fan).await;
+        assert_eq!(status, StatusCode::NO_CONTENT, "follow failed: {body}");
+    }
+
+    let (_, body) = common::get(&app, "/api/v1/users/maker").await;
+    assert_eq!(parse(&body)["followerCount"], 1);
+
+    let (_, body) = common::get(&app, "/api/v1/users/maker/followers").await;
+    let followers = parse(&body);
+    assert_eq!(followers["total"], 1);
+    assert_eq!(followers["data"][0]["username"], "fan");
+
+    let (_, body) = common::get(&app, "/api/v1/users/fan/following").await;
+    let following = parse(&body);
+    assert_eq!(following["total"], 1);
+    assert_eq!(following["data"][0]["username"], "maker");
+
+    let (status, _) = common::delete_with_auth(&app, "/api/v1/users/maker/follow", &fan).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, body) = common::get(&app, "/api/v1/users/maker").await;
+    assert_eq!(parse(&body)["followerCount"], 0);
+}
+
+#[tokio::test]
+async fn cannot_follow_self() {
+    let app = test_app().await;
+    let (token, _) = signup_user(&app, "solo@example.com", "solo", "Password123").await;
+
+    let (status, _) =
+        common::post_json_with_auth(&app, "/api/v1/users/solo/follow", &json!({}), &token).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn follow_unknown_user() {
+    let app = test_app().await;
+    let (token, _) = signup_user(&app, "lost@example.com", "lost", "Password123").await;
+
+    let (status, _) =
+        common::post_json_with_auth(&app, "/api/v1/users/nobody/follow", &json!({}), &token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn follow_requires_auth() {
+    let app = test_app().await;
+    signup_user(&app, "shy@example.com", "shy", "Password123").await;
+
+    let (status, _) = common::post_json(&app, "/api/v1/users/shy/follow", &json!({})).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+// ──────────────────────────────────────────────────────────────────────────────
+// GET /api/v1/users/{username}/followers
+// ──────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn followers_are_paginated_newest_first() {
+    let app = test_app().await;
+    signup_user(&app, "star@example.com", "star", "Password123").await;
+    for name in ["first", "second", "third"] {
+        let (token, _) =
+            signup_user(&app, &format!("{name}@example.com"), name, "Password123").await;
+        common::post_json_with_auth(&app, "/api/v1/users/star/follow", &json!({}), &token).await;
+    }
+
+    let (status, body) = common::get(&app, "/api/v1/users/star/followers?limit=2").await;
+    assert_eq!(status, StatusCode::OK);
+    let page = parse(&body);
+    assert_eq!(page["total"], 3);
+    assert_eq!(page["data"].as_array().map(Vec::len), Some(2));
+    assert_eq!(page["data"][0]["username"], "third");
+}
@@ -0,0 +1,190 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use migration::{Migrator, MigratorTrait};
+use serde_json::json;
+
+async fn test_app() -> Router {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    aircade_api::routes::router(&state.config).with_state(state)
+}
+
+/// Helper: sign up a user and return (`access_token`, `refresh_token`).
+async fn signup_user(
+    app: &Router,
+    email: &str,
+    username: &str,
+    password: &str,
+) -> (String, String) {
+    let (status, body) = common::post_json(
+        app,
+        "/api/v1/auth/signup/email",
+        &json!({
+            "email": email,
+            "username": username,
+            "password": password,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "signup failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let token = json["token"].as_str().unwrap_or_default().to_string();
+    let refresh = json["refreshToken"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    (token, refresh)
+}
+
+fn parse(body: &str) -> serde_json::Value {
+    serde_json::from_str(body).unwrap_or_default()
+}
+
+async fn send_request(
+    app: &Router,
+    username: &str,
+    token: &str,
+) -> (StatusCode, serde_json::Value) {
+    let (status, body) = common::post_json_with_auth(
+        app,
+        &format!("/api/v1/users/{username}/friend-request"),
+        &json!({}),
+        token,
+    )
+    .await;
+    (status, parse(&body))
+}
+
+async fn friend_requests(app: &Router, token: &str) -> serde_json::Value {
+    let (status, body) =
+        common::get_with_auth(app, "/api/v1/users/me/friend-requests", token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    parse(&body)
+}
+
+async fn friends(app: &Router, token: &str) -> serde_json::Value {
+    let (status, body) = common::get_with_auth(app, "/api/v1/users/me/friends", token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    parse(&body)
+}
+
+// ──────────────────────────────────────────────────────────────────────────────
+// Friend requests
+// ──────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn request_accept_and_remove() {
+    let app = test_app().await;
+    let (alice, _) = signup_user(&app, "alice@example.com", "alice", "Password123").await;
+    let (bob, _) = signup_user(&app, "bob@example.com", "bob", "Password123").await;
+
+    let (status, request) = send_request(&app, "bob", &alice).await;
+    assert_eq!(status, StatusCode::CREATED, "{request}");
+    assert_eq!(request["status"], "pending");
+    assert_eq!(request["user"]["username"], "bob");
+
+    let (status, _) = send_request(&app, "bob", &alice).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    assert_eq!(
+        friend_requests(&app, &alice).await["outgoing"][0]["user"]["username"],
+        "bob"
+    );
+    let incoming = friend_requests(&app, &bob).await["incoming"].clone();
+    assert_eq!(incoming[0]["user"]["username"], "alice");
+    let id = incoming[0]["id"].as_str().unwrap_or_default().to_string();
+
+    // Only the recipient can accept
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/users/me/friend-requests/{id}/accept"),
+        &json!({}),
+        &alice,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/users/me/friend-requests/{id}/accept"),
+        &json!({}),
+        &bob,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let list = friends(&app, &alice).await;
+    assert_eq!(list["total"], 1);
+    assert_eq!(list["data"][0]["username"], "bob");
+    assert!(list["data"][0]["friendsSince"].is_string());
+    assert_eq!(friends(&app, &bob).await["data"][0]["username"], "alice");
+    assert_eq!(friend_requests(&app, &bob).await["incoming"], json!([]));
+
+    let (status, _) = send_request(&app, "alice", &bob).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _) = common::delete_with_auth(&app, "/api/v1/users/me/friends/alice", &bob).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(friends(&app, &alice).await["total"], 0);
+
+    let (status, _) = common::delete_with_auth(&app, "/api/v1/users/me/friends/alice", &bob).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn decline_and_withdraw() {
+    let app = test_app().await;
+    let (carol, _) = signup_user(&app, "carol@example.com", "carol", "Password123").await;
+    let (dave, _) = signup_user(&app, "dave@example.com", "dave", "Password123").await;
+
+    for decliner in [&dave, &carol] {
+        let (status, request) = send_request(&app, "dave", &carol).await;
+        assert_eq!(status, StatusCode::CREATED, "{request}");
+        let id = request["id"].as_str().unwrap_or_default();
+
+        let (status, _) = common::post_json_with_auth(
+            &app,
+            &format!("/api/v1/users/me/friend-requests/{id}/decline"),
+            &json!({}),
+            decliner,
+        )
+        .await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        assert_eq!(friend_requests(&app, &dave).await["incoming"], json!([]));
+    }
+
+    assert_eq!(friends(&app, &carol).await["total"], 0);
+}
+
+#[tokio::test]
+async fn requesting_back_accepts() {
+    let app = test_app().await;
+    let (erin, _) = signup_user(&app, "erin@example.com", "erin", "Password123").await;
+    let (frank, _) = signup_user(&app, "frank@example.com", "frank", "Password123").await;
+
+    send_request(&app, "frank", &erin).await;
+    let (status, request) = send_request(&app, "erin", &frank).await;
+    assert_eq!(status, StatusCode::OK, "{request}");
+    assert_eq!(request["status"], "accepted");
+
+    assert_eq!(friends(&app, &erin).await["data"][0]["username"], "frank");
+}
+
+#[tokio::test]
+async fn cannot_friend_self_or_unknown_user() {
+    let app = test_app().await;
+    let (token, _) = signup_user(&app, "solo@example.com", "solo", "Password123").await;
+
+    let (status, _) = send_request(&app, "solo", &token).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = send_request(&app, "nobody", &token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
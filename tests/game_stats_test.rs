@@ -0,0 +1,488 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::{DateTime, TimeZone, Utc};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, EntityTrait, PaginatorTrait};
+use uuid::Uuid;
+
+use aircade_api::entities::{analytics_event, game, game_play, login_event, player, session};
+use aircade_api::jobs::{game_stats, platform_stats};
+use aircade_api::state::AppState;
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a published public game and return its ID.
+async fn create_published_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Stats Game".to_string()),
+        slug: Set(format!("stats-game-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("published".to_string()),
+        visibility: Set("public".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+/// Insert an ended session hosted by `host_id` and return its ID.
+async fn create_session(
+    state: &AppState,
+    host_id: Uuid,
+    at: DateTime<Utc>,
+) -> anyhow::Result<Uuid> {
+    let id = Uuid::new_v4();
+
+    session::ActiveModel {
+        id: Set(id),
+        created_at: Set(at.fixed_offset()),
+        updated_at: Set(at.fixed_offset()),
+        ended_at: Set(Some(at.fixed_offset())),
+        host_id: Set(host_id),
+        game_id: Set(None),
+        game_version_id: Set(None),
+        session_code: Set(id.to_string()[..6].to_uppercase()),
+        status: Set("ended".to_string()),
+        max_players: Set(8),
+        playing_started_at: Set(None),
+        duration_seconds: Set(None),
+        peak_players: Set(None),
+        messages_relayed: Set(None),
+        disconnects: Set(None),
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+/// Record a finished play of `game_id` in `session_id`.
+async fn record_play(
+    state: &AppState,
+    game_id: Uuid,
+    session_id: Uuid,
+    started_at: DateTime<Utc>,
+    seconds: i64,
+    players: i32,
+) -> anyhow::Result<()> {
+    game_play::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        game_id: Set(game_id),
+        session_id: Set(session_id),
+        started_at: Set(started_at.fixed_offset()),
+        ended_at: Set(Some(
+            (started_at + chrono::Duration::seconds(seconds)).fixed_offset(),
+        )),
+        play_seconds: Set(seconds),
+        player_count: Set(players),
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(())
+}
+
+/// Add a player to `session_id`, the signed-in `user_id` or a guest, and return its ID.
+async fn join(state: &AppState, session_id: Uuid, user_id: Option<Uuid>) -> anyhow::Result<Uuid> {
+    let id = Uuid::new_v4();
+    player::ActiveModel {
+        id: Set(id),
+        created_at: Set(Utc::now().fixed_offset()),
+        session_id: Set(session_id),
+        user_id: Set(user_id),
+        display_name: Set("Player".to_string()),
+        avatar_url: Set(None),
+        connection_status: Set("disconnected".to_string()),
+        left_at: Set(None),
+        first_input_at: Set(None),
+        country: Set(None),
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+/// Record that `player_id` sent controller input.
+async fn send_input(state: &AppState, player_id: Uuid) -> anyhow::Result<()> {
+    player::ActiveModel {
+        id: Set(player_id),
+        first_input_at: Set(Some(Utc::now().fixed_offset())),
+        ..Default::default()
+    }
+    .update(&state.db)
+    .await?;
+
+    Ok(())
+}
+
+/// Record that `player_id` joined from `country`.
+async fn set_country(state: &AppState, player_id: Uuid, country: &str) -> anyhow::Result<()> {
+    player::ActiveModel {
+        id: Set(player_id),
+        country: Set(Some(country.to_string())),
+        ..Default::default()
+    }
+    .update(&state.db)
+    .await?;
+
+    Ok(())
+}
+
+/// Record a sign-in from `country` with `outcome`, now.
+async fn sign_in(state: &AppState, country: &str, outcome: &str) -> anyhow::Result<()> {
+    login_event::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(None),
+        identifier: Set(None),
+        provider: Set("email".to_string()),
+        outcome: Set(outcome.to_string()),
+        ip_address: Set(None),
+        user_agent: Set(None),
+        country: Set(Some(country.to_string())),
+        created_at: Set(Utc::now().fixed_offset()),
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(())
+}
+
+/// Store a `name` analytics event about `game_id` that occurred and was received `at`.
+async fn record_event(
+    state: &AppState,
+    name: &str,
+    game_id: Uuid,
+    at: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    analytics_event::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        name: Set(name.to_string()),
+        user_id: Set(None),
+        anonymous_id: Set(Some("device-1".to_string())),
+        game_id: Set(Some(game_id)),
+        session_id: Set(None),
+        properties: Set(serde_json::json!({})),
+        occurred_at: Set(at.fixed_offset()),
+        received_at: Set(at.fixed_offset()),
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(())
+}
+
+fn at(day: u32, hour: u32) -> anyhow::Result<DateTime<Utc>> {
+    Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0)
+        .single()
+        .ok_or_else(|| anyhow::anyhow!("invalid test date"))
+}
+
+#[tokio::test]
+async fn rollup_feeds_bucketed_creator_stats() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner, token) = common::create_user(&state, "user").await?;
+    let id = create_published_game(&state, owner).await?;
+
+    // Monday March 2nd, rolled up the day after
+    let early = create_session(&state, owner, at(2, 18)?).await?;
+    record_play(&state, id, early, at(2, 18)?, 60, 2).await?;
+    assert_eq!(game_stats::rollup(&state.db, at(3, 12)?).await?, 1);
+
+    let evening = create_session(&state, owner, at(9, 20)?).await?;
+    record_play(&state, id, evening, at(9, 20)?, 200, 2).await?;
+    let party = create_session(&state, owner, at(10, 10)?).await?;
+    record_play(&state, id, party, at(10, 10)?, 300, 3).await?;
+    record_play(&state, id, party, at(10, 11)?, 100, 3).await?;
+    assert_eq!(game_stats::rollup(&state.db, at(10, 12)?).await?, 2);
+
+    let (status, body) = common::get_with_auth(
+        &app,
+        &format!("/api/v1/games/{id}/stats?from=2026-03-02&to=2026-03-10"),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(json["bucket"], "day");
+    assert_eq!(json["totals"]["plays"], 4);
+    assert_eq!(json["totals"]["uniqueSessions"], 3);
+    assert_eq!(json["totals"]["playSeconds"], 660);
+    assert_eq!(json["totals"]["averageSessionSeconds"], 220);
+    assert_eq!(json["totals"]["averagePlayers"], 2.5);
+    assert_eq!(json["totals"]["peakPlayers"], 3);
+
+    let days = json["data"].as_array().cloned().unwrap_or_default();
+    assert_eq!(days.len(), 9);
+    assert_eq!(days[0]["start"], "2026-03-02");
+    assert_eq!(days[1]["plays"], 0);
+    assert_eq!(days[8]["plays"], 2);
+    assert_eq!(days[8]["uniqueSessions"], 1);
+
+    let (status, body) = common::get_with_auth(
+        &app,
+        &format!("/api/v1/games/{id}/stats?bucket=week&from=2026-03-02&to=2026-03-10"),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    let weeks = json["data"].as_array().cloned().unwrap_or_default();
+    assert_eq!(weeks.len(), 2);
+    assert_eq!(weeks[1]["start"], "2026-03-09");
+    assert_eq!(weeks[1]["plays"], 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn stats_are_creator_only() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner, token) = common::create_user(&state, "user").await?;
+    let (_other, other_token) = common::create_user(&state, "user").await?;
+    let id = create_published_game(&state, owner).await?;
+
+    let (status, _) =
+        common::get_with_auth(&app, &format!("/api/v1/games/{id}/stats"), &other_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) =
+        common::get_with_auth(&app, &format!("/api/v1/games/{id}/stats"), &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(json["data"].as_array().map(Vec::len), Some(30));
+    assert_eq!(json["totals"]["plays"], 0);
+
+    let (status, _) = common::get_with_auth(
+        &app,
+        &format!("/api/v1/games/{id}/stats?from=2026-03-10&to=2026-03-01"),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::get_with_auth(
+        &app,
+        &format!("/api/v1/games/{id}/stats?bucket=hour"),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[tokio::test]
+async fn rollup_counts_unique_players_and_opens() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner, token) = common::create_user(&state, "user").await?;
+    let (regular, _) = common::create_user(&state, "user").await?;
+    let id = create_published_game(&state, owner).await?;
+
+    // The same signed-in player in two sessions, plus a guest in each
+    for hour in [18, 21] {
+        let session_id = create_session(&state, owner, at(2, hour)?).await?;
+        join(&state, session_id, Some(regular)).await?;
+        join(&state, session_id, None).await?;
+        record_play(&state, id, session_id, at(2, hour)?, 60, 2).await?;
+    }
+    for hour in [17, 18, 21] {
+        record_event(&state, "game_opened", id, at(2, hour)?).await?;
+    }
+    record_event(&state, "editor_action", id, at(2, 19)?).await?;
+    // Opens of games that do not exist are not rolled up
+    record_event(&state, "game_opened", Uuid::new_v4(), at(2, 19)?).await?;
+
+    assert_eq!(game_stats::rollup(&state.db, at(3, 12)?).await?, 1);
+
+    let (status, body) = common::get_with_auth(
+        &app,
+        &format!("/api/v1/games/{id}/stats?from=2026-03-02&to=2026-03-02"),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(json["totals"]["plays"], 2);
+    assert_eq!(json["totals"]["uniquePlayers"], 3);
+    assert_eq!(json["totals"]["opens"], 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn platform_rollup_feeds_admin_stats() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner, _) = common::create_user(&state, "user").await?;
+    let (_admin, admin_token) = common::create_user(&state, "admin").await?;
+    let first = create_published_game(&state, owner).await?;
+    let second = create_published_game(&state, owner).await?;
+
+    // Sessions created and ended at the same instant, so their durations are zero
+    let evening = create_session(&state, owner, at(2, 18)?).await?;
+    join(&state, evening, Some(owner)).await?;
+    join(&state, evening, None).await?;
+    record_play(&state, first, evening, at(2, 18)?, 120, 2).await?;
+    record_play(&state, second, evening, at(2, 19)?, 60, 2).await?;
+    let night = create_session(&state, owner, at(2, 22)?).await?;
+    join(&state, night, Some(owner)).await?;
+    record_play(&state, first, night, at(2, 22)?, 30, 1).await?;
+    record_event(&state, "game_opened", first, at(2, 17)?).await?;
+    record_event(&state, "editor_action", second, at(3, 9)?).await?;
+
+    assert_eq!(platform_stats::rollup(&state.db, at(3, 12)?).await?, 3);
+
+    let (status, body) = common::get_with_auth(
+        &app,
+        "/api/v1/admin/stats?from=2026-03-02&to=2026-03-03",
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    let days = json["data"].as_array().cloned().unwrap_or_default();
+    assert_eq!(days.len(), 2);
+    assert_eq!(days[0]["sessions"], 2);
+    assert_eq!(days[0]["plays"], 3);
+    assert_eq!(days[0]["playSeconds"], 210);
+    assert_eq!(days[0]["uniquePlayers"], 2);
+    assert_eq!(days[0]["gamesPlayed"], 2);
+    assert_eq!(days[0]["events"], 1);
+    assert_eq!(days[1]["plays"], 0);
+    assert_eq!(json["totals"]["events"], 2);
+
+    let (_, token) = common::create_user(&state, "user").await?;
+    let (status, _) = common::get_with_auth(&app, "/api/v1/admin/stats", &token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    Ok(())
+}
+
+#[tokio::test]
+async fn analytics_events_are_pruned_after_retention() -> anyhow::Result<()> {
+    let (_app, state) = test_app().await;
+    let (owner, _) = common::create_user(&state, "user").await?;
+    let id = create_published_game(&state, owner).await?;
+
+    record_event(&state, "game_opened", id, at(1, 12)?).await?;
+    record_event(&state, "game_opened", id, at(20, 12)?).await?;
+
+    let now = at(1, 12)? + chrono::Duration::days(aircade_api::analytics::RETENTION_DAYS + 1);
+    assert_eq!(platform_stats::prune_events(&state.db, now).await?, 1);
+    assert_eq!(analytics_event::Entity::find().count(&state.db).await?, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn stats_include_the_join_to_play_funnel() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner, token) = common::create_user(&state, "user").await?;
+    let (regular, _) = common::create_user(&state, "user").await?;
+    let (once, _) = common::create_user(&state, "user").await?;
+    let id = create_published_game(&state, owner).await?;
+
+    // A regular who plays both nights, a one-off player, and a guest who never touches
+    // their controller
+    let first = create_session(&state, owner, at(2, 18)?).await?;
+    let player = join(&state, first, Some(regular)).await?;
+    send_input(&state, player).await?;
+    let player = join(&state, first, Some(once)).await?;
+    send_input(&state, player).await?;
+    join(&state, first, None).await?;
+    record_play(&state, id, first, at(2, 18)?, 60, 3).await?;
+
+    let second = create_session(&state, owner, at(3, 18)?).await?;
+    let player = join(&state, second, Some(regular)).await?;
+    send_input(&state, player).await?;
+    record_play(&state, id, second, at(3, 18)?, 60, 1).await?;
+
+    // Outside the requested range
+    let later = create_session(&state, owner, at(9, 18)?).await?;
+    join(&state, later, Some(once)).await?;
+    record_play(&state, id, later, at(9, 18)?, 60, 1).await?;
+
+    let (status, body) = common::get_with_auth(
+        &app,
+        &format!("/api/v1/games/{id}/stats?from=2026-03-01&to=2026-03-05"),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    let funnel = &json["funnel"];
+    assert_eq!(funnel["sessions"], 2);
+    assert_eq!(funnel["joinedPlayers"], 4);
+    assert_eq!(funnel["activePlayers"], 3);
+    assert_eq!(funnel["joinToPlayConversion"], 0.75);
+    assert_eq!(funnel["repeatPlayerRate"], 0.5);
+    assert_eq!(funnel["averagePlayersPerSession"], 2.0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn stats_break_players_down_by_country() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner, token) = common::create_user(&state, "user").await?;
+    let (_admin, admin_token) = common::create_user(&state, "admin").await?;
+    let id = create_published_game(&state, owner).await?;
+
+    let now = Utc::now();
+    let session = create_session(&state, owner, now).await?;
+    for country in [Some("NZ"), Some("NZ"), Some("DE"), None] {
+        let player = join(&state, session, None).await?;
+        if let Some(country) = country {
+            set_country(&state, player, country).await?;
+        }
+    }
+    record_play(&state, id, session, now, 60, 4).await?;
+    sign_in(&state, "DE", "success").await?;
+    sign_in(&state, "FR", "success").await?;
+    sign_in(&state, "NZ", "invalid_credentials").await?;
+
+    let (status, body) =
+        common::get_with_auth(&app, &format!("/api/v1/games/{id}/stats"), &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(
+        json["countries"],
+        serde_json::json!([
+            { "country": "NZ", "players": 2 },
+            { "country": "DE", "players": 1 },
+            { "country": null, "players": 1 },
+        ])
+    );
+
+    let (status, body) = common::get_with_auth(&app, "/api/v1/admin/stats", &admin_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(
+        json["countries"],
+        serde_json::json!([
+            { "country": "NZ", "players": 2, "signIns": 0 },
+            { "country": "DE", "players": 1, "signIns": 1 },
+            { "country": null, "players": 1, "signIns": 0 },
+            { "country": "FR", "players": 0, "signIns": 1 },
+        ])
+    );
+    Ok(())
+}
@@ -1,14 +1,14 @@
 mod common;
 
-use axum::http::StatusCode;
 use axum::Router;
+use axum::http::StatusCode;
 use migration::{Migrator, MigratorTrait};
 use serde_json::json;
 
-use aircade_api::config::{Config, Environment};
-use aircade_api::sessions::SessionManager;
-use aircade_api::state::AppState;
-use sea_orm::{ActiveModelTrait, ActiveValue, EntityTrait};
+use aircade_api::entities::{game, game_tag, tag};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter,
+};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Test Infrastructure
@@ -20,30 +20,9 @@ async fn test_app() -> Router {
         .unwrap_or_default();
     Migrator::up(&db, None).await.unwrap_or_default();
 
-    let state = AppState {
-        db,
-        config: Config {
-            database_url: String::new(),
-            server_host: std::net::IpAddr::from([127, 0, 0, 1]),
-            server_port: 0,
-            environment: Environment::Development,
-            log_level: "warn".to_string(),
-            jwt_secret: "test-secret-key-for-testing-only-32chars".to_string(),
-            jwt_access_expiration_secs: 900,
-            jwt_refresh_expiration_secs: 604_800,
-            google_client_id: String::new(),
-            google_client_secret: String::new(),
-            google_redirect_uri: String::new(),
-            github_client_id: String::new(),
-            github_client_secret: String::new(),
-            github_redirect_uri: String::new(),
-            frontend_url: "http://localhost:3001".to_string(),
-            upload_dir: "test_uploads".to_string(),
-        },
-        session_manager: SessionManager::new(),
-    };
+    let state = common::test_state(db, common::test_config());
 
-    aircade_api::routes::router().with_state(state)
+    aircade_api::routes::router(&state.config).with_state(state)
 }
 
 /// Sign up a new user and return (`access_token`, `user_id`).
@@ -304,29 +283,8 @@ async fn publish_game_no_code() {
         .unwrap_or_default();
     Migrator::up(&db, None).await.unwrap_or_default();
 
-    let state = AppState {
-        db: db.clone(),
-        config: Config {
-            database_url: String::new(),
-            server_host: std::net::IpAddr::from([127, 0, 0, 1]),
-            server_port: 0,
-            environment: Environment::Development,
-            log_level: "warn".to_string(),
-            jwt_secret: "test-secret-key-for-testing-only-32chars".to_string(),
-            jwt_access_expiration_secs: 900,
-            jwt_refresh_expiration_secs: 604_800,
-            google_client_id: String::new(),
-            google_client_secret: String::new(),
-            google_redirect_uri: String::new(),
-            github_client_id: String::new(),
-            github_client_secret: String::new(),
-            github_redirect_uri: String::new(),
-            frontend_url: "http://localhost:3001".to_string(),
-            upload_dir: "test_uploads".to_string(),
-        },
-        session_manager: SessionManager::new(),
-    };
-    let app = aircade_api::routes::router().with_state(state);
+    let state = common::test_state(db.clone(), common::test_config());
+    let app = aircade_api::routes::router(&state.config).with_state(state);
 
     // Sign up
     let (status, body) = common::post_json(
@@ -376,6 +334,87 @@ async fn publish_game_no_code() {
     assert_eq!(v["error"]["code"], "INVALID_GAME");
 }
 
+#[tokio::test]
+async fn validate_reports_diagnostics_and_blocks_publish() {
+    let (app, token, game_id, _) = setup_verified_user_and_published_game("val1").await;
+    let validate_uri = format!("/api/v1/games/{game_id}/validate");
+
+    let (status, body) = common::post_json_with_auth(&app, &validate_uri, &json!({}), &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["valid"], true);
+    assert_eq!(v["diagnostics"], json!([]));
+
+    let (status, _) = common::patch_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}"),
+        &json!({ "gameScreenCode": "function setup() {\n  createCanvas(400, 400;\n}" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = common::post_json_with_auth(&app, &validate_uri, &json!({}), &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["valid"], false);
+    assert_eq!(v["diagnostics"][0]["severity"], "error");
+    assert_eq!(v["diagnostics"][0]["code"], "SYNTAX_ERROR");
+    assert_eq!(v["diagnostics"][0]["file"], "gameScreen");
+    assert_eq!(v["diagnostics"][0]["line"], 2);
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/publish"),
+        &json!({}),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["error"]["code"], "INVALID_GAME_CODE");
+}
+
+#[tokio::test]
+async fn publish_scans_for_unsafe_apis() {
+    let (app, token, game_id, _) = setup_verified_user_and_published_game("scan1").await;
+    let game_uri = format!("/api/v1/games/{game_id}");
+    let publish_uri = format!("/api/v1/games/{game_id}/publish");
+
+    // Errors block publishing
+    let (status, _) = common::patch_json_with_auth(
+        &app,
+        &game_uri,
+        &json!({ "gameScreenCode": "function setup() { eval(document.cookie); }" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = common::post_json_with_auth(&app, &publish_uri, &json!({}), &token).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["error"]["code"], "UNSAFE_GAME_CODE");
+
+    // Warnings are recorded on the version
+    let (status, _) = common::patch_json_with_auth(
+        &app,
+        &game_uri,
+        &json!({ "gameScreenCode": "function setup() { fetch('https://example.com/scores'); }" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = common::post_json_with_auth(&app, &publish_uri, &json!({}), &token).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+
+    let (status, body) =
+        common::get_with_auth(&app, &format!("{game_uri}/versions/2"), &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["scanFindings"][0]["code"], "UNSAFE_API");
+    assert_eq!(v["scanFindings"][0]["severity"], "warning");
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Helper: create a verified user + published game
 // ─────────────────────────────────────────────────────────────────────────────
@@ -386,29 +425,8 @@ async fn setup_verified_user_and_published_game(suffix: &str) -> (Router, String
         .unwrap_or_default();
     Migrator::up(&db, None).await.unwrap_or_default();
 
-    let state = AppState {
-        db: db.clone(),
-        config: Config {
-            database_url: String::new(),
-            server_host: std::net::IpAddr::from([127, 0, 0, 1]),
-            server_port: 0,
-            environment: Environment::Development,
-            log_level: "warn".to_string(),
-            jwt_secret: "test-secret-key-for-testing-only-32chars".to_string(),
-            jwt_access_expiration_secs: 900,
-            jwt_refresh_expiration_secs: 604_800,
-            google_client_id: String::new(),
-            google_client_secret: String::new(),
-            google_redirect_uri: String::new(),
-            github_client_id: String::new(),
-            github_client_secret: String::new(),
-            github_redirect_uri: String::new(),
-            frontend_url: "http://localhost:3001".to_string(),
-            upload_dir: "test_uploads".to_string(),
-        },
-        session_manager: SessionManager::new(),
-    };
-    let app = aircade_api::routes::router().with_state(state);
+    let state = common::test_state(db.clone(), common::test_config());
+    let app = aircade_api::routes::router(&state.config).with_state(state);
 
     // Sign up user
     let (status, body) = common::post_json(
@@ -599,6 +617,83 @@ async fn list_and_get_versions() {
     assert_eq!(status, StatusCode::NOT_FOUND);
 }
 
+#[tokio::test]
+async fn restore_version_rolls_back_code() {
+    let (app, token, game_id, _) = setup_verified_user_and_published_game("rv1").await;
+    let original = "function setup() { createCanvas(400, 400); }";
+
+    // Ship a bad release as version 2
+    let (status, _) = common::patch_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}"),
+        &json!({ "gameScreenCode": "function setup() { brokenCall(); }" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/publish"),
+        &json!({ "changelog": "Oops" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+
+    // Restoring into the draft only does not publish
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/versions/1/restore"),
+        &json!({}),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["game"]["gameScreenCode"], original);
+    assert!(v["version"].is_null());
+
+    // Republishing creates version 3 from version 1's code
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/versions/1/restore"),
+        &json!({ "republish": true }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["version"]["versionNumber"], 3);
+    assert_eq!(v["version"]["changelog"], "Restored from version 1");
+    assert_eq!(v["game"]["publishedVersionId"], v["version"]["id"]);
+
+    let (status, body) =
+        common::get_with_auth(&app, &format!("/api/v1/games/{game_id}/versions/3"), &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["gameScreenCode"], original);
+
+    // Unknown versions and other users are rejected
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/versions/99/restore"),
+        &json!({}),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (other_token, _) = signup_and_get_token(&app, "rv2").await;
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/versions/1/restore"),
+        &json!({}),
+        &other_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // 4.15 – 4.17 Tags
 // ─────────────────────────────────────────────────────────────────────────────
@@ -826,3 +921,946 @@ async fn get_game_code_hidden_for_non_creator() {
     // Code fields should be absent for non-creator
     assert!(v.get("gameScreenCode").is_none() || v["gameScreenCode"].is_null());
 }
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Game Search
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn search_games_returns_only_published_public_matches() {
+    let (app, token, game_id, _) = setup_verified_user_and_published_game("srch1").await;
+
+    // A draft with a matching title must not be returned
+    let _ = create_game(&app, &token, "Draft srch1").await;
+
+    let (status, body) = common::get(&app, "/api/v1/search/games?q=srch1").await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["total"], 1);
+    assert_eq!(v["data"][0]["id"], game_id.as_str());
+}
+
+#[tokio::test]
+async fn search_games_ranks_title_matches_first() {
+    let app = test_app().await;
+
+    // Seeded Pong game mentions "paddle" only in its description
+    let (status, body) = common::get(&app, "/api/v1/search/games?q=pong%20paddle").await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["total"], 1);
+    assert_eq!(v["data"][0]["title"], "Pong");
+}
+
+#[tokio::test]
+async fn search_games_requires_query() {
+    let app = test_app().await;
+
+    let (status, _) = common::get(&app, "/api/v1/search/games?q=%20%20").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+/// Search for `q` and return the titles found.
+async fn search_titles(app: &Router, q: &str) -> anyhow::Result<Vec<String>> {
+    let (status, body) = common::get(app, &format!("/api/v1/search/games?q={q}")).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    Ok(v["data"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|g| g["title"].as_str().map(String::from))
+        .collect())
+}
+
+/// Tag the seeded Pong game, then rename and remove the tag, searching after each step.
+async fn assert_tag_search(db: sea_orm::DatabaseConnection) -> anyhow::Result<()> {
+    let state = common::test_state(db.clone(), common::test_config());
+    let app = aircade_api::routes::router(&state.config).with_state(state);
+    let pong = game::Entity::find()
+        .filter(game::Column::Slug.eq("pong"))
+        .one(&db)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Pong is not seeded"))?;
+    let puzzle = tag::Entity::find()
+        .filter(tag::Column::Slug.eq("puzzle"))
+        .one(&db)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Puzzle is not seeded"))?;
+    assert!(search_titles(&app, "puzzle").await?.is_empty());
+
+    game_tag::ActiveModel {
+        game_id: ActiveValue::Set(pong.id),
+        tag_id: ActiveValue::Set(puzzle.id),
+    }
+    .insert(&db)
+    .await?;
+    assert_eq!(search_titles(&app, "puzzle").await?, ["Pong"]);
+    // Terms may match the title and a tag together
+    assert_eq!(search_titles(&app, "pong%20puzzle").await?, ["Pong"]);
+
+    let mut renamed: tag::ActiveModel = puzzle.into();
+    renamed.name = ActiveValue::Set("Brainteaser".to_string());
+    let renamed = renamed.update(&db).await?;
+    assert!(search_titles(&app, "puzzle").await?.is_empty());
+    assert_eq!(search_titles(&app, "brainteaser").await?, ["Pong"]);
+
+    game_tag::Entity::delete_many()
+        .filter(game_tag::Column::TagId.eq(renamed.id))
+        .exec(&db)
+        .await?;
+    assert!(search_titles(&app, "brainteaser").await?.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn search_games_matches_tags() -> anyhow::Result<()> {
+    let db = sea_orm::Database::connect("sqlite::memory:").await?;
+    Migrator::up(&db, None).await?;
+    assert_tag_search(db).await
+}
+
+/// The full-text search SQL, run when `TEST_POSTGRES_URL` names a scratch database; the
+/// migrations are reapplied from scratch.
+#[tokio::test]
+async fn search_games_matches_tags_on_postgres() -> anyhow::Result<()> {
+    let Ok(url) = std::env::var("TEST_POSTGRES_URL") else {
+        return Ok(());
+    };
+    let db = sea_orm::Database::connect(url).await?;
+    Migrator::fresh(&db).await?;
+
+    let index: String = db
+        .query_one(sea_orm::Statement::from_string(
+            sea_orm::DbBackend::Postgres,
+            "SELECT indexdef FROM pg_indexes WHERE indexname = 'idx_game_search_vector'",
+        ))
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("search index is missing"))?
+        .try_get("", "indexdef")?;
+    assert!(index.contains("USING gin (search_vector)"), "{index}");
+
+    assert_tag_search(db).await
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Favorites
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn favorite_and_unfavorite_game() {
+    let (app, _, game_id, _) = setup_verified_user_and_published_game("fav1").await;
+    let (fan_token, _) = signup_and_get_token(&app, "fav1fan").await;
+    let uri = format!("/api/v1/games/{game_id}/favorite");
+
+    let (status, _) = common::post_json_with_auth(&app, &uri, &json!({}), &fan_token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // Favoriting again is idempotent
+    let (status, _) = common::post_json_with_auth(&app, &uri, &json!({}), &fan_token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, body) =
+        common::get_with_auth(&app, &format!("/api/v1/games/{game_id}"), &fan_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["isFavorited"], true);
+
+    let (status, body) =
+        common::get_with_auth(&app, "/api/v1/users/me/favorites", &fan_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["total"], 1);
+    assert_eq!(v["data"][0]["id"], game_id.as_str());
+
+    let (status, _) = common::delete_with_auth(&app, &uri, &fan_token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, body) =
+        common::get_with_auth(&app, &format!("/api/v1/games/{game_id}"), &fan_token).await;
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["isFavorited"], false);
+
+    let (_, body) = common::get_with_auth(&app, "/api/v1/users/me/favorites", &fan_token).await;
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["total"], 0);
+}
+
+#[tokio::test]
+async fn is_favorited_omitted_when_anonymous() {
+    let (app, _, game_id, _) = setup_verified_user_and_published_game("fav2").await;
+
+    let (status, body) = common::get(&app, &format!("/api/v1/games/{game_id}")).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert!(v.get("isFavorited").is_none());
+}
+
+#[tokio::test]
+async fn favorite_private_game_of_other_user_is_404() {
+    let app = test_app().await;
+    let (owner_token, _) = signup_and_get_token(&app, "fav3").await;
+    let (fan_token, _) = signup_and_get_token(&app, "fav3fan").await;
+    let game_id = create_game(&app, &owner_token, "Secret").await;
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/favorite"),
+        &json!({}),
+        &fan_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Thumbnails
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Encode a small solid-colour PNG.
+fn sample_png() -> Vec<u8> {
+    let img = image::RgbImage::from_pixel(64, 48, image::Rgb([200, 40, 90]));
+    let mut out = std::io::Cursor::new(Vec::new());
+    let _ = image::DynamicImage::ImageRgb8(img).write_to(&mut out, image::ImageFormat::Png);
+    out.into_inner()
+}
+
+#[tokio::test]
+async fn upload_thumbnail_generates_webp_sizes() {
+    let app = test_app().await;
+    let (token, _) = signup_and_get_token(&app, "thumb1").await;
+    let game_id = create_game(&app, &token, "Thumbs").await;
+    let uri = format!("/api/v1/games/{game_id}/thumbnail");
+
+    // Uploading twice replaces the previous generation
+    for _ in 0..2 {
+        let (status, body) = common::post_multipart_with_auth(
+            &app,
+            &uri,
+            "cover.png",
+            "image/png",
+            &sample_png(),
+            &[],
+            &token,
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED, "{body}");
+        let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+        assert_eq!(
+            v["thumbnailUrl"],
+            format!("assets/{game_id}/thumbnail-medium.webp")
+        );
+        assert_eq!(v["sizes"].as_array().map(Vec::len), Some(3));
+        assert_eq!(v["sizes"][0]["fileType"], "image/webp");
+    }
+
+    let (_, body) = common::get_with_auth(&app, &format!("/api/v1/games/{game_id}"), &token).await;
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(
+        v["thumbnailUrl"],
+        format!("assets/{game_id}/thumbnail-medium.webp")
+    );
+
+    let (_, body) =
+        common::get_with_auth(&app, &format!("/api/v1/games/{game_id}/assets"), &token).await;
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["data"].as_array().map(Vec::len), Some(3), "{body}");
+}
+
+#[tokio::test]
+async fn upload_asset_with_thumbnail_purpose_sets_thumbnail() {
+    let app = test_app().await;
+    let (token, _) = signup_and_get_token(&app, "thumb2").await;
+    let game_id = create_game(&app, &token, "Thumbs").await;
+
+    let (status, body) = common::post_multipart_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/assets"),
+        "cover.png",
+        "image/png",
+        &sample_png(),
+        &[("purpose", "thumbnail")],
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+
+    let (_, body) = common::get_with_auth(&app, &format!("/api/v1/games/{game_id}"), &token).await;
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(
+        v["thumbnailUrl"],
+        format!("assets/{game_id}/thumbnail-medium.webp")
+    );
+}
+
+#[tokio::test]
+async fn upload_thumbnail_rejects_invalid_images() {
+    let app = test_app().await;
+    let (token, _) = signup_and_get_token(&app, "thumb3").await;
+    let game_id = create_game(&app, &token, "Thumbs").await;
+    let uri = format!("/api/v1/games/{game_id}/thumbnail");
+
+    let (status, _) = common::post_multipart_with_auth(
+        &app,
+        &uri,
+        "cover.png",
+        "image/png",
+        b"definitely not a png",
+        &[],
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::post_multipart_with_auth(
+        &app,
+        &uri,
+        "cover.svg",
+        "image/svg+xml",
+        b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+        &[],
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Asset File Serving
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Upload a PNG asset and return its ID.
+async fn upload_png_asset(app: &Router, token: &str, game_id: &str) -> String {
+    let (status, body) = common::post_multipart_with_auth(
+        app,
+        &format!("/api/v1/games/{game_id}/assets"),
+        "sprite.png",
+        "image/png",
+        &sample_png(),
+        &[],
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    v["id"].as_str().unwrap_or_default().to_string()
+}
+
+#[tokio::test]
+async fn get_asset_file_serves_bytes_with_cache_headers() {
+    let (app, token, game_id, _) = setup_verified_user_and_published_game("file1").await;
+    let asset_id = upload_png_asset(&app, &token, &game_id).await;
+    let uri = format!("/api/v1/games/{game_id}/assets/{asset_id}/file");
+
+    let (status, headers, body) = common::get_raw(&app, &uri, &[]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, sample_png());
+    assert_eq!(headers["content-type"], "image/png");
+    assert_eq!(
+        headers["cache-control"],
+        "public, max-age=31536000, immutable"
+    );
+    let etag = headers["etag"].to_str().unwrap_or_default().to_string();
+    assert_eq!(etag, format!("\"{asset_id}\""));
+
+    // Conditional request hits the cache
+    let (status, _, body) = common::get_raw(&app, &uri, &[("if-none-match", &etag)]).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert!(body.is_empty());
+}
+
+#[tokio::test]
+async fn get_asset_file_respects_private_visibility() {
+    let app = test_app().await;
+    let (token, _) = signup_and_get_token(&app, "file2").await;
+    let game_id = create_game(&app, &token, "Hidden").await;
+    let asset_id = upload_png_asset(&app, &token, &game_id).await;
+    let uri = format!("/api/v1/games/{game_id}/assets/{asset_id}/file");
+
+    let (status, _, _) = common::get_raw(&app, &uri, &[]).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let auth = format!("Bearer {token}");
+    let (status, headers, _) = common::get_raw(&app, &uri, &[("authorization", &auth)]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        headers["cache-control"],
+        "private, max-age=31536000, immutable"
+    );
+}
+
+#[tokio::test]
+async fn export_and_import_round_trip() {
+    let (app, token, game_id, _) = setup_verified_user_and_published_game("export1").await;
+    upload_png_asset(&app, &token, &game_id).await;
+
+    let uri = format!("/api/v1/games/{game_id}/export");
+    let auth = format!("Bearer {token}");
+    let (status, headers, archive) = common::get_raw(&app, &uri, &[("authorization", &auth)]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["content-type"], "application/zip");
+    assert!(archive.starts_with(b"PK"));
+
+    // Only the creator can export
+    let (other, _) = signup_and_get_token(&app, "export2").await;
+    let (status, _, _) =
+        common::get_raw(&app, &uri, &[("authorization", &format!("Bearer {other}"))]).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = common::post_multipart_with_auth(
+        &app,
+        "/api/v1/games/import",
+        "game.zip",
+        "application/zip",
+        &archive,
+        &[],
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let imported_id = v["id"].as_str().unwrap_or_default().to_string();
+    assert_ne!(imported_id, game_id);
+    assert_eq!(v["status"], "draft");
+    assert_eq!(v["visibility"], "private");
+    assert_eq!(
+        v["gameScreenCode"],
+        "function setup() { createCanvas(400, 400); }"
+    );
+
+    let (status, body) =
+        common::get_with_auth(&app, &format!("/api/v1/games/{imported_id}/assets"), &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["total"], 1);
+    assert_eq!(v["data"][0]["fileName"], "sprite.png");
+    let asset_id = v["data"][0]["id"].as_str().unwrap_or_default();
+
+    let (status, _, data) = common::get_raw(
+        &app,
+        &format!("/api/v1/games/{imported_id}/assets/{asset_id}/file"),
+        &[("authorization", &auth)],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(data, sample_png());
+}
+
+#[tokio::test]
+async fn import_rejects_invalid_archive() {
+    let app = test_app().await;
+    let (token, _) = signup_and_get_token(&app, "import1").await;
+
+    let (status, _) = common::post_multipart_with_auth(
+        &app,
+        "/api/v1/games/import",
+        "game.zip",
+        "application/zip",
+        b"not a zip",
+        &[],
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn list_templates_returns_seeded_templates() {
+    let app = test_app().await;
+
+    let (status, body) = common::get(&app, "/api/v1/templates").await;
+    assert_eq!(status, StatusCode::OK);
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let slugs: Vec<&str> = v["data"]
+        .as_array()
+        .map(|a| a.iter().filter_map(|t| t["slug"].as_str()).collect())
+        .unwrap_or_default();
+    assert_eq!(slugs, ["drawing", "pong", "quiz"]);
+    assert!(v["data"][0]["gameScreenCode"].is_string());
+}
+
+#[tokio::test]
+async fn create_game_from_template_prefills_code() {
+    let app = test_app().await;
+    let (token, _) = signup_and_get_token(&app, "tmpl1").await;
+
+    let (_, body) = common::get(&app, "/api/v1/templates").await;
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let pong = v["data"]
+        .as_array()
+        .and_then(|a| a.iter().find(|t| t["slug"] == "pong"))
+        .cloned()
+        .unwrap_or_default();
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/games",
+        &json!({ "title": "My Pong", "templateId": pong["id"], "maxPlayers": 4 }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let game: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(game["gameScreenCode"], pong["gameScreenCode"]);
+    assert_eq!(game["controllerScreenCode"], pong["controllerScreenCode"]);
+    assert_eq!(game["minPlayers"], 2);
+    // Explicit values override the template
+    assert_eq!(game["maxPlayers"], 4);
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/games",
+        &json!({ "title": "Nope", "templateId": uuid::Uuid::new_v4() }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn changelog_renders_sanitized_release_notes() {
+    let (app, token, game_id, _) = setup_verified_user_and_published_game("notes1").await;
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/publish"),
+        &json!({ "changelog": "**Faster** paddles <script>alert(1)</script>" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+
+    let (status, body) = common::get(&app, &format!("/api/v1/games/{game_id}/changelog")).await;
+    assert_eq!(status, StatusCode::OK);
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["total"], 2);
+
+    let latest = &v["data"][0];
+    assert_eq!(latest["versionNumber"], 2);
+    assert!(latest["publishedAt"].is_string());
+    let html = latest["changelogHtml"].as_str().unwrap_or_default();
+    assert!(html.contains("<strong>Faster</strong>"), "{html}");
+    assert!(!html.contains("<script"), "{html}");
+
+    assert_eq!(v["data"][1]["changelog"], "Initial release");
+}
+
+#[tokio::test]
+async fn tag_suggestions_use_keywords_and_similar_games() {
+    // Another creator's published game is tagged Puzzle + Relaxed
+    let (app, other_token, other_id, _) = setup_verified_user_and_published_game("suggest1").await;
+    let (status, _) = common::put_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{other_id}/tags"),
+        &json!({ "tagIds": ["01000000-0000-4000-8000-000000000005", "02000000-0000-4000-8000-000000000003"] }),
+        &other_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (token, _) = signup_and_get_token(&app, "suggest2").await;
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/games",
+        &json!({ "title": "Turn-based Puzzle Duel", "description": "Draw your way out" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let game_id = v["id"].as_str().unwrap_or_default();
+
+    let (status, body) = common::get_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/tags/suggestions"),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let suggestions = v["data"].as_array().cloned().unwrap_or_default();
+    let find = |slug: &str| suggestions.iter().find(|s| s["slug"] == slug).cloned();
+
+    assert_eq!(suggestions[0]["slug"], "puzzle");
+    assert_eq!(
+        find("puzzle").map(|s| s["reasons"].clone()),
+        Some(json!(["keyword", "similarGames"]))
+    );
+    assert!(find("turn-based").is_some());
+    assert!(find("drawing").is_some());
+    assert_eq!(
+        find("relaxed").map(|s| s["reasons"].clone()),
+        Some(json!(["similarGames"]))
+    );
+
+    // Only collaborators can ask for suggestions
+    let (status, _) = common::get_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/tags/suggestions"),
+        &other_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn related_games_share_tags_and_are_published() {
+    let puzzle = "01000000-0000-4000-8000-000000000005";
+    let relaxed = "02000000-0000-4000-8000-000000000003";
+
+    let (app, other_token, other_id, _) = setup_verified_user_and_published_game("related1").await;
+    let (status, _) = common::put_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{other_id}/tags"),
+        &json!({ "tagIds": [puzzle, relaxed] }),
+        &other_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (token, _) = signup_and_get_token(&app, "related2").await;
+    let draft_id = create_game(&app, &token, "Puzzle Draft").await;
+    let (status, _) = common::put_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{draft_id}/tags"),
+        &json!({ "tagIds": [puzzle] }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // The published game is related to the draft through the shared tag
+    let (status, body) =
+        common::get_with_auth(&app, &format!("/api/v1/games/{draft_id}/related"), &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let games = v["data"].as_array().cloned().unwrap_or_default();
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0]["id"], other_id.as_str());
+
+    // Unpublished games are never recommended
+    let (status, body) = common::get(&app, &format!("/api/v1/games/{other_id}/related")).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["data"], json!([]));
+
+    // Private games stay hidden from strangers
+    let (status, _) = common::get(&app, &format!("/api/v1/games/{draft_id}/related")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn publish_validates_and_snapshots_manifest() {
+    let (app, token, game_id, _) = setup_verified_user_and_published_game("manifest1").await;
+    let uri = format!("/api/v1/games/{game_id}");
+
+    // Duplicate control ids block publishing
+    let (status, _) = common::patch_json_with_auth(
+        &app,
+        &uri,
+        &json!({ "manifest": { "controls": [
+            { "type": "button", "id": "jump" },
+            { "type": "button", "id": "jump" },
+        ] } }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) =
+        common::post_json_with_auth(&app, &format!("{uri}/publish"), &json!({}), &token).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+    assert!(body.contains("INVALID_MANIFEST"), "{body}");
+
+    let manifest = json!({
+        "controls": [
+            { "type": "joystick", "id": "move" },
+            { "type": "button", "id": "jump", "label": "Jump" },
+        ],
+        "orientation": "landscape",
+    });
+    let (status, _) =
+        common::patch_json_with_auth(&app, &uri, &json!({ "manifest": manifest }), &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) =
+        common::post_json_with_auth(&app, &format!("{uri}/publish"), &json!({}), &token).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+
+    // The published version carries the manifest with the game's player counts
+    let (status, body) = common::get(&app, &format!("{uri}/versions/2")).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["manifest"]["orientation"], "landscape");
+    assert_eq!(v["manifest"]["controls"][1]["label"], "Jump");
+    assert!(v["manifest"]["minPlayers"].is_number());
+    assert!(v["manifest"]["maxPlayers"].is_number());
+}
+
+#[tokio::test]
+async fn forks_and_lineage() {
+    let (app, token, game_id, username) = setup_verified_user_and_published_game("lin1").await;
+
+    // The creator remixes their own game and publishes the remix
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/fork"),
+        &json!({}),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let remix_id = v["id"].as_str().unwrap_or_default().to_string();
+    let _ = common::patch_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{remix_id}"),
+        &json!({ "visibility": "public" }),
+        &token,
+    )
+    .await;
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{remix_id}/publish"),
+        &json!({}),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Another user's draft fork of the remix stays out of the public fork list
+    let (token2, _) = signup_and_get_token(&app, "lin1b").await;
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{remix_id}/fork"),
+        &json!({}),
+        &token2,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let fork_id = v["id"].as_str().unwrap_or_default().to_string();
+
+    let (status, body) = common::get(&app, &format!("/api/v1/games/{game_id}/forks")).await;
+    assert_eq!(status, StatusCode::OK);
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["total"], 1);
+    assert_eq!(v["data"][0]["id"], remix_id.as_str());
+
+    let (_, body) = common::get(&app, &format!("/api/v1/games/{remix_id}/forks")).await;
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["total"], 0);
+
+    // Lineage walks back to the original, parent first
+    let (status, body) =
+        common::get_with_auth(&app, &format!("/api/v1/games/{fork_id}/lineage"), &token2).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["data"][0]["id"], remix_id.as_str());
+    assert_eq!(v["data"][1]["id"], game_id.as_str());
+    assert_eq!(v["data"][1]["creator"]["username"], username.as_str());
+
+    // A private ancestor keeps its creator credit but hides its title
+    let _ = common::patch_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}"),
+        &json!({ "visibility": "private" }),
+        &token,
+    )
+    .await;
+    let (_, body) =
+        common::get_with_auth(&app, &format!("/api/v1/games/{fork_id}/lineage"), &token2).await;
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["data"][1]["available"], false);
+    assert!(v["data"][1]["title"].is_null());
+    assert_eq!(v["data"][1]["creator"]["username"], username.as_str());
+}
+
+#[tokio::test]
+async fn bulk_update_my_games() {
+    let app = test_app().await;
+    let (token, _) = signup_and_get_token(&app, "bulk1").await;
+    let (other_token, _) = signup_and_get_token(&app, "bulk2").await;
+    let first = create_game(&app, &token, "First").await;
+    let second = create_game(&app, &token, "Second").await;
+    let theirs = create_game(&app, &other_token, "Theirs").await;
+
+    let bulk = |body: serde_json::Value| {
+        let app = app.clone();
+        let token = token.clone();
+        async move {
+            let (status, body) =
+                common::post_json_with_auth(&app, "/api/v1/users/me/games/bulk", &body, &token)
+                    .await;
+            assert_eq!(status, StatusCode::OK, "{body}");
+            let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+            v["data"]
+                .as_array()
+                .map(|items| {
+                    items
+                        .iter()
+                        .map(|i| i["success"].as_bool().unwrap_or_default())
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        }
+    };
+
+    // Other users' games are reported, not changed
+    let results = bulk(json!({ "gameIds": [first, second, theirs], "action": "archive" })).await;
+    assert_eq!(results, [true, true, false]);
+
+    let results = bulk(json!({ "gameIds": [first], "action": "archive" })).await;
+    assert_eq!(results, [false]);
+
+    let results = bulk(json!({ "gameIds": [first], "action": "unarchive" })).await;
+    assert_eq!(results, [true]);
+
+    let results = bulk(json!({
+        "gameIds": [first, second],
+        "action": "set_visibility",
+        "visibility": "unlisted",
+    }))
+    .await;
+    assert_eq!(results, [true, true]);
+    let (_, body) = common::get_with_auth(&app, &format!("/api/v1/games/{first}"), &token).await;
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["visibility"], "unlisted");
+    assert_eq!(v["status"], "draft");
+
+    let results = bulk(json!({ "gameIds": [second], "action": "delete" })).await;
+    assert_eq!(results, [true]);
+    let (_, body) = common::get_with_auth(&app, "/api/v1/users/me/games", &token).await;
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["total"], 1);
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/users/me/games/bulk",
+        &json!({ "gameIds": [first], "action": "set_visibility", "visibility": "secret" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn deleted_game_can_be_restored_from_trash() {
+    let app = test_app().await;
+    let (token, _) = signup_and_get_token(&app, "trash1").await;
+    let (other_token, _) = signup_and_get_token(&app, "trash2").await;
+    let game_id = create_game(&app, &token, "Oops").await;
+    let asset_id = upload_png_asset(&app, &token, &game_id).await;
+
+    let (status, _) =
+        common::delete_with_auth(&app, &format!("/api/v1/games/{game_id}"), &token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, body) = common::get_with_auth(&app, "/api/v1/users/me/games/trash", &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["total"], 1);
+    assert_eq!(v["data"][0]["id"], game_id.as_str());
+    assert!(v["data"][0]["restorableUntil"].is_string());
+
+    // Only the creator can restore it
+    let uri = format!("/api/v1/games/{game_id}/restore");
+    let (status, _) = common::post_json_with_auth(&app, &uri, &json!({}), &other_token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = common::post_json_with_auth(&app, &uri, &json!({}), &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let (status, _) =
+        common::get_with_auth(&app, &format!("/api/v1/games/{game_id}"), &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = common::get_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/assets/{asset_id}"),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, body) = common::get_with_auth(&app, "/api/v1/users/me/games/trash", &token).await;
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["total"], 0);
+
+    // Live games cannot be "restored"
+    let (status, _) = common::post_json_with_auth(&app, &uri, &json!({}), &token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn embed_card_and_oembed() {
+    let (app, token, game_id, username) = setup_verified_user_and_published_game("emb1").await;
+
+    let (status, body) = common::get(&app, &format!("/api/v1/games/{game_id}/embed")).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["creator"]["username"], username.as_str());
+    assert_eq!(
+        v["embedUrl"],
+        format!("http://localhost:3001/embed/{game_id}")
+    );
+    assert!(
+        v["html"]
+            .as_str()
+            .unwrap_or_default()
+            .starts_with("<iframe")
+    );
+    assert!(v["gameScreenCode"].is_null());
+
+    let page = format!("http://localhost:3001/games/{game_id}");
+    let (status, body) = common::get(
+        &app,
+        &format!(
+            "/api/v1/oembed?url={}&maxwidth=320",
+            urlencoding::encode(&page)
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(v["type"], "rich");
+    assert_eq!(v["version"], "1.0");
+    assert_eq!(v["provider_name"], "AirCade");
+    assert_eq!(
+        (v["width"].as_u64(), v["height"].as_u64()),
+        (Some(320), Some(180))
+    );
+
+    let (status, _) = common::get(
+        &app,
+        &format!(
+            "/api/v1/oembed?url={}&format=xml",
+            urlencoding::encode(&page)
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_IMPLEMENTED);
+
+    let (status, _) = common::get(
+        &app,
+        "/api/v1/oembed?url=https%3A%2F%2Fexample.com%2Fgames%2Fabc",
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Private games cannot be embedded
+    let _ = common::patch_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}"),
+        &json!({ "visibility": "private" }),
+        &token,
+    )
+    .await;
+    let (status, _) = common::get(&app, &format!("/api/v1/games/{game_id}/embed")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = common::get(
+        &app,
+        &format!("/api/v1/oembed?url={}", urlencoding::encode(&page)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,183 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use migration::{Migrator, MigratorTrait};
+use serde_json::json;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> Router {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    aircade_api::routes::router(&state.config).with_state(state)
+}
+
+/// Helper: sign up a user and return (`access_token`, `refresh_token`).
+async fn signup_user(
+    app: &Router,
+    email: &str,
+    username: &str,
+    password: &str,
+) -> (String, String) {
+    let (status, body) = common::post_json(
+        app,
+        "/api/v1/auth/signup/email",
+        &json!({
+            "email": email,
+            "username": username,
+            "password": password,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "signup failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let token = json["token"].as_str().unwrap_or_default().to_string();
+    let refresh = json["refreshToken"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    (token, refresh)
+}
+
+/// Create a guest account and return its access token.
+async fn create_guest(app: &Router) -> String {
+    let (status, body) = common::post_json(app, "/api/v1/auth/guest", &json!({})).await;
+    assert_eq!(status, StatusCode::CREATED, "guest signup failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(json["user"]["role"], "guest");
+    json["token"].as_str().unwrap_or_default().to_string()
+}
+
+/// Create a game with some code and return its id.
+async fn create_draft(app: &Router, token: &str) -> String {
+    let (status, body) = common::post_json_with_auth(
+        app,
+        "/api/v1/games",
+        &json!({ "title": "Guest Game" }),
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "create game failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let game_id = json["id"].as_str().unwrap_or_default().to_string();
+
+    let _ = common::patch_json_with_auth(
+        app,
+        &format!("/api/v1/games/{game_id}"),
+        &json!({ "gameScreenCode": "function setup() { createCanvas(400, 400); }" }),
+        token,
+    )
+    .await;
+    game_id
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn guest_can_draft_but_not_publish() {
+    let app = test_app().await;
+    let token = create_guest(&app).await;
+    let game_id = create_draft(&app, &token).await;
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/publish"),
+        &json!({}),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(json["error"]["code"], "GUEST_ACCOUNT");
+}
+
+#[tokio::test]
+async fn upgrade_with_email_keeps_drafts() {
+    let app = test_app().await;
+    let token = create_guest(&app).await;
+    let game_id = create_draft(&app, &token).await;
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/auth/guest/upgrade",
+        &json!({
+            "email": "Upgraded@Example.com",
+            "password": "Password123",
+            "username": "upgraded",
+        }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "upgrade failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(json["user"]["role"], "user");
+    assert_eq!(json["user"]["email"], "upgraded@example.com");
+    assert_eq!(json["user"]["username"], "upgraded");
+    assert_eq!(json["user"]["emailVerified"], false);
+    let upgraded = json["token"].as_str().unwrap_or_default().to_string();
+
+    let (status, body) = common::get_with_auth(&app, "/api/v1/users/me/games", &upgraded).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(
+        body.contains(&game_id),
+        "draft missing after upgrade: {body}"
+    );
+
+    let (status, _body) = common::post_json(
+        &app,
+        "/api/v1/auth/signin/email",
+        &json!({ "email": "upgraded@example.com", "password": "Password123" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Only guests can upgrade
+    let (status, _body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/auth/guest/upgrade",
+        &json!({ "email": "again@example.com", "password": "Password123" }),
+        &upgraded,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn upgrade_rejects_taken_email_and_incomplete_requests() {
+    let app = test_app().await;
+    signup_user(&app, "taken@example.com", "taken", "Password123").await;
+    let token = create_guest(&app).await;
+
+    let (status, _body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/auth/guest/upgrade",
+        &json!({ "email": "taken@example.com", "password": "Password123" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/auth/guest/upgrade",
+        &json!({ "email": "fresh@example.com" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = common::get_with_auth(&app, "/api/v1/users/me", &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(json["role"], "guest");
+}
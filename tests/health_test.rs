@@ -4,10 +4,6 @@ use axum::Router;
 use axum::http::StatusCode;
 use migration::{Migrator, MigratorTrait};
 
-use aircade_api::config::{Config, Environment};
-use aircade_api::sessions::SessionManager;
-use aircade_api::state::AppState;
-
 /// Build the app router backed by an in-memory `SQLite` database with migrations.
 async fn test_app() -> Router {
     let db = sea_orm::Database::connect("sqlite::memory:")
@@ -16,30 +12,9 @@ async fn test_app() -> Router {
 
     Migrator::up(&db, None).await.unwrap_or_default();
 
-    let state = AppState {
-        db,
-        config: Config {
-            database_url: String::new(),
-            server_host: std::net::IpAddr::from([127, 0, 0, 1]),
-            server_port: 0,
-            environment: Environment::Development,
-            log_level: "warn".to_string(),
-            jwt_secret: "test-secret-key-for-testing-only-32chars".to_string(),
-            jwt_access_expiration_secs: 900,
-            jwt_refresh_expiration_secs: 604_800,
-            google_client_id: String::new(),
-            google_client_secret: String::new(),
-            google_redirect_uri: String::new(),
-            github_client_id: String::new(),
-            github_client_secret: String::new(),
-            github_redirect_uri: String::new(),
-            frontend_url: "http://localhost:3001".to_string(),
-            upload_dir: "test_uploads".to_string(),
-        },
-        session_manager: SessionManager::new(),
-    };
-
-    aircade_api::routes::router().with_state(state)
+    let state = common::test_state(db, common::test_config());
+
+    aircade_api::routes::router(&state.config).with_state(state)
 }
 
 #[tokio::test]
@@ -68,6 +43,24 @@ async fn health_api_returns_200() {
     assert!(json["database"]["latency_ms"].is_number());
 }
 
+#[tokio::test]
+async fn health_api_reports_each_component() {
+    let app = test_app().await;
+    let (status, body) = common::get(&app, "/api/v1/health").await;
+
+    assert_eq!(status, StatusCode::OK);
+
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or(serde_json::Value::Null);
+    assert_eq!(json["status"], "healthy", "{body}");
+    let components = &json["components"];
+    for name in ["database", "migrations", "storage"] {
+        assert_eq!(components[name]["status"], "up", "{name}: {body}");
+        assert!(components[name]["latency_ms"].is_number(), "{name}");
+    }
+    assert_eq!(components["email"]["status"], "disabled");
+    assert!(components["email"]["detail"].is_string());
+}
+
 #[tokio::test]
 async fn unknown_route_returns_404() {
     let app = test_app().await;
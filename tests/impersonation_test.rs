@@ -0,0 +1,283 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::{impersonation_log, user};
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Helper: sign up a user and return (`access_token`, `refresh_token`).
+async fn signup_user(
+    app: &Router,
+    email: &str,
+    username: &str,
+    password: &str,
+) -> (String, String) {
+    let (status, body) = common::post_json(
+        app,
+        "/api/v1/auth/signup/email",
+        &json!({
+            "email": email,
+            "username": username,
+            "password": password,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "signup failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let token = json["token"].as_str().unwrap_or_default().to_string();
+    let refresh = json["refreshToken"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    (token, refresh)
+}
+
+async fn make_admin(state: &AppState, email: &str) -> anyhow::Result<()> {
+    let admin = user::Entity::find()
+        .filter(user::Column::Email.eq(email))
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("no user {email}"))?;
+    let mut active: user::ActiveModel = admin.into();
+    active.role = Set("admin".to_string());
+    active.update(&state.db).await?;
+    Ok(())
+}
+
+async fn user_id(state: &AppState, email: &str) -> anyhow::Result<Uuid> {
+    user::Entity::find()
+        .filter(user::Column::Email.eq(email))
+        .one(&state.db)
+        .await?
+        .map(|u| u.id)
+        .ok_or_else(|| anyhow::anyhow!("no user {email}"))
+}
+
+async fn impersonate(app: &Router, admin_token: &str, target: Uuid) -> (StatusCode, String) {
+    common::post_json_with_auth(
+        app,
+        &format!("/api/v1/admin/users/{target}/impersonate"),
+        &json!({ "reason": "Support ticket #4411" }),
+        admin_token,
+    )
+    .await
+}
+
+async fn log_for(state: &AppState, target: Uuid) -> anyhow::Result<Vec<impersonation_log::Model>> {
+    Ok(impersonation_log::Entity::find()
+        .filter(impersonation_log::Column::UserId.eq(target))
+        .order_by_asc(impersonation_log::Column::CreatedAt)
+        .all(&state.db)
+        .await?)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn admin_acts_as_user_and_every_request_is_logged() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (admin_token, _) = signup_user(&app, "admin@example.com", "overseer", "Password123").await;
+    let (user_token, _) = signup_user(&app, "user@example.com", "player", "Password123").await;
+    make_admin(&state, "admin@example.com").await?;
+    let admin_id = user_id(&state, "admin@example.com").await?;
+    let target = user_id(&state, "user@example.com").await?;
+
+    let (status, _body) = impersonate(&app, &user_token, admin_id).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = impersonate(&app, &admin_token, target).await;
+    assert_eq!(status, StatusCode::CREATED, "impersonate failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(json["username"], "player");
+    assert!(json["expiresAt"].is_string());
+    let token = json["token"].as_str().unwrap_or_default().to_string();
+
+    let (status, body) = common::get_with_auth(&app, "/api/v1/users/me", &token).await;
+    assert_eq!(status, StatusCode::OK, "get me failed: {body}");
+    let me: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(me["username"], "player");
+
+    let log = log_for(&state, target).await?;
+    assert_eq!(log.len(), 2);
+    assert!(log.iter().all(|e| e.admin_id == admin_id));
+    assert_eq!(log[0].outcome, "started");
+    assert_eq!(log[0].reason.as_deref(), Some("Support ticket #4411"));
+    assert_eq!(log[1].outcome, "allowed");
+    assert_eq!(log[1].method, "GET");
+    assert_eq!(log[1].path, "/api/v1/users/me");
+    Ok(())
+}
+
+#[tokio::test]
+async fn destructive_actions_are_refused_while_impersonating() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (admin_token, _) = signup_user(&app, "admin@example.com", "overseer", "Password123").await;
+    signup_user(&app, "user@example.com", "player", "Password123").await;
+    make_admin(&state, "admin@example.com").await?;
+    let target = user_id(&state, "user@example.com").await?;
+
+    let (_status, body) = impersonate(&app, &admin_token, target).await;
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let token = json["token"].as_str().unwrap_or_default().to_string();
+
+    let (status, _body) = common::delete_with_auth(&app, "/api/v1/users/me", &token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/auth/password/change",
+        &json!({ "currentPassword": "Password123", "newPassword": "Hijacked123" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let account = user::Entity::find_by_id(target).one(&state.db).await?;
+    assert!(account.is_some_and(|u| u.account_status == "active"));
+
+    let refused: Vec<String> = log_for(&state, target)
+        .await?
+        .into_iter()
+        .filter(|e| e.outcome == "refused")
+        .map(|e| e.path)
+        .collect();
+    assert_eq!(
+        refused,
+        ["/api/v1/users/me", "/api/v1/auth/password/change"]
+    );
+    Ok(())
+}
+
+/// Impersonate `user@example.com` as `admin@example.com` and return the token.
+async fn impersonation_token(app: &Router, state: &AppState) -> anyhow::Result<String> {
+    let (admin_token, _) = signup_user(app, "admin@example.com", "overseer", "Password123").await;
+    make_admin(state, "admin@example.com").await?;
+    let target = user_id(state, "user@example.com").await?;
+
+    let (status, body) = impersonate(app, &admin_token, target).await;
+    assert_eq!(status, StatusCode::CREATED, "impersonate failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    Ok(json["token"].as_str().unwrap_or_default().to_string())
+}
+
+#[tokio::test]
+async fn student_password_resets_are_refused_while_impersonating() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (teacher_token, _) = signup_user(&app, "user@example.com", "teacher", "Password123").await;
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/classrooms",
+        &json!({ "name": "Period 3" }),
+        &teacher_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let classroom: serde_json::Value = serde_json::from_str(&body)?;
+    let id = classroom["id"].as_str().unwrap_or_default();
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/classrooms/{id}/students"),
+        &json!({ "names": ["Grace"] }),
+        &teacher_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let students: serde_json::Value = serde_json::from_str(&body)?;
+    let student_id = students["data"][0]["userId"].as_str().unwrap_or_default();
+
+    let token = impersonation_token(&app, &state).await?;
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/classrooms/{id}/students/{student_id}/password"),
+        &json!({}),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(!body.contains("password\":"), "{body}");
+
+    let (status, _) = common::post_json(
+        &app,
+        "/api/v1/auth/signin/classroom",
+        &json!({
+            "classCode": classroom["code"],
+            "username": students["data"][0]["username"],
+            "password": students["data"][0]["password"],
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    Ok(())
+}
+
+#[tokio::test]
+async fn billing_checkout_is_refused_while_impersonating() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    signup_user(&app, "user@example.com", "player", "Password123").await;
+
+    let token = impersonation_token(&app, &state).await?;
+    let (status, _body) =
+        common::post_json_with_auth(&app, "/api/v1/billing/checkout", &json!({}), &token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let target = user_id(&state, "user@example.com").await?;
+    let refused = log_for(&state, target)
+        .await?
+        .into_iter()
+        .filter(|e| e.outcome == "refused")
+        .map(|e| e.path)
+        .collect::<Vec<_>>();
+    assert_eq!(refused, ["/api/v1/billing/checkout"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn impersonation_requires_a_reason_and_a_non_admin_target() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (admin_token, _) = signup_user(&app, "admin@example.com", "overseer", "Password123").await;
+    signup_user(&app, "other@example.com", "other", "Password123").await;
+    make_admin(&state, "admin@example.com").await?;
+    make_admin(&state, "other@example.com").await?;
+    let other_admin = user_id(&state, "other@example.com").await?;
+
+    let (status, _body) = impersonate(&app, &admin_token, other_admin).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/users/{other_admin}/impersonate"),
+        &json!({ "reason": "  " }),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _body) = impersonate(&app, &admin_token, Uuid::new_v4()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    assert!(log_for(&state, other_admin).await?.is_empty());
+    Ok(())
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use std::net::SocketAddr;
+
+use axum::Router;
+use axum::extract::connect_info::MockConnectInfo;
+use axum::http::StatusCode;
+use migration::{Migrator, MigratorTrait};
+
+use aircade_api::config::Config;
+use aircade_api::middleware::ip_filter::{self, IpFilter};
+use aircade_api::middleware::proxy::{self, TrustedProxies};
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The load balancer requests normally arrive through.
+const PROXY: &str = "198.51.100.250";
+
+/// The app wrapped in the IP filter the way `main` wraps it, reached from `peer`.
+async fn test_app(admin_allowed_ips: &str, denied_ips: &str, peer: &str) -> Router {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(
+        db,
+        Config {
+            admin_allowed_ips: ip_filter::parse_list(admin_allowed_ips).unwrap_or_default(),
+            denied_ips: ip_filter::parse_list(denied_ips).unwrap_or_default(),
+            trusted_proxies: ip_filter::parse_list(PROXY).unwrap_or_default(),
+            ..common::test_config()
+        },
+    );
+
+    aircade_api::routes::router(&state.config)
+        .layer(axum::middleware::from_fn_with_state(
+            IpFilter::from_config(&state.config),
+            ip_filter::enforce,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            TrustedProxies::from_config(&state.config),
+            proxy::resolve,
+        ))
+        .layer(MockConnectInfo(SocketAddr::new(
+            peer.parse().unwrap_or_else(|_| [127, 0, 0, 1].into()),
+            443,
+        )))
+        .with_state(state)
+}
+
+async fn get_from(app: &Router, uri: &str, ip: &str) -> StatusCode {
+    let (status, _headers, _body) = common::get_raw(app, uri, &[("x-forwarded-for", ip)]).await;
+    status
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn admin_routes_are_only_reachable_from_allowed_networks() {
+    let app = test_app("10.0.0.0/8", "", PROXY).await;
+
+    let outside = get_from(&app, "/api/v1/admin/tags", "203.0.113.5").await;
+    assert_eq!(outside, StatusCode::FORBIDDEN);
+
+    // Inside the network the request reaches authentication
+    let inside = get_from(&app, "/api/v1/admin/tags", "10.20.30.40").await;
+    assert_eq!(inside, StatusCode::UNAUTHORIZED);
+
+    let public = get_from(&app, "/api/v1/health", "203.0.113.5").await;
+    assert_eq!(public, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn runtime_metrics_are_only_reachable_from_allowed_networks() {
+    let app = test_app("10.0.0.0/8", "", PROXY).await;
+
+    let outside = get_from(&app, "/api/v1/admin/metrics", "203.0.113.5").await;
+    assert_eq!(outside, StatusCode::FORBIDDEN);
+    let inside = get_from(&app, "/api/v1/admin/metrics", "10.20.30.40").await;
+    assert_eq!(inside, StatusCode::UNAUTHORIZED);
+
+    // The old path outside `/admin` is gone
+    let old = get_from(&app, "/api/v1/metrics", "10.20.30.40").await;
+    assert_eq!(old, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn denied_networks_are_refused_on_every_route() {
+    let app = test_app("", "192.0.2.0/24", PROXY).await;
+
+    assert_eq!(
+        get_from(&app, "/api/v1/health", "192.0.2.99").await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        get_from(&app, "/api/v1/health", "198.51.100.1").await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn forwarded_addresses_are_only_believed_from_trusted_proxies() {
+    // Straight from an outside client, X-Forwarded-For is ignored
+    let direct = test_app("10.0.0.0/8", "", "203.0.113.5").await;
+    assert_eq!(
+        get_from(&direct, "/api/v1/admin/tags", "10.20.30.40").await,
+        StatusCode::FORBIDDEN
+    );
+
+    // Through the proxy, a prefilled hop is left of the address the proxy appended
+    let proxied = test_app("10.0.0.0/8", "", PROXY).await;
+    assert_eq!(
+        get_from(&proxied, "/api/v1/admin/tags", "10.20.30.40, 203.0.113.5").await,
+        StatusCode::FORBIDDEN
+    );
+
+    // A client outside the allowlist cannot escape the deny list by forging either header
+    let denied = test_app("", "203.0.113.0/24", "203.0.113.5").await;
+    let (status, _headers, _body) = common::get_raw(
+        &denied,
+        "/api/v1/health",
+        &[
+            ("x-forwarded-for", "198.51.100.1"),
+            ("x-real-ip", "198.51.100.1"),
+        ],
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use migration::{Migrator, MigratorTrait};
+use serde_json::json;
+use tracing::Level;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+use aircade_api::logging;
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+fn debug_enabled() -> bool {
+    tracing::enabled!(target: "aircade_api", Level::DEBUG)
+}
+
+async fn log_level(app: &Router, token: &str) -> anyhow::Result<serde_json::Value> {
+    let (status, body) = common::get_with_auth(app, "/api/v1/admin/log-level", token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    Ok(serde_json::from_str(&body)?)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// /api/v1/admin/log-level
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn admins_override_the_log_filter_until_it_expires_or_is_reset() -> anyhow::Result<()> {
+    let _ = tracing_subscriber::registry()
+        .with(logging::reloadable_filter("aircade_api=warn"))
+        .try_init();
+    let (app, state) = test_app().await;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+    assert!(!debug_enabled());
+
+    let (status, body) = common::put_json_with_auth(
+        &app,
+        "/api/v1/admin/log-level",
+        &json!({ "directives": "aircade_api=debug", "ttlSecs": 1 }),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert!(debug_enabled());
+    let json = log_level(&app, &admin_token).await?;
+    assert_eq!(json["defaultDirectives"], "aircade_api=warn");
+    assert_eq!(json["override"]["directives"], "aircade_api=debug");
+
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+    assert!(!debug_enabled(), "the override expired");
+    assert!(log_level(&app, &admin_token).await?["override"].is_null());
+
+    let (status, _) = common::put_json_with_auth(
+        &app,
+        "/api/v1/admin/log-level",
+        &json!({ "directives": "aircade_api=debug" }),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(debug_enabled());
+
+    let (status, _) = common::delete_with_auth(&app, "/api/v1/admin/log-level", &admin_token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(!debug_enabled());
+
+    let (status, _) = common::delete_with_auth(&app, "/api/v1/admin/log-level", &admin_token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn only_admins_set_valid_log_filters() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+    let (_, user_token) = common::create_user(&state, "user").await?;
+
+    let (status, _) = common::put_json_with_auth(
+        &app,
+        "/api/v1/admin/log-level",
+        &json!({ "directives": "aircade_api=debug" }),
+        &user_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    for invalid in [
+        json!({ "directives": "aircade_api=loud" }),
+        json!({ "directives": " " }),
+        json!({ "directives": "aircade_api=debug", "ttlSecs": 0 }),
+        json!({ "directives": "aircade_api=debug", "ttlSecs": 86_400 }),
+    ] {
+        let (status, body) =
+            common::put_json_with_auth(&app, "/api/v1/admin/log-level", &invalid, &admin_token)
+                .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{invalid}: {body}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,171 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::{login_event, user};
+use aircade_api::jobs::purge;
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Helper: sign up a user and return (`access_token`, `refresh_token`).
+async fn signup_user(
+    app: &Router,
+    email: &str,
+    username: &str,
+    password: &str,
+) -> (String, String) {
+    let (status, body) = common::post_json(
+        app,
+        "/api/v1/auth/signup/email",
+        &json!({
+            "email": email,
+            "username": username,
+            "password": password,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "signup failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let token = json["token"].as_str().unwrap_or_default().to_string();
+    let refresh = json["refreshToken"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    (token, refresh)
+}
+
+async fn signin_from(app: &Router, email: &str, password: &str, ip: &str) -> StatusCode {
+    let (status, _headers, _body) = common::post_json_raw(
+        app,
+        "/api/v1/auth/signin/email",
+        &json!({ "email": email, "password": password }),
+        &[("user-agent", "Firefox/130"), ("x-forwarded-for", ip)],
+    )
+    .await;
+    status
+}
+
+async fn make_admin(state: &AppState, email: &str) -> anyhow::Result<()> {
+    let admin = user::Entity::find()
+        .filter(user::Column::Email.eq(email))
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("no user {email}"))?;
+    let mut active: user::ActiveModel = admin.into();
+    active.role = Set("admin".to_string());
+    active.update(&state.db).await?;
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn user_sees_own_failed_and_successful_signins() {
+    let (app, _state) = test_app().await;
+    let (token, _) = signup_user(&app, "audit@example.com", "audited", "Password123").await;
+
+    let bad = signin_from(&app, "audit@example.com", "WrongPassword", "198.51.100.9").await;
+    assert_eq!(bad, StatusCode::UNAUTHORIZED);
+    let good = signin_from(&app, "audit@example.com", "Password123", "203.0.113.7").await;
+    assert_eq!(good, StatusCode::OK);
+
+    let (status, body) =
+        common::get_with_auth(&app, "/api/v1/users/me/security/events", &token).await;
+    assert_eq!(status, StatusCode::OK, "list events failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(json["total"], 2);
+    let events = json["data"].as_array().cloned().unwrap_or_default();
+    let outcomes: Vec<&str> = events
+        .iter()
+        .filter_map(|e| e["outcome"].as_str())
+        .collect();
+    assert!(outcomes.contains(&"success"));
+    assert!(outcomes.contains(&"invalid_credentials"));
+    assert!(events.iter().all(|e| e["provider"] == "email"));
+    assert!(
+        events
+            .iter()
+            .any(|e| e["ipAddress"] == "198.51.100.9" && e["userAgent"] == "Firefox/130")
+    );
+}
+
+#[tokio::test]
+async fn admins_can_search_the_audit_log() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (admin_token, _) = signup_user(&app, "admin@example.com", "overseer", "Password123").await;
+    let (user_token, _) = signup_user(&app, "user@example.com", "plain", "Password123").await;
+    make_admin(&state, "admin@example.com").await?;
+
+    // Credential stuffing from one address against an account that does not exist
+    for _ in 0..3 {
+        signin_from(&app, "ghost@example.com", "Password123", "192.0.2.66").await;
+    }
+
+    let (status, _body) =
+        common::get_with_auth(&app, "/api/v1/admin/login-events", &user_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = common::get_with_auth(
+        &app,
+        "/api/v1/admin/login-events?ipAddress=192.0.2.66",
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "admin search failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(json["total"], 3);
+    assert_eq!(json["data"][0]["outcome"], "unknown_account");
+    assert_eq!(json["data"][0]["identifier"], "ghost@example.com");
+    assert!(json["data"][0]["userId"].is_null());
+    Ok(())
+}
+
+#[tokio::test]
+async fn purge_prunes_old_signin_events() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    signup_user(&app, "old@example.com", "oldtimer", "Password123").await;
+    signin_from(&app, "old@example.com", "Password123", "203.0.113.7").await;
+
+    login_event::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(None),
+        identifier: Set(Some("old@example.com".to_string())),
+        provider: Set("email".to_string()),
+        outcome: Set("invalid_credentials".to_string()),
+        ip_address: Set(None),
+        user_agent: Set(None),
+        country: Set(None),
+        created_at: Set((Utc::now() - Duration::days(120)).fixed_offset()),
+    }
+    .insert(&state.db)
+    .await?;
+
+    let purged = purge::run(&state.db, &state.config, Utc::now()).await?;
+    assert_eq!(purged.login_events, 1);
+    assert_eq!(login_event::Entity::find().count(&state.db).await?, 1);
+    Ok(())
+}
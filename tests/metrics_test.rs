@@ -0,0 +1,91 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use migration::{Migrator, MigratorTrait};
+use uuid::Uuid;
+
+use aircade_api::sessions::ClientRole;
+use aircade_api::state::AppState;
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+#[tokio::test]
+async fn metrics_requires_admin() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+
+    let (status, _body) = common::get(&app, "/api/v1/admin/metrics").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (_, token) = common::create_user(&state, "user").await?;
+    let (status, _body) = common::get_with_auth(&app, "/api/v1/admin/metrics", &token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    Ok(())
+}
+
+#[tokio::test]
+async fn metrics_reports_connections_and_relay_counters() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, token) = common::create_user(&state, "admin").await?;
+
+    let session_id = Uuid::new_v4();
+    let (host_tx, _host_rx) = tokio::sync::mpsc::unbounded_channel();
+    let (player_tx, player_rx) = tokio::sync::mpsc::unbounded_channel();
+    state
+        .session_manager
+        .register(session_id, ClientRole::Host, host_tx);
+    state
+        .session_manager
+        .register(session_id, ClientRole::Player(Uuid::new_v4()), player_tx);
+
+    // Host receives, player channel is closed → one relayed, one dropped
+    drop(player_rx);
+    state.session_manager.broadcast(session_id, "{}");
+
+    let (status, body) = common::get_with_auth(&app, "/api/v1/admin/metrics", &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(json["sessions"]["activeSessions"], 1);
+    assert_eq!(json["sessions"]["connectedHosts"], 1);
+    assert_eq!(json["sessions"]["connectedPlayers"], 1);
+    assert_eq!(json["sessions"]["messagesRelayedTotal"], 1);
+    assert_eq!(json["sessions"]["droppedSendsTotal"], 1);
+    assert!(json["sessions"]["messagesRelayedPerSecond"].is_number());
+    assert_eq!(json["sessions"]["connectsTotal"], 2);
+    assert_eq!(json["sessions"]["messagesReceivedByType"]["other"], 0);
+    assert!(json["sessions"]["messageSizes"]["buckets"].is_array());
+    Ok(())
+}
+
+#[tokio::test]
+async fn metrics_report_database_pool_utilization() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, token) = common::create_user(&state, "admin").await?;
+
+    let (status, body) = common::get_with_auth(&app, "/api/v1/admin/metrics", &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let database = &json["database"];
+    let size = database["size"].as_u64().unwrap_or_default();
+    assert!(size >= 1, "{database}");
+    assert_eq!(
+        database["inUse"].as_u64().unwrap_or_default()
+            + database["idle"].as_u64().unwrap_or_default(),
+        size
+    );
+    assert!(database["maxConnections"].as_u64().unwrap_or_default() >= size);
+    assert!(database["acquireWaitMs"].is_u64());
+    Ok(())
+}
@@ -0,0 +1,231 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveModelTrait;
+use sea_orm::ActiveValue::Set;
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::game;
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a published public game owned by `owner_id` and return its ID.
+async fn create_published_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Reported Game".to_string()),
+        slug: Set(format!("reported-game-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("published".to_string()),
+        visibility: Set("public".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+/// Report `target_type` `target_id` as `token`'s user and return the report ID.
+async fn report(
+    app: &Router,
+    token: &str,
+    target_type: &str,
+    target_id: Uuid,
+) -> anyhow::Result<String> {
+    let (status, body) = common::post_json_with_auth(
+        app,
+        "/api/v1/reports",
+        &json!({ "targetType": target_type, "targetId": target_id, "reason": "spam" }),
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    Ok(v["id"].as_str().unwrap_or_default().to_string())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// /api/v1/admin/reports
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn claimed_reports_are_left_to_their_moderator() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (creator_id, creator_token) = common::create_user(&state, "user").await?;
+    let (_, reporter_token) = common::create_user(&state, "user").await?;
+    let (first_id, first_token) = common::create_user(&state, "moderator").await?;
+    let (second_id, second_token) = common::create_user(&state, "moderator").await?;
+    let game_id = create_published_game(&state, creator_id).await?;
+    let id = report(&app, &reporter_token, "game", game_id).await?;
+
+    // Only moderators see the queue
+    let (status, _) = common::get_with_auth(&app, "/api/v1/admin/reports", &reporter_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = common::get_with_auth(&app, "/api/v1/admin/reports", &first_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let queue: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(queue["total"], 1);
+    assert_eq!(queue["data"][0]["id"], id.as_str());
+    assert_eq!(queue["data"][0]["content"]["title"], "Reported Game");
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/reports/{id}/claim"),
+        &json!({}),
+        &first_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    for action in ["claim", "dismiss"] {
+        let (status, _) = common::post_json_with_auth(
+            &app,
+            &format!("/api/v1/admin/reports/{id}/{action}"),
+            &json!({}),
+            &second_token,
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT, "{action}");
+    }
+
+    let (_, body) = common::get_with_auth(
+        &app,
+        &format!("/api/v1/admin/reports?assignedTo={first_id}"),
+        &second_token,
+    )
+    .await;
+    let mine: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(mine["total"], 1);
+
+    // Reports only go to moderators
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/reports/{id}/assign"),
+        &json!({ "moderatorId": creator_id }),
+        &first_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/reports/{id}/assign"),
+        &json!({ "moderatorId": second_id }),
+        &first_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/reports/{id}/warn"),
+        &json!({ "note": "Please stop posting links in your description." }),
+        &second_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let resolved: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(resolved["status"], "resolved");
+    assert_eq!(resolved["resolution"], "warned");
+    assert_eq!(resolved["resolvedBy"], second_id.to_string());
+
+    let (_, body) = common::get_with_auth(&app, "/api/v1/admin/reports", &first_token).await;
+    let queue: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(queue["total"], 0);
+
+    let (_, body) =
+        common::get_with_auth(&app, "/api/v1/users/me/notifications", &creator_token).await;
+    let notices: Vec<serde_json::Value> = serde_json::from_str(&body)?;
+    assert!(notices.iter().any(|n| {
+        n["kind"] == "moderation_warning"
+            && n["body"]
+                .as_str()
+                .unwrap_or_default()
+                .contains("Please stop posting links")
+    }));
+
+    // The warned game stays up
+    let (status, _) = common::get(&app, &format!("/api/v1/games/{game_id}")).await;
+    assert_eq!(status, StatusCode::OK);
+    Ok(())
+}
+
+#[tokio::test]
+async fn takedown_removes_content_and_resolves_duplicates() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (creator_id, _) = common::create_user(&state, "user").await?;
+    let (_, first_token) = common::create_user(&state, "user").await?;
+    let (_, second_token) = common::create_user(&state, "user").await?;
+    let (_, moderator_token) = common::create_user(&state, "moderator").await?;
+    let game_id = create_published_game(&state, creator_id).await?;
+
+    let id = report(&app, &first_token, "game", game_id).await?;
+    report(&app, &second_token, "game", game_id).await?;
+    let user_report = report(&app, &first_token, "user", creator_id).await?;
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/reports/{id}/takedown"),
+        &json!({}),
+        &moderator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let resolved: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(resolved["resolution"], "taken_down");
+    assert_eq!(resolved["content"]["status"], "removed_by_moderation");
+
+    let (status, _) = common::get(&app, &format!("/api/v1/games/{game_id}")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, body) = common::get_with_auth(
+        &app,
+        "/api/v1/admin/reports?status=resolved",
+        &moderator_token,
+    )
+    .await;
+    let resolved: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(resolved["total"], 2);
+
+    // Taking down a user suspends the account
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/reports/{user_report}/takedown"),
+        &json!({ "note": "Spam account." }),
+        &moderator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let resolved: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(resolved["content"]["accountStatus"], "suspended");
+    Ok(())
+}
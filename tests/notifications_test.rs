@@ -0,0 +1,349 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use futures_util::StreamExt;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, EntityTrait};
+use serde_json::json;
+use tokio_tungstenite::tungstenite::Message;
+use uuid::Uuid;
+
+use aircade_api::email;
+use aircade_api::entities::{game, user};
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a published public game owned by `owner_id` and return its ID.
+async fn create_published_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Party Hit".to_string()),
+        slug: Set(format!("party-hit-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("published".to_string()),
+        visibility: Set("public".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+async fn username_of(state: &AppState, user_id: Uuid) -> String {
+    user::Entity::find_by_id(user_id)
+        .one(&state.db)
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default()
+}
+
+async fn my_notifications(app: &Router, token: &str) -> anyhow::Result<serde_json::Value> {
+    let (status, body) = common::get_with_auth(app, "/api/v1/users/me/notifications", token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    Ok(serde_json::from_str(&body)?)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Notifications from existing flows
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn review_notifies_the_creator() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (creator_id, creator_token) = common::create_user(&state, "user").await?;
+    let (_, reviewer_token) = common::create_user(&state, "user").await?;
+    let game_id = create_published_game(&state, creator_id).await?;
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/reviews"),
+        &json!({ "rating": 4, "body": "Great fun" }),
+        &reviewer_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+
+    let v = my_notifications(&app, &creator_token).await?;
+    assert_eq!(v[0]["kind"], "review_posted");
+    assert!(v[0]["body"].as_str().unwrap_or_default().contains("4/5"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn new_follower_notifies_once() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (creator_id, creator_token) = common::create_user(&state, "user").await?;
+    let (_, fan_token) = common::create_user(&state, "user").await?;
+    let creator = username_of(&state, creator_id).await;
+
+    for _ in 0..2 {
+        common::post_json_with_auth(
+            &app,
+            &format!("/api/v1/users/{creator}/follow"),
+            &json!({}),
+            &fan_token,
+        )
+        .await;
+    }
+
+    let v = my_notifications(&app, &creator_token).await?;
+    assert_eq!(v.as_array().map(Vec::len), Some(1));
+    assert_eq!(v[0]["kind"], "follower_gained");
+    Ok(())
+}
+
+#[tokio::test]
+async fn featuring_a_game_notifies_the_creator() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (creator_id, creator_token) = common::create_user(&state, "user").await?;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+    let game_id = create_published_game(&state, creator_id).await?;
+
+    // Creators cannot feature their own games
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/games/{game_id}/feature"),
+        &json!({}),
+        &creator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/games/{game_id}/feature"),
+        &json!({}),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT, "{body}");
+
+    let v = my_notifications(&app, &creator_token).await?;
+    assert_eq!(v[0]["kind"], "game_featured");
+
+    let (_, body) = common::get(&app, &format!("/api/v1/games/{game_id}")).await;
+    let game: serde_json::Value = serde_json::from_str(&body)?;
+    assert!(game["featuredAt"].is_string(), "{body}");
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Mark all read
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn mark_all_read() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (creator_id, creator_token) = common::create_user(&state, "user").await?;
+    let creator = username_of(&state, creator_id).await;
+    for _ in 0..2 {
+        let (_, fan_token) = common::create_user(&state, "user").await?;
+        common::post_json_with_auth(
+            &app,
+            &format!("/api/v1/users/{creator}/follow"),
+            &json!({}),
+            &fan_token,
+        )
+        .await;
+    }
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/users/me/notifications/read-all",
+        &json!({}),
+        &creator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let v = my_notifications(&app, &creator_token).await?;
+    assert_eq!(v.as_array().map(Vec::len), Some(2));
+    assert!(
+        v.as_array()
+            .is_some_and(|all| all.iter().all(|n| n["readAt"].is_string()))
+    );
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Email preferences
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn notification_preferences_default_and_update() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, token) = common::create_user(&state, "user").await?;
+
+    let (status, body) =
+        common::get_with_auth(&app, "/api/v1/users/me/notification-preferences", &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(
+        v,
+        json!({
+            "productUpdates": false,
+            "reviewAlerts": true,
+            "securityAlerts": true,
+            "sessionSummaries": true,
+            "sessionInvites": true,
+        })
+    );
+
+    let (status, body) = common::patch_json_with_auth(
+        &app,
+        "/api/v1/users/me/notification-preferences",
+        &json!({ "productUpdates": true, "reviewAlerts": false }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    // Omitted categories keep their setting, and a second update edits the same row
+    let (status, body) = common::patch_json_with_auth(
+        &app,
+        "/api/v1/users/me/notification-preferences",
+        &json!({ "sessionSummaries": false }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(
+        v,
+        json!({
+            "productUpdates": true,
+            "reviewAlerts": false,
+            "securityAlerts": true,
+            "sessionSummaries": false,
+            "sessionInvites": true,
+        })
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn opted_out_categories_are_not_emailed() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (user_id, token) = common::create_user(&state, "user").await?;
+
+    let send = |category| email::send_to_user(&state.db, user_id, category, "Subject", "Body");
+    assert!(send(email::Category::ReviewAlerts).await?);
+    assert!(!send(email::Category::ProductUpdates).await?);
+
+    common::patch_json_with_auth(
+        &app,
+        "/api/v1/users/me/notification-preferences",
+        &json!({ "reviewAlerts": false }),
+        &token,
+    )
+    .await;
+    assert!(!send(email::Category::ReviewAlerts).await?);
+    assert!(send(email::Category::SecurityAlerts).await?);
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// WebSocket push
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Serve `app` on a random local port and return its address.
+async fn serve(app: Router) -> anyhow::Result<std::net::SocketAddr> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    Ok(addr)
+}
+
+/// Read the next text frame as JSON, failing after a second.
+async fn next_json<S>(socket: &mut S) -> anyhow::Result<serde_json::Value>
+where
+    S: futures_util::Stream<Item = Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin,
+{
+    loop {
+        let msg = tokio::time::timeout(Duration::from_secs(1), socket.next())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("socket closed"))??;
+        if let Message::Text(text) = msg {
+            return Ok(serde_json::from_str(&text)?);
+        }
+    }
+}
+
+#[tokio::test]
+async fn new_notifications_are_pushed_over_websocket() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (creator_id, creator_token) = common::create_user(&state, "user").await?;
+    let (_, fan_token) = common::create_user(&state, "user").await?;
+    let creator = username_of(&state, creator_id).await;
+
+    let addr = serve(app.clone()).await?;
+    let (mut socket, _) = tokio_tungstenite::connect_async(format!(
+        "ws://{addr}/api/v1/users/me/notifications/ws?token={creator_token}"
+    ))
+    .await?;
+    let connected = next_json(&mut socket).await?;
+    assert_eq!(connected["type"], "connected");
+    assert_eq!(connected["payload"]["unreadCount"], 0);
+
+    common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/users/{creator}/follow"),
+        &json!({}),
+        &fan_token,
+    )
+    .await;
+
+    let pushed = next_json(&mut socket).await?;
+    assert_eq!(pushed["type"], "notification");
+    assert_eq!(pushed["payload"]["kind"], "follower_gained");
+    Ok(())
+}
+
+#[tokio::test]
+async fn websocket_rejects_invalid_token() -> anyhow::Result<()> {
+    let (app, _state) = test_app().await;
+    let addr = serve(app).await?;
+
+    let result = tokio_tungstenite::connect_async(format!(
+        "ws://{addr}/api/v1/users/me/notifications/ws?token=nope"
+    ))
+    .await;
+    match result {
+        Err(tokio_tungstenite::tungstenite::Error::Http(response)) => {
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+        other => anyhow::bail!("expected the handshake to be refused, got {other:?}"),
+    }
+    Ok(())
+}
@@ -0,0 +1,383 @@
+mod common;
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use http_body_util::BodyExt;
+use migration::{Migrator, MigratorTrait};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tower::ServiceExt;
+
+async fn test_app() -> Router {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    aircade_api::routes::router(&state.config).with_state(state)
+}
+
+/// Helper: sign up a user and return (`access_token`, `refresh_token`).
+async fn signup_user(
+    app: &Router,
+    email: &str,
+    username: &str,
+    password: &str,
+) -> (String, String) {
+    let (status, body) = common::post_json(
+        app,
+        "/api/v1/auth/signup/email",
+        &json!({
+            "email": email,
+            "username": username,
+            "password": password,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "signup failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let token = json["token"].as_str().unwrap_or_default().to_string();
+    let refresh = json["refreshToken"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    (token, refresh)
+}
+
+const REDIRECT_URI: &str = "https://tool.example.com/callback";
+
+/// Helper: POST a form to `/api/v1/oauth/token`, optionally with HTTP Basic client
+/// credentials, and return (status, JSON body).
+async fn token_request(
+    app: &Router,
+    form: &[(&str, &str)],
+    basic: Option<(&str, &str)>,
+) -> (StatusCode, serde_json::Value) {
+    let body: String = form
+        .iter()
+        .map(|(k, v)| format!("{k}={}", urlencoding::encode(v)))
+        .collect::<Vec<_>>()
+        .join("&");
+    let mut builder = Request::builder()
+        .method("POST")
+        .uri("/api/v1/oauth/token")
+        .header("content-type", "application/x-www-form-urlencoded");
+    if let Some((id, secret)) = basic {
+        let credentials = data_encoding::BASE64.encode(format!("{id}:{secret}").as_bytes());
+        builder = builder.header("authorization", format!("Basic {credentials}"));
+    }
+    let request = builder.body(Body::from(body)).unwrap_or_default();
+    let response = app.clone().oneshot(request).await.unwrap_or_default();
+    let status = response.status();
+    let bytes = response
+        .into_body()
+        .collect()
+        .await
+        .map(http_body_util::Collected::to_bytes)
+        .unwrap_or_default();
+    (status, serde_json::from_slice(&bytes).unwrap_or_default())
+}
+
+/// Helper: register a client as `token`'s user and return the response JSON.
+async fn register_client(app: &Router, token: &str, confidential: bool) -> serde_json::Value {
+    let (status, body) = common::post_json_with_auth(
+        app,
+        "/api/v1/oauth/clients",
+        &json!({
+            "name": "Level Editor",
+            "redirectUris": [REDIRECT_URI],
+            "confidential": confidential,
+        }),
+        token,
+    )
+    .await;
+    assert_eq!(
+        status,
+        StatusCode::CREATED,
+        "register client failed: {body}"
+    );
+    serde_json::from_str(&body).unwrap_or_default()
+}
+
+/// Helper: approve (or deny) an authorization request and return the query of the redirect
+/// back to the client.
+async fn decide(
+    app: &Router,
+    token: &str,
+    params: &serde_json::Value,
+    approve: bool,
+) -> Vec<(String, String)> {
+    let mut body = params.clone();
+    body["approve"] = json!(approve);
+    let (status, body) =
+        common::post_json_with_auth(app, "/api/v1/oauth/authorize", &body, token).await;
+    assert_eq!(status, StatusCode::OK, "authorize failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let redirect = json["redirectTo"].as_str().unwrap_or_default();
+    assert!(redirect.starts_with(REDIRECT_URI), "{redirect}");
+    reqwest::Url::parse(redirect)
+        .map(|url| url.query_pairs().into_owned().collect())
+        .unwrap_or_default()
+}
+
+fn query_value(query: &[(String, String)], key: &str) -> String {
+    query
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.clone())
+        .unwrap_or_default()
+}
+
+#[tokio::test]
+async fn confidential_client_authorization_code_flow() {
+    let app = test_app().await;
+    let (developer, _refresh) =
+        signup_user(&app, "dev@example.com", "devuser", "Password123").await;
+    let (player, _refresh) =
+        signup_user(&app, "player@example.com", "playeruser", "Password123").await;
+
+    let client = register_client(&app, &developer, true).await;
+    let client_id = client["clientId"].as_str().unwrap_or_default();
+    let secret = client["clientSecret"].as_str().unwrap_or_default();
+    assert!(secret.starts_with("acd_cs_"));
+
+    // Consent screen data
+    let (status, body) = common::get_with_auth(
+        &app,
+        &format!(
+            "/api/v1/oauth/authorize?response_type=code&client_id={client_id}&scope=games:write%20games:read&state=xyz"
+        ),
+        &player,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "consent failed: {body}");
+    let consent: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(consent["client"]["name"], "Level Editor");
+    assert_eq!(consent["scopes"], json!(["games:read", "games:write"]));
+    assert_eq!(consent["redirectUri"], REDIRECT_URI);
+
+    let params = json!({
+        "response_type": "code",
+        "client_id": client_id,
+        "redirect_uri": REDIRECT_URI,
+        "scope": "games:read",
+        "state": "xyz",
+    });
+    let query = decide(&app, &player, &params, true).await;
+    assert_eq!(query_value(&query, "state"), "xyz");
+    let code = query_value(&query, "code");
+
+    let form = [
+        ("grant_type", "authorization_code"),
+        ("code", code.as_str()),
+        ("redirect_uri", REDIRECT_URI),
+    ];
+    let (status, json) = token_request(&app, &form, Some((client_id, secret))).await;
+    assert_eq!(status, StatusCode::OK, "token failed: {json}");
+    assert_eq!(json["token_type"], "Bearer");
+    assert_eq!(json["scope"], "games:read");
+    let access_token = json["access_token"].as_str().unwrap_or_default();
+
+    // The token acts as the player, within the approved scope
+    let (status, _body) = common::get_with_auth(&app, "/api/v1/users/me/games", access_token).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/games",
+        &json!({ "title": "Nope" }),
+        access_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // ...and shows up in the player's token list
+    let (_status, body) = common::get_with_auth(&app, "/api/v1/users/me/tokens", &player).await;
+    let tokens: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(tokens[0]["oauthClientId"], client_id);
+    assert_eq!(tokens[0]["name"], "Level Editor");
+
+    // Codes are single-use
+    let (status, json) = token_request(&app, &form, Some((client_id, secret))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"], "invalid_grant");
+
+    // Deleting the client revokes its tokens
+    let (status, _body) = common::delete_with_auth(
+        &app,
+        &format!("/api/v1/oauth/clients/{client_id}"),
+        &developer,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _body) = common::get_with_auth(&app, "/api/v1/users/me/games", access_token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn public_client_requires_pkce() {
+    let app = test_app().await;
+    let (developer, _refresh) =
+        signup_user(&app, "pkcedev@example.com", "pkcedev", "Password123").await;
+    let client = register_client(&app, &developer, false).await;
+    let client_id = client["clientId"].as_str().unwrap_or_default();
+    assert!(client["clientSecret"].is_null());
+    assert_eq!(client["confidential"], false);
+
+    let mut params = json!({
+        "response_type": "code",
+        "client_id": client_id,
+        "scope": "profile:read",
+        "approve": true,
+    });
+    let (status, _body) =
+        common::post_json_with_auth(&app, "/api/v1/oauth/authorize", &params, &developer).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let verifier = "a-sufficiently-long-code-verifier-for-pkce-0123456789";
+    let challenge = data_encoding::BASE64URL_NOPAD.encode(&Sha256::digest(verifier.as_bytes()));
+    params["code_challenge"] = json!(challenge);
+    params["code_challenge_method"] = json!("S256");
+
+    let code = query_value(&decide(&app, &developer, &params, true).await, "code");
+    let (status, json) = token_request(
+        &app,
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", code.as_str()),
+            ("redirect_uri", REDIRECT_URI),
+            ("client_id", client_id),
+            (
+                "code_verifier",
+                "the-wrong-verifier-but-still-long-enough-to-be-valid",
+            ),
+        ],
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"], "invalid_grant");
+
+    let code = query_value(&decide(&app, &developer, &params, true).await, "code");
+    let (status, json) = token_request(
+        &app,
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", code.as_str()),
+            ("redirect_uri", REDIRECT_URI),
+            ("client_id", client_id),
+            ("code_verifier", verifier),
+        ],
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "token failed: {json}");
+    let access_token = json["access_token"].as_str().unwrap_or_default();
+    let (status, _body) = common::get_with_auth(&app, "/api/v1/users/me", access_token).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn redirect_uri_is_only_required_when_the_authorization_named_it() {
+    let app = test_app().await;
+    let (token, _refresh) =
+        signup_user(&app, "redir@example.com", "rediruser", "Password123").await;
+    let client = register_client(&app, &token, true).await;
+    let client_id = client["clientId"].as_str().unwrap_or_default();
+    let secret = client["clientSecret"].as_str().unwrap_or_default();
+    let exchange = async |params: &serde_json::Value, redirect_uri: Option<&str>| {
+        let code = query_value(&decide(&app, &token, params, true).await, "code");
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code.as_str()),
+        ];
+        form.extend(redirect_uri.map(|uri| ("redirect_uri", uri)));
+        token_request(&app, &form, Some((client_id, secret))).await
+    };
+
+    // Omitted from the authorization request: the client's only registered URI was used
+    let mut params = json!({
+        "response_type": "code",
+        "client_id": client_id,
+        "scope": "games:read",
+    });
+    let (status, json) = exchange(&params, None).await;
+    assert_eq!(status, StatusCode::OK, "token failed: {json}");
+    let (status, json) = exchange(&params, Some(REDIRECT_URI)).await;
+    assert_eq!(status, StatusCode::OK, "token failed: {json}");
+    let (status, json) = exchange(&params, Some("https://tool.example.com/other")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"], "invalid_grant");
+
+    // Named in the authorization request: it must be sent again
+    params["redirect_uri"] = json!(REDIRECT_URI);
+    let (status, json) = exchange(&params, None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"], "invalid_grant");
+    let (status, json) = exchange(&params, Some(REDIRECT_URI)).await;
+    assert_eq!(status, StatusCode::OK, "token failed: {json}");
+}
+
+#[tokio::test]
+async fn denied_authorization_redirects_with_error() {
+    let app = test_app().await;
+    let (token, _refresh) = signup_user(&app, "deny@example.com", "denyuser", "Password123").await;
+    let client = register_client(&app, &token, true).await;
+
+    let params = json!({
+        "response_type": "code",
+        "client_id": client["clientId"],
+        "scope": "games:read",
+        "state": "abc",
+    });
+    let query = decide(&app, &token, &params, false).await;
+    assert_eq!(query_value(&query, "error"), "access_denied");
+    assert_eq!(query_value(&query, "state"), "abc");
+    assert_eq!(query_value(&query, "code"), "");
+}
+
+#[tokio::test]
+async fn invalid_requests_are_rejected() {
+    let app = test_app().await;
+    let (token, _refresh) =
+        signup_user(&app, "invalid@example.com", "invaliduser", "Password123").await;
+
+    let (status, _body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/oauth/clients",
+        &json!({ "name": "Tool", "redirectUris": ["http://tool.example.com/cb"] }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let client = register_client(&app, &token, true).await;
+    let client_id = client["clientId"].as_str().unwrap_or_default();
+    for query in [
+        format!("response_type=token&client_id={client_id}&scope=games:read"),
+        format!(
+            "response_type=code&client_id={client_id}&scope=games:read&redirect_uri=https://evil.example.com/cb"
+        ),
+        format!("response_type=code&client_id={client_id}&scope=admin"),
+        format!("response_type=code&client_id={client_id}&scope=games:read&code_challenge=abc"),
+    ] {
+        let (status, body) =
+            common::get_with_auth(&app, &format!("/api/v1/oauth/authorize?{query}"), &token).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{query}: {body}");
+    }
+
+    let (status, json) = token_request(
+        &app,
+        &[("grant_type", "authorization_code"), ("code", "nope")],
+        Some((client_id, "acd_cs_wrong")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(json["error"], "invalid_client");
+
+    let (status, json) = token_request(&app, &[("grant_type", "password")], None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"], "unsupported_grant_type");
+}
@@ -0,0 +1,311 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{Form, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use data_encoding::BASE64URL_NOPAD;
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use migration::{Migrator, MigratorTrait};
+use ring::rand::SystemRandom;
+use ring::signature::{ECDSA_P256_SHA256_FIXED_SIGNING, EcdsaKeyPair, KeyPair};
+use serde_json::json;
+
+use aircade_api::config::{Config, OidcProviderConfig};
+
+const CLIENT_ID: &str = "aircade";
+
+/// The code the mock provider accepts at its token endpoint.
+const GOOD_CODE: &str = "good-code";
+
+#[derive(Clone)]
+struct MockKey {
+    kid: String,
+    signing_key: EncodingKey,
+    jwk: serde_json::Value,
+}
+
+#[derive(Clone)]
+struct MockProvider {
+    issuer: String,
+    key: Arc<Mutex<MockKey>>,
+    /// The nonce of the last authorization request, echoed in ID tokens.
+    nonce: Arc<Mutex<String>>,
+    discovery_fetches: Arc<AtomicUsize>,
+    jwks_fetches: Arc<AtomicUsize>,
+}
+
+impl MockProvider {
+    fn key(&self) -> Option<MockKey> {
+        self.key.lock().ok().map(|key| key.clone())
+    }
+
+    /// Replace the signing key, as a provider rotating its keys would.
+    fn rotate_key(&self, kid: &str) -> anyhow::Result<()> {
+        let key = mock_key(kid)?;
+        if let Ok(mut current) = self.key.lock() {
+            *current = key;
+        }
+        Ok(())
+    }
+
+    fn set_nonce(&self, nonce: &str) {
+        if let Ok(mut current) = self.nonce.lock() {
+            *current = nonce.to_string();
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct TokenForm {
+    code: String,
+}
+
+async fn discovery(State(mock): State<MockProvider>) -> Json<serde_json::Value> {
+    mock.discovery_fetches.fetch_add(1, Ordering::SeqCst);
+    Json(json!({
+        "issuer": mock.issuer,
+        "authorization_endpoint": format!("{}/authorize", mock.issuer),
+        "token_endpoint": format!("{}/token", mock.issuer),
+        "jwks_uri": format!("{}/jwks", mock.issuer),
+    }))
+}
+
+async fn token(
+    State(mock): State<MockProvider>,
+    headers: HeaderMap,
+    Form(form): Form<TokenForm>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    if form.code != GOOD_CODE || !headers.contains_key(header::AUTHORIZATION) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let key = mock.key().ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    let nonce = mock.nonce.lock().map(|n| n.clone()).unwrap_or_default();
+    let mut jwt_header = Header::new(Algorithm::ES256);
+    jwt_header.kid = Some(key.kid);
+    let claims = json!({
+        "iss": mock.issuer,
+        "aud": CLIENT_ID,
+        "exp": chrono::Utc::now().timestamp() + 300,
+        "sub": "user-42",
+        "email": "SSO.User@Example.com",
+        "email_verified": true,
+        "name": "Sso User",
+        "nonce": nonce,
+    });
+    let id_token = jsonwebtoken::encode(&jwt_header, &claims, &key.signing_key)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(
+        json!({ "access_token": "opaque", "token_type": "Bearer", "id_token": id_token }),
+    ))
+}
+
+async fn keys(State(mock): State<MockProvider>) -> Json<serde_json::Value> {
+    mock.jwks_fetches.fetch_add(1, Ordering::SeqCst);
+    Json(json!({ "keys": mock.key().map(|key| vec![key.jwk]).unwrap_or_default() }))
+}
+
+/// A fresh P-256 signing key and its public JWK.
+fn mock_key(kid: &str) -> anyhow::Result<MockKey> {
+    let rng = SystemRandom::new();
+    let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng)?;
+    let key = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng)?;
+    let point = key.public_key().as_ref();
+    Ok(MockKey {
+        kid: kid.to_string(),
+        signing_key: EncodingKey::from_ec_der(pkcs8.as_ref()),
+        jwk: json!({
+            "kty": "EC",
+            "crv": "P-256",
+            "kid": kid,
+            "use": "sig",
+            "alg": "ES256",
+            "x": BASE64URL_NOPAD.encode(point.get(1..33).unwrap_or_default()),
+            "y": BASE64URL_NOPAD.encode(point.get(33..65).unwrap_or_default()),
+        }),
+    })
+}
+
+/// Serve a minimal `OpenID` Connect provider on a random local port.
+async fn start_mock_provider() -> anyhow::Result<MockProvider> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let mock = MockProvider {
+        issuer: format!("http://{}", listener.local_addr()?),
+        key: Arc::new(Mutex::new(mock_key("test-key")?)),
+        nonce: Arc::default(),
+        discovery_fetches: Arc::default(),
+        jwks_fetches: Arc::default(),
+    };
+    let router = Router::new()
+        .route("/.well-known/openid-configuration", get(discovery))
+        .route("/token", post(token))
+        .route("/jwks", get(keys))
+        .with_state(mock.clone());
+    tokio::spawn(async move { axum::serve(listener, router).await });
+
+    Ok(mock)
+}
+
+async fn test_app(issuer: &str) -> Router {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(
+        db,
+        Config {
+            oidc_providers: vec![OidcProviderConfig {
+                name: "keycloak".to_string(),
+                display_name: "Company SSO".to_string(),
+                issuer: issuer.to_string(),
+                client_id: CLIENT_ID.to_string(),
+                client_secret: "client-secret".to_string(),
+                redirect_uri: "http://localhost:3000/api/v1/auth/oidc/keycloak/callback"
+                    .to_string(),
+                scopes: vec!["openid".to_string(), "email".to_string()],
+            }],
+            ..common::test_config()
+        },
+    );
+
+    aircade_api::routes::router(&state.config).with_state(state)
+}
+
+/// Start a sign-in, have the mock provider remember its nonce, and return the `state`
+/// parameter the provider would echo back.
+async fn initiate(app: &Router, mock: &MockProvider) -> String {
+    let (status, headers, _) = common::get_raw(app, "/api/v1/auth/oidc/keycloak", &[]).await;
+    assert_eq!(status, StatusCode::SEE_OTHER);
+    let location = headers
+        .get(header::LOCATION)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    assert!(location.contains("/authorize?response_type=code&client_id=aircade"));
+    assert!(location.contains("scope=openid+email"));
+
+    let param = |name: &str| {
+        location
+            .split(['?', '&'])
+            .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+            .unwrap_or_default()
+            .to_string()
+    };
+    let nonce = param("nonce");
+    assert!(!nonce.is_empty());
+    mock.set_nonce(&nonce);
+    param("state")
+}
+
+#[tokio::test]
+async fn oidc_provider_signin_creates_and_reuses_account() -> anyhow::Result<()> {
+    let mock = start_mock_provider().await?;
+    let app = test_app(&mock.issuer).await;
+
+    let (status, body) = common::get(&app, "/api/v1/auth/oidc").await;
+    assert_eq!(status, StatusCode::OK);
+    let providers: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(
+        providers,
+        json!([{ "name": "keycloak", "displayName": "Company SSO" }])
+    );
+
+    let state = initiate(&app, &mock).await;
+    let (status, body) = common::get(
+        &app,
+        &format!("/api/v1/auth/oidc/keycloak/callback?code={GOOD_CODE}&state={state}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let first: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(first["user"]["email"], "sso.user@example.com");
+    assert_eq!(first["user"]["displayName"], "Sso User");
+    assert_eq!(first["user"]["emailVerified"], true);
+    assert!(first["token"].is_string());
+
+    // Signing in again finds the same account through the stored provider ID
+    let state = initiate(&app, &mock).await;
+    let (status, body) = common::get(
+        &app,
+        &format!("/api/v1/auth/oidc/keycloak/callback?code={GOOD_CODE}&state={state}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let second: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(second["user"]["id"], first["user"]["id"]);
+
+    // The discovery document and keys were fetched once and reused
+    assert_eq!(mock.discovery_fetches.load(Ordering::SeqCst), 1);
+    assert_eq!(mock.jwks_fetches.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn oidc_provider_keys_are_refetched_after_rotation() -> anyhow::Result<()> {
+    let mock = start_mock_provider().await?;
+    let app = test_app(&mock.issuer).await;
+
+    let state = initiate(&app, &mock).await;
+    let (status, body) = common::get(
+        &app,
+        &format!("/api/v1/auth/oidc/keycloak/callback?code={GOOD_CODE}&state={state}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    mock.rotate_key("rotated-key")?;
+    let state = initiate(&app, &mock).await;
+    let (status, body) = common::get(
+        &app,
+        &format!("/api/v1/auth/oidc/keycloak/callback?code={GOOD_CODE}&state={state}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    // The unknown key id made the cached keys stale; the discovery document was still fresh
+    assert_eq!(mock.jwks_fetches.load(Ordering::SeqCst), 2);
+    assert_eq!(mock.discovery_fetches.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn oidc_provider_rejects_bad_requests() -> anyhow::Result<()> {
+    let mock = start_mock_provider().await?;
+    let app = test_app(&mock.issuer).await;
+
+    let (status, _) = common::get(&app, "/api/v1/auth/oidc/unknown").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = common::get(
+        &app,
+        &format!("/api/v1/auth/oidc/keycloak/callback?code={GOOD_CODE}&state=forged"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let state = initiate(&app, &mock).await;
+    let (status, _) = common::get(
+        &app,
+        &format!("/api/v1/auth/oidc/keycloak/callback?code=wrong&state={state}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // An ID token issued for another sign-in carries that sign-in's nonce
+    let state = initiate(&app, &mock).await;
+    mock.set_nonce("another-sign-in");
+    let (status, body) = common::get(
+        &app,
+        &format!("/api/v1/auth/oidc/keycloak/callback?code={GOOD_CODE}&state={state}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body.contains("different sign-in"), "{body}");
+
+    Ok(())
+}
@@ -0,0 +1,212 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use migration::{Migrator, MigratorTrait};
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+fn username(user_id: Uuid) -> String {
+    format!("user_{}", &user_id.to_string()[..8])
+}
+
+/// Create an organization with `slug` owned by the holder of `token`.
+async fn create_organization(app: &Router, token: &str, slug: &str) {
+    let (status, body) = common::post_json_with_auth(
+        app,
+        "/api/v1/organizations",
+        &json!({ "name": "Pixel Studio", "slug": slug }),
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+}
+
+async fn invite(app: &Router, token: &str, slug: &str, user_id: Uuid, role: &str) -> StatusCode {
+    let (status, _) = common::post_json_with_auth(
+        app,
+        &format!("/api/v1/organizations/{slug}/members"),
+        &json!({ "username": username(user_id), "role": role }),
+        token,
+    )
+    .await;
+    status
+}
+
+async fn org_game_count(app: &Router, slug: &str, token: Option<&str>) -> serde_json::Value {
+    let uri = format!("/api/v1/organizations/{slug}/games");
+    let (_, body) = match token {
+        Some(token) => common::get_with_auth(app, &uri, token).await,
+        None => common::get(app, &uri).await,
+    };
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    v["total"].clone()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// /api/v1/organizations
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn members_share_the_organization_catalog() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, owner_token) = common::create_user(&state, "user").await?;
+    let (member_id, member_token) = common::create_user(&state, "user").await?;
+    let (_, outsider_token) = common::create_user(&state, "user").await?;
+    create_organization(&app, &owner_token, "pixel-studio").await;
+
+    let (_, body) = common::get(&app, "/api/v1/organizations/pixel-studio").await;
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    let organization_id = v["id"].as_str().unwrap_or_default().to_string();
+    assert_eq!(v["memberCount"], 1);
+
+    // Pending invites grant no access
+    assert_eq!(
+        invite(&app, &owner_token, "pixel-studio", member_id, "editor").await,
+        StatusCode::CREATED
+    );
+    let (status, _) = common::get_with_auth(
+        &app,
+        "/api/v1/organizations/pixel-studio/members",
+        &member_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/games",
+        &json!({ "title": "Team Game", "organizationId": organization_id }),
+        &member_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/organizations/pixel-studio/invite/accept",
+        &json!({}),
+        &member_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/games",
+        &json!({ "title": "Team Game", "organizationId": organization_id }),
+        &member_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["ownerType"], "organization");
+    let game_id = v["id"].as_str().unwrap_or_default().to_string();
+
+    // Another member can edit it; an outsider cannot see the draft
+    let (status, body) = common::patch_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}"),
+        &json!({ "title": "Team Game 2" }),
+        &owner_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let (status, _) =
+        common::get_with_auth(&app, &format!("/api/v1/games/{game_id}"), &outsider_token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    assert_eq!(
+        org_game_count(&app, "pixel-studio", Some(&owner_token)).await,
+        1
+    );
+    assert_eq!(
+        org_game_count(&app, "pixel-studio", Some(&outsider_token)).await,
+        0
+    );
+    assert_eq!(org_game_count(&app, "pixel-studio", None).await, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn only_owners_and_admins_invite() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, owner_token) = common::create_user(&state, "user").await?;
+    let (editor_id, editor_token) = common::create_user(&state, "user").await?;
+    let (invitee_id, invitee_token) = common::create_user(&state, "user").await?;
+    create_organization(&app, &owner_token, "pixel-studio").await;
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/organizations",
+        &json!({ "name": "Copycat", "slug": "pixel-studio" }),
+        &editor_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    assert_eq!(
+        invite(&app, &owner_token, "pixel-studio", editor_id, "owner").await,
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        invite(&app, &owner_token, "pixel-studio", editor_id, "editor").await,
+        StatusCode::CREATED
+    );
+    assert_eq!(
+        invite(&app, &owner_token, "pixel-studio", editor_id, "editor").await,
+        StatusCode::CONFLICT
+    );
+    common::post_json_with_auth(
+        &app,
+        "/api/v1/organizations/pixel-studio/invite/accept",
+        &json!({}),
+        &editor_token,
+    )
+    .await;
+    assert_eq!(
+        invite(&app, &editor_token, "pixel-studio", invitee_id, "editor").await,
+        StatusCode::FORBIDDEN
+    );
+
+    // Declining removes the invite
+    assert_eq!(
+        invite(&app, &owner_token, "pixel-studio", invitee_id, "admin").await,
+        StatusCode::CREATED
+    );
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/organizations/pixel-studio/invite/decline",
+        &json!({}),
+        &invitee_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, body) = common::get_with_auth(
+        &app,
+        "/api/v1/organizations/pixel-studio/members",
+        &owner_token,
+    )
+    .await;
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["data"].as_array().map(Vec::len), Some(2));
+    Ok(())
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use axum::Router;
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::routing::get;
+use migration::{Migrator, MigratorTrait};
+use serde_json::json;
+use sha1::{Digest, Sha1};
+
+use aircade_api::config::Config;
+
+/// A password the mock range API reports as breached.
+const BREACHED_PASSWORD: &str = "Password123";
+
+/// Serve a mock Pwned Passwords range API on a random local port and return its base URL.
+async fn start_mock_range_api() -> anyhow::Result<String> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}", listener.local_addr()?);
+
+    let hash = hex::encode_upper(Sha1::digest(BREACHED_PASSWORD.as_bytes()));
+    let (breached_prefix, breached_suffix) = hash.split_at(5);
+    let (breached_prefix, breached_suffix) =
+        (breached_prefix.to_string(), breached_suffix.to_string());
+    let router = Router::new().route(
+        "/range/{prefix}",
+        get(move |Path(prefix): Path<String>| async move {
+            // One padding entry (count 0) and, for the breached prefix, the real match
+            let padding = "0000000000000000000000000000000000A:0\r\n";
+            if prefix == breached_prefix {
+                format!("{padding}{breached_suffix}:42\r\n")
+            } else {
+                padding.to_string()
+            }
+        }),
+    );
+    tokio::spawn(async move { axum::serve(listener, router).await });
+
+    Ok(url)
+}
+
+async fn test_app(hibp_api_url: &str) -> Router {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(
+        db,
+        Config {
+            hibp_enabled: true,
+            hibp_api_url: hibp_api_url.to_string(),
+            ..common::test_config()
+        },
+    );
+
+    aircade_api::routes::router(&state.config).with_state(state)
+}
+
+async fn signup(app: &Router, email: &str, username: &str, password: &str) -> (StatusCode, String) {
+    common::post_json(
+        app,
+        "/api/v1/auth/signup/email",
+        &json!({ "email": email, "username": username, "password": password }),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn signup_rejects_breached_password() -> anyhow::Result<()> {
+    let app = test_app(&start_mock_range_api().await?).await;
+
+    let (status, body) = signup(&app, "pwned@example.com", "pwneduser", BREACHED_PASSWORD).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(json["error"]["code"], "PASSWORD_BREACHED");
+
+    let (status, body) = signup(
+        &app,
+        "safe@example.com",
+        "safeuser",
+        "correct horse battery",
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "signup failed: {body}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn password_change_rejects_breached_password() -> anyhow::Result<()> {
+    let app = test_app(&start_mock_range_api().await?).await;
+    let (status, body) = signup(
+        &app,
+        "change@example.com",
+        "changeuser",
+        "correct horse battery",
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    let token = json["token"].as_str().unwrap_or_default();
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/auth/password/change",
+        &json!({ "currentPassword": "correct horse battery", "newPassword": BREACHED_PASSWORD }),
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn unreachable_range_api_does_not_block_signup() {
+    // Nothing listens on port 9 (discard) locally
+    let app = test_app("http://127.0.0.1:9").await;
+
+    let (status, body) = signup(
+        &app,
+        "offline@example.com",
+        "offlineuser",
+        BREACHED_PASSWORD,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "signup failed: {body}");
+}
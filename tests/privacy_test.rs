@@ -0,0 +1,193 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveModelTrait;
+use sea_orm::ActiveValue::Set;
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::{game, game_version};
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a published public game with one version owned by `owner_id` and return its ID.
+async fn create_published_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Privacy Game".to_string()),
+        slug: Set(format!("privacy-game-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("published".to_string()),
+        visibility: Set("public".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    game_version::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        created_at: Set(now),
+        game_id: Set(id),
+        version_number: Set(1),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+/// Set `token`'s privacy flags.
+async fn set_privacy(app: &Router, token: &str, flags: &serde_json::Value) -> serde_json::Value {
+    let (status, body) =
+        common::patch_json_with_auth(app, "/api/v1/users/me/privacy", flags, token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    serde_json::from_str(&body).unwrap_or_default()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET/PATCH /api/v1/users/me/privacy
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn privacy_settings_default_off_and_update_partially() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, token) = common::create_user(&state, "user").await?;
+
+    let (status, body) = common::get_with_auth(&app, "/api/v1/users/me/privacy", &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(
+        v,
+        json!({ "hideFromSearch": false, "hideGames": false, "hideActivity": false })
+    );
+
+    set_privacy(&app, &token, &json!({ "hideGames": true })).await;
+    let v = set_privacy(&app, &token, &json!({ "hideActivity": true })).await;
+    assert_eq!(
+        v,
+        json!({ "hideFromSearch": false, "hideGames": true, "hideActivity": true })
+    );
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Enforcement
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn hidden_games_are_only_visible_to_their_owner() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner_id, owner_token) = common::create_user(&state, "user").await?;
+    let (_, other_token) = common::create_user(&state, "user").await?;
+    create_published_game(&state, owner_id).await?;
+    let username = format!("user_{}", &owner_id.to_string()[..8]);
+
+    set_privacy(&app, &owner_token, &json!({ "hideGames": true })).await;
+
+    let games_uri = format!("/api/v1/users/{username}/games");
+    let (status, _) = common::get(&app, &games_uri).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = common::get_with_auth(&app, &games_uri, &other_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, body) = common::get_with_auth(&app, &games_uri, &owner_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["total"], 1);
+
+    let profile_uri = format!("/api/v1/users/{username}");
+    let (status, body) = common::get(&app, &profile_uri).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert!(v["stats"].is_null());
+    let (_, body) = common::get_with_auth(&app, &profile_uri, &owner_token).await;
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["stats"]["gamesPublished"], 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn hidden_from_search_excludes_games_and_marks_profile_noindex() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner_id, owner_token) = common::create_user(&state, "user").await?;
+    create_published_game(&state, owner_id).await?;
+    let username = format!("user_{}", &owner_id.to_string()[..8]);
+    let search_uri = "/api/v1/search/games?q=privacy";
+
+    let (status, body) = common::get(&app, search_uri).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["total"], 1);
+
+    set_privacy(&app, &owner_token, &json!({ "hideFromSearch": true })).await;
+
+    let (_, body) = common::get(&app, search_uri).await;
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["total"], 0);
+
+    let (status, headers, _) =
+        common::get_raw(&app, &format!("/api/v1/users/{username}"), &[]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        headers.get("x-robots-tag").and_then(|h| h.to_str().ok()),
+        Some("noindex")
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn hidden_activity_is_left_out_of_followers_feeds() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (creator_id, creator_token) = common::create_user(&state, "user").await?;
+    let (_, follower_token) = common::create_user(&state, "user").await?;
+    create_published_game(&state, creator_id).await?;
+    let username = format!("user_{}", &creator_id.to_string()[..8]);
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/users/{username}/follow"),
+        &json!({}),
+        &follower_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT, "{body}");
+
+    let (_, body) = common::get_with_auth(&app, "/api/v1/users/me/feed", &follower_token).await;
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["total"], 1);
+
+    set_privacy(&app, &creator_token, &json!({ "hideActivity": true })).await;
+
+    let (status, body) =
+        common::get_with_auth(&app, "/api/v1/users/me/feed", &follower_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["total"], 0);
+    Ok(())
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveModelTrait;
+use sea_orm::ActiveValue::Set;
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::game;
+use aircade_api::state::AppState;
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a published public game and return its ID.
+async fn create_published_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Stats Game".to_string()),
+        slug: Set(format!("stats-game-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("published".to_string()),
+        visibility: Set("public".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+#[tokio::test]
+async fn report_requires_a_valid_existing_target() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner, token) = common::create_user(&state, "user").await?;
+    let game_id = create_published_game(&state, owner).await?;
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/reports",
+        &json!({
+            "targetType": "game",
+            "targetId": game_id,
+            "reason": "spam",
+            "details": "  Links to a scam site  ",
+        }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let report: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(report["status"], "open");
+    assert_eq!(report["details"], "Links to a scam site");
+
+    // One open report per reporter and target
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/reports",
+        &json!({ "targetType": "game", "targetId": game_id, "reason": "other" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/reports",
+        &json!({ "targetType": "user", "targetId": owner, "reason": "rude" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/reports",
+        &json!({ "targetType": "review", "targetId": Uuid::new_v4(), "reason": "spam" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[tokio::test]
+async fn anonymous_reports_are_rate_limited() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner, _token) = common::create_user(&state, "user").await?;
+    let game_id = create_published_game(&state, owner).await?;
+
+    let report = json!({ "targetType": "game", "targetId": game_id, "reason": "spam" });
+    for _ in 0..5 {
+        let (status, body) = common::post_json(&app, "/api/v1/reports", &report).await;
+        assert_eq!(status, StatusCode::CREATED, "{body}");
+    }
+
+    let (status, body) = common::post_json(&app, "/api/v1/reports", &report).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(body.contains("RATE_LIMITED"));
+    Ok(())
+}
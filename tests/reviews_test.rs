@@ -0,0 +1,283 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveModelTrait;
+use sea_orm::ActiveValue::Set;
+use sea_orm::EntityTrait;
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::game;
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a published public game owned by `owner_id` and return its ID.
+async fn create_published_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Reviewed".to_string()),
+        slug: Set(format!("reviewed-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("published".to_string()),
+        visibility: Set("public".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+/// Creator + reviewer + published game + one review. Returns
+/// (`app`, `state`, `creator_token`, `reviewer_token`, `game_id`, `review_id`).
+async fn setup_review() -> anyhow::Result<(Router, AppState, String, String, Uuid, String)> {
+    let (app, state) = test_app().await;
+    let (creator_id, creator_token) = common::create_user(&state, "user").await?;
+    let (_, reviewer_token) = common::create_user(&state, "user").await?;
+    let game_id = create_published_game(&state, creator_id).await?;
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/reviews"),
+        &json!({ "rating": 4, "body": "Great party game!" }),
+        &reviewer_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    let review_id = v["id"].as_str().unwrap_or_default().to_string();
+
+    Ok((
+        app,
+        state,
+        creator_token,
+        reviewer_token,
+        game_id,
+        review_id,
+    ))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Reviews
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn create_review_updates_game_rating() -> anyhow::Result<()> {
+    let (_app, state, _, _, game_id, _) = setup_review().await?;
+
+    let game = game::Entity::find_by_id(game_id).one(&state.db).await?;
+    let game = game.ok_or_else(|| anyhow::anyhow!("game missing"))?;
+    assert_eq!(game.review_count, 1);
+    assert!((game.avg_rating - 4.0).abs() < f32::EPSILON);
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_review_twice_conflicts() -> anyhow::Result<()> {
+    let (app, _state, _, reviewer_token, game_id, _) = setup_review().await?;
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/reviews"),
+        &json!({ "rating": 5 }),
+        &reviewer_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_review_rejects_own_game_and_bad_rating() -> anyhow::Result<()> {
+    let (app, _state, creator_token, reviewer_token, game_id, _) = setup_review().await?;
+    let uri = format!("/api/v1/games/{game_id}/reviews");
+
+    let (status, _) =
+        common::post_json_with_auth(&app, &uri, &json!({ "rating": 5 }), &creator_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) =
+        common::post_json_with_auth(&app, &uri, &json!({ "rating": 6 }), &reviewer_token).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Creator Replies
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn creator_reply_lifecycle() -> anyhow::Result<()> {
+    let (app, _state, creator_token, _, game_id, review_id) = setup_review().await?;
+    let reply_uri = format!("/api/v1/games/{game_id}/reviews/{review_id}/reply");
+    let list_uri = format!("/api/v1/games/{game_id}/reviews");
+
+    // Post
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &reply_uri,
+        &json!({ "body": "Thanks for playing!" }),
+        &creator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+
+    // A second reply is rejected
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &reply_uri,
+        &json!({ "body": "Again" }),
+        &creator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Returned in listing
+    let (status, body) = common::get(&app, &list_uri).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["total"], 1);
+    assert_eq!(v["data"][0]["creatorReply"]["body"], "Thanks for playing!");
+
+    // Edit
+    let (status, body) = common::patch_json_with_auth(
+        &app,
+        &reply_uri,
+        &json!({ "body": "Update coming soon." }),
+        &creator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["creatorReply"]["body"], "Update coming soon.");
+
+    // Delete
+    let (status, _) = common::delete_with_auth(&app, &reply_uri, &creator_token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, body) = common::get(&app, &list_uri).await;
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert!(v["data"][0]["creatorReply"].is_null());
+    Ok(())
+}
+
+#[tokio::test]
+async fn reply_forbidden_for_non_creator() -> anyhow::Result<()> {
+    let (app, _state, _, reviewer_token, game_id, review_id) = setup_review().await?;
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/reviews/{review_id}/reply"),
+        &json!({ "body": "Replying to myself" }),
+        &reviewer_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    Ok(())
+}
+
+#[tokio::test]
+async fn edit_missing_reply_is_404() -> anyhow::Result<()> {
+    let (app, _state, creator_token, _, game_id, review_id) = setup_review().await?;
+
+    let (status, _) = common::patch_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/reviews/{review_id}/reply"),
+        &json!({ "body": "Nothing to edit" }),
+        &creator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Creator profile stats
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn creator_profile_stats_aggregate_published_games() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (creator_id, _) = common::create_user(&state, "user").await?;
+    let (_, first_token) = common::create_user(&state, "user").await?;
+    let (_, second_token) = common::create_user(&state, "user").await?;
+    let first_game = create_published_game(&state, creator_id).await?;
+    let second_game = create_published_game(&state, creator_id).await?;
+    let draft = create_published_game(&state, creator_id).await?;
+
+    for (game_id, play_count, status) in [
+        (first_game, 3, "published"),
+        (second_game, 4, "published"),
+        (draft, 100, "draft"),
+    ] {
+        game::ActiveModel {
+            id: Set(game_id),
+            play_count: Set(play_count),
+            status: Set(status.to_string()),
+            ..Default::default()
+        }
+        .update(&state.db)
+        .await?;
+    }
+
+    for (game_id, token, rating) in [
+        (first_game, &first_token, 4),
+        (first_game, &second_token, 5),
+        (second_game, &first_token, 1),
+    ] {
+        let (status, body) = common::post_json_with_auth(
+            &app,
+            &format!("/api/v1/games/{game_id}/reviews"),
+            &json!({ "rating": rating }),
+            token,
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED, "{body}");
+    }
+
+    let username = format!("user_{}", &creator_id.to_string()[..8]);
+    common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/users/{username}/follow"),
+        &json!({}),
+        &first_token,
+    )
+    .await;
+
+    let (status, body) = common::get(&app, &format!("/api/v1/users/{username}")).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["stats"]["gamesPublished"], 2);
+    assert_eq!(v["stats"]["totalPlayCount"], 7);
+    assert_eq!(v["stats"]["followers"], 1);
+    // The mean of all three ratings, not of the two games' averages
+    let avg = v["stats"]["avgRating"].as_f64().unwrap_or_default();
+    assert!((avg - 10.0 / 3.0).abs() < 1e-4, "{avg}");
+    Ok(())
+}
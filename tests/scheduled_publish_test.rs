@@ -0,0 +1,152 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, EntityTrait};
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::{game, game_version};
+use aircade_api::jobs::scheduled_publish;
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a private draft game owned by `owner_id` and return its ID.
+async fn create_private_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Team Project".to_string()),
+        slug: Set(format!("team-project-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("draft".to_string()),
+        visibility: Set("private".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        game_screen_code: Set(Some(
+            "function setup() { createCanvas(400, 400); }".to_string(),
+        )),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Scheduled Publishing
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn scheduled_publish_runs_when_due() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner_id, token) = common::create_user(&state, "user").await?;
+    let game_id = create_private_game(&state, owner_id).await?;
+    let publish_at = Utc::now() + Duration::hours(1);
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/publish"),
+        &json!({ "changelog": "Launch day", "publishAt": publish_at.to_rfc3339() }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED, "{body}");
+
+    // Nothing happens before the scheduled time
+    assert_eq!(
+        scheduled_publish::run_due(
+            &state.db,
+            &state.config,
+            &state.notification_hub,
+            Utc::now()
+        )
+        .await?,
+        0
+    );
+    let found = game::Entity::find_by_id(game_id).one(&state.db).await?;
+    assert_eq!(found.map(|g| g.status).as_deref(), Some("draft"));
+
+    let later = publish_at + Duration::minutes(1);
+    assert_eq!(
+        scheduled_publish::run_due(&state.db, &state.config, &state.notification_hub, later)
+            .await?,
+        1
+    );
+    let found = game::Entity::find_by_id(game_id).one(&state.db).await?;
+    let version_id = found.as_ref().and_then(|g| g.published_version_id);
+    assert_eq!(found.map(|g| g.status).as_deref(), Some("published"));
+
+    let version = game_version::Entity::find_by_id(version_id.unwrap_or_default())
+        .one(&state.db)
+        .await?;
+    assert_eq!(
+        version.and_then(|v| v.changelog).as_deref(),
+        Some("Launch day")
+    );
+
+    // The schedule is consumed
+    assert_eq!(
+        scheduled_publish::run_due(&state.db, &state.config, &state.notification_hub, later)
+            .await?,
+        0
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn cancel_scheduled_publish() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner_id, token) = common::create_user(&state, "user").await?;
+    let game_id = create_private_game(&state, owner_id).await?;
+    let uri = format!("/api/v1/games/{game_id}/publish");
+
+    let past = Utc::now() - Duration::hours(1);
+    let (status, _) =
+        common::post_json_with_auth(&app, &uri, &json!({ "publishAt": past }), &token).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let publish_at = Utc::now() + Duration::hours(1);
+    let (status, _) =
+        common::post_json_with_auth(&app, &uri, &json!({ "publishAt": publish_at }), &token).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+
+    let (status, _) = common::delete_with_auth(&app, &uri, &token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = common::delete_with_auth(&app, &uri, &token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let later = publish_at + Duration::minutes(1);
+    assert_eq!(
+        scheduled_publish::run_due(&state.db, &state.config, &state.notification_hub, later)
+            .await?,
+        0
+    );
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{EntityTrait, PaginatorTrait};
+use serde_json::json;
+
+use aircade_api::config::Environment;
+use aircade_api::entities::{game, session, user};
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// /api/v1/admin/seed
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn seed_creates_requested_volumes() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, user_token) = common::create_user(&state, "user").await?;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+    let request = json!({
+        "users": 6,
+        "games": 4,
+        "reviewsPerGame": 3,
+        "sessionsPerGame": 2,
+        "password": "seeded-password",
+    });
+
+    let (_, body) = common::get(&app, "/api/v1/games").await;
+    let before: serde_json::Value = serde_json::from_str(&body)?;
+
+    let (status, _) =
+        common::post_json_with_auth(&app, "/api/v1/admin/seed", &request, &user_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) =
+        common::post_json_with_auth(&app, "/api/v1/admin/seed", &request, &admin_token).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let created: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(created["users"], 6);
+    assert_eq!(created["games"], 4);
+    assert_eq!(created["reviews"], 12);
+    assert_eq!(created["sessions"], 8);
+
+    // Seeded games are published, rated and have a play history
+    let (status, body) = common::get(&app, "/api/v1/games").await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let listed: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(
+        listed["total"].as_u64().unwrap_or_default(),
+        before["total"].as_u64().unwrap_or_default() + 4
+    );
+    let seeded: Vec<user::Model> = user::Entity::find()
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .filter(|u| u.email.ends_with("@example.com"))
+        .collect();
+    assert_eq!(seeded.len(), 6);
+    let seeded_games = game::Entity::find()
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .filter(|g| seeded.iter().any(|u| u.id == g.owner_id));
+    for game in seeded_games {
+        assert!(game.published_version_id.is_some());
+        assert!(game.game_screen_code.is_some());
+        assert_eq!(game.review_count, 3);
+        assert_eq!(game.play_count, 2);
+    }
+    let ended = session::Entity::find().count(&state.db).await?;
+    assert_eq!(ended, 8);
+
+    // Seeded accounts can sign in with the given password
+    let (status, body) = common::post_json(
+        &app,
+        "/api/v1/auth/signin/email",
+        &json!({ "email": seeded[0].email, "password": "seeded-password" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn seed_is_refused_in_production_and_out_of_range() -> anyhow::Result<()> {
+    let (app, mut state) = test_app().await;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/admin/seed",
+        &json!({ "users": 0 }),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/admin/seed",
+        &json!({ "games": 100_000 }),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    state.config.environment = Environment::Production;
+    let production = aircade_api::routes::router(&state.config).with_state(state);
+    let (status, body) =
+        common::post_json_with_auth(&production, "/api/v1/admin/seed", &json!({}), &admin_token)
+            .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+    Ok(())
+}
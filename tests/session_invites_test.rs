@@ -0,0 +1,224 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use migration::{Migrator, MigratorTrait};
+use serde_json::json;
+
+use aircade_api::state::AppState;
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Sign up a user and return (`access_token`, `refresh_token`).
+async fn signup_user(
+    app: &Router,
+    email: &str,
+    username: &str,
+    password: &str,
+) -> (String, String) {
+    let (status, body) = common::post_json(
+        app,
+        "/api/v1/auth/signup/email",
+        &json!({
+            "email": email,
+            "username": username,
+            "password": password,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "signup failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    (
+        json["token"].as_str().unwrap_or_default().to_string(),
+        json["refreshToken"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+    )
+}
+
+/// Create a session and return the session response JSON.
+async fn create_session(app: &Router, token: &str) -> serde_json::Value {
+    let (status, body) =
+        common::post_json_with_auth(app, "/api/v1/sessions", &json!({ "maxPlayers": 4 }), token)
+            .await;
+    assert_eq!(status, StatusCode::CREATED, "create session failed: {body}");
+    serde_json::from_str(&body).unwrap_or_default()
+}
+
+/// Make `friend` and `host` (whose username is `host_name`) friends.
+async fn befriend(app: &Router, host: &str, host_name: &str, friend: &str) {
+    let (status, _) = common::post_json_with_auth(
+        app,
+        &format!("/api/v1/users/{host_name}/friend-request"),
+        &json!({}),
+        friend,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (_, body) = common::get_with_auth(app, "/api/v1/users/me/friend-requests", host).await;
+    let requests: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let id = requests["incoming"][0]["id"].as_str().unwrap_or_default();
+    let (status, _) = common::post_json_with_auth(
+        app,
+        &format!("/api/v1/users/me/friend-requests/{id}/accept"),
+        &json!({}),
+        host,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
+
+async fn user_id(app: &Router, username: &str) -> String {
+    let (_, body) = common::get(app, &format!("/api/v1/users/{username}")).await;
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    v["id"].as_str().unwrap_or_default().to_string()
+}
+
+/// The session invites among the user's notifications.
+async fn invite_notifications(app: &Router, token: &str) -> Vec<serde_json::Value> {
+    let (_, body) = common::get_with_auth(app, "/api/v1/users/me/notifications", token).await;
+    let all: Vec<serde_json::Value> = serde_json::from_str(&body).unwrap_or_default();
+    all.into_iter()
+        .filter(|n| n["kind"] == "session_invite")
+        .collect()
+}
+
+// ──────────────────────────────────────────────────────────────────────────────
+// POST /api/v1/sessions/{sessionId}/invites
+// ──────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn host_invites_friends_and_followers() {
+    let (app, _state) = test_app().await;
+    let (host, _) = signup_user(&app, "host@example.com", "host", "Password123").await;
+    let (friend, _) = signup_user(&app, "friend@example.com", "friend", "Password123").await;
+    let (fan, _) = signup_user(&app, "fan@example.com", "fan", "Password123").await;
+    befriend(&app, &host, "host", &friend).await;
+    common::post_json_with_auth(&app, "/api/v1/users/host/follow", &json!({}), &fan).await;
+
+    let session = create_session(&app, &host).await;
+    let id = session["id"].as_str().unwrap_or_default();
+    let code = session["sessionCode"].as_str().unwrap_or_default();
+    let invitee_ids = [user_id(&app, "friend").await, user_id(&app, "fan").await];
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/sessions/{id}/invites"),
+        &json!({ "userIds": invitee_ids, "email": true }),
+        &host,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let invites: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(invites.as_array().map(Vec::len), Some(2));
+    assert!(invites.as_array().is_some_and(|all| {
+        all.iter()
+            .all(|i| i["status"] == "pending" && i["emailed"] == true)
+    }));
+
+    let notices = invite_notifications(&app, &friend).await;
+    assert_eq!(notices.len(), 1);
+    assert!(
+        notices[0]["body"]
+            .as_str()
+            .unwrap_or_default()
+            .contains(code)
+    );
+
+    // Inviting again is a no-op
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/sessions/{id}/invites"),
+        &json!({ "userIds": [invitee_ids[0]] }),
+        &host,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    assert_eq!(invite_notifications(&app, &friend).await.len(), 1);
+}
+
+#[tokio::test]
+async fn invites_are_limited_to_host_friends_and_followers() {
+    let (app, _state) = test_app().await;
+    let (host, _) = signup_user(&app, "host@example.com", "host", "Password123").await;
+    let (stranger, _) = signup_user(&app, "stranger@example.com", "stranger", "Password123").await;
+
+    let session = create_session(&app, &host).await;
+    let id = session["id"].as_str().unwrap_or_default();
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/sessions/{id}/invites"),
+        &json!({ "userIds": [user_id(&app, "stranger").await] }),
+        &host,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Only the host manages invites
+    let (status, _) =
+        common::get_with_auth(&app, &format!("/api/v1/sessions/{id}/invites"), &stranger).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn invite_status_follows_join_and_decline() {
+    let (app, _state) = test_app().await;
+    let (host, _) = signup_user(&app, "host@example.com", "host", "Password123").await;
+    let (joiner, _) = signup_user(&app, "joiner@example.com", "joiner", "Password123").await;
+    let (decliner, _) = signup_user(&app, "decliner@example.com", "decliner", "Password123").await;
+    befriend(&app, &host, "host", &joiner).await;
+    befriend(&app, &host, "host", &decliner).await;
+
+    let session = create_session(&app, &host).await;
+    let id = session["id"].as_str().unwrap_or_default();
+    let code = session["sessionCode"].as_str().unwrap_or_default();
+    common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/sessions/{id}/invites"),
+        &json!({ "userIds": [user_id(&app, "joiner").await, user_id(&app, "decliner").await] }),
+        &host,
+    )
+    .await;
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/sessions/{code}/join"),
+        &json!({ "displayName": "Joiner" }),
+        &joiner,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/sessions/{id}/invites/decline"),
+        &json!({}),
+        &decliner,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, body) =
+        common::get_with_auth(&app, &format!("/api/v1/sessions/{id}/invites"), &host).await;
+    let invites: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let status_of = |username: &str| {
+        invites
+            .as_array()
+            .and_then(|all| all.iter().find(|i| i["user"]["username"] == username))
+            .map(|i| i["status"].clone())
+    };
+    assert_eq!(status_of("joiner"), Some(json!("accepted")));
+    assert_eq!(status_of("decliner"), Some(json!("declined")));
+}
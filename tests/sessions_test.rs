@@ -1,13 +1,23 @@
 mod common;
 
+use std::io::Read;
+use std::time::Duration;
+
 use axum::Router;
-use axum::http::StatusCode;
+use axum::http::{HeaderValue, StatusCode};
+use futures_util::StreamExt;
 use migration::{Migrator, MigratorTrait};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 use serde_json::json;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 use uuid::Uuid;
 
-use aircade_api::config::{Config, Environment};
-use aircade_api::sessions::{ClientRole, SessionManager};
+use aircade_api::config::Config;
+use aircade_api::entities::{game, game_play, player, session};
+use aircade_api::geo::GeoDatabase;
+use aircade_api::middleware::rate_limit::{Quota, RateLimitPolicy, RateLimits};
+use aircade_api::sessions::{ClientRole, SessionManager, SessionTelemetry, StateSnapshot};
 use aircade_api::state::AppState;
 
 async fn test_app() -> (Router, AppState) {
@@ -16,30 +26,9 @@ async fn test_app() -> (Router, AppState) {
         .unwrap_or_default();
     Migrator::up(&db, None).await.unwrap_or_default();
 
-    let state = AppState {
-        db,
-        config: Config {
-            database_url: String::new(),
-            server_host: std::net::IpAddr::from([127, 0, 0, 1]),
-            server_port: 0,
-            environment: Environment::Development,
-            log_level: "warn".to_string(),
-            jwt_secret: "test-secret-key-for-testing-only-32chars".to_string(),
-            jwt_access_expiration_secs: 900,
-            jwt_refresh_expiration_secs: 604_800,
-            google_client_id: String::new(),
-            google_client_secret: String::new(),
-            google_redirect_uri: String::new(),
-            github_client_id: String::new(),
-            github_client_secret: String::new(),
-            github_redirect_uri: String::new(),
-            frontend_url: "http://localhost:3001".to_string(),
-            upload_dir: "test_uploads".to_string(),
-        },
-        session_manager: SessionManager::new(),
-    };
+    let state = common::test_state(db, common::test_config());
 
-    let router = aircade_api::routes::router().with_state(state.clone());
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
     (router, state)
 }
 
@@ -224,6 +213,39 @@ async fn join_session_success() {
     assert_eq!(join_resp["session"]["sessionCode"], code);
 }
 
+#[tokio::test]
+async fn join_session_records_the_players_country() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (token, _refresh) =
+        signup_user(&app, "host-geo@example.com", "hostgeo", "Password123").await;
+    aircade_api::geo::install(
+        GeoDatabase::parse("192.0.2.0,192.0.2.255,NZ").map_err(anyhow::Error::msg)?,
+    );
+
+    let session_json = create_session(&app, &token).await;
+    let code = session_json["sessionCode"].as_str().unwrap_or_default();
+    for (name, ip) in [("Kiwi", "192.0.2.10"), ("Elsewhere", "198.51.100.1")] {
+        let (status, _headers, body) = common::post_json_raw(
+            &app,
+            &format!("/api/v1/sessions/{code}/join"),
+            &json!({ "displayName": name }),
+            &[("x-forwarded-for", ip)],
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED, "join failed: {body}");
+    }
+
+    let countries: Vec<(String, Option<String>)> = player::Entity::find()
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|p| (p.display_name, p.country))
+        .collect();
+    assert!(countries.contains(&("Kiwi".to_string(), Some("NZ".to_string()))));
+    assert!(countries.contains(&("Elsewhere".to_string(), None)));
+    Ok(())
+}
+
 #[tokio::test]
 async fn join_session_not_found() {
     let (app, _state) = test_app().await;
@@ -236,6 +258,101 @@ async fn join_session_not_found() {
     assert_eq!(status, StatusCode::NOT_FOUND);
 }
 
+#[tokio::test]
+async fn join_session_rate_limited_per_ip() {
+    let (app, _state) = test_app().await;
+    let body = json!({ "displayName": "Guesser" });
+
+    for _ in 0..30 {
+        let (status, _headers, _body) = common::post_json_raw(
+            &app,
+            "/api/v1/sessions/ZZZZZ/join",
+            &body,
+            &[("x-forwarded-for", "203.0.113.7")],
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    let (status, headers, _body) = common::post_json_raw(
+        &app,
+        "/api/v1/sessions/ZZZZZ/join",
+        &body,
+        &[("x-forwarded-for", "203.0.113.7")],
+    )
+    .await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(headers.contains_key("retry-after"));
+
+    let (status, _headers, _body) = common::post_json_raw(
+        &app,
+        "/api/v1/sessions/ZZZZZ/join",
+        &body,
+        &[("x-forwarded-for", "198.51.100.4")],
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn join_session_rate_limited_per_account() -> anyhow::Result<()> {
+    let db = sea_orm::Database::connect("sqlite::memory:").await?;
+    Migrator::up(&db, None).await?;
+    let config = Config {
+        rate_limits: RateLimits {
+            join: RateLimitPolicy {
+                per_ip: None,
+                per_account: Some(Quota {
+                    burst: 2,
+                    refill: Duration::from_hours(1),
+                }),
+            },
+            ..RateLimits::default()
+        },
+        ..common::test_config()
+    };
+    let state = common::test_state(db, config);
+    let app = aircade_api::routes::router(&state.config).with_state(state.clone());
+    let (_, token) = common::create_user(&state, "user").await?;
+    let bearer = format!("Bearer {token}");
+    let body = json!({ "displayName": "Hopper" });
+
+    // Each attempt comes from a different address, so only the account bucket drains.
+    for ip in ["203.0.113.1", "203.0.113.2"] {
+        let (status, _headers, _body) = common::post_json_raw(
+            &app,
+            "/api/v1/sessions/ZZZZZ/join",
+            &body,
+            &[("x-forwarded-for", ip), ("authorization", &bearer)],
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    let (status, headers, _body) = common::post_json_raw(
+        &app,
+        "/api/v1/sessions/ZZZZZ/join",
+        &body,
+        &[
+            ("x-forwarded-for", "203.0.113.3"),
+            ("authorization", &bearer),
+        ],
+    )
+    .await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(headers.contains_key("retry-after"));
+
+    let (status, _headers, _body) = common::post_json_raw(
+        &app,
+        "/api/v1/sessions/ZZZZZ/join",
+        &body,
+        &[("x-forwarded-for", "203.0.113.3")],
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    Ok(())
+}
+
 #[tokio::test]
 async fn join_session_full() {
     let (app, _state) = test_app().await;
@@ -642,3 +759,324 @@ async fn full_session_flow() {
     let ended_session: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
     assert_eq!(ended_session["status"], "ended");
 }
+
+// ──────────────────────────────────────────────────────────────────────────────
+// Play count / play time accrual
+// ──────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn load_and_end_accrue_play_stats_once() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (token, _refresh) =
+        signup_user(&app, "accrue@example.com", "accrueuser", "Password123").await;
+
+    let session_json = create_session(&app, &token).await;
+    let session_id = session_json["id"].as_str().unwrap_or_default();
+    let session_uuid = Uuid::parse_str(session_id).unwrap_or_default();
+    simulate_ws_connections(&state.session_manager, session_uuid, Some(Uuid::new_v4()));
+
+    let pong_uuid = Uuid::parse_str("00000000-0000-0000-0000-000000000010")?;
+    let before = game::Entity::find_by_id(pong_uuid)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("seeded game missing"))?;
+
+    // Loading the same game twice only counts one play
+    for _ in 0..2 {
+        let (status, body) = common::post_json_with_auth(
+            &app,
+            &format!("/api/v1/sessions/{session_id}/game"),
+            &json!({ "gameId": pong_uuid }),
+            &token,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "load game failed: {body}");
+    }
+
+    // Pretend the game has been running for 90 seconds
+    let started = (chrono::Utc::now() - chrono::Duration::seconds(90)).fixed_offset();
+    session::Entity::update_many()
+        .col_expr(
+            session::Column::PlayingStartedAt,
+            sea_orm::sea_query::Expr::value(started),
+        )
+        .filter(session::Column::Id.eq(session_uuid))
+        .exec(&state.db)
+        .await?;
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/sessions/{session_id}/end"),
+        &json!({}),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // Ending again is rejected and does not accrue a second time
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/sessions/{session_id}/end"),
+        &json!({}),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let after = game::Entity::find_by_id(pong_uuid)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("seeded game missing"))?;
+    assert_eq!(after.play_count, before.play_count + 1);
+    let accrued = after.total_play_time - before.total_play_time;
+    assert!((90..120).contains(&accrued), "accrued {accrued}s");
+
+    // The play is recorded once, closed when the session ended
+    let plays = game_play::Entity::find()
+        .filter(game_play::Column::SessionId.eq(session_uuid))
+        .all(&state.db)
+        .await?;
+    assert_eq!(plays.len(), 1);
+    assert_eq!(plays[0].game_id, pong_uuid);
+    assert!(plays[0].ended_at.is_some());
+    assert_eq!(plays[0].play_seconds, accrued);
+    Ok(())
+}
+
+// ──────────────────────────────────────────────────────────────────────────────
+// Game state keyframe cache (delta encoding support)
+// ──────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn state_snapshot_keeps_latest_keyframe() {
+    let manager = SessionManager::new();
+    let session_id = Uuid::new_v4();
+
+    assert!(manager.latest_snapshot(session_id).is_none());
+
+    manager.store_snapshot(
+        session_id,
+        StateSnapshot {
+            seq: Some(1),
+            message: r#"{"type":"game_state","seq":1,"payload":{}}"#.to_string(),
+        },
+    );
+    manager.store_snapshot(
+        session_id,
+        StateSnapshot {
+            seq: Some(5),
+            message: r#"{"type":"game_state","seq":5,"payload":{}}"#.to_string(),
+        },
+    );
+
+    let snapshot = manager.latest_snapshot(session_id);
+    assert_eq!(snapshot.and_then(|s| s.seq), Some(5));
+}
+
+#[tokio::test]
+async fn remove_session_clears_state_snapshot() {
+    let manager = SessionManager::new();
+    let session_id = Uuid::new_v4();
+
+    manager.store_snapshot(
+        session_id,
+        StateSnapshot {
+            seq: None,
+            message: r#"{"type":"game_state","payload":{}}"#.to_string(),
+        },
+    );
+    manager.remove_session(session_id);
+
+    assert!(manager.latest_snapshot(session_id).is_none());
+}
+
+#[tokio::test]
+async fn deflate_compression_is_app_level_not_permessage_deflate() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (token, _refresh) =
+        signup_user(&app, "deflate@example.com", "deflateuser", "Password123").await;
+    let session_json = create_session(&app, &token).await;
+    let session_id: Uuid = session_json["id"].as_str().unwrap_or_default().parse()?;
+    let code = session_json["sessionCode"].as_str().unwrap_or_default();
+    let (status, body) = common::post_json(
+        &app,
+        &format!("/api/v1/sessions/{code}/join"),
+        &json!({ "displayName": "Player One" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "join failed: {body}");
+    let joined: serde_json::Value = serde_json::from_str(&body)?;
+    let player_id = joined["player"]["id"].as_str().unwrap_or_default();
+
+    // A keyframe large enough to be compressed, sent to the player on connect
+    let keyframe = json!({ "type": "game_state", "payload": { "board": "x".repeat(4096) } });
+    state.session_manager.store_snapshot(
+        session_id,
+        StateSnapshot {
+            seq: None,
+            message: keyframe.to_string(),
+        },
+    );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let mut request = format!(
+        "ws://{addr}/api/v1/sessions/{session_id}/ws?role=player&playerId={player_id}&compression=deflate"
+    )
+    .into_client_request()?;
+    request.headers_mut().insert(
+        "sec-websocket-extensions",
+        HeaderValue::from_static("permessage-deflate"),
+    );
+    let (mut socket, response) = tokio_tungstenite::connect_async(request).await?;
+    assert!(!response.headers().contains_key("sec-websocket-extensions"));
+
+    let mut next = async || {
+        tokio::time::timeout(Duration::from_secs(1), socket.next())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("socket closed"))?
+            .map_err(anyhow::Error::from)
+    };
+    assert!(matches!(next().await?, Message::Text(_)));
+    let Message::Binary(compressed) = next().await? else {
+        anyhow::bail!("expected the keyframe as a binary frame");
+    };
+    let mut inflated = String::new();
+    flate2::read::DeflateDecoder::new(compressed.as_ref()).read_to_string(&mut inflated)?;
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&inflated)?,
+        keyframe
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn record_input_reports_each_player_once() {
+    let manager = SessionManager::new();
+    let session_id = Uuid::new_v4();
+    let player_id = Uuid::new_v4();
+
+    assert!(manager.record_input(session_id, player_id));
+    assert!(!manager.record_input(session_id, player_id));
+    assert!(manager.record_input(session_id, Uuid::new_v4()));
+
+    manager.remove_session(session_id);
+    assert!(manager.record_input(session_id, player_id));
+}
+
+#[tokio::test]
+async fn metrics_count_connections_and_received_messages() {
+    let manager = SessionManager::new();
+    let session_id = Uuid::new_v4();
+    let player_id = Uuid::new_v4();
+
+    simulate_ws_connections(&manager, session_id, Some(player_id));
+    manager.unregister(session_id, &ClientRole::Player(player_id));
+    manager.record_received("player_input", 100);
+    manager.record_received("player_input", 129);
+    manager.record_received("game_state_update", 200_000);
+    manager.record_received("made_up", 10);
+
+    let metrics = manager.metrics();
+    assert_eq!(metrics.connects_total, 2);
+    assert_eq!(metrics.disconnects_total, 1);
+    assert_eq!(metrics.messages_received_by_type["player_input"], 2);
+    assert_eq!(metrics.messages_received_by_type["game_state_update"], 1);
+    assert_eq!(metrics.messages_received_by_type["game_state_delta"], 0);
+    assert_eq!(metrics.messages_received_by_type["other"], 1);
+    assert!(!metrics.messages_received_by_type.contains_key("made_up"));
+
+    let sizes = metrics.message_sizes;
+    assert_eq!(sizes.count, 4);
+    assert_eq!(sizes.sum_bytes, 200_239);
+    let counts: Vec<u64> = sizes.buckets.iter().map(|bucket| bucket.count).collect();
+    assert_eq!(counts, [2, 1, 0, 0, 0, 0, 1]);
+    assert_eq!(
+        sizes.buckets.last().and_then(|bucket| bucket.le_bytes),
+        None
+    );
+}
+
+#[tokio::test]
+async fn telemetry_tracks_peak_players_messages_and_disconnects() {
+    let manager = SessionManager::new();
+    let session_id = Uuid::new_v4();
+    let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+
+    simulate_ws_connections(&manager, session_id, Some(first));
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+    manager.register(session_id, ClientRole::Player(second), tx);
+    manager.broadcast_to_players(session_id, "{}");
+    manager.unregister(session_id, &ClientRole::Player(second));
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+    manager.register(session_id, ClientRole::Player(second), tx);
+
+    let telemetry = manager.telemetry(session_id);
+    assert_eq!(telemetry.peak_players, 2);
+    assert_eq!(telemetry.disconnects, 1);
+
+    manager.remove_session(session_id);
+    assert_eq!(manager.telemetry(session_id), SessionTelemetry::default());
+}
+
+#[tokio::test]
+async fn ended_sessions_show_their_summary_in_the_host_history() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (token, _refresh) =
+        signup_user(&app, "history@example.com", "historyhost", "Password123").await;
+
+    let ended = create_session(&app, &token).await;
+    let ended_id: Uuid = ended["id"].as_str().unwrap_or_default().parse()?;
+    let player_id = Uuid::new_v4();
+    let (host_tx, _host_rx) = tokio::sync::mpsc::unbounded_channel();
+    state
+        .session_manager
+        .register(ended_id, ClientRole::Host, host_tx);
+    let (player_tx, _player_rx) = tokio::sync::mpsc::unbounded_channel();
+    state
+        .session_manager
+        .register(ended_id, ClientRole::Player(player_id), player_tx);
+    state.session_manager.broadcast(ended_id, "{}");
+    state
+        .session_manager
+        .unregister(ended_id, &ClientRole::Player(player_id));
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/sessions/{ended_id}/end"),
+        &json!({}),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let open = create_session(&app, &token).await;
+
+    let (status, body) =
+        common::get_with_auth(&app, "/api/v1/users/me/hosted-sessions", &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(json["total"], 2);
+    let data = json["data"].as_array().cloned().unwrap_or_default();
+    let find = |id: &serde_json::Value| data.iter().find(|s| &s["id"] == id).cloned();
+    assert!(find(&open["id"]).is_some_and(|s| s["summary"].is_null()));
+
+    let ended = find(&ended["id"]).unwrap_or_default();
+    assert_eq!(ended["status"], "ended");
+    let summary = &ended["summary"];
+    assert!(summary["durationSeconds"].as_i64().is_some());
+    assert_eq!(summary["peakPlayers"], 1);
+    assert_eq!(summary["messagesRelayed"], 2);
+    assert_eq!(summary["disconnects"], 1);
+
+    // Other users only see their own sessions
+    let (other, _refresh) =
+        signup_user(&app, "guest@example.com", "guestuser", "Password123").await;
+    let (status, body) =
+        common::get_with_auth(&app, "/api/v1/users/me/hosted-sessions", &other).await;
+    assert_eq!(status, StatusCode::OK);
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(json["total"], 0);
+    Ok(())
+}
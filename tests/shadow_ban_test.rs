@@ -0,0 +1,181 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::{game, notification};
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a published public game titled `Hidden Gem <id>` owned by `owner_id` and return its ID.
+async fn create_published_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set(format!("Hidden Gem {id}")),
+        slug: Set(format!("hidden-gem-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("published".to_string()),
+        visibility: Set("public".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+/// Shadow-ban (`ban`) or lift the ban on `user_id` as moderator `token`.
+async fn set_shadow_ban(app: &Router, token: &str, user_id: Uuid, ban: bool) -> StatusCode {
+    let uri = format!("/api/v1/admin/users/{user_id}/shadow-ban");
+    if ban {
+        common::post_json_with_auth(app, &uri, &json!({}), token)
+            .await
+            .0
+    } else {
+        common::delete_with_auth(app, &uri, token).await.0
+    }
+}
+
+/// IDs in the `data` of a paginated response.
+fn ids(body: &str) -> Vec<String> {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| {
+            v["data"].as_array().map(|items| {
+                items
+                    .iter()
+                    .filter_map(|i| i["id"].as_str().map(ToString::to_string))
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+async fn review_count(state: &AppState, game_id: Uuid) -> anyhow::Result<i64> {
+    Ok(game::Entity::find_by_id(game_id)
+        .one(&state.db)
+        .await?
+        .map(|g| g.review_count)
+        .unwrap_or_default())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Shadow Bans
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn shadow_banned_content_is_hidden_from_everyone_else() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, moderator_token) = common::create_user(&state, "moderator").await?;
+    let (banned_id, banned_token) = common::create_user(&state, "user").await?;
+    let (owner_id, owner_token) = common::create_user(&state, "user").await?;
+    let hidden_id = create_published_game(&state, banned_id).await?.to_string();
+    let reviewed_id = create_published_game(&state, owner_id).await?;
+    let reviews_uri = format!("/api/v1/games/{reviewed_id}/reviews");
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &reviews_uri,
+        &json!({ "rating": 1, "body": "Terrible." }),
+        &banned_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    assert_eq!(review_count(&state, reviewed_id).await?, 1);
+
+    assert_eq!(
+        set_shadow_ban(&app, &owner_token, banned_id, true).await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        set_shadow_ban(&app, &moderator_token, banned_id, true).await,
+        StatusCode::NO_CONTENT
+    );
+
+    // Everyone else stops seeing the game and the review...
+    let (_, body) = common::get(&app, "/api/v1/games").await;
+    assert!(!ids(&body).contains(&hidden_id));
+    let (_, body) = common::get(&app, "/api/v1/search/games?q=hidden").await;
+    assert!(!ids(&body).contains(&hidden_id));
+    let (_, body) = common::get_with_auth(&app, &reviews_uri, &owner_token).await;
+    assert!(ids(&body).is_empty(), "{body}");
+    assert_eq!(review_count(&state, reviewed_id).await?, 0);
+
+    // ...while the banned user sees both as before
+    let (_, body) = common::get_with_auth(&app, "/api/v1/games", &banned_token).await;
+    assert!(ids(&body).contains(&hidden_id));
+    let (_, body) =
+        common::get_with_auth(&app, "/api/v1/search/games?q=hidden", &banned_token).await;
+    assert!(ids(&body).contains(&hidden_id));
+    let (_, body) = common::get_with_auth(&app, &reviews_uri, &banned_token).await;
+    assert_eq!(ids(&body).len(), 1, "{body}");
+
+    assert_eq!(
+        set_shadow_ban(&app, &moderator_token, banned_id, false).await,
+        StatusCode::NO_CONTENT
+    );
+    let (_, body) = common::get(&app, "/api/v1/games").await;
+    assert!(ids(&body).contains(&hidden_id));
+    assert_eq!(review_count(&state, reviewed_id).await?, 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn shadow_banned_users_notify_no_one() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, moderator_token) = common::create_user(&state, "moderator").await?;
+    let (banned_id, banned_token) = common::create_user(&state, "user").await?;
+    let (followee_id, _) = common::create_user(&state, "user").await?;
+    let followee = format!("user_{}", &followee_id.to_string()[..8]);
+
+    assert_eq!(
+        set_shadow_ban(&app, &moderator_token, banned_id, true).await,
+        StatusCode::NO_CONTENT
+    );
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/users/{followee}/follow"),
+        &json!({}),
+        &banned_token,
+    )
+    .await;
+    assert!(status.is_success(), "{status}: {body}");
+
+    let received = notification::Entity::find()
+        .filter(notification::Column::UserId.eq(followee_id))
+        .count(&state.db)
+        .await?;
+    assert_eq!(received, 0);
+
+    Ok(())
+}
@@ -0,0 +1,226 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::{DateTime, Duration, Utc};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, EntityTrait};
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::health_check;
+use aircade_api::jobs::health_checks;
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Record `component`'s check as `status` at `checked_at`.
+async fn insert_check(
+    state: &AppState,
+    component: &str,
+    status: &str,
+    checked_at: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    health_check::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        component: Set(component.to_string()),
+        status: Set(status.to_string()),
+        latency_ms: Set(Some(3)),
+        checked_at: Set(checked_at.fixed_offset()),
+    }
+    .insert(&state.db)
+    .await?;
+    Ok(())
+}
+
+async fn status(app: &Router) -> anyhow::Result<serde_json::Value> {
+    let (status, body) = common::get(app, "/api/v1/status").await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    Ok(serde_json::from_str(&body)?)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// /api/v1/status
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn status_checks_components_before_any_history() -> anyhow::Result<()> {
+    let (app, _) = test_app().await;
+
+    let json = status(&app).await?;
+    assert_eq!(json["status"], "operational", "{json}");
+    assert_eq!(json["components"]["database"]["status"], "up");
+    assert_eq!(json["components"]["email"]["status"], "disabled");
+    assert!(json["incident"].is_null());
+    assert_eq!(json["uptime"], json!({}));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn status_reports_the_latest_checks_and_uptime() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let now = Utc::now();
+
+    // Three of the four checks in the last day passed, and one more failed earlier this week
+    for minutes in [40, 30, 20] {
+        insert_check(&state, "database", "up", now - Duration::minutes(minutes)).await?;
+        insert_check(
+            &state,
+            "email",
+            "disabled",
+            now - Duration::minutes(minutes),
+        )
+        .await?;
+    }
+    insert_check(&state, "database", "down", now - Duration::minutes(10)).await?;
+    insert_check(&state, "email", "disabled", now - Duration::minutes(10)).await?;
+    insert_check(&state, "database", "down", now - Duration::days(3)).await?;
+
+    let json = status(&app).await?;
+    assert_eq!(json["status"], "degraded", "{json}");
+    assert_eq!(json["components"]["database"]["status"], "down");
+    assert_eq!(json["components"]["email"]["status"], "disabled");
+    assert_eq!(
+        json["uptime"]["database"],
+        json!({ "24h": 75.0, "7d": 60.0, "30d": 60.0 })
+    );
+    assert_eq!(
+        json["uptime"]["email"],
+        json!({ "24h": 100.0, "7d": 100.0, "30d": 100.0 })
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn health_check_job_records_and_prunes_checks() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let now = Utc::now();
+
+    insert_check(&state, "database", "up", now - Duration::days(31)).await?;
+    health_checks::record(&state.db, &state.config, now).await?;
+    let pruned = health_checks::prune(&state.db, now).await?;
+    assert_eq!(pruned, 1);
+
+    let checks = health_check::Entity::find().all(&state.db).await?;
+    let mut components: Vec<&str> = checks.iter().map(|c| c.component.as_str()).collect();
+    components.sort_unstable();
+    assert_eq!(components, ["database", "email", "migrations", "storage"]);
+
+    let json = status(&app).await?;
+    assert_eq!(json["status"], "operational", "{json}");
+    assert_eq!(json["uptime"]["migrations"]["24h"], 100.0);
+
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// /api/v1/admin/status/incident
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn admins_post_update_and_resolve_the_incident() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+
+    let (status_code, body) = common::put_json_with_auth(
+        &app,
+        "/api/v1/admin/status/incident",
+        &json!({
+            "title": "Sessions failing to start",
+            "message": "We are investigating.",
+            "impact": "major",
+        }),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status_code, StatusCode::OK, "{body}");
+    let posted: serde_json::Value = serde_json::from_str(&body)?;
+
+    let (status_code, body) = common::put_json_with_auth(
+        &app,
+        "/api/v1/admin/status/incident",
+        &json!({
+            "title": "Sessions failing to start",
+            "message": "A fix is being deployed.",
+            "impact": "minor",
+        }),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status_code, StatusCode::OK, "{body}");
+    let updated: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(
+        updated["id"], posted["id"],
+        "the ongoing incident is updated"
+    );
+    assert_eq!(updated["startedAt"], posted["startedAt"]);
+
+    let json = status(&app).await?;
+    assert_eq!(json["incident"]["message"], "A fix is being deployed.");
+    assert_eq!(json["incident"]["impact"], "minor");
+
+    let (status_code, _) =
+        common::delete_with_auth(&app, "/api/v1/admin/status/incident", &admin_token).await;
+    assert_eq!(status_code, StatusCode::NO_CONTENT);
+    assert!(status(&app).await?["incident"].is_null());
+
+    let (status_code, _) =
+        common::delete_with_auth(&app, "/api/v1/admin/status/incident", &admin_token).await;
+    assert_eq!(status_code, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn only_admins_post_valid_incidents() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+    let (_, user_token) = common::create_user(&state, "user").await?;
+
+    let incident = json!({ "title": "Outage", "message": "Down.", "impact": "major" });
+    let (status_code, _) = common::put_json_with_auth(
+        &app,
+        "/api/v1/admin/status/incident",
+        &incident,
+        &user_token,
+    )
+    .await;
+    assert_eq!(status_code, StatusCode::FORBIDDEN);
+
+    let (status_code, _) = common::put_json_with_auth(
+        &app,
+        "/api/v1/admin/status/incident",
+        &json!({ "title": "Outage", "message": "Down.", "impact": "catastrophic" }),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status_code, StatusCode::BAD_REQUEST);
+
+    let (status_code, _) = common::put_json_with_auth(
+        &app,
+        "/api/v1/admin/status/incident",
+        &json!({ "title": " ", "message": "Down.", "impact": "major" }),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status_code, StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
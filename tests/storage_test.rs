@@ -0,0 +1,422 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveModelTrait;
+use sea_orm::ActiveValue::Set;
+use sea_orm::EntityTrait;
+use uuid::Uuid;
+
+use aircade_api::config::{Config, StorageBackend};
+use aircade_api::entities::{game, game_asset, storage_object, user};
+use aircade_api::jobs::purge;
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// App backed by the given storage backend, with uploads under a fresh temporary directory.
+async fn test_app(storage_backend: StorageBackend) -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(
+        db,
+        Config {
+            upload_dir: std::env::temp_dir()
+                .join(format!("aircade-storage-{}", Uuid::new_v4()))
+                .to_string_lossy()
+                .into_owned(),
+            storage_backend,
+            ..common::test_config()
+        },
+    );
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a published public game owned by `owner_id` and return its ID.
+async fn create_published_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Party Hit".to_string()),
+        slug: Set(format!("party-hit-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("published".to_string()),
+        visibility: Set("public".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Storage Backends
+// ─────────────────────────────────────────────────────────────────────────────
+
+const SPRITE: &[u8] = b"\x89PNG\r\n\x1a\nnot really a sprite";
+
+#[tokio::test]
+async fn filesystem_backend_stores_asset_on_disk() -> anyhow::Result<()> {
+    let (app, state) = test_app(StorageBackend::Filesystem).await;
+    let (user_id, token) = common::create_user(&state, "user").await?;
+    let game_id = create_published_game(&state, user_id).await?;
+
+    let (status, body) = common::post_multipart_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/assets"),
+        "sprite.png",
+        "image/png",
+        SPRITE,
+        &[],
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    let asset_id: Uuid = v["id"].as_str().unwrap_or_default().parse()?;
+
+    let row = game_asset::Entity::find_by_id(asset_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("asset row missing"))?;
+    assert_eq!(row.storage_backend, "filesystem");
+    assert!(row.file_data.is_empty());
+
+    let on_disk = std::path::Path::new(&state.config.upload_dir)
+        .join("assets")
+        .join(row.storage_key.unwrap_or_default());
+    assert_eq!(std::fs::read(on_disk)?, SPRITE);
+
+    let (status, _, served) = common::get_raw(
+        &app,
+        &format!("/api/v1/games/{game_id}/assets/{asset_id}/file"),
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(served, SPRITE);
+
+    std::fs::remove_dir_all(&state.config.upload_dir)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn backfill_moves_inline_assets_to_configured_storage() -> anyhow::Result<()> {
+    let (app, state) = test_app(StorageBackend::Filesystem).await;
+    let (user_id, _) = common::create_user(&state, "user").await?;
+    let game_id = create_published_game(&state, user_id).await?;
+
+    // An asset uploaded before pluggable storage existed
+    let asset_id = Uuid::new_v4();
+    game_asset::ActiveModel {
+        id: Set(asset_id),
+        created_at: Set(Utc::now().fixed_offset()),
+        deleted_at: Set(None),
+        game_id: Set(game_id),
+        file_name: Set("sprite.png".to_string()),
+        file_type: Set("image/png".to_string()),
+        file_size: Set(i32::try_from(SPRITE.len())?),
+        file_data: Set(SPRITE.to_vec()),
+        storage_url: Set(format!("assets/{game_id}/sprite.png")),
+        storage_backend: Set("inline".to_string()),
+        storage_key: Set(None),
+        content_hash: Set(None),
+    }
+    .insert(&state.db)
+    .await?;
+
+    let moved = aircade_api::jobs::storage_backfill::run(&state.db, &state.config).await?;
+    assert_eq!(moved, 1);
+    assert_eq!(
+        aircade_api::jobs::storage_backfill::run(&state.db, &state.config).await?,
+        0
+    );
+
+    let row = game_asset::Entity::find_by_id(asset_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("asset row missing"))?;
+    assert_eq!(row.storage_backend, "filesystem");
+    assert!(row.file_data.is_empty());
+    assert!(row.content_hash.is_some());
+
+    let (status, _, served) = common::get_raw(
+        &app,
+        &format!("/api/v1/games/{game_id}/assets/{asset_id}/file"),
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(served, SPRITE);
+
+    std::fs::remove_dir_all(&state.config.upload_dir)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn database_backend_uses_storage_object_table() -> anyhow::Result<()> {
+    let (app, state) = test_app(StorageBackend::Database).await;
+    let (user_id, token) = common::create_user(&state, "user").await?;
+    let game_id = create_published_game(&state, user_id).await?;
+
+    let (status, body) = common::post_multipart_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/assets"),
+        "sprite.png",
+        "image/png",
+        SPRITE,
+        &[],
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    let asset_id: Uuid = v["id"].as_str().unwrap_or_default().parse()?;
+
+    let row = game_asset::Entity::find_by_id(asset_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("asset row missing"))?;
+    assert_eq!(row.storage_backend, "database");
+    assert!(row.file_data.is_empty());
+
+    let object = aircade_api::entities::storage_object::Entity::find_by_id(
+        row.storage_key.unwrap_or_default(),
+    )
+    .one(&state.db)
+    .await?
+    .ok_or_else(|| anyhow::anyhow!("storage object missing"))?;
+    assert_eq!(object.data, SPRITE);
+    assert_eq!(object.content_type, "image/png");
+    Ok(())
+}
+
+/// Upload `data` as an asset of `game_id` and return the stored row.
+async fn upload_asset(
+    app: &Router,
+    state: &AppState,
+    token: &str,
+    game_id: Uuid,
+    data: &[u8],
+) -> anyhow::Result<game_asset::Model> {
+    let (status, body) = common::post_multipart_with_auth(
+        app,
+        &format!("/api/v1/games/{game_id}/assets"),
+        "sounds.ogg",
+        "audio/ogg",
+        data,
+        &[],
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    let asset_id: Uuid = v["id"].as_str().unwrap_or_default().parse()?;
+
+    game_asset::Entity::find_by_id(asset_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("asset row missing"))
+}
+
+#[tokio::test]
+async fn identical_uploads_by_same_creator_share_a_blob() -> anyhow::Result<()> {
+    let (app, state) = test_app(StorageBackend::Database).await;
+    let (alice_id, alice_token) = common::create_user(&state, "user").await?;
+    let (bob_id, bob_token) = common::create_user(&state, "user").await?;
+    let first_game = create_published_game(&state, alice_id).await?;
+    let second_game = create_published_game(&state, alice_id).await?;
+    let bobs_game = create_published_game(&state, bob_id).await?;
+
+    let original = upload_asset(&app, &state, &alice_token, first_game, SPRITE).await?;
+    let same_game = upload_asset(&app, &state, &alice_token, first_game, SPRITE).await?;
+    let other_game = upload_asset(&app, &state, &alice_token, second_game, SPRITE).await?;
+    let other_owner = upload_asset(&app, &state, &bob_token, bobs_game, SPRITE).await?;
+
+    assert_eq!(
+        original.content_hash.as_deref(),
+        Some("6a3a3e8a6865742c1493984bf91b0a6e214ce2bdf225207b6b018453e5e6d349")
+    );
+    assert_eq!(same_game.storage_key, original.storage_key);
+    assert_eq!(other_game.storage_key, original.storage_key);
+    assert_ne!(other_owner.storage_key, original.storage_key);
+    assert_eq!(other_owner.content_hash, original.content_hash);
+
+    let objects = aircade_api::entities::storage_object::Entity::find()
+        .all(&state.db)
+        .await?;
+    assert_eq!(objects.len(), 2);
+
+    // The shared blob is served for every asset that references it
+    let (status, _, served) = common::get_raw(
+        &app,
+        &format!("/api/v1/games/{second_game}/assets/{}/file", other_game.id),
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(served, SPRITE);
+    Ok(())
+}
+
+#[tokio::test]
+async fn upload_rejected_when_plan_quota_exceeded() -> anyhow::Result<()> {
+    let (app, state) = test_app(StorageBackend::Database).await;
+    let (user_id, token) = common::create_user(&state, "user").await?;
+    let game_id = create_published_game(&state, user_id).await?;
+
+    // Fill the free plan's 50 MB almost completely
+    let near_limit = i32::try_from(aircade_api::storage::quota::FREE_QUOTA_BYTES)? - 8;
+    game_asset::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        created_at: Set(Utc::now().fixed_offset()),
+        deleted_at: Set(None),
+        game_id: Set(game_id),
+        file_name: Set("music.ogg".to_string()),
+        file_type: Set("audio/ogg".to_string()),
+        file_size: Set(near_limit),
+        file_data: Set(Vec::new()),
+        storage_url: Set(format!("assets/{game_id}/music.ogg")),
+        storage_backend: Set("database".to_string()),
+        storage_key: Set(Some("games/placeholder".to_string())),
+        content_hash: Set(None),
+    }
+    .insert(&state.db)
+    .await?;
+
+    let (status, body) = common::get_with_auth(&app, "/api/v1/users/me/storage", &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["plan"], "free");
+    assert_eq!(v["usedBytes"], near_limit);
+    assert_eq!(v["quotaBytes"], 50 * 1024 * 1024);
+
+    let (status, body) = common::post_multipart_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/assets"),
+        "sprite.png",
+        "image/png",
+        SPRITE,
+        &[],
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["error"]["code"], "QUOTA_EXCEEDED");
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Purging Deleted Rows
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn purge_removes_expired_games_and_orphaned_blobs() -> anyhow::Result<()> {
+    let (app, state) = test_app(StorageBackend::Database).await;
+    let (user_id, token) = common::create_user(&state, "user").await?;
+    let first_game = create_published_game(&state, user_id).await?;
+    let second_game = create_published_game(&state, user_id).await?;
+    let shared = upload_asset(&app, &state, &token, first_game, SPRITE).await?;
+    upload_asset(&app, &state, &token, second_game, SPRITE).await?;
+
+    let (status, _) =
+        common::delete_with_auth(&app, &format!("/api/v1/games/{first_game}"), &token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // Still inside the retention window: nothing is purged
+    let purged = purge::run(&state.db, &state.config, Utc::now()).await?;
+    assert_eq!(purged, purge::PurgeStats::default());
+
+    let later = Utc::now() + Duration::days(state.config.deleted_retention_days + 1);
+    let purged = purge::run(&state.db, &state.config, later).await?;
+    assert_eq!((purged.games, purged.assets, purged.blobs), (1, 1, 0));
+    assert!(
+        game::Entity::find_by_id(first_game)
+            .one(&state.db)
+            .await?
+            .is_none()
+    );
+    assert!(
+        game_asset::Entity::find_by_id(shared.id)
+            .one(&state.db)
+            .await?
+            .is_none()
+    );
+
+    // The blob is still used by the other game's asset
+    let key = shared.storage_key.unwrap_or_default();
+    assert!(
+        storage_object::Entity::find_by_id(key.clone())
+            .one(&state.db)
+            .await?
+            .is_some()
+    );
+
+    // Once the last user of the blob is purged, the blob goes too
+    let (status, _) =
+        common::delete_with_auth(&app, &format!("/api/v1/games/{second_game}"), &token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let purged = purge::run(&state.db, &state.config, later).await?;
+    assert_eq!((purged.games, purged.assets, purged.blobs), (1, 1, 1));
+    assert!(
+        storage_object::Entity::find_by_id(key)
+            .one(&state.db)
+            .await?
+            .is_none()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn purge_removes_expired_accounts_with_their_games() -> anyhow::Result<()> {
+    let (app, state) = test_app(StorageBackend::Database).await;
+    let (user_id, token) = common::create_user(&state, "user").await?;
+    let game_id = create_published_game(&state, user_id).await?;
+    upload_asset(&app, &state, &token, game_id, SPRITE).await?;
+
+    let found = user::Entity::find_by_id(user_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("user missing"))?;
+    let mut active: user::ActiveModel = found.into();
+    active.deleted_at = Set(Some(Utc::now().fixed_offset()));
+    active.update(&state.db).await?;
+
+    let later = Utc::now() + Duration::days(state.config.deleted_retention_days + 1);
+    let purged = purge::run(&state.db, &state.config, later).await?;
+    assert_eq!(
+        (purged.users, purged.games, purged.assets, purged.blobs),
+        (1, 1, 1, 1)
+    );
+    assert!(
+        user::Entity::find_by_id(user_id)
+            .one(&state.db)
+            .await?
+            .is_none()
+    );
+    assert!(
+        game::Entity::find_by_id(game_id)
+            .one(&state.db)
+            .await?
+            .is_none()
+    );
+    Ok(())
+}
@@ -0,0 +1,208 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, EntityTrait};
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::user;
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Suspend `user_id` with `reason`.
+async fn suspend(state: &AppState, user_id: Uuid, reason: &str) -> anyhow::Result<()> {
+    user::ActiveModel {
+        id: Set(user_id),
+        account_status: Set("suspended".to_string()),
+        suspension_reason: Set(Some(reason.to_string())),
+        ..Default::default()
+    }
+    .update(&state.db)
+    .await?;
+    Ok(())
+}
+
+/// File an appeal as `token`'s user and return its ID.
+async fn appeal(app: &Router, token: &str) -> anyhow::Result<String> {
+    let (status, body) = common::post_json_with_auth(
+        app,
+        "/api/v1/auth/appeal",
+        &json!({ "statement": "I was posting my own artwork, not spam. Please review again." }),
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    Ok(v["id"].as_str().unwrap_or_default().to_string())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// /api/v1/auth/appeal
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn suspended_users_can_only_appeal() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (user_id, token) = common::create_user(&state, "user").await?;
+
+    // Active accounts have nothing to appeal
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/auth/appeal",
+        &json!({ "statement": "I would like to appeal something, please." }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    suspend(&state, user_id, "Spam links in game descriptions.").await?;
+    let (status, _) = common::get_with_auth(&app, "/api/v1/users/me", &token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/auth/appeal",
+        &json!({ "statement": "Sorry" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    appeal(&app, &token).await?;
+    let (status, body) = common::get_with_auth(&app, "/api/v1/auth/appeal", &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let mine: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(mine["status"], "pending");
+    assert_eq!(mine["suspensionReason"], "Spam links in game descriptions.");
+
+    // One pending appeal at a time
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/auth/appeal",
+        &json!({ "statement": "Following up on my appeal, it was a misunderstanding." }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// /api/v1/admin/suspension-appeals
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn approving_an_appeal_lifts_the_suspension() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (user_id, token) = common::create_user(&state, "user").await?;
+    let (_, moderator_token) = common::create_user(&state, "moderator").await?;
+    let (admin_id, admin_token) = common::create_user(&state, "admin").await?;
+    suspend(&state, user_id, "Spam.").await?;
+    let id = appeal(&app, &token).await?;
+
+    let (status, _) =
+        common::get_with_auth(&app, "/api/v1/admin/suspension-appeals", &moderator_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) =
+        common::get_with_auth(&app, "/api/v1/admin/suspension-appeals", &admin_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let queue: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(queue["total"], 1);
+    assert_eq!(queue["data"][0]["id"], id.as_str());
+    assert_eq!(queue["data"][0]["user"]["id"], user_id.to_string());
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/suspension-appeals/{id}/approve"),
+        &json!({ "note": "Thanks for explaining." }),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let decided: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(decided["status"], "approved");
+    assert_eq!(decided["reviewerId"], admin_id.to_string());
+    assert_eq!(decided["user"]["accountStatus"], "active");
+
+    let reinstated = user::Entity::find_by_id(user_id).one(&state.db).await?;
+    assert!(reinstated.is_some_and(|u| u.suspension_reason.is_none()));
+
+    // Decided appeals can't be decided again
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/suspension-appeals/{id}/deny"),
+        &json!({}),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) =
+        common::get_with_auth(&app, "/api/v1/users/me/notifications", &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let notices: Vec<serde_json::Value> = serde_json::from_str(&body)?;
+    assert!(notices.iter().any(|n| {
+        n["kind"] == "suspension_appeal_reviewed"
+            && n["body"]
+                .as_str()
+                .unwrap_or_default()
+                .contains("Thanks for explaining.")
+    }));
+    Ok(())
+}
+
+#[tokio::test]
+async fn denied_users_stay_suspended_and_may_appeal_again() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (user_id, token) = common::create_user(&state, "user").await?;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+    suspend(&state, user_id, "Spam.").await?;
+    let id = appeal(&app, &token).await?;
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/suspension-appeals/{id}/deny"),
+        &json!({ "note": "The links were still up yesterday." }),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let decided: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(decided["user"]["accountStatus"], "suspended");
+
+    let (_, body) = common::get_with_auth(&app, "/api/v1/auth/appeal", &token).await;
+    let mine: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(mine["status"], "denied");
+    assert_eq!(mine["reviewNote"], "The links were still up yesterday.");
+
+    let (_, body) = common::get_with_auth(
+        &app,
+        "/api/v1/admin/suspension-appeals?status=denied",
+        &admin_token,
+    )
+    .await;
+    let denied: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(denied["total"], 1);
+
+    appeal(&app, &token).await?;
+    Ok(())
+}
@@ -0,0 +1,219 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveModelTrait;
+use sea_orm::ActiveValue::Set;
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::game;
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a published public game owned by `owner_id` and return its ID.
+async fn create_published_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Removed Game".to_string()),
+        slug: Set(format!("removed-game-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("published".to_string()),
+        visibility: Set("public".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+const APPEAL: &str = "The flagged content was a false positive from a placeholder asset.";
+
+/// Take down `game_id` as `moderator_token`'s moderator.
+async fn take_down(app: &Router, moderator_token: &str, game_id: Uuid) -> anyhow::Result<()> {
+    let (status, body) = common::post_json_with_auth(
+        app,
+        &format!("/api/v1/admin/games/{game_id}/takedown"),
+        &json!({ "reason": "Contains copyrighted music." }),
+        moderator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/v1/admin/games/{id}/takedown
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn takedown_hides_game_and_blocks_publishing() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (creator_id, creator_token) = common::create_user(&state, "user").await?;
+    let (_, moderator_token) = common::create_user(&state, "moderator").await?;
+    let game_id = create_published_game(&state, creator_id).await?;
+
+    // Only moderators take games down
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/games/{game_id}/takedown"),
+        &json!({ "reason": "Spam." }),
+        &creator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    take_down(&app, &moderator_token, game_id).await?;
+
+    let (status, _) = common::get(&app, &format!("/api/v1/games/{game_id}")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) =
+        common::get_with_auth(&app, &format!("/api/v1/games/{game_id}"), &creator_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let game: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(game["status"], "removed_by_moderation");
+
+    for action in ["publish", "archive"] {
+        let (status, body) = common::post_json_with_auth(
+            &app,
+            &format!("/api/v1/games/{game_id}/{action}"),
+            &json!({}),
+            &creator_token,
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{action}");
+        let v: serde_json::Value = serde_json::from_str(&body)?;
+        assert_eq!(v["error"]["code"], "REMOVED_BY_MODERATION");
+    }
+
+    let (status, body) = common::get_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/takedown"),
+        &creator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let takedown: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(takedown["reason"], "Contains copyrighted music.");
+
+    let (_, body) =
+        common::get_with_auth(&app, "/api/v1/users/me/notifications", &creator_token).await;
+    let notices: Vec<serde_json::Value> = serde_json::from_str(&body)?;
+    assert!(notices.iter().any(|n| {
+        n["kind"] == "content_removed"
+            && n["body"]
+                .as_str()
+                .unwrap_or_default()
+                .contains("Contains copyrighted music.")
+    }));
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Appeals
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn approved_appeal_restores_the_game() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (creator_id, creator_token) = common::create_user(&state, "user").await?;
+    let (_, moderator_token) = common::create_user(&state, "moderator").await?;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+    let game_id = create_published_game(&state, creator_id).await?;
+
+    // Nothing to appeal yet
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/appeal"),
+        &json!({ "message": APPEAL }),
+        &creator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    take_down(&app, &moderator_token, game_id).await?;
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/appeal"),
+        &json!({ "message": APPEAL }),
+        &creator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let appeal: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(appeal["appealStatus"], "pending");
+    let id = appeal["id"].as_str().unwrap_or_default().to_string();
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/appeal"),
+        &json!({ "message": APPEAL }),
+        &creator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Moderators take games down, but only admins decide appeals
+    let (status, _) = common::get_with_auth(&app, "/api/v1/admin/appeals", &moderator_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = common::get_with_auth(&app, "/api/v1/admin/appeals", &admin_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let queue: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(queue["total"], 1);
+    assert_eq!(queue["data"][0]["game"]["id"], game_id.to_string());
+    assert_eq!(queue["data"][0]["appealMessage"], APPEAL);
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/appeals/{id}/approve"),
+        &json!({ "note": "The track is licensed." }),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let decided: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(decided["appealStatus"], "approved");
+
+    let (status, body) = common::get(&app, &format!("/api/v1/games/{game_id}")).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let game: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(game["status"], "published");
+
+    let (_, body) =
+        common::get_with_auth(&app, "/api/v1/users/me/notifications", &creator_token).await;
+    let notices: Vec<serde_json::Value> = serde_json::from_str(&body)?;
+    assert!(
+        notices
+            .iter()
+            .any(|n| n["kind"] == "takedown_appeal_reviewed")
+    );
+    Ok(())
+}
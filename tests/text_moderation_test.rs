@@ -0,0 +1,207 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter};
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::config::Config;
+use aircade_api::entities::{content_report, game};
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(
+        db,
+        Config {
+            text_moderation: aircade_api::text_moderation::TextModeration {
+                words: aircade_api::text_moderation::parse_words("scam:60,free robux")
+                    .unwrap_or_default(),
+                ..Default::default()
+            },
+            ..common::test_config()
+        },
+    );
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a game with `status` and `description` owned by `owner_id` and return its ID.
+async fn create_game(
+    state: &AppState,
+    owner_id: Uuid,
+    status: &str,
+    description: &str,
+) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Space Race".to_string()),
+        slug: Set(format!("space-race-{id}")),
+        description: Set(Some(description.to_string())),
+        technology: Set("p5js".to_string()),
+        status: Set(status.to_string()),
+        visibility: Set("public".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        game_screen_code: Set(Some(
+            "function setup() { createCanvas(400, 400); }".to_string(),
+        )),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+/// Open reports against `target_id`.
+async fn open_reports(
+    state: &AppState,
+    target_id: Uuid,
+) -> anyhow::Result<Vec<content_report::Model>> {
+    Ok(content_report::Entity::find()
+        .filter(content_report::Column::TargetId.eq(target_id))
+        .filter(content_report::Column::Status.eq("open"))
+        .all(&state.db)
+        .await?)
+}
+
+fn error_code(body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| v["error"]["code"].as_str().map(ToString::to_string))
+        .unwrap_or_default()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Games
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn publishing_blocks_or_reports_moderated_text() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner_id, token) = common::create_user(&state, "user").await?;
+
+    let blocked = create_game(&state, owner_id, "draft", "Get free r0bux inside!").await?;
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{blocked}/publish"),
+        &json!({}),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+    assert_eq!(error_code(&body), "CONTENT_BLOCKED");
+
+    let borderline = create_game(&state, owner_id, "draft", "A race through space.").await?;
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{borderline}/publish"),
+        &json!({ "changelog": "Fixed the scam level" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let reports = open_reports(&state, borderline).await?;
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].target_type, "game");
+    assert_eq!(reports[0].reporter_id, None);
+    assert!(
+        reports[0]
+            .details
+            .as_deref()
+            .is_some_and(|d| d.contains("changelog"))
+    );
+
+    // Publishing again does not pile up automated reports
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{borderline}/publish"),
+        &json!({ "changelog": "Another scam level" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    assert_eq!(open_reports(&state, borderline).await?.len(), 1);
+
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Reviews and display names
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn reviews_and_display_names_are_screened() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner_id, _) = common::create_user(&state, "user").await?;
+    let (reviewer_id, token) = common::create_user(&state, "user").await?;
+    let game_id = create_game(&state, owner_id, "published", "A race through space.").await?;
+    let uri = format!("/api/v1/games/{game_id}/reviews");
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &uri,
+        &json!({ "rating": 1, "body": "FREE ROBUX!!!" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &uri,
+        &json!({ "rating": 1, "body": "This game is a $cam." }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    let review_id: Uuid = v["id"].as_str().unwrap_or_default().parse()?;
+    let reports = open_reports(&state, review_id).await?;
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].target_type, "review");
+
+    let (status, body) = common::patch_json_with_auth(
+        &app,
+        "/api/v1/users/me",
+        &json!({ "displayName": "Free Robux" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+    assert_eq!(error_code(&body), "CONTENT_BLOCKED");
+
+    let (status, body) = common::patch_json_with_auth(
+        &app,
+        "/api/v1/users/me",
+        &json!({ "displayName": "Scam Hunter" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let reports = open_reports(&state, reviewer_id).await?;
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].target_type, "user");
+
+    Ok(())
+}
@@ -0,0 +1,180 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveModelTrait;
+use sea_orm::ActiveValue::Set;
+use sea_orm::EntityTrait;
+use uuid::Uuid;
+
+use aircade_api::entities::{game, player, session};
+use aircade_api::jobs::trending;
+use aircade_api::state::AppState;
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a published public game and return its ID.
+async fn create_published_game(
+    state: &AppState,
+    owner_id: Uuid,
+    title: &str,
+) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set(title.to_string()),
+        slug: Set(format!("{}-{id}", title.to_lowercase())),
+        technology: Set("p5js".to_string()),
+        status: Set("published".to_string()),
+        visibility: Set("public".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+/// Insert a session that played `game_id`, last active `age` ago, with `players` joined.
+async fn record_play(
+    state: &AppState,
+    host_id: Uuid,
+    game_id: Uuid,
+    age: Duration,
+    players: usize,
+) -> anyhow::Result<()> {
+    let at = (Utc::now() - age).fixed_offset();
+    let session_id = Uuid::new_v4();
+
+    session::ActiveModel {
+        id: Set(session_id),
+        created_at: Set(at),
+        updated_at: Set(at),
+        ended_at: Set(None),
+        host_id: Set(host_id),
+        game_id: Set(Some(game_id)),
+        game_version_id: Set(None),
+        session_code: Set(session_id.to_string()[..6].to_uppercase()),
+        status: Set("playing".to_string()),
+        max_players: Set(8),
+        playing_started_at: Set(None),
+        duration_seconds: Set(None),
+        peak_players: Set(None),
+        messages_relayed: Set(None),
+        disconnects: Set(None),
+    }
+    .insert(&state.db)
+    .await?;
+
+    for i in 0..players {
+        player::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            created_at: Set(at),
+            session_id: Set(session_id),
+            user_id: Set(None),
+            display_name: Set(format!("Player {i}")),
+            avatar_url: Set(None),
+            connection_status: Set("connected".to_string()),
+            left_at: Set(None),
+            first_input_at: Set(None),
+            country: Set(None),
+        }
+        .insert(&state.db)
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn trending_score(state: &AppState, game_id: Uuid) -> anyhow::Result<f64> {
+    Ok(game::Entity::find_by_id(game_id)
+        .one(&state.db)
+        .await?
+        .map(|g| g.trending_score)
+        .unwrap_or_default())
+}
+
+#[tokio::test]
+async fn recompute_favours_recent_busy_sessions() -> anyhow::Result<()> {
+    let (_app, state) = test_app().await;
+    let (owner, _) = common::create_user(&state, "user").await?;
+    let hot = create_published_game(&state, owner, "Hot").await?;
+    let cooling = create_published_game(&state, owner, "Cooling").await?;
+    let expired = create_published_game(&state, owner, "Stale").await?;
+
+    record_play(&state, owner, hot, Duration::hours(1), 4).await?;
+    record_play(&state, owner, cooling, Duration::days(5), 4).await?;
+    record_play(&state, owner, expired, Duration::days(30), 8).await?;
+
+    let updated = trending::recompute(&state.db, Utc::now()).await?;
+    assert_eq!(updated, 2);
+
+    let hot_score = trending_score(&state, hot).await?;
+    let cooling_score = trending_score(&state, cooling).await?;
+    assert!(hot_score > cooling_score, "{hot_score} <= {cooling_score}");
+    assert!(cooling_score > 0.0);
+    assert!(trending_score(&state, expired).await?.abs() < f64::EPSILON);
+    Ok(())
+}
+
+#[tokio::test]
+async fn recompute_resets_games_without_recent_activity() -> anyhow::Result<()> {
+    let (_app, state) = test_app().await;
+    let (owner, _) = common::create_user(&state, "user").await?;
+    let id = create_published_game(&state, owner, "Faded").await?;
+
+    record_play(&state, owner, id, Duration::hours(2), 1).await?;
+    trending::recompute(&state.db, Utc::now()).await?;
+    assert!(trending_score(&state, id).await? > 0.0);
+
+    // Two weeks later the session has dropped out of the window
+    trending::recompute(&state.db, Utc::now() + Duration::days(14)).await?;
+    assert!(trending_score(&state, id).await?.abs() < f64::EPSILON);
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_games_sorted_by_trending() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner, _) = common::create_user(&state, "user").await?;
+    let quiet = create_published_game(&state, owner, "Quiet").await?;
+    let hot = create_published_game(&state, owner, "Hot").await?;
+
+    record_play(&state, owner, quiet, Duration::days(3), 1).await?;
+    record_play(&state, owner, hot, Duration::hours(1), 6).await?;
+    trending::recompute(&state.db, Utc::now()).await?;
+
+    let (status, body) = common::get(&app, "/api/v1/games?sort=trending").await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["data"][0]["id"], hot.to_string());
+    assert_eq!(v["data"][1]["id"], quiet.to_string());
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_games_rejects_unknown_sort() {
+    let (app, _state) = test_app().await;
+
+    let (status, _) = common::get(&app, "/api/v1/games?sort=hottest").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,255 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{EntityTrait, sea_query::Expr};
+use serde_json::json;
+
+use aircade_api::auth::totp;
+
+use aircade_api::entities::user_totp;
+use aircade_api::state::AppState;
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Sign up a user and return (`access_token`, `refresh_token`).
+async fn signup_user(
+    app: &Router,
+    email: &str,
+    username: &str,
+    password: &str,
+) -> (String, String) {
+    let (status, body) = common::post_json(
+        app,
+        "/api/v1/auth/signup/email",
+        &json!({
+            "email": email,
+            "username": username,
+            "password": password,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "signup failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    (
+        json["token"].as_str().unwrap_or_default().to_string(),
+        json["refreshToken"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+    )
+}
+
+/// Forget which TOTP step was used last, so the next code in the same 30s window is accepted.
+async fn allow_code_reuse(state: &AppState) -> anyhow::Result<()> {
+    user_totp::Entity::update_many()
+        .col_expr(
+            user_totp::Column::LastUsedStep,
+            Expr::value(Option::<i64>::None),
+        )
+        .exec(&state.db)
+        .await?;
+    Ok(())
+}
+
+fn current_code(secret: &str) -> String {
+    totp::code(secret, Utc::now().timestamp()).unwrap_or_default()
+}
+
+async fn signin(app: &Router) -> serde_json::Value {
+    let (status, body) = common::post_json(
+        app,
+        "/api/v1/auth/signin/email",
+        &json!({ "email": "twofa@example.com", "password": "Password123" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "signin failed: {body}");
+    serde_json::from_str(&body).unwrap_or_default()
+}
+
+async fn verify(app: &Router, challenge_token: &str, code: &str) -> StatusCode {
+    let (status, _) = common::post_json(
+        app,
+        "/api/v1/auth/2fa/verify",
+        &json!({ "challengeToken": challenge_token, "code": code }),
+    )
+    .await;
+    status
+}
+
+#[tokio::test]
+async fn totp_enrollment_and_two_step_signin() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (token, _refresh) =
+        signup_user(&app, "twofa@example.com", "twofauser", "Password123").await;
+
+    let (status, body) =
+        common::post_json_with_auth(&app, "/api/v1/auth/2fa/totp/setup", &json!({}), &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let setup: serde_json::Value = serde_json::from_str(&body)?;
+    let secret = setup["secret"].as_str().unwrap_or_default().to_string();
+    assert!(
+        setup["provisioningUri"]
+            .as_str()
+            .unwrap_or_default()
+            .starts_with("otpauth://totp/AirCade:")
+    );
+
+    // Pending enrollment does not change sign-in yet
+    assert!(signin(&app).await["token"].is_string());
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/auth/2fa/totp/confirm",
+        &json!({ "code": "000000" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let confirm_code = current_code(&secret);
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/auth/2fa/totp/confirm",
+        &json!({ "code": confirm_code }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    // Password alone now only yields a challenge
+    let challenge = signin(&app).await;
+    assert_eq!(challenge["twoFactorRequired"], true);
+    assert!(challenge["token"].is_null());
+    let challenge_token = challenge["challengeToken"].as_str().unwrap_or_default();
+
+    // The challenge is not an access token
+    let (status, _) = common::get_with_auth(&app, "/api/v1/users/me", challenge_token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // The code used to confirm cannot be replayed
+    let (status, _) = common::post_json(
+        &app,
+        "/api/v1/auth/2fa/verify",
+        &json!({ "challengeToken": challenge_token, "code": confirm_code }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    allow_code_reuse(&state).await?;
+    let (status, body) = common::post_json(
+        &app,
+        "/api/v1/auth/2fa/verify",
+        &json!({ "challengeToken": challenge_token, "code": current_code(&secret) }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let signed_in: serde_json::Value = serde_json::from_str(&body)?;
+    assert!(signed_in["token"].is_string());
+    assert!(signed_in["refreshToken"].is_string());
+
+    // Disabling needs a valid code, after which sign-in is one step again
+    let (status, _) = common::delete_json_with_auth(
+        &app,
+        "/api/v1/auth/2fa/totp",
+        &json!({ "code": "000000" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    allow_code_reuse(&state).await?;
+    let (status, _) = common::delete_json_with_auth(
+        &app,
+        "/api/v1/auth/2fa/totp",
+        &json!({ "code": current_code(&secret) }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(signin(&app).await["token"].is_string());
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_rejects_invalid_challenge() {
+    let (app, _state) = test_app().await;
+
+    let (status, _) = common::post_json(
+        &app,
+        "/api/v1/auth/2fa/verify",
+        &json!({ "challengeToken": "not-a-token", "code": "123456" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn verify_locks_the_challenge_after_repeated_invalid_codes() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (token, _refresh) =
+        signup_user(&app, "twofa@example.com", "twofauser", "Password123").await;
+    let (_, body) =
+        common::post_json_with_auth(&app, "/api/v1/auth/2fa/totp/setup", &json!({}), &token).await;
+    let setup: serde_json::Value = serde_json::from_str(&body)?;
+    let secret = setup["secret"].as_str().unwrap_or_default().to_string();
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/auth/2fa/totp/confirm",
+        &json!({ "code": current_code(&secret) }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    allow_code_reuse(&state).await?;
+
+    let challenge = signin(&app).await;
+    let challenge_token = challenge["challengeToken"].as_str().unwrap_or_default();
+    let wrong_code: String = current_code(&secret)
+        .chars()
+        .map(|c| {
+            if c == '9' {
+                '0'
+            } else {
+                char::from(c as u8 + 1)
+            }
+        })
+        .collect();
+
+    for _ in 0..5 {
+        let status = verify(&app, challenge_token, &wrong_code).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    // Even the right code is refused now, and signing in again does not reset the count
+    let status = verify(&app, challenge_token, &current_code(&secret)).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    let fresh = signin(&app).await;
+    let fresh_token = fresh["challengeToken"].as_str().unwrap_or_default();
+    let status = verify(&app, fresh_token, &current_code(&secret)).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+
+    // Once the attempts are older than a challenge lives, a new challenge works
+    user_totp::Entity::update_many()
+        .col_expr(
+            user_totp::Column::LastFailedAt,
+            Expr::value(Utc::now().fixed_offset() - chrono::Duration::minutes(6)),
+        )
+        .exec(&state.db)
+        .await?;
+    let status = verify(&app, fresh_token, &current_code(&secret)).await;
+    assert_eq!(status, StatusCode::OK);
+    Ok(())
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use migration::{Migrator, MigratorTrait};
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// User Notes
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn moderators_leave_notes_shown_on_user_detail() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (moderator_id, moderator_token) = common::create_user(&state, "moderator").await?;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+    let (user_id, user_token) = common::create_user(&state, "user").await?;
+    let notes_uri = format!("/api/v1/admin/users/{user_id}/notes");
+    let detail_uri = format!("/api/v1/admin/users/{user_id}");
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &notes_uri,
+        &json!({ "body": "Looks fine to me" }),
+        &user_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) =
+        common::post_json_with_auth(&app, &notes_uri, &json!({ "body": "  " }), &moderator_token)
+            .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &notes_uri,
+        &json!({ "body": "Warned in DMs about spam links" }),
+        &moderator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &notes_uri,
+        &json!({ "body": "Second warning" }),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+
+    let (status, _) = common::get_with_auth(&app, &detail_uri, &user_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = common::get_with_auth(&app, &detail_uri, &moderator_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["id"], user_id.to_string());
+    assert_eq!(v["shadowBanned"], false);
+    let notes = v["notes"].as_array().cloned().unwrap_or_default();
+    assert_eq!(notes.len(), 2);
+    assert_eq!(notes[1]["body"], "Warned in DMs about spam links");
+    assert_eq!(notes[1]["authorId"], moderator_id.to_string());
+    assert!(notes[1]["authorUsername"].is_string());
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/users/{}/notes", Uuid::new_v4()),
+        &json!({ "body": "Nobody" }),
+        &moderator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
@@ -5,40 +5,15 @@ use axum::http::StatusCode;
 use migration::{Migrator, MigratorTrait};
 use serde_json::json;
 
-use aircade_api::config::{Config, Environment};
-use aircade_api::sessions::SessionManager;
-use aircade_api::state::AppState;
-
 async fn test_app() -> Router {
     let db = sea_orm::Database::connect("sqlite::memory:")
         .await
         .unwrap_or_default();
     Migrator::up(&db, None).await.unwrap_or_default();
 
-    let state = AppState {
-        db,
-        config: Config {
-            database_url: String::new(),
-            server_host: std::net::IpAddr::from([127, 0, 0, 1]),
-            server_port: 0,
-            environment: Environment::Development,
-            log_level: "warn".to_string(),
-            jwt_secret: "test-secret-key-for-testing-only-32chars".to_string(),
-            jwt_access_expiration_secs: 900,
-            jwt_refresh_expiration_secs: 604_800,
-            google_client_id: String::new(),
-            google_client_secret: String::new(),
-            google_redirect_uri: String::new(),
-            github_client_id: String::new(),
-            github_client_secret: String::new(),
-            github_redirect_uri: String::new(),
-            frontend_url: "http://localhost:3001".to_string(),
-            upload_dir: "test_uploads".to_string(),
-        },
-        session_manager: SessionManager::new(),
-    };
-
-    aircade_api::routes::router().with_state(state)
+    let state = common::test_state(db, common::test_config());
+
+    aircade_api::routes::router(&state.config).with_state(state)
 }
 
 /// Helper: sign up a user and return (`access_token`, `refresh_token`).
@@ -175,6 +150,69 @@ async fn update_me_bio_too_long() {
     assert_eq!(status, StatusCode::BAD_REQUEST);
 }
 
+#[tokio::test]
+async fn update_me_extended_profile_fields() {
+    let app = test_app().await;
+    let (token, _refresh) =
+        signup_user(&app, "extra@example.com", "extrauser", "Password123").await;
+
+    let (status, body) = common::patch_json_with_auth(
+        &app,
+        "/api/v1/users/me",
+        &json!({
+            "location": " Lisbon ",
+            "websiteUrl": "https://extra.example.com",
+            "pronouns": "they/them",
+            "socialLinks": { "github": "https://github.com/extra", "itch-io": "https://extra.itch.io" },
+        }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "update profile failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(json["location"], "Lisbon");
+    assert_eq!(json["socialLinks"]["github"], "https://github.com/extra");
+
+    let (_, body) = common::get(&app, "/api/v1/users/extrauser").await;
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(json["websiteUrl"], "https://extra.example.com");
+    assert_eq!(json["pronouns"], "they/them");
+    assert_eq!(json["socialLinks"]["itch-io"], "https://extra.itch.io");
+
+    // Blank values and an empty map clear the fields
+    let (status, body) = common::patch_json_with_auth(
+        &app,
+        "/api/v1/users/me",
+        &json!({ "location": "", "socialLinks": {} }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert!(json["location"].is_null());
+    assert!(json["socialLinks"].is_null());
+    assert_eq!(json["pronouns"], "they/them");
+}
+
+#[tokio::test]
+async fn update_me_rejects_invalid_profile_links() {
+    let app = test_app().await;
+    let (token, _refresh) =
+        signup_user(&app, "links@example.com", "linksuser", "Password123").await;
+
+    for body in [
+        json!({ "websiteUrl": "javascript:alert(1)" }),
+        json!({ "websiteUrl": "not a url" }),
+        json!({ "socialLinks": { "github": "ftp://github.com/x" } }),
+        json!({ "socialLinks": { "Bad Name": "https://example.com" } }),
+        json!({ "pronouns": "a".repeat(41) }),
+    ] {
+        let (status, _) =
+            common::patch_json_with_auth(&app, "/api/v1/users/me", &body, &token).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    }
+}
+
 // ──────────────────────────────────────────────────────────────────────────────
 // DELETE /api/v1/users/me/avatar
 // ──────────────────────────────────────────────────────────────────────────────
@@ -255,6 +293,28 @@ async fn change_username_invalid() {
     assert_eq!(status, StatusCode::BAD_REQUEST);
 }
 
+#[tokio::test]
+async fn change_username_blocked() {
+    let app = test_app().await;
+    let (token, _refresh) = signup_user(&app, "blk@example.com", "blkuser", "Password123").await;
+
+    for name in ["admin", "4dm1n", "Supp0rt"] {
+        let (status, body) = common::patch_json_with_auth(
+            &app,
+            "/api/v1/users/me/username",
+            &json!({ "newUsername": name }),
+            &token,
+        )
+        .await;
+        assert_eq!(
+            status,
+            StatusCode::BAD_REQUEST,
+            "{name} was accepted: {body}"
+        );
+        assert!(body.contains("not available"));
+    }
+}
+
 // ──────────────────────────────────────────────────────────────────────────────
 // PATCH /api/v1/users/me/email
 // ──────────────────────────────────────────────────────────────────────────────
@@ -274,11 +334,12 @@ async fn change_email_success() {
         &token,
     )
     .await;
-    assert_eq!(status, StatusCode::OK, "email change failed: {body}");
+    assert_eq!(status, StatusCode::ACCEPTED, "email change failed: {body}");
 
+    // Nothing changes until the current address confirms
     let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
-    assert_eq!(json["email"], "new@example.com");
-    assert_eq!(json["emailVerified"], false);
+    assert_eq!(json["email"], "old@example.com");
+    assert_eq!(json["pendingEmail"], "new@example.com");
 }
 
 #[tokio::test]
@@ -374,6 +435,165 @@ async fn deactivate_account_wrong_password() {
     assert_eq!(status, StatusCode::UNAUTHORIZED);
 }
 
+// ──────────────────────────────────────────────────────────────────────────────
+// /api/v1/users/me/sessions
+// ──────────────────────────────────────────────────────────────────────────────
+
+/// Helper: sign in with a `User-Agent` and return the refresh token.
+async fn signin_from(app: &Router, email: &str, password: &str, user_agent: &str) -> String {
+    let (status, _headers, body) = common::post_json_raw(
+        app,
+        "/api/v1/auth/signin/email",
+        &json!({ "email": email, "password": password }),
+        &[
+            ("user-agent", user_agent),
+            ("x-forwarded-for", "203.0.113.7"),
+        ],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "signin failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    json["refreshToken"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string()
+}
+
+async fn list_sessions(app: &Router, token: &str) -> Vec<serde_json::Value> {
+    let (status, body) = common::get_with_auth(app, "/api/v1/users/me/sessions", token).await;
+    assert_eq!(status, StatusCode::OK, "list sessions failed: {body}");
+    serde_json::from_str(&body).unwrap_or_default()
+}
+
+#[tokio::test]
+async fn list_sessions_shows_devices() {
+    let app = test_app().await;
+    let (token, _refresh) =
+        signup_user(&app, "devices@example.com", "devicesuser", "Password123").await;
+    signin_from(&app, "devices@example.com", "Password123", "TestPhone/1.0").await;
+
+    let sessions = list_sessions(&app, &token).await;
+    assert_eq!(sessions.len(), 2);
+    let phone = sessions
+        .iter()
+        .find(|s| s["userAgent"] == "TestPhone/1.0")
+        .cloned()
+        .unwrap_or_default();
+    assert_eq!(phone["ipAddress"], "203.0.113.7");
+    assert!(phone["signedInAt"].is_string());
+    assert!(phone["expiresAt"].is_string());
+}
+
+#[tokio::test]
+async fn refresh_keeps_session_sign_in_time() {
+    let app = test_app().await;
+    let (token, refresh) =
+        signup_user(&app, "rotate@example.com", "rotateuser", "Password123").await;
+    let before = list_sessions(&app, &token).await;
+
+    let (status, _body) = common::post_json(
+        &app,
+        "/api/v1/auth/refresh",
+        &json!({ "refreshToken": refresh }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // The rotated token replaces the old one as the same device
+    let after = list_sessions(&app, &token).await;
+    assert_eq!(after.len(), 1);
+    assert_ne!(after[0]["id"], before[0]["id"]);
+    assert_eq!(after[0]["signedInAt"], before[0]["signedInAt"]);
+}
+
+#[tokio::test]
+async fn revoke_session_signs_device_out() {
+    let app = test_app().await;
+    let (token, _refresh) =
+        signup_user(&app, "revoke@example.com", "revokeuser", "Password123").await;
+    let phone_refresh =
+        signin_from(&app, "revoke@example.com", "Password123", "TestPhone/1.0").await;
+
+    let sessions = list_sessions(&app, &token).await;
+    let phone = sessions
+        .iter()
+        .find(|s| s["userAgent"] == "TestPhone/1.0")
+        .and_then(|s| s["id"].as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let (status, _body) =
+        common::delete_with_auth(&app, &format!("/api/v1/users/me/sessions/{phone}"), &token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(list_sessions(&app, &token).await.len(), 1);
+
+    // The revoked device can no longer refresh
+    let (status, _body) = common::post_json(
+        &app,
+        "/api/v1/auth/refresh",
+        &json!({ "refreshToken": phone_refresh }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Revoking it again (or an unknown id) is a 404
+    let (status, _body) =
+        common::delete_with_auth(&app, &format!("/api/v1/users/me/sessions/{phone}"), &token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn revoke_session_of_other_user_not_found() {
+    let app = test_app().await;
+    let (owner_token, _refresh) =
+        signup_user(&app, "owner@example.com", "owneruser", "Password123").await;
+    let (other_token, _refresh) =
+        signup_user(&app, "other@example.com", "otheruser", "Password123").await;
+
+    let sessions = list_sessions(&app, &owner_token).await;
+    let id = sessions[0]["id"].as_str().unwrap_or_default();
+
+    let (status, _body) = common::delete_with_auth(
+        &app,
+        &format!("/api/v1/users/me/sessions/{id}"),
+        &other_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(list_sessions(&app, &owner_token).await.len(), 1);
+}
+
+#[tokio::test]
+async fn sign_out_everywhere() {
+    let app = test_app().await;
+    let (token, refresh) = signup_user(
+        &app,
+        "everywhere@example.com",
+        "everywhereuser",
+        "Password123",
+    )
+    .await;
+    signin_from(
+        &app,
+        "everywhere@example.com",
+        "Password123",
+        "TestPhone/1.0",
+    )
+    .await;
+
+    let (status, _body) = common::delete_with_auth(&app, "/api/v1/users/me/sessions", &token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(list_sessions(&app, &token).await.is_empty());
+
+    let (status, _body) = common::post_json(
+        &app,
+        "/api/v1/auth/refresh",
+        &json!({ "refreshToken": refresh }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
 // ──────────────────────────────────────────────────────────────────────────────
 // GET /api/v1/users/{username} (public profile)
 // ──────────────────────────────────────────────────────────────────────────────
@@ -390,6 +610,10 @@ async fn public_profile_success() {
     assert_eq!(json["username"], "pubuser");
     assert!(json["stats"]["gamesPublished"].is_number());
     assert!(json["stats"]["totalPlayCount"].is_number());
+    assert_eq!(json["stats"]["followers"], 0);
+    assert!(json["stats"]["avgRating"].is_null());
+    // No upload, so a generated avatar stands in
+    assert_eq!(json["avatarUrl"], "/api/v1/avatars/generate?seed=pubuser");
     // Private fields should NOT be present
     assert!(json["email"].is_null());
     assert!(json["role"].is_null());
@@ -422,3 +646,83 @@ async fn public_profile_deactivated_hidden() {
     let (status, _body) = common::get(&app, "/api/v1/users/hiddenuser").await;
     assert_eq!(status, StatusCode::NOT_FOUND);
 }
+
+// ──────────────────────────────────────────────────────────────────────────────
+// GET/PUT /api/v1/users/me/preferences
+// ──────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn preferences_round_trip() {
+    let app = test_app().await;
+    let (token, _refresh) =
+        signup_user(&app, "prefs@example.com", "prefsuser", "Password123").await;
+
+    let (status, body) = common::get_with_auth(&app, "/api/v1/users/me/preferences", &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body, "{}");
+
+    let prefs = json!({ "theme": "dark", "editor": { "fontSize": 14 }, "controllerHaptics": true });
+    let (status, body) =
+        common::put_json_with_auth(&app, "/api/v1/users/me/preferences", &prefs, &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let (_, body) = common::get_with_auth(&app, "/api/v1/users/me/preferences", &token).await;
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(json, prefs);
+
+    // A later PUT replaces the whole document
+    let (status, _) = common::put_json_with_auth(
+        &app,
+        "/api/v1/users/me/preferences",
+        &json!({ "theme": "light" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = common::get_with_auth(&app, "/api/v1/users/me/preferences", &token).await;
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    assert_eq!(json, json!({ "theme": "light" }));
+}
+
+#[tokio::test]
+async fn preferences_reject_unknown_keys() {
+    let app = test_app().await;
+    let (token, _refresh) =
+        signup_user(&app, "badprefs@example.com", "badprefsuser", "Password123").await;
+
+    for prefs in [
+        json!({ "sidebar": "left" }),
+        json!({ "controllerHaptics": "yes" }),
+        json!(["dark"]),
+    ] {
+        let (status, body) =
+            common::put_json_with_auth(&app, "/api/v1/users/me/preferences", &prefs, &token).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    }
+}
+
+// ──────────────────────────────────────────────────────────────────────────────
+// GET /api/v1/avatars/generate
+// ──────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn generated_avatar_is_deterministic_svg() {
+    let app = test_app().await;
+
+    let (status, first) = common::get(&app, "/api/v1/avatars/generate?seed=Ada%20Lovelace").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(first.starts_with("<svg"));
+    assert!(first.contains(">AL</text>"));
+    let (_, second) = common::get(&app, "/api/v1/avatars/generate?seed=Ada%20Lovelace").await;
+    assert_eq!(first, second);
+
+    let (status, body) =
+        common::get(&app, "/api/v1/avatars/generate?seed=ada&style=identicon").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("<rect"));
+
+    let (status, _) = common::get(&app, "/api/v1/avatars/generate?seed=%20").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = common::get(&app, "/api/v1/avatars/generate?seed=ada&style=cubes").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,211 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveModelTrait;
+use sea_orm::ActiveValue::Set;
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::game;
+use aircade_api::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a published public game owned by `owner_id` and return its ID.
+async fn create_published_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Verified Game".to_string()),
+        slug: Set(format!("verified-game-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("published".to_string()),
+        visibility: Set("public".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+const JUSTIFICATION: &str = "I run a studio that has shipped several party games.";
+
+/// Apply for verification and return the request ID.
+async fn apply(app: &Router, token: &str) -> anyhow::Result<String> {
+    let (status, body) = common::post_json_with_auth(
+        app,
+        "/api/v1/users/me/verification-request",
+        &json!({ "justification": JUSTIFICATION }),
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["status"], "pending");
+    Ok(v["id"].as_str().unwrap_or_default().to_string())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/v1/users/me/verification-request
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn applying_validates_and_rejects_duplicates() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, token) = common::create_user(&state, "user").await?;
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/users/me/verification-request",
+        &json!({ "justification": "Please" }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    apply(&app, &token).await?;
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/users/me/verification-request",
+        &json!({ "justification": JUSTIFICATION }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, body) =
+        common::get_with_auth(&app, "/api/v1/users/me/verification-request", &token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["justification"], JUSTIFICATION);
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// /api/v1/admin/verification-requests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn approval_shows_badge_on_profile_and_games() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (creator_id, creator_token) = common::create_user(&state, "user").await?;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+    let game_id = create_published_game(&state, creator_id).await?;
+    let id = apply(&app, &creator_token).await?;
+
+    // Only admins review requests
+    let (status, _) =
+        common::get_with_auth(&app, "/api/v1/admin/verification-requests", &creator_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) =
+        common::get_with_auth(&app, "/api/v1/admin/verification-requests", &admin_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let queue: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(queue["total"], 1);
+    assert_eq!(queue["data"][0]["id"], id.as_str());
+    assert_eq!(queue["data"][0]["user"]["id"], creator_id.to_string());
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/verification-requests/{id}/approve"),
+        &json!({}),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    // A decided request cannot be decided again
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/verification-requests/{id}/deny"),
+        &json!({}),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let username = format!("user_{}", &creator_id.to_string()[..8]);
+    let (_, body) = common::get(&app, &format!("/api/v1/users/{username}")).await;
+    let profile: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(profile["verified"], true);
+
+    let (_, body) = common::get(&app, &format!("/api/v1/games/{game_id}")).await;
+    let game: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(game["creator"]["verified"], true);
+
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/users/me/verification-request",
+        &json!({ "justification": JUSTIFICATION }),
+        &creator_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    Ok(())
+}
+
+#[tokio::test]
+async fn denial_notifies_with_note_and_allows_reapplying() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (_, token) = common::create_user(&state, "user").await?;
+    let (_, admin_token) = common::create_user(&state, "admin").await?;
+    let id = apply(&app, &token).await?;
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/admin/verification-requests/{id}/deny"),
+        &json!({ "note": "Publish a few more games first." }),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(v["status"], "denied");
+
+    let (_, body) = common::get_with_auth(&app, "/api/v1/users/me", &token).await;
+    let me: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(me["verified"], false);
+
+    let (_, body) = common::get_with_auth(&app, "/api/v1/users/me/notifications", &token).await;
+    let notices: Vec<serde_json::Value> = serde_json::from_str(&body)?;
+    let notice = notices
+        .iter()
+        .find(|n| n["kind"] == "verification_reviewed")
+        .cloned()
+        .unwrap_or_default();
+    assert!(
+        notice["body"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("Publish a few more games first.")
+    );
+
+    apply(&app, &token).await?;
+    Ok(())
+}
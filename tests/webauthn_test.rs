@@ -0,0 +1,361 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use data_encoding::BASE64URL_NOPAD;
+use migration::{Migrator, MigratorTrait};
+use ring::rand::SystemRandom;
+use ring::signature::{ECDSA_P256_SHA256_ASN1_SIGNING, EcdsaKeyPair, KeyPair};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use aircade_api::state::AppState;
+
+/// Origin of the frontend in [`test_app`]'s config.
+const ORIGIN: &str = "http://localhost:3001";
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Sign up a user and return (`access_token`, `refresh_token`).
+async fn signup_user(
+    app: &Router,
+    email: &str,
+    username: &str,
+    password: &str,
+) -> (String, String) {
+    let (status, body) = common::post_json(
+        app,
+        "/api/v1/auth/signup/email",
+        &json!({
+            "email": email,
+            "username": username,
+            "password": password,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "signup failed: {body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    (
+        json["token"].as_str().unwrap_or_default().to_string(),
+        json["refreshToken"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+    )
+}
+
+/// A software passkey authenticator for the `localhost` relying party.
+struct Authenticator {
+    key: EcdsaKeyPair,
+    credential_id: Vec<u8>,
+    counter: u32,
+    rng: SystemRandom,
+}
+
+impl Authenticator {
+    fn new() -> anyhow::Result<Self> {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng)?;
+        let key = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref(), &rng)?;
+        Ok(Self {
+            key,
+            credential_id: vec![7; 16],
+            counter: 0,
+            rng,
+        })
+    }
+
+    fn credential_id(&self) -> String {
+        BASE64URL_NOPAD.encode(&self.credential_id)
+    }
+
+    /// Authenticator data with the user present and verified flags set.
+    fn auth_data(&self, attested: bool) -> Vec<u8> {
+        let mut data = Sha256::digest(b"localhost").to_vec();
+        data.push(if attested { 0x45 } else { 0x05 });
+        data.extend_from_slice(&self.counter.to_be_bytes());
+        if attested {
+            data.extend_from_slice(&[0; 16]);
+            data.extend_from_slice(
+                &u16::try_from(self.credential_id.len())
+                    .unwrap_or(0)
+                    .to_be_bytes(),
+            );
+            data.extend_from_slice(&self.credential_id);
+            // COSE EC2 key: {1: 2, 3: -7, -1: 1, -2: x, -3: y}
+            let point = self.key.public_key().as_ref();
+            data.extend_from_slice(&[0xa5, 0x01, 0x02, 0x03, 0x26, 0x20, 0x01, 0x21, 0x58, 0x20]);
+            data.extend_from_slice(point.get(1..33).unwrap_or_default());
+            data.extend_from_slice(&[0x22, 0x58, 0x20]);
+            data.extend_from_slice(point.get(33..65).unwrap_or_default());
+        }
+        data
+    }
+
+    /// Response to `navigator.credentials.create()`, with `none` attestation.
+    fn create(&self, challenge: &str, origin: &str) -> serde_json::Value {
+        let auth_data = self.auth_data(true);
+        let mut attestation = vec![0xa3, 0x63];
+        attestation.extend_from_slice(b"fmt");
+        attestation.push(0x64);
+        attestation.extend_from_slice(b"none");
+        attestation.push(0x67);
+        attestation.extend_from_slice(b"attStmt");
+        attestation.extend_from_slice(&[0xa0, 0x68]);
+        attestation.extend_from_slice(b"authData");
+        attestation.push(0x59);
+        attestation.extend_from_slice(&u16::try_from(auth_data.len()).unwrap_or(0).to_be_bytes());
+        attestation.extend_from_slice(&auth_data);
+
+        json!({
+            "id": self.credential_id(),
+            "type": "public-key",
+            "response": {
+                "clientDataJSON": client_data("webauthn.create", challenge, origin),
+                "attestationObject": BASE64URL_NOPAD.encode(&attestation),
+            },
+        })
+    }
+
+    /// Response to `navigator.credentials.get()`, bumping the signature counter.
+    fn get(&mut self, challenge: &str) -> anyhow::Result<serde_json::Value> {
+        self.counter += 1;
+        let auth_data = self.auth_data(false);
+        let client_data_json = client_data("webauthn.get", challenge, ORIGIN);
+
+        let mut signed = auth_data.clone();
+        signed.extend_from_slice(&Sha256::digest(
+            BASE64URL_NOPAD.decode(client_data_json.as_bytes())?,
+        ));
+        let signature = self.key.sign(&self.rng, &signed)?;
+
+        Ok(json!({
+            "id": self.credential_id(),
+            "type": "public-key",
+            "response": {
+                "clientDataJSON": client_data_json,
+                "authenticatorData": BASE64URL_NOPAD.encode(&auth_data),
+                "signature": BASE64URL_NOPAD.encode(signature.as_ref()),
+            },
+        }))
+    }
+}
+
+fn client_data(kind: &str, challenge: &str, origin: &str) -> String {
+    let json = json!({ "type": kind, "challenge": challenge, "origin": origin }).to_string();
+    BASE64URL_NOPAD.encode(json.as_bytes())
+}
+
+/// Start a ceremony and return (`challengeId`, `challenge`).
+async fn options(app: &Router, uri: &str, token: Option<&str>) -> (String, String) {
+    let (status, body) = match token {
+        Some(token) => common::post_json_with_auth(app, uri, &json!({}), token).await,
+        None => common::post_json(app, uri, &json!({})).await,
+    };
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    (
+        json["challengeId"].as_str().unwrap_or_default().to_string(),
+        json["publicKey"]["challenge"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+    )
+}
+
+async fn register(app: &Router, token: &str, authenticator: &Authenticator) -> serde_json::Value {
+    let (challenge_id, challenge) =
+        options(app, "/api/v1/auth/webauthn/register/options", Some(token)).await;
+    let (status, body) = common::post_json_with_auth(
+        app,
+        "/api/v1/auth/webauthn/register/verify",
+        &json!({
+            "challengeId": challenge_id,
+            "name": "Laptop",
+            "credential": authenticator.create(&challenge, ORIGIN),
+        }),
+        token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    serde_json::from_str(&body).unwrap_or_default()
+}
+
+#[tokio::test]
+async fn passkey_registration_and_passwordless_signin() -> anyhow::Result<()> {
+    let (app, _state) = test_app().await;
+    let (token, _) = signup_user(&app, "passkey@example.com", "passkeyuser", "Password123").await;
+    let mut authenticator = Authenticator::new()?;
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/auth/webauthn/register/options",
+        &json!({}),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let creation: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(creation["publicKey"]["rp"]["id"], "localhost");
+    assert_eq!(creation["publicKey"]["pubKeyCredParams"][0]["alg"], -7);
+
+    let credential = register(&app, &token, &authenticator).await;
+    assert_eq!(credential["name"], "Laptop");
+
+    // The registered passkey is excluded from further registrations
+    let (_, body) = common::post_json_with_auth(
+        &app,
+        "/api/v1/auth/webauthn/register/options",
+        &json!({}),
+        &token,
+    )
+    .await;
+    let creation: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(
+        creation["publicKey"]["excludeCredentials"][0]["id"],
+        authenticator.credential_id()
+    );
+
+    let (challenge_id, challenge) =
+        options(&app, "/api/v1/auth/webauthn/authenticate/options", None).await;
+    let assertion = authenticator.get(&challenge)?;
+    let (status, body) = common::post_json(
+        &app,
+        "/api/v1/auth/webauthn/authenticate/verify",
+        &json!({ "challengeId": challenge_id, "credential": assertion }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let signed_in: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(signed_in["user"]["email"], "passkey@example.com");
+    assert!(signed_in["token"].is_string());
+
+    // Challenges are single use
+    let (status, _) = common::post_json(
+        &app,
+        "/api/v1/auth/webauthn/authenticate/verify",
+        &json!({ "challengeId": challenge_id, "credential": assertion }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // A replayed assertion (stale counter, wrong challenge) is rejected
+    let (challenge_id, _) = options(&app, "/api/v1/auth/webauthn/authenticate/options", None).await;
+    let (status, _) = common::post_json(
+        &app,
+        "/api/v1/auth/webauthn/authenticate/verify",
+        &json!({ "challengeId": challenge_id, "credential": assertion }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, body) =
+        common::get_with_auth(&app, "/api/v1/auth/webauthn/credentials", &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let list: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(list.as_array().map(Vec::len), Some(1));
+    assert!(list[0]["lastUsedAt"].is_string());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn passkey_ceremonies_reject_bad_responses() -> anyhow::Result<()> {
+    let (app, _state) = test_app().await;
+    let (token, _) = signup_user(&app, "badkey@example.com", "badkeyuser", "Password123").await;
+    let mut authenticator = Authenticator::new()?;
+
+    let (status, _) =
+        common::post_json(&app, "/api/v1/auth/webauthn/register/options", &json!({})).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Wrong origin
+    let (challenge_id, challenge) =
+        options(&app, "/api/v1/auth/webauthn/register/options", Some(&token)).await;
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/auth/webauthn/register/verify",
+        &json!({
+            "challengeId": challenge_id,
+            "credential": authenticator.create(&challenge, "https://evil.example"),
+        }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Wrong challenge
+    let (challenge_id, _) =
+        options(&app, "/api/v1/auth/webauthn/register/options", Some(&token)).await;
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/auth/webauthn/register/verify",
+        &json!({
+            "challengeId": challenge_id,
+            "credential": authenticator.create("c29tZXRoaW5nIGVsc2U", ORIGIN),
+        }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let credential = register(&app, &token, &authenticator).await;
+
+    // Registering the same passkey twice
+    let (challenge_id, challenge) =
+        options(&app, "/api/v1/auth/webauthn/register/options", Some(&token)).await;
+    let (status, _) = common::post_json_with_auth(
+        &app,
+        "/api/v1/auth/webauthn/register/verify",
+        &json!({
+            "challengeId": challenge_id,
+            "credential": authenticator.create(&challenge, ORIGIN),
+        }),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // A signature from another key
+    let (challenge_id, challenge) =
+        options(&app, "/api/v1/auth/webauthn/authenticate/options", None).await;
+    let mut impostor = Authenticator::new()?;
+    let (status, _) = common::post_json(
+        &app,
+        "/api/v1/auth/webauthn/authenticate/verify",
+        &json!({ "challengeId": challenge_id, "credential": impostor.get(&challenge)? }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Removed passkeys can no longer sign in
+    let id = credential["id"].as_str().unwrap_or_default();
+    let (status, _) = common::delete_with_auth(
+        &app,
+        &format!("/api/v1/auth/webauthn/credentials/{id}"),
+        &token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (challenge_id, challenge) =
+        options(&app, "/api/v1/auth/webauthn/authenticate/options", None).await;
+    let (status, _) = common::post_json(
+        &app,
+        "/api/v1/auth/webauthn/authenticate/verify",
+        &json!({ "challengeId": challenge_id, "credential": authenticator.get(&challenge)? }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
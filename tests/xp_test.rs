@@ -0,0 +1,201 @@
+mod common;
+
+use axum::Router;
+use axum::http::StatusCode;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::ActiveModelTrait;
+use sea_orm::ActiveValue::Set;
+use serde_json::json;
+use uuid::Uuid;
+
+use aircade_api::entities::game;
+use aircade_api::state::AppState;
+use aircade_api::xp;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test Infrastructure
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn test_app() -> (Router, AppState) {
+    let db = sea_orm::Database::connect("sqlite::memory:")
+        .await
+        .unwrap_or_default();
+    Migrator::up(&db, None).await.unwrap_or_default();
+
+    let state = common::test_state(db, common::test_config());
+
+    let router = aircade_api::routes::router(&state.config).with_state(state.clone());
+    (router, state)
+}
+
+/// Insert a private draft game owned by `owner_id` and return its ID.
+async fn create_private_game(state: &AppState, owner_id: Uuid) -> anyhow::Result<Uuid> {
+    let now = Utc::now().fixed_offset();
+    let id = Uuid::new_v4();
+
+    game::ActiveModel {
+        id: Set(id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        owner_id: Set(owner_id),
+        title: Set("Team Project".to_string()),
+        slug: Set(format!("team-project-{id}")),
+        technology: Set("p5js".to_string()),
+        status: Set("draft".to_string()),
+        visibility: Set("private".to_string()),
+        min_players: Set(1),
+        max_players: Set(4),
+        game_screen_code: Set(Some(
+            "function setup() { createCanvas(400, 400); }".to_string(),
+        )),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(id)
+}
+
+/// Make `game_id` public so other users can review it.
+async fn make_public(state: &AppState, game_id: Uuid) -> anyhow::Result<()> {
+    game::ActiveModel {
+        id: Set(game_id),
+        visibility: Set("public".to_string()),
+        ..Default::default()
+    }
+    .update(&state.db)
+    .await?;
+    Ok(())
+}
+
+async fn my_xp(app: &Router, token: &str) -> serde_json::Value {
+    let (status, body) = common::get_with_auth(app, "/api/v1/users/me/xp", token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    serde_json::from_str(&body).unwrap_or_default()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Levels
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn levels_follow_thresholds() {
+    assert_eq!(xp::level_for(0), 1);
+    assert_eq!(xp::level_for(99), 1);
+    assert_eq!(xp::level_for(100), 2);
+    assert_eq!(xp::level_for(10_000), 10);
+    assert_eq!(xp::level_for(1_000_000), 10);
+    assert_eq!(xp::level_xp(2), Some(100));
+    assert_eq!(xp::level_xp(11), None);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Earning XP
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn publishing_and_reviews_earn_creator_xp() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (owner_id, owner_token) = common::create_user(&state, "user").await?;
+    let (_, reviewer_token) = common::create_user(&state, "user").await?;
+    let game_id = create_private_game(&state, owner_id).await?;
+    make_public(&state, game_id).await?;
+
+    // Publishing the same game again earns nothing more
+    for _ in 0..2 {
+        let (status, body) = common::post_json_with_auth(
+            &app,
+            &format!("/api/v1/games/{game_id}/publish"),
+            &json!({}),
+            &owner_token,
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED, "{body}");
+    }
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/games/{game_id}/reviews"),
+        &json!({ "rating": 5 }),
+        &reviewer_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+
+    let xp = my_xp(&app, &owner_token).await;
+    assert_eq!(xp["xp"], 65);
+    assert_eq!(xp["level"], 1);
+    assert_eq!(xp["currentLevelXp"], 0);
+    assert_eq!(xp["nextLevelXp"], 100);
+    assert_eq!(
+        xp["breakdown"],
+        json!({ "hosting": 0, "playing": 0, "publishing": 50, "reviews": 15 })
+    );
+    assert_eq!(my_xp(&app, &reviewer_token).await["xp"], 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn session_players_show_levels_and_earn_play_xp() -> anyhow::Result<()> {
+    let (app, state) = test_app().await;
+    let (host_id, host_token) = common::create_user(&state, "user").await?;
+    let (player_id, player_token) = common::create_user(&state, "user").await?;
+
+    let (status, body) =
+        common::post_json_with_auth(&app, "/api/v1/sessions", &json!({}), &host_token).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let session: serde_json::Value = serde_json::from_str(&body)?;
+    let session_id: Uuid = session["id"].as_str().unwrap_or_default().parse()?;
+    let code = session["sessionCode"].as_str().unwrap_or_default();
+
+    let (status, body) = common::post_json_with_auth(
+        &app,
+        &format!("/api/v1/sessions/{code}/join"),
+        &json!({ "displayName": "Signed In" }),
+        &player_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let joined: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(joined["player"]["level"], 1);
+
+    let (status, body) = common::post_json(
+        &app,
+        &format!("/api/v1/sessions/{code}/join"),
+        &json!({ "displayName": "Guest" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let joined: serde_json::Value = serde_json::from_str(&body)?;
+    assert!(joined["player"]["level"].is_null());
+
+    // Each play pays out once
+    let play_id = Uuid::new_v4();
+    for _ in 0..2 {
+        xp::play_started(&state.db, host_id, session_id, play_id).await;
+    }
+    assert_eq!(my_xp(&app, &host_token).await["breakdown"]["hosting"], 20);
+    assert_eq!(my_xp(&app, &player_token).await["breakdown"]["playing"], 10);
+
+    xp::grant(&state.db, player_id, xp::Source::Publishing, Uuid::new_v4()).await?;
+    xp::grant(&state.db, player_id, xp::Source::Publishing, Uuid::new_v4()).await?;
+    let (_, body) = common::get(&app, &format!("/api/v1/sessions/{code}")).await;
+    let session: serde_json::Value = serde_json::from_str(&body)?;
+    let level_of = |name: &str| {
+        session["players"]
+            .as_array()
+            .and_then(|all| all.iter().find(|p| p["displayName"] == name))
+            .map(|p| p["level"].clone())
+    };
+    assert_eq!(level_of("Signed In"), Some(json!(2)));
+    assert_eq!(level_of("Guest"), Some(serde_json::Value::Null));
+
+    let (_, body) = common::get(
+        &app,
+        &format!("/api/v1/users/user_{}", &player_id.to_string()[..8]),
+    )
+    .await;
+    let profile: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(profile["level"], 2);
+    Ok(())
+}